@@ -0,0 +1,261 @@
+// ─── Interface CLI ───
+// Headless front end for servers, automation and CI smoke tests. Drives the
+// same `Launcher` as the desktop app, against the same data dir and
+// settings, and prints progress to stdout. Do not run it against a data dir
+// the desktop app (or another CLI) is using at the same time.
+//
+// Exit codes: 0 on success, 1 when the operation fails, 2 on bad usage.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use interface_lib::{
+    configured_locale, default_data_dir, ensure_writable_dir, CliText, EventEmitter, Launcher,
+    LauncherError, LoaderType, Locale, NewInstance,
+};
+use tracing_subscriber::EnvFilter;
+
+const RUNNING_POLL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+enum Command {
+    Help,
+    List,
+    Create(NewInstance),
+    Launch { id: String },
+    JavaInstall { minecraft_version: String },
+}
+
+/// Bad usage, printed once the locale of the data dir is known.
+#[derive(Debug)]
+enum UsageError {
+    Text(CliText, String),
+    Launcher(LauncherError),
+}
+
+impl UsageError {
+    fn render(&self, locale: Locale) -> String {
+        match self {
+            UsageError::Text(text, arg) => text.format(locale, std::slice::from_ref(arg)),
+            UsageError::Launcher(err) => err.localized(locale),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Cli {
+    data_dir: Option<PathBuf>,
+    command: Result<Command, UsageError>,
+}
+
+/// `--data-dir` is kept even when the rest fails, so usage errors print in
+/// the language of that data dir.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Cli {
+    let mut data_dir = None;
+    let command = parse_command(args, &mut data_dir);
+    Cli { data_dir, command }
+}
+
+fn parse_command(
+    args: impl IntoIterator<Item = String>,
+    data_dir: &mut Option<PathBuf>,
+) -> Result<Command, UsageError> {
+    let mut positional = Vec::new();
+    let mut loader = LoaderType::Vanilla;
+    let mut loader_version = None;
+    let mut memory_max_mb = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| UsageError::Text(CliText::MissingValue, flag.to_string()))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => positional.insert(0, "help".to_string()),
+            "--data-dir" => *data_dir = Some(PathBuf::from(value("--data-dir")?)),
+            "--loader" => loader = value("--loader")?.parse().map_err(UsageError::Launcher)?,
+            "--loader-version" => loader_version = Some(value("--loader-version")?),
+            "--memory" => {
+                let raw = value("--memory")?;
+                memory_max_mb = Some(
+                    raw.parse::<u32>()
+                        .map_err(|_| UsageError::Text(CliText::InvalidMemory, raw))?,
+                );
+            }
+            flag if flag.starts_with("--") => {
+                return Err(UsageError::Text(CliText::UnknownOption, flag.to_string()))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match positional.as_slice() {
+        [] | ["help", ..] => Command::Help,
+        ["list"] => Command::List,
+        ["create", name, minecraft_version] => Command::Create(NewInstance {
+            name: name.to_string(),
+            minecraft_version: minecraft_version.to_string(),
+            loader,
+            loader_version,
+            memory_max_mb,
+        }),
+        ["launch", id] => Command::Launch { id: id.to_string() },
+        ["java", "install", minecraft_version] => Command::JavaInstall {
+            minecraft_version: minecraft_version.to_string(),
+        },
+        [command, ..] => {
+            return Err(UsageError::Text(
+                CliText::InvalidArguments,
+                command.to_string(),
+            ))
+        }
+    };
+    Ok(command)
+}
+
+/// Prints progress and log events as plain lines.
+struct StdoutEmitter {
+    locale: Locale,
+}
+
+impl EventEmitter for StdoutEmitter {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        let text = |key: &str| {
+            payload
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+        };
+        match event {
+            "instance-create-progress" | "instance-launch-progress" => {
+                let value = payload.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
+                println!("[{value:>3}%] {}", text("stage"));
+            }
+            "instance-create-log" | "instance-launch-log" => println!("{}", text("message")),
+            "java-install-progress" => {
+                let phase = match text("phase") {
+                    "download" => CliText::JavaPhaseDownload.template(self.locale),
+                    "extract" => CliText::JavaPhaseExtract.template(self.locale),
+                    "validate" => CliText::JavaPhaseValidate.template(self.locale),
+                    other => other,
+                }
+                .to_string();
+                let downloaded = payload
+                    .get("downloaded_bytes")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                match payload.get("total_bytes").and_then(|v| v.as_u64()) {
+                    Some(total) if total > 0 => {
+                        let percent = downloaded.saturating_mul(100) / total;
+                        println!(
+                            "{}",
+                            CliText::JavaPhaseProgress
+                                .format(self.locale, &[phase, percent.to_string()])
+                        );
+                    }
+                    _ => println!("{}", CliText::JavaPhase.format(self.locale, &[phase])),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn run(launcher: &Launcher, locale: Locale, command: Command) -> Result<(), LauncherError> {
+    match command {
+        Command::Help => println!("{}", CliText::Usage.template(locale)),
+        Command::List => {
+            let instances = launcher.list_instances().await?;
+            if instances.is_empty() {
+                eprintln!("{}", CliText::NoInstances.template(locale));
+            }
+            for instance in instances {
+                let loader = match &instance.loader_version {
+                    Some(version) => format!("{} {version}", instance.loader),
+                    None => instance.loader.to_string(),
+                };
+                println!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    instance.id,
+                    instance.name,
+                    instance.minecraft_version,
+                    loader,
+                    format!("{:?}", instance.state).to_lowercase()
+                );
+            }
+        }
+        Command::Create(request) => {
+            let instance = launcher.create_instance(request).await?;
+            let instance = launcher.install_instance(&instance.id).await?;
+            println!(
+                "{}",
+                CliText::InstanceReady.format(locale, &[instance.name, instance.id])
+            );
+        }
+        Command::Launch { id } => {
+            launcher.launch_instance(&id).await?;
+            println!("{}", CliText::GameStarted.template(locale));
+            while launcher.is_running(&id).await {
+                tokio::time::sleep(RUNNING_POLL).await;
+            }
+            println!("{}", CliText::GameClosed.template(locale));
+        }
+        Command::JavaInstall { minecraft_version } => {
+            let java_path = launcher.install_java(&minecraft_version).await?;
+            println!(
+                "{}",
+                CliText::JavaReady.format(locale, &[java_path.display().to_string()])
+            );
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // Logs go to stderr so stdout only carries progress and results.
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .init();
+
+    let cli = parse_args(std::env::args().skip(1));
+    let data_dir = cli.data_dir.unwrap_or_else(default_data_dir);
+    let locale = configured_locale(&data_dir);
+    let command = match cli.command {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!(
+                "{}\n\n{}",
+                err.render(locale),
+                CliText::Usage.template(locale)
+            );
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Err(err) = ensure_writable_dir(&data_dir) {
+        eprintln!(
+            "{}",
+            CliText::DataDirNotWritable.format(locale, &[err.localized(locale)])
+        );
+        return ExitCode::FAILURE;
+    }
+    let launcher = Launcher::open(data_dir, Arc::new(StdoutEmitter { locale })).await;
+    match run(&launcher, locale, command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!(
+                "{}",
+                CliText::Failed.format(locale, &[err.localized(locale)])
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,20 +1,32 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use futures_util::stream::{self, StreamExt};
 use serde::Deserialize;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 use crate::core::downloader::{DownloadEntry, Downloader};
 use crate::core::error::{LauncherError, LauncherResult};
 use crate::core::http::build_http_client;
+use crate::core::mirrors;
+use crate::core::net::{self, RetryPolicy};
+use crate::core::version::compare::release_before;
 
 /// Manages Minecraft asset downloads (sounds, textures referenced by asset index).
 pub struct AssetManager;
 
 /// Top-level asset index JSON structure.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct AssetIndex {
     pub objects: HashMap<String, AssetObject>,
+    /// 1.6 / early 1.7 (`legacy` index): the game reads real file names
+    /// from `assets/virtual/<index>/`.
+    #[serde(default, rename = "virtual")]
+    pub is_virtual: bool,
+    /// Pre-1.6 (`pre-1.6` index): files are expected under `<game_dir>/resources/`.
+    #[serde(default)]
+    pub map_to_resources: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,24 +36,69 @@ pub struct AssetObject {
 }
 
 const RESOURCES_URL: &str = "https://resources.download.minecraft.net";
+/// Public BMCLAPI mirror of the resources host, tried for objects the
+/// primary host (and the configured mirror, if any) could not serve.
+/// Objects are still SHA-1 verified.
+const SECONDARY_RESOURCES_URL: &str = "https://bmclapi2.bangbang93.com/assets";
+
+impl AssetIndex {
+    /// Every object of the index as a download into `objects_dir`.
+    pub fn object_downloads(&self, objects_dir: &Path) -> Vec<DownloadEntry> {
+        self.objects
+            .values()
+            .map(|obj| DownloadEntry {
+                url: format!("{}/{}/{}", RESOURCES_URL, &obj.hash[..2], obj.hash),
+                dest: objects_dir.join(&obj.hash[..2]).join(&obj.hash),
+                sha1: Some(obj.hash.clone()),
+                size: Some(obj.size),
+            })
+            .collect()
+    }
+}
+
+/// Outcome of syncing the object store against an asset index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssetSyncReport {
+    /// Objects already on disk with the expected SHA-1.
+    pub verified: usize,
+    /// Objects that were missing or corrupt and fetched again.
+    pub downloaded: usize,
+    /// Downloaded objects that only a fallback host could serve.
+    pub recovered: usize,
+}
 
 impl AssetManager {
     /// Download the asset index JSON and all referenced assets.
+    ///
+    /// When `cancel` fires the batch is dropped and `LauncherError::Cancelled`
+    /// is returned; partially written objects are resumed on the next run.
     pub async fn download_assets(
         index_url: &str,
         assets_dir: &Path,
         downloader: &Downloader,
-    ) -> LauncherResult<()> {
+        cancel: Option<&CancellationToken>,
+    ) -> LauncherResult<AssetSyncReport> {
+        let is_cancelled = || cancel.is_some_and(CancellationToken::is_cancelled);
+        if is_cancelled() {
+            return Err(LauncherError::Cancelled);
+        }
+
         // 1. Download asset index JSON
         let client = build_http_client()?;
-        let index_resp = client.get(index_url).send().await?;
-        if !index_resp.status().is_success() {
-            return Err(LauncherError::DownloadFailed {
-                url: index_url.to_string(),
-                status: index_resp.status().as_u16(),
-            });
-        }
-        let index_text = index_resp.text().await?;
+        let index_text = mirrors::with_mirror_fallback(&downloader.mirrors(), index_url, |url| {
+            let client = &client;
+            async move {
+                let index_resp = net::get_with_retry(client, &url).await?;
+                if !index_resp.status().is_success() {
+                    return Err(LauncherError::DownloadFailed {
+                        url,
+                        status: index_resp.status().as_u16(),
+                    });
+                }
+                Ok(index_resp.text().await?)
+            }
+        })
+        .await?;
         let index: AssetIndex = serde_json::from_str(&index_text)?;
 
         // Save index file
@@ -63,39 +120,514 @@ impl AssetManager {
                 source: e,
             })?;
 
-        // 2. Build download entries for all asset objects
+        // 2. Fetch only the objects that are missing or fail verification
         let objects_dir = assets_dir.join("objects");
-        let mut entries = Vec::new();
+        let report = Self::sync_objects(
+            &index,
+            &objects_dir,
+            &[RESOURCES_URL, SECONDARY_RESOURCES_URL],
+            RetryPolicy::default(),
+            downloader,
+            cancel,
+        )
+        .await?;
 
-        for (_name, obj) in &index.objects {
-            let hash_prefix = &obj.hash[..2];
-            let dest = objects_dir.join(hash_prefix).join(&obj.hash);
+        // 3. Old versions read assets by their original names
+        let index_id = index_name.trim_end_matches(".json");
+        if let Some(target) = legacy_layout_dir(&index, assets_dir, index_id) {
+            let copied = tokio::task::spawn_blocking(move || {
+                materialize_legacy_layout(&index, &objects_dir, &target)
+            })
+            .await
+            .map_err(|e| LauncherError::Other(format!("Legacy asset task failed: {e}")))??;
+            info!(
+                "Materialized {} legacy assets for index {}",
+                copied, index_id
+            );
+        }
 
-            if dest.exists() {
-                continue; // Already downloaded
-            }
+        Ok(report)
+    }
 
-            let url = format!("{}/{}/{}", RESOURCES_URL, hash_prefix, obj.hash);
-            entries.push(DownloadEntry {
-                url,
-                dest,
-                sha1: Some(obj.hash.clone()),
-                size: Some(obj.size),
-            });
+    /// Verify every object of `index` already in `objects_dir` against its
+    /// SHA-1 (in parallel, bounded by the downloader's concurrency) and
+    /// download the missing or mismatched ones from the first of `base_urls`.
+    /// Objects that fail (e.g. a CDN 404) are retried as `policy` says,
+    /// going through the hosts in turn; the sync fails with their hashes
+    /// once the retries run out.
+    async fn sync_objects(
+        index: &AssetIndex,
+        objects_dir: &Path,
+        base_urls: &[&str],
+        policy: RetryPolicy,
+        downloader: &Downloader,
+        cancel: Option<&CancellationToken>,
+    ) -> LauncherResult<AssetSyncReport> {
+        let is_cancelled = || cancel.is_some_and(CancellationToken::is_cancelled);
+
+        let targets: Vec<(String, u64, PathBuf)> = index
+            .objects
+            .values()
+            .map(|obj| {
+                let dest = objects_dir.join(&obj.hash[..2]).join(&obj.hash);
+                (obj.hash.clone(), obj.size, dest)
+            })
+            .collect();
+        let checks = stream::iter(targets)
+            .map(|(hash, size, dest)| async move {
+                let valid = dest.exists()
+                    && Downloader::validate_sha1(&dest, &hash)
+                        .await
+                        .unwrap_or(false);
+                (hash, size, dest, valid)
+            })
+            .buffer_unordered(downloader.max_concurrency());
+        let checks: Vec<_> = match cancel {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => return Err(LauncherError::Cancelled),
+                checks = checks.collect() => checks,
+            },
+            None => checks.collect().await,
+        };
+
+        let mut report = AssetSyncReport::default();
+        let mut pending = Vec::new();
+        for (hash, size, dest, valid) in checks {
+            if valid {
+                report.verified += 1;
+            } else {
+                pending.push((hash, size, dest));
+            }
+        }
+        if is_cancelled() {
+            return Err(LauncherError::Cancelled);
         }
 
         info!(
-            "Downloading {} asset objects ({} already cached)",
-            entries.len(),
-            index.objects.len() - entries.len()
+            "Downloading {} asset objects ({} verified on disk)",
+            pending.len(),
+            report.verified
+        );
+
+        // 3. Download batch, retrying the failures on the next host
+        let requested = pending.len();
+        let attempts = base_urls.iter().cycle().take(policy.retries as usize + 1);
+        for (attempt, base_url) in (0u32..).zip(attempts) {
+            if pending.is_empty() {
+                break;
+            }
+            if attempt > 0 {
+                let delay = policy.backoff(attempt - 1);
+                warn!(
+                    "{} asset objects failed; retrying from {} in {:?} (attempt {}/{})",
+                    pending.len(),
+                    base_url,
+                    delay,
+                    attempt,
+                    policy.retries
+                );
+                match cancel {
+                    Some(token) => tokio::select! {
+                        biased;
+                        _ = token.cancelled() => return Err(LauncherError::Cancelled),
+                        _ = tokio::time::sleep(delay) => {}
+                    },
+                    None => tokio::time::sleep(delay).await,
+                }
+            }
+            let entries = pending
+                .iter()
+                .map(|(hash, size, dest)| DownloadEntry {
+                    url: format!("{}/{}/{}", base_url, &hash[..2], hash),
+                    dest: dest.clone(),
+                    sha1: Some(hash.clone()),
+                    size: Some(*size),
+                })
+                .collect();
+            let failures = match cancel {
+                Some(token) => tokio::select! {
+                    biased;
+                    _ = token.cancelled() => return Err(LauncherError::Cancelled),
+                    failures = downloader.download_batch(entries) => failures,
+                },
+                None => downloader.download_batch(entries).await,
+            };
+            if attempt > 0 {
+                report.recovered += pending.len() - failures.len();
+            }
+            pending = failures
+                .into_iter()
+                .map(|(entry, _)| {
+                    let hash = entry.sha1.unwrap_or_default();
+                    (hash, entry.size.unwrap_or_default(), entry.dest)
+                })
+                .collect();
+        }
+
+        if !pending.is_empty() {
+            let mut hashes: Vec<String> = pending.into_iter().map(|(hash, _, _)| hash).collect();
+            hashes.sort();
+            return Err(LauncherError::AssetsUnavailable { hashes });
+        }
+        report.downloaded = requested;
+
+        Ok(report)
+    }
+}
+
+/// Directory the game expects as `${game_assets}` for `index`, or `None`
+/// for the modern hashed layout. Assets live in `<game_dir>/assets`, so
+/// `map_to_resources` resolves to the sibling `resources/` directory.
+fn legacy_layout_dir(index: &AssetIndex, assets_dir: &Path, index_id: &str) -> Option<PathBuf> {
+    if index.map_to_resources {
+        Some(assets_dir.parent().unwrap_or(assets_dir).join("resources"))
+    } else if index.is_virtual {
+        Some(assets_dir.join("virtual").join(index_id))
+    } else {
+        None
+    }
+}
+
+/// Whether `minecraft_version` predates per-version asset indexes (1.7.2 and
+/// older, including alpha/beta ids), so the shared `legacy` index is a valid
+/// fallback. Ids that cannot be parsed are treated as legacy.
+pub fn uses_legacy_asset_index(minecraft_version: &str) -> bool {
+    let lower = minecraft_version.trim().to_ascii_lowercase();
+    if let Some((year, rest)) = lower.split_once('w') {
+        if year.len() == 2 && year.bytes().all(|b| b.is_ascii_digit()) {
+            let week = rest.get(..2).and_then(|week| week.parse::<u32>().ok());
+            // 13w43a (1.7.3 cycle) was the first snapshot with its own index.
+            return (year.parse::<u32>().unwrap_or(0), week.unwrap_or(0)) < (13, 43);
+        }
+    }
+
+    release_before(&lower, &[1, 7, 3]).unwrap_or(true)
+}
+
+/// `${game_assets}` for a launch: the legacy tree when the saved index
+/// `assets/indexes/<index_id>.json` asks for one, `assets_dir` otherwise.
+pub fn game_assets_dir(assets_dir: &Path, index_id: &str) -> PathBuf {
+    std::fs::read(assets_dir.join("indexes").join(format!("{index_id}.json")))
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<AssetIndex>(&raw).ok())
+        .and_then(|index| legacy_layout_dir(&index, assets_dir, index_id))
+        .unwrap_or_else(|| assets_dir.to_path_buf())
+}
+
+/// Copy every hashed object to `target/<original name>`, skipping files that
+/// are already there with the right size. Returns how many were written.
+fn materialize_legacy_layout(
+    index: &AssetIndex,
+    objects_dir: &Path,
+    target: &Path,
+) -> LauncherResult<usize> {
+    let mut copied = 0;
+    for (name, obj) in &index.objects {
+        let relative = Path::new(name);
+        if relative
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            tracing::warn!("Skipping legacy asset with unsafe name {}", name);
+            continue;
+        }
+
+        let dest = target.join(relative);
+        if std::fs::metadata(&dest).is_ok_and(|meta| meta.len() == obj.size) {
+            continue;
+        }
+        let source = objects_dir.join(&obj.hash[..2]).join(&obj.hash);
+        if !source.exists() {
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        std::fs::copy(&source, &dest).map_err(|e| LauncherError::Io {
+            path: dest.clone(),
+            source: e,
+        })?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One retry on the next host, without waiting.
+    const NO_DELAY: RetryPolicy = RetryPolicy {
+        retries: 1,
+        base_delay: std::time::Duration::ZERO,
+    };
+
+    #[test]
+    fn legacy_asset_index_applies_up_to_1_7_2() {
+        for legacy in [
+            "1.6.4",
+            "1.7",
+            "1.7.2",
+            "b1.7.3",
+            "a1.0.4",
+            "rd-132211",
+            "13w41b",
+        ] {
+            assert!(uses_legacy_asset_index(legacy), "{legacy}");
+        }
+        for modern in [
+            "1.7.10",
+            "1.12.2",
+            "1.20",
+            "1.20.1-pre2",
+            "23w13a",
+            "1.21.4",
+        ] {
+            assert!(!uses_legacy_asset_index(modern), "{modern}");
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_token_stops_before_any_request() {
+        let temp = std::env::temp_dir().join(format!("assets-cancel-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = AssetManager::download_assets(
+            "http://127.0.0.1:9/indexes/17.json",
+            &temp,
+            &Downloader::new(None),
+            Some(&token),
+        )
+        .await;
+
+        assert!(matches!(result, Err(LauncherError::Cancelled)));
+        assert!(!temp.join("indexes").exists());
+    }
+
+    fn sha1_hex(bytes: &[u8]) -> String {
+        use sha1::{Digest, Sha1};
+        hex::encode(Sha1::digest(bytes))
+    }
+
+    /// Serves `/<prefix>/<hash>` from `objects` (404 for unknown hashes) and
+    /// records requested paths.
+    async fn serve_objects(
+        objects: HashMap<String, Vec<u8>>,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use crate::core::test_support::{response, serve};
+
+        let requested = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = requested.clone();
+        let base = serve(move |request| {
+            let hash = request.path.rsplit('/').next().unwrap_or("").to_string();
+            log.lock().unwrap().push(hash.clone());
+            let reply = match objects.get(&hash) {
+                Some(body) => response("200 OK", &[], body),
+                None => response("404 Not Found", &[], b""),
+            };
+            async move { reply }
+        })
+        .await;
+        (base, requested)
+    }
+
+    #[tokio::test]
+    async fn only_missing_or_corrupt_objects_are_fetched() {
+        let temp = std::env::temp_dir().join(format!("assets-diff-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let objects_dir = temp.join("objects");
+
+        let mut served = HashMap::new();
+        let mut index = AssetIndex::default();
+        for i in 0..10 {
+            let body = format!("asset-{i}").into_bytes();
+            let hash = sha1_hex(&body);
+            index.objects.insert(
+                format!("minecraft/sounds/{i}.ogg"),
+                AssetObject {
+                    hash: hash.clone(),
+                    size: body.len() as u64,
+                },
+            );
+            // Objects 0 and 1 are missing, object 2 is corrupt.
+            if i >= 2 {
+                let dest = objects_dir.join(&hash[..2]).join(&hash);
+                std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+                let on_disk = if i == 2 {
+                    b"garbage".to_vec()
+                } else {
+                    body.clone()
+                };
+                std::fs::write(dest, on_disk).unwrap();
+            }
+            served.insert(hash, body);
+        }
+        let expected: std::collections::HashSet<String> = (0..3)
+            .map(|i| sha1_hex(format!("asset-{i}").as_bytes()))
+            .collect();
+
+        let (base, requested) = serve_objects(served).await;
+        let report = AssetManager::sync_objects(
+            &index,
+            &objects_dir,
+            &[&base],
+            NO_DELAY,
+            &Downloader::new(None),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            report,
+            AssetSyncReport {
+                verified: 7,
+                downloaded: 3,
+                recovered: 0
+            }
         );
+        let fetched: std::collections::HashSet<String> =
+            requested.lock().unwrap().iter().cloned().collect();
+        assert_eq!(fetched, expected);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn objects_missing_on_the_primary_host_come_from_the_fallback() {
+        let temp = std::env::temp_dir().join(format!("assets-fallback-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let objects_dir = temp.join("objects");
+
+        let mut served = HashMap::new();
+        let mut index = AssetIndex::default();
+        for i in 0..3 {
+            let body = format!("fallback-{i}").into_bytes();
+            let hash = sha1_hex(&body);
+            index.objects.insert(
+                format!("minecraft/lang/{i}.json"),
+                AssetObject {
+                    hash: hash.clone(),
+                    size: body.len() as u64,
+                },
+            );
+            served.insert(hash, body);
+        }
+        // The primary host 404s every object.
+        let (primary, primary_requests) = serve_objects(HashMap::new()).await;
+        let (fallback, _) = serve_objects(served).await;
+
+        let report = AssetManager::sync_objects(
+            &index,
+            &objects_dir,
+            &[&primary, &fallback],
+            NO_DELAY,
+            &Downloader::new(None),
+            None,
+        )
+        .await
+        .unwrap();
 
-        // 3. Download batch
-        let failures = downloader.download_batch(entries).await;
-        if !failures.is_empty() {
-            tracing::warn!("{} asset downloads failed", failures.len());
+        assert_eq!(
+            report,
+            AssetSyncReport {
+                verified: 0,
+                downloaded: 3,
+                recovered: 3
+            }
+        );
+        assert_eq!(primary_requests.lock().unwrap().len(), 3);
+        for obj in index.objects.values() {
+            assert!(objects_dir.join(&obj.hash[..2]).join(&obj.hash).exists());
         }
 
-        Ok(())
+        // With every host failing, the error names the missing objects once
+        // the retries went through both hosts twice.
+        let _ = std::fs::remove_dir_all(&objects_dir);
+        let (other, other_requests) = serve_objects(HashMap::new()).await;
+        let err = AssetManager::sync_objects(
+            &index,
+            &objects_dir,
+            &[&primary, &other],
+            RetryPolicy {
+                retries: 3,
+                ..NO_DELAY
+            },
+            &Downloader::new(None),
+            None,
+        )
+        .await
+        .unwrap_err();
+        let mut expected: Vec<String> = index.objects.values().map(|o| o.hash.clone()).collect();
+        expected.sort();
+        assert!(matches!(err, LauncherError::AssetsUnavailable { hashes } if hashes == expected));
+        assert_eq!(primary_requests.lock().unwrap().len(), 3 + 6);
+        assert_eq!(other_requests.lock().unwrap().len(), 6);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn virtual_index_builds_tree_with_original_names() {
+        let temp = std::env::temp_dir().join(format!("assets-legacy-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let assets_dir = temp.join("minecraft").join("assets");
+        let objects_dir = assets_dir.join("objects");
+
+        let raw_index = {
+            let mut objects = serde_json::Map::new();
+            for name in ["sound/step/grass1.ogg", "lang/en_US.lang"] {
+                let hash = sha1_hex(name.as_bytes());
+                let dest = objects_dir.join(&hash[..2]).join(&hash);
+                std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+                std::fs::write(dest, name).unwrap();
+                objects.insert(
+                    name.into(),
+                    serde_json::json!({ "hash": hash, "size": name.len() }),
+                );
+            }
+            objects.insert(
+                "../escape.txt".into(),
+                serde_json::json!({ "hash": sha1_hex(b"x"), "size": 1 }),
+            );
+            serde_json::json!({ "virtual": true, "objects": objects }).to_string()
+        };
+        let indexes_dir = assets_dir.join("indexes");
+        std::fs::create_dir_all(&indexes_dir).unwrap();
+        std::fs::write(indexes_dir.join("legacy.json"), &raw_index).unwrap();
+
+        let index: AssetIndex = serde_json::from_str(&raw_index).unwrap();
+        assert!(index.is_virtual && !index.map_to_resources);
+
+        let target = game_assets_dir(&assets_dir, "legacy");
+        assert_eq!(target, assets_dir.join("virtual").join("legacy"));
+        assert_eq!(
+            materialize_legacy_layout(&index, &objects_dir, &target).unwrap(),
+            2
+        );
+        assert_eq!(
+            std::fs::read_to_string(target.join("sound/step/grass1.ogg")).unwrap(),
+            "sound/step/grass1.ogg"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target.join("lang/en_US.lang")).unwrap(),
+            "lang/en_US.lang"
+        );
+        assert!(!target.parent().unwrap().join("escape.txt").exists());
+        // Second run finds everything in place.
+        assert_eq!(
+            materialize_legacy_layout(&index, &objects_dir, &target).unwrap(),
+            0
+        );
+        // Modern indexes keep the hashed layout.
+        assert_eq!(game_assets_dir(&assets_dir, "17"), assets_dir);
+
+        let _ = std::fs::remove_dir_all(&temp);
     }
 }
@@ -1,6 +1,11 @@
 pub mod asset_index;
+pub mod shared_store;
 
+pub use asset_index::game_assets_dir;
+pub use asset_index::uses_legacy_asset_index;
 #[allow(unused_imports)]
 pub use asset_index::AssetIndex;
 #[allow(unused_imports)]
 pub use asset_index::AssetManager;
+pub use asset_index::AssetSyncReport;
+pub use shared_store::ShareReport;
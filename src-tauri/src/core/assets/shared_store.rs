@@ -0,0 +1,420 @@
+// ─── Shared Asset Store ───
+// Asset objects are content-addressed (`objects/<xx>/<sha1>`), so every
+// instance can point at one copy under `<data_dir>/assets`. Files are
+// hardlinked into the instance; where the filesystem refuses links (FAT,
+// another volume) they are copied instead. An object only enters the store,
+// or replaces an instance copy, when its SHA-1 matches its name.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sha1::Sha1;
+use tracing::warn;
+
+use crate::core::downloader::resume::hash_file;
+use crate::core::error::{LauncherError, LauncherResult};
+
+use super::AssetIndex;
+
+/// How a file ended up in its destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Linked,
+    Copied,
+    AlreadyShared,
+}
+
+/// Outcome of linking an instance against the shared store.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShareReport {
+    /// Files now hardlinked to the store.
+    pub linked: usize,
+    /// Files that had to be copied because links are unsupported.
+    pub copied: usize,
+    /// Files that already pointed at the store.
+    pub already_shared: usize,
+    /// Bytes of duplicate copies replaced by links.
+    pub freed_bytes: u64,
+    /// Files left alone because their content did not match.
+    pub skipped: usize,
+}
+
+impl ShareReport {
+    fn record(&mut self, placement: Placement) {
+        match placement {
+            Placement::Linked => self.linked += 1,
+            Placement::Copied => self.copied += 1,
+            Placement::AlreadyShared => self.already_shared += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &ShareReport) {
+        self.linked += other.linked;
+        self.copied += other.copied;
+        self.already_shared += other.already_shared;
+        self.freed_bytes += other.freed_bytes;
+        self.skipped += other.skipped;
+    }
+}
+
+type LinkFn = fn(&Path, &Path) -> io::Result<()>;
+
+fn io_err(path: &Path) -> impl FnOnce(io::Error) -> LauncherError + '_ {
+    move |source| LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Whether both paths are the same file on disk.
+///
+/// Std exposes no stable file ID on Windows, so there this is always false:
+/// an already shared file is placed again (an atomic rename of an identical
+/// link, so harmless) and dedupe reports its size as freed once more.
+fn same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (std::fs::metadata(a), std::fs::metadata(b)) {
+            (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (a, b);
+        false
+    }
+}
+
+/// Whether the object at `path` hashes to its file name.
+fn object_is_intact(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    hash_file::<Sha1>(path).is_ok_and(|hash| hash.eq_ignore_ascii_case(name))
+}
+
+fn same_contents(a: &Path, b: &Path) -> bool {
+    match (std::fs::read(a), std::fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Make `dst` a link to `src`, or a copy when linking fails. The new file is
+/// staged next to `dst` and renamed over it, so an existing `dst` is never
+/// left half-written.
+fn place_file(src: &Path, dst: &Path, link: LinkFn) -> io::Result<Placement> {
+    if dst.exists() && same_file(src, dst) {
+        return Ok(Placement::AlreadyShared);
+    }
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let staged = dst.with_extension("share-tmp");
+    let _ = std::fs::remove_file(&staged);
+    let placement = match link(src, &staged) {
+        Ok(()) => Placement::Linked,
+        Err(_) => {
+            std::fs::copy(src, &staged)?;
+            Placement::Copied
+        }
+    };
+    if let Err(err) = std::fs::rename(&staged, dst) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(err);
+    }
+    Ok(placement)
+}
+
+/// Link the index `index_id` and every object it lists from `store` into
+/// `assets_dir`. Objects the instance already has are left alone.
+pub fn link_index_into(
+    store: &Path,
+    assets_dir: &Path,
+    index_id: &str,
+) -> LauncherResult<ShareReport> {
+    link_index_into_with(store, assets_dir, index_id, |src, dst| {
+        std::fs::hard_link(src, dst)
+    })
+}
+
+fn link_index_into_with(
+    store: &Path,
+    assets_dir: &Path,
+    index_id: &str,
+    link: LinkFn,
+) -> LauncherResult<ShareReport> {
+    let index_rel = PathBuf::from("indexes").join(format!("{index_id}.json"));
+    let store_index = store.join(&index_rel);
+    let raw = std::fs::read(&store_index).map_err(io_err(&store_index))?;
+    let index: AssetIndex = serde_json::from_slice(&raw)?;
+
+    let mut report = ShareReport::default();
+    let instance_index = assets_dir.join(&index_rel);
+    report
+        .record(place_file(&store_index, &instance_index, link).map_err(io_err(&instance_index))?);
+
+    for object in index.objects.values() {
+        let Some(prefix) = object.hash.get(..2) else {
+            continue;
+        };
+        let rel = PathBuf::from("objects").join(prefix).join(&object.hash);
+        let dst = assets_dir.join(&rel);
+        if dst.exists() {
+            report.record(Placement::AlreadyShared);
+            continue;
+        }
+        let src = store.join(&rel);
+        if !src.is_file() {
+            continue;
+        }
+        report.record(place_file(&src, &dst, link).map_err(io_err(&dst))?);
+    }
+    Ok(report)
+}
+
+/// Replace the objects in `assets_dir` by links into `store`. Objects the
+/// store lacks are moved into it first (as a link, so nothing is copied).
+/// Objects whose hash does not match their name never enter the store; a
+/// corrupt store entry is replaced by an intact instance copy. Indexes are
+/// only shared when both copies are identical.
+pub fn dedupe_into_store(store: &Path, assets_dir: &Path) -> LauncherResult<ShareReport> {
+    dedupe_into_store_with(store, assets_dir, |src, dst| std::fs::hard_link(src, dst))
+}
+
+fn dedupe_into_store_with(
+    store: &Path,
+    assets_dir: &Path,
+    link: LinkFn,
+) -> LauncherResult<ShareReport> {
+    let mut report = ShareReport::default();
+    for dir in ["objects", "indexes"] {
+        let root = assets_dir.join(dir);
+        let mut stack = vec![root.clone()];
+        while let Some(current) = stack.pop() {
+            let Ok(entries) = std::fs::read_dir(&current) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                if !file_type.is_file() {
+                    continue;
+                }
+                let Ok(rel) = path.strip_prefix(assets_dir) else {
+                    continue;
+                };
+                let shared = store.join(rel);
+                if shared.exists() && same_file(&path, &shared) {
+                    report.record(Placement::AlreadyShared);
+                    continue;
+                }
+                let intact = |file: &Path| dir != "objects" || object_is_intact(file);
+
+                if !shared.exists() {
+                    if !intact(&path) {
+                        warn!("Not sharing corrupt asset {}", path.display());
+                        report.skipped += 1;
+                        continue;
+                    }
+                    // The store adopts this copy; the instance keeps using it.
+                    place_file(&path, &shared, link).map_err(io_err(&shared))?;
+                    report.record(Placement::AlreadyShared);
+                    continue;
+                }
+
+                if dir == "indexes" && !same_contents(&path, &shared) {
+                    warn!("Asset index {} differs from the store", path.display());
+                    report.skipped += 1;
+                    continue;
+                }
+                if !intact(&shared) {
+                    if intact(&path) {
+                        warn!("Replacing corrupt store object {}", shared.display());
+                        place_file(&path, &shared, link).map_err(io_err(&shared))?;
+                        report.record(Placement::AlreadyShared);
+                    } else {
+                        warn!("Not sharing corrupt asset {}", path.display());
+                        report.skipped += 1;
+                    }
+                    continue;
+                }
+
+                let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                let placement = place_file(&shared, &path, link).map_err(io_err(&path))?;
+                if placement == Placement::Linked {
+                    report.freed_bytes += size;
+                }
+                report.record(placement);
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::temp_dir;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn failing_link(_: &Path, _: &Path) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "no links here"))
+    }
+
+    const INDEX: &str = r#"{"objects":{"icon.png":{"hash":"ab12","size":5}}}"#;
+
+    #[test]
+    fn index_objects_are_linked_from_the_store() {
+        let root = temp_dir("shared-assets-link");
+        let store = root.join("store");
+        let instance = root.join("instance");
+        write(&store.join("indexes/17.json"), INDEX);
+        write(&store.join("objects/ab/ab12"), "asset");
+
+        let report = link_index_into(&store, &instance, "17").unwrap();
+        assert_eq!(report.linked + report.copied, 2);
+        assert_eq!(
+            std::fs::read_to_string(instance.join("objects/ab/ab12")).unwrap(),
+            "asset"
+        );
+        #[cfg(unix)]
+        {
+            assert_eq!(report.linked, 2);
+            assert!(same_file(
+                &store.join("objects/ab/ab12"),
+                &instance.join("objects/ab/ab12")
+            ));
+        }
+
+        // A second pass leaves everything in place.
+        let again = link_index_into(&store, &instance, "17").unwrap();
+        assert_eq!(again.linked + again.copied, 0);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unsupported_links_fall_back_to_copies() {
+        let root = temp_dir("shared-assets-copy");
+        let store = root.join("store");
+        let instance = root.join("instance");
+        write(&store.join("indexes/17.json"), INDEX);
+        write(&store.join("objects/ab/ab12"), "asset");
+
+        let report = link_index_into_with(&store, &instance, "17", failing_link).unwrap();
+        assert_eq!((report.linked, report.copied), (0, 2));
+        assert!(!same_file(
+            &store.join("objects/ab/ab12"),
+            &instance.join("objects/ab/ab12")
+        ));
+        assert_eq!(
+            std::fs::read_to_string(instance.join("objects/ab/ab12")).unwrap(),
+            "asset"
+        );
+        assert!(!instance.join("objects/ab/ab12.share-tmp").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    /// Where an object with `contents` lives under `root`.
+    fn object_path(root: &Path, contents: &str) -> PathBuf {
+        let hash = hex::encode(<Sha1 as sha1::Digest>::digest(contents.as_bytes()));
+        root.join("objects").join(&hash[..2]).join(&hash)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dedupe_turns_duplicate_copies_into_links() {
+        let root = temp_dir("shared-assets-dedupe");
+        let store = root.join("store");
+        let first = root.join("first");
+        let second = root.join("second");
+        write(&object_path(&first, "asset"), "asset");
+        write(&object_path(&second, "asset"), "asset");
+        write(&object_path(&second, "other"), "other");
+
+        let report = dedupe_into_store(&store, &first).unwrap();
+        assert_eq!((report.linked, report.freed_bytes), (0, 0));
+        let report = dedupe_into_store(&store, &second).unwrap();
+        assert_eq!((report.linked, report.freed_bytes), (1, 5));
+
+        let shared = object_path(&store, "asset");
+        assert!(same_file(&shared, &object_path(&first, "asset")));
+        assert!(same_file(&shared, &object_path(&second, "asset")));
+        assert!(same_file(
+            &object_path(&store, "other"),
+            &object_path(&second, "other")
+        ));
+
+        // Without link support the instance copy is kept and nothing is freed.
+        let third = root.join("third");
+        write(&object_path(&third, "asset"), "asset");
+        let report = dedupe_into_store_with(&store, &third, failing_link).unwrap();
+        assert_eq!((report.copied, report.freed_bytes), (1, 0));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dedupe_never_shares_objects_that_do_not_match_their_hash() {
+        let root = temp_dir("shared-assets-corrupt");
+        let store = root.join("store");
+        let first = root.join("first");
+        let second = root.join("second");
+        let name = object_path(Path::new(""), "asset");
+
+        // A corrupt instance copy is not adopted by the store.
+        write(&first.join(&name), "assex");
+        let report = dedupe_into_store(&store, &first).unwrap();
+        assert_eq!(report.skipped, 1);
+        assert!(!store.join(&name).exists());
+
+        // A corrupt store entry is replaced by an intact copy...
+        write(&store.join(&name), "assex");
+        write(&second.join(&name), "asset");
+        let report = dedupe_into_store(&store, &second).unwrap();
+        assert_eq!((report.skipped, report.freed_bytes), (0, 0));
+        assert!(same_file(&store.join(&name), &second.join(&name)));
+
+        // ...which then fixes the corrupt copy of the first instance.
+        let report = dedupe_into_store(&store, &first).unwrap();
+        assert_eq!(report.linked, 1);
+        assert_eq!(std::fs::read_to_string(first.join(&name)).unwrap(), "asset");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn differing_indexes_are_not_replaced() {
+        let root = temp_dir("shared-assets-index");
+        let store = root.join("store");
+        let instance = root.join("instance");
+        write(&store.join("indexes/17.json"), INDEX);
+        write(&instance.join("indexes/17.json"), "{}");
+
+        let report = dedupe_into_store(&store, &instance).unwrap();
+        assert_eq!(report.skipped, 1);
+        assert_eq!(
+            std::fs::read_to_string(instance.join("indexes/17.json")).unwrap(),
+            "{}"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,151 @@
+// ─── Atomic File Writes ───
+// Persisted JSON (instance.json, launcher settings, runtime index) is written
+// to a temp file in the target's directory, flushed to disk and renamed over
+// the target. The rename is atomic on the same filesystem, so a crash or
+// power loss leaves either the old file or the new one, never a truncated
+// mix of both. A file that exists but no longer parses is moved aside
+// instead of being overwritten by the next save.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::io::AsyncWriteExt;
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Unique sibling of `path` for one write, so concurrent saves of the same
+/// file never share a temp file.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let unique = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}-{}.tmp", name, std::process::id(), unique))
+}
+
+/// Replace `path` with `contents` atomically.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let temp = temp_path(path);
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        std::fs::rename(&temp, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Rename an unreadable `path` to `<name>.<timestamp>.bak` next to it, so
+/// the next save starts fresh without destroying what was there.
+pub fn set_aside(path: &Path) -> std::io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let backup = path.with_file_name(format!("{name}.{stamp}.bak"));
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Async variant of [`write`] for the tokio pipelines.
+pub async fn write_async(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let temp = temp_path(path);
+    let result = async {
+        let mut file = tokio::fs::File::create(&temp).await?;
+        file.write_all(contents.as_ref()).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temp, path).await
+    }
+    .await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::temp_dir;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    fn payload(fill: u8) -> Vec<u8> {
+        // Large enough that a plain `fs::write` is observable half-done.
+        let mut body = vec![fill; 256 * 1024];
+        body[0] = b'[';
+        *body.last_mut().unwrap() = b']';
+        body
+    }
+
+    #[test]
+    fn readers_never_observe_a_half_written_file() {
+        let dir = temp_dir("atomic-write-readers");
+        let target = dir.join("instance.json");
+        write(&target, payload(b'a')).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let done = Arc::clone(&done);
+            let target = target.clone();
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::SeqCst) {
+                    let body = std::fs::read(&target).expect("target always exists");
+                    assert_eq!(body.len(), 256 * 1024, "observed a truncated file");
+                    assert!(body[1..body.len() - 1].iter().all(|b| *b == body[1]));
+                    reads += 1;
+                }
+                reads
+            })
+        };
+
+        for round in 0..50 {
+            write(&target, payload(if round % 2 == 0 { b'b' } else { b'a' })).unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+        assert!(reader.join().unwrap() > 0);
+
+        // Every temp file was renamed away.
+        let leftovers = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(leftovers, 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn an_interrupted_write_leaves_the_previous_file_intact() {
+        let dir = temp_dir("atomic-write-interrupted");
+        let target = dir.join("launcher_settings.json");
+        write(&target, br#"{"schema_version":1}"#).unwrap();
+
+        // A crash mid-write only ever truncates the temp sibling.
+        std::fs::write(temp_path(&target), br#"{"schema_vers"#).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            r#"{"schema_version":1}"#
+        );
+
+        // A failed write (missing directory) does not leave a temp behind.
+        assert!(write(&dir.join("missing").join("x.json"), b"{}").is_err());
+        assert!(!dir.join("missing").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn async_write_replaces_the_target() {
+        let dir = temp_dir("atomic-write-async");
+        let target = dir.join("index.json");
+        write_async(&target, b"old").await.unwrap();
+        write_async(&target, b"new").await.unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
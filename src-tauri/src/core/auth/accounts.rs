@@ -0,0 +1,219 @@
+// ─── Account Book ───
+// Accounts saved once and shared by every instance, persisted in
+// `<data_dir>/accounts.json`. Instances reference an account by id and keep
+// an inlined copy of the profile, which is what launches fall back to when
+// the saved account has been removed.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+
+use super::LaunchAccountProfile;
+
+/// File name of the account book inside the data dir.
+pub const ACCOUNTS_FILE: &str = "accounts.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAccount {
+    pub id: String,
+    #[serde(flatten)]
+    pub profile: LaunchAccountProfile,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountBook {
+    #[serde(default)]
+    pub accounts: Vec<SavedAccount>,
+    #[serde(default)]
+    pub default_account_id: Option<String>,
+}
+
+impl AccountBook {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(ACCOUNTS_FILE)
+    }
+
+    /// Read the book from `data_dir`. A missing file is an empty book; an
+    /// unreadable one is moved aside to a `.bak` file and the book starts
+    /// empty, so the launcher still starts and the next save loses nothing.
+    pub fn load(data_dir: &Path) -> Self {
+        let path = Self::path(data_dir);
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&raw).unwrap_or_else(|err| {
+            match atomic_file::set_aside(&path) {
+                Ok(backup) => warn!(
+                    "Unreadable {} ({}); moved it to {}",
+                    path.display(),
+                    err,
+                    backup.display()
+                ),
+                Err(rename_err) => warn!(
+                    "Unreadable {} ({}) and cannot move it aside: {}",
+                    path.display(),
+                    err,
+                    rename_err
+                ),
+            }
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, data_dir: &Path) -> LauncherResult<()> {
+        let path = Self::path(data_dir);
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(&path, json).map_err(|source| LauncherError::Io { path, source })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&SavedAccount> {
+        self.accounts.iter().find(|account| account.id == id)
+    }
+
+    /// Store `profile` under a new id. The first account becomes the default.
+    pub fn add(&mut self, profile: LaunchAccountProfile) -> &SavedAccount {
+        let id = Uuid::new_v4().to_string();
+        if self.default_account().is_none() {
+            self.default_account_id = Some(id.clone());
+        }
+        self.accounts.push(SavedAccount {
+            id,
+            profile: profile.sanitized(),
+        });
+        self.accounts.last().expect("account was just pushed")
+    }
+
+    /// Remove the account `id`. Removing the default hands the role to the
+    /// first remaining account.
+    pub fn remove(&mut self, id: &str) -> LauncherResult<SavedAccount> {
+        let index = self
+            .accounts
+            .iter()
+            .position(|account| account.id == id)
+            .ok_or_else(|| LauncherError::AccountNotFound(id.to_string()))?;
+        let removed = self.accounts.remove(index);
+        if self.default_account_id.as_deref() == Some(id) {
+            self.default_account_id = self.accounts.first().map(|account| account.id.clone());
+        }
+        Ok(removed)
+    }
+
+    pub fn set_default(&mut self, id: &str) -> LauncherResult<()> {
+        if self.get(id).is_none() {
+            return Err(LauncherError::AccountNotFound(id.to_string()));
+        }
+        self.default_account_id = Some(id.to_string());
+        Ok(())
+    }
+
+    /// The default account, or the first saved one when the stored default
+    /// id no longer exists.
+    pub fn default_account(&self) -> Option<&SavedAccount> {
+        self.default_account_id
+            .as_deref()
+            .and_then(|id| self.get(id))
+            .or_else(|| self.accounts.first())
+    }
+
+    /// Profile to launch with: the saved account `account_id` when it still
+    /// exists, otherwise the instance's inlined `fallback`.
+    pub fn resolve<'a>(
+        &'a self,
+        account_id: Option<&str>,
+        fallback: &'a LaunchAccountProfile,
+    ) -> &'a LaunchAccountProfile {
+        account_id
+            .and_then(|id| self.get(id))
+            .map_or(fallback, |account| &account.profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_remove_and_default_selection() {
+        let mut book = AccountBook::default();
+        assert!(book.default_account().is_none());
+
+        let steve = book.add(LaunchAccountProfile::offline("Steve")).id.clone();
+        let alex = book.add(LaunchAccountProfile::offline("Alex")).id.clone();
+        assert_eq!(book.accounts.len(), 2);
+        // The first account becomes the default; later ones do not.
+        assert_eq!(book.default_account().unwrap().id, steve);
+
+        book.set_default(&alex).unwrap();
+        assert_eq!(book.default_account().unwrap().profile.username, "Alex");
+        assert!(matches!(
+            book.set_default("missing"),
+            Err(LauncherError::AccountNotFound(_))
+        ));
+
+        assert_eq!(book.remove(&alex).unwrap().profile.username, "Alex");
+        assert_eq!(book.default_account_id.as_deref(), Some(steve.as_str()));
+        assert!(book.remove(&alex).is_err());
+
+        book.remove(&steve).unwrap();
+        assert!(book.default_account().is_none());
+        assert_eq!(book.default_account_id, None);
+    }
+
+    #[test]
+    fn dangling_default_falls_back_to_the_first_account() {
+        let mut book = AccountBook::default();
+        let steve = book.add(LaunchAccountProfile::offline("Steve")).id.clone();
+        book.default_account_id = Some("deleted-by-hand".into());
+
+        assert_eq!(book.default_account().unwrap().id, steve);
+    }
+
+    #[test]
+    fn resolve_prefers_the_saved_account_and_keeps_the_inlined_copy() {
+        let mut book = AccountBook::default();
+        let id = book.add(LaunchAccountProfile::offline("Saved")).id.clone();
+        let inlined = LaunchAccountProfile::offline("Inlined");
+
+        assert_eq!(book.resolve(Some(&id), &inlined).username, "Saved");
+        assert_eq!(book.resolve(Some("gone"), &inlined).username, "Inlined");
+        assert_eq!(book.resolve(None, &inlined).username, "Inlined");
+    }
+
+    #[test]
+    fn book_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!("account-book-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert!(AccountBook::load(&dir).accounts.is_empty());
+        let mut book = AccountBook::default();
+        let id = book.add(LaunchAccountProfile::offline("Steve")).id.clone();
+        book.save(&dir).unwrap();
+
+        let loaded = AccountBook::load(&dir);
+        assert_eq!(loaded.default_account_id.as_deref(), Some(id.as_str()));
+        assert_eq!(loaded.get(&id).unwrap().profile.username, "Steve");
+
+        std::fs::write(AccountBook::path(&dir), "{ not json").unwrap();
+        assert!(AccountBook::load(&dir).accounts.is_empty());
+        // The unreadable book is kept aside, not overwritten by the next save.
+        AccountBook::default().save(&dir).unwrap();
+        let kept: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".bak"))
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(kept[0].path()).unwrap(),
+            "{ not json"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
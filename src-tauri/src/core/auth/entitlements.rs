@@ -0,0 +1,208 @@
+// ─── Game Ownership ───
+// A Microsoft login succeeds for accounts that never bought Java Edition;
+// the game then silently starts in demo mode. The `mcstore` entitlements of
+// the account's token say whether it owns the game (purchase or Game Pass).
+// Results are cached per uuid in `<data_dir>/cache/entitlements.json` so
+// launches do not query the API every time, and the last known answer is
+// kept when a check fails.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::net::{self, RetryPolicy};
+
+const ENTITLEMENTS_URL: &str = "https://api.minecraftservices.com/entitlements/mcstore";
+const ENTITLEMENTS_CACHE_FILE: &str = "entitlements.json";
+/// Entitlements granting Java Edition; the `_bedrock` ones do not.
+const JAVA_ENTITLEMENTS: [&str; 2] = ["product_minecraft", "game_minecraft"];
+
+/// How long a checked ownership is trusted before asking again.
+pub fn entitlement_ttl() -> Duration {
+    Duration::hours(24)
+}
+
+#[derive(Debug, Deserialize)]
+struct EntitlementsResponse {
+    #[serde(default)]
+    items: Vec<EntitlementItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntitlementItem {
+    name: String,
+}
+
+/// Whether an entitlements response grants Java Edition.
+pub fn parse_entitlements(body: &str) -> LauncherResult<bool> {
+    let response: EntitlementsResponse = serde_json::from_str(body)?;
+    Ok(response
+        .items
+        .iter()
+        .any(|item| JAVA_ENTITLEMENTS.contains(&item.name.as_str())))
+}
+
+/// Ask the Minecraft services whether the holder of `access_token` owns the game.
+pub async fn fetch_ownership(client: &reqwest::Client, access_token: &str) -> LauncherResult<bool> {
+    let response = net::send_with_retry(
+        || client.get(ENTITLEMENTS_URL).bearer_auth(access_token),
+        RetryPolicy::default(),
+    )
+    .await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url: ENTITLEMENTS_URL.into(),
+            status: status.as_u16(),
+        });
+    }
+    parse_entitlements(&response.text().await?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entitlement {
+    pub owns_minecraft: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EntitlementCache {
+    #[serde(default)]
+    entries: HashMap<String, Entitlement>,
+}
+
+impl EntitlementCache {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("cache").join(ENTITLEMENTS_CACHE_FILE)
+    }
+
+    /// Cache in `data_dir`; empty when missing or unreadable.
+    pub fn load(data_dir: &Path) -> Self {
+        std::fs::read(Self::path(data_dir))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> LauncherResult<()> {
+        let path = Self::path(data_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(&path, json).map_err(|source| LauncherError::Io { path, source })
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<Entitlement> {
+        self.entries.get(uuid).copied()
+    }
+
+    /// Ownership of `uuid`: the cached answer while younger than
+    /// [`entitlement_ttl`], else the result of `fetch`, cached. When `fetch`
+    /// fails the last known answer is kept; `None` means it is unknown.
+    pub async fn resolve<F, Fut>(
+        &mut self,
+        uuid: &str,
+        now: DateTime<Utc>,
+        fetch: F,
+    ) -> Option<bool>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = LauncherResult<bool>>,
+    {
+        let cached = self.get(uuid);
+        if let Some(entry) = cached.filter(|entry| now - entry.checked_at < entitlement_ttl()) {
+            return Some(entry.owns_minecraft);
+        }
+        match fetch().await {
+            Ok(owns_minecraft) => {
+                self.entries.insert(
+                    uuid.to_string(),
+                    Entitlement {
+                        owns_minecraft,
+                        checked_at: now,
+                    },
+                );
+                Some(owns_minecraft)
+            }
+            Err(err) => {
+                warn!("Cannot check game ownership of {}: {}", uuid, err);
+                cached.map(|entry| entry.owns_minecraft)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entitlements_tell_owners_from_non_owners() {
+        let owner = r#"{
+            "items": [
+                { "name": "product_minecraft", "signature": "eyJ0" },
+                { "name": "game_minecraft", "signature": "eyJ0" }
+            ],
+            "signature": "eyJhbGciOi",
+            "keyId": "1"
+        }"#;
+        let game_pass = r#"{"items":[{"name":"game_minecraft","signature":"x"}],"keyId":"1"}"#;
+        let bedrock_only = r#"{
+            "items": [
+                { "name": "product_minecraft_bedrock", "signature": "eyJ0" },
+                { "name": "game_minecraft_bedrock", "signature": "eyJ0" }
+            ],
+            "keyId": "1"
+        }"#;
+        let none = r#"{"items":[],"signature":"eyJhbGciOi","keyId":"1"}"#;
+
+        assert!(parse_entitlements(owner).unwrap());
+        assert!(parse_entitlements(game_pass).unwrap());
+        assert!(!parse_entitlements(bedrock_only).unwrap());
+        assert!(!parse_entitlements(none).unwrap());
+        assert!(!parse_entitlements("{}").unwrap());
+        assert!(parse_entitlements("<html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn ownership_is_cached_and_kept_when_the_check_fails() {
+        let uuid = "069a79f444e94726a5befca90e38aaf5";
+        let start = Utc::now();
+        let mut cache = EntitlementCache::default();
+
+        let offline = || async { Err(LauncherError::Other("sin conexión".into())) };
+        assert_eq!(cache.resolve(uuid, start, offline).await, None);
+
+        assert_eq!(
+            cache.resolve(uuid, start, || async { Ok(false) }).await,
+            Some(false)
+        );
+        let within = start + entitlement_ttl() - Duration::minutes(1);
+        assert_eq!(
+            cache
+                .resolve(uuid, within, || async {
+                    panic!("fresh entries must not be checked again")
+                })
+                .await,
+            Some(false)
+        );
+
+        let later = start + entitlement_ttl() * 2;
+        assert_eq!(cache.resolve(uuid, later, offline).await, Some(false));
+        assert_eq!(
+            cache.resolve(uuid, later, || async { Ok(true) }).await,
+            Some(true)
+        );
+        assert_eq!(cache.get(uuid).unwrap().checked_at, later);
+    }
+}
@@ -1,5 +1,14 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::error::LauncherResult;
+
+pub mod accounts;
+pub mod entitlements;
+pub mod profile;
+pub mod skin;
+
 pub const AZURE_CLIENT_ID_FALLBACK: &str = "00000000402B5328";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +27,13 @@ pub struct LaunchAccountProfile {
     pub xuid: String,
     pub user_type: String,
     pub client_id: String,
+    /// Local PNG shown as the player skin of an offline account; see `skin`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offline_skin_path: Option<PathBuf>,
+    /// Result of the last entitlements check of a Microsoft account; `None`
+    /// until checked. See `entitlements`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owns_minecraft: Option<bool>,
 }
 
 impl Default for LaunchAccountProfile {
@@ -36,7 +52,26 @@ impl LaunchAccountProfile {
             xuid: "0".into(),
             user_type: "legacy".into(),
             client_id: AZURE_CLIENT_ID_FALLBACK.into(),
+            offline_skin_path: None,
+            owns_minecraft: None,
+        }
+    }
+
+    /// Offline accounts, and Microsoft accounts known not to own the game,
+    /// can only play the demo.
+    pub fn is_unlicensed(&self) -> bool {
+        match self.mode {
+            AccountMode::Offline => true,
+            AccountMode::Microsoft => self.owns_minecraft == Some(false),
+        }
+    }
+
+    /// The profile as given, or an error if its offline skin is unusable.
+    pub fn validated(self) -> LauncherResult<Self> {
+        if let Some(skin_path) = &self.offline_skin_path {
+            skin::read_skin_file(skin_path)?;
         }
+        Ok(self)
     }
 
     pub fn sanitized(mut self) -> Self {
@@ -0,0 +1,392 @@
+// ─── Account Profiles ───
+// Skin and cape of a Microsoft account, read from the session server so the
+// UI can draw the player's head. The session server rate-limits lookups of
+// the same profile, so results are cached per uuid in
+// `<data_dir>/cache/profiles.json` for `profile_ttl()`, and an expired entry is
+// still returned (flagged stale) when the refresh fails, e.g. offline. The
+// cache lives in `AppState` behind its own mutex so concurrent lookups don't
+// overwrite each other's entries on disk.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::net::{self, RetryPolicy};
+
+const SESSION_PROFILE_URL: &str = "https://sessionserver.mojang.com/session/minecraft/profile";
+const PROFILE_CACHE_FILE: &str = "profiles.json";
+
+/// How long a fetched profile is used without asking the session server.
+pub fn profile_ttl() -> Duration {
+    Duration::minutes(30)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountProfile {
+    /// Undashed uuid, as the session server returns it.
+    pub uuid: String,
+    pub username: String,
+    pub skin_url: Option<String>,
+    /// `slim` (Alex) or `classic` (Steve).
+    pub skin_model: String,
+    pub cape_url: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+    /// Served from an expired cache entry because the refresh failed.
+    #[serde(default)]
+    pub stale: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionProfile {
+    id: String,
+    name: String,
+    #[serde(default)]
+    properties: Vec<SessionProperty>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionProperty {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TexturesPayload {
+    #[serde(default)]
+    textures: HashMap<String, Texture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Texture {
+    url: String,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// `uuid` without dashes, lowercased, if it is a valid uuid.
+pub fn normalize_uuid(uuid: &str) -> LauncherResult<String> {
+    let compact: String = uuid
+        .trim()
+        .chars()
+        .filter(|c| *c != '-')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if compact.len() != 32 || !compact.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(LauncherError::message(
+            ErrorText::InvalidAccountUuid,
+            &[&uuid],
+        ));
+    }
+    Ok(compact)
+}
+
+/// Profile out of a session server response body.
+pub fn parse_session_profile(
+    body: &str,
+    fetched_at: DateTime<Utc>,
+) -> LauncherResult<AccountProfile> {
+    let session: SessionProfile = serde_json::from_str(body)?;
+    let textures = session
+        .properties
+        .iter()
+        .find(|property| property.name == "textures")
+        .and_then(|property| {
+            base64::engine::general_purpose::STANDARD
+                .decode(&property.value)
+                .ok()
+        })
+        .and_then(|json| serde_json::from_slice::<TexturesPayload>(&json).ok())
+        .unwrap_or_default();
+
+    let skin = textures.textures.get("SKIN");
+    Ok(AccountProfile {
+        uuid: session.id,
+        username: session.name,
+        skin_url: skin.map(|skin| skin.url.clone()),
+        skin_model: match skin.and_then(|skin| skin.metadata.get("model")) {
+            Some(model) if model == "slim" => "slim".into(),
+            _ => "classic".into(),
+        },
+        cape_url: textures.textures.get("CAPE").map(|cape| cape.url.clone()),
+        fetched_at,
+        stale: false,
+    })
+}
+
+/// Fetch the profile of `uuid` (undashed) from the session server.
+pub async fn fetch_session_profile(
+    client: &reqwest::Client,
+    uuid: &str,
+) -> LauncherResult<AccountProfile> {
+    let url = format!("{SESSION_PROFILE_URL}/{uuid}");
+    let response = net::send_with_retry(|| client.get(&url), RetryPolicy::default()).await?;
+    let status = response.status();
+    // 204 / 404: no Minecraft profile with that uuid.
+    if !status.is_success() || status == reqwest::StatusCode::NO_CONTENT {
+        return Err(LauncherError::DownloadFailed {
+            url,
+            status: status.as_u16(),
+        });
+    }
+    parse_session_profile(&response.text().await?, Utc::now())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProfileCache {
+    #[serde(default)]
+    profiles: HashMap<String, AccountProfile>,
+}
+
+impl ProfileCache {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("cache").join(PROFILE_CACHE_FILE)
+    }
+
+    /// Cache in `data_dir`; empty when missing or unreadable.
+    pub fn load(data_dir: &Path) -> Self {
+        std::fs::read(Self::path(data_dir))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> LauncherResult<()> {
+        let path = Self::path(data_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(&path, json).map_err(|source| LauncherError::Io { path, source })
+    }
+
+    /// Cached profile of `uuid` if fetched less than [`profile_ttl`] before `now`.
+    pub fn fresh(&self, uuid: &str, now: DateTime<Utc>) -> Option<&AccountProfile> {
+        self.profiles
+            .get(uuid)
+            .filter(|profile| now - profile.fetched_at < profile_ttl())
+    }
+
+    pub fn get(&self, uuid: &str) -> Option<&AccountProfile> {
+        self.profiles.get(uuid)
+    }
+
+    pub fn insert(&mut self, profile: AccountProfile) {
+        self.profiles.insert(profile.uuid.clone(), profile);
+    }
+
+    /// Profile of `uuid` (undashed): the cached one while fresh, else the
+    /// result of `fetch`, cached. When `fetch` fails an expired entry is
+    /// returned with `stale` set; the error only surfaces without one.
+    pub async fn resolve<F, Fut>(
+        &mut self,
+        uuid: &str,
+        now: DateTime<Utc>,
+        fetch: F,
+    ) -> LauncherResult<AccountProfile>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = LauncherResult<AccountProfile>>,
+    {
+        if let Some(profile) = self.fresh(uuid, now) {
+            return Ok(profile.clone());
+        }
+        match fetch().await {
+            Ok(mut profile) => {
+                // Key by the requested uuid even if the server formats it differently.
+                profile.uuid = uuid.to_string();
+                self.insert(profile.clone());
+                Ok(profile)
+            }
+            Err(err) => match self.get(uuid) {
+                Some(cached) => {
+                    warn!(
+                        "Cannot refresh profile {} ({}); using the copy from {}",
+                        uuid, err, cached.fetched_at
+                    );
+                    Ok(AccountProfile {
+                        stale: true,
+                        ..cached.clone()
+                    })
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
+
+/// [`ProfileCache::resolve`] on the shared `cache`, saving it to `data_dir`
+/// when a fresh profile was fetched. The lock is held until the save so a
+/// concurrent lookup cannot write an older copy over this one.
+pub async fn resolve_cached<F, Fut>(
+    cache: &Mutex<ProfileCache>,
+    data_dir: &Path,
+    uuid: &str,
+    now: DateTime<Utc>,
+    fetch: F,
+) -> LauncherResult<AccountProfile>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = LauncherResult<AccountProfile>>,
+{
+    let mut cache = cache.lock().await;
+    let was_fresh = cache.fresh(uuid, now).is_some();
+    let profile = cache.resolve(uuid, now, fetch).await?;
+    if !was_fresh && !profile.stale {
+        if let Err(err) = cache.save(data_dir) {
+            warn!("Could not save the profile cache: {}", err);
+        }
+    }
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "069a79f444e94726a5befca90e38aaf5";
+
+    fn session_body() -> String {
+        let textures = serde_json::json!({
+            "timestamp": 1700000000000u64,
+            "profileId": UUID,
+            "profileName": "Notch",
+            "textures": {
+                "SKIN": { "url": "http://textures.minecraft.net/texture/skin",
+                          "metadata": { "model": "slim" } },
+                "CAPE": { "url": "http://textures.minecraft.net/texture/cape" }
+            }
+        });
+        serde_json::json!({
+            "id": UUID,
+            "name": "Notch",
+            "properties": [{
+                "name": "textures",
+                "value": base64::engine::general_purpose::STANDARD.encode(textures.to_string())
+            }]
+        })
+        .to_string()
+    }
+
+    fn profile(name: &str, fetched_at: DateTime<Utc>) -> AccountProfile {
+        AccountProfile {
+            uuid: UUID.into(),
+            username: name.into(),
+            skin_url: None,
+            skin_model: "classic".into(),
+            cape_url: None,
+            fetched_at,
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn session_textures_are_decoded() {
+        let now = Utc::now();
+        let parsed = parse_session_profile(&session_body(), now).unwrap();
+        assert_eq!(parsed.username, "Notch");
+        assert_eq!(
+            parsed.skin_url.as_deref(),
+            Some("http://textures.minecraft.net/texture/skin")
+        );
+        assert_eq!(parsed.skin_model, "slim");
+        assert_eq!(
+            parsed.cape_url.as_deref(),
+            Some("http://textures.minecraft.net/texture/cape")
+        );
+        assert_eq!(
+            normalize_uuid("069A79F4-44E9-4726-A5BE-FCA90E38AAF5").unwrap(),
+            UUID
+        );
+        assert!(normalize_uuid("not-a-uuid").is_err());
+    }
+
+    #[tokio::test]
+    async fn cached_profiles_are_reused_until_they_expire() {
+        let start = Utc::now();
+        let mut cache = ProfileCache::default();
+
+        let first = cache
+            .resolve(UUID, start, || async move { Ok(profile("Notch", start)) })
+            .await
+            .unwrap();
+        assert_eq!(first.username, "Notch");
+
+        // Within the TTL the session server is not asked again.
+        let within = start + profile_ttl() - Duration::minutes(1);
+        let cached = cache
+            .resolve(UUID, within, || async {
+                panic!("fresh entries must not be refetched")
+            })
+            .await
+            .unwrap();
+        assert_eq!(cached, first);
+
+        // Past the TTL the profile is refreshed.
+        let later = start + profile_ttl() + Duration::minutes(1);
+        let renamed = cache
+            .resolve(UUID, later, || async move { Ok(profile("Jeb", later)) })
+            .await
+            .unwrap();
+        assert_eq!(renamed.username, "Jeb");
+        assert!(!renamed.stale);
+    }
+
+    #[tokio::test]
+    async fn offline_falls_back_to_the_expired_copy() {
+        let start = Utc::now();
+        let mut cache = ProfileCache::default();
+        cache.insert(profile("Notch", start));
+
+        let offline = || async { Err(LauncherError::Other("sin conexión".into())) };
+        let later = start + profile_ttl() * 4;
+        let fallback = cache.resolve(UUID, later, offline).await.unwrap();
+        assert_eq!(fallback.username, "Notch");
+        assert!(fallback.stale);
+
+        let mut empty = ProfileCache::default();
+        assert!(empty.resolve(UUID, later, offline).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn concurrent_lookups_keep_every_entry_on_disk() {
+        let data_dir = std::env::temp_dir().join(format!("profile-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let cache = Mutex::new(ProfileCache::default());
+        let now = Utc::now();
+        let other = "853c80ef3c3749fdaa49938b674adae6";
+
+        let slow = || async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(profile("Notch", now))
+        };
+        let fast = || async move {
+            Ok(AccountProfile {
+                uuid: other.into(),
+                ..profile("jeb_", now)
+            })
+        };
+        let (a, b) = tokio::join!(
+            resolve_cached(&cache, &data_dir, UUID, now, slow),
+            resolve_cached(&cache, &data_dir, other, now, fast),
+        );
+        a.unwrap();
+        b.unwrap();
+
+        let saved = ProfileCache::load(&data_dir);
+        assert!(saved.get(UUID).is_some());
+        assert!(saved.get(other).is_some());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
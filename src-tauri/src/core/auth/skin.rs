@@ -0,0 +1,576 @@
+// ─── Offline Skins ───
+// Offline accounts have no profile on the session servers, so the game
+// falls back to the default Steve/Alex textures. Instead of patching the
+// client or running a fake skin server, the launcher writes the chosen skin
+// into a small resource pack inside the instance that replaces the default
+// player texture, and enables it in `options.txt`. Only the local player
+// sees it; other players on a server still see their own skins.
+//
+// Limitation: a resource pack cannot target one player. The game picks a
+// default texture from the player UUID, so the pack only replaces the one
+// the local player gets (for the session UUID and the offline-mode UUID of
+// the username). Other default-skinned players that hash to the same
+// texture are reskinned too, and before 1.8 every player shares `steve.png`.
+// Since 1.19.3 the texture also fixes the arm model (slim or wide), which
+// may not match the skin.
+
+use std::path::{Path, PathBuf};
+
+use image::{ImageFormat, RgbaImage};
+use md5::{Digest, Md5};
+use serde_json::json;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::version::compare::minecraft_release;
+
+use super::{AccountMode, LaunchAccountProfile};
+
+/// Folder name of the generated pack under `<game_dir>/resourcepacks`.
+pub const SKIN_PACK_DIR: &str = "interface-offline-skin";
+
+/// Default skins since 1.19.3, in the order the game indexes them: the slim
+/// variants first, then the wide ones.
+const DEFAULT_SKINS: [&str; 9] = [
+    "alex", "ari", "efe", "kai", "makena", "noor", "steve", "sunny", "zuri",
+];
+
+/// Check `bytes` is a PNG skin of 64x64 (1.8+ layout) or 64x32 (legacy).
+pub fn validate_skin_png(bytes: &[u8]) -> LauncherResult<(u32, u32)> {
+    if !matches!(image::guess_format(bytes), Ok(ImageFormat::Png)) {
+        return Err(LauncherError::message(ErrorText::SkinNotPng, &[]));
+    }
+    let image = image::load_from_memory_with_format(bytes, ImageFormat::Png)
+        .map_err(|e| LauncherError::message(ErrorText::SkinDecodeFailed, &[&e]))?;
+    match (image.width(), image.height()) {
+        dimensions @ ((64, 64) | (64, 32)) => Ok(dimensions),
+        (width, height) => Err(LauncherError::message(
+            ErrorText::SkinWrongSize,
+            &[&width, &height],
+        )),
+    }
+}
+
+/// Read and validate the skin file at `path`.
+pub fn read_skin_file(path: &Path) -> LauncherResult<Vec<u8>> {
+    let bytes = std::fs::read(path).map_err(|e| LauncherError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    validate_skin_png(&bytes)?;
+    Ok(bytes)
+}
+
+/// Install or remove the skin pack for `account` before a launch. A missing
+/// or invalid skin never blocks the launch: it is logged and skipped.
+/// Returns the pack folder when a skin was applied.
+pub fn apply_offline_skin(
+    game_dir: &Path,
+    minecraft_version: &str,
+    account: &LaunchAccountProfile,
+) -> Option<PathBuf> {
+    let skin_path = match (&account.mode, &account.offline_skin_path) {
+        (AccountMode::Offline, Some(path)) => path,
+        _ => {
+            remove_skin_pack(game_dir);
+            return None;
+        }
+    };
+
+    let bytes = match read_skin_file(skin_path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            warn!("Skipping offline skin {}: {}", skin_path.display(), err);
+            remove_skin_pack(game_dir);
+            return None;
+        }
+    };
+
+    let uuids = player_uuids(account);
+    match install_skin_pack(game_dir, minecraft_version, &uuids, &bytes) {
+        Ok(pack_dir) => {
+            info!("Offline skin applied from {}", skin_path.display());
+            Some(pack_dir)
+        }
+        Err(err) => {
+            warn!("Cannot install offline skin pack: {}", err);
+            None
+        }
+    }
+}
+
+/// UUIDs the game may give the local player: the session UUID (single
+/// player) and the one offline-mode servers derive from the username.
+fn player_uuids(account: &LaunchAccountProfile) -> Vec<Uuid> {
+    let mut uuids = Vec::with_capacity(2);
+    if let Ok(uuid) = Uuid::parse_str(account.uuid.trim()) {
+        uuids.push(uuid);
+    }
+    let offline = offline_player_uuid(&account.username);
+    if !uuids.contains(&offline) {
+        uuids.push(offline);
+    }
+    uuids
+}
+
+/// `UUID.nameUUIDFromBytes("OfflinePlayer:<name>")`, as offline-mode servers
+/// compute it.
+fn offline_player_uuid(username: &str) -> Uuid {
+    let digest: [u8; 16] = Md5::digest(format!("OfflinePlayer:{username}")).into();
+    uuid::Builder::from_md5_bytes(digest).into_uuid()
+}
+
+/// Java's `UUID.hashCode`, which the game uses to pick a default skin.
+fn java_uuid_hash(uuid: &Uuid) -> i32 {
+    let (most, least) = uuid.as_u64_pair();
+    let hilo = most ^ least;
+    ((hilo >> 32) as i32) ^ (hilo as i32)
+}
+
+/// Texture under `textures/entity` the game shows for `uuid` when it has no
+/// skin of its own.
+fn default_skin_texture(minecraft_version: &str, uuid: &Uuid) -> String {
+    let hash = java_uuid_hash(uuid);
+    match minecraft_release(minecraft_version) {
+        Some((minor, _)) if minor < 8 => "steve.png".to_string(),
+        Some(release) if release < (19, 3) => if hash & 1 == 1 {
+            "alex.png"
+        } else {
+            "steve.png"
+        }
+        .to_string(),
+        _ => {
+            let index = hash.rem_euclid(2 * DEFAULT_SKINS.len() as i32) as usize;
+            let model = if index < DEFAULT_SKINS.len() {
+                "slim"
+            } else {
+                "wide"
+            };
+            let name = DEFAULT_SKINS[index % DEFAULT_SKINS.len()];
+            format!("player/{model}/{name}.png")
+        }
+    }
+}
+
+/// Bring `skin` to the layout of `minecraft_version`: 64x32 before 1.8 and
+/// 64x64 after, converting legacy skins the way the game does.
+fn skin_for_version(minecraft_version: &str, skin: &[u8]) -> LauncherResult<Vec<u8>> {
+    let image = image::load_from_memory_with_format(skin, ImageFormat::Png)
+        .map_err(|e| LauncherError::message(ErrorText::SkinDecodeFailed, &[&e]))?
+        .to_rgba8();
+    let legacy_layout =
+        matches!(minecraft_release(minecraft_version), Some((minor, _)) if minor < 8);
+    let converted = match (legacy_layout, image.height()) {
+        (true, 64) => image::imageops::crop_imm(&image, 0, 0, 64, 32).to_image(),
+        (false, 32) => convert_legacy_skin(&image),
+        _ => return Ok(skin.to_vec()),
+    };
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgba8(converted)
+        .write_to(&mut bytes, ImageFormat::Png)
+        .map_err(|e| LauncherError::message(ErrorText::SkinConvertFailed, &[&e]))?;
+    Ok(bytes.into_inner())
+}
+
+/// 64x32 skin to the 64x64 layout: the left arm and leg are mirrored copies
+/// of the right ones, as the game does for legacy skins.
+fn convert_legacy_skin(legacy: &RgbaImage) -> RgbaImage {
+    let mut skin = RgbaImage::new(64, 64);
+    image::imageops::replace(&mut skin, legacy, 0, 0);
+    // (x, y, dx, dy, width, height), copied mirrored horizontally.
+    const MIRRORED: [(u32, u32, i32, i32, u32, u32); 12] = [
+        (4, 16, 16, 32, 4, 4),
+        (8, 16, 16, 32, 4, 4),
+        (0, 20, 24, 32, 4, 12),
+        (4, 20, 16, 32, 4, 12),
+        (8, 20, 8, 32, 4, 12),
+        (12, 20, 16, 32, 4, 12),
+        (44, 16, -8, 32, 4, 4),
+        (48, 16, -8, 32, 4, 4),
+        (40, 20, 0, 32, 4, 12),
+        (44, 20, -8, 32, 4, 12),
+        (48, 20, -16, 32, 4, 12),
+        (52, 20, -8, 32, 4, 12),
+    ];
+    for (x, y, dx, dy, width, height) in MIRRORED {
+        for row in 0..height {
+            for column in 0..width {
+                let pixel = *legacy.get_pixel(x + column, y + row);
+                let to_x = (x as i32 + dx) as u32 + width - 1 - column;
+                let to_y = (y as i32 + dy) as u32 + row;
+                skin.put_pixel(to_x, to_y, pixel);
+            }
+        }
+    }
+    skin
+}
+
+/// Write the pack with `skin` over the default texture of each of `uuids`
+/// and enable it in `options.txt`.
+pub fn install_skin_pack(
+    game_dir: &Path,
+    minecraft_version: &str,
+    uuids: &[Uuid],
+    skin: &[u8],
+) -> LauncherResult<PathBuf> {
+    let pack_dir = game_dir.join("resourcepacks").join(SKIN_PACK_DIR);
+    let textures = pack_dir.join("assets/minecraft/textures/entity");
+    // Start over so textures of another version or account do not linger.
+    remove_pack_dir(&pack_dir);
+
+    let pack_format = resource_pack_format(minecraft_version);
+    let mcmeta = json!({
+        "pack": {
+            "pack_format": pack_format,
+            "supported_formats": { "min_inclusive": pack_format, "max_inclusive": 999 },
+            "description": "Skin offline (Interface)"
+        }
+    });
+    write_file(&pack_dir.join("pack.mcmeta"), mcmeta.to_string().as_bytes())?;
+
+    // The pack is rebuilt on every launch, so it only needs the textures of
+    // the version about to run.
+    let skin = skin_for_version(minecraft_version, skin)?;
+    for uuid in uuids {
+        write_file(
+            &textures.join(default_skin_texture(minecraft_version, uuid)),
+            &skin,
+        )?;
+    }
+
+    enable_in_options(
+        &game_dir.join("options.txt"),
+        &pack_entry(minecraft_version),
+    )?;
+    Ok(pack_dir)
+}
+
+/// Delete the pack and take it out of `options.txt`.
+fn remove_skin_pack(game_dir: &Path) {
+    remove_pack_dir(&game_dir.join("resourcepacks").join(SKIN_PACK_DIR));
+    let options_path = game_dir.join("options.txt");
+    if let Err(err) = disable_in_options(&options_path) {
+        warn!(
+            "Cannot remove the offline skin pack from {}: {}",
+            options_path.display(),
+            err
+        );
+    }
+}
+
+fn remove_pack_dir(pack_dir: &Path) {
+    if pack_dir.exists() {
+        if let Err(err) = std::fs::remove_dir_all(pack_dir) {
+            warn!(
+                "Cannot remove offline skin pack {}: {}",
+                pack_dir.display(),
+                err
+            );
+        }
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> LauncherResult<()> {
+    let io_err = |e| LauncherError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    std::fs::write(path, bytes).map_err(io_err)
+}
+
+fn read_options(options_path: &Path) -> LauncherResult<String> {
+    match std::fs::read_to_string(options_path) {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(LauncherError::Io {
+            path: options_path.to_path_buf(),
+            source: e,
+        }),
+    }
+}
+
+/// Add `entry` to the `resourcePacks` list in `options.txt`, creating the
+/// line (or the file) when missing. Other options are kept as they are.
+fn enable_in_options(options_path: &Path, entry: &str) -> LauncherResult<()> {
+    let existing = read_options(options_path)?;
+
+    let mut found = false;
+    let mut lines: Vec<String> = existing
+        .lines()
+        .map(|line| match line.strip_prefix("resourcePacks:") {
+            Some(value) => {
+                found = true;
+                // Leave a line we cannot parse alone rather than lose packs.
+                let Ok(mut packs) = serde_json::from_str::<Vec<String>>(value) else {
+                    return line.to_string();
+                };
+                if !packs.iter().any(|pack| pack == entry) {
+                    packs.push(entry.to_string());
+                }
+                format!("resourcePacks:{}", json!(packs))
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    if !found {
+        let packs = if entry.starts_with("file/") {
+            json!(["vanilla", entry])
+        } else {
+            json!([entry])
+        };
+        lines.push(format!("resourcePacks:{packs}"));
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    if updated != existing {
+        write_file(options_path, updated.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Take the pack, under either of its names, out of the `resourcePacks` and
+/// `incompatibleResourcePacks` lists. A missing file is left missing.
+fn disable_in_options(options_path: &Path) -> LauncherResult<()> {
+    let existing = read_options(options_path)?;
+    let file_entry = format!("file/{SKIN_PACK_DIR}");
+    let mut changed = false;
+    let lines: Vec<String> = existing
+        .lines()
+        .map(|line| {
+            let Some((key, value)) = line.split_once(':') else {
+                return line.to_string();
+            };
+            if key != "resourcePacks" && key != "incompatibleResourcePacks" {
+                return line.to_string();
+            }
+            let Ok(mut packs) = serde_json::from_str::<Vec<String>>(value) else {
+                return line.to_string();
+            };
+            let before = packs.len();
+            packs.retain(|pack| pack != SKIN_PACK_DIR && *pack != file_entry);
+            if packs.len() == before {
+                return line.to_string();
+            }
+            changed = true;
+            format!("{key}:{}", json!(packs))
+        })
+        .collect();
+
+    if changed {
+        let mut updated = lines.join("\n");
+        updated.push('\n');
+        write_file(options_path, updated.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// `options.txt` names folder packs `file/<name>` since 1.13.
+fn pack_entry(minecraft_version: &str) -> String {
+    match minecraft_release(minecraft_version) {
+        Some((minor, _)) if minor < 13 => SKIN_PACK_DIR.to_string(),
+        _ => format!("file/{SKIN_PACK_DIR}"),
+    }
+}
+
+/// Resource pack format of a release. Snapshots and unknown versions get the
+/// newest known format; `supported_formats` covers later releases.
+fn resource_pack_format(minecraft_version: &str) -> u32 {
+    let Some((minor, patch)) = minecraft_release(minecraft_version) else {
+        return 64;
+    };
+    match (minor, patch) {
+        (0..=8, _) => 1,
+        (9..=10, _) => 2,
+        (11..=12, _) => 3,
+        (13..=14, _) => 4,
+        (15, _) | (16, 0..=1) => 5,
+        (16, _) => 6,
+        (17, _) => 7,
+        (18, _) => 8,
+        (19, 0..=2) => 9,
+        (19, 3) => 12,
+        (19, _) => 13,
+        (20, 0..=1) => 15,
+        (20, 2) => 18,
+        (20, 3..=4) => 22,
+        (20, _) => 32,
+        (21, 0..=1) => 34,
+        (21, 2..=3) => 42,
+        (21, 4) => 46,
+        (21, 5) => 55,
+        (21, 6) => 63,
+        _ => 64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgba};
+
+    fn skin_png(width: u32, height: u32) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(width, height, Rgba([200u8, 120, 80, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut bytes, ImageFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    fn temp_game_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("offline-skin-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn accepts_only_64x64_and_64x32_pngs() {
+        assert_eq!(validate_skin_png(&skin_png(64, 64)).unwrap(), (64, 64));
+        assert_eq!(validate_skin_png(&skin_png(64, 32)).unwrap(), (64, 32));
+        assert!(validate_skin_png(&skin_png(128, 128)).is_err());
+        assert!(validate_skin_png(&skin_png(32, 64)).is_err());
+        assert!(validate_skin_png(b"\x89PNG\r\n\x1a\n garbage").is_err());
+
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(ImageBuffer::from_pixel(64, 64, Rgba([0u8, 0, 0, 255])))
+            .to_rgb8()
+            .write_to(&mut jpeg, ImageFormat::Jpeg)
+            .unwrap();
+        assert!(validate_skin_png(&jpeg.into_inner()).is_err());
+    }
+
+    #[test]
+    fn missing_skin_is_skipped_without_touching_the_instance() {
+        let game_dir = temp_game_dir("missing");
+        let account = LaunchAccountProfile {
+            offline_skin_path: Some(game_dir.join("does-not-exist.png")),
+            ..LaunchAccountProfile::offline("Steve")
+        };
+
+        assert_eq!(apply_offline_skin(&game_dir, "1.20.1", &account), None);
+        assert!(!game_dir.join("resourcepacks").exists());
+        assert!(!game_dir.join("options.txt").exists());
+
+        let _ = std::fs::remove_dir_all(&game_dir);
+    }
+
+    #[test]
+    fn valid_skin_is_packed_and_enabled_once() {
+        let game_dir = temp_game_dir("valid");
+        let skin = game_dir.join("skin.png");
+        std::fs::write(&skin, skin_png(64, 64)).unwrap();
+        std::fs::write(
+            game_dir.join("options.txt"),
+            "fov:0.5\nresourcePacks:[\"vanilla\"]\nlang:es_es\n",
+        )
+        .unwrap();
+        let account = LaunchAccountProfile {
+            offline_skin_path: Some(skin),
+            ..LaunchAccountProfile::offline("Steve")
+        };
+
+        let pack = apply_offline_skin(&game_dir, "1.20.1", &account).unwrap();
+        apply_offline_skin(&game_dir, "1.20.1", &account).unwrap();
+
+        // Only the textures picked for the nil session UUID and the
+        // offline-mode UUID of "Steve" are replaced.
+        let textures = pack.join("assets/minecraft/textures/entity");
+        assert!(pack.join("pack.mcmeta").is_file());
+        assert!(textures.join("player/slim/alex.png").is_file());
+        assert!(textures.join("player/wide/efe.png").is_file());
+        assert!(!textures.join("player/wide/steve.png").exists());
+        assert!(!textures.join("steve.png").exists());
+        let options = std::fs::read_to_string(game_dir.join("options.txt")).unwrap();
+        assert_eq!(
+            options,
+            "fov:0.5\nresourcePacks:[\"vanilla\",\"file/interface-offline-skin\"]\nlang:es_es\n"
+        );
+
+        // Switching to an older version leaves only its textures.
+        apply_offline_skin(&game_dir, "1.16.5", &account).unwrap();
+        assert!(textures.join("steve.png").is_file());
+        assert!(textures.join("alex.png").is_file());
+        assert!(!textures.join("player").exists());
+
+        // Clearing the skin removes the pack and its options entry again.
+        let plain = LaunchAccountProfile::offline("Steve");
+        assert_eq!(apply_offline_skin(&game_dir, "1.20.1", &plain), None);
+        assert!(!pack.exists());
+        let options = std::fs::read_to_string(game_dir.join("options.txt")).unwrap();
+        assert_eq!(
+            options,
+            "fov:0.5\nresourcePacks:[\"vanilla\"]\nlang:es_es\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&game_dir);
+    }
+
+    #[test]
+    fn default_texture_follows_the_player_uuid() {
+        let notch = offline_player_uuid("Notch");
+        assert_eq!(notch.to_string(), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+        assert_eq!(default_skin_texture("1.7.10", &notch), "steve.png");
+        assert_eq!(default_skin_texture("1.12.2", &notch), "steve.png");
+        assert_eq!(
+            default_skin_texture("1.20.1", &notch),
+            "player/slim/makena.png"
+        );
+
+        let steve = offline_player_uuid("Steve");
+        assert_eq!(default_skin_texture("1.12.2", &steve), "alex.png");
+        assert_eq!(
+            default_skin_texture("1.19.3", &steve),
+            "player/wide/efe.png"
+        );
+        assert_eq!(
+            default_skin_texture("24w14a", &steve),
+            "player/wide/efe.png"
+        );
+    }
+
+    #[test]
+    fn legacy_skins_are_converted_to_the_version_layout() {
+        let mut legacy = RgbaImage::from_pixel(64, 32, Rgba([0u8, 0, 0, 0]));
+        // Right leg front column 4 and right arm front column 44.
+        legacy.put_pixel(4, 20, Rgba([255, 0, 0, 255]));
+        legacy.put_pixel(44, 20, Rgba([0, 255, 0, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(legacy)
+            .write_to(&mut bytes, ImageFormat::Png)
+            .unwrap();
+
+        let modern = skin_for_version("1.20.1", bytes.get_ref()).unwrap();
+        let modern = image::load_from_memory(&modern).unwrap().to_rgba8();
+        assert_eq!(modern.dimensions(), (64, 64));
+        // Mirrored into the left leg (20..24, 52) and left arm (36..40, 52).
+        assert_eq!(*modern.get_pixel(23, 52), Rgba([255, 0, 0, 255]));
+        assert_eq!(*modern.get_pixel(39, 52), Rgba([0, 255, 0, 255]));
+        assert_eq!(*modern.get_pixel(4, 20), Rgba([255, 0, 0, 255]));
+
+        let old = skin_for_version("1.7.10", &skin_png(64, 64)).unwrap();
+        let old = image::load_from_memory(&old).unwrap();
+        assert_eq!((old.width(), old.height()), (64, 32));
+        assert_eq!(
+            skin_for_version("1.7.10", bytes.get_ref()).unwrap(),
+            bytes.into_inner()
+        );
+    }
+
+    #[test]
+    fn pack_format_and_entry_follow_the_release() {
+        assert_eq!(resource_pack_format("1.8.9"), 1);
+        assert_eq!(resource_pack_format("1.16.5"), 6);
+        assert_eq!(resource_pack_format("1.20.1"), 15);
+        assert_eq!(resource_pack_format("1.21-pre1"), 34);
+        assert_eq!(resource_pack_format("24w14a"), 64);
+        assert_eq!(pack_entry("1.12.2"), SKIN_PACK_DIR);
+        assert_eq!(pack_entry("1.13"), format!("file/{SKIN_PACK_DIR}"));
+    }
+}
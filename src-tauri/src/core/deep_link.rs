@@ -0,0 +1,173 @@
+// ─── Deep Links ───
+// `interface://launch/<id>` asks to start an instance from a web page or a
+// desktop shortcut; the user confirms it in the launcher. The URI arrives
+// from the deep-link plugin, or as a command-line argument when the OS
+// starts a new launcher process for it. Parsing is kept free of Tauri so it
+// can be checked on its own; the id is validated here because it ends up
+// joined to the instances dir.
+//
+// At cold start the link is handled before the window listens for events,
+// so the last request is also kept in `AppState` until the frontend takes it.
+
+use serde::Serialize;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+/// URI scheme registered by the launcher.
+pub const URI_SCHEME: &str = "interface";
+
+/// Event asking the frontend to confirm the launch of a linked instance,
+/// with `{ uri, id, name }`. A link never starts the game on its own.
+pub const LAUNCH_REQUEST_EVENT: &str = "deep-link-launch-request";
+
+/// Longest instance id accepted from a URI.
+const MAX_ID_LEN: usize = 128;
+
+/// Launch link waiting for the user's confirmation; the payload of
+/// [`LAUNCH_REQUEST_EVENT`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaunchRequest {
+    pub uri: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Launch { id: String },
+}
+
+/// URI that makes the launcher start the instance `id`.
+pub fn launch_uri(id: &str) -> String {
+    format!("{URI_SCHEME}://launch/{id}")
+}
+
+/// First argument that looks like a launcher URI, as passed by the OS.
+pub fn find_deep_link<I, S>(args: I) -> Option<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| arg.as_ref().trim().to_string())
+        .find(|arg| has_scheme(arg))
+}
+
+/// URI in the command line of a launcher process (`argv[0]` is the
+/// executable), e.g. the one a second copy forwards to the running one.
+pub fn forwarded_deep_link(argv: &[String]) -> Option<String> {
+    find_deep_link(argv.iter().skip(1))
+}
+
+fn has_scheme(uri: &str) -> bool {
+    uri.get(..URI_SCHEME.len() + 1)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{URI_SCHEME}:")))
+}
+
+pub fn parse_deep_link(uri: &str) -> LauncherResult<DeepLink> {
+    let invalid = |text: ErrorText| LauncherError::message(text, &[&uri]);
+
+    let uri = uri.trim();
+    if !has_scheme(uri) {
+        return Err(invalid(ErrorText::DeepLinkNotInterface));
+    }
+    let rest = uri[URI_SCHEME.len() + 1..].trim_start_matches('/');
+    // Query and fragment carry nothing we use.
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = rest.trim_end_matches('/').split('/');
+
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(action), Some(id), None) if action.eq_ignore_ascii_case("launch") => {
+            validate_id(id).map_err(invalid)?;
+            Ok(DeepLink::Launch { id: id.to_string() })
+        }
+        (Some(action), None, None) if action.eq_ignore_ascii_case("launch") => {
+            Err(invalid(ErrorText::DeepLinkMissingInstance))
+        }
+        _ => Err(invalid(ErrorText::DeepLinkUnknownAction)),
+    }
+}
+
+fn validate_id(id: &str) -> Result<(), ErrorText> {
+    if id.is_empty() {
+        return Err(ErrorText::DeepLinkMissingInstance);
+    }
+    if id.len() > MAX_ID_LEN {
+        return Err(ErrorText::DeepLinkIdTooLong);
+    }
+    let allowed = id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !allowed || id.starts_with('.') {
+        return Err(ErrorText::DeepLinkInvalidId);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_uris_are_parsed_and_validated() {
+        let launch = |id: &str| Some(DeepLink::Launch { id: id.into() });
+
+        assert_eq!(
+            parse_deep_link(&launch_uri("3f2a-survival")).ok(),
+            launch("3f2a-survival")
+        );
+        assert_eq!(
+            parse_deep_link("INTERFACE://Launch/abc/").ok(),
+            launch("abc")
+        );
+        assert_eq!(
+            parse_deep_link("interface:launch/abc?from=web").ok(),
+            launch("abc")
+        );
+
+        for bad in [
+            "https://launch/abc",
+            "interface://launch",
+            "interface://launch/",
+            "interface://launch/../../etc",
+            "interface://launch/a/b",
+            "interface://launch/a%2Fb",
+            "interface://launch/.hidden",
+            "interface://delete/abc",
+        ] {
+            assert!(parse_deep_link(bad).is_err(), "{bad} should be rejected");
+        }
+        assert!(parse_deep_link(&launch_uri(&"a".repeat(MAX_ID_LEN + 1))).is_err());
+    }
+
+    #[test]
+    fn the_uri_is_found_among_process_arguments() {
+        assert_eq!(
+            find_deep_link(["/usr/bin/interface", "interface://launch/abc"]),
+            Some("interface://launch/abc".to_string())
+        );
+        assert_eq!(find_deep_link(["/usr/bin/interface", "--verbose"]), None);
+    }
+
+    #[test]
+    fn a_second_process_forwards_its_uri_but_not_its_executable() {
+        let argv = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            forwarded_deep_link(&argv(&[
+                r"C:\Program Files\interface\interface.exe",
+                "--flag",
+                " interface://launch/abc/ ",
+            ])),
+            Some("interface://launch/abc/".to_string())
+        );
+        // A plain second start just focuses the running window.
+        assert_eq!(forwarded_deep_link(&argv(&["/usr/bin/interface"])), None);
+        assert_eq!(
+            forwarded_deep_link(&argv(&["interface:odd-exe-name"])),
+            None
+        );
+        assert_eq!(forwarded_deep_link(&[]), None);
+    }
+}
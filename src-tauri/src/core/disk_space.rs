@@ -0,0 +1,59 @@
+// ─── Disk Space ───
+// Free-space checks run before large downloads, so a full disk fails up
+// front with a clear error instead of halfway through an install.
+
+use std::path::Path;
+
+use crate::core::error::{LauncherError, LauncherResult};
+
+/// Free bytes on the disk holding `path` (the most specific mount point
+/// wins). `None` when no mounted disk contains it.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut best_len = 0usize;
+    let mut available = None;
+    for disk in disks.list() {
+        let mount = disk.mount_point();
+        if canonical.starts_with(mount) {
+            let len = mount.as_os_str().len();
+            if len >= best_len {
+                best_len = len;
+                available = Some(disk.available_space());
+            }
+        }
+    }
+    available
+}
+
+/// Fail with `InsufficientDiskSpace` when `needed` exceeds `available`. An
+/// unknown amount of free space lets the operation go ahead.
+pub fn check_space(needed: u64, available: Option<u64>) -> LauncherResult<()> {
+    match available {
+        Some(available) if available < needed => {
+            Err(LauncherError::InsufficientDiskSpace { needed, available })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn ensure_space(path: &Path, needed: u64) -> LauncherResult<()> {
+    check_space(needed, available_space(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_space_compares_against_the_threshold() {
+        assert!(check_space(100, Some(100)).is_ok());
+        assert!(check_space(100, None).is_ok());
+        match check_space(101, Some(100)) {
+            Err(LauncherError::InsufficientDiskSpace { needed, available }) => {
+                assert_eq!((needed, available), (101, 100));
+            }
+            other => panic!("expected InsufficientDiskSpace, got {other:?}"),
+        }
+    }
+}
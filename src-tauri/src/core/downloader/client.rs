@@ -1,24 +1,37 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 
 use futures_util::stream::{self, StreamExt};
 use reqwest::Client;
 use sha1::{Digest, Sha1};
-use tauri::{AppHandle, Emitter};
-use tokio::io::AsyncWriteExt;
+use tokio::sync::{Semaphore, SemaphorePermit};
 use tracing::{debug, info};
 
+use super::resume::{download_resumable, ExpectedHash};
+use super::throttle::BandwidthThrottle;
 use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::events::EventEmitter;
 use crate::core::http::build_http_client;
+use crate::core::i18n::ErrorText;
+use crate::core::mirrors::{self, DownloadMirrors};
 
-/// Payload emitted to the frontend on download progress.
+/// Aggregate progress payload emitted to the frontend as `download-progress`.
+///
+/// Batches report how many files are done out of the whole batch; single
+/// downloads report `1/1` once finished.
 #[derive(Clone, serde::Serialize)]
 pub struct DownloadProgress {
-    pub url: String,
+    pub completed: usize,
+    pub total: usize,
     pub bytes_downloaded: u64,
-    pub total_bytes: Option<u64>,
+    pub bytes_total: u64,
     pub file_name: String,
 }
 
+/// Receives the progress of every download of an observed [`Downloader`].
+pub type DownloadObserverFn = dyn Fn(&DownloadProgress) + Send + Sync;
+
 /// A single file to download with optional SHA-1 for validation.
 #[derive(Debug, Clone)]
 pub struct DownloadEntry {
@@ -28,148 +41,265 @@ pub struct DownloadEntry {
     pub size: Option<u64>,
 }
 
+/// Upper bound accepted for `max_concurrent_downloads`.
+pub const MAX_CONCURRENT_DOWNLOADS_CAP: usize = 64;
+
+/// Default parallelism derived from the CPU count (two sockets per core,
+/// clamped so small machines still overlap latency and big ones don't get
+/// rate-limited by the CDN).
+pub fn default_max_concurrent_downloads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| (n.get() * 2).clamp(4, 32))
+        .unwrap_or(8)
+}
+
 /// Concurrent, SHA-1 validated downloader.
 pub struct Downloader {
     client: Client,
-    /// Maximum number of parallel downloads.
-    concurrency: usize,
-    /// Optional Tauri app handle for emitting progress events.
-    app_handle: Option<AppHandle>,
+    /// Permits for in-flight downloads; shared by single and batch downloads.
+    limiter: Arc<Semaphore>,
+    /// Current permit count of `limiter`.
+    concurrency: Arc<AtomicUsize>,
+    /// Permits a shrink found in use, retired as their downloads finish.
+    pending_retire: Arc<AtomicUsize>,
+    /// Optional sink for `download-progress` events.
+    events: Option<Arc<dyn EventEmitter>>,
+    /// Also told about progress; see `view`.
+    observer: Option<Arc<DownloadObserverFn>>,
+    /// Mirror hosts tried before the official ones.
+    mirrors: Arc<RwLock<DownloadMirrors>>,
+    /// Bandwidth cap shared by every stream of this downloader and its views.
+    throttle: Arc<BandwidthThrottle>,
 }
 
 impl Downloader {
-    pub fn new(app_handle: Option<AppHandle>) -> Self {
+    pub fn new(events: Option<Arc<dyn EventEmitter>>) -> Self {
         let client = build_http_client().expect("Failed to build HTTP client");
+        let concurrency = default_max_concurrent_downloads();
 
         Self {
             client,
-            concurrency: 8,
-            app_handle,
+            limiter: Arc::new(Semaphore::new(concurrency)),
+            concurrency: Arc::new(AtomicUsize::new(concurrency)),
+            pending_retire: Arc::default(),
+            events,
+            observer: None,
+            mirrors: Arc::default(),
+            throttle: Arc::default(),
         }
     }
 
-    pub fn with_concurrency(mut self, n: usize) -> Self {
-        self.concurrency = n;
+    /// A downloader sharing this one's client, limits and event sink. With
+    /// an `observer` it also reports its own progress there, so a caller can
+    /// tell its downloads apart from others running at the same time.
+    pub fn view(&self, observer: Option<Arc<DownloadObserverFn>>) -> Self {
+        Self {
+            client: self.client.clone(),
+            limiter: self.limiter.clone(),
+            concurrency: self.concurrency.clone(),
+            pending_retire: self.pending_retire.clone(),
+            events: self.events.clone(),
+            observer,
+            mirrors: self.mirrors.clone(),
+            throttle: self.throttle.clone(),
+        }
+    }
+
+    pub fn with_concurrency(self, n: usize) -> Self {
+        self.set_max_concurrency(n);
+        self
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.concurrency.load(Ordering::SeqCst)
+    }
+
+    /// Change the number of parallel downloads at runtime.
+    ///
+    /// Growing takes effect immediately. Shrinking retires idle permits now
+    /// and the rest as in-flight downloads finish; growing again first keeps
+    /// the ones not retired yet.
+    pub fn set_max_concurrency(&self, n: usize) {
+        let n = n.clamp(1, MAX_CONCURRENT_DOWNLOADS_CAP);
+        let previous = self.concurrency.swap(n, Ordering::SeqCst);
+
+        if n > previous {
+            let grow = n - previous;
+            let kept = take_up_to(&self.pending_retire, grow);
+            if grow > kept {
+                self.limiter.add_permits(grow - kept);
+            }
+        } else if n < previous {
+            let excess = previous - n;
+            let forgotten = self.limiter.forget_permits(excess);
+            self.pending_retire
+                .fetch_add(excess - forgotten, Ordering::SeqCst);
+        }
+    }
+
+    /// Hand a permit back, or retire it if a shrink is still pending.
+    fn release(&self, permit: SemaphorePermit<'_>) {
+        if take_up_to(&self.pending_retire, 1) == 1 {
+            permit.forget();
+        }
+    }
+
+    pub fn with_mirrors(self, mirrors: DownloadMirrors) -> Self {
+        self.set_mirrors(mirrors);
+        self
+    }
+
+    /// Mirrors currently applied to this downloader and its views.
+    pub fn mirrors(&self) -> DownloadMirrors {
+        self.mirrors
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the mirrors at runtime; downloads already started keep theirs.
+    pub fn set_mirrors(&self, mirrors: DownloadMirrors) {
+        *self.mirrors.write().unwrap_or_else(|e| e.into_inner()) = mirrors;
+    }
+
+    pub fn with_max_bytes_per_sec(self, limit: Option<u64>) -> Self {
+        self.throttle.set_max_bytes_per_sec(limit);
         self
     }
 
+    /// The bandwidth cap, for transfers that bypass `download_file`.
+    pub fn throttle(&self) -> Arc<BandwidthThrottle> {
+        self.throttle.clone()
+    }
+
     // ── Single file download ────────────────────────────
 
     /// Download a single file to `dest`, optionally validating SHA-1.
     ///
-    /// Creates parent directories as needed. Drops the file handle
-    /// immediately after writing to avoid Windows OS Error 5.
+    /// Bytes are streamed into `<dest>.part` with a persisted checkpoint, so
+    /// an interrupted transfer resumes with a `Range` request next time.
     pub async fn download_file(
         &self,
         url: &str,
         dest: &Path,
         sha1_expected: Option<&str>,
     ) -> LauncherResult<()> {
-        // Ensure parent dir exists
-        if let Some(parent) = dest.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| LauncherError::Io {
-                    path: parent.to_path_buf(),
-                    source: e,
-                })?;
-        }
+        let expected = sha1_expected.map(|sha1| ExpectedHash::Sha1(sha1.to_string()));
+        self.download_verified(url, dest, expected.as_ref()).await
+    }
 
-        let response = self.client.get(url).send().await?;
+    /// Download a single file to `dest`, validating its SHA-512.
+    ///
+    /// Used for Modrinth content, which publishes SHA-512 as its primary hash.
+    pub async fn download_file_sha512(
+        &self,
+        url: &str,
+        dest: &Path,
+        sha512_expected: &str,
+    ) -> LauncherResult<()> {
+        let expected = ExpectedHash::Sha512(sha512_expected.to_string());
+        self.download_verified(url, dest, Some(&expected)).await
+    }
 
-        let status = response.status();
-        if !status.is_success() {
-            return Err(LauncherError::DownloadFailed {
-                url: url.to_string(),
-                status: status.as_u16(),
-            });
-        }
+    /// Resumable download validated against any supported hash.
+    pub async fn download_verified(
+        &self,
+        url: &str,
+        dest: &Path,
+        expected: Option<&ExpectedHash>,
+    ) -> LauncherResult<()> {
+        let size = self.download_limited(url, dest, expected).await?;
+        self.emit_progress(DownloadProgress {
+            completed: 1,
+            total: 1,
+            bytes_downloaded: size,
+            bytes_total: size,
+            file_name: file_name_of(dest),
+        });
+        Ok(())
+    }
 
-        let total_bytes = response.content_length();
-        let bytes = response.bytes().await?;
-
-        // Validate SHA-1 before writing (compute on the in-memory buffer)
-        if let Some(expected) = sha1_expected {
-            let mut hasher = Sha1::new();
-            hasher.update(&bytes);
-            let actual = hex::encode(hasher.finalize());
-            if actual != expected {
-                return Err(LauncherError::Sha1Mismatch {
-                    path: dest.to_path_buf(),
-                    expected: expected.to_string(),
-                    actual,
-                });
-            }
-        }
+    /// Download while holding one concurrency permit.
+    async fn download_limited(
+        &self,
+        url: &str,
+        dest: &Path,
+        expected: Option<&ExpectedHash>,
+    ) -> LauncherResult<u64> {
+        let permit = self
+            .limiter
+            .acquire()
+            .await
+            .map_err(|e| LauncherError::message(ErrorText::DownloadLimiterClosed, &[&e]))?;
+        let result = mirrors::with_mirror_fallback(&self.mirrors(), url, |url| async move {
+            download_resumable(&self.client, &url, dest, expected, Some(&self.throttle)).await
+        })
+        .await;
+        self.release(permit);
+        let size = result?;
+        debug!("Downloaded: {} -> {:?}", url, dest);
+        Ok(size)
+    }
 
-        // Write to file inside a block to ensure the handle is dropped immediately
-        {
-            let mut file = tokio::fs::File::create(dest)
-                .await
-                .map_err(|e| LauncherError::Io {
-                    path: dest.to_path_buf(),
-                    source: e,
-                })?;
-            file.write_all(&bytes)
-                .await
-                .map_err(|e| LauncherError::Io {
-                    path: dest.to_path_buf(),
-                    source: e,
-                })?;
-            file.flush().await.map_err(|e| LauncherError::Io {
-                path: dest.to_path_buf(),
-                source: e,
-            })?;
-            // file is dropped here — critical on Windows
+    fn emit_progress(&self, progress: DownloadProgress) {
+        if let Some(observer) = &self.observer {
+            observer(&progress);
         }
-
-        // Emit progress event if app handle is available
-        if let Some(handle) = &self.app_handle {
-            let file_name = dest
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default();
-            let _ = handle.emit(
-                "download-progress",
-                DownloadProgress {
-                    url: url.to_string(),
-                    bytes_downloaded: bytes.len() as u64,
-                    total_bytes,
-                    file_name,
-                },
-            );
+        if let Some(events) = &self.events {
+            events.emit("download-progress", progress);
         }
-
-        debug!("Downloaded: {} -> {:?}", url, dest);
-        Ok(())
     }
 
     // ── Batch concurrent downloads ──────────────────────
 
-    /// Download many files concurrently using `buffer_unordered`.
+    /// Download many files concurrently, bounded by the shared limiter.
     ///
+    /// Emits an aggregate `download-progress` event after every file.
     /// Returns the list of files that failed (if any).
     pub async fn download_batch(
         &self,
         entries: Vec<DownloadEntry>,
     ) -> Vec<(DownloadEntry, LauncherError)> {
+        let total = entries.len();
+        let bytes_total: u64 = entries.iter().filter_map(|e| e.size).sum();
         info!(
             "Starting batch download: {} files, concurrency={}",
-            entries.len(),
-            self.concurrency
+            total,
+            self.max_concurrency()
         );
 
+        let completed = AtomicUsize::new(0);
+        let bytes_downloaded = AtomicU64::new(0);
+
         let results: Vec<_> = stream::iter(entries)
             .map(|entry| {
-                let client = &self;
+                let completed = &completed;
+                let bytes_downloaded = &bytes_downloaded;
                 async move {
-                    let result = client
-                        .download_file(&entry.url, &entry.dest, entry.sha1.as_deref())
+                    let expected = entry.sha1.clone().map(ExpectedHash::Sha1);
+                    let result = self
+                        .download_limited(&entry.url, &entry.dest, expected.as_ref())
                         .await;
-                    (entry, result)
+
+                    let size = match &result {
+                        Ok(size) => *size,
+                        Err(_) => 0,
+                    };
+                    let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                    let bytes = bytes_downloaded.fetch_add(size, Ordering::SeqCst) + size;
+                    self.emit_progress(DownloadProgress {
+                        completed: done,
+                        total,
+                        bytes_downloaded: bytes,
+                        bytes_total,
+                        file_name: file_name_of(&entry.dest),
+                    });
+
+                    (entry, result.map(|_| ()))
                 }
             })
-            .buffer_unordered(self.concurrency)
+            // The semaphore is the real bound; this only caps queued futures.
+            .buffer_unordered(MAX_CONCURRENT_DOWNLOADS_CAP)
             .collect()
             .await;
 
@@ -191,6 +321,152 @@ impl Downloader {
         let mut hasher = Sha1::new();
         hasher.update(&bytes);
         let actual = hex::encode(hasher.finalize());
-        Ok(actual == expected)
+        Ok(actual.eq_ignore_ascii_case(expected))
+    }
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Subtract up to `max` from `counter` without going below zero; returns
+/// how much was taken.
+fn take_up_to(counter: &AtomicUsize, max: usize) -> usize {
+    counter
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+            (pending > 0).then(|| pending - pending.min(max))
+        })
+        .map_or(0, |pending| pending.min(max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{response, serve};
+
+    /// Serves a fixed body after a short delay and records the peak number
+    /// of requests handled at the same time.
+    async fn serve_slowly() -> (String, Arc<AtomicUsize>) {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let peak_out = peak.clone();
+
+        let base = serve(move |_| {
+            let (active, peak) = (active.clone(), peak.clone());
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                response("200 OK", &[], b"ok")
+            }
+        })
+        .await;
+
+        (base, peak_out)
+    }
+
+    #[tokio::test]
+    async fn batch_never_exceeds_configured_concurrency() {
+        let (base, peak) = serve_slowly().await;
+        let dir = std::env::temp_dir().join(format!("downloader-limit-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let downloader = Downloader::new(None).with_concurrency(3);
+        let entries = (0..12)
+            .map(|i| DownloadEntry {
+                url: format!("{}/file-{}", base, i),
+                dest: dir.join(format!("file-{}", i)),
+                sha1: None,
+                size: Some(2),
+            })
+            .collect();
+
+        let failures = downloader.download_batch(entries).await;
+
+        assert!(failures.is_empty());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(peak.load(Ordering::SeqCst) >= 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn max_concurrency_can_change_at_runtime() {
+        let downloader = Downloader::new(None).with_concurrency(4);
+        assert_eq!(downloader.max_concurrency(), 4);
+        assert_eq!(downloader.limiter.available_permits(), 4);
+
+        downloader.set_max_concurrency(10);
+        assert_eq!(downloader.limiter.available_permits(), 10);
+
+        downloader.set_max_concurrency(2);
+        assert_eq!(downloader.max_concurrency(), 2);
+        assert_eq!(downloader.limiter.available_permits(), 2);
+
+        downloader.set_max_concurrency(0);
+        assert_eq!(downloader.max_concurrency(), 1);
+    }
+
+    #[tokio::test]
+    async fn shrinking_while_busy_then_growing_keeps_the_new_limit() {
+        let downloader = Downloader::new(None).with_concurrency(4);
+        let mut busy = Vec::new();
+        for _ in 0..3 {
+            busy.push(downloader.limiter.acquire().await.unwrap());
+        }
+
+        // One idle permit goes now, two more once their downloads finish.
+        downloader.set_max_concurrency(1);
+        assert_eq!(downloader.limiter.available_permits(), 0);
+        downloader.set_max_concurrency(4);
+        for permit in busy.drain(..) {
+            downloader.release(permit);
+        }
+        assert_eq!(downloader.limiter.available_permits(), 4);
+
+        for _ in 0..4 {
+            busy.push(downloader.limiter.acquire().await.unwrap());
+        }
+        downloader.set_max_concurrency(2);
+        for permit in busy.drain(..) {
+            downloader.release(permit);
+        }
+        assert_eq!(downloader.limiter.available_permits(), 2);
+    }
+
+    #[test]
+    fn views_share_the_bandwidth_cap() {
+        let downloader = Downloader::new(None).with_max_bytes_per_sec(Some(1_000));
+        let view = downloader.view(None);
+        assert!(Arc::ptr_eq(&downloader.throttle(), &view.throttle()));
+        assert!(!Arc::ptr_eq(
+            &downloader.throttle(),
+            &Downloader::new(None).throttle()
+        ));
+    }
+
+    #[tokio::test]
+    async fn views_download_through_the_mirrors_set_later() {
+        let base = serve(|_| async { response("200 OK", &[], b"jar") }).await;
+        let dir = std::env::temp_dir().join(format!("downloader-mirror-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let downloader = Downloader::new(None);
+        let view = downloader.view(None);
+        downloader.set_mirrors(DownloadMirrors {
+            libraries: Some(base),
+            ..DownloadMirrors::default()
+        });
+
+        let dest = dir.join("x.jar");
+        view.download_file("https://libraries.minecraft.net/x.jar", &dest, None)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"jar");
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
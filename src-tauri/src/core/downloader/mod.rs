@@ -1,6 +1,9 @@
 pub mod client;
+pub mod resume;
+pub mod throttle;
 
 pub use client::DownloadEntry;
-#[allow(unused_imports)]
 pub use client::DownloadProgress;
 pub use client::Downloader;
+pub use client::{default_max_concurrent_downloads, MAX_CONCURRENT_DOWNLOADS_CAP};
+pub use resume::{download_resumable_with_progress, ExpectedHash};
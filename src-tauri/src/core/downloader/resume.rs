@@ -0,0 +1,587 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tracing::{debug, warn};
+
+use super::throttle::BandwidthThrottle;
+use crate::core::error::{LauncherError, LauncherResult};
+
+/// How many times a failed or broken transfer is retried before giving up.
+const RESUME_RETRIES: u32 = 3;
+/// Persist the byte checkpoint roughly every MiB written.
+const CHECKPOINT_INTERVAL_BYTES: u64 = 1024 * 1024;
+
+/// Byte progress callback: `(downloaded_bytes, total_bytes)`, where the
+/// total is known only when the server sends a `Content-Length`.
+pub type ByteProgress<'a> = &'a (dyn Fn(u64, Option<u64>) + Send + Sync);
+
+/// Hash a finished download must match before it replaces `dest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedHash {
+    Sha1(String),
+    Sha256(String),
+    Sha512(String),
+}
+
+impl ExpectedHash {
+    /// Hash `path` with the matching algorithm and compare.
+    pub async fn verify(&self, path: &Path) -> LauncherResult<()> {
+        let expected = self.clone();
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || expected.verify_blocking(&path))
+            .await
+            .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))?
+    }
+
+    fn verify_blocking(&self, path: &Path) -> LauncherResult<()> {
+        let actual = match self {
+            ExpectedHash::Sha1(_) => hash_file::<Sha1>(path)?,
+            ExpectedHash::Sha256(_) => hash_file::<Sha256>(path)?,
+            ExpectedHash::Sha512(_) => hash_file::<Sha512>(path)?,
+        };
+
+        match self {
+            ExpectedHash::Sha1(expected) if !actual.eq_ignore_ascii_case(expected) => {
+                Err(LauncherError::Sha1Mismatch {
+                    path: path.to_path_buf(),
+                    expected: expected.clone(),
+                    actual,
+                })
+            }
+            ExpectedHash::Sha256(expected) if !actual.eq_ignore_ascii_case(expected) => {
+                Err(LauncherError::Sha256Mismatch {
+                    path: path.to_path_buf(),
+                    expected: expected.clone(),
+                    actual,
+                })
+            }
+            ExpectedHash::Sha512(expected) if !actual.eq_ignore_ascii_case(expected) => {
+                Err(LauncherError::Sha512Mismatch {
+                    path: path.to_path_buf(),
+                    expected: expected.clone(),
+                    actual,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DownloadCheckpoint {
+    downloaded_bytes: u64,
+}
+
+/// `<dest>.part` — bytes received so far.
+pub fn part_path(dest: &Path) -> PathBuf {
+    append_extension(dest, "part")
+}
+
+/// `<dest>.part.checkpoint.json` — last byte offset known to be on disk.
+pub fn checkpoint_path(dest: &Path) -> PathBuf {
+    append_extension(dest, "part.checkpoint.json")
+}
+
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+/// Download `url` into `dest`, resuming from a previous `.part` file if one
+/// was left behind by an interrupted transfer.
+///
+/// The partial file is only trusted up to its persisted checkpoint. If the
+/// resumed result fails hash validation the partial data is discarded and
+/// the file is fetched again from scratch. Returns the final file size.
+pub async fn download_resumable(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    expected: Option<&ExpectedHash>,
+    throttle: Option<&BandwidthThrottle>,
+) -> LauncherResult<u64> {
+    download_resumable_with_progress(client, url, dest, expected, None, throttle).await
+}
+
+/// `download_resumable`, reporting bytes written to `on_progress` after
+/// every received chunk.
+pub async fn download_resumable_with_progress(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    expected: Option<&ExpectedHash>,
+    on_progress: Option<ByteProgress<'_>>,
+    throttle: Option<&BandwidthThrottle>,
+) -> LauncherResult<u64> {
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+    }
+
+    let part = part_path(dest);
+    let checkpoint = checkpoint_path(dest);
+
+    let resumed_from = resume_offset(&part, &checkpoint).await;
+    let mut size =
+        fetch_with_retries(client, url, &part, &checkpoint, on_progress, throttle).await?;
+
+    if let Some(expected) = expected {
+        if let Err(err) = expected.verify(&part).await {
+            discard_partial(&part, &checkpoint).await;
+            if resumed_from == 0 {
+                return Err(err);
+            }
+
+            warn!(
+                "Resumed download of {} failed validation ({}); re-downloading from scratch",
+                url, err
+            );
+            size =
+                fetch_with_retries(client, url, &part, &checkpoint, on_progress, throttle).await?;
+            if let Err(err) = expected.verify(&part).await {
+                discard_partial(&part, &checkpoint).await;
+                return Err(err);
+            }
+        }
+    }
+
+    tokio::fs::rename(&part, dest)
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: dest.to_path_buf(),
+            source: e,
+        })?;
+    let _ = tokio::fs::remove_file(&checkpoint).await;
+
+    Ok(size)
+}
+
+async fn fetch_with_retries(
+    client: &Client,
+    url: &str,
+    part: &Path,
+    checkpoint: &Path,
+    on_progress: Option<ByteProgress<'_>>,
+    throttle: Option<&BandwidthThrottle>,
+) -> LauncherResult<u64> {
+    let mut attempt = 0;
+    loop {
+        let offset = resume_offset(part, checkpoint).await;
+        match fetch_into_part(client, url, part, checkpoint, offset, on_progress, throttle).await {
+            Ok(size) => return Ok(size),
+            // Retry transfers that broke mid-body, and requests that could
+            // not connect or timed out before a byte arrived; other errors
+            // (bad statuses, disk) surface immediately.
+            Err(LauncherError::Http(err))
+                if attempt < RESUME_RETRIES
+                    && (err.is_connect()
+                        || err.is_timeout()
+                        || resume_offset(part, checkpoint).await > offset) =>
+            {
+                attempt += 1;
+                warn!(
+                    "Transfer of {} failed ({}); retrying (attempt {}/{})",
+                    url, err, attempt, RESUME_RETRIES
+                );
+                tokio::time::sleep(Duration::from_millis(2_u64.pow(attempt) * 250)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Stream the response body into `part`, appending from `offset` when the
+/// server honours the `Range` request.
+async fn fetch_into_part(
+    client: &Client,
+    url: &str,
+    part: &Path,
+    checkpoint: &Path,
+    mut offset: u64,
+    on_progress: Option<ByteProgress<'_>>,
+    throttle: Option<&BandwidthThrottle>,
+) -> LauncherResult<u64> {
+    let mut request = client.get(url);
+    if offset > 0 {
+        debug!("Resuming {} from byte {}", url, offset);
+        request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+    }
+    let response = request.send().await?;
+
+    let status = response.status();
+    if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+        // Stale checkpoint: start over on the next attempt.
+        discard_partial(part, checkpoint).await;
+        return Box::pin(fetch_into_part(
+            client,
+            url,
+            part,
+            checkpoint,
+            0,
+            on_progress,
+            throttle,
+        ))
+        .await;
+    }
+    if !status.is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url: url.to_string(),
+            status: status.as_u16(),
+        });
+    }
+    if status != StatusCode::PARTIAL_CONTENT {
+        // Server ignored the range: the body is the whole file.
+        offset = 0;
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(part)
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: part.to_path_buf(),
+            source: e,
+        })?;
+    file.set_len(offset).await.map_err(|e| LauncherError::Io {
+        path: part.to_path_buf(),
+        source: e,
+    })?;
+    file.seek(std::io::SeekFrom::Start(offset))
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: part.to_path_buf(),
+            source: e,
+        })?;
+
+    let total = response.content_length().map(|length| length + offset);
+    let mut downloaded = offset;
+    let mut last_checkpoint = offset;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                let _ = file.flush().await;
+                write_checkpoint(checkpoint, downloaded).await?;
+                return Err(err.into());
+            }
+        };
+
+        if let Some(throttle) = throttle {
+            throttle.throttle(chunk.len()).await;
+        }
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| LauncherError::Io {
+                path: part.to_path_buf(),
+                source: e,
+            })?;
+        downloaded += chunk.len() as u64;
+        if let Some(on_progress) = on_progress {
+            on_progress(downloaded, total);
+        }
+
+        if downloaded - last_checkpoint >= CHECKPOINT_INTERVAL_BYTES {
+            file.flush().await.map_err(|e| LauncherError::Io {
+                path: part.to_path_buf(),
+                source: e,
+            })?;
+            write_checkpoint(checkpoint, downloaded).await?;
+            last_checkpoint = downloaded;
+        }
+    }
+
+    file.flush().await.map_err(|e| LauncherError::Io {
+        path: part.to_path_buf(),
+        source: e,
+    })?;
+    // file is dropped here — critical on Windows before the final rename
+    drop(file);
+
+    Ok(downloaded)
+}
+
+/// Offset to resume from: the checkpoint, if the `.part` file really holds
+/// at least that many bytes. Anything else restarts the transfer.
+async fn resume_offset(part: &Path, checkpoint: &Path) -> u64 {
+    let Ok(meta) = tokio::fs::metadata(part).await else {
+        return 0;
+    };
+    let Ok(bytes) = tokio::fs::read(checkpoint).await else {
+        return 0;
+    };
+    match serde_json::from_slice::<DownloadCheckpoint>(&bytes) {
+        Ok(cp) if cp.downloaded_bytes <= meta.len() => cp.downloaded_bytes,
+        _ => 0,
+    }
+}
+
+async fn write_checkpoint(checkpoint: &Path, downloaded_bytes: u64) -> LauncherResult<()> {
+    let payload = serde_json::to_vec(&DownloadCheckpoint { downloaded_bytes })?;
+    tokio::fs::write(checkpoint, payload)
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: checkpoint.to_path_buf(),
+            source: e,
+        })
+}
+
+async fn discard_partial(part: &Path, checkpoint: &Path) {
+    let _ = tokio::fs::remove_file(part).await;
+    let _ = tokio::fs::remove_file(checkpoint).await;
+}
+
+/// Hex digest of the file at `path`, read in chunks.
+pub fn hash_file<D: Digest>(path: &Path) -> LauncherResult<String> {
+    let mut file = std::fs::File::open(path).map_err(|e| LauncherError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let mut hasher = D::new();
+    let mut buffer = [0_u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| LauncherError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{response, serve, temp_dir};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Minimal HTTP/1.1 server that honours `Range: bytes=N-` and records the
+    /// range header of every request it receives.
+    async fn serve_range(body: &'static [u8]) -> (String, Arc<Mutex<Vec<Option<u64>>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_in_handler = seen.clone();
+
+        let base = serve(move |request| {
+            let range = request
+                .header("range")
+                .and_then(|v| v.strip_prefix("bytes="))
+                .and_then(|v| v.trim_end_matches('-').parse::<u64>().ok());
+            seen_in_handler.lock().unwrap().push(range);
+
+            let start = range.unwrap_or(0) as usize;
+            let slice = &body[start.min(body.len())..];
+            let reply = match range {
+                Some(_) => response(
+                    "206 Partial Content",
+                    &[(
+                        "Content-Range",
+                        format!("bytes {}-{}/{}", start, body.len() - 1, body.len()),
+                    )],
+                    slice,
+                ),
+                None => response("200 OK", &[], slice),
+            };
+            async move { reply }
+        })
+        .await;
+
+        (format!("{base}/file.bin"), seen)
+    }
+
+    fn sha1_hex(data: &[u8]) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(data);
+        hex::encode(hasher.finalize())
+    }
+
+    #[tokio::test]
+    async fn interrupted_download_resumes_from_checkpoint() {
+        const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+        let (url, seen) = serve_range(BODY).await;
+        let dir = temp_dir("downloader-resume");
+        let dest = dir.join("lib.jar");
+
+        // Simulate a transfer that died after 10 bytes were safely written.
+        std::fs::write(part_path(&dest), &BODY[..10]).unwrap();
+        std::fs::write(checkpoint_path(&dest), br#"{"downloaded_bytes":10}"#).unwrap();
+
+        let client = Client::new();
+        let size = download_resumable(
+            &client,
+            &url,
+            &dest,
+            Some(&ExpectedHash::Sha1(sha1_hex(BODY))),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(size, BODY.len() as u64);
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert_eq!(seen.lock().unwrap().as_slice(), &[Some(10)]);
+        assert!(!part_path(&dest).exists());
+        assert!(!checkpoint_path(&dest).exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn timed_out_requests_are_retried_from_zero_bytes() {
+        const BODY: &[u8] = b"retried body";
+        let requests = Arc::new(AtomicUsize::new(0));
+        let base = serve(move |_| {
+            let first = requests.fetch_add(1, Ordering::SeqCst) == 0;
+            async move {
+                // The first request gets no answer until the client gave up.
+                if first {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+                response("200 OK", &[], BODY)
+            }
+        })
+        .await;
+        let url = format!("{base}/file.bin");
+        let dir = temp_dir("downloader-resume-timeout");
+        let dest = dir.join("lib.jar");
+
+        let client = Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        download_resumable(
+            &client,
+            &url,
+            &dest,
+            Some(&ExpectedHash::Sha1(sha1_hex(BODY))),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn corrupt_partial_falls_back_to_clean_download() {
+        const BODY: &[u8] = b"the quick brown fox jumps over the lazy dog";
+        let (url, seen) = serve_range(BODY).await;
+        let dir = temp_dir("downloader-resume-corrupt");
+        let dest = dir.join("asset.bin");
+
+        std::fs::write(part_path(&dest), b"XXXXXXXX").unwrap();
+        std::fs::write(checkpoint_path(&dest), br#"{"downloaded_bytes":8}"#).unwrap();
+
+        let client = Client::new();
+        download_resumable(
+            &client,
+            &url,
+            &dest,
+            Some(&ExpectedHash::Sha1(sha1_hex(BODY))),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert_eq!(seen.lock().unwrap().as_slice(), &[Some(8), None]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn progress_reports_increasing_byte_counts() {
+        static BODY: [u8; 256 * 1024] = [7_u8; 256 * 1024];
+        let (url, _) = serve_range(&BODY).await;
+        let dir = temp_dir("downloader-progress");
+        let dest = dir.join("runtime.zip");
+
+        let reports = Mutex::new(Vec::new());
+        let on_progress = |downloaded: u64, total: Option<u64>| {
+            reports.lock().unwrap().push((downloaded, total));
+        };
+        download_resumable_with_progress(
+            &Client::new(),
+            &url,
+            &dest,
+            Some(&ExpectedHash::Sha1(sha1_hex(&BODY))),
+            Some(&on_progress),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let reports = reports.into_inner().unwrap();
+        assert!(!reports.is_empty());
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(
+            reports.last(),
+            Some(&(BODY.len() as u64, Some(BODY.len() as u64)))
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn resume_offset_ignores_checkpoint_beyond_part_length() {
+        let dir = temp_dir("downloader-resume-offset");
+        let dest = dir.join("x.bin");
+        std::fs::write(part_path(&dest), b"abc").unwrap();
+        std::fs::write(checkpoint_path(&dest), br#"{"downloaded_bytes":99}"#).unwrap();
+
+        assert_eq!(
+            resume_offset(&part_path(&dest), &checkpoint_path(&dest)).await,
+            0
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn expected_hashes_ignore_case_and_report_their_algorithm() {
+        let dir = temp_dir("downloader-expected-hash");
+        let path = dir.join("x.bin");
+        std::fs::write(&path, b"hash me").unwrap();
+
+        let sha1 = sha1_hex(b"hash me");
+        ExpectedHash::Sha1(sha1.to_uppercase())
+            .verify(&path)
+            .await
+            .unwrap();
+        let sha256 = hash_file::<Sha256>(&path).unwrap();
+        ExpectedHash::Sha256(sha256.to_uppercase())
+            .verify(&path)
+            .await
+            .unwrap();
+
+        let err = ExpectedHash::Sha256("00".into())
+            .verify(&path)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, LauncherError::Sha256Mismatch { actual, .. } if *actual == sha256),
+            "{err:?}"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
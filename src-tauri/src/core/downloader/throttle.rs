@@ -0,0 +1,117 @@
+// ─── Bandwidth Throttle ───
+// One token bucket shared by every download stream of a `Downloader` (its
+// views and the Java runtime installer included), so the
+// `max_download_bytes_per_sec` cap holds for the launcher as a whole and
+// not per connection. Streams take tokens for each received chunk and sleep
+// when the bucket runs dry.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The `max_download_bytes_per_sec` cap; without a limit it lets everything
+/// through.
+#[derive(Debug, Default)]
+pub struct BandwidthThrottle {
+    bucket: Mutex<Option<TokenBucket>>,
+}
+
+impl BandwidthThrottle {
+    pub fn new(limit: Option<u64>) -> Self {
+        let throttle = Self::default();
+        throttle.set_max_bytes_per_sec(limit);
+        throttle
+    }
+
+    /// Cap the combined download rate; `None` or `Some(0)` removes the cap.
+    pub fn set_max_bytes_per_sec(&self, limit: Option<u64>) {
+        let bucket = limit
+            .filter(|rate| *rate > 0)
+            .map(|rate| TokenBucket::new(rate, Instant::now()));
+        *self.bucket.lock().unwrap_or_else(|e| e.into_inner()) = bucket;
+    }
+
+    /// Account for `bytes` just received, waiting as long as the cap requires.
+    pub async fn throttle(&self, bytes: usize) {
+        let delay = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            match bucket.as_mut() {
+                Some(bucket) => bucket.reserve(bytes as u64, Instant::now()),
+                None => return,
+            }
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Holds up to one second of tokens. A reservation may drive the balance
+/// negative; the caller then waits until the refill covers the debt.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.rate as f64).min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    /// Take `bytes` tokens and return how long to wait before using them.
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_limits_throughput_to_the_configured_rate() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(1_000, start);
+        let mut now = start;
+
+        // 10 KB in 100-byte chunks, each sent once its wait has passed.
+        for _ in 0..100 {
+            now += bucket.reserve(100, now);
+        }
+
+        // The first second of tokens is a free burst; the rest runs at 1 KB/s.
+        let elapsed = now.duration_since(start).as_secs_f64();
+        assert!((8.9..=9.1).contains(&elapsed), "took {elapsed}s");
+    }
+
+    #[test]
+    fn idle_time_refills_at_most_one_second_of_tokens() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(1_000, start);
+        assert_eq!(bucket.reserve(1_000, start), Duration::ZERO);
+        assert_eq!(bucket.reserve(500, start), Duration::from_millis(500),);
+
+        // A long pause does not bank more than the one-second burst.
+        let later = start + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(1_000, later), Duration::ZERO);
+        assert!(bucket.reserve(1, later) > Duration::ZERO);
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::core::i18n::{self, ErrorCode};
+use crate::core::launch::JvmArgConflict;
+
 /// Central error type for the entire launcher backend.
 /// Every module returns `Result<T, LauncherError>`.
 #[derive(Debug, Error)]
@@ -12,6 +15,9 @@ pub enum LauncherError {
         source: std::io::Error,
     },
 
+    #[error("Not enough disk space: {needed} bytes needed, {available} available")]
+    InsufficientDiskSpace { needed: u64, available: u64 },
+
     // ── Network ─────────────────────────────────────────
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
@@ -19,6 +25,10 @@ pub enum LauncherError {
     #[error("Download failed for {url}: HTTP {status}")]
     DownloadFailed { url: String, status: u16 },
 
+    /// `hashes` are the asset objects no host could serve.
+    #[error("{} asset objects could not be downloaded from any host: {}", hashes.len(), preview_hashes(hashes))]
+    AssetsUnavailable { hashes: Vec<String> },
+
     // ── Integrity ───────────────────────────────────────
     #[error("SHA-1 mismatch for {path:?}: expected {expected}, got {actual}")]
     Sha1Mismatch {
@@ -27,6 +37,20 @@ pub enum LauncherError {
         actual: String,
     },
 
+    #[error("SHA-256 mismatch for {path:?}: expected {expected}, got {actual}")]
+    Sha256Mismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("SHA-512 mismatch for {path:?}: expected {expected}, got {actual}")]
+    Sha512Mismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
     // ── Maven ───────────────────────────────────────────
     #[error("Invalid Maven coordinate: {0}")]
     InvalidMavenCoordinate(String),
@@ -42,6 +66,9 @@ pub enum LauncherError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("NBT error: {0}")]
+    Nbt(#[from] quartz_nbt::io::NbtIoError),
+
     // ── Instance ────────────────────────────────────────
     #[error("Instance not found: {0}")]
     InstanceNotFound(String),
@@ -49,6 +76,22 @@ pub enum LauncherError {
     #[error("Instance already exists: {0}")]
     InstanceAlreadyExists(String),
 
+    #[error("Instance is already running: {0}")]
+    InstanceAlreadyRunning(String),
+
+    #[error("The {hook} hook exited with status {status}")]
+    HookFailed { hook: String, status: String },
+
+    #[error("Instance {id} is not ready to launch (current state: {state})")]
+    InstanceNotReady { id: String, state: String },
+
+    #[error("Instance metadata is incomplete: {0}")]
+    InstanceIncomplete(String),
+
+    // ── Accounts ────────────────────────────────────────
+    #[error("Account not found: {0}")]
+    AccountNotFound(String),
+
     // ── Java ────────────────────────────────────────────
     #[error("Java not found for major version {0}")]
     JavaNotFound(u32),
@@ -56,6 +99,20 @@ pub enum LauncherError {
     #[error("Java execution failed: {0}")]
     JavaExecution(String),
 
+    #[error("Invalid JVM arguments: {}", join_conflicts(.0))]
+    InvalidJvmArgs(Vec<JvmArgConflict>),
+
+    #[error("Could not resolve a Java runtime: {0}")]
+    JavaResolutionFailed(String),
+
+    // ── Launch ──────────────────────────────────────────
+    /// `failures` holds the preflight check labels (`MissingJava`, ...).
+    #[error("Preflight checks failed: {}", failures.join(", "))]
+    PreflightFailed { failures: Vec<String> },
+
+    #[error("Classpath is empty")]
+    ClasspathEmpty,
+
     // ── Loader ──────────────────────────────────────────
     #[error("Loader error: {0}")]
     Loader(String),
@@ -63,15 +120,44 @@ pub enum LauncherError {
     #[error("Loader API unreachable: {0}")]
     LoaderApi(String),
 
+    #[error("Loader processor {name} failed:\n{output_tail}")]
+    LoaderProcessorFailed { name: String, output_tail: String },
+
     // ── Archive ─────────────────────────────────────────
     #[error("Zip extraction error: {0}")]
     Zip(#[from] zip::result::ZipError),
 
+    // ── Control flow ────────────────────────────────────
+    #[error("Operation cancelled")]
+    Cancelled,
+
     // ── Generic ─────────────────────────────────────────
+    /// A failure without a variant of its own, worded by the i18n catalog.
+    #[error("{0}")]
+    Message(i18n::Message),
+
     #[error("{0}")]
     Other(String),
 }
 
+/// First few hashes for the message; the full list is serialized apart.
+fn preview_hashes(hashes: &[String]) -> String {
+    const PREVIEW: usize = 5;
+    let mut preview = hashes[..hashes.len().min(PREVIEW)].join(", ");
+    if hashes.len() > PREVIEW {
+        preview.push_str(", ...");
+    }
+    preview
+}
+
+fn join_conflicts(conflicts: &[JvmArgConflict]) -> String {
+    conflicts
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Convenience alias used throughout the crate.
 pub type LauncherResult<T> = Result<T, LauncherError>;
 
@@ -85,7 +171,9 @@ impl From<std::io::Error> for LauncherError {
 }
 
 // ── Serialization for Tauri IPC ─────────────────────────
-// Tauri commands require the error type to implement `Serialize`.
+// Tauri commands require the error type to implement `Serialize`. `kind` is
+// a stable per-variant discriminant the frontend can branch on; `category`
+// groups related variants.
 impl serde::Serialize for LauncherError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -93,35 +181,101 @@ impl serde::Serialize for LauncherError {
     {
         use serde::ser::SerializeMap;
 
-        let mut map = serializer.serialize_map(Some(5))?;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.code().name())?;
         map.serialize_entry("message", &self.to_string())?;
         map.serialize_entry("i18n_key", self.i18n_key())?;
+        map.serialize_entry("title", self.title(i18n::Locale::default()))?;
         map.serialize_entry("severity", self.severity())?;
         map.serialize_entry("recoverable", &self.is_recoverable())?;
-        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("category", self.category())?;
+        match self {
+            LauncherError::InvalidJvmArgs(conflicts) => {
+                map.serialize_entry("conflicts", conflicts)?;
+            }
+            LauncherError::PreflightFailed { failures } => {
+                map.serialize_entry("failures", failures)?;
+            }
+            LauncherError::AssetsUnavailable { hashes } => {
+                map.serialize_entry("hashes", hashes)?;
+            }
+            LauncherError::Message(message) => {
+                map.serialize_entry("args", &message.args)?;
+            }
+            LauncherError::InsufficientDiskSpace { needed, available } => {
+                map.serialize_entry("needed", needed)?;
+                map.serialize_entry("available", available)?;
+            }
+            _ => {}
+        }
         map.end()
     }
 }
 
 impl LauncherError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LauncherError::Io { .. } => ErrorCode::Io,
+            LauncherError::InsufficientDiskSpace { .. } => ErrorCode::InsufficientDiskSpace,
+            LauncherError::Http(_) => ErrorCode::Http,
+            LauncherError::DownloadFailed { .. } => ErrorCode::DownloadFailed,
+            LauncherError::AssetsUnavailable { .. } => ErrorCode::AssetsUnavailable,
+            LauncherError::Sha1Mismatch { .. } => ErrorCode::Sha1Mismatch,
+            LauncherError::Sha256Mismatch { .. } => ErrorCode::Sha256Mismatch,
+            LauncherError::Sha512Mismatch { .. } => ErrorCode::Sha512Mismatch,
+            LauncherError::InvalidMavenCoordinate(_) => ErrorCode::InvalidMavenCoordinate,
+            LauncherError::PomParse(_) => ErrorCode::PomParse,
+            LauncherError::Xml(_) => ErrorCode::Xml,
+            LauncherError::Json(_) => ErrorCode::Json,
+            LauncherError::Nbt(_) => ErrorCode::Nbt,
+            LauncherError::InstanceNotFound(_) => ErrorCode::InstanceNotFound,
+            LauncherError::InstanceAlreadyExists(_) => ErrorCode::InstanceAlreadyExists,
+            LauncherError::InstanceAlreadyRunning(_) => ErrorCode::InstanceAlreadyRunning,
+            LauncherError::HookFailed { .. } => ErrorCode::HookFailed,
+            LauncherError::InstanceNotReady { .. } => ErrorCode::InstanceNotReady,
+            LauncherError::InstanceIncomplete(_) => ErrorCode::InstanceIncomplete,
+            LauncherError::AccountNotFound(_) => ErrorCode::AccountNotFound,
+            LauncherError::JavaNotFound(_) => ErrorCode::JavaNotFound,
+            LauncherError::JavaExecution(_) => ErrorCode::JavaExecution,
+            LauncherError::InvalidJvmArgs(_) => ErrorCode::InvalidJvmArgs,
+            LauncherError::JavaResolutionFailed(_) => ErrorCode::JavaResolutionFailed,
+            LauncherError::PreflightFailed { .. } => ErrorCode::PreflightFailed,
+            LauncherError::ClasspathEmpty => ErrorCode::ClasspathEmpty,
+            LauncherError::Loader(_) => ErrorCode::Loader,
+            LauncherError::LoaderApi(_) => ErrorCode::LoaderApi,
+            LauncherError::LoaderProcessorFailed { .. } => ErrorCode::LoaderProcessorFailed,
+            LauncherError::Zip(_) => ErrorCode::Zip,
+            LauncherError::Cancelled => ErrorCode::Cancelled,
+            LauncherError::Message(_) => ErrorCode::Message,
+            LauncherError::Other(_) => ErrorCode::Other,
+        }
+    }
+
+    /// Catalog failure from `text` and its `{}` arguments.
+    pub fn message(text: i18n::ErrorText, args: &[&dyn std::fmt::Display]) -> Self {
+        LauncherError::Message(i18n::Message::new(text, args))
+    }
+
     pub fn i18n_key(&self) -> &'static str {
         match self {
-            LauncherError::Io { .. } => "error.io",
-            LauncherError::Http(_) => "error.http",
-            LauncherError::DownloadFailed { .. } => "error.download_failed",
-            LauncherError::Sha1Mismatch { .. } => "error.sha1_mismatch",
-            LauncherError::InvalidMavenCoordinate(_) => "error.invalid_maven_coordinate",
-            LauncherError::PomParse(_) => "error.pom_parse",
-            LauncherError::Xml(_) => "error.xml",
-            LauncherError::Json(_) => "error.json",
-            LauncherError::InstanceNotFound(_) => "error.instance_not_found",
-            LauncherError::InstanceAlreadyExists(_) => "error.instance_already_exists",
-            LauncherError::JavaNotFound(_) => "error.java_not_found",
-            LauncherError::JavaExecution(_) => "error.java_execution",
-            LauncherError::Loader(_) => "error.loader",
-            LauncherError::LoaderApi(_) => "error.loader_api",
-            LauncherError::Zip(_) => "error.zip",
-            LauncherError::Other(_) => "error.other",
+            LauncherError::Message(message) => message.text.key(),
+            _ => self.code().key(),
+        }
+    }
+
+    /// Short localized summary for logs and as a fallback when the
+    /// frontend has no translation for the key.
+    pub fn title(&self, locale: i18n::Locale) -> &'static str {
+        self.code().text(locale)
+    }
+
+    /// Full message in `locale`: catalog texts render in it, other variants
+    /// keep their detail after the localized title.
+    pub fn localized(&self, locale: i18n::Locale) -> String {
+        match self {
+            LauncherError::Message(message) => message.render(locale),
+            LauncherError::Other(text) => text.clone(),
+            _ => format!("{}: {self}", self.title(locale)),
         }
     }
 
@@ -133,20 +287,35 @@ impl LauncherError {
         }
     }
 
-    pub fn kind(&self) -> &'static str {
+    pub fn category(&self) -> &'static str {
         match self {
-            LauncherError::Io { .. } => "io",
-            LauncherError::Http(_) | LauncherError::DownloadFailed { .. } => "network",
-            LauncherError::Sha1Mismatch { .. } => "integrity",
+            LauncherError::Io { .. } | LauncherError::InsufficientDiskSpace { .. } => "io",
+            LauncherError::Http(_)
+            | LauncherError::DownloadFailed { .. }
+            | LauncherError::AssetsUnavailable { .. } => "network",
+            LauncherError::Sha1Mismatch { .. }
+            | LauncherError::Sha256Mismatch { .. }
+            | LauncherError::Sha512Mismatch { .. } => "integrity",
             LauncherError::InvalidMavenCoordinate(_) | LauncherError::PomParse(_) => "maven",
-            LauncherError::Xml(_) | LauncherError::Json(_) => "parsing",
-            LauncherError::InstanceNotFound(_) | LauncherError::InstanceAlreadyExists(_) => {
-                "instance"
-            }
-            LauncherError::JavaNotFound(_) | LauncherError::JavaExecution(_) => "java",
-            LauncherError::Loader(_) | LauncherError::LoaderApi(_) => "loader",
+            LauncherError::Xml(_) | LauncherError::Json(_) | LauncherError::Nbt(_) => "parsing",
+            LauncherError::InstanceNotFound(_)
+            | LauncherError::InstanceAlreadyExists(_)
+            | LauncherError::InstanceAlreadyRunning(_)
+            | LauncherError::HookFailed { .. }
+            | LauncherError::InstanceNotReady { .. }
+            | LauncherError::InstanceIncomplete(_) => "instance",
+            LauncherError::AccountNotFound(_) => "account",
+            LauncherError::JavaNotFound(_)
+            | LauncherError::JavaExecution(_)
+            | LauncherError::InvalidJvmArgs(_)
+            | LauncherError::JavaResolutionFailed(_) => "java",
+            LauncherError::PreflightFailed { .. } | LauncherError::ClasspathEmpty => "launch",
+            LauncherError::Loader(_)
+            | LauncherError::LoaderApi(_)
+            | LauncherError::LoaderProcessorFailed { .. } => "loader",
             LauncherError::Zip(_) => "archive",
-            LauncherError::Other(_) => "generic",
+            LauncherError::Cancelled => "cancelled",
+            LauncherError::Message(_) | LauncherError::Other(_) => "generic",
         }
     }
 
@@ -155,9 +324,182 @@ impl LauncherError {
             self,
             LauncherError::Http(_)
                 | LauncherError::DownloadFailed { .. }
+                | LauncherError::AssetsUnavailable { .. }
                 | LauncherError::LoaderApi(_)
                 | LauncherError::Io { .. }
                 | LauncherError::JavaNotFound(_)
+                | LauncherError::Cancelled
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn one_of_each() -> Vec<LauncherError> {
+        let path = PathBuf::from("x");
+        vec![
+            LauncherError::Io {
+                path: path.clone(),
+                source: std::io::Error::other("io"),
+            },
+            LauncherError::Http(
+                reqwest::Client::new()
+                    .get("no es una url")
+                    .build()
+                    .unwrap_err(),
+            ),
+            LauncherError::DownloadFailed {
+                url: "https://example.com".into(),
+                status: 404,
+            },
+            LauncherError::AssetsUnavailable {
+                hashes: vec!["ab".into()],
+            },
+            LauncherError::Sha1Mismatch {
+                path: path.clone(),
+                expected: "a".into(),
+                actual: "b".into(),
+            },
+            LauncherError::Sha256Mismatch {
+                path: path.clone(),
+                expected: "a".into(),
+                actual: "b".into(),
+            },
+            LauncherError::Sha512Mismatch {
+                path,
+                expected: "a".into(),
+                actual: "b".into(),
+            },
+            LauncherError::InvalidMavenCoordinate("a".into()),
+            LauncherError::PomParse("pom".into()),
+            LauncherError::Xml(quick_xml::DeError::Custom("xml".into())),
+            LauncherError::Json(serde_json::from_str::<u8>("{").unwrap_err()),
+            LauncherError::Nbt(quartz_nbt::io::NbtIoError::MissingRootTag),
+            LauncherError::InstanceNotFound("id".into()),
+            LauncherError::InstanceAlreadyExists("id".into()),
+            LauncherError::InstanceAlreadyRunning("id".into()),
+            LauncherError::HookFailed {
+                hook: "pre-launch".into(),
+                status: "1".into(),
+            },
+            LauncherError::InstanceNotReady {
+                id: "id".into(),
+                state: "Installing".into(),
+            },
+            LauncherError::InstanceIncomplete("main_class".into()),
+            LauncherError::InsufficientDiskSpace {
+                needed: 2,
+                available: 1,
+            },
+            LauncherError::AccountNotFound("id".into()),
+            LauncherError::JavaNotFound(21),
+            LauncherError::JavaExecution("java".into()),
+            LauncherError::InvalidJvmArgs(vec![JvmArgConflict::MalformedProperty {
+                arg: "-D".into(),
+            }]),
+            LauncherError::JavaResolutionFailed("java".into()),
+            LauncherError::PreflightFailed {
+                failures: vec!["MissingJava".into()],
+            },
+            LauncherError::ClasspathEmpty,
+            LauncherError::Loader("loader".into()),
+            LauncherError::LoaderApi("api".into()),
+            LauncherError::LoaderProcessorFailed {
+                name: "proc".into(),
+                output_tail: String::new(),
+            },
+            LauncherError::Zip(zip::result::ZipError::FileNotFound),
+            LauncherError::Cancelled,
+            LauncherError::message(i18n::ErrorText::NoSavedLog, &[&"id"]),
+            LauncherError::Other("other".into()),
+        ]
+    }
+
+    #[test]
+    fn each_variant_has_a_unique_stable_key() {
+        let errors = one_of_each();
+        let keys: HashSet<&str> = errors.iter().map(LauncherError::i18n_key).collect();
+        assert_eq!(keys.len(), errors.len());
+        for err in &errors {
+            assert!(err.i18n_key().starts_with("error."), "{err:?}");
+            assert!(!err.code().text(i18n::Locale::En).is_empty());
+        }
+
+        assert_eq!(LauncherError::Cancelled.i18n_key(), "error.cancelled");
+        assert_eq!(
+            LauncherError::JavaNotFound(21).i18n_key(),
+            "error.java_not_found"
+        );
+        let json = serde_json::to_value(LauncherError::InstanceNotFound("id".into())).unwrap();
+        assert_eq!(json["i18n_key"], "error.instance_not_found");
+    }
+
+    #[test]
+    fn serialized_kind_is_a_unique_discriminant() {
+        let errors = one_of_each();
+        let kinds: HashSet<String> = errors
+            .iter()
+            .map(|err| serde_json::to_value(err).unwrap()["kind"].to_string())
+            .collect();
+        assert_eq!(kinds.len(), errors.len());
+    }
+
+    #[test]
+    fn serialized_shape_carries_variant_data() {
+        let json = serde_json::to_value(LauncherError::PreflightFailed {
+            failures: vec!["MissingJava".into(), "MissingLibraries".into()],
+        })
+        .unwrap();
+        assert_eq!(json["kind"], "preflight_failed");
+        assert_eq!(json["category"], "launch");
+        assert_eq!(json["i18n_key"], "error.preflight_failed");
+        assert_eq!(
+            json["failures"],
+            serde_json::json!(["MissingJava", "MissingLibraries"])
+        );
+        assert_eq!(json["recoverable"], false);
+
+        let json = serde_json::to_value(LauncherError::ClasspathEmpty).unwrap();
+        assert_eq!(json["kind"], "classpath_empty");
+        assert_eq!(json["message"], "Classpath is empty");
+        assert!(json.get("failures").is_none());
+
+        let json = serde_json::to_value(LauncherError::JavaNotFound(17)).unwrap();
+        assert_eq!(json["kind"], "java_not_found");
+        assert_eq!(json["category"], "java");
+        assert_eq!(json["recoverable"], true);
+
+        let json = serde_json::to_value(LauncherError::message(
+            i18n::ErrorText::NoRunningProcess,
+            &[&"abc"],
+        ))
+        .unwrap();
+        assert_eq!(json["kind"], "message");
+        assert_eq!(json["i18n_key"], "error.text.no_running_process");
+        assert_eq!(json["args"], serde_json::json!(["abc"]));
+        assert_eq!(
+            json["message"],
+            "No hay proceso activo para la instancia abc"
+        );
+    }
+
+    #[test]
+    fn each_error_text_has_its_own_stable_key() {
+        let variant_keys: HashSet<&str> = one_of_each()
+            .iter()
+            .filter(|err| !matches!(err, LauncherError::Message(_)))
+            .map(LauncherError::i18n_key)
+            .collect();
+        let mut keys = HashSet::new();
+        for text in i18n::ErrorText::ALL {
+            let err = LauncherError::message(text, &[]);
+            assert_eq!(err.i18n_key(), text.key());
+            assert_eq!(serde_json::to_value(&err).unwrap()["kind"], "message");
+            assert!(!variant_keys.contains(err.i18n_key()), "{text:?}");
+            assert!(keys.insert(err.i18n_key()), "{text:?}");
+        }
+    }
+}
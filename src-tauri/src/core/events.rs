@@ -0,0 +1,46 @@
+// ─── Events ───
+// Progress and log events the backend reports while it works. The Tauri app
+// forwards them to the frontend; headless callers (tests, a CLI) can pass a
+// `NoopEmitter` or their own sink.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::warn;
+
+/// Destination for backend events.
+pub trait EventEmitter: Send + Sync {
+    fn emit_json(&self, event: &str, payload: serde_json::Value);
+}
+
+impl dyn EventEmitter + '_ {
+    /// Serialize `payload` and emit it as `event`.
+    pub fn emit<S: Serialize>(&self, event: &str, payload: S) {
+        match serde_json::to_value(payload) {
+            Ok(value) => self.emit_json(event, value),
+            Err(err) => warn!("Cannot serialize payload for event {}: {}", event, err),
+        }
+    }
+}
+
+impl<T: EventEmitter + ?Sized> EventEmitter for Arc<T> {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        (**self).emit_json(event, payload);
+    }
+}
+
+/// Drops every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopEmitter;
+
+impl EventEmitter for NoopEmitter {
+    fn emit_json(&self, _event: &str, _payload: serde_json::Value) {}
+}
+
+impl<R: tauri::Runtime> EventEmitter for tauri::AppHandle<R> {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        if let Err(err) = tauri::Emitter::emit(self, event, payload) {
+            warn!("Cannot emit event {}: {}", event, err);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// ─── CLI Texts ───
+// What `interface-cli` prints: usage, results and its own usage errors.
+// Texts take their arguments in order through `{}` placeholders, like log
+// lines; the CLI renders them in the language of the launcher settings.
+
+use super::{fill, pick, Locale};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CliText {
+    Usage,
+    MissingValue,
+    InvalidMemory,
+    UnknownOption,
+    InvalidArguments,
+    DataDirNotWritable,
+    Failed,
+    NoInstances,
+    InstanceReady,
+    GameStarted,
+    GameClosed,
+    JavaReady,
+    JavaPhase,
+    JavaPhaseProgress,
+    JavaPhaseDownload,
+    JavaPhaseExtract,
+    JavaPhaseValidate,
+}
+
+impl CliText {
+    #[cfg(test)]
+    const ALL: [CliText; 17] = [
+        CliText::Usage,
+        CliText::MissingValue,
+        CliText::InvalidMemory,
+        CliText::UnknownOption,
+        CliText::InvalidArguments,
+        CliText::DataDirNotWritable,
+        CliText::Failed,
+        CliText::NoInstances,
+        CliText::InstanceReady,
+        CliText::GameStarted,
+        CliText::GameClosed,
+        CliText::JavaReady,
+        CliText::JavaPhase,
+        CliText::JavaPhaseProgress,
+        CliText::JavaPhaseDownload,
+        CliText::JavaPhaseExtract,
+        CliText::JavaPhaseValidate,
+    ];
+
+    /// (key, Spanish, English)
+    fn entry(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            CliText::Usage => (
+                "cli.usage",
+                "\
+Uso: interface-cli [--data-dir <carpeta>] <comando>
+
+Comandos:
+  list                                   Lista las instancias
+  create <nombre> <versión-mc> [--loader <loader>] [--loader-version <versión>] [--memory <MB>]
+                                         Crea e instala una instancia
+  launch <id>                            Inicia una instancia y espera a que se cierre
+  java install <versión-mc>              Instala la Java gestionada para esa versión
+
+Sin --data-dir se usa INTERFACE_DATA_DIR si está definida.",
+                "\
+Usage: interface-cli [--data-dir <folder>] <command>
+
+Commands:
+  list                                   List the instances
+  create <name> <mc-version> [--loader <loader>] [--loader-version <version>] [--memory <MB>]
+                                         Create and install an instance
+  launch <id>                            Launch an instance and wait for it to close
+  java install <mc-version>              Install the managed Java for that version
+
+Without --data-dir, INTERFACE_DATA_DIR is used when set.",
+            ),
+            CliText::MissingValue => (
+                "cli.missing_value",
+                "Falta el valor de {}",
+                "Missing value for {}",
+            ),
+            CliText::InvalidMemory => (
+                "cli.invalid_memory",
+                "Memoria inválida: {}",
+                "Invalid memory: {}",
+            ),
+            CliText::UnknownOption => (
+                "cli.unknown_option",
+                "Opción desconocida: {}",
+                "Unknown option: {}",
+            ),
+            CliText::InvalidArguments => (
+                "cli.invalid_arguments",
+                "Argumentos inválidos para «{}»",
+                "Invalid arguments for «{}»",
+            ),
+            CliText::DataDirNotWritable => (
+                "cli.data_dir_not_writable",
+                "Error: la carpeta de datos no es escribible: {}",
+                "Error: the data folder is not writable: {}",
+            ),
+            CliText::Failed => ("cli.failed", "Error: {}", "Error: {}"),
+            CliText::NoInstances => ("cli.no_instances", "No hay instancias.", "No instances."),
+            CliText::InstanceReady => (
+                "cli.instance_ready",
+                "Instancia lista: {} ({})",
+                "Instance ready: {} ({})",
+            ),
+            CliText::GameStarted => (
+                "cli.game_started",
+                "Juego iniciado; esperando a que se cierre...",
+                "Game started; waiting for it to close...",
+            ),
+            CliText::GameClosed => ("cli.game_closed", "El juego se cerró.", "The game closed."),
+            CliText::JavaReady => ("cli.java_ready", "Java lista: {}", "Java ready: {}"),
+            CliText::JavaPhase => ("cli.java_phase", "Java ({})", "Java ({})"),
+            CliText::JavaPhaseProgress => (
+                "cli.java_phase_progress",
+                "Java ({}): {}%",
+                "Java ({}): {}%",
+            ),
+            CliText::JavaPhaseDownload => ("cli.java_phase.download", "descarga", "download"),
+            CliText::JavaPhaseExtract => ("cli.java_phase.extract", "extracción", "extract"),
+            CliText::JavaPhaseValidate => ("cli.java_phase.validate", "validación", "validate"),
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        self.entry().0
+    }
+
+    pub fn template(self, locale: Locale) -> &'static str {
+        let (_, es, en) = self.entry();
+        pick(locale, es, en)
+    }
+
+    pub fn format(self, locale: Locale, args: &[String]) -> String {
+        fill(self.template(locale), args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn cli_texts_are_unique_and_both_texts_take_the_same_arguments() {
+        let keys: HashSet<&str> = CliText::ALL.iter().map(|text| text.key()).collect();
+        assert_eq!(keys.len(), CliText::ALL.len());
+        for text in CliText::ALL {
+            assert!(text.key().starts_with("cli."));
+            assert_eq!(
+                text.template(Locale::Es).matches("{}").count(),
+                text.template(Locale::En).matches("{}").count(),
+                "{text:?}"
+            );
+        }
+        assert_eq!(
+            CliText::InstanceReady.format(Locale::En, &["Survival".into(), "abc".into()]),
+            "Instance ready: Survival (abc)"
+        );
+    }
+}
@@ -0,0 +1,561 @@
+// ─── Log Lines ───
+// Lines the launcher itself writes to the create and launch logs. Texts
+// take their arguments in order through `{}` placeholders; events carry the
+// key and the arguments so the frontend can render its own translation.
+
+use serde::{Serialize, Serializer};
+
+use super::{fill, pick, Locale};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogKey {
+    CreateStarted,
+    CreateModpackImported,
+    CreateModpackFileSkipped,
+    CreateVanillaReady,
+    CreateLoaderUpgraded,
+    CreateLoaderInstalled,
+    CreateJavaSelected,
+    CreateFailed,
+    CreateReady,
+    LaunchRequested,
+    LaunchDemoMode,
+    LaunchValidationFailed,
+    LaunchValidated,
+    LaunchPhasePreparation,
+    LaunchPreparationFailed,
+    LaunchResourcesReady,
+    LaunchPhaseBootstrap,
+    LaunchPreflightRunning,
+    LaunchPreflightLoaderJavaFix,
+    LaunchPreflightLoaderJavaFixDisabled,
+    LaunchPreflightRepairDisabled,
+    LaunchPreflightRepairStarting,
+    LaunchHooksDisabled,
+    LaunchAborted,
+    LaunchPhaseJarAnalysis,
+    LaunchPhaseGame,
+    LaunchSpawnFailed,
+    LaunchRunning,
+    LaunchExited,
+    LaunchExitedWithCode,
+    LaunchExitedWithoutCode,
+    LaunchExitCodeOneHint,
+    LaunchCrash,
+    LaunchCrashWithoutReport,
+    LaunchWaitFailed,
+    LaunchCancelled,
+    LaunchNoPid,
+    LaunchStopRequested,
+    LaunchStoppedByUser,
+    LaunchError,
+    CheckInstanceDir,
+    CheckGameDir,
+    CheckAssetsDir,
+    CheckClientJarVerified,
+    CheckClientJarUnverified,
+    CheckClientJarCorrupt,
+    CheckClientJarMissing,
+    CheckLoader,
+    CheckMainClass,
+    CheckAssetIndex,
+    CheckJavaBinary,
+    CheckLoaderJava,
+    CheckLoaderAsmTooOld,
+    CheckLoaderNeedsDelta,
+    CheckJavaVersion,
+    CheckJava64,
+    CheckArgs,
+    CheckUnresolvedPlaceholders,
+    CheckMaven,
+    CheckExtraMods,
+    RepairRevertedToGamma,
+    RepairSucceeded,
+    RepairAttempt,
+    RepairClassified,
+    RepairResolvingJava,
+    RepairLoaderToolingJava,
+    RepairPhaseRuntimes,
+    RepairPurgingOldAsm,
+    RepairUpdatingLoader,
+    RepairRetryingPhase,
+    BootstrapRuntime,
+}
+
+impl LogKey {
+    #[cfg(test)]
+    const ALL: [LogKey; 71] = [
+        LogKey::CreateStarted,
+        LogKey::CreateModpackImported,
+        LogKey::CreateModpackFileSkipped,
+        LogKey::CreateVanillaReady,
+        LogKey::CreateLoaderUpgraded,
+        LogKey::CreateLoaderInstalled,
+        LogKey::CreateJavaSelected,
+        LogKey::CreateFailed,
+        LogKey::CreateReady,
+        LogKey::LaunchRequested,
+        LogKey::LaunchDemoMode,
+        LogKey::LaunchValidationFailed,
+        LogKey::LaunchValidated,
+        LogKey::LaunchPhasePreparation,
+        LogKey::LaunchPreparationFailed,
+        LogKey::LaunchResourcesReady,
+        LogKey::LaunchPhaseBootstrap,
+        LogKey::LaunchPreflightRunning,
+        LogKey::LaunchPreflightLoaderJavaFix,
+        LogKey::LaunchPreflightLoaderJavaFixDisabled,
+        LogKey::LaunchPreflightRepairDisabled,
+        LogKey::LaunchPreflightRepairStarting,
+        LogKey::LaunchHooksDisabled,
+        LogKey::LaunchAborted,
+        LogKey::LaunchPhaseJarAnalysis,
+        LogKey::LaunchPhaseGame,
+        LogKey::LaunchSpawnFailed,
+        LogKey::LaunchRunning,
+        LogKey::LaunchExited,
+        LogKey::LaunchExitedWithCode,
+        LogKey::LaunchExitedWithoutCode,
+        LogKey::LaunchExitCodeOneHint,
+        LogKey::LaunchCrash,
+        LogKey::LaunchCrashWithoutReport,
+        LogKey::LaunchWaitFailed,
+        LogKey::LaunchCancelled,
+        LogKey::LaunchNoPid,
+        LogKey::LaunchStopRequested,
+        LogKey::LaunchStoppedByUser,
+        LogKey::LaunchError,
+        LogKey::CheckInstanceDir,
+        LogKey::CheckGameDir,
+        LogKey::CheckAssetsDir,
+        LogKey::CheckClientJarVerified,
+        LogKey::CheckClientJarUnverified,
+        LogKey::CheckClientJarCorrupt,
+        LogKey::CheckClientJarMissing,
+        LogKey::CheckLoader,
+        LogKey::CheckMainClass,
+        LogKey::CheckAssetIndex,
+        LogKey::CheckJavaBinary,
+        LogKey::CheckLoaderJava,
+        LogKey::CheckLoaderAsmTooOld,
+        LogKey::CheckLoaderNeedsDelta,
+        LogKey::CheckJavaVersion,
+        LogKey::CheckJava64,
+        LogKey::CheckArgs,
+        LogKey::CheckUnresolvedPlaceholders,
+        LogKey::CheckMaven,
+        LogKey::CheckExtraMods,
+        LogKey::RepairRevertedToGamma,
+        LogKey::RepairSucceeded,
+        LogKey::RepairAttempt,
+        LogKey::RepairClassified,
+        LogKey::RepairResolvingJava,
+        LogKey::RepairLoaderToolingJava,
+        LogKey::RepairPhaseRuntimes,
+        LogKey::RepairPurgingOldAsm,
+        LogKey::RepairUpdatingLoader,
+        LogKey::RepairRetryingPhase,
+        LogKey::BootstrapRuntime,
+    ];
+
+    /// (key, Spanish, English)
+    fn entry(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            LogKey::CreateStarted => (
+                "log.create.started",
+                "Instancia creada en disco, iniciando instalación base...",
+                "Instance created on disk, starting the base install...",
+            ),
+            LogKey::CreateModpackImported => (
+                "log.create.modpack_imported",
+                "Modpack {} '{}' importado ({} {}), iniciando instalación base...",
+                "{} modpack '{}' imported ({} {}), starting the base install...",
+            ),
+            LogKey::CreateModpackFileSkipped => (
+                "log.create.modpack_file_skipped",
+                "El proyecto {} no permite descargar el archivo {} desde otros launchers; descárgalo a mano en {}",
+                "Project {} does not allow third-party downloads of file {}; download it manually into {}",
+            ),
+            LogKey::CreateVanillaReady => (
+                "log.create.vanilla_ready",
+                "Runtime Vanilla preparado.",
+                "Vanilla runtime ready.",
+            ),
+            LogKey::CreateLoaderUpgraded => (
+                "log.create.loader_upgraded",
+                "Loader {} actualizado automáticamente de {} a {} por compatibilidad con Java 21.",
+                "Loader {} updated automatically from {} to {} for Java 21 compatibility.",
+            ),
+            LogKey::CreateLoaderInstalled => (
+                "log.create.loader_installed",
+                "Loader {} {} instalado.",
+                "Loader {} {} installed.",
+            ),
+            LogKey::CreateJavaSelected => (
+                "log.create.java_selected",
+                "✅ Java seleccionada automáticamente para {}: {}",
+                "✅ Java picked automatically for {}: {}",
+            ),
+            LogKey::CreateFailed => (
+                "log.create.failed",
+                "Falló la creación: {}",
+                "Creation failed: {}",
+            ),
+            LogKey::CreateReady => (
+                "log.create.ready",
+                "Instancia creada correctamente y verificada.",
+                "Instance created and verified.",
+            ),
+            LogKey::LaunchRequested => (
+                "log.launch.requested",
+                "[PREPARACIÓN] Solicitud de inicio recibida en backend.",
+                "[PREPARATION] Launch request received.",
+            ),
+            LogKey::LaunchDemoMode => (
+                "log.launch.demo_mode",
+                "[CUENTA] La cuenta {} no tiene Minecraft: Java Edition; el juego se iniciará en modo demo.",
+                "[ACCOUNT] The account {} does not own Minecraft: Java Edition; the game will start in demo mode.",
+            ),
+            LogKey::LaunchValidationFailed => (
+                "log.launch.validation_failed",
+                "[ERROR] Validación fallida: {}",
+                "[ERROR] Validation failed: {}",
+            ),
+            LogKey::LaunchValidated => (
+                "log.launch.validated",
+                "[PREPARACIÓN] Validación completada. Preparando archivos, Java y librerías.",
+                "[PREPARATION] Validation complete. Preparing files, Java and libraries.",
+            ),
+            LogKey::LaunchPhasePreparation => (
+                "log.launch.phase_preparation",
+                "[FASE] preparación",
+                "[PHASE] preparation",
+            ),
+            LogKey::LaunchPreparationFailed => (
+                "log.launch.preparation_failed",
+                "[ERROR] Preparación fallida: {}",
+                "[ERROR] Preparation failed: {}",
+            ),
+            LogKey::LaunchResourcesReady => (
+                "log.launch.resources_ready",
+                "[DESCARGA] Recursos y dependencias listos. Construyendo classpath y extrayendo nativos.",
+                "[DOWNLOAD] Resources and dependencies ready. Building the classpath and extracting natives.",
+            ),
+            LogKey::LaunchPhaseBootstrap => (
+                "log.launch.phase_bootstrap",
+                "[FASE] bootstrap",
+                "[PHASE] bootstrap",
+            ),
+            LogKey::LaunchPreflightRunning => (
+                "log.launch.preflight_running",
+                "[PREPARACIÓN] Ejecutando checklist preflight (estructura, Java, args, Maven, loader, bootstrap).",
+                "[PREPARATION] Running the preflight checklist (structure, Java, args, Maven, loader, bootstrap).",
+            ),
+            LogKey::LaunchPreflightLoaderJavaFix => (
+                "log.launch.preflight_loader_java_fix",
+                "[PREPARACIÓN] Preflight detectó incompatibilidad loader↔Java; se aplicará una corrección automática sin reintentos.",
+                "[PREPARATION] Preflight found a loader↔Java incompatibility; an automatic fix will be applied without retries.",
+            ),
+            LogKey::LaunchPreflightLoaderJavaFixDisabled => (
+                "log.launch.preflight_loader_java_fix_disabled",
+                "[PREPARACIÓN] Preflight detectó incompatibilidad loader↔Java; la corrección automática está desactivada en los ajustes.",
+                "[PREPARATION] Preflight found a loader↔Java incompatibility; the automatic fix is disabled in the settings.",
+            ),
+            LogKey::LaunchPreflightRepairDisabled => (
+                "log.launch.preflight_repair_disabled",
+                "[PREPARACIÓN] Preflight con fallos transitorios: autoreparación desactivada en los ajustes (0 intentos).",
+                "[PREPARATION] Preflight found transient failures: automatic repair is disabled in the settings (0 attempts).",
+            ),
+            LogKey::LaunchPreflightRepairStarting => (
+                "log.launch.preflight_repair_starting",
+                "[PREPARACIÓN] Preflight con fallos transitorios: se iniciará autoreparación (máx. {} intentos).",
+                "[PREPARATION] Preflight found transient failures: starting automatic repair (up to {} attempts).",
+            ),
+            LogKey::LaunchHooksDisabled => (
+                "log.launch.hooks_disabled",
+                "[HOOK] Hooks desactivados en ajustes; se omite el hook pre-lanzamiento.",
+                "[HOOK] Hooks are disabled in the settings; skipping the pre-launch hook.",
+            ),
+            LogKey::LaunchAborted => (
+                "log.launch.aborted",
+                "[ERROR] Lanzamiento abortado: {}",
+                "[ERROR] Launch aborted: {}",
+            ),
+            LogKey::LaunchPhaseJarAnalysis => (
+                "log.launch.phase_jar_analysis",
+                "[FASE] análisis de jars",
+                "[PHASE] jar analysis",
+            ),
+            LogKey::LaunchPhaseGame => (
+                "log.launch.phase_game",
+                "[FASE] launch del juego",
+                "[PHASE] game launch",
+            ),
+            LogKey::LaunchSpawnFailed => (
+                "log.launch.spawn_failed",
+                "[ERROR] No se pudo lanzar Minecraft: {}",
+                "[ERROR] Could not start Minecraft: {}",
+            ),
+            LogKey::LaunchRunning => (
+                "log.launch.running",
+                "[RUNTIME] Instancia en ejecución (PID {}).",
+                "[RUNTIME] Instance running (PID {}).",
+            ),
+            LogKey::LaunchExited => (
+                "log.launch.exited",
+                "[RUNTIME] Proceso finalizado correctamente.",
+                "[RUNTIME] The process exited normally.",
+            ),
+            LogKey::LaunchExitedWithCode => (
+                "log.launch.exited_with_code",
+                "[ERROR] El proceso finalizó con código {}",
+                "[ERROR] The process exited with code {}",
+            ),
+            LogKey::LaunchExitedWithoutCode => (
+                "log.launch.exited_without_code",
+                "[ERROR] El proceso finalizó sin código de salida (terminación externa).",
+                "[ERROR] The process exited without an exit code (terminated externally).",
+            ),
+            LogKey::LaunchExitCodeOneHint => (
+                "log.launch.exit_code_one_hint",
+                "[ERROR] Código 1 suele ser fallo de bootstrap/clase principal. Revisa líneas [DIAGNÓSTICO] previas (classpath, securejarhandler/modlauncher, URLStreamHandlerFactory).",
+                "[ERROR] Exit code 1 usually means a bootstrap/main class failure. Check the earlier [DIAGNOSTIC] lines (classpath, securejarhandler/modlauncher, URLStreamHandlerFactory).",
+            ),
+            LogKey::LaunchCrash => ("log.launch.crash", "[CRASH] {} ({})", "[CRASH] {} ({})"),
+            LogKey::LaunchCrashWithoutReport => (
+                "log.launch.crash_without_report",
+                "[CRASH] Sin crash report; últimas líneas del log ({})",
+                "[CRASH] No crash report; last lines of the log ({})",
+            ),
+            LogKey::LaunchWaitFailed => (
+                "log.launch.wait_failed",
+                "[ERROR] Fallo al esperar el proceso: {}",
+                "[ERROR] Lost track of the process: {}",
+            ),
+            LogKey::LaunchCancelled => (
+                "log.launch.cancelled",
+                "[PREPARACIÓN] Inicio cancelado por el usuario.",
+                "[PREPARATION] Launch cancelled by the user.",
+            ),
+            LogKey::LaunchNoPid => (
+                "log.launch.no_pid",
+                "[RUNTIME] No había PID registrado. Estado corregido a listo.",
+                "[RUNTIME] No PID was recorded. State reset to ready.",
+            ),
+            LogKey::LaunchStopRequested => (
+                "log.launch.stop_requested",
+                "[RUNTIME] Solicitando cierre ordenado (PID {}, espera máxima {}s).",
+                "[RUNTIME] Asking the game to close (PID {}, waiting up to {}s).",
+            ),
+            LogKey::LaunchStoppedByUser => (
+                "log.launch.stopped_by_user",
+                "[RUNTIME] Instancia detenida por usuario (PID {}).",
+                "[RUNTIME] Instance stopped by the user (PID {}).",
+            ),
+            LogKey::LaunchError => ("log.launch.error", "[ERROR] {}", "[ERROR] {}"),
+            LogKey::CheckInstanceDir => (
+                "log.check.instance_dir",
+                "[CHECK] {} Estructura base de instancia: {}",
+                "[CHECK] {} Instance base folder: {}",
+            ),
+            LogKey::CheckGameDir => (
+                "log.check.game_dir",
+                "[CHECK] {} Carpeta minecraft disponible: {}",
+                "[CHECK] {} Minecraft folder available: {}",
+            ),
+            LogKey::CheckAssetsDir => (
+                "log.check.assets_dir",
+                "[CHECK] {} Carpeta assets disponible: {}",
+                "[CHECK] {} Assets folder available: {}",
+            ),
+            LogKey::CheckClientJarVerified => (
+                "log.check.client_jar_verified",
+                "[CHECK] {} Bootstrap client.jar verificado (SHA-1, {} bytes): {}",
+                "[CHECK] {} Bootstrap client.jar verified (SHA-1, {} bytes): {}",
+            ),
+            LogKey::CheckClientJarUnverified => (
+                "log.check.client_jar_unverified",
+                "[CHECK] {} Bootstrap client.jar presente ({} bytes, sin SHA-1 para verificar): {}",
+                "[CHECK] {} Bootstrap client.jar present ({} bytes, no SHA-1 to check): {}",
+            ),
+            LogKey::CheckClientJarCorrupt => (
+                "log.check.client_jar_corrupt",
+                "[CHECK] {} Bootstrap client.jar dañado ({} bytes, SHA-1 distinto): {}",
+                "[CHECK] {} Bootstrap client.jar corrupted ({} bytes, SHA-1 mismatch): {}",
+            ),
+            LogKey::CheckClientJarMissing => (
+                "log.check.client_jar_missing",
+                "[CHECK] {} Bootstrap client.jar ausente: {}",
+                "[CHECK] {} Bootstrap client.jar missing: {}",
+            ),
+            LogKey::CheckLoader => (
+                "log.check.loader",
+                "[CHECK] {} Loader configurado ({} {})",
+                "[CHECK] {} Loader configured ({} {})",
+            ),
+            LogKey::CheckMainClass => (
+                "log.check.main_class",
+                "[CHECK] {} Main class resuelta: {}",
+                "[CHECK] {} Main class resolved: {}",
+            ),
+            LogKey::CheckAssetIndex => (
+                "log.check.asset_index",
+                "[CHECK] {} Índice de assets resuelto: {}",
+                "[CHECK] {} Asset index resolved: {}",
+            ),
+            LogKey::CheckJavaBinary => (
+                "log.check.java_binary",
+                "[CHECK] {} Binario Java inyectado en instancia: {}",
+                "[CHECK] {} Java binary assigned to the instance: {}",
+            ),
+            LogKey::CheckLoaderJava => (
+                "log.check.loader_java",
+                "[CHECK] {} Compatibilidad loader↔Java validada (bootstrap {}, Java objetivo {}, tooling {})",
+                "[CHECK] {} Loader↔Java compatibility checked (bootstrap {}, target Java {}, tooling {})",
+            ),
+            LogKey::CheckLoaderAsmTooOld => (
+                "log.check.loader_asm_too_old",
+                "[CHECK] El loader seleccionado requiere Java de herramientas diferente al de ejecución. Loader incompatible con Java {} detectado: ASM antiguo en librerías [{}]. Actualiza la versión de {} para {}.",
+                "[CHECK] The selected loader needs a different tooling Java than the game. Loader incompatible with Java {}: old ASM in the libraries [{}]. Update {} for {}.",
+            ),
+            LogKey::CheckLoaderNeedsDelta => (
+                "log.check.loader_needs_delta",
+                "[CHECK] Loader marcado como requiere Delta para fases sensibles (ASM/JAR analysis).",
+                "[CHECK] Loader flagged as needing Delta for sensitive phases (ASM/JAR analysis).",
+            ),
+            LogKey::CheckJavaVersion => (
+                "log.check.java_version",
+                "[CHECK] {} Java compatible con Minecraft {} (requerida {}, actual {})",
+                "[CHECK] {} Java compatible with Minecraft {} (required {}, found {})",
+            ),
+            LogKey::CheckJava64 => (
+                "log.check.java_64",
+                "[CHECK] {} Java de 64 bits validada",
+                "[CHECK] {} 64-bit Java checked",
+            ),
+            LogKey::CheckArgs => (
+                "log.check.args",
+                "[CHECK] {} Argumentos listos (JVM placeholders soportados: {}, Game placeholders soportados: {})",
+                "[CHECK] {} Arguments ready (JVM placeholders supported: {}, game placeholders supported: {})",
+            ),
+            LogKey::CheckUnresolvedPlaceholders => (
+                "log.check.unresolved_placeholders",
+                "[CHECK] Placeholders no reemplazados detectados -> JVM: {} | Game: {}",
+                "[CHECK] Unreplaced placeholders found -> JVM: {} | Game: {}",
+            ),
+            LogKey::CheckMaven => (
+                "log.check.maven",
+                "[CHECK] {} Dependencias Maven listas (faltantes: {})",
+                "[CHECK] {} Maven dependencies ready (missing: {})",
+            ),
+            LogKey::CheckExtraMods => (
+                "log.check.extra_mods",
+                "[CHECK] {} JARs extra en mods detectados: {}",
+                "[CHECK] {} Extra JARs found in mods: {}",
+            ),
+            LogKey::RepairRevertedToGamma => (
+                "log.repair.reverted_to_gamma",
+                "[REPAIR] Detectada configuración legacy innecesaria (ASM compatible encontrado). Revirtiendo a Gamma runtime.",
+                "[REPAIR] Unneeded legacy setup found (compatible ASM present). Reverting to the Gamma runtime.",
+            ),
+            LogKey::RepairSucceeded => (
+                "log.repair.succeeded",
+                "[REPAIR] Instancia reparada y validada correctamente.",
+                "[REPAIR] Instance repaired and validated.",
+            ),
+            LogKey::RepairAttempt => (
+                "log.repair.attempt",
+                "[REPAIR] Intento automático {}/{}.",
+                "[REPAIR] Automatic attempt {}/{}.",
+            ),
+            LogKey::RepairClassified => (
+                "log.repair.classified",
+                "[REPAIR] Preflight falló. Clasificación detectada: {}",
+                "[REPAIR] Preflight failed. Detected classification: {}",
+            ),
+            LogKey::RepairResolvingJava => (
+                "log.repair.resolving_java",
+                "[REPAIR] Resolviendo runtime de Java administrado compatible.",
+                "[REPAIR] Resolving a compatible managed Java runtime.",
+            ),
+            LogKey::RepairLoaderToolingJava => (
+                "log.repair.loader_tooling_java",
+                "[REPAIR] El loader requiere Java de herramientas distinto. Se ajustó automáticamente.",
+                "[REPAIR] The loader needs a different tooling Java. Adjusted automatically.",
+            ),
+            LogKey::RepairPhaseRuntimes => (
+                "log.repair.phase_runtimes",
+                "[REPAIR] Reasignando runtime de fase: bootstrap=delta ({}) | game=gamma.",
+                "[REPAIR] Reassigning phase runtimes: bootstrap=delta ({}) | game=gamma.",
+            ),
+            LogKey::RepairPurgingOldAsm => (
+                "log.repair.purging_old_asm",
+                "[REPAIR] Se detectó loader con ASM antiguo para Java 21. Se purgarán artefactos del loader y se reinstalará limpio.",
+                "[REPAIR] The loader ships an ASM too old for Java 21. Its artifacts will be purged and reinstalled clean.",
+            ),
+            LogKey::RepairUpdatingLoader => (
+                "log.repair.updating_loader",
+                "[REPAIR] Actualizando loader {} de {} a {}.",
+                "[REPAIR] Updating loader {} from {} to {}.",
+            ),
+            LogKey::RepairRetryingPhase => (
+                "log.repair.retrying_phase",
+                "[REPAIR] Reasignando runtime de fase y reintentando solo la fase fallida.",
+                "[REPAIR] Reassigning the phase runtime and retrying only the failed phase.",
+            ),
+            LogKey::BootstrapRuntime => (
+                "log.bootstrap.runtime",
+                "[BOOTSTRAP] Runtime de fase asignado: {} | binario: {} | JAVA_HOME: {}",
+                "[BOOTSTRAP] Phase runtime assigned: {} | binary: {} | JAVA_HOME: {}",
+            ),
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        self.entry().0
+    }
+
+    /// The text with its `{}` placeholders.
+    pub fn template(self, locale: Locale) -> &'static str {
+        let (_, es, en) = self.entry();
+        pick(locale, es, en)
+    }
+
+    pub fn format(self, locale: Locale, args: &[String]) -> String {
+        fill(self.template(locale), args)
+    }
+}
+
+impl Serialize for LogKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn log_keys_are_unique_and_both_texts_take_the_same_arguments() {
+        let keys: HashSet<&str> = LogKey::ALL.iter().map(|key| key.key()).collect();
+        assert_eq!(keys.len(), LogKey::ALL.len());
+        for key in LogKey::ALL {
+            assert!(key.key().starts_with("log."));
+            assert_eq!(
+                key.template(Locale::Es).matches("{}").count(),
+                key.template(Locale::En).matches("{}").count(),
+                "{key:?}"
+            );
+        }
+        assert_eq!(
+            LogKey::LaunchRunning.format(Locale::En, &["42".into()]),
+            "[RUNTIME] Instance running (PID 42)."
+        );
+        assert_eq!(
+            LogKey::RepairAttempt.format(Locale::Es, &["1".into(), "3".into()]),
+            "[REPAIR] Intento automático 1/3."
+        );
+    }
+}
@@ -0,0 +1,475 @@
+// ─── Messages ───
+// Stable keys for errors and progress stages. Events and serialized errors
+// carry the key so the frontend can translate it; the bundled Spanish and
+// English texts are what the backend writes to logs and sends as fallback.
+// Callers pass the locale of the launcher settings explicitly.
+
+use serde::{Deserialize, Serialize, Serializer};
+
+mod cli;
+mod logs;
+mod texts;
+
+pub use cli::CliText;
+pub use logs::LogKey;
+pub use texts::{ErrorText, Message};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Es,
+    En,
+}
+
+fn pick(locale: Locale, es: &'static str, en: &'static str) -> &'static str {
+    match locale {
+        Locale::Es => es,
+        Locale::En => en,
+    }
+}
+
+/// `template` with each `{}` replaced by the next of `args`; placeholders
+/// left without an argument stay as they are.
+fn fill(template: &str, args: &[String]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(at) = rest.find("{}") {
+        out.push_str(&rest[..at]);
+        match args.next() {
+            Some(arg) => out.push_str(arg),
+            None => out.push_str("{}"),
+        }
+        rest = &rest[at + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One code per `LauncherError` variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    Io,
+    InsufficientDiskSpace,
+    Http,
+    DownloadFailed,
+    AssetsUnavailable,
+    Sha1Mismatch,
+    Sha256Mismatch,
+    Sha512Mismatch,
+    InvalidMavenCoordinate,
+    PomParse,
+    Xml,
+    Json,
+    Nbt,
+    InstanceNotFound,
+    InstanceAlreadyExists,
+    InstanceAlreadyRunning,
+    HookFailed,
+    InstanceNotReady,
+    InstanceIncomplete,
+    AccountNotFound,
+    JavaNotFound,
+    JavaExecution,
+    InvalidJvmArgs,
+    JavaResolutionFailed,
+    PreflightFailed,
+    ClasspathEmpty,
+    Loader,
+    LoaderApi,
+    LoaderProcessorFailed,
+    Zip,
+    Cancelled,
+    Message,
+    Other,
+}
+
+impl ErrorCode {
+    /// (key, Spanish, English)
+    fn entry(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            ErrorCode::Io => (
+                "error.io",
+                "Error al leer o escribir archivos",
+                "File read/write error",
+            ),
+            ErrorCode::InsufficientDiskSpace => (
+                "error.insufficient_disk_space",
+                "No hay espacio suficiente en el disco",
+                "Not enough disk space",
+            ),
+            ErrorCode::Http => ("error.http", "Error de red", "Network error"),
+            ErrorCode::DownloadFailed => (
+                "error.download_failed",
+                "La descarga falló",
+                "Download failed",
+            ),
+            ErrorCode::AssetsUnavailable => (
+                "error.assets_unavailable",
+                "No se pudieron descargar algunos assets de ningún servidor",
+                "Some assets could not be downloaded from any host",
+            ),
+            ErrorCode::Sha1Mismatch => (
+                "error.sha1_mismatch",
+                "El archivo descargado está dañado (SHA-1)",
+                "Downloaded file is corrupted (SHA-1)",
+            ),
+            ErrorCode::Sha256Mismatch => (
+                "error.sha256_mismatch",
+                "El archivo descargado está dañado (SHA-256)",
+                "Downloaded file is corrupted (SHA-256)",
+            ),
+            ErrorCode::Sha512Mismatch => (
+                "error.sha512_mismatch",
+                "El archivo descargado está dañado (SHA-512)",
+                "Downloaded file is corrupted (SHA-512)",
+            ),
+            ErrorCode::InvalidMavenCoordinate => (
+                "error.invalid_maven_coordinate",
+                "Coordenada Maven inválida",
+                "Invalid Maven coordinate",
+            ),
+            ErrorCode::PomParse => (
+                "error.pom_parse",
+                "No se pudo leer el POM",
+                "Could not read the POM",
+            ),
+            ErrorCode::Xml => ("error.xml", "XML inválido", "Invalid XML"),
+            ErrorCode::Json => ("error.json", "JSON inválido", "Invalid JSON"),
+            ErrorCode::Nbt => ("error.nbt", "Datos NBT inválidos", "Invalid NBT data"),
+            ErrorCode::InstanceNotFound => (
+                "error.instance_not_found",
+                "No se encontró la instancia",
+                "Instance not found",
+            ),
+            ErrorCode::InstanceAlreadyExists => (
+                "error.instance_already_exists",
+                "La instancia ya existe",
+                "Instance already exists",
+            ),
+            ErrorCode::InstanceAlreadyRunning => (
+                "error.instance_already_running",
+                "La instancia ya está en ejecución",
+                "Instance is already running",
+            ),
+            ErrorCode::HookFailed => (
+                "error.hook_failed",
+                "Falló el hook de la instancia",
+                "Instance hook failed",
+            ),
+            ErrorCode::InstanceNotReady => (
+                "error.instance_not_ready",
+                "La instancia no está lista para iniciarse",
+                "Instance is not ready to launch",
+            ),
+            ErrorCode::InstanceIncomplete => (
+                "error.instance_incomplete",
+                "La instancia está incompleta; reinstálala",
+                "Instance is incomplete; reinstall it",
+            ),
+            ErrorCode::AccountNotFound => (
+                "error.account_not_found",
+                "No se encontró la cuenta",
+                "Account not found",
+            ),
+            ErrorCode::JavaNotFound => (
+                "error.java_not_found",
+                "No se encontró una Java compatible",
+                "No compatible Java found",
+            ),
+            ErrorCode::JavaExecution => (
+                "error.java_execution",
+                "Java no pudo ejecutarse",
+                "Java failed to run",
+            ),
+            ErrorCode::InvalidJvmArgs => (
+                "error.invalid_jvm_args",
+                "Argumentos JVM inválidos",
+                "Invalid JVM arguments",
+            ),
+            ErrorCode::JavaResolutionFailed => (
+                "error.java_resolution_failed",
+                "No se pudo elegir una Java para la instancia",
+                "Could not pick a Java runtime for the instance",
+            ),
+            ErrorCode::PreflightFailed => (
+                "error.preflight_failed",
+                "Fallaron las comprobaciones previas al inicio",
+                "Pre-launch checks failed",
+            ),
+            ErrorCode::ClasspathEmpty => (
+                "error.classpath_empty",
+                "El classpath está vacío",
+                "The classpath is empty",
+            ),
+            ErrorCode::Loader => ("error.loader", "Error del loader", "Loader error"),
+            ErrorCode::LoaderApi => (
+                "error.loader_api",
+                "No se pudo contactar la API del loader",
+                "Loader API unreachable",
+            ),
+            ErrorCode::LoaderProcessorFailed => (
+                "error.loader_processor_failed",
+                "Falló un procesador del loader",
+                "A loader processor failed",
+            ),
+            ErrorCode::Zip => (
+                "error.zip",
+                "Archivo comprimido inválido",
+                "Invalid archive",
+            ),
+            ErrorCode::Cancelled => (
+                "error.cancelled",
+                "Operación cancelada",
+                "Operation cancelled",
+            ),
+            ErrorCode::Message => (
+                "error.message",
+                "No se pudo completar la operación",
+                "The operation could not be completed",
+            ),
+            ErrorCode::Other => ("error.other", "Error inesperado", "Unexpected error"),
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        self.entry().0
+    }
+
+    /// Key without the `error.` prefix, e.g. `preflight_failed`.
+    pub fn name(self) -> &'static str {
+        self.key().trim_start_matches("error.")
+    }
+
+    pub fn text(self, locale: Locale) -> &'static str {
+        let (_, es, en) = self.entry();
+        pick(locale, es, en)
+    }
+}
+
+/// Progress stages reported while creating and launching instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKey {
+    CreateStructureCreated,
+    CreatePreparingVanilla,
+    CreateVanillaInstalled,
+    CreateInstallingLoader,
+    CreateDownloadingAssets,
+    CreateFailed,
+    CreateReady,
+    CreateModpackImported,
+    CreateDownloadingModpack,
+    LaunchValidating,
+    LaunchValidationFailed,
+    LaunchValidated,
+    LaunchPreparationFailed,
+    LaunchPrepared,
+    LaunchPreflightFailed,
+    LaunchPreLaunchHookFailed,
+    LaunchStartingProcess,
+    LaunchProcessFailed,
+    LaunchRunning,
+    LaunchIdle,
+    LaunchExitedWithError,
+    LaunchWaitFailed,
+    LaunchStopped,
+    LaunchCancelled,
+}
+
+impl MessageKey {
+    #[cfg(test)]
+    const ALL: [MessageKey; 24] = [
+        MessageKey::CreateStructureCreated,
+        MessageKey::CreatePreparingVanilla,
+        MessageKey::CreateVanillaInstalled,
+        MessageKey::CreateInstallingLoader,
+        MessageKey::CreateDownloadingAssets,
+        MessageKey::CreateFailed,
+        MessageKey::CreateReady,
+        MessageKey::CreateModpackImported,
+        MessageKey::CreateDownloadingModpack,
+        MessageKey::LaunchValidating,
+        MessageKey::LaunchValidationFailed,
+        MessageKey::LaunchValidated,
+        MessageKey::LaunchPreparationFailed,
+        MessageKey::LaunchPrepared,
+        MessageKey::LaunchPreflightFailed,
+        MessageKey::LaunchPreLaunchHookFailed,
+        MessageKey::LaunchStartingProcess,
+        MessageKey::LaunchProcessFailed,
+        MessageKey::LaunchRunning,
+        MessageKey::LaunchIdle,
+        MessageKey::LaunchExitedWithError,
+        MessageKey::LaunchWaitFailed,
+        MessageKey::LaunchStopped,
+        MessageKey::LaunchCancelled,
+    ];
+
+    /// (key, Spanish, English)
+    fn entry(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            MessageKey::CreateStructureCreated => (
+                "progress.create.structure_created",
+                "Estructura creada",
+                "Folders created",
+            ),
+            MessageKey::CreatePreparingVanilla => (
+                "progress.create.preparing_vanilla",
+                "Preparando Vanilla",
+                "Preparing Vanilla",
+            ),
+            MessageKey::CreateVanillaInstalled => (
+                "progress.create.vanilla_installed",
+                "Vanilla instalado",
+                "Vanilla installed",
+            ),
+            MessageKey::CreateInstallingLoader => (
+                "progress.create.installing_loader",
+                "Instalando loader",
+                "Installing loader",
+            ),
+            MessageKey::CreateDownloadingAssets => (
+                "progress.create.downloading_assets",
+                "Descargando assets",
+                "Downloading assets",
+            ),
+            MessageKey::CreateFailed => (
+                "progress.create.failed",
+                "Error en creación",
+                "Creation failed",
+            ),
+            MessageKey::CreateReady => {
+                ("progress.create.ready", "Instancia lista", "Instance ready")
+            }
+            MessageKey::CreateModpackImported => (
+                "progress.create.modpack_imported",
+                "Modpack importado",
+                "Modpack imported",
+            ),
+            MessageKey::CreateDownloadingModpack => (
+                "progress.create.downloading_modpack",
+                "Descargando archivos del modpack",
+                "Downloading modpack files",
+            ),
+            MessageKey::LaunchValidating => (
+                "progress.launch.validating",
+                "Iniciando validación de instancia",
+                "Validating instance",
+            ),
+            MessageKey::LaunchValidationFailed => (
+                "progress.launch.validation_failed",
+                "Validación fallida",
+                "Validation failed",
+            ),
+            MessageKey::LaunchValidated => (
+                "progress.launch.validated",
+                "Validación completada",
+                "Validation complete",
+            ),
+            MessageKey::LaunchPreparationFailed => (
+                "progress.launch.preparation_failed",
+                "Error en preparación",
+                "Preparation failed",
+            ),
+            MessageKey::LaunchPrepared => (
+                "progress.launch.prepared",
+                "Instalación y verificación completadas",
+                "Installation and checks complete",
+            ),
+            MessageKey::LaunchPreflightFailed => (
+                "progress.launch.preflight_failed",
+                "Preflight fallido",
+                "Preflight checks failed",
+            ),
+            MessageKey::LaunchPreLaunchHookFailed => (
+                "progress.launch.pre_launch_hook_failed",
+                "Hook pre-lanzamiento fallido",
+                "Pre-launch hook failed",
+            ),
+            MessageKey::LaunchStartingProcess => (
+                "progress.launch.starting_process",
+                "Lanzando proceso de Minecraft",
+                "Starting Minecraft",
+            ),
+            MessageKey::LaunchProcessFailed => (
+                "progress.launch.process_failed",
+                "Error al iniciar proceso",
+                "Could not start the process",
+            ),
+            MessageKey::LaunchRunning => (
+                "progress.launch.running",
+                "Instancia en ejecución",
+                "Instance running",
+            ),
+            MessageKey::LaunchIdle => {
+                ("progress.launch.idle", "Pendiente de inicio", "Not started")
+            }
+            MessageKey::LaunchExitedWithError => (
+                "progress.launch.exited_with_error",
+                "Minecraft finalizó con error",
+                "Minecraft exited with an error",
+            ),
+            MessageKey::LaunchWaitFailed => (
+                "progress.launch.wait_failed",
+                "Error de espera del proceso",
+                "Lost track of the game process",
+            ),
+            MessageKey::LaunchStopped => (
+                "progress.launch.stopped",
+                "Instancia detenida",
+                "Instance stopped",
+            ),
+            MessageKey::LaunchCancelled => (
+                "progress.launch.cancelled",
+                "Inicio cancelado",
+                "Launch cancelled",
+            ),
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        self.entry().0
+    }
+
+    pub fn text(self, locale: Locale) -> &'static str {
+        let (_, es, en) = self.entry();
+        pick(locale, es, en)
+    }
+}
+
+impl Serialize for MessageKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn message_keys_are_unique_and_translated() {
+        let keys: HashSet<&str> = MessageKey::ALL.iter().map(|key| key.key()).collect();
+        assert_eq!(keys.len(), MessageKey::ALL.len());
+        for key in MessageKey::ALL {
+            assert!(key.key().starts_with("progress."));
+            assert!(!key.text(Locale::Es).is_empty());
+            assert!(!key.text(Locale::En).is_empty());
+        }
+        assert_eq!(
+            serde_json::to_value(MessageKey::LaunchRunning).unwrap(),
+            "progress.launch.running"
+        );
+    }
+
+    #[test]
+    fn placeholders_take_the_arguments_in_order() {
+        let args = ["a".to_string(), "b".to_string()];
+        assert_eq!(fill("{} y {}", &args), "a y b");
+        assert_eq!(fill("{}-{}-{}", &args), "a-b-{}");
+        assert_eq!(fill("sin argumentos", &args), "sin argumentos");
+        assert_eq!(fill("{}", &["{}".to_string()]), "{}");
+    }
+}
@@ -0,0 +1,916 @@
+// ─── Error Texts ───
+// Messages of `LauncherError::Message`, for failures that have no variant of
+// their own. Like log lines they take `{}` arguments in order; serialized
+// errors carry the key and the arguments.
+
+use std::fmt;
+
+use super::{fill, pick, Locale};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorText {
+    OpenFolderFailed,
+    ElevationFailed,
+    ElevationWindowsOnly,
+    EmptyGroup,
+    EmptyGroupInstances,
+    NoSavedLog,
+    JvmProfileNotFound,
+    JvmProfileNameRequired,
+    JvmProfileBuiltIn,
+    InvalidJavaExecutable,
+    JavaTooOld,
+    MemoryTooLow,
+    InvalidWindowSize,
+    WrapperNotFound,
+    ThumbnailFailed,
+    RuntimeNotInstalled,
+    InstallerNotFound,
+    LocalInstallerUnsupported,
+    NoCompanionApi,
+    NoCompanionVersion,
+    ModNotFromModrinth,
+    ModUpToDate,
+    VersionWithoutFiles,
+    NoRunningProcess,
+    ArchiveWithoutInstanceJson,
+    CloseBeforeRestore,
+    AccountRequired,
+    SetupFailed,
+    ReinstallFailed,
+    InvalidJavaOverride,
+    SettingsSaveFailed,
+    MigrationFailed,
+    ZgcNeedsJava15,
+    ShenandoahNeedsJava12,
+    ShenandoahUnavailableOnOracle,
+    HookCrashed,
+    HookTimedOut,
+    KillFailed,
+    KillCommandFailed,
+    EmbeddedJavaMissing,
+    MainClassMissing,
+    IncompatibleJava,
+    NeoForgeVersionMismatch,
+    LoaderVersionMissing,
+    InvalidServersDat,
+    ServerNameRequired,
+    ServerNotFound,
+    DeepLinkNotInterface,
+    DeepLinkMissingInstance,
+    DeepLinkUnknownAction,
+    DeepLinkIdTooLong,
+    DeepLinkInvalidId,
+    InvalidShortcut,
+    ApplicationsDirNotFound,
+    HomeDirNotFound,
+    ShortcutsUnsupported,
+    IconTooLarge,
+    IconFormatUnsupported,
+    IconDecodeFailed,
+    IconSaveFailed,
+    InvalidScreenshotName,
+    ScreenshotNotFound,
+    ScreenshotTooLarge,
+    ScreenshotReadFailed,
+    InvalidGameOption,
+    ConfigPathNotAllowed,
+    ConfigNotEditable,
+    ConfigNotText,
+    ConfigTooLarge,
+    ConfigIsDirectory,
+    InvalidWorldName,
+    WorldNotFound,
+    BackupAlreadyExists,
+    BackupHasSeveralWorlds,
+    BackupEmpty,
+    BackupOutsideFolder,
+    WorldAlreadyExists,
+    SetupStepDone,
+    SetupStepOutOfOrder,
+    SetupComplete,
+    InvalidModName,
+    ModFileNotFound,
+    FileAlreadyExists,
+    OnlyJarsCanBeDisabled,
+    NotADisabledMod,
+    ModpackEntryMissing,
+    UnsupportedCurseForgeManifest,
+    InvalidCurseForgeLoader,
+    UnsupportedCurseForgeLoader,
+    UnsupportedMrpackGame,
+    UnsupportedMrpackFormat,
+    MrpackWithoutMinecraft,
+    InvalidMrpackPath,
+    MrpackFileWithoutSha512,
+    MrpackFileWithoutDownloads,
+    UnknownLoader,
+    InvalidInstanceStructure,
+    InstanceMetadataIncomplete,
+    LoaderNeedsDeltaButGammaForced,
+    SystemJavaNotFound,
+    NoJavaAssigned,
+    NoGammaJavaAssigned,
+    JavaHomeUnresolved,
+    InvalidBootstrapRuntime,
+    CorruptInstanceNeedsRecovery,
+    JavaRuntimeNotInstalled,
+    InvalidModrinthFileName,
+    SkinNotPng,
+    SkinDecodeFailed,
+    SkinWrongSize,
+    SkinConvertFailed,
+    InvalidAccountUuid,
+    JavaExecutableNotFound,
+    JavaNot64Bit,
+    RuntimeLinkOutside,
+    InvalidRuntimeLink,
+    DownloadLimiterClosed,
+    DataDirOverridden,
+}
+
+impl ErrorText {
+    #[cfg(test)]
+    pub(crate) const ALL: [ErrorText; 118] = [
+        ErrorText::OpenFolderFailed,
+        ErrorText::ElevationFailed,
+        ErrorText::ElevationWindowsOnly,
+        ErrorText::EmptyGroup,
+        ErrorText::EmptyGroupInstances,
+        ErrorText::NoSavedLog,
+        ErrorText::JvmProfileNotFound,
+        ErrorText::JvmProfileNameRequired,
+        ErrorText::JvmProfileBuiltIn,
+        ErrorText::InvalidJavaExecutable,
+        ErrorText::JavaTooOld,
+        ErrorText::MemoryTooLow,
+        ErrorText::InvalidWindowSize,
+        ErrorText::WrapperNotFound,
+        ErrorText::ThumbnailFailed,
+        ErrorText::RuntimeNotInstalled,
+        ErrorText::InstallerNotFound,
+        ErrorText::LocalInstallerUnsupported,
+        ErrorText::NoCompanionApi,
+        ErrorText::NoCompanionVersion,
+        ErrorText::ModNotFromModrinth,
+        ErrorText::ModUpToDate,
+        ErrorText::VersionWithoutFiles,
+        ErrorText::NoRunningProcess,
+        ErrorText::ArchiveWithoutInstanceJson,
+        ErrorText::CloseBeforeRestore,
+        ErrorText::AccountRequired,
+        ErrorText::SetupFailed,
+        ErrorText::ReinstallFailed,
+        ErrorText::InvalidJavaOverride,
+        ErrorText::SettingsSaveFailed,
+        ErrorText::MigrationFailed,
+        ErrorText::ZgcNeedsJava15,
+        ErrorText::ShenandoahNeedsJava12,
+        ErrorText::ShenandoahUnavailableOnOracle,
+        ErrorText::HookCrashed,
+        ErrorText::HookTimedOut,
+        ErrorText::KillFailed,
+        ErrorText::KillCommandFailed,
+        ErrorText::EmbeddedJavaMissing,
+        ErrorText::MainClassMissing,
+        ErrorText::IncompatibleJava,
+        ErrorText::NeoForgeVersionMismatch,
+        ErrorText::LoaderVersionMissing,
+        ErrorText::InvalidServersDat,
+        ErrorText::ServerNameRequired,
+        ErrorText::ServerNotFound,
+        ErrorText::DeepLinkNotInterface,
+        ErrorText::DeepLinkMissingInstance,
+        ErrorText::DeepLinkUnknownAction,
+        ErrorText::DeepLinkIdTooLong,
+        ErrorText::DeepLinkInvalidId,
+        ErrorText::InvalidShortcut,
+        ErrorText::ApplicationsDirNotFound,
+        ErrorText::HomeDirNotFound,
+        ErrorText::ShortcutsUnsupported,
+        ErrorText::IconTooLarge,
+        ErrorText::IconFormatUnsupported,
+        ErrorText::IconDecodeFailed,
+        ErrorText::IconSaveFailed,
+        ErrorText::InvalidScreenshotName,
+        ErrorText::ScreenshotNotFound,
+        ErrorText::ScreenshotTooLarge,
+        ErrorText::ScreenshotReadFailed,
+        ErrorText::InvalidGameOption,
+        ErrorText::ConfigPathNotAllowed,
+        ErrorText::ConfigNotEditable,
+        ErrorText::ConfigNotText,
+        ErrorText::ConfigTooLarge,
+        ErrorText::ConfigIsDirectory,
+        ErrorText::InvalidWorldName,
+        ErrorText::WorldNotFound,
+        ErrorText::BackupAlreadyExists,
+        ErrorText::BackupHasSeveralWorlds,
+        ErrorText::BackupEmpty,
+        ErrorText::BackupOutsideFolder,
+        ErrorText::WorldAlreadyExists,
+        ErrorText::SetupStepDone,
+        ErrorText::SetupStepOutOfOrder,
+        ErrorText::SetupComplete,
+        ErrorText::InvalidModName,
+        ErrorText::ModFileNotFound,
+        ErrorText::FileAlreadyExists,
+        ErrorText::OnlyJarsCanBeDisabled,
+        ErrorText::NotADisabledMod,
+        ErrorText::ModpackEntryMissing,
+        ErrorText::UnsupportedCurseForgeManifest,
+        ErrorText::InvalidCurseForgeLoader,
+        ErrorText::UnsupportedCurseForgeLoader,
+        ErrorText::UnsupportedMrpackGame,
+        ErrorText::UnsupportedMrpackFormat,
+        ErrorText::MrpackWithoutMinecraft,
+        ErrorText::InvalidMrpackPath,
+        ErrorText::MrpackFileWithoutSha512,
+        ErrorText::MrpackFileWithoutDownloads,
+        ErrorText::UnknownLoader,
+        ErrorText::InvalidInstanceStructure,
+        ErrorText::InstanceMetadataIncomplete,
+        ErrorText::LoaderNeedsDeltaButGammaForced,
+        ErrorText::SystemJavaNotFound,
+        ErrorText::NoJavaAssigned,
+        ErrorText::NoGammaJavaAssigned,
+        ErrorText::JavaHomeUnresolved,
+        ErrorText::InvalidBootstrapRuntime,
+        ErrorText::CorruptInstanceNeedsRecovery,
+        ErrorText::JavaRuntimeNotInstalled,
+        ErrorText::InvalidModrinthFileName,
+        ErrorText::SkinNotPng,
+        ErrorText::SkinDecodeFailed,
+        ErrorText::SkinWrongSize,
+        ErrorText::SkinConvertFailed,
+        ErrorText::InvalidAccountUuid,
+        ErrorText::JavaExecutableNotFound,
+        ErrorText::JavaNot64Bit,
+        ErrorText::RuntimeLinkOutside,
+        ErrorText::InvalidRuntimeLink,
+        ErrorText::DownloadLimiterClosed,
+        ErrorText::DataDirOverridden,
+    ];
+
+    /// (key, Spanish, English)
+    fn entry(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            ErrorText::OpenFolderFailed => (
+                "error.text.open_folder_failed",
+                "No se pudo abrir el explorador para {}",
+                "Could not open the file manager for {}",
+            ),
+            ErrorText::ElevationFailed => (
+                "error.text.elevation_failed",
+                "No se pudo solicitar permisos de administrador para eliminar la instancia.",
+                "Could not ask for administrator rights to delete the instance.",
+            ),
+            ErrorText::ElevationWindowsOnly => (
+                "error.text.elevation_windows_only",
+                "La elevación de privilegios para eliminar instancias sólo está disponible en Windows.",
+                "Deleting instances with administrator rights is only available on Windows.",
+            ),
+            ErrorText::EmptyGroup => (
+                "error.text.empty_group",
+                "El grupo no puede estar vacío",
+                "The group cannot be empty",
+            ),
+            ErrorText::EmptyGroupInstances => (
+                "error.text.empty_group_instances",
+                "No hay instancias en el grupo '{}'",
+                "There are no instances in the group '{}'",
+            ),
+            ErrorText::NoSavedLog => (
+                "error.text.no_saved_log",
+                "La instancia {} todavía no tiene un log guardado",
+                "The instance {} has no saved log yet",
+            ),
+            ErrorText::JvmProfileNotFound => (
+                "error.text.jvm_profile_not_found",
+                "No existe el perfil JVM '{}'",
+                "There is no JVM profile named '{}'",
+            ),
+            ErrorText::JvmProfileNameRequired => (
+                "error.text.jvm_profile_name_required",
+                "El perfil JVM necesita un nombre",
+                "The JVM profile needs a name",
+            ),
+            ErrorText::JvmProfileBuiltIn => (
+                "error.text.jvm_profile_built_in",
+                "El perfil JVM '{}' viene incluido y no se puede sobrescribir",
+                "The JVM profile '{}' is built in and cannot be overwritten",
+            ),
+            ErrorText::InvalidJavaExecutable => (
+                "error.text.invalid_java_executable",
+                "'{}' no es un ejecutable de Java válido",
+                "'{}' is not a valid Java executable",
+            ),
+            ErrorText::JavaTooOld => (
+                "error.text.java_too_old",
+                "'{}' es Java {} pero {} necesita Java {}",
+                "'{}' is Java {} but {} needs Java {}",
+            ),
+            ErrorText::MemoryTooLow => (
+                "error.text.memory_too_low",
+                "La memoria mínima permitida es 512 MB",
+                "The minimum memory allowed is 512 MB",
+            ),
+            ErrorText::InvalidWindowSize => (
+                "error.text.invalid_window_size",
+                "El tamaño de ventana {}x{} no es válido (cada lado entre {} y {})",
+                "The window size {}x{} is not valid (each side between {} and {})",
+            ),
+            ErrorText::WrapperNotFound => (
+                "error.text.wrapper_not_found",
+                "No se encontró el wrapper '{}' en el PATH",
+                "The wrapper '{}' was not found in the PATH",
+            ),
+            ErrorText::ThumbnailFailed => (
+                "error.text.thumbnail_failed",
+                "No se pudo generar la miniatura: {}",
+                "Could not create the thumbnail: {}",
+            ),
+            ErrorText::RuntimeNotInstalled => (
+                "error.text.runtime_not_installed",
+                "El runtime {} no está instalado",
+                "The runtime {} is not installed",
+            ),
+            ErrorText::InstallerNotFound => (
+                "error.text.installer_not_found",
+                "No se encontró el instalador {}",
+                "The installer {} was not found",
+            ),
+            ErrorText::LocalInstallerUnsupported => (
+                "error.text.local_installer_unsupported",
+                "La instalación desde un JAR local no está disponible para {}",
+                "Installing from a local JAR is not available for {}",
+            ),
+            ErrorText::NoCompanionApi => (
+                "error.text.no_companion_api",
+                "El loader {} no tiene un mod de API que instalar",
+                "The {} loader has no API mod to install",
+            ),
+            ErrorText::NoCompanionVersion => (
+                "error.text.no_companion_version",
+                "No hay una versión de {} para Minecraft {}",
+                "There is no version of {} for Minecraft {}",
+            ),
+            ErrorText::ModNotFromModrinth => (
+                "error.text.mod_not_from_modrinth",
+                "El mod {} no está instalado desde Modrinth en esta instancia",
+                "The mod {} was not installed from Modrinth in this instance",
+            ),
+            ErrorText::ModUpToDate => (
+                "error.text.mod_up_to_date",
+                "{} ya está actualizado",
+                "{} is already up to date",
+            ),
+            ErrorText::VersionWithoutFiles => (
+                "error.text.version_without_files",
+                "La versión {} de {} no tiene archivos",
+                "Version {} of {} has no files",
+            ),
+            ErrorText::NoRunningProcess => (
+                "error.text.no_running_process",
+                "No hay proceso activo para la instancia {}",
+                "There is no running process for the instance {}",
+            ),
+            ErrorText::ArchiveWithoutInstanceJson => (
+                "error.text.archive_without_instance_json",
+                "El archivo no contiene instance.json: {}",
+                "The archive has no instance.json: {}",
+            ),
+            ErrorText::CloseBeforeRestore => (
+                "error.text.close_before_restore",
+                "Cierra la instancia antes de restaurar un mundo",
+                "Close the instance before restoring a world",
+            ),
+            ErrorText::AccountRequired => (
+                "error.text.account_required",
+                "Indica una cuenta guardada o los datos de la cuenta",
+                "Pick a saved account or give the account details",
+            ),
+            ErrorText::SetupFailed => (
+                "error.text.setup_failed",
+                "No se pudo completar la instalación inicial: {}",
+                "The initial setup could not be completed: {}",
+            ),
+            ErrorText::ReinstallFailed => (
+                "error.text.reinstall_failed",
+                "No se pudo reinstalar el launcher: {}",
+                "Could not reinstall the launcher: {}",
+            ),
+            ErrorText::InvalidJavaOverride => (
+                "error.text.invalid_java_override",
+                "Ruta Java inválida para override manual: {}",
+                "Invalid Java path for the manual override: {}",
+            ),
+            ErrorText::SettingsSaveFailed => (
+                "error.text.settings_save_failed",
+                "No se pudo guardar launcher_settings.json: {}",
+                "Could not save launcher_settings.json: {}",
+            ),
+            ErrorText::MigrationFailed => (
+                "error.text.migration_failed",
+                "No se pudo migrar el launcher: {}",
+                "Could not move the launcher: {}",
+            ),
+            ErrorText::ZgcNeedsJava15 => (
+                "error.text.zgc_needs_java_15",
+                "ZGC requiere Java 15 o superior (la instancia usa Java {})",
+                "ZGC needs Java 15 or newer (the instance uses Java {})",
+            ),
+            ErrorText::ShenandoahNeedsJava12 => (
+                "error.text.shenandoah_needs_java_12",
+                "Shenandoah requiere Java 12 o superior (la instancia usa Java {})",
+                "Shenandoah needs Java 12 or newer (the instance uses Java {})",
+            ),
+            ErrorText::ShenandoahUnavailableOnOracle => (
+                "error.text.shenandoah_unavailable_on_oracle",
+                "El runtime de Oracle no incluye Shenandoah; usa una build OpenJDK (Temurin, Zulu…)",
+                "Oracle's runtime does not include Shenandoah; use an OpenJDK build (Temurin, Zulu…)",
+            ),
+            ErrorText::HookCrashed => (
+                "error.text.hook_crashed",
+                "El hook terminó inesperadamente: {}",
+                "The hook ended unexpectedly: {}",
+            ),
+            ErrorText::HookTimedOut => (
+                "error.text.hook_timed_out",
+                "El hook {} no terminó en {} s y se detuvo",
+                "The {} hook did not finish within {} s and was stopped",
+            ),
+            ErrorText::KillFailed => (
+                "error.text.kill_failed",
+                "No se pudo finalizar proceso {}: {}",
+                "Could not end process {}: {}",
+            ),
+            ErrorText::KillCommandFailed => (
+                "error.text.kill_command_failed",
+                "El comando para cerrar el proceso {} devolvió código {}",
+                "The command closing process {} returned code {}",
+            ),
+            ErrorText::EmbeddedJavaMissing => (
+                "error.text.embedded_java_missing",
+                "Java embebido no encontrado en {}",
+                "Embedded Java not found at {}",
+            ),
+            ErrorText::MainClassMissing => (
+                "error.text.main_class_missing",
+                "La instancia no tiene main class",
+                "Main class not set on instance",
+            ),
+            ErrorText::IncompatibleJava => (
+                "error.text.incompatible_java",
+                "Java incompatible para Minecraft {}: requerida {}, detectada {}",
+                "Incompatible Java for Minecraft {}: {} required, {} found",
+            ),
+            ErrorText::NeoForgeVersionMismatch => (
+                "error.text.neoforge_version_mismatch",
+                "NeoForge {} no corresponde a Minecraft {}",
+                "NeoForge {} is not for Minecraft {}",
+            ),
+            ErrorText::LoaderVersionMissing => (
+                "error.text.loader_version_missing",
+                "La versión {} de {} no existe para Minecraft {}",
+                "Version {} of {} does not exist for Minecraft {}",
+            ),
+            ErrorText::InvalidServersDat => (
+                "error.text.invalid_servers_dat",
+                "servers.dat inválido: {}",
+                "Invalid servers.dat: {}",
+            ),
+            ErrorText::ServerNameRequired => (
+                "error.text.server_name_required",
+                "El servidor necesita un nombre y una dirección",
+                "A server needs a name and an address",
+            ),
+            ErrorText::ServerNotFound => (
+                "error.text.server_not_found",
+                "No existe un servidor en la posición {}",
+                "There is no server at position {}",
+            ),
+            ErrorText::DeepLinkNotInterface => (
+                "error.text.deep_link_not_interface",
+                "Enlace no válido «{}»: no es un enlace de Interface",
+                "Invalid link «{}»: not an Interface link",
+            ),
+            ErrorText::DeepLinkMissingInstance => (
+                "error.text.deep_link_missing_instance",
+                "Enlace no válido «{}»: falta el id de la instancia",
+                "Invalid link «{}»: the instance id is missing",
+            ),
+            ErrorText::DeepLinkUnknownAction => (
+                "error.text.deep_link_unknown_action",
+                "Enlace no válido «{}»: acción desconocida",
+                "Invalid link «{}»: unknown action",
+            ),
+            ErrorText::DeepLinkIdTooLong => (
+                "error.text.deep_link_id_too_long",
+                "Enlace no válido «{}»: el id de la instancia es demasiado largo",
+                "Invalid link «{}»: the instance id is too long",
+            ),
+            ErrorText::DeepLinkInvalidId => (
+                "error.text.deep_link_invalid_id",
+                "Enlace no válido «{}»: el id de la instancia contiene caracteres no permitidos",
+                "Invalid link «{}»: the instance id contains characters that are not allowed",
+            ),
+            ErrorText::InvalidShortcut => (
+                "error.text.invalid_shortcut",
+                "El acceso directo «{}» tiene un nombre o comando inválido",
+                "The shortcut «{}» has an invalid name or command",
+            ),
+            ErrorText::ApplicationsDirNotFound => (
+                "error.text.applications_dir_not_found",
+                "No se encontró la carpeta de aplicaciones",
+                "The applications folder was not found",
+            ),
+            ErrorText::HomeDirNotFound => (
+                "error.text.home_dir_not_found",
+                "No se encontró la carpeta personal",
+                "The home folder was not found",
+            ),
+            ErrorText::ShortcutsUnsupported => (
+                "error.text.shortcuts_unsupported",
+                "Los accesos directos no están disponibles en este sistema",
+                "Shortcuts are not available on this system",
+            ),
+            ErrorText::IconTooLarge => (
+                "error.text.icon_too_large",
+                "El icono supera el tamaño máximo de {} KB",
+                "The icon exceeds the maximum size of {} KB",
+            ),
+            ErrorText::IconFormatUnsupported => (
+                "error.text.icon_format_unsupported",
+                "El icono debe ser una imagen PNG o JPEG",
+                "The icon must be a PNG or JPEG image",
+            ),
+            ErrorText::IconDecodeFailed => (
+                "error.text.icon_decode_failed",
+                "No se pudo decodificar el icono: {}",
+                "Could not decode the icon: {}",
+            ),
+            ErrorText::IconSaveFailed => (
+                "error.text.icon_save_failed",
+                "No se pudo guardar el icono en {}: {}",
+                "Could not save the icon to {}: {}",
+            ),
+            ErrorText::InvalidScreenshotName => (
+                "error.text.invalid_screenshot_name",
+                "Nombre de captura inválido: {}",
+                "Invalid screenshot name: {}",
+            ),
+            ErrorText::ScreenshotNotFound => (
+                "error.text.screenshot_not_found",
+                "No se encontró la captura {}",
+                "Screenshot {} not found",
+            ),
+            ErrorText::ScreenshotTooLarge => (
+                "error.text.screenshot_too_large",
+                "La captura {} es demasiado grande para la vista previa",
+                "Screenshot {} is too large to preview",
+            ),
+            ErrorText::ScreenshotReadFailed => (
+                "error.text.screenshot_read_failed",
+                "No se pudo leer la captura {}: {}",
+                "Could not read screenshot {}: {}",
+            ),
+            ErrorText::InvalidGameOption => (
+                "error.text.invalid_game_option",
+                "Opción inválida para {}: «{}»",
+                "Invalid option for {}: «{}»",
+            ),
+            ErrorText::ConfigPathNotAllowed => (
+                "error.text.config_path_not_allowed",
+                "Ruta de configuración no permitida: {}",
+                "Configuration path not allowed: {}",
+            ),
+            ErrorText::ConfigNotEditable => (
+                "error.text.config_not_editable",
+                "{} no es un archivo de texto editable",
+                "{} is not an editable text file",
+            ),
+            ErrorText::ConfigNotText => (
+                "error.text.config_not_text",
+                "{} no es un archivo de texto",
+                "{} is not a text file",
+            ),
+            ErrorText::ConfigTooLarge => (
+                "error.text.config_too_large",
+                "{} supera el tamaño máximo de {} KB",
+                "{} exceeds the maximum size of {} KB",
+            ),
+            ErrorText::ConfigIsDirectory => (
+                "error.text.config_is_directory",
+                "{} es una carpeta",
+                "{} is a folder",
+            ),
+            ErrorText::InvalidWorldName => (
+                "error.text.invalid_world_name",
+                "Nombre de mundo inválido: {}",
+                "Invalid world name: {}",
+            ),
+            ErrorText::WorldNotFound => (
+                "error.text.world_not_found",
+                "No existe el mundo '{}'",
+                "World '{}' does not exist",
+            ),
+            ErrorText::BackupAlreadyExists => (
+                "error.text.backup_already_exists",
+                "Ya existe una copia de '{}' con la misma marca de tiempo",
+                "A backup of '{}' with the same timestamp already exists",
+            ),
+            ErrorText::BackupHasSeveralWorlds => (
+                "error.text.backup_has_several_worlds",
+                "La copia contiene más de un mundo",
+                "The backup contains more than one world",
+            ),
+            ErrorText::BackupEmpty => (
+                "error.text.backup_empty",
+                "La copia de seguridad está vacía",
+                "The backup is empty",
+            ),
+            ErrorText::BackupOutsideFolder => (
+                "error.text.backup_outside_folder",
+                "La copia {} no está en la carpeta de copias de la instancia",
+                "Backup {} is not in the instance's backup folder",
+            ),
+            ErrorText::WorldAlreadyExists => (
+                "error.text.world_already_exists",
+                "El mundo '{}' ya existe; confirma la sobrescritura para restaurarlo",
+                "World '{}' already exists; confirm overwriting to restore it",
+            ),
+            ErrorText::SetupStepDone => (
+                "error.text.setup_step_done",
+                "El paso {} ya se completó; el siguiente es {}",
+                "Step {} is already done; the next one is {}",
+            ),
+            ErrorText::SetupStepOutOfOrder => (
+                "error.text.setup_step_out_of_order",
+                "El paso {} no puede ejecutarse antes de {}",
+                "Step {} cannot run before {}",
+            ),
+            ErrorText::SetupComplete => (
+                "error.text.setup_complete",
+                "La configuración inicial ya está completa",
+                "The initial setup is already complete",
+            ),
+            ErrorText::InvalidModName => (
+                "error.text.invalid_mod_name",
+                "Nombre de mod inválido: {}",
+                "Invalid mod name: {}",
+            ),
+            ErrorText::ModFileNotFound => (
+                "error.text.mod_file_not_found",
+                "No se encontró el mod {}",
+                "Mod {} not found",
+            ),
+            ErrorText::FileAlreadyExists => (
+                "error.text.file_already_exists",
+                "Ya existe un archivo {}",
+                "A file {} already exists",
+            ),
+            ErrorText::OnlyJarsCanBeDisabled => (
+                "error.text.only_jars_can_be_disabled",
+                "Solo se pueden desactivar archivos .jar: {}",
+                "Only .jar files can be disabled: {}",
+            ),
+            ErrorText::NotADisabledMod => (
+                "error.text.not_a_disabled_mod",
+                "El archivo no es un mod desactivado: {}",
+                "The file is not a disabled mod: {}",
+            ),
+            ErrorText::ModpackEntryMissing => (
+                "error.text.modpack_entry_missing",
+                "El modpack no contiene {}: {}",
+                "The modpack does not contain {}: {}",
+            ),
+            ErrorText::UnsupportedCurseForgeManifest => (
+                "error.text.unsupported_curseforge_manifest",
+                "Tipo de manifest CurseForge no soportado: {}",
+                "Unsupported CurseForge manifest type: {}",
+            ),
+            ErrorText::InvalidCurseForgeLoader => (
+                "error.text.invalid_curseforge_loader",
+                "Loader CurseForge inválido: {}",
+                "Invalid CurseForge loader: {}",
+            ),
+            ErrorText::UnsupportedCurseForgeLoader => (
+                "error.text.unsupported_curseforge_loader",
+                "Loader CurseForge no soportado: {}",
+                "Unsupported CurseForge loader: {}",
+            ),
+            ErrorText::UnsupportedMrpackGame => (
+                "error.text.unsupported_mrpack_game",
+                "Juego del mrpack no soportado: {}",
+                "Unsupported mrpack game: {}",
+            ),
+            ErrorText::UnsupportedMrpackFormat => (
+                "error.text.unsupported_mrpack_format",
+                "Versión de formato mrpack no soportada: {}",
+                "Unsupported mrpack format version: {}",
+            ),
+            ErrorText::MrpackWithoutMinecraft => (
+                "error.text.mrpack_without_minecraft",
+                "El mrpack no declara versión de Minecraft",
+                "The mrpack does not declare a Minecraft version",
+            ),
+            ErrorText::InvalidMrpackPath => (
+                "error.text.invalid_mrpack_path",
+                "Ruta inválida en el mrpack: {}",
+                "Invalid path in the mrpack: {}",
+            ),
+            ErrorText::MrpackFileWithoutSha512 => (
+                "error.text.mrpack_file_without_sha512",
+                "El archivo {} no declara sha512",
+                "The file {} does not declare a sha512",
+            ),
+            ErrorText::MrpackFileWithoutDownloads => (
+                "error.text.mrpack_file_without_downloads",
+                "El archivo {} no tiene URLs de descarga",
+                "The file {} has no download URLs",
+            ),
+            ErrorText::UnknownLoader => (
+                "error.text.unknown_loader",
+                "Loader desconocido: {}",
+                "Unknown loader: {}",
+            ),
+            ErrorText::InvalidInstanceStructure => (
+                "error.text.invalid_instance_structure",
+                "Estructura inválida: {} no es un directorio",
+                "Invalid structure: {} is not a directory",
+            ),
+            ErrorText::InstanceMetadataIncomplete => (
+                "error.text.instance_metadata_incomplete",
+                "La instancia no tiene main class o versión de Java requerida",
+                "The instance has no main class or required Java version",
+            ),
+            ErrorText::LoaderNeedsDeltaButGammaForced => (
+                "error.text.loader_needs_delta_but_gamma_forced",
+                "El loader requiere Delta para bootstrap, pero el usuario forzó solo Gamma.",
+                "The loader needs Delta to bootstrap, but only Gamma was forced.",
+            ),
+            ErrorText::SystemJavaNotFound => (
+                "error.text.system_java_not_found",
+                "Preferencia Java=System configurada pero no se encontró una Java compatible en PATH.",
+                "Java preference is System but no compatible Java was found on PATH.",
+            ),
+            ErrorText::NoJavaAssigned => (
+                "error.text.no_java_assigned",
+                "No hay Java asignada a la instancia",
+                "The instance has no Java assigned",
+            ),
+            ErrorText::NoGammaJavaAssigned => (
+                "error.text.no_gamma_java_assigned",
+                "No hay Java Gamma asignada a la instancia",
+                "The instance has no Gamma Java assigned",
+            ),
+            ErrorText::JavaHomeUnresolved => (
+                "error.text.java_home_unresolved",
+                "No se pudo resolver JAVA_HOME para bootstrap",
+                "Could not resolve JAVA_HOME for bootstrap",
+            ),
+            ErrorText::InvalidBootstrapRuntime => (
+                "error.text.invalid_bootstrap_runtime",
+                "Runtime de bootstrap inválido ({}): {}",
+                "Invalid bootstrap runtime ({}): {}",
+            ),
+            ErrorText::CorruptInstanceNeedsRecovery => (
+                "error.text.corrupt_instance_needs_recovery",
+                "instance.json dañado; recupera la instancia antes de iniciarla",
+                "instance.json is damaged; recover the instance before launching it",
+            ),
+            ErrorText::JavaRuntimeNotInstalled => (
+                "error.text.java_runtime_not_installed",
+                "No hay un runtime de Java {} instalado; inicia la instancia una vez para instalarlo.",
+                "No Java {} runtime is installed; launch the instance once to install it.",
+            ),
+            ErrorText::InvalidModrinthFileName => (
+                "error.text.invalid_modrinth_file_name",
+                "Nombre de archivo inválido en Modrinth: {}",
+                "Invalid file name on Modrinth: {}",
+            ),
+            ErrorText::SkinNotPng => (
+                "error.text.skin_not_png",
+                "La skin debe ser una imagen PNG",
+                "The skin must be a PNG image",
+            ),
+            ErrorText::SkinDecodeFailed => (
+                "error.text.skin_decode_failed",
+                "No se pudo decodificar la skin: {}",
+                "Could not decode the skin: {}",
+            ),
+            ErrorText::SkinWrongSize => (
+                "error.text.skin_wrong_size",
+                "La skin debe medir 64x64 o 64x32 píxeles (mide {}x{})",
+                "The skin must be 64x64 or 64x32 pixels (it is {}x{})",
+            ),
+            ErrorText::SkinConvertFailed => (
+                "error.text.skin_convert_failed",
+                "No se pudo convertir la skin: {}",
+                "Could not convert the skin: {}",
+            ),
+            ErrorText::InvalidAccountUuid => (
+                "error.text.invalid_account_uuid",
+                "UUID de cuenta inválido: {}",
+                "Invalid account UUID: {}",
+            ),
+            ErrorText::JavaExecutableNotFound => (
+                "error.text.java_executable_not_found",
+                "No se encontró un Java ejecutable en {}",
+                "No Java executable was found in {}",
+            ),
+            ErrorText::JavaNot64Bit => (
+                "error.text.java_not_64_bit",
+                "El Java de {} no es de 64 bits",
+                "The Java in {} is not 64-bit",
+            ),
+            ErrorText::RuntimeLinkOutside => (
+                "error.text.runtime_link_outside",
+                "El runtime contiene un enlace fuera de su carpeta: {}",
+                "The runtime contains a link outside its folder: {}",
+            ),
+            ErrorText::InvalidRuntimeLink => (
+                "error.text.invalid_runtime_link",
+                "Enlace inválido en el runtime: {}",
+                "Invalid link in the runtime: {}",
+            ),
+            ErrorText::DownloadLimiterClosed => (
+                "error.text.download_limiter_closed",
+                "El limitador de descargas se cerró: {}",
+                "Download limiter closed: {}",
+            ),
+            ErrorText::DataDirOverridden => (
+                "error.text.data_dir_overridden",
+                "La carpeta de datos la fija INTERFACE_DATA_DIR ({}); quita la variable para migrar el launcher",
+                "The data dir is set by INTERFACE_DATA_DIR ({}); unset it to move the launcher",
+            ),
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        self.entry().0
+    }
+
+    /// The text with its `{}` placeholders.
+    pub fn template(self, locale: Locale) -> &'static str {
+        let (_, es, en) = self.entry();
+        pick(locale, es, en)
+    }
+}
+
+/// A catalog text with its arguments. `Display` uses the default locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub text: ErrorText,
+    pub args: Vec<String>,
+}
+
+impl Message {
+    pub fn new(text: ErrorText, args: &[&dyn fmt::Display]) -> Self {
+        Self {
+            text,
+            args: args.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    pub fn render(&self, locale: Locale) -> String {
+        fill(self.text.template(locale), &self.args)
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Locale::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn error_texts_are_unique_and_both_texts_take_the_same_arguments() {
+        let keys: HashSet<&str> = ErrorText::ALL.iter().map(|text| text.key()).collect();
+        assert_eq!(keys.len(), ErrorText::ALL.len());
+        for text in ErrorText::ALL {
+            assert!(text.key().starts_with("error.text."));
+            assert_eq!(
+                text.template(Locale::Es).matches("{}").count(),
+                text.template(Locale::En).matches("{}").count(),
+                "{text:?}"
+            );
+        }
+
+        let message = Message::new(ErrorText::InvalidWindowSize, &[&0, &480, &320, &7680]);
+        assert_eq!(
+            message.to_string(),
+            "El tamaño de ventana 0x480 no es válido (cada lado entre 320 y 7680)"
+        );
+        assert_eq!(
+            message.render(Locale::En),
+            "The window size 0x480 is not valid (each side between 320 and 7680)"
+        );
+    }
+}
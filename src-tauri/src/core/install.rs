@@ -0,0 +1,827 @@
+// ─── Install Pipeline ───
+// Vanilla + loader + assets installation shared by instance creation, modpack
+// import and the launch preparation. Libraries and assets download side by
+// side and report weighted progress through `InstallProgress`.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::core::assets::{shared_store, AssetManager, AssetSyncReport};
+use crate::core::disk_space;
+use crate::core::downloader::Downloader;
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::{ErrorText, LogKey, MessageKey};
+use crate::core::install_progress::{self, InstallPhase, InstallProgress};
+use crate::core::instance::{Instance, InstanceState, LoaderType};
+use crate::core::launch::events::{
+    emit_create_error, emit_create_log, emit_create_message, emit_create_progress,
+    emit_create_progress_label,
+};
+use crate::core::launch::preflight::{recommend_latest_loader_version, validate_or_resolve_java};
+use crate::core::loaders;
+use crate::core::mirrors::DownloadMirrors;
+use crate::core::state::AppState;
+use crate::core::version::{
+    canonicalize_libraries, merge_resolved_libraries, VersionJson, VersionManifest,
+};
+
+/// Race a preparation phase against the launch's cancellation token.
+pub(crate) async fn run_cancellable<T>(
+    cancel: &CancellationToken,
+    phase: impl std::future::Future<Output = Result<T, LauncherError>>,
+) -> Result<T, LauncherError> {
+    if cancel.is_cancelled() {
+        return Err(LauncherError::Cancelled);
+    }
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(LauncherError::Cancelled),
+        result = phase => result,
+    }
+}
+
+/// Results of the library phase of a launch-time install.
+pub(crate) struct InstalledGame {
+    vanilla: loaders::LoaderInstallResult,
+    /// Newer loader build picked for Java 21+ Forge / NeoForge, if any.
+    loader_version: Option<String>,
+    loader: Option<loaders::LoaderInstallResult>,
+}
+
+/// Headroom for what the version JSON does not list: loader libraries,
+/// extracted natives and processor output.
+pub(crate) const INSTALL_DISK_MARGIN_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Published vanilla version JSON of `minecraft_version`; `None` for versions
+/// the manifest lacks, which the installer reports itself.
+async fn fetch_version_json(
+    client: &reqwest::Client,
+    metadata_cache: &crate::core::metadata_cache::MetadataCache,
+    mirrors: &DownloadMirrors,
+    minecraft_version: &str,
+) -> Result<Option<VersionJson>, LauncherError> {
+    let (manifest, _) = VersionManifest::fetch_cached(client, metadata_cache, mirrors).await?;
+    let Some(entry) = manifest.find_version(minecraft_version) else {
+        return Ok(None);
+    };
+    let (version_json, _) =
+        VersionJson::fetch_cached(client, metadata_cache, minecraft_version, &entry.url).await?;
+    Ok(Some(version_json))
+}
+
+/// Check the data-dir disk can hold the install and return the asset index
+/// URL, so the asset phase can start next to the libraries. Both come from
+/// the installed version JSON, or from one fetch when there is none yet.
+pub(crate) async fn prepare_install(
+    state: &AppState,
+    minecraft_version: &str,
+    installed_version_json: Option<&VersionJson>,
+    libs_dir: &Path,
+    assets_dir: &Path,
+) -> Result<Option<String>, LauncherError> {
+    let fetched = match installed_version_json {
+        Some(_) => None,
+        None => {
+            fetch_version_json(
+                &state.http_client,
+                &state.metadata_cache(),
+                &state.downloader.mirrors(),
+                minecraft_version,
+            )
+            .await?
+        }
+    };
+    let Some(version_json) = installed_version_json.or(fetched.as_ref()) else {
+        return Ok(None);
+    };
+    let needed =
+        version_json.estimated_download_bytes(libs_dir, assets_dir) + INSTALL_DISK_MARGIN_BYTES;
+    disk_space::ensure_space(&state.data_dir, needed)?;
+    Ok(version_json
+        .asset_index
+        .as_ref()
+        .map(|asset_index| asset_index.url.clone()))
+}
+
+/// Install Vanilla, then the loader, without touching the instance so the
+/// phase can run next to the asset download.
+pub(crate) async fn install_game_libraries(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &Instance,
+    runtime_root: &Path,
+    libs_dir: &Path,
+    cancel: &CancellationToken,
+    progress: Option<&Arc<InstallProgress>>,
+) -> Result<InstalledGame, LauncherError> {
+    let client = &state.http_client;
+    let metadata_cache = &state.metadata_cache();
+    let vanilla_installer = loaders::Installer::new(&LoaderType::Vanilla, client.clone());
+    let libraries_downloader =
+        install_progress::phase_downloader(progress, &state.downloader, InstallPhase::Libraries);
+    let vanilla = run_cancellable(
+        cancel,
+        vanilla_installer.install(loaders::InstallContext {
+            minecraft_version: &instance.minecraft_version,
+            loader_version: "",
+            instance_dir: runtime_root,
+            libs_dir,
+            downloader: &libraries_downloader,
+            http_client: client,
+            resolve_transitive: false,
+            metadata_cache,
+            on_step: None,
+            runtime: state.runtime_options(),
+            data_dir: &state.data_dir,
+            local_installer: None,
+        }),
+    )
+    .await?;
+    complete_phases(progress, &[InstallPhase::Libraries]);
+
+    if instance.loader == LoaderType::Vanilla {
+        complete_phases(
+            progress,
+            &[InstallPhase::Loader, InstallPhase::LoaderProcessors],
+        );
+        return Ok(InstalledGame {
+            vanilla,
+            loader_version: None,
+            loader: None,
+        });
+    }
+
+    let mut recommended_version = None;
+    if vanilla
+        .java_major
+        .is_some_and(|java_major| java_major >= 21)
+        && matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge)
+    {
+        recommended_version = run_cancellable(
+            cancel,
+            recommend_latest_loader_version(events, state, instance),
+        )
+        .await?;
+    }
+
+    let mut loader = None;
+    if let Some(loader_version) = recommended_version
+        .as_ref()
+        .or(instance.loader_version.as_ref())
+    {
+        let installer = loaders::Installer::new(&instance.loader, client.clone());
+        let loader_downloader =
+            install_progress::phase_downloader(progress, &state.downloader, InstallPhase::Loader);
+        let on_processor = |step: usize, total: usize, _: &str| {
+            if let Some(progress) = progress {
+                progress.step(InstallPhase::LoaderProcessors, step, total);
+            }
+        };
+        loader = Some(
+            run_cancellable(
+                cancel,
+                installer.install(loaders::InstallContext {
+                    minecraft_version: &instance.minecraft_version,
+                    loader_version,
+                    instance_dir: runtime_root,
+                    libs_dir,
+                    downloader: &loader_downloader,
+                    http_client: client,
+                    resolve_transitive: true,
+                    metadata_cache,
+                    on_step: Some(&on_processor),
+                    runtime: state.runtime_options(),
+                    data_dir: &state.data_dir,
+                    local_installer: None,
+                }),
+            )
+            .await?,
+        );
+    }
+    complete_phases(
+        progress,
+        &[InstallPhase::Loader, InstallPhase::LoaderProcessors],
+    );
+
+    Ok(InstalledGame {
+        vanilla,
+        loader_version: recommended_version,
+        loader,
+    })
+}
+
+pub(crate) fn complete_phases(progress: Option<&Arc<InstallProgress>>, phases: &[InstallPhase]) {
+    if let Some(progress) = progress {
+        for phase in phases {
+            progress.complete(*phase);
+        }
+    }
+}
+
+/// Run the library and asset phases of an install side by side. Both draw
+/// from the downloader's shared semaphore, so the total number of requests
+/// stays bounded; the first error drops the other phase.
+pub(crate) async fn download_phases<L, A>(
+    libraries: impl std::future::Future<Output = Result<L, LauncherError>>,
+    assets: impl std::future::Future<Output = Result<A, LauncherError>>,
+) -> Result<(L, A), LauncherError> {
+    tokio::try_join!(libraries, assets)
+}
+
+/// Fetch the assets of `index_url` into `assets_dir`. With the
+/// `shared_assets` setting the objects are downloaded once into the launcher
+/// store and linked into the instance; versions up to 1.7.2 keep their own
+/// copy because their legacy layouts are built inside the instance.
+pub(crate) async fn sync_instance_assets(
+    state: &AppState,
+    minecraft_version: &str,
+    index_url: &str,
+    assets_dir: &Path,
+    downloader: &Downloader,
+    cancel: Option<&CancellationToken>,
+) -> Result<AssetSyncReport, LauncherError> {
+    if !state.launcher_settings.shared_assets
+        || crate::core::assets::uses_legacy_asset_index(minecraft_version)
+    {
+        return AssetManager::download_assets(index_url, assets_dir, downloader, cancel).await;
+    }
+
+    let store = state.assets_dir();
+    let report = AssetManager::download_assets(index_url, &store, downloader, cancel).await?;
+    let index_id = index_url
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".json")
+        .to_string();
+    let assets_dir = assets_dir.to_path_buf();
+    let shared = tokio::task::spawn_blocking(move || {
+        shared_store::link_index_into(&store, &assets_dir, &index_id)
+    })
+    .await
+    .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+    info!(
+        "Shared assets: {} linked, {} copied, {} already shared",
+        shared.linked, shared.copied, shared.already_shared
+    );
+    Ok(report)
+}
+
+/// `progress`, when given, follows the library, loader and asset downloads.
+pub(crate) async fn prepare_instance_for_launch(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &mut Instance,
+    cancel: &CancellationToken,
+    progress: Option<&Arc<InstallProgress>>,
+) -> Result<(), LauncherError> {
+    let runtime_root = instance.runtime_root_dir();
+    tokio::fs::create_dir_all(&runtime_root)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: runtime_root.clone(),
+            source,
+        })?;
+
+    let game_dir = instance.game_dir();
+    tokio::fs::create_dir_all(&game_dir)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: game_dir.clone(),
+            source,
+        })?;
+    let assets_dir = game_dir.join("assets");
+    tokio::fs::create_dir_all(&assets_dir)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: assets_dir.clone(),
+            source,
+        })?;
+    let libs_dir = state.libraries_dir();
+    tokio::fs::create_dir_all(&libs_dir)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: libs_dir.clone(),
+            source,
+        })?;
+
+    let has_invalid_loader_main_class =
+        matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge)
+            && instance
+                .main_class
+                .as_deref()
+                .is_some_and(|mc| mc == "net.minecraft.client.main.Main");
+
+    let version_json_path = runtime_root.join(format!("{}.json", instance.minecraft_version));
+    let installed_version_json = tokio::fs::read_to_string(&version_json_path)
+        .await
+        .ok()
+        .and_then(|raw| serde_json::from_str::<VersionJson>(&raw).ok());
+
+    // Instances installed before `required_java_major` was tracked still have
+    // the version JSON on disk; read it instead of refetching the manifest.
+    if instance.main_class.is_some() && instance.required_java_major.is_none() {
+        if let Some(version_json) = &installed_version_json {
+            instance.required_java_major = Some(version_json.required_java_major());
+        }
+    }
+
+    let needs_install = instance.main_class.is_none()
+        || instance.required_java_major.is_none()
+        || has_invalid_loader_main_class
+        || !instance.client_jar_path().exists()
+        || instance.libraries.iter().any(|coord| {
+            crate::core::maven::MavenArtifact::parse(coord)
+                .map(|artifact| !libs_dir.join(artifact.local_path()).exists())
+                .unwrap_or(false)
+        });
+
+    if needs_install {
+        let asset_index_url = run_cancellable(
+            cancel,
+            prepare_install(
+                state,
+                &instance.minecraft_version,
+                installed_version_json.as_ref(),
+                &libs_dir,
+                &assets_dir,
+            ),
+        )
+        .await?;
+
+        let libraries_phase = install_game_libraries(
+            events,
+            state,
+            instance,
+            &runtime_root,
+            &libs_dir,
+            cancel,
+            progress,
+        );
+        let minecraft_version = instance.minecraft_version.clone();
+        let assets_downloader =
+            install_progress::phase_downloader(progress, &state.downloader, InstallPhase::Assets);
+        let assets_phase = async {
+            let report = match &asset_index_url {
+                Some(url) => sync_instance_assets(
+                    state,
+                    &minecraft_version,
+                    url,
+                    &assets_dir,
+                    &assets_downloader,
+                    Some(cancel),
+                )
+                .await
+                .map(Some),
+                None => Ok(None),
+            };
+            complete_phases(progress, &[InstallPhase::Assets]);
+            report
+        };
+        let (installed, asset_report) = download_phases(libraries_phase, assets_phase).await?;
+
+        let vanilla_result = installed.vanilla;
+        instance.main_class = Some(vanilla_result.main_class);
+        instance.asset_index = vanilla_result.asset_index_id;
+        instance.libraries = vanilla_result.libraries;
+        instance.resolved_libraries = vanilla_result.resolved_libraries;
+        instance.required_java_major = vanilla_result.java_major;
+        if let Some(loader_version) = installed.loader_version {
+            instance.loader_version = Some(loader_version);
+        }
+        if let Some(loader_result) = installed.loader {
+            instance.main_class = Some(loader_result.main_class);
+            instance.jvm_args.extend(loader_result.extra_jvm_args);
+            instance.game_args.extend(loader_result.extra_game_args);
+            instance.libraries.extend(loader_result.libraries);
+            merge_resolved_libraries(
+                &mut instance.resolved_libraries,
+                loader_result.resolved_libraries,
+            );
+            if loader_result.asset_index_id.is_some() {
+                instance.asset_index = loader_result.asset_index_id;
+            }
+        }
+
+        if let Some(report) = asset_report {
+            info!(
+                "Assets for {}: {} verified, {} downloaded ({} from a fallback host)",
+                instance.id, report.verified, report.downloaded, report.recovered
+            );
+        }
+    }
+
+    if !needs_install {
+        if let Some(version_json) = &installed_version_json {
+            let libraries_downloader = install_progress::phase_downloader(
+                progress,
+                &state.downloader,
+                InstallPhase::Libraries,
+            );
+            let repaired = run_cancellable(
+                cancel,
+                version_json.repair_libraries(&libs_dir, &libraries_downloader),
+            )
+            .await?;
+            if repaired > 0 {
+                info!(
+                    "Repaired {} corrupted or missing libraries for instance {}",
+                    repaired, instance.id
+                );
+            }
+        }
+    }
+
+    // Instances installed before the index id was saved, or whose loader
+    // result dropped it, read it back from the vanilla version JSON.
+    if instance.asset_index.is_none() {
+        instance.asset_index = installed_version_json
+            .as_ref()
+            .and_then(|version_json| version_json.asset_index.as_ref())
+            .map(|asset_index| asset_index.id.clone());
+    }
+
+    if instance.main_class.is_none() || instance.required_java_major.is_none() {
+        return Err(LauncherError::message(
+            ErrorText::InstanceMetadataIncomplete,
+            &[],
+        ));
+    }
+
+    run_cancellable(cancel, validate_or_resolve_java(state, instance)).await?;
+    instance.libraries = canonicalize_libraries(
+        std::mem::take(&mut instance.libraries),
+        installed_version_json.as_ref(),
+    );
+    Ok(())
+}
+
+/// Runs the Vanilla + loader + assets installation for an instance that was
+/// just created on disk, emitting `instance-create-*` events along the way.
+/// `local_installer` is a Forge/NeoForge installer JAR to use instead of
+/// downloading one; its loader version is kept as is.
+pub(crate) async fn install_created_instance(
+    events: &Arc<dyn EventEmitter>,
+    state: &AppState,
+    mut instance: Instance,
+    local_installer: Option<&Path>,
+) -> Result<Instance, LauncherError> {
+    let runtime_root = instance.runtime_root_dir();
+    tokio::fs::create_dir_all(&runtime_root)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: runtime_root.clone(),
+            source,
+        })?;
+
+    let libs_dir = state.libraries_dir();
+    let client = state.http_client.clone();
+    let vanilla_installer = loaders::Installer::new(&LoaderType::Vanilla, client.clone());
+    let metadata_cache = state.metadata_cache();
+    let locale = state.launcher_settings.language;
+
+    if let Err(err) = state
+        .instance_manager
+        .set_state(&mut instance, InstanceState::Installing)
+        .await
+    {
+        error!(
+            "Cannot persist installing state for {}: {}",
+            instance.id, err
+        );
+    }
+    // Downloads fill 16–95%; Java resolution and the final checks follow.
+    let progress = {
+        let events = events.clone();
+        let id = instance.id.clone();
+        InstallProgress::new(
+            16,
+            95,
+            MessageKey::CreatePreparingVanilla,
+            move |value, stage| {
+                emit_create_progress(events.as_ref(), locale, &id, value, stage, "running")
+            },
+        )
+    };
+    progress.set_stage(MessageKey::CreatePreparingVanilla);
+
+    let install_result: Result<(), LauncherError> = async {
+        prepare_install(
+            state,
+            &instance.minecraft_version,
+            None,
+            &libs_dir,
+            &instance.game_dir().join("assets"),
+        )
+        .await?;
+
+        let vanilla_result = vanilla_installer
+            .install(loaders::InstallContext {
+                minecraft_version: &instance.minecraft_version,
+                loader_version: "",
+                instance_dir: &runtime_root,
+                libs_dir: &libs_dir,
+                downloader: &progress.downloader(&state.downloader, InstallPhase::Libraries),
+                http_client: &client,
+                resolve_transitive: false,
+                metadata_cache: &metadata_cache,
+                on_step: None,
+                runtime: state.runtime_options(),
+                data_dir: &state.data_dir,
+                local_installer: None,
+            })
+            .await?;
+
+        progress.complete(InstallPhase::Libraries);
+        progress.set_stage(MessageKey::CreateVanillaInstalled);
+        emit_create_message(
+            events,
+            locale,
+            &instance.id,
+            "info",
+            LogKey::CreateVanillaReady,
+            &[],
+        );
+
+        instance.main_class = Some(vanilla_result.main_class.clone());
+        instance.asset_index = vanilla_result.asset_index_id.clone();
+        instance.libraries = vanilla_result.libraries.clone();
+        instance.resolved_libraries = vanilla_result.resolved_libraries.clone();
+        instance.jvm_args = vanilla_result.extra_jvm_args.clone();
+        instance.game_args = vanilla_result.extra_game_args.clone();
+        instance.required_java_major = vanilla_result.java_major;
+
+        if instance.loader != LoaderType::Vanilla {
+            if local_installer.is_none()
+                && vanilla_result
+                    .java_major
+                    .is_some_and(|java_major| java_major >= 21)
+                && matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge)
+            {
+                if let Some(recommended_version) =
+                    recommend_latest_loader_version(events.as_ref(), state, &instance).await?
+                {
+                    emit_create_message(
+                        events,
+                        locale,
+                        &instance.id,
+                        "info",
+                        LogKey::CreateLoaderUpgraded,
+                        &[
+                            &instance.loader,
+                            &format!("{:?}", instance.loader_version),
+                            &recommended_version,
+                        ],
+                    );
+                    instance.loader_version = Some(recommended_version);
+                }
+            }
+
+            if let Some(ref loader_version) = instance.loader_version {
+                progress.set_stage(MessageKey::CreateInstallingLoader);
+                let instance_id = instance.id.clone();
+                let on_processor = |step: usize, total: usize, label: &str| {
+                    progress.step(InstallPhase::LoaderProcessors, step, total);
+                    emit_create_progress_label(
+                        events.as_ref(),
+                        &instance_id,
+                        progress.percent(),
+                        label,
+                        "running",
+                    );
+                    emit_create_log(events, &instance_id, "info", label.to_string());
+                };
+                let installer = loaders::Installer::new(&instance.loader, client.clone());
+                let loader_result = installer
+                    .install(loaders::InstallContext {
+                        minecraft_version: &instance.minecraft_version,
+                        loader_version,
+                        instance_dir: &runtime_root,
+                        libs_dir: &libs_dir,
+                        downloader: &progress.downloader(&state.downloader, InstallPhase::Loader),
+                        http_client: &client,
+                        resolve_transitive: true,
+                        metadata_cache: &metadata_cache,
+                        on_step: Some(&on_processor),
+                        runtime: state.runtime_options(),
+                        data_dir: &state.data_dir,
+                        local_installer,
+                    })
+                    .await?;
+
+                emit_create_message(
+                    events,
+                    locale,
+                    &instance.id,
+                    "info",
+                    LogKey::CreateLoaderInstalled,
+                    &[&instance.loader, &loader_version],
+                );
+
+                instance.main_class = Some(loader_result.main_class);
+                instance.jvm_args.extend(loader_result.extra_jvm_args);
+                instance.game_args.extend(loader_result.extra_game_args);
+                instance.libraries.extend(loader_result.libraries);
+                merge_resolved_libraries(
+                    &mut instance.resolved_libraries,
+                    loader_result.resolved_libraries,
+                );
+                if loader_result.asset_index_id.is_some() {
+                    instance.asset_index = loader_result.asset_index_id;
+                }
+            }
+        }
+
+        progress.complete(InstallPhase::Loader);
+        progress.complete(InstallPhase::LoaderProcessors);
+
+        let assets_dir = instance.game_dir().join("assets");
+        tokio::fs::create_dir_all(&assets_dir)
+            .await
+            .map_err(|source| LauncherError::Io {
+                path: assets_dir.clone(),
+                source,
+            })?;
+
+        if let Some(url) = vanilla_result.asset_index_url {
+            progress.set_stage(MessageKey::CreateDownloadingAssets);
+            sync_instance_assets(
+                state,
+                &instance.minecraft_version,
+                &url,
+                &assets_dir,
+                &progress.downloader(&state.downloader, InstallPhase::Assets),
+                None,
+            )
+            .await?;
+        }
+        progress.complete(InstallPhase::Assets);
+
+        instance.libraries.sort();
+        instance.libraries.dedup();
+
+        validate_or_resolve_java(state, &mut instance).await?;
+        if let Some(java_path) = &instance.java_path {
+            emit_create_message(
+                events,
+                locale,
+                &instance.id,
+                "info",
+                LogKey::CreateJavaSelected,
+                &[&instance.minecraft_version, &java_path.display()],
+            );
+        }
+
+        Ok(())
+    }
+    .await;
+
+    if let Err(err) = install_result {
+        emit_create_progress(
+            events,
+            locale,
+            &instance.id,
+            100,
+            MessageKey::CreateFailed,
+            "error",
+        );
+        emit_create_error(events, locale, &instance.id, LogKey::CreateFailed, &err);
+        instance.state = InstanceState::Error;
+        if let Err(save_err) = state.instance_manager.save(&instance).await {
+            error!(
+                "Cannot persist failed instance state for {}: {}",
+                instance.id, save_err
+            );
+        }
+        return Err(err);
+    }
+
+    instance.state = InstanceState::Ready;
+    state.instance_manager.verify_structure(&instance).await?;
+    state.instance_manager.save(&instance).await?;
+    emit_create_progress(
+        events,
+        locale,
+        &instance.id,
+        100,
+        MessageKey::CreateReady,
+        "done",
+    );
+    emit_create_message(
+        events,
+        locale,
+        &instance.id,
+        "info",
+        LogKey::CreateReady,
+        &[],
+    );
+
+    info!("Instance '{}' created and ready", instance.name);
+    Ok(instance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn library_and_asset_phases_overlap() {
+        // Each fake phase waits for the other one to start, which only
+        // completes if they run concurrently.
+        let barrier = tokio::sync::Barrier::new(2);
+        let fake_phase = |downloaded: usize| {
+            let barrier = &barrier;
+            async move {
+                barrier.wait().await;
+                Ok::<_, LauncherError>(downloaded)
+            }
+        };
+        let phases = download_phases(fake_phase(120), fake_phase(3400));
+        let (libraries, assets) = tokio::time::timeout(std::time::Duration::from_secs(5), phases)
+            .await
+            .expect("phases ran sequentially")
+            .unwrap();
+        assert_eq!((libraries, assets), (120, 3400));
+    }
+
+    #[tokio::test]
+    async fn install_prep_reuses_the_installed_version_json() {
+        // Every request through this proxy is refused, so a second fetch of
+        // the manifest or version JSON would fail the test.
+        let client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all("http://127.0.0.1:9").unwrap())
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("install-prep-{}", std::process::id()));
+        let mut state =
+            AppState::open(dir.clone(), Arc::new(crate::core::events::NoopEmitter)).await;
+        state.http_client = client;
+        let version_json: VersionJson = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "assetIndex": {
+                "id": "5",
+                "url": "https://piston-meta.mojang.com/v1/packages/5.json",
+                "totalSize": 1000
+            },
+            "libraries": []
+        }))
+        .unwrap();
+
+        let asset_index_url = prepare_install(
+            &state,
+            "1.20.1",
+            Some(&version_json),
+            &dir.join("libraries"),
+            &dir.join("assets"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            asset_index_url.as_deref(),
+            Some("https://piston-meta.mojang.com/v1/packages/5.json")
+        );
+        assert!(!dir.join("cache").join("metadata").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn failing_phase_aborts_the_other() {
+        struct DropFlag<'a>(&'a std::sync::atomic::AtomicBool);
+        impl Drop for DropFlag<'_> {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let assets_dropped = std::sync::atomic::AtomicBool::new(false);
+        let libraries = async {
+            tokio::task::yield_now().await;
+            Err::<(), _>(LauncherError::DownloadFailed {
+                url: "https://libraries.minecraft.net/x.jar".into(),
+                status: 503,
+            })
+        };
+        let assets = async {
+            let _flag = DropFlag(&assets_dropped);
+            std::future::pending::<Result<(), LauncherError>>().await
+        };
+        let err = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            download_phases(libraries, assets),
+        )
+        .await
+        .expect("error did not abort the asset phase")
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            LauncherError::DownloadFailed { status: 503, .. }
+        ));
+        assert!(assets_dropped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,295 @@
+// ─── Install Progress ───
+// Percentages for the create / launch progress bars that follow the work
+// actually done instead of jumping between fixed markers. Every install
+// phase owns a weighted share of the bar's range and reports how far along
+// it is: download phases from the `download-progress` counts of their own
+// observed downloader (bytes when sizes are known, files otherwise), loader
+// processors from their step counter. Phases may run side by side. A phase
+// that discovers another batch of work can lower its completion, but the
+// reported percentage never moves backwards.
+
+use std::sync::{Arc, Mutex};
+
+use crate::core::downloader::{DownloadProgress, Downloader};
+use crate::core::i18n::MessageKey;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallPhase {
+    /// Vanilla client jar, libraries and natives.
+    Libraries,
+    /// Loader installer and libraries.
+    Loader,
+    /// Forge / NeoForge installer processors.
+    LoaderProcessors,
+    Assets,
+}
+
+/// Share of the bar given to each phase. Assets are the bulk of a fresh
+/// install; processors are few downloads but slow.
+pub const INSTALL_WEIGHTS: [(InstallPhase, u32); 4] = [
+    (InstallPhase::Libraries, 30),
+    (InstallPhase::Loader, 10),
+    (InstallPhase::LoaderProcessors, 15),
+    (InstallPhase::Assets, 45),
+];
+
+/// Overall percentage in `start..=end` for phases given as
+/// `(weight, completion)`, with completion clamped to `0.0..=1.0`. Rounds
+/// down, so `end` is only reached once every phase is complete.
+pub fn weighted_percent(start: u8, end: u8, phases: &[(u32, f64)]) -> u8 {
+    let total: u32 = phases.iter().map(|(weight, _)| weight).sum();
+    if total == 0 || end <= start {
+        return start;
+    }
+    let done: f64 = phases
+        .iter()
+        .map(|(weight, completion)| f64::from(*weight) * completion.clamp(0.0, 1.0))
+        .sum();
+    let span = f64::from(end - start);
+    start + ((span * done / f64::from(total)).floor() as u8).min(end - start)
+}
+
+/// Completion of one phase from the download events of its downloader.
+/// Its batches run one after another; when a new one starts, the previous
+/// one counts as finished at its full size.
+#[derive(Debug, Default, Clone, Copy)]
+struct DownloadTally {
+    finished_files: usize,
+    finished_bytes: u64,
+    completed: usize,
+    total: usize,
+    bytes: u64,
+    bytes_total: u64,
+}
+
+impl DownloadTally {
+    fn observe(&mut self, progress: &DownloadProgress) {
+        if self.total > 0 && progress.completed <= self.completed {
+            self.finished_files += self.total;
+            self.finished_bytes += self.bytes_total.max(self.bytes);
+        }
+        self.completed = progress.completed;
+        self.total = progress.total;
+        self.bytes = progress.bytes_downloaded;
+        self.bytes_total = progress.bytes_total;
+    }
+
+    fn completion(&self) -> f64 {
+        let bytes_total = self.finished_bytes + self.bytes_total;
+        if bytes_total > 0 {
+            return (self.finished_bytes + self.bytes) as f64 / bytes_total as f64;
+        }
+        let files_total = self.finished_files + self.total;
+        if files_total > 0 {
+            return (self.finished_files + self.completed) as f64 / files_total as f64;
+        }
+        0.0
+    }
+}
+
+#[derive(Debug)]
+struct PhaseState {
+    phase: InstallPhase,
+    weight: u32,
+    downloads: DownloadTally,
+    steps: f64,
+    complete: bool,
+}
+
+impl PhaseState {
+    fn completion(&self) -> f64 {
+        if self.complete {
+            1.0
+        } else {
+            self.downloads.completion().max(self.steps)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Tracker {
+    phases: Vec<PhaseState>,
+    stage: MessageKey,
+    reported: u8,
+}
+
+/// Called with the new percentage and the current stage label.
+pub type InstallProgressFn = dyn Fn(u8, MessageKey) + Send + Sync;
+
+/// Weighted progress of an install between `start` and `end` percent.
+pub struct InstallProgress {
+    start: u8,
+    end: u8,
+    tracker: Mutex<Tracker>,
+    on_progress: Box<InstallProgressFn>,
+}
+
+impl InstallProgress {
+    pub fn new(
+        start: u8,
+        end: u8,
+        stage: MessageKey,
+        on_progress: impl Fn(u8, MessageKey) + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let phases = INSTALL_WEIGHTS
+            .iter()
+            .map(|&(phase, weight)| PhaseState {
+                phase,
+                weight,
+                downloads: DownloadTally::default(),
+                steps: 0.0,
+                complete: false,
+            })
+            .collect();
+        Arc::new(Self {
+            start,
+            end,
+            tracker: Mutex::new(Tracker {
+                phases,
+                stage,
+                reported: start,
+            }),
+            on_progress: Box::new(on_progress),
+        })
+    }
+
+    /// `base`, reporting what it downloads as progress of `phase`.
+    pub fn downloader(self: &Arc<Self>, base: &Downloader, phase: InstallPhase) -> Downloader {
+        let progress = Arc::clone(self);
+        base.view(Some(Arc::new(move |download: &DownloadProgress| {
+            progress.update(|tracker| tracker.phase(phase).downloads.observe(download));
+        })))
+    }
+
+    /// Step `step` of `total` of `phase` has started.
+    pub fn step(&self, phase: InstallPhase, step: usize, total: usize) {
+        let fraction = step.saturating_sub(1) as f64 / total.max(1) as f64;
+        self.update(|tracker| {
+            let state = tracker.phase(phase);
+            state.steps = state.steps.max(fraction);
+        });
+    }
+
+    /// `phase` is done, or has nothing to do.
+    pub fn complete(&self, phase: InstallPhase) {
+        self.update(|tracker| tracker.phase(phase).complete = true);
+    }
+
+    /// Show `stage` from now on, reporting it at the current percentage.
+    pub fn set_stage(&self, stage: MessageKey) {
+        let mut tracker = self.lock();
+        tracker.stage = stage;
+        (self.on_progress)(tracker.reported, stage);
+    }
+
+    pub fn percent(&self) -> u8 {
+        self.lock().reported
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Tracker)) {
+        let mut tracker = self.lock();
+        change(&mut tracker);
+        let phases: Vec<(u32, f64)> = tracker
+            .phases
+            .iter()
+            .map(|state| (state.weight, state.completion()))
+            .collect();
+        let percent = weighted_percent(self.start, self.end, &phases);
+        if percent > tracker.reported {
+            tracker.reported = percent;
+            (self.on_progress)(percent, tracker.stage);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Tracker> {
+        self.tracker.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// `base` observed for `phase` when there is a `progress` to report to.
+pub fn phase_downloader(
+    progress: Option<&Arc<InstallProgress>>,
+    base: &Downloader,
+    phase: InstallPhase,
+) -> Downloader {
+    match progress {
+        Some(progress) => progress.downloader(base, phase),
+        None => base.view(None),
+    }
+}
+
+impl Tracker {
+    fn phase(&mut self, phase: InstallPhase) -> &mut PhaseState {
+        self.phases
+            .iter_mut()
+            .find(|state| state.phase == phase)
+            .expect("every install phase has a weight")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(completed: usize, total: usize, bytes: u64, bytes_total: u64) -> DownloadProgress {
+        DownloadProgress {
+            completed,
+            total,
+            bytes_downloaded: bytes,
+            bytes_total,
+            file_name: "file".into(),
+        }
+    }
+
+    #[test]
+    fn phases_fill_their_weighted_share_of_the_range() {
+        assert_eq!(weighted_percent(15, 72, &[]), 15);
+        assert_eq!(weighted_percent(15, 72, &[(30, 0.0), (70, 0.0)]), 15);
+        assert_eq!(weighted_percent(15, 72, &[(30, 1.0), (70, 1.0)]), 72);
+        // 30% of the weight done: 15 + 57 * 0.3 = 32.1.
+        assert_eq!(weighted_percent(15, 72, &[(30, 1.0), (70, 0.0)]), 32);
+        // Half of each phase is half of the range.
+        assert_eq!(weighted_percent(0, 100, &[(30, 0.5), (70, 0.5)]), 50);
+        // Almost done still rounds down.
+        assert_eq!(weighted_percent(0, 100, &[(1, 0.999)]), 99);
+        assert_eq!(weighted_percent(0, 100, &[(1, 4.0), (1, -1.0)]), 50);
+        assert_eq!(weighted_percent(80, 40, &[(1, 1.0)]), 80);
+    }
+
+    #[test]
+    fn download_batches_accumulate_and_the_bar_never_goes_back() {
+        let mut tally = DownloadTally::default();
+        tally.observe(&batch(1, 4, 100, 400));
+        assert_eq!(tally.completion(), 0.25);
+        tally.observe(&batch(4, 4, 400, 400));
+        assert_eq!(tally.completion(), 1.0);
+        // A second batch of the same size halves the completion.
+        tally.observe(&batch(1, 2, 200, 400));
+        assert_eq!(tally.completion(), 0.75);
+
+        let mut files = DownloadTally::default();
+        files.observe(&batch(3, 4, 0, 0));
+        assert_eq!(files.completion(), 0.75);
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let progress = InstallProgress::new(0, 100, MessageKey::LaunchValidated, move |p, _| {
+            sink.lock().unwrap().push(p)
+        });
+        let download = |p: &DownloadProgress| {
+            progress.update(|tracker| tracker.phase(InstallPhase::Libraries).downloads.observe(p))
+        };
+        download(&batch(2, 2, 10, 10));
+        assert_eq!(progress.percent(), 30);
+        // The loader's libraries show up: the phase is half done again, the bar holds.
+        download(&batch(1, 3, 10, 30));
+        progress.step(InstallPhase::LoaderProcessors, 2, 3);
+        assert_eq!(progress.percent(), 30);
+        progress.complete(InstallPhase::Loader);
+        progress.complete(InstallPhase::LoaderProcessors);
+        assert_eq!(progress.percent(), 40);
+        progress.complete(InstallPhase::Assets);
+        progress.complete(InstallPhase::Libraries);
+        assert_eq!(*reported.lock().unwrap(), [30, 40, 85, 100]);
+    }
+}
@@ -0,0 +1,287 @@
+// ─── Instance Config Files ───
+// Reading and editing game settings without launching the game:
+// `options.txt` as ordered `key:value` pairs, and any other text file of the
+// instance (mod configs, `server.properties`) by path. Edits to
+// `options.txt` only touch the keys given, so options the UI does not know
+// about keep their value and position. Paths from the frontend must stay
+// inside the instance directory.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+use super::Instance;
+
+pub const OPTIONS_FILE: &str = "options.txt";
+/// Largest file `read_text_config` returns; configs are small.
+pub const MAX_CONFIG_BYTES: u64 = 2 * 1024 * 1024;
+/// Written by the launcher only.
+const PROTECTED_FILES: [&str; 2] = ["instance.json", "mods.json"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameOption {
+    pub key: String,
+    pub value: String,
+}
+
+/// Options in file order. Lines without a `:` are not options and skipped.
+pub fn parse_options(text: &str) -> Vec<GameOption> {
+    text.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| GameOption {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+/// `text` with `updates` applied: existing keys are rewritten in place,
+/// new ones appended in key order, every other line left untouched.
+pub fn apply_options(text: &str, updates: &BTreeMap<String, String>) -> LauncherResult<String> {
+    for (key, value) in updates {
+        if key.is_empty() || key.contains([':', '\n', '\r']) || value.contains(['\n', '\r']) {
+            return Err(LauncherError::message(
+                ErrorText::InvalidGameOption,
+                &[&OPTIONS_FILE, key],
+            ));
+        }
+    }
+
+    let mut pending = updates.clone();
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| match line.split_once(':') {
+            Some((key, _)) => match pending.remove(key) {
+                Some(value) => format!("{key}:{value}"),
+                None => line.to_string(),
+            },
+            None => line.to_string(),
+        })
+        .collect();
+    lines.extend(
+        pending
+            .into_iter()
+            .map(|(key, value)| format!("{key}:{value}")),
+    );
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    Ok(updated)
+}
+
+pub fn read_game_options(instance: &Instance) -> LauncherResult<Vec<GameOption>> {
+    let path = instance.game_dir().join(OPTIONS_FILE);
+    Ok(parse_options(&read_if_exists(&path)?))
+}
+
+/// Apply `updates` to `options.txt`, creating it when the game never ran.
+pub fn write_game_options(
+    instance: &Instance,
+    updates: &BTreeMap<String, String>,
+) -> LauncherResult<Vec<GameOption>> {
+    let path = instance.game_dir().join(OPTIONS_FILE);
+    let existing = read_if_exists(&path)?;
+    let updated = apply_options(&existing, updates)?;
+    if updated != existing {
+        write_text(&path, &updated)?;
+    }
+    Ok(parse_options(&updated))
+}
+
+/// Resolve `relative` inside the instance directory. Rejects absolute
+/// paths, `..`, files the launcher owns, and symlinks leading outside.
+pub fn config_path(instance: &Instance, relative: &str) -> LauncherResult<PathBuf> {
+    let invalid = || LauncherError::message(ErrorText::ConfigPathNotAllowed, &[&relative]);
+    let relative_path = Path::new(relative);
+    if relative_path.as_os_str().is_empty()
+        || relative_path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(invalid());
+    }
+    // Case-insensitive filesystems (Windows, macOS) open `Instance.JSON`
+    // as `instance.json`.
+    let mut components = relative_path.components();
+    if let (Some(Component::Normal(name)), None) = (components.next(), components.next()) {
+        let name = name.to_string_lossy();
+        if PROTECTED_FILES
+            .iter()
+            .any(|protected| name.eq_ignore_ascii_case(protected))
+        {
+            return Err(invalid());
+        }
+    }
+
+    let path = instance.path.join(relative_path);
+    // The deepest existing ancestor must still be inside the instance once
+    // symlinks are resolved.
+    if let Some(existing) = path.ancestors().find(|ancestor| ancestor.exists()) {
+        let root = std::fs::canonicalize(&instance.path).map_err(|source| LauncherError::Io {
+            path: instance.path.clone(),
+            source,
+        })?;
+        let resolved = std::fs::canonicalize(existing).map_err(|source| LauncherError::Io {
+            path: existing.to_path_buf(),
+            source,
+        })?;
+        if !resolved.starts_with(&root) {
+            return Err(invalid());
+        }
+    }
+    Ok(path)
+}
+
+pub fn read_text_config(instance: &Instance, relative: &str) -> LauncherResult<String> {
+    let path = config_path(instance, relative)?;
+    let io_err = |source| LauncherError::Io {
+        path: path.clone(),
+        source,
+    };
+    let metadata = std::fs::metadata(&path).map_err(io_err)?;
+    if !metadata.is_file() || metadata.len() > MAX_CONFIG_BYTES {
+        return Err(LauncherError::message(
+            ErrorText::ConfigNotEditable,
+            &[&relative],
+        ));
+    }
+    let bytes = std::fs::read(&path).map_err(io_err)?;
+    String::from_utf8(bytes)
+        .map_err(|_| LauncherError::message(ErrorText::ConfigNotText, &[&relative]))
+}
+
+pub fn write_text_config(
+    instance: &Instance,
+    relative: &str,
+    contents: &str,
+) -> LauncherResult<()> {
+    if contents.len() as u64 > MAX_CONFIG_BYTES {
+        return Err(LauncherError::message(
+            ErrorText::ConfigTooLarge,
+            &[&relative, &(MAX_CONFIG_BYTES / 1024)],
+        ));
+    }
+    let path = config_path(instance, relative)?;
+    if path.is_dir() {
+        return Err(LauncherError::message(
+            ErrorText::ConfigIsDirectory,
+            &[&relative],
+        ));
+    }
+    write_text(&path, contents)
+}
+
+fn read_if_exists(path: &Path) -> LauncherResult<String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(source) => Err(LauncherError::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+fn write_text(path: &Path, contents: &str) -> LauncherResult<()> {
+    let io_err = |source| LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    atomic_file::write(path, contents).map_err(io_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+
+    fn instance_in(dir: &Path) -> Instance {
+        let mut instance = Instance::new(
+            "Configs".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            dir,
+        );
+        instance.path = dir.to_path_buf();
+        instance
+    }
+
+    #[test]
+    fn options_round_trip_keeps_unrelated_keys_in_place() {
+        let text = "version:3465\nfov:0.0\nkey_key.attack:key.mouse.left\nlang:en_us\n";
+        let updates = BTreeMap::from([
+            ("fov".to_string(), "0.25".to_string()),
+            ("renderDistance".to_string(), "16".to_string()),
+        ]);
+
+        let updated = apply_options(text, &updates).unwrap();
+        assert_eq!(
+            updated,
+            "version:3465\nfov:0.25\nkey_key.attack:key.mouse.left\nlang:en_us\nrenderDistance:16\n"
+        );
+        let keys: Vec<_> = parse_options(&updated)
+            .into_iter()
+            .map(|option| option.key)
+            .collect();
+        assert_eq!(
+            keys,
+            ["version", "fov", "key_key.attack", "lang", "renderDistance"]
+        );
+        // Applying the same values again changes nothing.
+        assert_eq!(apply_options(&updated, &updates).unwrap(), updated);
+
+        let bad = BTreeMap::from([("fov\nlang".to_string(), "x".to_string())]);
+        assert!(apply_options(text, &bad).is_err());
+    }
+
+    #[test]
+    fn config_paths_must_stay_inside_the_instance() {
+        let dir = std::env::temp_dir().join(format!("config-files-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let instance = instance_in(&dir);
+
+        write_text_config(&instance, "config/sodium-options.json", "{}").unwrap();
+        assert_eq!(
+            read_text_config(&instance, "config/sodium-options.json").unwrap(),
+            "{}"
+        );
+        for bad in [
+            "../outside.txt",
+            "config/../../outside.txt",
+            "/etc/passwd",
+            "",
+            "instance.json",
+            "Instance.JSON",
+            "MODS.json",
+        ] {
+            assert!(
+                config_path(&instance, bad).is_err(),
+                "{bad} should be rejected"
+            );
+        }
+        assert!(write_text_config(&instance, "../outside.txt", "x").is_err());
+        assert!(!dir.parent().unwrap().join("outside.txt").exists());
+
+        #[cfg(unix)]
+        {
+            let outside = dir.with_file_name(format!("config-outside-{}", std::process::id()));
+            std::fs::create_dir_all(&outside).unwrap();
+            std::os::unix::fs::symlink(&outside, dir.join("escape")).unwrap();
+            assert!(config_path(&instance, "escape/x.txt").is_err());
+            let _ = std::fs::remove_dir_all(&outside);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
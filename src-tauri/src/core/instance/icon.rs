@@ -0,0 +1,215 @@
+// ─── Instance Icons ───
+// An instance icon is either a built-in identifier (`builtin:<name>`, drawn
+// by the frontend) or an image copied into `<instance>/icon.png`.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use image::ImageFormat;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+use super::Instance;
+
+/// File name of a custom icon inside the instance directory.
+pub const ICON_FILE: &str = "icon.png";
+/// Largest accepted source image, so icons stay cheap to inline as data URLs.
+/// Files that grew past it on disk are not inlined at all.
+pub const MAX_ICON_BYTES: usize = 512 * 1024;
+
+const BUILTIN_PREFIX: &str = "builtin:";
+
+/// Icons bundled with the frontend that need no file on disk.
+pub const BUILTIN_ICONS: [&str; 8] = [
+    "builtin:grass",
+    "builtin:dirt",
+    "builtin:crafting_table",
+    "builtin:furnace",
+    "builtin:creeper",
+    "builtin:diamond",
+    "builtin:ender_pearl",
+    "builtin:chest",
+];
+
+pub fn is_builtin_icon(icon: &str) -> bool {
+    BUILTIN_ICONS.contains(&icon)
+}
+
+fn is_bare_file_name(name: &str) -> bool {
+    !name.contains(['/', '\\'])
+        && Path::new(name).file_name() == Some(name.as_ref())
+        && !name.starts_with('.')
+}
+
+/// Check `bytes` is a PNG or JPEG within `MAX_ICON_BYTES` that actually decodes.
+pub fn validate_icon_image(bytes: &[u8]) -> LauncherResult<ImageFormat> {
+    if bytes.len() > MAX_ICON_BYTES {
+        return Err(LauncherError::message(
+            ErrorText::IconTooLarge,
+            &[&(MAX_ICON_BYTES / 1024)],
+        ));
+    }
+
+    let format = match image::guess_format(bytes) {
+        Ok(format @ (ImageFormat::Png | ImageFormat::Jpeg)) => format,
+        _ => {
+            return Err(LauncherError::message(
+                ErrorText::IconFormatUnsupported,
+                &[],
+            ))
+        }
+    };
+    image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| LauncherError::message(ErrorText::IconDecodeFailed, &[&e]))?;
+    Ok(format)
+}
+
+/// Validate `source` and store it as `<instance_dir>/icon.png` (JPEGs are
+/// re-encoded). Returns the relative path to keep in `Instance::icon`.
+pub fn install_icon(instance_dir: &Path, source: &Path) -> LauncherResult<String> {
+    let bytes = std::fs::read(source).map_err(|e| LauncherError::Io {
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+    let format = validate_icon_image(&bytes)?;
+
+    let target = instance_dir.join(ICON_FILE);
+    let written = match format {
+        ImageFormat::Png => std::fs::write(&target, &bytes).map_err(|e| e.to_string()),
+        _ => image::load_from_memory_with_format(&bytes, format)
+            .and_then(|decoded| decoded.save_with_format(&target, ImageFormat::Png))
+            .map_err(|e| e.to_string()),
+    };
+    written
+        .map_err(|e| LauncherError::message(ErrorText::IconSaveFailed, &[&target.display(), &e]))?;
+
+    Ok(ICON_FILE.to_string())
+}
+
+impl Instance {
+    /// Absolute path of the custom icon file, if the icon is not built-in.
+    /// Only a bare file name inside the instance directory is accepted, since
+    /// `instance.json` may come from an imported archive.
+    pub fn icon_path(&self) -> Option<PathBuf> {
+        self.icon
+            .as_deref()
+            .filter(|icon| !icon.starts_with(BUILTIN_PREFIX))
+            .filter(|icon| is_bare_file_name(icon))
+            .map(|icon| self.path.join(icon))
+    }
+
+    /// Custom icon inlined as a `data:image/png;base64,…` URL. The size is
+    /// checked before reading, so a replaced file cannot be arbitrarily large.
+    pub fn icon_data_url(&self) -> Option<String> {
+        let path = self.icon_path()?;
+        let metadata = std::fs::metadata(&path).ok()?;
+        if !metadata.is_file() || metadata.len() > MAX_ICON_BYTES as u64 {
+            return None;
+        }
+        let bytes = std::fs::read(path).ok()?;
+        Some(format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgba};
+
+    fn encoded(format: ImageFormat) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(4, 4, Rgba([30u8, 160, 60, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image)
+            .to_rgb8()
+            .write_to(&mut bytes, format)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn rejects_non_images_and_oversized_files() {
+        assert!(validate_icon_image(b"#!/bin/sh\necho not an image").is_err());
+        // A PNG signature with garbage after it must fail to decode.
+        assert!(validate_icon_image(b"\x89PNG\r\n\x1a\n garbage").is_err());
+        assert!(validate_icon_image(&vec![0u8; MAX_ICON_BYTES + 1]).is_err());
+        assert_eq!(
+            validate_icon_image(&encoded(ImageFormat::Png)).unwrap(),
+            ImageFormat::Png
+        );
+        assert_eq!(
+            validate_icon_image(&encoded(ImageFormat::Jpeg)).unwrap(),
+            ImageFormat::Jpeg
+        );
+    }
+
+    #[test]
+    fn icon_paths_stay_inside_the_instance() {
+        let mut instance = Instance::new(
+            "Icons".into(),
+            "1.20.1".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            Path::new("/data/instances"),
+        );
+        instance.icon = Some(ICON_FILE.into());
+        assert_eq!(instance.icon_path(), Some(instance.path.join(ICON_FILE)));
+
+        for icon in [
+            "builtin:grass",
+            "../../../home/user/.ssh/id_rsa",
+            "/etc/passwd",
+            "sub/icon.png",
+            "C:\\Windows\\win.ini",
+            "..",
+        ] {
+            instance.icon = Some(icon.into());
+            assert_eq!(instance.icon_path(), None, "{icon} should be rejected");
+        }
+    }
+
+    #[test]
+    fn oversized_icon_files_are_not_inlined() {
+        let temp = std::env::temp_dir().join(format!("instance-icon-size-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let mut instance = Instance::new(
+            "Icons".into(),
+            "1.20.1".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            &temp,
+        );
+        std::fs::create_dir_all(&instance.path).unwrap();
+        instance.icon = Some(ICON_FILE.into());
+
+        std::fs::write(instance.path.join(ICON_FILE), vec![0u8; MAX_ICON_BYTES + 1]).unwrap();
+        assert_eq!(instance.icon_data_url(), None);
+        std::fs::write(instance.path.join(ICON_FILE), b"png-bytes").unwrap();
+        assert_eq!(
+            instance.icon_data_url().as_deref(),
+            Some("data:image/png;base64,cG5nLWJ5dGVz")
+        );
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn jpeg_is_stored_as_png() {
+        let temp = std::env::temp_dir().join(format!("instance-icon-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(&temp).unwrap();
+        let source = temp.join("photo.jpg");
+        std::fs::write(&source, encoded(ImageFormat::Jpeg)).unwrap();
+
+        assert_eq!(install_icon(&temp, &source).unwrap(), ICON_FILE);
+        let stored = std::fs::read(temp.join(ICON_FILE)).unwrap();
+        assert_eq!(image::guess_format(&stored).unwrap(), ImageFormat::Png);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
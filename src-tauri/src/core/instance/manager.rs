@@ -3,9 +3,14 @@ use std::path::{Path, PathBuf};
 use tracing::info;
 
 use super::model::{Instance, InstanceState};
+use super::{ordering, recovery};
+use crate::core::atomic_file;
 use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::long_path::long_path;
 
 /// Manages the lifecycle of instances on disk.
+#[derive(Clone)]
 pub struct InstanceManager {
     /// Root directory where all instances live.
     instances_dir: PathBuf,
@@ -50,6 +55,9 @@ impl InstanceManager {
 
         self.verify_structure(&instance).await?;
 
+        // New instances go to the end of the list.
+        instance.sort_index = ordering::next_sort_index(&self.list().await?);
+
         // Persist instance.json
         self.save(&instance).await?;
 
@@ -69,21 +77,26 @@ impl InstanceManager {
                         source,
                     })?;
             if !metadata.is_dir() {
-                return Err(LauncherError::Other(format!(
-                    "Estructura inválida: {:?} no es un directorio",
-                    path
-                )));
+                return Err(LauncherError::message(
+                    ErrorText::InvalidInstanceStructure,
+                    &[&path.display()],
+                ));
             }
         }
 
         Ok(())
     }
 
-    /// Save instance metadata to disk.
+    /// Save instance metadata to disk. Saving a salvaged instance keeps the
+    /// unreadable file as `instance.json.corrupt` first.
     pub async fn save(&self, instance: &Instance) -> LauncherResult<()> {
         let json = serde_json::to_string_pretty(instance)?;
         let config_path = instance.config_path();
 
+        if instance.needs_recovery {
+            backup_corrupt_config(&config_path).await?;
+        }
+
         if let Some(parent) = config_path.parent() {
             tokio::fs::create_dir_all(parent)
                 .await
@@ -93,7 +106,7 @@ impl InstanceManager {
                 })?;
         }
 
-        tokio::fs::write(&config_path, json)
+        atomic_file::write_async(&config_path, json)
             .await
             .map_err(|e| LauncherError::Io {
                 path: config_path,
@@ -103,7 +116,8 @@ impl InstanceManager {
         Ok(())
     }
 
-    /// Load a single instance by ID.
+    /// Load a single instance by ID. An unreadable `instance.json` yields a
+    /// salvaged instance flagged `needs_recovery` (see [`recovery::salvage`]).
     pub async fn load(&self, id: &str) -> LauncherResult<Instance> {
         let config_path = self.instances_dir.join(id).join("instance.json");
         if !config_path.exists() {
@@ -118,7 +132,22 @@ impl InstanceManager {
                     source: e,
                 })?;
 
-        let instance: Instance = serde_json::from_str(&json)?;
+        Ok(parse_or_salvage(&config_path, &json))
+    }
+
+    /// Rewrite the metadata of a salvaged instance from what is left on disk.
+    /// The result is in `Created` state and needs a reinstall; instances
+    /// whose `instance.json` is fine are returned unchanged.
+    pub async fn recover(&self, id: &str) -> LauncherResult<Instance> {
+        let instance = self.load(id).await?;
+        if !instance.needs_recovery {
+            return Ok(instance);
+        }
+
+        backup_corrupt_config(&instance.config_path()).await?;
+        let instance = recovery::rebuild(instance);
+        self.save(&instance).await?;
+        info!("Recovered metadata of instance {}", id);
         Ok(instance)
     }
 
@@ -146,14 +175,10 @@ impl InstanceManager {
                 let config_path = path.join("instance.json");
                 if config_path.exists() {
                     match tokio::fs::read_to_string(&config_path).await {
-                        Ok(json) => match serde_json::from_str::<Instance>(&json) {
-                            Ok(inst) => instances.push(inst),
-                            Err(e) => {
-                                tracing::warn!("Corrupt instance.json at {:?}: {}", config_path, e);
-                            }
-                        },
+                        Ok(json) => instances.push(parse_or_salvage(&config_path, &json)),
                         Err(e) => {
                             tracing::warn!("Cannot read {:?}: {}", config_path, e);
+                            instances.push(recovery::salvage(&path, ""));
                         }
                     }
                 }
@@ -163,6 +188,34 @@ impl InstanceManager {
         Ok(instances)
     }
 
+    /// List all instances in display order.
+    pub async fn list_sorted(&self) -> LauncherResult<Vec<Instance>> {
+        let mut instances = self.list().await?;
+        ordering::sort_instances(&mut instances);
+        Ok(instances)
+    }
+
+    /// Persist a manual ordering (see [`ordering::apply_order`]) and return
+    /// the instances in their new order.
+    pub async fn reorder(&self, ordered_ids: &[String]) -> LauncherResult<Vec<Instance>> {
+        let mut instances = self.list().await?;
+        for index in ordering::apply_order(&mut instances, ordered_ids) {
+            self.save(&instances[index]).await?;
+        }
+        Ok(instances)
+    }
+
+    /// Rename group `from` to `to` (or ungroup) on every instance in it.
+    /// Returns how many instances were updated.
+    pub async fn rename_group(&self, from: &str, to: Option<&str>) -> LauncherResult<usize> {
+        let mut instances = self.list().await?;
+        let changed = ordering::rename_group(&mut instances, from, to);
+        for index in &changed {
+            self.save(&instances[*index]).await?;
+        }
+        Ok(changed.len())
+    }
+
     /// Delete an instance from disk.
     pub async fn delete(&self, id: &str) -> LauncherResult<()> {
         let instance_dir = self.instances_dir.join(id);
@@ -170,7 +223,7 @@ impl InstanceManager {
             return Err(LauncherError::InstanceNotFound(id.to_string()));
         }
 
-        tokio::fs::remove_dir_all(&instance_dir)
+        tokio::fs::remove_dir_all(long_path(&instance_dir))
             .await
             .map_err(|e| LauncherError::Io {
                 path: instance_dir,
@@ -200,6 +253,27 @@ impl InstanceManager {
     }
 }
 
+fn parse_or_salvage(config_path: &Path, json: &str) -> Instance {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        tracing::warn!("Corrupt instance.json at {:?}: {}", config_path, e);
+        let instance_dir = config_path.parent().unwrap_or(config_path);
+        recovery::salvage(instance_dir, json)
+    })
+}
+
+/// Copy an unreadable `instance.json` aside before it is overwritten.
+async fn backup_corrupt_config(config_path: &Path) -> LauncherResult<()> {
+    let backup = config_path.with_extension("json.corrupt");
+    match tokio::fs::copy(config_path, &backup).await {
+        Ok(_) => Ok(()),
+        Err(source) if source.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(source) => Err(LauncherError::Io {
+            path: backup,
+            source,
+        }),
+    }
+}
+
 async fn create_dir_safe(path: &Path) -> LauncherResult<()> {
     tokio::fs::create_dir_all(path)
         .await
@@ -1,5 +1,13 @@
+pub mod config_files;
+pub mod icon;
 pub mod manager;
 pub mod model;
+pub mod ordering;
+pub mod play_stats;
+pub mod recovery;
+pub mod screenshots;
+pub mod transfer;
+pub mod verify;
 
 pub use manager::InstanceManager;
-pub use model::{Instance, InstanceState, LoaderType};
+pub use model::{Instance, InstanceState, LoaderType, WINDOW_SIZE_RANGE};
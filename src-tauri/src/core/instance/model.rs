@@ -4,7 +4,10 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use crate::core::auth::LaunchAccountProfile;
+use crate::core::error::LauncherError;
+use crate::core::i18n::ErrorText;
 use crate::core::java::RuntimeRole;
+use crate::core::version::ResolvedLibrary;
 
 /// Supported mod loaders — strongly typed, no magic strings.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -29,6 +32,21 @@ impl std::fmt::Display for LoaderType {
     }
 }
 
+impl std::str::FromStr for LoaderType {
+    type Err = LauncherError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "vanilla" => Ok(LoaderType::Vanilla),
+            "forge" => Ok(LoaderType::Forge),
+            "fabric" => Ok(LoaderType::Fabric),
+            "neoforge" => Ok(LoaderType::NeoForge),
+            "quilt" => Ok(LoaderType::Quilt),
+            other => Err(LauncherError::message(ErrorText::UnknownLoader, &[&other])),
+        }
+    }
+}
+
 /// Lifecycle state of an instance.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -73,6 +91,10 @@ pub struct Instance {
     pub asset_index: Option<String>,
     /// Library coordinates saved during installation.
     pub libraries: Vec<String>,
+    /// Download URL and hash of the libraries whose source the installers
+    /// knew; used to verify and repair them without re-resolving metadata.
+    #[serde(default)]
+    pub resolved_libraries: Vec<ResolvedLibrary>,
     /// Extra JVM arguments from config or loader.
     pub jvm_args: Vec<String>,
     /// Extra game arguments from loader.
@@ -91,8 +113,63 @@ pub struct Instance {
     /// Account profile used to resolve launch placeholders for premium/offline modes.
     #[serde(default)]
     pub account: LaunchAccountProfile,
+    /// Saved account (see `auth::accounts`) this instance launches with;
+    /// `account` is used instead when the id is no longer in the book.
+    #[serde(default)]
+    pub account_id: Option<String>,
+    /// Program (plus arguments) the java invocation is wrapped in, e.g.
+    /// `["gamemoderun"]` or `["prime-run"]`.
+    #[serde(default)]
+    pub wrapper_command: Option<Vec<String>>,
+    /// Shell command run in the game directory before launch; a non-zero
+    /// exit aborts the launch.
+    #[serde(default)]
+    pub pre_launch_hook: Option<String>,
+    /// Shell command run in the game directory after the game exits.
+    #[serde(default)]
+    pub post_exit_hook: Option<String>,
+    /// Built-in icon id (`builtin:<name>`) or a path relative to the instance
+    /// directory (`icon.png`).
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// User-defined category shown as a section in the instance list.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Position in the instance list; assigned on creation, changed by reordering.
+    #[serde(default)]
+    pub sort_index: u32,
+    /// Managed runtime identifier chosen by the user; used instead of the
+    /// newest compatible runtime while it is still installed.
+    #[serde(default)]
+    pub pinned_runtime_identifier: Option<String>,
+    /// Seconds spent in game across all finished sessions.
+    #[serde(default)]
+    pub total_play_seconds: u64,
+    /// Number of finished game sessions.
+    #[serde(default)]
+    pub launch_count: u32,
+    /// Game window size as `(width, height)`; [`DEFAULT_WINDOW_SIZE`] when unset.
+    #[serde(default)]
+    pub resolution: Option<(u32, u32)>,
+    /// Start the game in fullscreen (`--fullscreen`).
+    #[serde(default)]
+    pub start_fullscreen: bool,
+    /// Keep `--demo` flags instead of stripping them, and launch offline
+    /// accounts (which have no license) in demo mode.
+    #[serde(default)]
+    pub allow_demo_mode: bool,
+    /// Set on instances salvaged from an unreadable `instance.json` (see
+    /// `recovery`); never persisted.
+    #[serde(skip)]
+    pub needs_recovery: bool,
 }
 
+/// Window size used when an instance has none configured.
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (1280, 720);
+
+/// Accepted range for each window dimension.
+pub const WINDOW_SIZE_RANGE: std::ops::RangeInclusive<u32> = 320..=7680;
+
 fn default_bootstrap_runtime() -> RuntimeRole {
     RuntimeRole::Gamma
 }
@@ -129,6 +206,7 @@ impl Instance {
             main_class: None,
             asset_index: None,
             libraries: Vec::new(),
+            resolved_libraries: Vec::new(),
             jvm_args: Vec::new(),
             game_args: Vec::new(),
             required_java_major: None,
@@ -136,9 +214,28 @@ impl Instance {
             game_runtime: default_game_runtime(),
             loader_requires_delta: false,
             account: LaunchAccountProfile::default(),
+            account_id: None,
+            wrapper_command: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            icon: None,
+            group: None,
+            sort_index: 0,
+            pinned_runtime_identifier: None,
+            total_play_seconds: 0,
+            launch_count: 0,
+            resolution: None,
+            start_fullscreen: false,
+            allow_demo_mode: false,
+            needs_recovery: false,
         }
     }
 
+    /// Configured window size, or [`DEFAULT_WINDOW_SIZE`].
+    pub fn window_size(&self) -> (u32, u32) {
+        self.resolution.unwrap_or(DEFAULT_WINDOW_SIZE)
+    }
+
     /// Path to the instance's `minecraft/` game working directory.
     pub fn game_dir(&self) -> PathBuf {
         self.runtime_root_dir().join("minecraft")
@@ -159,7 +256,22 @@ impl Instance {
         self.runtime_root_dir().join("logs")
     }
 
-    /// Path to the `natives` folder (extracted per launch session).
+    /// Path to the world `saves/` directory inside the game directory.
+    pub fn saves_dir(&self) -> PathBuf {
+        self.game_dir().join("saves")
+    }
+
+    /// Path to the in-game `screenshots/` directory.
+    pub fn screenshots_dir(&self) -> PathBuf {
+        self.game_dir().join("screenshots")
+    }
+
+    /// Path to the world backup archives.
+    pub fn backups_dir(&self) -> PathBuf {
+        self.runtime_root_dir().join("backups")
+    }
+
+    /// Path to the `natives` folder, with one subfolder per launch session.
     pub fn natives_dir(&self) -> PathBuf {
         self.runtime_root_dir().join("natives")
     }
@@ -181,3 +293,48 @@ impl Instance {
         self.path.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_libraries_round_trip_through_instance_json() {
+        let mut instance = Instance::new(
+            "Round trip".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            Some("0.15.11".into()),
+            4096,
+            std::path::Path::new("/instances"),
+        );
+        instance.resolved_libraries = vec![
+            ResolvedLibrary {
+                coord: "com.mojang:brigadier:1.1.8".into(),
+                path: PathBuf::from("com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar"),
+                url: "https://libraries.minecraft.net/com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar"
+                    .into(),
+                sha1: Some("5244ce82c3337bba4a196a3ce858bfaecc74404a".into()),
+                size: Some(77392),
+            },
+            ResolvedLibrary {
+                coord: "net.fabricmc:fabric-loader:0.15.11".into(),
+                path: PathBuf::from("net/fabricmc/fabric-loader/0.15.11/fabric-loader-0.15.11.jar"),
+                url: "https://maven.fabricmc.net/net/fabricmc/fabric-loader/0.15.11/fabric-loader-0.15.11.jar"
+                    .into(),
+                sha1: None,
+                size: None,
+            },
+        ];
+
+        let json = serde_json::to_string_pretty(&instance).unwrap();
+        let restored: Instance = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.resolved_libraries, instance.resolved_libraries);
+
+        // Files written before the field existed still load.
+        let mut legacy = serde_json::to_value(&instance).unwrap();
+        legacy.as_object_mut().unwrap().remove("resolved_libraries");
+        let restored: Instance = serde_json::from_value(legacy).unwrap();
+        assert!(restored.resolved_libraries.is_empty());
+    }
+}
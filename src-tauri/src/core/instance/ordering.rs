@@ -0,0 +1,182 @@
+// ─── Instance Ordering ───
+// Sorting, manual reordering and grouping of instances. Every function only
+// mutates the given slice and reports which entries changed so the manager
+// rewrites just those `instance.json` files.
+
+use super::Instance;
+
+/// Stable display order: `sort_index`, then creation date, then id.
+pub fn sort_instances(instances: &mut [Instance]) {
+    instances.sort_by(|a, b| {
+        a.sort_index
+            .cmp(&b.sort_index)
+            .then(a.created_at.cmp(&b.created_at))
+            .then(a.id.cmp(&b.id))
+    });
+}
+
+/// Index for a new instance so it lands after every existing one.
+pub fn next_sort_index(instances: &[Instance]) -> u32 {
+    instances
+        .iter()
+        .map(|instance| instance.sort_index.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Renumber `instances` so the ids in `ordered_ids` come first, in that order,
+/// followed by the rest in their current order. Unknown ids are ignored.
+/// Returns the positions (after sorting) whose `sort_index` changed.
+pub fn apply_order(instances: &mut [Instance], ordered_ids: &[String]) -> Vec<usize> {
+    sort_instances(instances);
+    let rank = |instance: &Instance| {
+        ordered_ids
+            .iter()
+            .position(|id| *id == instance.id)
+            .unwrap_or(ordered_ids.len())
+    };
+    // `sort_by_key` is stable, so unlisted instances keep their relative order.
+    instances.sort_by_key(rank);
+
+    let mut changed = Vec::new();
+    for (index, instance) in instances.iter_mut().enumerate() {
+        let sort_index = index as u32;
+        if instance.sort_index != sort_index {
+            instance.sort_index = sort_index;
+            changed.push(index);
+        }
+    }
+    changed
+}
+
+/// Normalize a group name: trimmed, with empty meaning "no group".
+pub fn normalize_group(group: Option<&str>) -> Option<String> {
+    group
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .map(str::to_string)
+}
+
+/// Move every instance in group `from` to `to` (or out of any group).
+/// Returns the positions of the instances that were touched.
+pub fn rename_group(instances: &mut [Instance], from: &str, to: Option<&str>) -> Vec<usize> {
+    let to = normalize_group(to);
+    let mut changed = Vec::new();
+    for (index, instance) in instances.iter_mut().enumerate() {
+        if instance.group.as_deref() == Some(from) && instance.group != to {
+            instance.group = to.clone();
+            changed.push(index);
+        }
+    }
+    changed
+}
+
+/// Split sorted instances into groups: named groups alphabetically
+/// (case-insensitive), ungrouped instances last.
+pub fn group_instances(mut instances: Vec<Instance>) -> Vec<(Option<String>, Vec<Instance>)> {
+    sort_instances(&mut instances);
+    let mut groups: Vec<(Option<String>, Vec<Instance>)> = Vec::new();
+    for instance in instances {
+        match groups.iter_mut().find(|(name, _)| *name == instance.group) {
+            Some((_, members)) => members.push(instance),
+            None => groups.push((instance.group.clone(), vec![instance])),
+        }
+    }
+    groups.sort_by_key(|(name, _)| {
+        (
+            name.is_none(),
+            name.as_deref().map(str::to_lowercase).unwrap_or_default(),
+        )
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+
+    fn instance(name: &str, sort_index: u32, group: Option<&str>) -> Instance {
+        let mut instance = Instance::new(
+            name.into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        instance.sort_index = sort_index;
+        instance.group = group.map(str::to_string);
+        instance
+    }
+
+    fn names(instances: &[Instance]) -> Vec<&str> {
+        instances.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    #[test]
+    fn reorder_is_stable_for_unlisted_instances() {
+        let mut instances = vec![
+            instance("c", 2, None),
+            instance("a", 0, None),
+            instance("d", 3, None),
+            instance("b", 1, None),
+        ];
+        let d = instances[2].id.clone();
+
+        let changed = apply_order(&mut instances, &[d, "missing".into()]);
+        assert_eq!(names(&instances), ["d", "a", "b", "c"]);
+        assert_eq!(
+            instances.iter().map(|i| i.sort_index).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(changed, [0, 1, 2, 3]);
+
+        // Re-applying the same order is a no-op.
+        let ids: Vec<String> = instances.iter().map(|i| i.id.clone()).collect();
+        assert!(apply_order(&mut instances, &ids).is_empty());
+        assert_eq!(next_sort_index(&instances), 4);
+    }
+
+    #[test]
+    fn group_rename_only_touches_matching_instances() {
+        let mut instances = vec![
+            instance("a", 0, Some("Modded")),
+            instance("b", 1, Some("Vanilla")),
+            instance("c", 2, Some("Modded")),
+            instance("d", 3, None),
+        ];
+
+        let changed = rename_group(&mut instances, "Modded", Some("  Mods  "));
+        assert_eq!(changed, [0, 2]);
+        assert_eq!(instances[0].group.as_deref(), Some("Mods"));
+        assert_eq!(instances[1].group.as_deref(), Some("Vanilla"));
+        assert_eq!(instances[2].group.as_deref(), Some("Mods"));
+        assert_eq!(instances[3].group, None);
+
+        assert_eq!(rename_group(&mut instances, "Mods", Some(" ")), [0, 2]);
+        assert_eq!(instances[0].group, None);
+    }
+
+    #[test]
+    fn groups_are_sorted_with_ungrouped_last() {
+        let grouped = group_instances(vec![
+            instance("x", 3, None),
+            instance("b", 1, Some("vanilla")),
+            instance("a", 2, Some("Modded")),
+            instance("c", 0, Some("vanilla")),
+        ]);
+        let summary: Vec<(Option<&str>, Vec<&str>)> = grouped
+            .iter()
+            .map(|(name, members)| (name.as_deref(), names(members)))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some("Modded"), vec!["a"]),
+                (Some("vanilla"), vec!["c", "b"]),
+                (None, vec!["x"]),
+            ]
+        );
+    }
+}
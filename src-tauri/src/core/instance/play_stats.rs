@@ -0,0 +1,107 @@
+// ─── Play Statistics ───
+// Cumulative play time and launch counts. Sessions are recorded by the
+// process-wait task when the game exits, whether it closed normally or was
+// force-closed, so partial sessions still count.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::Instance;
+
+/// Play time of a single instance.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct InstancePlayTime {
+    pub id: String,
+    pub name: String,
+    pub total_play_seconds: u64,
+    pub launch_count: u32,
+}
+
+/// Play statistics across every instance.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayStats {
+    pub total_play_seconds: u64,
+    pub total_hours: f64,
+    pub total_launches: u64,
+    pub most_played: Option<InstancePlayTime>,
+    /// Instances that have been played, most played first.
+    pub instances: Vec<InstancePlayTime>,
+}
+
+/// Add a finished session of `elapsed` to the instance totals.
+pub fn record_session(instance: &mut Instance, elapsed: Duration) {
+    instance.total_play_seconds = instance
+        .total_play_seconds
+        .saturating_add(elapsed.as_secs());
+    instance.launch_count = instance.launch_count.saturating_add(1);
+}
+
+pub fn aggregate(instances: &[Instance]) -> PlayStats {
+    let mut played: Vec<InstancePlayTime> = instances
+        .iter()
+        .filter(|instance| instance.launch_count > 0)
+        .map(|instance| InstancePlayTime {
+            id: instance.id.clone(),
+            name: instance.name.clone(),
+            total_play_seconds: instance.total_play_seconds,
+            launch_count: instance.launch_count,
+        })
+        .collect();
+    played.sort_by(|a, b| {
+        b.total_play_seconds
+            .cmp(&a.total_play_seconds)
+            .then(b.launch_count.cmp(&a.launch_count))
+            .then(a.name.cmp(&b.name))
+    });
+
+    let total_play_seconds = played.iter().map(|entry| entry.total_play_seconds).sum();
+    PlayStats {
+        total_play_seconds,
+        total_hours: total_play_seconds as f64 / 3600.0,
+        total_launches: played
+            .iter()
+            .map(|entry| u64::from(entry.launch_count))
+            .sum(),
+        most_played: played.first().cloned(),
+        instances: played,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+
+    fn instance(name: &str) -> Instance {
+        Instance::new(
+            name.into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        )
+    }
+
+    #[test]
+    fn sessions_accumulate_play_time_and_launches() {
+        let mut survival = instance("Survival");
+        record_session(&mut survival, Duration::from_secs(90 * 60));
+        record_session(&mut survival, Duration::from_millis(30 * 60 * 1000 + 999));
+        assert_eq!(survival.total_play_seconds, 2 * 3600);
+        assert_eq!(survival.launch_count, 2);
+
+        let mut modded = instance("Modded");
+        record_session(&mut modded, Duration::from_secs(3 * 3600));
+        let never_played = instance("New");
+
+        let stats = aggregate(&[survival, modded, never_played]);
+        assert_eq!(stats.total_play_seconds, 5 * 3600);
+        assert_eq!(stats.total_hours, 5.0);
+        assert_eq!(stats.total_launches, 3);
+        assert_eq!(stats.most_played.unwrap().name, "Modded");
+        let names: Vec<&str> = stats.instances.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Modded", "Survival"]);
+    }
+}
@@ -0,0 +1,321 @@
+// ─── Instance Recovery ───
+// An `instance.json` that no longer parses (truncated write, hand edit) must
+// not make the instance disappear while its files are still on disk. The
+// manager lists such instances as salvaged copies built from the directory
+// layout plus whatever fields of the broken file are still readable; a
+// recovery then rewrites the metadata and reinstalls.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use super::model::{Instance, InstanceState, LoaderType};
+
+/// Memory given to salvaged instances whose broken file lost the setting.
+const SALVAGED_MEMORY_MB: u32 = 2048;
+
+/// Fields never taken from the broken file: identity comes from the
+/// directory and the state is always `Error` until recovered.
+const LAYOUT_OWNED_FIELDS: [&str; 3] = ["id", "path", "state"];
+
+/// Minecraft version and loader found on disk.
+#[derive(Debug, Default, PartialEq)]
+struct InstalledLayout {
+    minecraft_version: Option<String>,
+    loader: Option<LoaderType>,
+    loader_version: Option<String>,
+}
+
+/// Build an `Instance` for the directory `instance_dir` whose `instance.json`
+/// content `raw` failed to deserialize. The result is in `Error` state and
+/// flagged `needs_recovery`.
+pub fn salvage(instance_dir: &Path, raw: &str) -> Instance {
+    let id = instance_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let layout = detect_layout(instance_dir);
+
+    let mut base = Instance::new(
+        format!(
+            "Instancia recuperada {}",
+            id.chars().take(8).collect::<String>()
+        ),
+        layout.minecraft_version.unwrap_or_default(),
+        layout.loader.unwrap_or(LoaderType::Vanilla),
+        layout.loader_version,
+        SALVAGED_MEMORY_MB,
+        instance_dir.parent().unwrap_or(instance_dir),
+    );
+    base.id = id;
+    base.path = instance_dir.to_path_buf();
+
+    // Take every readable field that still deserializes on its own.
+    let mut instance = base.clone();
+    if let Ok(Value::Object(mut record)) = serde_json::to_value(&base) {
+        for (key, value) in readable_fields(raw) {
+            if LAYOUT_OWNED_FIELDS.contains(&key.as_str()) {
+                continue;
+            }
+            let previous = record.insert(key.clone(), value);
+            match serde_json::from_value::<Instance>(Value::Object(record.clone())) {
+                Ok(candidate) => instance = candidate,
+                Err(_) => match previous {
+                    Some(previous) => {
+                        record.insert(key, previous);
+                    }
+                    None => {
+                        record.remove(&key);
+                    }
+                },
+            }
+        }
+    }
+
+    instance.state = InstanceState::Error;
+    instance.needs_recovery = true;
+    instance
+}
+
+/// Turn a salvaged instance into one that can be saved and reinstalled: the
+/// user's settings stay, the install results are cleared so the next
+/// install derives them again from the version and loader.
+pub fn rebuild(mut instance: Instance) -> Instance {
+    instance.main_class = None;
+    instance.asset_index = None;
+    instance.libraries.clear();
+    instance.resolved_libraries.clear();
+    instance.required_java_major = None;
+    instance.state = InstanceState::Created;
+    instance.needs_recovery = false;
+    instance
+}
+
+/// Top-level fields of a damaged `instance.json`. A file that is valid JSON
+/// with a wrong shape yields all of its fields; a truncated one yields the
+/// single-line `"key": value` entries at the top indentation level of
+/// `to_string_pretty` output.
+fn readable_fields(raw: &str) -> Map<String, Value> {
+    if let Ok(Value::Object(fields)) = serde_json::from_str(raw) {
+        return fields;
+    }
+
+    let mut fields = Map::new();
+    for line in raw.lines() {
+        // Nested objects are indented further; their keys are not ours.
+        let Some(entry) = line
+            .strip_prefix("  ")
+            .filter(|entry| entry.starts_with('"'))
+        else {
+            continue;
+        };
+        let entry = entry.trim_end().trim_end_matches(',');
+        if let Ok(Value::Object(parsed)) = serde_json::from_str(&format!("{{{entry}}}")) {
+            fields.extend(parsed);
+        }
+    }
+    fields
+}
+
+fn detect_layout(instance_dir: &Path) -> InstalledLayout {
+    let mut layout = InstalledLayout::default();
+
+    // Vanilla: `<id>.json` next to `client.jar`, saved by the version installer.
+    // Fabric / Quilt: `<loader>-<mc>-<loader version>.json` profiles.
+    let entries = std::fs::read_dir(instance_dir)
+        .into_iter()
+        .flatten()
+        .flatten();
+    for entry in entries {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(stem) = file_name.strip_suffix(".json") else {
+            continue;
+        };
+        if stem == "instance" {
+            continue;
+        }
+
+        let loader_profile = [
+            ("fabric-", LoaderType::Fabric),
+            ("quilt-", LoaderType::Quilt),
+        ]
+        .into_iter()
+        .find_map(|(prefix, loader)| stem.strip_prefix(prefix).map(|rest| (rest, loader)));
+        if let Some((rest, loader)) = loader_profile {
+            if let Some((minecraft_version, loader_version)) = rest.rsplit_once('-') {
+                layout.loader = Some(loader);
+                layout.loader_version = Some(loader_version.to_string());
+                layout
+                    .minecraft_version
+                    .get_or_insert_with(|| minecraft_version.to_string());
+            }
+            continue;
+        }
+
+        if is_version_json(&entry.path(), stem) {
+            layout.minecraft_version = Some(stem.to_string());
+        }
+    }
+
+    // Forge / NeoForge: installed profiles under `minecraft/versions/<id>/`.
+    if layout.loader.is_none() {
+        let versions_dir = instance_dir.join("minecraft").join("versions");
+        let entries = std::fs::read_dir(versions_dir)
+            .into_iter()
+            .flatten()
+            .flatten();
+        for entry in entries {
+            let id = entry.file_name().to_string_lossy().into_owned();
+            if let Some(version) = id.strip_prefix("neoforge-") {
+                layout.loader = Some(LoaderType::NeoForge);
+                layout.loader_version = Some(version.to_string());
+            } else if let Some((_, version)) = id.split_once("-forge-") {
+                layout.loader = Some(LoaderType::Forge);
+                layout.loader_version = Some(version.to_string());
+            }
+        }
+    }
+
+    if layout.loader.is_none() && layout.minecraft_version.is_some() {
+        layout.loader = Some(LoaderType::Vanilla);
+    }
+    layout
+}
+
+/// Whether `path` is a Mojang version JSON for version `id`.
+fn is_version_json(path: &Path, id: &str) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+        .is_some_and(|json| {
+            json.get("id").and_then(Value::as_str) == Some(id)
+                && json.pointer("/downloads/client").is_some()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::InstanceManager;
+    use crate::core::test_support::temp_dir;
+
+    fn write_version_json(instance_dir: &Path, id: &str) {
+        std::fs::write(
+            instance_dir.join(format!("{id}.json")),
+            format!(r#"{{"id":"{id}","downloads":{{"client":{{"url":"x"}}}}}}"#),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn truncated_file_keeps_its_readable_fields() {
+        let root = temp_dir("instance-salvage-truncated");
+        let dir = root.join("abcdef12-0000");
+        std::fs::create_dir_all(&dir).unwrap();
+        write_version_json(&dir, "1.20.1");
+        std::fs::write(dir.join("fabric-1.20.1-0.15.11.json"), "{}").unwrap();
+
+        let mut original = Instance::new(
+            "Survival".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            Some("0.15.11".into()),
+            6144,
+            &root,
+        );
+        original.group = Some("Mods".into());
+        let json = serde_json::to_string_pretty(&original).unwrap();
+        let truncated = &json[..json.find("\"jvm_args\"").unwrap()];
+
+        let salvaged = salvage(&dir, truncated);
+        assert!(salvaged.needs_recovery);
+        assert_eq!(salvaged.state, InstanceState::Error);
+        assert_eq!(salvaged.id, "abcdef12-0000");
+        assert_eq!(salvaged.path, dir);
+        assert_eq!(salvaged.name, "Survival");
+        assert_eq!(salvaged.max_memory_mb, 6144);
+        assert_eq!(salvaged.loader, LoaderType::Fabric);
+        assert_eq!(salvaged.loader_version.as_deref(), Some("0.15.11"));
+        assert_eq!(salvaged.created_at, original.created_at);
+        // `group` came after the cut; nothing invented for it.
+        assert_eq!(salvaged.group, None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unreadable_file_falls_back_to_the_layout() {
+        let root = temp_dir("instance-salvage-layout");
+        let dir = root.join("forge-instance");
+        std::fs::create_dir_all(dir.join("minecraft/versions/1.20.1-forge-47.2.0")).unwrap();
+        write_version_json(&dir, "1.20.1");
+
+        // Valid JSON, wrong types: only the fields that fit are kept.
+        let salvaged = salvage(
+            &dir,
+            r#"{"name": 42, "max_memory_mb": 3072, "loader": "?"}"#,
+        );
+        assert_eq!(salvaged.name, "Instancia recuperada forge-in");
+        assert_eq!(salvaged.max_memory_mb, 3072);
+        assert_eq!(salvaged.minecraft_version, "1.20.1");
+        assert_eq!(salvaged.loader, LoaderType::Forge);
+        assert_eq!(salvaged.loader_version.as_deref(), Some("47.2.0"));
+
+        let rebuilt = rebuild(salvaged);
+        assert!(!rebuilt.needs_recovery);
+        assert_eq!(rebuilt.state, InstanceState::Created);
+        assert!(rebuilt.main_class.is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn malformed_instance_json_still_appears_in_the_list() {
+        let root = temp_dir("instance-salvage-list");
+        let manager = InstanceManager::new(root.clone());
+        let healthy = manager
+            .create(Instance::new(
+                "Healthy".into(),
+                "1.20.1".into(),
+                LoaderType::Vanilla,
+                None,
+                2048,
+                &root,
+            ))
+            .await
+            .unwrap();
+
+        let broken_dir = root.join("broken");
+        std::fs::create_dir_all(&broken_dir).unwrap();
+        write_version_json(&broken_dir, "1.19.4");
+        std::fs::write(
+            broken_dir.join("instance.json"),
+            "{\n  \"name\": \"Old world\",\n  \"pa",
+        )
+        .unwrap();
+
+        let instances = manager.list().await.unwrap();
+        assert_eq!(instances.len(), 2);
+        let broken = instances.iter().find(|i| i.id == "broken").unwrap();
+        assert!(broken.needs_recovery);
+        assert_eq!(broken.name, "Old world");
+        assert_eq!(broken.minecraft_version, "1.19.4");
+        assert!(
+            !instances
+                .iter()
+                .find(|i| i.id == healthy.id)
+                .unwrap()
+                .needs_recovery
+        );
+
+        let recovered = manager.recover("broken").await.unwrap();
+        assert_eq!(recovered.state, InstanceState::Created);
+        // The damaged file is kept next to the rewritten one.
+        assert!(broken_dir.join("instance.json.corrupt").exists());
+        let reloaded = manager.load("broken").await.unwrap();
+        assert!(!reloaded.needs_recovery);
+        assert_eq!(reloaded.name, "Old world");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
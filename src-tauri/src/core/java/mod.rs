@@ -3,9 +3,12 @@ pub mod runtime;
 
 pub use runtime::detect_java_installations;
 pub use runtime::ensure_embedded_runtime_registered;
+pub use runtime::inspect_java_binary;
+pub use runtime::is_java_compatible_for_loader;
 pub use runtime::is_java_compatible_major;
 pub use runtime::managed_runtime_dir;
 pub use runtime::managed_runtime_info_in_dir;
+pub use runtime::pinned_runtime_in_dir;
 pub use runtime::required_java_for_minecraft_version;
 pub use runtime::resolve_java_binary_in_dir;
 pub use runtime::resolve_runtime;
@@ -13,4 +16,5 @@ pub use runtime::resolve_runtime_in_dir;
 pub use runtime::JavaInstallation;
 pub use runtime::ManagedRuntimeInfo;
 pub use runtime::RuntimeDiagnostic;
+pub use runtime::RuntimeOptions;
 pub use runtime::RuntimeRole;
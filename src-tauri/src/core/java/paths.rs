@@ -1,10 +1,13 @@
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
 
 use crate::core::error::{LauncherError, LauncherResult};
 
 const APP_DIR_NAME: &str = "InterfaceOficial";
 
+/// Environment variable that replaces the data dir for portable installs
+/// and CI.
+pub const DATA_DIR_ENV: &str = "INTERFACE_DATA_DIR";
+
 #[derive(Debug, Clone)]
 pub struct RuntimePaths {
     app_data_dir: PathBuf,
@@ -13,6 +16,18 @@ pub struct RuntimePaths {
 }
 
 impl RuntimePaths {
+    /// Paths rooted at `data_dir`, created if missing.
+    pub fn under(data_dir: &Path) -> LauncherResult<Self> {
+        let temp_dir = std::env::temp_dir().join(APP_DIR_NAME);
+        let resource_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources");
+
+        Ok(Self {
+            app_data_dir: canonical_or_create_dir(data_dir)?,
+            resource_dir: canonical_or_create_dir(&resource_dir)?,
+            temp_dir: canonical_or_create_dir(&temp_dir)?,
+        })
+    }
+
     pub fn app_data_dir(&self) -> &Path {
         &self.app_data_dir
     }
@@ -26,32 +41,27 @@ impl RuntimePaths {
     }
 }
 
-static RUNTIME_PATHS: OnceLock<RuntimePaths> = OnceLock::new();
-
-pub fn runtime_paths() -> LauncherResult<&'static RuntimePaths> {
-    if let Some(paths) = RUNTIME_PATHS.get() {
-        return Ok(paths);
-    }
-
-    let data_dir = dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(APP_DIR_NAME);
-
-    let temp_dir = std::env::temp_dir().join(APP_DIR_NAME);
-    let resource_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources");
-
-    let canonical_data = canonical_or_create_dir(&data_dir)?;
-    let canonical_temp = canonical_or_create_dir(&temp_dir)?;
-    let canonical_resource = canonical_or_create_dir(&resource_dir)?;
-
-    let paths = RuntimePaths {
-        app_data_dir: canonical_data,
-        resource_dir: canonical_resource,
-        temp_dir: canonical_temp,
-    };
+/// `INTERFACE_DATA_DIR`, when set to a non-empty path.
+pub fn data_dir_override() -> Option<PathBuf> {
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
 
-    let _ = RUNTIME_PATHS.set(paths);
-    Ok(RUNTIME_PATHS.get().expect("runtime paths set"))
+/// Fail early with the offending path when `dir` cannot be created or
+/// written to, instead of on the first download.
+pub fn ensure_writable_dir(dir: &Path) -> LauncherResult<()> {
+    std::fs::create_dir_all(dir).map_err(|source| LauncherError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let probe = dir.join(format!(".write-probe-{}", std::process::id()));
+    std::fs::write(&probe, b"").map_err(|source| LauncherError::Io {
+        path: probe.clone(),
+        source,
+    })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
 }
 
 fn canonical_or_create_dir(path: &Path) -> LauncherResult<PathBuf> {
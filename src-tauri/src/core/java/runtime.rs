@@ -1,8 +1,8 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::io::{Seek, SeekFrom};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use chrono::Utc;
@@ -12,16 +12,24 @@ use tokio::io::AsyncWriteExt;
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::core::atomic_file;
+use crate::core::disk_space;
+use crate::core::downloader::throttle::BandwidthThrottle;
 use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{InstanceManager, LoaderType};
+use crate::core::mirrors::DownloadMirrors;
 
-use super::paths::{runtime_paths, RuntimePaths};
+use super::paths::RuntimePaths;
 
 const ADOPTIUM_API_BASE: &str = "https://api.adoptium.net/v3/assets/latest";
+const ZULU_API_BASE: &str = "https://api.azul.com/metadata/v1/zulu/packages";
 const RESOLVED_CACHE_FILE: &str = "resolved_java.json";
 const RUNTIME_SCHEMA_VERSION: u32 = 4;
 const RUNTIME_LAYOUT_VERSION: &str = "v1";
 const RUNTIME_LOCK_STALE_SECS: i64 = 60 * 10;
-const RUNTIME_KEEP_PER_MAJOR: usize = 2;
+/// Default of the `runtime_keep_per_major` launcher setting.
+pub const DEFAULT_RUNTIME_KEEP_PER_MAJOR: usize = 2;
 const RUNTIME_USER_AGENT: &str = "InterfaceOficial-RuntimeManager/1.0";
 const ADOPTIUM_CACHE_FILE: &str = "adoptium_cache.json";
 const ADOPTIUM_CACHE_TTL_SECS: i64 = 60 * 30;
@@ -29,6 +37,136 @@ const GLOBAL_BACKOFF_429_FILE: &str = "adoptium_backoff_429.json";
 const GLOBAL_BACKOFF_429_SECS: i64 = 30;
 const MIN_FREE_DISK_BYTES: u64 = 512 * 1024 * 1024;
 
+/// Distribution managed runtimes are downloaded from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuntimeVendor {
+    #[default]
+    Temurin,
+    Zulu,
+    #[serde(rename = "graalvm")]
+    GraalVm,
+    Corretto,
+}
+
+impl RuntimeVendor {
+    /// Name stored in `RuntimeMetadata::vendor` and runtime identifiers.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Temurin => "Temurin",
+            Self::Zulu => "Zulu",
+            Self::GraalVm => "GraalVM",
+            Self::Corretto => "Corretto",
+        }
+    }
+}
+
+/// Launcher settings that shape how runtimes are resolved and downloaded,
+/// passed down from `AppState::runtime_options`.
+#[derive(Debug, Clone)]
+pub struct RuntimeOptions {
+    /// Vendor tried first for new runtime downloads. Installed runtimes of
+    /// other vendors keep being used.
+    pub vendor: RuntimeVendor,
+    /// How many runtimes of one major (and arch) survive the cleanup after
+    /// an install; at least 1.
+    pub keep_per_major: usize,
+    /// Fall back to x64 runtimes on arm64 hosts that can emulate them.
+    pub allow_x64_emulation: bool,
+    /// Re-hash the recorded key files of each runtime when selecting one.
+    /// Off by default: hashing `lib/modules` costs about a second per runtime.
+    pub verify_integrity: bool,
+    /// Mirror for the vendor APIs (Adoptium).
+    pub mirrors: DownloadMirrors,
+    /// The downloader's bandwidth cap, applied to runtime archives too.
+    pub throttle: Option<Arc<BandwidthThrottle>>,
+}
+
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            vendor: RuntimeVendor::default(),
+            keep_per_major: DEFAULT_RUNTIME_KEEP_PER_MAJOR,
+            allow_x64_emulation: false,
+            verify_integrity: false,
+            mirrors: DownloadMirrors::default(),
+            throttle: None,
+        }
+    }
+}
+
+/// Vendors to query for a download: the chosen one, then Temurin.
+fn vendor_fallback_order(preferred: RuntimeVendor) -> Vec<RuntimeVendor> {
+    let mut order = vec![preferred];
+    if preferred != RuntimeVendor::Temurin {
+        order.push(RuntimeVendor::Temurin);
+    }
+    order
+}
+
+/// Archive format runtimes are published in for `os`: zip on Windows,
+/// tar.gz elsewhere.
+fn runtime_archive_extension(os: &str) -> &'static str {
+    if os == "windows" {
+        "zip"
+    } else {
+        "tar.gz"
+    }
+}
+
+/// Where to look up a `image_type` (`jre` or `jdk`) build of `vendor`. For
+/// Temurin and Zulu this is a metadata API; Corretto and GraalVM publish
+/// stable "latest" archive URLs. `None` when the vendor has no such build.
+/// Temurin picks the platform's archive format itself.
+fn vendor_release_url(
+    vendor: RuntimeVendor,
+    major: u32,
+    arch: &str,
+    os: &str,
+    image_type: &str,
+) -> Option<String> {
+    // Temurin gets the launcher's own names; the others spell them
+    // `macos` and `aarch64`.
+    let vendor_os = if os == "mac" { "macos" } else { os };
+    let vendor_arch = if arch == "arm64" { "aarch64" } else { arch };
+    let ext = runtime_archive_extension(os);
+    match vendor {
+        RuntimeVendor::Temurin => Some(format!(
+            "{ADOPTIUM_API_BASE}/{major}/hotspot?architecture={arch}&image_type={image_type}&os={os}"
+        )),
+        RuntimeVendor::Zulu => Some(format!(
+            "{ZULU_API_BASE}/?java_version={major}&os={vendor_os}&arch={vendor_arch}&archive_type={ext}&java_package_type={image_type}&javafx_bundled=false&release_status=ga&availability_types=CA&latest=true&page_size=1"
+        )),
+        RuntimeVendor::Corretto => Some(format!(
+            "https://corretto.aws/downloads/latest/amazon-corretto-{major}-{vendor_arch}-{vendor_os}-{image_type}.{ext}"
+        )),
+        // GraalVM only ships JDKs, starting with Java 17.
+        RuntimeVendor::GraalVm => (image_type == "jdk" && major >= 17).then(|| {
+            format!(
+                "https://download.oracle.com/graalvm/{major}/latest/graalvm-jdk-{major}_{vendor_os}-{vendor_arch}_bin.{ext}"
+            )
+        }),
+    }
+}
+
+/// Architectures to try for a runtime, in order of preference. On arm64
+/// hosts with emulation allowed, x64 follows the native arch so old
+/// Minecraft versions without arm64 natives can still get a JRE.
+fn runtime_arch_fallback(native_arch: &str, emulation_allowed: bool) -> Vec<String> {
+    let mut archs = vec![native_arch.to_string()];
+    if native_arch == "arm64" && emulation_allowed {
+        archs.push("x64".to_string());
+    }
+    archs
+}
+
+/// Architectures usable on this host, honouring the emulation setting and
+/// whether an x64 emulator (Rosetta 2 / box64) is actually installed.
+fn runtime_archs(options: &RuntimeOptions) -> Vec<String> {
+    let allowed = options.allow_x64_emulation && platform::x64_emulation_available();
+    runtime_arch_fallback(&platform::platform_arch(), allowed)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RuntimeError {
     #[error("io error at {path:?}: {source}")]
@@ -65,6 +203,18 @@ pub struct JavaInstallation {
     pub major: u32,
     pub is_64bit: bool,
     pub vendor: String,
+    /// `java.runtime.name`, e.g. "OpenJDK Runtime Environment".
+    #[serde(default)]
+    pub runtime_name: Option<String>,
+    /// `java.vm.name`, e.g. "OpenJDK 64-Bit Server VM" or "Eclipse OpenJ9 VM".
+    #[serde(default)]
+    pub java_vm_name: Option<String>,
+    /// Whether the VM exposes `-XX:+UseShenandoahGC`.
+    #[serde(default)]
+    pub has_shenandoah: bool,
+    /// Whether the VM exposes `-XX:+UseZGC`.
+    #[serde(default)]
+    pub has_zgc: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,8 +226,32 @@ pub struct ManagedRuntimeInfo {
     pub arch: String,
     pub root: PathBuf,
     pub java_bin: PathBuf,
+    /// Imported from a folder outside the launcher; never deleted by it.
+    #[serde(default)]
+    pub external: bool,
+}
+
+/// Stage of a managed runtime install.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JavaInstallPhase {
+    Download,
+    Extract,
+    Validate,
+}
+
+/// Progress of a managed runtime install, emitted as `java-install-progress`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JavaInstallProgress {
+    pub major: u32,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub phase: JavaInstallPhase,
 }
 
+/// Optional observer of runtime installs; headless callers pass `None`.
+pub type JavaInstallProgressFn = dyn Fn(JavaInstallProgress) + Send + Sync;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum RuntimeRole {
     Gamma,
@@ -125,6 +299,18 @@ struct RuntimeMetadata {
     validated_at: Option<String>,
     #[serde(default)]
     validation: Option<RuntimeValidation>,
+    /// SHA-256 of the key JRE files, relative to the runtime root. Empty for
+    /// runtimes installed before it was recorded.
+    #[serde(default)]
+    file_hashes: BTreeMap<String, String>,
+    /// Added with [`import_runtime_in_dir`]: tracked, but never deleted by
+    /// the cleanup.
+    #[serde(default)]
+    external: bool,
+    /// Java home of an external runtime. Its folder under the runtimes dir
+    /// only holds `runtime.json`.
+    #[serde(default)]
+    external_root: Option<PathBuf>,
 }
 
 fn default_role() -> RuntimeRole {
@@ -174,6 +360,18 @@ struct AdoptiumVersion {
     openjdk_version: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct ZuluPackage {
+    package_uuid: String,
+    download_url: String,
+    java_version: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ZuluPackageDetails {
+    sha256_hash: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DownloadRuntimeSpec {
     major: u32,
@@ -200,11 +398,6 @@ struct CachedRuntimeSpec {
     spec: DownloadRuntimeSpec,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct DownloadCheckpoint {
-    downloaded_bytes: u64,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Backoff429State {
     until_ts: i64,
@@ -217,6 +410,66 @@ pub struct RuntimeDiagnostic {
     pub temp_dir: String,
     pub runtimes_root: String,
     pub indexed_runtimes: usize,
+    /// Unix time until which Adoptium requests wait after a 429, if pending.
+    pub backoff_until_ts: Option<i64>,
+    /// Adoptium release specs in `adoptium_cache.json`, fresh or not.
+    pub cached_specs: usize,
+    /// Age in seconds of the oldest cached spec.
+    pub oldest_cached_spec_secs: Option<i64>,
+}
+
+/// 429 backoff and spec cache state read from `base_dir`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RuntimeCacheStatus {
+    backoff_until_ts: Option<i64>,
+    cached_specs: usize,
+    oldest_cached_spec_secs: Option<i64>,
+}
+
+fn runtime_cache_status(base_dir: &Path, now_ts: i64) -> RuntimeCacheStatus {
+    let backoff_until_ts = std::fs::read(base_dir.join(GLOBAL_BACKOFF_429_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Backoff429State>(&bytes).ok())
+        .map(|state| state.until_ts)
+        .filter(|until_ts| *until_ts > now_ts);
+    let cache = std::fs::read(base_dir.join(ADOPTIUM_CACHE_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<AdoptiumCache>(&bytes).ok())
+        .unwrap_or_default();
+    RuntimeCacheStatus {
+        backoff_until_ts,
+        cached_specs: cache.entries.len(),
+        oldest_cached_spec_secs: cache
+            .entries
+            .values()
+            .map(|entry| now_ts.saturating_sub(entry.stored_at).max(0))
+            .max(),
+    }
+}
+
+/// Delete the Adoptium spec cache, the 429 backoff marker and the resolved
+/// Java caches in `base_dir`. Installed runtimes are left alone. Returns the
+/// files that existed and were removed.
+pub async fn clear_runtime_caches_in_dir(base_dir: &Path) -> LauncherResult<Vec<PathBuf>> {
+    let mut files = vec![
+        base_dir.join(ADOPTIUM_CACHE_FILE),
+        base_dir.join(GLOBAL_BACKOFF_429_FILE),
+    ];
+    files.extend(
+        [RuntimeRole::Gamma, RuntimeRole::Delta]
+            .into_iter()
+            .map(|role| resolved_cache_path(base_dir, role)),
+    );
+
+    let mut removed = Vec::new();
+    for path in files {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => removed.push(path),
+            Err(source) if source.kind() == std::io::ErrorKind::NotFound => {}
+            Err(source) => return Err(LauncherError::Io { path, source }),
+        }
+    }
+    Ok(removed)
 }
 
 #[derive(Debug, Clone)]
@@ -234,16 +487,22 @@ impl RuntimeManager {
         Ok(Self { paths, client })
     }
 
-    pub fn from_global_paths() -> LauncherResult<Self> {
-        Self::new(runtime_paths()?.clone())
+    /// Manager for the runtimes under `data_dir`.
+    pub fn for_data_dir(data_dir: &Path) -> LauncherResult<Self> {
+        Self::new(RuntimePaths::under(data_dir)?)
     }
 
-    pub async fn list_runtimes(&self) -> LauncherResult<Vec<ManagedRuntimeInfo>> {
+    pub async fn list_runtimes(
+        &self,
+        options: RuntimeOptions,
+    ) -> LauncherResult<Vec<ManagedRuntimeInfo>> {
         let mut out = Vec::new();
         for role in [RuntimeRole::Gamma, RuntimeRole::Delta] {
             let runtimes_root = runtimes_root_for_role(self.paths.app_data_dir(), role);
-            let candidates =
-                select::scan_runtime_candidates(&runtimes_root, &platform::platform_arch()).await?;
+            let mut candidates = Vec::new();
+            for arch in runtime_archs(&options) {
+                candidates.extend(select::scan_runtime_candidates(&runtimes_root, &arch).await?);
+            }
             for candidate in candidates {
                 out.push(ManagedRuntimeInfo {
                     identifier: candidate.metadata.identifier,
@@ -251,6 +510,7 @@ impl RuntimeManager {
                     vendor: candidate.metadata.vendor,
                     version: candidate.metadata.version,
                     arch: candidate.metadata.arch,
+                    external: candidate.metadata.external,
                     root: candidate.root,
                     java_bin: candidate.java_bin,
                 });
@@ -259,12 +519,17 @@ impl RuntimeManager {
         Ok(out)
     }
 
-    pub async fn resolve_java(&self, required_major: u32) -> LauncherResult<PathBuf> {
+    pub async fn resolve_java(
+        &self,
+        required_major: u32,
+        options: RuntimeOptions,
+    ) -> LauncherResult<PathBuf> {
         resolve_runtime_in_dir(
             self.paths.app_data_dir(),
             RuntimeRole::Gamma,
             required_major,
             None,
+            options,
         )
         .await
     }
@@ -273,6 +538,7 @@ impl RuntimeManager {
         &self,
         role: RuntimeRole,
         minecraft_version: Option<&str>,
+        options: RuntimeOptions,
     ) -> LauncherResult<PathBuf> {
         let required_major = role.expected_major(minecraft_version);
         resolve_runtime_in_dir(
@@ -280,6 +546,7 @@ impl RuntimeManager {
             role,
             required_major,
             minecraft_version,
+            options,
         )
         .await
     }
@@ -306,6 +573,15 @@ impl RuntimeManager {
             })
     }
 
+    /// Remove one managed runtime; see [`remove_runtime_in_dir`].
+    pub async fn remove_runtime(
+        &self,
+        identifier: &str,
+        in_use: &[PathBuf],
+    ) -> LauncherResult<bool> {
+        remove_runtime_in_dir(self.paths.app_data_dir(), identifier, in_use).await
+    }
+
     pub async fn diagnostics(&self) -> LauncherResult<RuntimeDiagnostic> {
         let runtimes_root = self
             .paths
@@ -320,15 +596,24 @@ impl RuntimeManager {
             .await?
             .runtimes
             .len();
+        let cache = runtime_cache_status(self.paths.app_data_dir(), Utc::now().timestamp());
         Ok(RuntimeDiagnostic {
             app_data_dir: self.paths.app_data_dir().to_string_lossy().to_string(),
             resource_dir: self.paths.resource_dir().to_string_lossy().to_string(),
             temp_dir: self.paths.temp_dir().to_string_lossy().to_string(),
             runtimes_root: runtimes_root.to_string_lossy().to_string(),
             indexed_runtimes: gamma + delta,
+            backoff_until_ts: cache.backoff_until_ts,
+            cached_specs: cache.cached_specs,
+            oldest_cached_spec_secs: cache.oldest_cached_spec_secs,
         })
     }
 
+    /// Drop the download caches; see [`clear_runtime_caches_in_dir`].
+    pub async fn clear_caches(&self) -> LauncherResult<Vec<PathBuf>> {
+        clear_runtime_caches_in_dir(self.paths.app_data_dir()).await
+    }
+
     pub fn http_client(&self) -> &reqwest::Client {
         &self.client
     }
@@ -350,18 +635,82 @@ fn runtimes_root_for_role(data_dir: &Path, role: RuntimeRole) -> PathBuf {
         .join(role.as_dir_name())
 }
 
-pub async fn resolve_java_binary(required_major: u32) -> LauncherResult<PathBuf> {
-    let base_dir = launcher_base_dir();
-    resolve_runtime_in_dir(&base_dir, RuntimeRole::Gamma, required_major, None).await
+/// Whether one of the `in_use` Java binaries lives inside `runtime_root`.
+fn runtime_in_use(runtime_root: &Path, in_use: &[PathBuf]) -> bool {
+    let canonical_root =
+        std::fs::canonicalize(runtime_root).unwrap_or_else(|_| runtime_root.to_path_buf());
+    in_use.iter().any(|java_bin| {
+        let canonical = std::fs::canonicalize(java_bin).unwrap_or_else(|_| java_bin.clone());
+        canonical.starts_with(&canonical_root) || java_bin.starts_with(runtime_root)
+    })
+}
+
+/// Delete the managed runtime `identifier` and drop it from its role's
+/// `index.json`. Refuses when one of the `in_use` Java binaries lives inside
+/// it. Returns `false` when no such runtime is installed.
+pub async fn remove_runtime_in_dir(
+    data_dir: &Path,
+    identifier: &str,
+    in_use: &[PathBuf],
+) -> LauncherResult<bool> {
+    let mut components = Path::new(identifier).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        return Err(LauncherError::Other(format!(
+            "Invalid runtime identifier: {identifier}"
+        )));
+    }
+
+    for role in [RuntimeRole::Gamma, RuntimeRole::Delta] {
+        let runtimes_root = runtimes_root_for_role(data_dir, role);
+        let runtime_root = runtimes_root.join(identifier);
+        let mut index = read_runtime_index(&runtimes_root).await?;
+        let indexed = index.runtimes.iter().any(|rt| rt.identifier == identifier);
+        if !indexed && !runtime_root.exists() {
+            continue;
+        }
+
+        if runtime_in_use(&runtime_root, in_use) {
+            return Err(LauncherError::Other(format!(
+                "Runtime {identifier} is in use by a running instance"
+            )));
+        }
+
+        if runtime_root.exists() {
+            tokio::fs::remove_dir_all(&runtime_root)
+                .await
+                .map_err(|source| LauncherError::Io {
+                    path: runtime_root.clone(),
+                    source,
+                })?;
+        }
+        if indexed {
+            index.runtimes.retain(|rt| rt.identifier != identifier);
+            let index_path = runtimes_root.join("index.json");
+            let payload = serde_json::to_vec_pretty(&index)?;
+            atomic_file::write_async(&index_path, payload)
+                .await
+                .map_err(|source| LauncherError::Io {
+                    path: index_path,
+                    source,
+                })?;
+        }
+        info!("Removed managed runtime {}", identifier);
+        return Ok(true);
+    }
+    Ok(false)
 }
 
 pub async fn resolve_runtime(
+    data_dir: &Path,
     role: RuntimeRole,
     minecraft_version: Option<&str>,
+    options: RuntimeOptions,
 ) -> LauncherResult<PathBuf> {
-    let base_dir = launcher_base_dir();
     let required_major = role.expected_major(minecraft_version);
-    resolve_runtime_in_dir(&base_dir, role, required_major, minecraft_version).await
+    resolve_runtime_in_dir(data_dir, role, required_major, minecraft_version, options).await
 }
 
 pub async fn ensure_embedded_runtime_registered(data_dir: &Path) -> LauncherResult<()> {
@@ -412,6 +761,9 @@ pub async fn ensure_embedded_runtime_registered(data_dir: &Path) -> LauncherResu
             expected_major: runtime_major,
             valid: info.major == runtime_major && info.is_64bit,
         }),
+        file_hashes: BTreeMap::new(),
+        external: false,
+        external_root: None,
     };
 
     let runtimes_root = runtimes_root_for_role(data_dir, role);
@@ -432,17 +784,129 @@ pub async fn ensure_embedded_runtime_registered(data_dir: &Path) -> LauncherResu
     Ok(())
 }
 
+/// Track the Java installed at `path` (a Java home or its `java` binary) as
+/// a managed runtime without copying it, so instances can pin it.
+pub async fn import_runtime_in_dir(
+    data_dir: &Path,
+    path: &Path,
+) -> LauncherResult<ManagedRuntimeInfo> {
+    let (java_home, java_bin) = if path.is_file() {
+        let home = path
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(path)
+            .to_path_buf();
+        (home, path.to_path_buf())
+    } else {
+        (path.to_path_buf(), locate_java_binary(path))
+    };
+    let java_home = std::fs::canonicalize(&java_home).map_err(|source| LauncherError::Io {
+        path: java_home.clone(),
+        source,
+    })?;
+    let Some(info) = probe::probe_java(&java_bin) else {
+        return Err(LauncherError::message(
+            ErrorText::JavaExecutableNotFound,
+            &[&path.display()],
+        ));
+    };
+    if !info.is_64bit {
+        return Err(LauncherError::message(
+            ErrorText::JavaNot64Bit,
+            &[&path.display()],
+        ));
+    }
+    register_external_runtime(data_dir, &java_home, &java_bin, &info).await
+}
+
+async fn register_external_runtime(
+    data_dir: &Path,
+    java_home: &Path,
+    java_bin: &Path,
+    info: &JavaInstallation,
+) -> LauncherResult<ManagedRuntimeInfo> {
+    let runtime_major = runtime_track(info.major);
+    let arch = platform::platform_arch();
+    let role = RuntimeRole::Gamma;
+    let metadata = RuntimeMetadata {
+        schema_version: RUNTIME_SCHEMA_VERSION,
+        identifier: format!(
+            "java{}-external-{}-{}",
+            runtime_major,
+            normalize_version_for_id(&info.version),
+            arch
+        ),
+        major: runtime_major,
+        vendor: info.vendor.clone(),
+        version: info.version.clone(),
+        arch: arch.clone(),
+        sha256_zip: String::new(),
+        // Left empty so updating the JDK in place does not invalidate it.
+        sha256_java: String::new(),
+        installed_at: Utc::now().to_rfc3339(),
+        source_url: format!("external://{}", java_home.display()),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        chmod_applied: true,
+        java_bin_rel: java_bin
+            .strip_prefix(java_home)
+            .ok()
+            .map(|p| p.to_string_lossy().to_string()),
+        role,
+        max_known_bytecode_major: Some(runtime_major + 44),
+        validated_at: Some(Utc::now().to_rfc3339()),
+        validation: Some(RuntimeValidation {
+            java_version_output: info.version.clone(),
+            major_detected: info.major,
+            is_64bit: info.is_64bit,
+            expected_major: runtime_major,
+            valid: info.major == runtime_major && info.is_64bit,
+        }),
+        file_hashes: BTreeMap::new(),
+        external: true,
+        external_root: Some(java_home.to_path_buf()),
+    };
+
+    let runtimes_root = runtimes_root_for_role(data_dir, role);
+    let metadata_root = runtimes_root.join(&metadata.identifier);
+    tokio::fs::create_dir_all(&metadata_root)
+        .await
+        .map_err(|source| LauncherError::Io {
+            path: metadata_root.clone(),
+            source,
+        })?;
+    write_runtime_metadata(&metadata_root, &metadata).await?;
+    update_runtime_index(&runtimes_root, &metadata).await?;
+    info!(
+        "Imported external runtime {} from {}",
+        metadata.identifier,
+        java_home.display()
+    );
+
+    Ok(ManagedRuntimeInfo {
+        identifier: metadata.identifier,
+        major: metadata.major,
+        vendor: metadata.vendor,
+        version: metadata.version,
+        arch: metadata.arch,
+        root: java_home.to_path_buf(),
+        java_bin: java_bin.to_path_buf(),
+        external: true,
+    })
+}
+
 pub async fn managed_runtime_info_in_dir(
     data_dir: &Path,
     required_major: u32,
+    options: RuntimeOptions,
 ) -> LauncherResult<Option<ManagedRuntimeInfo>> {
-    managed_runtime_info_by_role_in_dir(data_dir, RuntimeRole::Gamma, required_major).await
+    managed_runtime_info_by_role_in_dir(data_dir, RuntimeRole::Gamma, required_major, options).await
 }
 
 pub async fn managed_runtime_info_by_role_in_dir(
     data_dir: &Path,
     role: RuntimeRole,
     required_major: u32,
+    options: RuntimeOptions,
 ) -> LauncherResult<Option<ManagedRuntimeInfo>> {
     info!(
         "java runtime startup platform_os={} platform_arch={}",
@@ -450,11 +914,16 @@ pub async fn managed_runtime_info_by_role_in_dir(
         platform::platform_arch()
     );
     let runtime_major = runtime_track(required_major);
-    let arch = platform::platform_arch();
+    let archs = runtime_archs(&options);
     let runtimes_root = runtimes_root_for_role(data_dir, role);
 
-    let Some(candidate) =
-        select::best_compatible_runtime(&runtimes_root, runtime_major, &arch).await?
+    let Some(candidate) = select::best_compatible_runtime_for_archs(
+        &runtimes_root,
+        runtime_major,
+        &archs,
+        options.verify_integrity,
+    )
+    .await?
     else {
         return Ok(None);
     };
@@ -465,24 +934,53 @@ pub async fn managed_runtime_info_by_role_in_dir(
         vendor: candidate.metadata.vendor,
         version: candidate.metadata.version,
         arch: candidate.metadata.arch,
+        external: candidate.metadata.external,
         root: candidate.root,
         java_bin: candidate.java_bin,
     }))
 }
 
-pub async fn managed_runtime_info(
+/// Role and Java binary of the managed runtime `identifier`, if it is still
+/// installed under any role, belongs to the track of `required_major` and
+/// starts correctly.
+pub async fn pinned_runtime_in_dir(
+    data_dir: &Path,
+    identifier: &str,
     required_major: u32,
-) -> LauncherResult<Option<ManagedRuntimeInfo>> {
-    let base_dir = launcher_base_dir();
-    managed_runtime_info_in_dir(&base_dir, required_major).await
+    options: RuntimeOptions,
+) -> LauncherResult<Option<(RuntimeRole, PathBuf)>> {
+    let runtime_major = runtime_track(required_major);
+    let mut candidates = Vec::new();
+    for role in [RuntimeRole::Gamma, RuntimeRole::Delta] {
+        let runtimes_root = runtimes_root_for_role(data_dir, role);
+        for arch in runtime_archs(&options) {
+            candidates.extend(select::scan_runtime_candidates(&runtimes_root, &arch).await?);
+        }
+    }
+
+    Ok(
+        select::pinned_runtime(candidates, identifier, runtime_major)
+            .filter(|candidate| runtime_is_valid(&candidate.java_bin, runtime_major))
+            .map(|candidate| (candidate.metadata.role, candidate.java_bin)),
+    )
 }
 
-#[instrument(skip(data_dir))]
+#[instrument(skip(data_dir, on_progress))]
 pub async fn resolve_java_binary_in_dir(
     data_dir: &Path,
     required_major: u32,
+    options: RuntimeOptions,
+    on_progress: Option<&JavaInstallProgressFn>,
 ) -> LauncherResult<PathBuf> {
-    resolve_runtime_in_dir(data_dir, RuntimeRole::Gamma, required_major, None).await
+    resolve_runtime_with_progress(
+        data_dir,
+        RuntimeRole::Gamma,
+        required_major,
+        None,
+        options,
+        on_progress,
+    )
+    .await
 }
 
 pub async fn resolve_runtime_in_dir(
@@ -490,6 +988,26 @@ pub async fn resolve_runtime_in_dir(
     role: RuntimeRole,
     required_major: u32,
     minecraft_version: Option<&str>,
+    options: RuntimeOptions,
+) -> LauncherResult<PathBuf> {
+    resolve_runtime_with_progress(
+        data_dir,
+        role,
+        required_major,
+        minecraft_version,
+        options,
+        None,
+    )
+    .await
+}
+
+async fn resolve_runtime_with_progress(
+    data_dir: &Path,
+    role: RuntimeRole,
+    required_major: u32,
+    minecraft_version: Option<&str>,
+    options: RuntimeOptions,
+    on_progress: Option<&JavaInstallProgressFn>,
 ) -> LauncherResult<PathBuf> {
     let role = runtime_role_override().unwrap_or(role);
     let runtime_major = runtime_track(required_major);
@@ -506,7 +1024,7 @@ pub async fn resolve_runtime_in_dir(
         })?;
     cleanup_abandoned_runtime_locks(&runtimes_root).await;
 
-    let arch = platform::platform_arch();
+    let archs = runtime_archs(&options);
 
     if let Some(cached) = read_resolution_cache(data_dir, role, runtime_major)? {
         if runtime_is_valid(&cached, runtime_major) {
@@ -514,8 +1032,13 @@ pub async fn resolve_runtime_in_dir(
         }
     }
 
-    if let Some(existing) =
-        select::best_compatible_runtime(&runtimes_root, runtime_major, &arch).await?
+    if let Some(existing) = select::best_compatible_runtime_for_archs(
+        &runtimes_root,
+        runtime_major,
+        &archs,
+        options.verify_integrity,
+    )
+    .await?
     {
         write_resolution_cache(data_dir, role, runtime_major, &existing.java_bin).await?;
         return Ok(existing.java_bin);
@@ -523,26 +1046,48 @@ pub async fn resolve_runtime_in_dir(
 
     let lock_path = runtimes_root.join(format!(
         ".downloading_{:?}_java{}_{}.lock",
-        role, runtime_major, arch
+        role, runtime_major, archs[0]
     ));
     let _lock = acquire_runtime_lock(&lock_path).await?;
 
-    if let Some(existing) =
-        select::best_compatible_runtime(&runtimes_root, runtime_major, &arch).await?
+    if let Some(existing) = select::best_compatible_runtime_for_archs(
+        &runtimes_root,
+        runtime_major,
+        &archs,
+        options.verify_integrity,
+    )
+    .await?
     {
         write_resolution_cache(data_dir, role, runtime_major, &existing.java_bin).await?;
         return Ok(existing.java_bin);
     }
 
-    match install_runtime(&runtimes_root, role, runtime_major, &arch).await {
+    let pinned = pinned_runtime_identifiers(data_dir).await?;
+    match install_runtime(
+        data_dir,
+        role,
+        runtime_major,
+        &archs,
+        options,
+        &pinned,
+        on_progress,
+    )
+    .await
+    {
         Ok(installed) => {
             write_resolution_cache(data_dir, role, runtime_major, &installed).await?;
             Ok(installed)
         }
         Err(err) => {
-            if let Some(existing) =
-                select::any_compatible_runtime(&runtimes_root, runtime_major, &arch).await?
-            {
+            let mut cached = None;
+            for arch in &archs {
+                cached =
+                    select::any_compatible_runtime(&runtimes_root, runtime_major, arch).await?;
+                if cached.is_some() {
+                    break;
+                }
+            }
+            if let Some(existing) = cached {
                 warn!(
                     "Runtime install failed, using cached runtime {}: {}",
                     existing.metadata.identifier, err
@@ -564,12 +1109,11 @@ fn runtime_role_override() -> Option<RuntimeRole> {
     }
 }
 
-pub async fn detect_java_installations() -> Vec<JavaInstallation> {
-    detect_java_installations_sync()
+pub async fn detect_java_installations(data_dir: &Path) -> Vec<JavaInstallation> {
+    detect_java_installations_sync(data_dir)
 }
 
-pub fn detect_java_installations_sync() -> Vec<JavaInstallation> {
-    let data_dir = launcher_base_dir();
+pub fn detect_java_installations_sync(data_dir: &Path) -> Vec<JavaInstallation> {
     let runtimes_root = data_dir.join("runtimes");
     let mut detected: Vec<JavaInstallation> = Vec::new();
 
@@ -614,6 +1158,10 @@ pub fn detect_java_installations_sync() -> Vec<JavaInstallation> {
                 major,
                 is_64bit,
                 vendor: "system".to_string(),
+                runtime_name: None,
+                java_vm_name: None,
+                has_shenandoah: false,
+                has_zgc: false,
             });
         }
     }
@@ -623,14 +1171,38 @@ pub fn detect_java_installations_sync() -> Vec<JavaInstallation> {
     detected
 }
 
-#[instrument(skip(runtimes_root))]
+#[instrument(skip(data_dir, pinned, on_progress))]
 async fn install_runtime(
-    runtimes_root: &Path,
+    data_dir: &Path,
     role: RuntimeRole,
     required_major: u32,
-    arch: &str,
+    archs: &[String],
+    options: RuntimeOptions,
+    pinned: &[String],
+    on_progress: Option<&JavaInstallProgressFn>,
 ) -> LauncherResult<PathBuf> {
-    let spec = download::fetch_runtime_spec(required_major, arch).await?;
+    let report = |phase, downloaded_bytes, total_bytes| {
+        if let Some(on_progress) = on_progress {
+            on_progress(JavaInstallProgress {
+                major: required_major,
+                downloaded_bytes,
+                total_bytes,
+                phase,
+            });
+        }
+    };
+
+    let runtimes_root = &runtimes_root_for_role(data_dir, role);
+    let lookup = download::VendorLookup {
+        mirrors: &options.mirrors,
+        data_dir,
+    };
+    let spec =
+        download::fetch_runtime_spec_for_archs(required_major, archs, options.vendor, &lookup)
+            .await?;
+    // The metadata keeps the runtime's real arch (e.g. `x64` under Rosetta)
+    // so later scans for that arch find it.
+    let arch = spec.arch.clone();
     let identifier = format!(
         "java{}-{}-{}-{}",
         spec.major,
@@ -642,13 +1214,15 @@ async fn install_runtime(
     let runtime_root = runtimes_root.join(&identifier);
     let staging_id = Uuid::new_v4().to_string();
     let temp_root = runtimes_root.join("temp").join(format!("{staging_id}_dir"));
-    let zip_path = runtimes_root.join("temp").join(format!("{staging_id}.zip"));
+    let archive_path = runtimes_root
+        .join("temp")
+        .join(format!("{staging_id}.archive"));
 
     if temp_root.exists() {
         let _ = tokio::fs::remove_dir_all(&temp_root).await;
     }
 
-    if let Some(parent) = zip_path.parent() {
+    if let Some(parent) = archive_path.parent() {
         tokio::fs::create_dir_all(parent)
             .await
             .map_err(|source| LauncherError::Io {
@@ -659,16 +1233,30 @@ async fn install_runtime(
 
     let download_start = Instant::now();
     info!("Downloading runtime {} from {}", identifier, spec.url);
-    ensure_min_disk_space(runtimes_root, MIN_FREE_DISK_BYTES)?;
-    download::download_to_file_with_hash(&spec.url, &zip_path, &spec.sha256).await?;
+    disk_space::ensure_space(runtimes_root, MIN_FREE_DISK_BYTES)?;
+    let on_bytes = |downloaded, total| report(JavaInstallPhase::Download, downloaded, total);
+    download::download_to_file_with_hash(
+        data_dir,
+        &spec.url,
+        &archive_path,
+        &spec.sha256,
+        Some(&on_bytes),
+        options.throttle.as_deref(),
+    )
+    .await?;
     info!(
         "Runtime download finished in {:?}",
         download_start.elapsed()
     );
+    let archive_size = tokio::fs::metadata(&archive_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    report(JavaInstallPhase::Extract, archive_size, Some(archive_size));
 
     let extract_start = Instant::now();
-    ensure_min_disk_space(runtimes_root, MIN_FREE_DISK_BYTES)?;
-    extract::extract_zip_file(&zip_path, &temp_root)?;
+    disk_space::ensure_space(runtimes_root, MIN_FREE_DISK_BYTES)?;
+    extract::extract_runtime_archive(&archive_path, &temp_root)?;
     info!(
         "Runtime extraction finished in {:?}",
         extract_start.elapsed()
@@ -692,15 +1280,19 @@ async fn install_runtime(
         max_known_bytecode_major: Some(required_major + 44),
         validated_at: None,
         validation: None,
+        file_hashes: BTreeMap::new(),
+        external: false,
+        external_root: None,
     };
 
     ensure_java_executable_once(&temp_root, &metadata).await?;
     metadata.chmod_applied = true;
 
+    report(JavaInstallPhase::Validate, archive_size, Some(archive_size));
     let java_bin = locate_java_binary(&temp_root);
     let validation = build_runtime_validation(&java_bin, required_major);
     if !validation.valid {
-        let _ = tokio::fs::remove_file(&zip_path).await;
+        let _ = tokio::fs::remove_file(&archive_path).await;
         let _ = tokio::fs::remove_dir_all(&temp_root).await;
         return Err(LauncherError::Other(format!(
             "Downloaded runtime failed validation: {}",
@@ -712,6 +1304,7 @@ async fn install_runtime(
     metadata.validation = Some(validation);
 
     metadata.sha256_java = sha256_file(&java_bin)?;
+    metadata.file_hashes = integrity_manifest(&temp_root, &java_bin)?;
     metadata.java_bin_rel = java_bin
         .strip_prefix(&temp_root)
         .ok()
@@ -741,10 +1334,17 @@ async fn install_runtime(
         });
     }
 
-    let _ = tokio::fs::remove_file(&zip_path).await;
+    let _ = tokio::fs::remove_file(&archive_path).await;
     let _ = tokio::fs::remove_dir_all(&backup_root).await;
     update_runtime_index(runtimes_root, &metadata).await?;
-    cleanup_old_runtimes(runtimes_root, required_major, arch).await?;
+    cleanup_old_runtimes(
+        runtimes_root,
+        required_major,
+        &arch,
+        options.keep_per_major,
+        pinned,
+    )
+    .await?;
 
     let final_java = locate_java_binary(&runtime_root);
     if probe::probe_java(&final_java).is_none() {
@@ -763,7 +1363,7 @@ async fn write_runtime_metadata(
 ) -> LauncherResult<()> {
     let metadata_path = runtime_root.join("runtime.json");
     let payload = serde_json::to_vec_pretty(metadata)?;
-    tokio::fs::write(&metadata_path, payload)
+    atomic_file::write_async(&metadata_path, payload)
         .await
         .map_err(|source| LauncherError::Io {
             path: metadata_path,
@@ -789,7 +1389,7 @@ async fn update_runtime_index(
     index.runtimes.push(new_metadata.clone());
 
     let payload = serde_json::to_vec_pretty(&index)?;
-    tokio::fs::write(&index_path, payload)
+    atomic_file::write_async(&index_path, payload)
         .await
         .map_err(|source| LauncherError::Io {
             path: index_path,
@@ -806,33 +1406,124 @@ async fn read_runtime_index(runtimes_root: &Path) -> LauncherResult<RuntimeIndex
     Ok(serde_json::from_slice::<RuntimeIndex>(&bytes).unwrap_or_default())
 }
 
-#[instrument(skip(runtimes_root))]
-async fn cleanup_old_runtimes(runtimes_root: &Path, major: u32, arch: &str) -> LauncherResult<()> {
-    let mut index = read_runtime_index(runtimes_root).await?;
+/// Runtime identifiers the instances in `data_dir` are pinned to.
+async fn pinned_runtime_identifiers(data_dir: &Path) -> LauncherResult<Vec<String>> {
+    let instances = InstanceManager::new(data_dir.join("instances"))
+        .list()
+        .await?;
+    Ok(instances
+        .into_iter()
+        .filter_map(|instance| instance.pinned_runtime_identifier)
+        .collect())
+}
+
+/// Identifiers of the `major`/`arch` runtimes beyond the `keep` most
+/// recently installed ones. External runtimes and the `pinned` ones are
+/// never stale and do not count towards `keep`.
+fn stale_runtimes(
+    index: &RuntimeIndex,
+    major: u32,
+    arch: &str,
+    keep: usize,
+    pinned: &[String],
+) -> Vec<String> {
     let mut same_major = index
         .runtimes
         .iter()
-        .filter(|rt| rt.major == major && rt.arch == arch)
-        .cloned()
+        .filter(|rt| {
+            rt.major == major && rt.arch == arch && !rt.external && !pinned.contains(&rt.identifier)
+        })
         .collect::<Vec<_>>();
-
     same_major.sort_by(|a, b| a.installed_at.cmp(&b.installed_at).reverse());
-    for stale in same_major.into_iter().skip(RUNTIME_KEEP_PER_MAJOR) {
-        let path = runtimes_root.join(&stale.identifier);
+    same_major
+        .into_iter()
+        .skip(keep.max(1))
+        .map(|rt| rt.identifier.clone())
+        .collect()
+}
+
+#[instrument(skip(runtimes_root, pinned))]
+async fn cleanup_old_runtimes(
+    runtimes_root: &Path,
+    major: u32,
+    arch: &str,
+    keep: usize,
+    pinned: &[String],
+) -> LauncherResult<()> {
+    let mut index = read_runtime_index(runtimes_root).await?;
+    for stale in stale_runtimes(&index, major, arch, keep, pinned) {
+        let path = runtimes_root.join(&stale);
         if path.exists() {
             let _ = tokio::fs::remove_dir_all(&path).await;
         }
-        index
-            .runtimes
-            .retain(|rt| rt.identifier != stale.identifier);
+        index.runtimes.retain(|rt| rt.identifier != stale);
     }
 
     let index_path = runtimes_root.join("index.json");
     let payload = serde_json::to_vec_pretty(&index)?;
-    tokio::fs::write(index_path, payload).await?;
+    atomic_file::write_async(&index_path, payload).await?;
     Ok(())
 }
 
+/// Apply the keep-per-major policy to every major and arch of both roles
+/// right away. Runtimes holding one of the `in_use` Java binaries and the
+/// ones instances are `pinned` to are kept. Returns the identifiers that
+/// were removed.
+pub async fn prune_runtimes_in_dir(
+    data_dir: &Path,
+    keep: usize,
+    in_use: &[PathBuf],
+    pinned: &[String],
+) -> LauncherResult<Vec<String>> {
+    let mut removed = Vec::new();
+    for role in [RuntimeRole::Gamma, RuntimeRole::Delta] {
+        let runtimes_root = runtimes_root_for_role(data_dir, role);
+        let mut index = read_runtime_index(&runtimes_root).await?;
+        let mut groups = index
+            .runtimes
+            .iter()
+            .map(|rt| (rt.major, rt.arch.clone()))
+            .collect::<Vec<_>>();
+        groups.sort();
+        groups.dedup();
+
+        let mut changed = false;
+        for (major, arch) in groups {
+            for stale in stale_runtimes(&index, major, &arch, keep, pinned) {
+                let runtime_root = runtimes_root.join(&stale);
+                if runtime_in_use(&runtime_root, in_use) {
+                    warn!("Keeping runtime {} while a running instance uses it", stale);
+                    continue;
+                }
+                if runtime_root.exists() {
+                    tokio::fs::remove_dir_all(&runtime_root)
+                        .await
+                        .map_err(|source| LauncherError::Io {
+                            path: runtime_root.clone(),
+                            source,
+                        })?;
+                }
+                index.runtimes.retain(|rt| rt.identifier != stale);
+                info!("Pruned managed runtime {}", stale);
+                removed.push(stale);
+                changed = true;
+            }
+        }
+
+        if changed {
+            let index_path = runtimes_root.join("index.json");
+            let payload = serde_json::to_vec_pretty(&index)?;
+            atomic_file::write_async(&index_path, payload)
+                .await
+                .map_err(|source| LauncherError::Io {
+                    path: index_path,
+                    source,
+                })?;
+        }
+    }
+    Ok(removed)
+}
+
 async fn acquire_runtime_lock(lock_path: &Path) -> LauncherResult<RuntimeLockGuard> {
     let mut attempts = 0_u32;
     loop {
@@ -934,30 +1625,65 @@ fn runtime_hash_matches(candidate: &RuntimeCandidate) -> bool {
     }
 }
 
-fn ensure_min_disk_space(path: &Path, minimum_bytes: u64) -> LauncherResult<()> {
-    let disks = sysinfo::Disks::new_with_refreshed_list();
-    let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
-    let mut best_len = 0usize;
-    let mut available = None;
-    for disk in disks.list() {
-        let mount = disk.mount_point();
-        if canonical.starts_with(mount) {
-            let len = mount.as_os_str().len();
-            if len >= best_len {
-                best_len = len;
-                available = Some(disk.available_space());
+/// Directories of a Java home whose files go into the integrity manifest:
+/// the launchers, the module image and the native libraries.
+const INTEGRITY_DIRS: [&str; 4] = ["bin", "lib", "lib/server", "lib/jli"];
+
+/// Hash the files directly inside [`INTEGRITY_DIRS`] of the Java home that
+/// holds `java_bin`. Keys are relative to `runtime_root` with `/` separators.
+fn integrity_manifest(
+    runtime_root: &Path,
+    java_bin: &Path,
+) -> LauncherResult<BTreeMap<String, String>> {
+    let Some(java_home) = java_bin.parent().and_then(Path::parent) else {
+        return Ok(BTreeMap::new());
+    };
+    let mut manifest = BTreeMap::new();
+    for dir in INTEGRITY_DIRS {
+        let Ok(entries) = std::fs::read_dir(java_home.join(dir)) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if !entry.file_type().is_ok_and(|file_type| file_type.is_file()) {
+                continue;
             }
+            let Ok(relative) = path.strip_prefix(runtime_root) else {
+                continue;
+            };
+            let key = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            manifest.insert(key, sha256_file(&path)?);
         }
     }
-    if let Some(bytes) = available {
-        if bytes < minimum_bytes {
-            return Err(LauncherError::Other(format!(
-                "Espacio insuficiente para instalar runtime: disponible={} requerido={}",
-                bytes, minimum_bytes
-            )));
-        }
+    Ok(manifest)
+}
+
+/// Files of the manifest that are missing or no longer match their hash.
+fn integrity_drift(runtime_root: &Path, manifest: &BTreeMap<String, String>) -> Vec<String> {
+    manifest
+        .iter()
+        .filter(|(relative, expected)| {
+            !sha256_file(&runtime_root.join(relative.as_str()))
+                .is_ok_and(|actual| actual.eq_ignore_ascii_case(expected))
+        })
+        .map(|(relative, _)| relative.clone())
+        .collect()
+}
+
+fn runtime_integrity_ok(candidate: &RuntimeCandidate) -> bool {
+    let drift = integrity_drift(&candidate.root, &candidate.metadata.file_hashes);
+    if !drift.is_empty() {
+        warn!(
+            "Runtime {} changed since install: {}",
+            candidate.metadata.identifier,
+            drift.join(", ")
+        );
     }
-    Ok(())
+    drift.is_empty()
 }
 
 async fn cleanup_abandoned_runtime_locks(runtimes_root: &Path) {
@@ -984,21 +1710,26 @@ fn runtime_track(required_major: u32) -> u32 {
 }
 
 pub fn required_java_for_minecraft_version(minecraft_version: &str) -> u32 {
-    let lower = minecraft_version.to_ascii_lowercase();
-    if let Some(week_pos) = lower.find('w') {
-        let year_hint = &lower[..week_pos];
-        if year_hint.len() >= 2 {
-            let year_suffix = &year_hint[year_hint.len() - 2..];
-            if let Ok(snapshot_year) = year_suffix.parse::<u32>() {
-                if snapshot_year >= 24 {
-                    return 21;
-                }
-                return 17;
-            }
-        }
+    let lower = minecraft_version.trim().to_ascii_lowercase();
+    if let Some((year, week)) = parse_weekly_snapshot(&lower) {
+        // 24w14a moved to Java 21; 21w19a was the first snapshot needing Java 16+.
+        return if (year, week) >= (24, 14) {
+            21
+        } else if (year, week) >= (21, 19) {
+            17
+        } else {
+            8
+        };
     }
 
-    let mut parts = minecraft_version.split('.');
+    // Pre-releases and release candidates (`1.21-pre1`, `1.21-rc1`,
+    // `1.14 Pre-Release 1`) share the Java requirement of their release.
+    let release = lower
+        .split(['-', ' ', '_'])
+        .next()
+        .unwrap_or(lower.as_str());
+
+    let mut parts = release.split('.');
     let major = parts
         .next()
         .and_then(|p| p.parse::<u32>().ok())
@@ -1021,11 +1752,41 @@ pub fn required_java_for_minecraft_version(minecraft_version: &str) -> u32 {
     }
 }
 
+/// Parse weekly snapshot ids such as `23w51b` into `(year, week)`.
+fn parse_weekly_snapshot(id: &str) -> Option<(u32, u32)> {
+    let (year, rest) = id.split_once('w')?;
+    if year.len() != 2 || !year.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let week = rest.get(..2)?;
+    if !week.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((year.parse().ok()?, week.parse().ok()?))
+}
+
 pub fn is_java_compatible_major(installed_major: u32, required_major: u32) -> bool {
     installed_major >= required_major
         && runtime_track(installed_major) == runtime_track(required_major)
 }
 
+/// Like [`is_java_compatible_major`], but with `allow_newer_major` any Java at
+/// least as new as required is accepted for Vanilla, Fabric and Quilt. Forge
+/// and NeoForge keep strict tracks because their ASM only reads class files up
+/// to the Java they were built for.
+pub fn is_java_compatible_for_loader(
+    installed_major: u32,
+    required_major: u32,
+    loader: &LoaderType,
+    allow_newer_major: bool,
+) -> bool {
+    if allow_newer_major && !matches!(loader, LoaderType::Forge | LoaderType::NeoForge) {
+        installed_major >= required_major
+    } else {
+        is_java_compatible_major(installed_major, required_major)
+    }
+}
+
 pub fn is_usable_java_binary(path: &Path) -> bool {
     probe::probe_java(path).is_some()
 }
@@ -1205,12 +1966,6 @@ fn resolved_cache_path(data_dir: &Path, role: RuntimeRole) -> PathBuf {
     data_dir.join(format!("{:?}_{}", role, RESOLVED_CACHE_FILE).to_ascii_lowercase())
 }
 
-fn launcher_base_dir() -> PathBuf {
-    runtime_paths()
-        .map(|paths| paths.app_data_dir().to_path_buf())
-        .unwrap_or_else(|_| PathBuf::from("."))
-}
-
 fn read_resolution_cache(
     data_dir: &Path,
     role: RuntimeRole,
@@ -1245,7 +2000,7 @@ async fn write_resolution_cache(
         .by_major
         .insert(major.to_string(), canonical.to_string_lossy().to_string());
     let payload = serde_json::to_vec_pretty(&cache)?;
-    tokio::fs::write(path, payload).await?;
+    atomic_file::write_async(&path, payload).await?;
     Ok(())
 }
 
@@ -1285,6 +2040,22 @@ mod platform {
         }
     }
 
+    /// Whether x64 binaries can run on this arm64 host: Rosetta 2 on macOS,
+    /// box64 (registered with binfmt or on `PATH`) on Linux.
+    pub fn x64_emulation_available() -> bool {
+        if cfg!(target_os = "macos") {
+            return std::path::Path::new("/Library/Apple/usr/libexec/oah/libRosettaRuntime")
+                .exists();
+        }
+        if cfg!(target_os = "linux") {
+            return std::path::Path::new("/proc/sys/fs/binfmt_misc/box64").exists()
+                || std::env::var_os("PATH").is_some_and(|path| {
+                    std::env::split_paths(&path).any(|dir| dir.join("box64").is_file())
+                });
+        }
+        false
+    }
+
     pub fn platform_os() -> &'static str {
         match std::env::consts::OS {
             "windows" => "windows",
@@ -1301,33 +2072,50 @@ mod probe {
     #[instrument]
     pub fn probe_java(path: &Path) -> Option<JavaInstallation> {
         let output = Command::new(path)
-            .args(["-XshowSettings:properties", "-version"])
+            .args([
+                "-XshowSettings:properties",
+                "-XX:+UnlockExperimentalVMOptions",
+                "-XX:+PrintFlagsFinal",
+                "-version",
+            ])
             .output()
             .ok()?;
+        if let Some(info) = parse_output(path, &output) {
+            return Some(info);
+        }
 
-        parse_output(path, output)
-    }
-
-    fn parse_output(path: &Path, output: std::process::Output) -> Option<JavaInstallation> {
+        // VMs that reject the HotSpot flags still report their version without them.
+        let output = Command::new(path)
+            .args(["-XshowSettings:properties", "-version"])
+            .output()
+            .ok()?;
+        parse_output(path, &output)
+    }
+
+    fn parse_output(path: &Path, output: &std::process::Output) -> Option<JavaInstallation> {
         let version_output = format!(
             "{}\n{}",
             String::from_utf8_lossy(&output.stderr),
             String::from_utf8_lossy(&output.stdout)
         );
+        parse_probe_text(path, &version_output)
+    }
+
+    pub(super) fn parse_probe_text(path: &Path, version_output: &str) -> Option<JavaInstallation> {
         debug!(
             "Probing {:?}: {}",
             path,
             version_output.lines().next().unwrap_or("")
         );
 
-        let version_str = parse_version_string(&version_output)?;
+        let version_str = parse_version_string(version_output)?;
         let major = parse_major_version(&version_str);
         let lower_output = version_output.to_ascii_lowercase();
         let is_64bit = lower_output.contains("sun.arch.data.model = 64")
             || lower_output.contains("os.arch = amd64")
             || lower_output.contains("os.arch = x86_64")
             || lower_output.contains("os.arch = aarch64");
-        let vendor = parse_vendor(&version_output);
+        let vendor = parse_vendor(version_output);
 
         let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
 
@@ -1337,6 +2125,28 @@ mod probe {
             major,
             is_64bit,
             vendor,
+            runtime_name: parse_property(version_output, "java.runtime.name"),
+            java_vm_name: parse_property(version_output, "java.vm.name"),
+            has_shenandoah: has_vm_flag(version_output, "UseShenandoahGC"),
+            has_zgc: has_vm_flag(version_output, "UseZGC"),
+        })
+    }
+
+    /// Value of a `-XshowSettings:properties` entry (`    key = value`).
+    fn parse_property(output: &str, key: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key)
+                .then(|| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        })
+    }
+
+    /// Whether `-XX:+PrintFlagsFinal` listed `flag` (`bool UseZGC = false {product}`).
+    fn has_vm_flag(output: &str, flag: &str) -> bool {
+        output.lines().any(|line| {
+            let mut tokens = line.split_whitespace();
+            tokens.next() == Some("bool") && tokens.next() == Some(flag)
         })
     }
 
@@ -1369,14 +2179,72 @@ mod probe {
 
 mod download {
     use super::*;
-    use futures_util::StreamExt;
+    use crate::core::downloader::resume::ByteProgress;
+    use crate::core::downloader::throttle::BandwidthThrottle;
+    use crate::core::downloader::{download_resumable_with_progress, ExpectedHash};
+    use crate::core::net::{send_with_retry, RetryPolicy};
 
+    /// Where a vendor lookup goes: the mirror to try first and the data dir
+    /// holding the release cache and the 429 backoff marker.
+    pub struct VendorLookup<'a> {
+        pub mirrors: &'a DownloadMirrors,
+        pub data_dir: &'a Path,
+    }
+
+    /// Find a release of `preferred`, falling back to Temurin when that
+    /// vendor has nothing for `required_major` on this platform.
     pub async fn fetch_runtime_spec(
         required_major: u32,
         arch: &str,
+        preferred: RuntimeVendor,
+        lookup: &VendorLookup<'_>,
+    ) -> LauncherResult<DownloadRuntimeSpec> {
+        let mut last_error = None;
+        for vendor in vendor_fallback_order(preferred) {
+            match fetch_vendor_spec(vendor, required_major, arch, lookup).await {
+                Ok(spec) => return Ok(spec),
+                Err(err) => {
+                    warn!(
+                        "No {} runtime for Java {} ({}): {}",
+                        vendor.display_name(),
+                        required_major,
+                        arch,
+                        err
+                    );
+                    // A failed lookup wins over a vendor without the build,
+                    // so callers do not mistake it for a missing release.
+                    if last_error.as_ref().is_none_or(is_release_missing) {
+                        last_error = Some(err);
+                    }
+                }
+            }
+        }
+        Err(last_error.unwrap_or(LauncherError::JavaNotFound(required_major)))
+    }
+
+    /// Whether `err` only says there is no such release, as opposed to the
+    /// lookup failing (offline, rate limited, server error).
+    fn is_release_missing(err: &LauncherError) -> bool {
+        matches!(
+            err,
+            LauncherError::JavaNotFound(_) | LauncherError::DownloadFailed { status: 404, .. }
+        )
+    }
+
+    async fn fetch_vendor_spec(
+        vendor: RuntimeVendor,
+        required_major: u32,
+        arch: &str,
+        lookup: &VendorLookup<'_>,
     ) -> LauncherResult<DownloadRuntimeSpec> {
-        let cache_key = format!("{}:{}:{}", required_major, arch, platform::platform_os());
-        if let Some(spec) = read_cached_spec(&cache_key)? {
+        let cache_key = format!(
+            "{}:{}:{}:{}",
+            vendor.display_name().to_lowercase(),
+            required_major,
+            arch,
+            platform::platform_os()
+        );
+        if let Some(spec) = read_cached_spec(lookup.data_dir, &cache_key)? {
             return Ok(spec);
         }
 
@@ -1385,165 +2253,257 @@ mod download {
         let mut resolved_spec: Option<DownloadRuntimeSpec> = None;
 
         for image_type in ["jre", "jdk"] {
-            let api_url = format!(
-                "{}/{}/hotspot?architecture={}&image_type={}&os={}",
-                ADOPTIUM_API_BASE,
+            let Some(api_url) = vendor_release_url(
+                vendor,
                 required_major,
                 arch,
+                platform::platform_os(),
                 image_type,
-                platform::platform_os()
-            );
-
-            match get_with_retry(&client, &api_url, 3, 0).await {
-                Ok(response) => {
-                    let status = response.status();
-                    if !status.is_success() {
-                        last_download_error = Some(LauncherError::DownloadFailed {
-                            url: api_url,
-                            status: status.as_u16(),
-                        });
-                        continue;
-                    }
+            ) else {
+                continue;
+            };
 
-                    let releases: Vec<AdoptiumRelease> = response.json().await?;
-                    if let Some(found) = releases.into_iter().next() {
-                        resolved_spec = Some(DownloadRuntimeSpec {
-                            major: required_major,
-                            arch: arch.to_string(),
-                            vendor: "Temurin".to_string(),
-                            version: clean_openjdk_version(&found.version.openjdk_version),
-                            url: found.binary.package.link,
-                            sha256: found.binary.package.checksum,
-                        });
-                        break;
-                    }
+            let found = match vendor {
+                RuntimeVendor::Temurin => {
+                    fetch_temurin_release(client, lookup, &api_url, required_major, arch).await
+                }
+                RuntimeVendor::Zulu => {
+                    fetch_zulu_release(client, lookup, &api_url, required_major, arch).await
+                }
+                RuntimeVendor::Corretto | RuntimeVendor::GraalVm => {
+                    fetch_direct_release(client, lookup, vendor, &api_url, required_major, arch)
+                        .await
                 }
+            };
+            match found {
+                Ok(Some(spec)) => {
+                    resolved_spec = Some(spec);
+                    break;
+                }
+                Ok(None) => {}
                 Err(source) => last_download_error = Some(source),
             }
         }
 
         let Some(spec) = resolved_spec else {
-            if let Some(error) = last_download_error {
-                return Err(error);
-            }
-            return Err(LauncherError::Other(format!(
-                "No runtime release found for Java {} ({arch})",
-                required_major
-            )));
+            return Err(last_download_error.unwrap_or(LauncherError::JavaNotFound(required_major)));
         };
 
-        write_cached_spec(&cache_key, &spec)?;
+        write_cached_spec(lookup.data_dir, &cache_key, &spec)?;
         Ok(spec)
     }
 
-    pub async fn download_to_file_with_hash(
+    /// GET `url`, trying the mirror of `lookup` first.
+    async fn get_mirrored(
+        client: &reqwest::Client,
+        lookup: &VendorLookup<'_>,
         url: &str,
-        output_path: &Path,
-        expected_sha256: &str,
-    ) -> LauncherResult<()> {
-        let checkpoint_path = output_path.with_extension("checkpoint.json");
-        let mut start_offset = 0_u64;
-        if output_path.exists() {
-            start_offset = tokio::fs::metadata(output_path)
-                .await
-                .map(|m| m.len())
-                .unwrap_or_default();
-        }
-
-        if checkpoint_path.exists() {
-            if let Ok(bytes) = tokio::fs::read(&checkpoint_path).await {
-                if let Ok(checkpoint) = serde_json::from_slice::<DownloadCheckpoint>(&bytes) {
-                    if checkpoint.downloaded_bytes > start_offset {
-                        start_offset = checkpoint.downloaded_bytes;
+    ) -> LauncherResult<reqwest::Response> {
+        let response = match lookup.mirrors.rewrite(url) {
+            Some(mirrored) => {
+                match get_with_retry(client, lookup.data_dir, &mirrored, 3, 0).await {
+                    Ok(response) if response.status().is_success() => response,
+                    _ => {
+                        warn!("Runtime mirror {} failed; using {}", mirrored, url);
+                        get_with_retry(client, lookup.data_dir, url, 3, 0).await?
                     }
                 }
             }
-        }
-
-        let client = http_client()?;
-        let response = get_with_retry(&client, url, 3, start_offset).await?;
-        let status = response.status();
-        if !(status.is_success() || status.as_u16() == 206) {
+            None => get_with_retry(client, lookup.data_dir, url, 3, 0).await?,
+        };
+        if !response.status().is_success() {
             return Err(LauncherError::DownloadFailed {
                 url: url.to_string(),
-                status: status.as_u16(),
+                status: response.status().as_u16(),
             });
         }
+        Ok(response)
+    }
 
-        let output = output_path.to_path_buf();
-        let checkpoint = checkpoint_path.clone();
-        let mut file = tokio::task::spawn_blocking(move || -> LauncherResult<std::fs::File> {
-            let mut options = std::fs::OpenOptions::new();
-            options.create(true).write(true);
-            if start_offset > 0 && status.as_u16() == 206 {
-                options.read(true);
-                let mut file = options.open(&output).map_err(|source| LauncherError::Io {
-                    path: output.clone(),
-                    source,
-                })?;
-                file.seek(SeekFrom::Start(start_offset))
-                    .map_err(|source| LauncherError::Io {
-                        path: output.clone(),
-                        source,
-                    })?;
-                Ok(file)
-            } else {
-                options.truncate(true);
-                let file = options.open(&output).map_err(|source| LauncherError::Io {
-                    path: output.clone(),
-                    source,
-                })?;
-                let _ = std::fs::remove_file(&checkpoint);
-                Ok(file)
-            }
+    async fn fetch_temurin_release(
+        client: &reqwest::Client,
+        lookup: &VendorLookup<'_>,
+        api_url: &str,
+        required_major: u32,
+        arch: &str,
+    ) -> LauncherResult<Option<DownloadRuntimeSpec>> {
+        let releases: Vec<AdoptiumRelease> =
+            get_mirrored(client, lookup, api_url).await?.json().await?;
+        Ok(releases
+            .into_iter()
+            .next()
+            .map(|found| DownloadRuntimeSpec {
+                major: required_major,
+                arch: arch.to_string(),
+                vendor: RuntimeVendor::Temurin.display_name().to_string(),
+                version: clean_openjdk_version(&found.version.openjdk_version),
+                url: found.binary.package.link,
+                sha256: found.binary.package.checksum,
+            }))
+    }
+
+    /// The package list has no checksum; it comes from the package details.
+    async fn fetch_zulu_release(
+        client: &reqwest::Client,
+        lookup: &VendorLookup<'_>,
+        api_url: &str,
+        required_major: u32,
+        arch: &str,
+    ) -> LauncherResult<Option<DownloadRuntimeSpec>> {
+        let packages: Vec<ZuluPackage> =
+            get_mirrored(client, lookup, api_url).await?.json().await?;
+        let Some(package) = packages.into_iter().next() else {
+            return Ok(None);
+        };
+        let details_url = format!("{ZULU_API_BASE}/{}", package.package_uuid);
+        let details: ZuluPackageDetails = get_mirrored(client, lookup, &details_url)
+            .await?
+            .json()
+            .await?;
+        Ok(Some(DownloadRuntimeSpec {
+            major: required_major,
+            arch: arch.to_string(),
+            vendor: RuntimeVendor::Zulu.display_name().to_string(),
+            version: package
+                .java_version
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join("."),
+            url: package.download_url,
+            sha256: details.sha256_hash,
+        }))
+    }
+
+    /// Vendors with a fixed "latest" archive URL publish its SHA-256 next to
+    /// it. A missing checksum means there is no such build.
+    async fn fetch_direct_release(
+        client: &reqwest::Client,
+        lookup: &VendorLookup<'_>,
+        vendor: RuntimeVendor,
+        archive_url: &str,
+        required_major: u32,
+        arch: &str,
+    ) -> LauncherResult<Option<DownloadRuntimeSpec>> {
+        let checksum_url = match vendor {
+            RuntimeVendor::Corretto => archive_url.replacen("/latest/", "/latest_sha256/", 1),
+            _ => format!("{archive_url}.sha256"),
+        };
+        let response = match get_mirrored(client, lookup, &checksum_url).await {
+            Ok(response) => response,
+            Err(LauncherError::DownloadFailed { status: 404, .. }) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let checksum = response.text().await?;
+        let Some(sha256) = checksum.split_whitespace().next().map(str::to_string) else {
+            return Ok(None);
+        };
+
+        // Corretto redirects `latest` to `resources/<version>/...`.
+        let version = match client.head(archive_url).send().await {
+            Ok(head) => head
+                .url()
+                .path_segments()
+                .and_then(|mut segments| {
+                    segments
+                        .by_ref()
+                        .find(|segment| *segment == "resources")
+                        .and_then(|_| segments.next())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| format!("{required_major}-latest")),
+            Err(_) => format!("{required_major}-latest"),
+        };
+
+        Ok(Some(DownloadRuntimeSpec {
+            major: required_major,
+            arch: arch.to_string(),
+            vendor: vendor.display_name().to_string(),
+            version,
+            url: archive_url.to_string(),
+            sha256,
+        }))
+    }
+
+    /// Query each arch in order and return the first release found, so an
+    /// arm64 host can fall back to an emulated x64 runtime. Only a missing
+    /// release moves on to the next arch; a failed lookup is returned as is.
+    pub async fn fetch_runtime_spec_for_archs(
+        required_major: u32,
+        archs: &[String],
+        preferred: RuntimeVendor,
+        lookup: &VendorLookup<'_>,
+    ) -> LauncherResult<DownloadRuntimeSpec> {
+        first_release_for_archs(required_major, archs, |arch| {
+            fetch_runtime_spec(required_major, arch, preferred, lookup)
         })
         .await
-        .map_err(|e| LauncherError::Other(format!("Task join error: {e}")))??;
-
-        let mut stream = response.bytes_stream();
-        let mut downloaded = start_offset;
-        let output_for_write = output_path.to_path_buf();
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            let chunk_len = chunk.len();
-            let out = output_for_write.clone();
-            file = tokio::task::spawn_blocking(move || -> LauncherResult<std::fs::File> {
-                use std::io::Write;
-                let mut f = file;
-                f.write_all(&chunk)
-                    .map_err(|source| LauncherError::Io { path: out, source })?;
-                Ok(f)
-            })
-            .await
-            .map_err(|e| LauncherError::Other(format!("Task join error: {e}")))??;
+    }
 
-            downloaded = downloaded.saturating_add(chunk_len as u64);
-            if downloaded % (4 * 1024 * 1024) < chunk_len as u64 {
-                let payload = serde_json::to_vec(&DownloadCheckpoint {
-                    downloaded_bytes: downloaded,
-                })?;
-                tokio::fs::write(&checkpoint_path, payload)
-                    .await
-                    .map_err(|source| LauncherError::Io {
-                        path: checkpoint_path.clone(),
-                        source,
-                    })?;
+    pub async fn first_release_for_archs<'a, F, Fut>(
+        required_major: u32,
+        archs: &'a [String],
+        fetch: F,
+    ) -> LauncherResult<DownloadRuntimeSpec>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: std::future::Future<Output = LauncherResult<DownloadRuntimeSpec>>,
+    {
+        let mut last_error = None;
+        for (index, arch) in archs.iter().enumerate() {
+            match fetch(arch).await {
+                Ok(spec) => {
+                    if index > 0 {
+                        warn!(
+                            "No {} runtime for Java {}; falling back to {} under emulation",
+                            archs[0], required_major, arch
+                        );
+                    }
+                    return Ok(spec);
+                }
+                Err(err) if is_release_missing(&err) => last_error = Some(err),
+                Err(err) => return Err(err),
             }
         }
+        Err(last_error.unwrap_or(LauncherError::JavaNotFound(required_major)))
+    }
 
-        let actual = sha256_file(output_path)?;
-        if !actual.eq_ignore_ascii_case(expected_sha256) {
-            return Err(LauncherError::Other(format!(
-                "SHA-256 mismatch for {:?}: expected {}, got {}",
-                output_path, expected_sha256, actual
-            )));
+    pub async fn download_to_file_with_hash(
+        data_dir: &Path,
+        url: &str,
+        output_path: &Path,
+        expected_sha256: &str,
+        on_progress: Option<ByteProgress<'_>>,
+        throttle: Option<&BandwidthThrottle>,
+    ) -> LauncherResult<()> {
+        enforce_global_backoff_if_needed(data_dir).await;
+        let client = http_client()?;
+        let expected = ExpectedHash::Sha256(expected_sha256.to_string());
+        match download_resumable_with_progress(
+            client,
+            url,
+            output_path,
+            Some(&expected),
+            on_progress,
+            throttle,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                if matches!(err, LauncherError::DownloadFailed { status: 429, .. }) {
+                    persist_global_backoff_429(data_dir).await;
+                }
+                Err(err)
+            }
         }
-        let _ = tokio::fs::remove_file(&checkpoint_path).await;
-        Ok(())
     }
 
-    fn read_cached_spec(cache_key: &str) -> LauncherResult<Option<DownloadRuntimeSpec>> {
-        let path = cache_path();
+    fn read_cached_spec(
+        data_dir: &Path,
+        cache_key: &str,
+    ) -> LauncherResult<Option<DownloadRuntimeSpec>> {
+        let path = cache_path(data_dir);
         let bytes = match std::fs::read(&path) {
             Ok(bytes) => bytes,
             Err(_) => return Ok(None),
@@ -1558,8 +2518,12 @@ mod download {
         Ok(Some(entry.spec.clone()))
     }
 
-    fn write_cached_spec(cache_key: &str, spec: &DownloadRuntimeSpec) -> LauncherResult<()> {
-        let path = cache_path();
+    fn write_cached_spec(
+        data_dir: &Path,
+        cache_key: &str,
+        spec: &DownloadRuntimeSpec,
+    ) -> LauncherResult<()> {
+        let path = cache_path(data_dir);
         let mut cache = match std::fs::read(&path) {
             Ok(bytes) => serde_json::from_slice::<AdoptiumCache>(&bytes).unwrap_or_default(),
             Err(_) => AdoptiumCache::default(),
@@ -1576,12 +2540,12 @@ mod download {
         Ok(())
     }
 
-    fn cache_path() -> PathBuf {
-        launcher_base_dir().join(ADOPTIUM_CACHE_FILE)
+    fn cache_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(ADOPTIUM_CACHE_FILE)
     }
 
-    fn backoff_path() -> PathBuf {
-        launcher_base_dir().join(GLOBAL_BACKOFF_429_FILE)
+    fn backoff_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(GLOBAL_BACKOFF_429_FILE)
     }
 
     fn windows_retry_multiplier() -> u64 {
@@ -1632,8 +2596,8 @@ mod download {
         Ok(CLIENT.get().expect("http client set"))
     }
 
-    async fn enforce_global_backoff_if_needed() {
-        let path = backoff_path();
+    async fn enforce_global_backoff_if_needed(data_dir: &Path) {
+        let path = backoff_path(data_dir);
         let Ok(bytes) = tokio::fs::read(path).await else {
             return;
         };
@@ -1646,69 +2610,165 @@ mod download {
         }
     }
 
-    async fn persist_global_backoff_429() {
+    async fn persist_global_backoff_429(data_dir: &Path) {
         let state = Backoff429State {
             until_ts: Utc::now().timestamp() + GLOBAL_BACKOFF_429_SECS,
         };
         if let Ok(payload) = serde_json::to_vec(&state) {
-            let _ = tokio::fs::write(backoff_path(), payload).await;
+            let _ = tokio::fs::write(backoff_path(data_dir), payload).await;
         }
     }
 
     async fn get_with_retry(
         client: &reqwest::Client,
+        data_dir: &Path,
         url: &str,
         retries: u32,
         start_offset: u64,
     ) -> LauncherResult<reqwest::Response> {
-        enforce_global_backoff_if_needed().await;
-        let mut last_error: Option<LauncherError> = None;
-        for attempt in 0..=retries {
-            let mut req = client.get(url);
-            if start_offset > 0 {
-                req = req.header(reqwest::header::RANGE, format!("bytes={start_offset}-"));
-            }
-            match req.send().await {
-                Ok(response) => {
-                    if response.status().as_u16() == 429 {
-                        persist_global_backoff_429().await;
-                    }
-                    return Ok(response);
-                }
-                Err(err) => {
-                    last_error = Some(err.into());
-                    if attempt < retries {
-                        let backoff_ms = 2_u64.pow(attempt + 1) * 250 * windows_retry_multiplier();
-                        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                    }
+        enforce_global_backoff_if_needed(data_dir).await;
+        let policy = RetryPolicy {
+            retries,
+            base_delay: Duration::from_millis(500 * windows_retry_multiplier()),
+        };
+        let response = send_with_retry(
+            || {
+                let req = client.get(url);
+                if start_offset > 0 {
+                    req.header(reqwest::header::RANGE, format!("bytes={start_offset}-"))
+                } else {
+                    req
                 }
-            }
+            },
+            policy,
+        )
+        .await?;
+        if response.status().as_u16() == 429 {
+            persist_global_backoff_429(data_dir).await;
         }
-        Err(last_error.unwrap_or_else(|| LauncherError::Other(format!("failed request to {url}"))))
+        Ok(response)
     }
 }
 
 mod extract {
     use super::*;
+    use std::io::Read;
 
-    pub fn extract_zip_file(zip_path: &Path, runtime_root: &Path) -> LauncherResult<()> {
-        let zip_file = std::fs::File::open(zip_path).map_err(|source| LauncherError::Io {
-            path: zip_path.to_path_buf(),
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+    fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> LauncherError + '_ {
+        move |source| LauncherError::Io {
+            path: path.to_path_buf(),
             source,
-        })?;
-        let mut archive = zip::ZipArchive::new(zip_file)?;
+        }
+    }
 
+    /// Unpack a downloaded runtime into `runtime_root`, dropping the
+    /// archive's top-level folder. Zip and tar.gz are told apart by their
+    /// first bytes, since Temurin picks the format per platform.
+    pub fn extract_runtime_archive(archive_path: &Path, runtime_root: &Path) -> LauncherResult<()> {
+        let mut magic = [0u8; 2];
+        std::fs::File::open(archive_path)
+            .and_then(|mut file| file.read_exact(&mut magic))
+            .map_err(io_error(archive_path))?;
+        if magic == GZIP_MAGIC {
+            extract_tar_gz_file(archive_path, runtime_root)
+        } else {
+            extract_zip_file(archive_path, runtime_root)
+        }
+    }
+
+    fn reset_dir(runtime_root: &Path) -> LauncherResult<()> {
         if runtime_root.exists() {
-            std::fs::remove_dir_all(runtime_root).map_err(|source| LauncherError::Io {
-                path: runtime_root.to_path_buf(),
-                source,
-            })?;
+            std::fs::remove_dir_all(runtime_root).map_err(io_error(runtime_root))?;
         }
+        std::fs::create_dir_all(runtime_root).map_err(io_error(runtime_root))
+    }
+
+    /// `path` without its top-level folder; `None` for that folder itself
+    /// and for paths that are absolute or climb with `..`.
+    fn relative_to_top_level(path: &Path) -> Option<PathBuf> {
+        let mut components = path.components();
+        components.next();
+        let mut relative = PathBuf::new();
+        for component in components {
+            match component {
+                Component::Normal(part) => relative.push(part),
+                Component::CurDir => {}
+                _ => return None,
+            }
+        }
+        (!relative.as_os_str().is_empty()).then_some(relative)
+    }
+
+    /// Whether a symlink at `link` (relative to the runtime root) pointing to
+    /// `target` stays inside the runtime.
+    fn link_stays_inside(link: &Path, target: &Path) -> bool {
+        let mut depth = link.components().count().saturating_sub(1);
+        for component in target.components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => depth -= 1,
+                _ => return false,
+            }
+        }
+        true
+    }
 
-        std::fs::create_dir_all(runtime_root).map_err(|source| LauncherError::Io {
-            path: runtime_root.to_path_buf(),
-            source,
-        })?;
+    fn extract_tar_gz_file(archive_path: &Path, runtime_root: &Path) -> LauncherResult<()> {
+        let file = std::fs::File::open(archive_path).map_err(io_error(archive_path))?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        reset_dir(runtime_root)?;
+
+        let entries = archive.entries().map_err(io_error(archive_path))?;
+        for entry in entries {
+            let mut entry = entry.map_err(io_error(archive_path))?;
+            let path = entry.path().map_err(io_error(archive_path))?.into_owned();
+            let Some(rel_path) = relative_to_top_level(&path) else {
+                continue;
+            };
+            let out_path = runtime_root.join(&rel_path);
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent).map_err(io_error(parent))?;
+            }
+
+            let kind = entry.header().entry_type();
+            let link_target = entry
+                .link_name()
+                .map_err(io_error(archive_path))?
+                .map(|target| target.into_owned());
+            if kind.is_symlink() {
+                let inside = link_target
+                    .as_deref()
+                    .is_some_and(|target| link_stays_inside(&rel_path, target));
+                if !inside {
+                    return Err(LauncherError::message(
+                        ErrorText::RuntimeLinkOutside,
+                        &[&path.display()],
+                    ));
+                }
+            } else if kind.is_hard_link() {
+                // Hard link targets are archive paths, not relative to the link.
+                let source = link_target
+                    .as_deref()
+                    .and_then(relative_to_top_level)
+                    .map(|target| runtime_root.join(target))
+                    .ok_or_else(|| {
+                        LauncherError::message(ErrorText::InvalidRuntimeLink, &[&path.display()])
+                    })?;
+                std::fs::hard_link(&source, &out_path).map_err(io_error(&out_path))?;
+                continue;
+            }
+            entry.unpack(&out_path).map_err(io_error(&out_path))?;
+        }
+        Ok(())
+    }
+
+    fn extract_zip_file(archive_path: &Path, runtime_root: &Path) -> LauncherResult<()> {
+        let zip_file = std::fs::File::open(archive_path).map_err(io_error(archive_path))?;
+        let mut archive = zip::ZipArchive::new(zip_file)?;
+        reset_dir(runtime_root)?;
 
         for index in 0..archive.len() {
             let mut zipped = archive.by_index(index)?;
@@ -1766,6 +2826,7 @@ mod select {
         runtimes_root: &Path,
         required_major: u32,
         arch: &str,
+        verify_integrity: bool,
     ) -> LauncherResult<Option<RuntimeCandidate>> {
         let mut candidates = scan_runtime_candidates(runtimes_root, arch).await?;
         for candidate in &candidates {
@@ -1779,7 +2840,8 @@ mod select {
         candidates.retain(|candidate| {
             let major_matches = candidate.metadata.major == required_major;
             let valid_runtime = runtime_is_valid(&candidate.java_bin, required_major);
-            let hash_matches = runtime_hash_matches(candidate);
+            let hash_matches = runtime_hash_matches(candidate)
+                && (!verify_integrity || runtime_integrity_ok(candidate));
             let version_parsed = parse_java_version(&candidate.metadata.version).is_some();
 
             if !major_matches {
@@ -1819,6 +2881,36 @@ mod select {
         Ok(candidates.into_iter().next())
     }
 
+    /// `best_compatible_runtime` over several archs, preferring earlier ones.
+    pub async fn best_compatible_runtime_for_archs(
+        runtimes_root: &Path,
+        required_major: u32,
+        archs: &[String],
+        verify_integrity: bool,
+    ) -> LauncherResult<Option<RuntimeCandidate>> {
+        for arch in archs {
+            if let Some(candidate) =
+                best_compatible_runtime(runtimes_root, required_major, arch, verify_integrity)
+                    .await?
+            {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The candidate with exactly `identifier`, ignoring version ordering.
+    pub fn pinned_runtime(
+        candidates: Vec<RuntimeCandidate>,
+        identifier: &str,
+        required_major: u32,
+    ) -> Option<RuntimeCandidate> {
+        candidates.into_iter().find(|candidate| {
+            candidate.metadata.identifier == identifier
+                && candidate.metadata.major == required_major
+        })
+    }
+
     pub async fn any_compatible_runtime(
         runtimes_root: &Path,
         required_major: u32,
@@ -1885,6 +2977,10 @@ mod select {
                 continue;
             }
 
+            let root = match metadata.external_root.clone() {
+                Some(external_root) if metadata.external => external_root,
+                _ => root,
+            };
             let java_bin = metadata
                 .java_bin_rel
                 .as_ref()
@@ -1906,6 +3002,88 @@ mod select {
 mod tests {
     use super::*;
 
+    const TEMURIN_17_PROBE: &str = "Property settings:
+    java.runtime.name = OpenJDK Runtime Environment
+    java.vendor = Eclipse Adoptium
+    java.vm.name = OpenJDK 64-Bit Server VM
+    os.arch = amd64
+    sun.arch.data.model = 64
+
+openjdk version \"17.0.8\" 2023-07-18
+OpenJDK Runtime Environment Temurin-17.0.8+7 (build 17.0.8+7)
+OpenJDK 64-Bit Server VM Temurin-17.0.8+7 (build 17.0.8+7, mixed mode, sharing)
+[Global flags]
+     bool UseParallelGC                            = false                                     {product} {default}
+     bool UseShenandoahGC                          = false                                     {product} {default}
+     bool UseZGC                                   = false                                     {product} {default}
+";
+
+    const CORRETTO_8_PROBE: &str = "Property settings:
+    java.runtime.name = OpenJDK Runtime Environment
+    java.vendor = Amazon.com Inc.
+    java.vm.name = OpenJDK 64-Bit Server VM
+    os.arch = amd64
+    sun.arch.data.model = 64
+
+openjdk version \"1.8.0_382\"
+OpenJDK Runtime Environment Corretto-8.382.05.1 (build 1.8.0_382-b05)
+OpenJDK 64-Bit Server VM Corretto-8.382.05.1 (build 25.382-b05, mixed mode)
+[Global flags]
+     bool UseG1GC                                   = false                               {product}
+     bool UseParallelGC                             = false                               {product}
+";
+
+    const SEMERU_17_PROBE: &str = "Property settings:
+    java.runtime.name = IBM Semeru Runtime Open Edition
+    java.vm.name = Eclipse OpenJ9 VM
+    os.arch = aarch64
+    sun.arch.data.model = 64
+
+openjdk version \"17.0.8.1\" 2023-08-24
+IBM Semeru Runtime Open Edition 17.0.8.1 (build 17.0.8.1+1)
+Eclipse OpenJ9 VM 17.0.8.1 (build openj9-0.40.0, JRE 17 Linux aarch64-64-Bit)
+";
+
+    #[test]
+    fn probe_reports_runtime_and_gc_features_per_vendor() {
+        let path = Path::new("/nonexistent/bin/java");
+
+        let temurin = probe::parse_probe_text(path, TEMURIN_17_PROBE).unwrap();
+        assert_eq!(temurin.major, 17);
+        assert_eq!(
+            temurin.runtime_name.as_deref(),
+            Some("OpenJDK Runtime Environment")
+        );
+        assert_eq!(
+            temurin.java_vm_name.as_deref(),
+            Some("OpenJDK 64-Bit Server VM")
+        );
+        assert!(temurin.has_shenandoah && temurin.has_zgc);
+
+        let corretto = probe::parse_probe_text(path, CORRETTO_8_PROBE).unwrap();
+        assert_eq!(corretto.major, 8);
+        assert!(corretto.is_64bit);
+        assert!(!corretto.has_shenandoah && !corretto.has_zgc);
+
+        let semeru = probe::parse_probe_text(path, SEMERU_17_PROBE).unwrap();
+        assert_eq!(
+            semeru.runtime_name.as_deref(),
+            Some("IBM Semeru Runtime Open Edition")
+        );
+        assert_eq!(semeru.java_vm_name.as_deref(), Some("Eclipse OpenJ9 VM"));
+        assert!(!semeru.has_shenandoah && !semeru.has_zgc);
+    }
+
+    #[test]
+    fn java_installation_without_probe_features_still_deserializes() {
+        let info: JavaInstallation = serde_json::from_str(
+            r#"{"path":"/usr/bin/java","version":"17.0.8","major":17,"is_64bit":true,"vendor":"OpenJDK"}"#,
+        )
+        .unwrap();
+        assert!(info.runtime_name.is_none());
+        assert!(!info.has_zgc);
+    }
+
     #[test]
     fn test_parse_major_modern() {
         assert_eq!(parse_major_version("17.0.8"), 17);
@@ -1924,6 +3102,19 @@ mod tests {
         assert_eq!(required_java_for_minecraft_version("1.20.5"), 21);
     }
 
+    #[test]
+    fn java_required_by_snapshot_and_prerelease_ids() {
+        assert_eq!(required_java_for_minecraft_version("23w51b"), 17);
+        assert_eq!(required_java_for_minecraft_version("24w13a"), 17);
+        assert_eq!(required_java_for_minecraft_version("24w14a"), 21);
+        assert_eq!(required_java_for_minecraft_version("20w45a"), 8);
+        assert_eq!(required_java_for_minecraft_version("1.20.5-pre1"), 21);
+        assert_eq!(required_java_for_minecraft_version("1.20.4-rc1"), 17);
+        assert_eq!(required_java_for_minecraft_version("1.21-pre1"), 21);
+        assert_eq!(required_java_for_minecraft_version("1.21-rc1"), 21);
+        assert_eq!(required_java_for_minecraft_version("1.14 Pre-Release 1"), 8);
+    }
+
     #[test]
     fn java_runtime_track_mapping() {
         assert_eq!(runtime_track(8), 8);
@@ -1932,6 +3123,21 @@ mod tests {
         assert_eq!(runtime_track(21), 21);
     }
 
+    #[test]
+    fn newer_java_major_policy_is_relaxed_only_for_non_asm_loaders() {
+        for loader in [LoaderType::Vanilla, LoaderType::Fabric, LoaderType::Quilt] {
+            assert!(!is_java_compatible_for_loader(17, 8, &loader, false));
+            assert!(is_java_compatible_for_loader(17, 8, &loader, true));
+            assert!(is_java_compatible_for_loader(21, 17, &loader, true));
+            assert!(!is_java_compatible_for_loader(8, 17, &loader, true));
+        }
+        for loader in [LoaderType::Forge, LoaderType::NeoForge] {
+            assert!(!is_java_compatible_for_loader(17, 8, &loader, true));
+            assert!(!is_java_compatible_for_loader(21, 17, &loader, true));
+            assert!(is_java_compatible_for_loader(17, 17, &loader, true));
+        }
+    }
+
     #[test]
     fn java_version_comparison_prefers_newer() {
         assert_eq!(
@@ -1939,4 +3145,612 @@ mod tests {
             Some(Ordering::Less)
         );
     }
+
+    #[test]
+    fn arm64_falls_back_to_x64_only_when_allowed() {
+        assert_eq!(runtime_arch_fallback("arm64", true), ["arm64", "x64"]);
+        assert_eq!(runtime_arch_fallback("arm64", false), ["arm64"]);
+        assert_eq!(runtime_arch_fallback("x64", true), ["x64"]);
+    }
+
+    #[tokio::test]
+    async fn arm64_falls_back_to_x64_only_when_no_release_exists() {
+        let archs = runtime_arch_fallback("arm64", true);
+        let spec = |arch: &str| DownloadRuntimeSpec {
+            major: 8,
+            arch: arch.into(),
+            vendor: "Temurin".into(),
+            version: "8.0.412".into(),
+            url: String::new(),
+            sha256: String::new(),
+        };
+
+        let found = download::first_release_for_archs(8, &archs, |arch| async move {
+            match arch {
+                "arm64" => Err(LauncherError::JavaNotFound(8)),
+                _ => Ok(spec(arch)),
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.arch, "x64");
+
+        // Offline or rate limited: the arm64 lookup failed, it did not come
+        // back empty, so no x64 runtime is picked behind the user's back.
+        let failed = download::first_release_for_archs(8, &archs, |arch| async move {
+            match arch {
+                "arm64" => Err(LauncherError::DownloadFailed {
+                    url: "https://api.adoptium.net".into(),
+                    status: 503,
+                }),
+                _ => Ok(spec(arch)),
+            }
+        })
+        .await;
+        assert!(matches!(
+            failed,
+            Err(LauncherError::DownloadFailed { status: 503, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn emulated_runtime_is_found_by_its_recorded_arch() {
+        let runtimes_root =
+            std::env::temp_dir().join(format!("runtime-arch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&runtimes_root);
+        let runtime_root = runtimes_root.join("java8-temurin-8.0.412-x64");
+        std::fs::create_dir_all(&runtime_root).unwrap();
+
+        let metadata = RuntimeMetadata {
+            schema_version: RUNTIME_SCHEMA_VERSION,
+            identifier: "java8-temurin-8.0.412-x64".into(),
+            major: 8,
+            vendor: "Temurin".into(),
+            version: "8.0.412".into(),
+            arch: "x64".into(),
+            sha256_zip: String::new(),
+            sha256_java: String::new(),
+            installed_at: Utc::now().to_rfc3339(),
+            source_url: String::new(),
+            launcher_version: String::new(),
+            chmod_applied: true,
+            java_bin_rel: None,
+            role: RuntimeRole::Gamma,
+            max_known_bytecode_major: None,
+            validated_at: None,
+            validation: None,
+            file_hashes: BTreeMap::new(),
+            external: false,
+            external_root: None,
+        };
+        write_runtime_metadata(&runtime_root, &metadata)
+            .await
+            .unwrap();
+
+        let native_only = select::scan_runtime_candidates(&runtimes_root, "arm64")
+            .await
+            .unwrap();
+        assert!(native_only.is_empty());
+
+        let mut found = Vec::new();
+        for arch in runtime_arch_fallback("arm64", true) {
+            found.extend(
+                select::scan_runtime_candidates(&runtimes_root, &arch)
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].metadata.arch, "x64");
+
+        let _ = std::fs::remove_dir_all(&runtimes_root);
+    }
+
+    fn candidate(identifier: &str, major: u32, version: &str) -> RuntimeCandidate {
+        RuntimeCandidate {
+            metadata: RuntimeMetadata {
+                schema_version: RUNTIME_SCHEMA_VERSION,
+                identifier: identifier.into(),
+                major,
+                vendor: "Temurin".into(),
+                version: version.into(),
+                arch: "x64".into(),
+                sha256_zip: String::new(),
+                sha256_java: String::new(),
+                installed_at: Utc::now().to_rfc3339(),
+                source_url: String::new(),
+                launcher_version: String::new(),
+                chmod_applied: true,
+                java_bin_rel: None,
+                role: RuntimeRole::Gamma,
+                max_known_bytecode_major: None,
+                validated_at: None,
+                validation: None,
+                file_hashes: BTreeMap::new(),
+                external: false,
+                external_root: None,
+            },
+            root: PathBuf::from(identifier),
+            java_bin: PathBuf::from(identifier).join("bin/java"),
+        }
+    }
+
+    #[test]
+    fn pinned_runtime_wins_over_newer_versions() {
+        let candidates = vec![
+            candidate("java17-temurin-17.0.12-x64", 17, "17.0.12"),
+            candidate("java17-temurin-17.0.8-x64", 17, "17.0.8"),
+            candidate("java21-temurin-21.0.4-x64", 21, "21.0.4"),
+        ];
+
+        let pinned =
+            select::pinned_runtime(candidates.clone(), "java17-temurin-17.0.8-x64", 17).unwrap();
+        assert_eq!(pinned.metadata.version, "17.0.8");
+
+        // A pin on another track or a removed runtime falls back (None).
+        assert!(
+            select::pinned_runtime(candidates.clone(), "java21-temurin-21.0.4-x64", 17).is_none()
+        );
+        assert!(select::pinned_runtime(candidates, "java17-temurin-17.0.1-x64", 17).is_none());
+    }
+
+    #[tokio::test]
+    async fn removing_one_runtime_keeps_the_rest_of_the_index() {
+        let data_dir = std::env::temp_dir().join(format!("runtime-remove-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let runtimes_root = runtimes_root_for_role(&data_dir, RuntimeRole::Gamma);
+        let old = candidate("java17-temurin-17.0.8-x64", 17, "17.0.8").metadata;
+        let new = candidate("java17-temurin-17.0.12-x64", 17, "17.0.12").metadata;
+        for metadata in [&old, &new] {
+            std::fs::create_dir_all(runtimes_root.join(&metadata.identifier).join("bin")).unwrap();
+            update_runtime_index(&runtimes_root, metadata)
+                .await
+                .unwrap();
+        }
+
+        // A running game still uses the newer runtime.
+        let in_use = vec![runtimes_root.join(&new.identifier).join("bin").join("java")];
+        assert!(remove_runtime_in_dir(&data_dir, &new.identifier, &in_use)
+            .await
+            .is_err());
+        assert!(runtimes_root.join(&new.identifier).exists());
+
+        assert!(remove_runtime_in_dir(&data_dir, &old.identifier, &in_use)
+            .await
+            .unwrap());
+        assert!(!runtimes_root.join(&old.identifier).exists());
+        let index = read_runtime_index(&runtimes_root).await.unwrap();
+        let remaining: Vec<_> = index.runtimes.iter().map(|rt| &rt.identifier).collect();
+        assert_eq!(remaining, [&new.identifier]);
+
+        assert!(!remove_runtime_in_dir(&data_dir, &old.identifier, &in_use)
+            .await
+            .unwrap());
+        assert!(remove_runtime_in_dir(&data_dir, "../java17", &[])
+            .await
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn stale_runtimes_keep_the_newest_per_major_and_arch() {
+        let mut index = RuntimeIndex::default();
+        for (identifier, major, arch, installed_at) in [
+            ("java17-a", 17, "x64", "2024-01-01T00:00:00Z"),
+            ("java17-b", 17, "x64", "2024-03-01T00:00:00Z"),
+            ("java17-c", 17, "x64", "2024-02-01T00:00:00Z"),
+            ("java17-arm", 17, "arm64", "2023-01-01T00:00:00Z"),
+            ("java21-a", 21, "x64", "2024-01-01T00:00:00Z"),
+            ("java21-b", 21, "x64", "2024-04-01T00:00:00Z"),
+        ] {
+            let mut metadata = candidate(identifier, major, "0").metadata;
+            metadata.arch = arch.into();
+            metadata.installed_at = installed_at.into();
+            index.runtimes.push(metadata);
+        }
+
+        assert_eq!(stale_runtimes(&index, 17, "x64", 2, &[]), ["java17-a"]);
+        assert_eq!(
+            stale_runtimes(&index, 17, "x64", 1, &[]),
+            ["java17-c", "java17-a"]
+        );
+        assert!(stale_runtimes(&index, 17, "x64", 5, &[]).is_empty());
+        assert!(stale_runtimes(&index, 17, "arm64", 1, &[]).is_empty());
+        assert_eq!(stale_runtimes(&index, 21, "x64", 1, &[]), ["java21-a"]);
+        // Zero still keeps one runtime.
+        assert_eq!(stale_runtimes(&index, 21, "x64", 0, &[]), ["java21-a"]);
+        // A pinned runtime is neither removed nor counted.
+        let pinned = ["java17-a".to_string()];
+        assert_eq!(stale_runtimes(&index, 17, "x64", 1, &pinned), ["java17-c"]);
+    }
+
+    #[tokio::test]
+    async fn pruning_applies_the_policy_to_every_major() {
+        let data_dir = std::env::temp_dir().join(format!("runtime-prune-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let runtimes_root = runtimes_root_for_role(&data_dir, RuntimeRole::Gamma);
+        for (identifier, major, installed_at) in [
+            ("java17-pinned", 17, "2023-01-01T00:00:00Z"),
+            ("java17-old", 17, "2024-01-01T00:00:00Z"),
+            ("java17-new", 17, "2024-02-01T00:00:00Z"),
+            ("java21-old", 21, "2024-01-01T00:00:00Z"),
+            ("java21-new", 21, "2024-02-01T00:00:00Z"),
+        ] {
+            let mut metadata = candidate(identifier, major, "0").metadata;
+            metadata.installed_at = installed_at.into();
+            std::fs::create_dir_all(runtimes_root.join(identifier).join("bin")).unwrap();
+            update_runtime_index(&runtimes_root, &metadata)
+                .await
+                .unwrap();
+        }
+
+        // The old Java 21 still runs a game and an instance is pinned to the
+        // oldest Java 17, so both survive.
+        let in_use = vec![runtimes_root.join("java21-old").join("bin").join("java")];
+        let pinned = ["java17-pinned".to_string()];
+        let removed = prune_runtimes_in_dir(&data_dir, 1, &in_use, &pinned)
+            .await
+            .unwrap();
+        assert_eq!(removed, ["java17-old"]);
+        assert!(!runtimes_root.join("java17-old").exists());
+        assert!(runtimes_root.join("java17-pinned").exists());
+        assert!(runtimes_root.join("java21-old").exists());
+
+        let index = read_runtime_index(&runtimes_root).await.unwrap();
+        let mut remaining: Vec<_> = index
+            .runtimes
+            .iter()
+            .map(|rt| rt.identifier.as_str())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            ["java17-new", "java17-pinned", "java21-new", "java21-old"]
+        );
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    fn tar_gz(path: &Path, build: impl FnOnce(&mut tar::Builder<Vec<u8>>)) {
+        let mut builder = tar::Builder::new(Vec::new());
+        build(&mut builder);
+        let tar = builder.into_inner().unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        std::io::Write::write_all(&mut gz, &tar).unwrap();
+        std::fs::write(path, gz.finish().unwrap()).unwrap();
+    }
+
+    fn tar_file(builder: &mut tar::Builder<Vec<u8>>, path: &str, body: &[u8]) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, path, body).unwrap();
+    }
+
+    fn tar_symlink(builder: &mut tar::Builder<Vec<u8>>, path: &str, target: &str) {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, path, target).unwrap();
+    }
+
+    #[test]
+    fn tar_gz_runtimes_extract_without_their_top_level_folder() {
+        let dir = std::env::temp_dir().join(format!("runtime-tar-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let archive = dir.join("runtime.archive");
+        tar_gz(&archive, |builder| {
+            tar_file(builder, "jdk-21.0.4+7-jre/bin/java", b"java");
+            tar_file(builder, "jdk-21.0.4+7-jre/lib/libjli.so", b"jli");
+            tar_symlink(
+                builder,
+                "jdk-21.0.4+7-jre/bin/libjli.so",
+                "../lib/libjli.so",
+            );
+        });
+        let root = dir.join("extracted");
+        extract::extract_runtime_archive(&archive, &root).unwrap();
+        assert_eq!(std::fs::read(root.join("bin/java")).unwrap(), b"java");
+        assert_eq!(std::fs::read(root.join("bin/libjli.so")).unwrap(), b"jli");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(root.join("bin/java"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        // Links out of the runtime are refused.
+        tar_gz(&archive, |builder| {
+            tar_symlink(builder, "jdk/bin/escape", "../../../outside");
+        });
+        assert!(extract::extract_runtime_archive(&archive, &root).is_err());
+
+        // Zips still extract the same way.
+        let file = std::fs::File::create(&archive).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        zip.start_file("jdk-17/bin/java", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(&mut zip, b"zipped").unwrap();
+        zip.finish().unwrap();
+        extract::extract_runtime_archive(&archive, &root).unwrap();
+        assert_eq!(std::fs::read(root.join("bin/java")).unwrap(), b"zipped");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn integrity_scan_flags_a_corrupted_library() {
+        let root = std::env::temp_dir().join(format!("runtime-integrity-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let java_bin = root.join("bin").join(java_exe());
+        for (path, contents) in [
+            (java_bin.clone(), "java"),
+            (root.join("lib").join("modules"), "modules"),
+            (root.join("lib/server/libjvm.so"), "jvm"),
+            (root.join("legal/NOTICE"), "ignored"),
+        ] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        let manifest = integrity_manifest(&root, &java_bin).unwrap();
+        assert_eq!(
+            manifest.keys().map(String::as_str).collect::<Vec<_>>(),
+            [
+                format!("bin/{}", java_exe()).as_str(),
+                "lib/modules",
+                "lib/server/libjvm.so"
+            ]
+        );
+        assert!(integrity_drift(&root, &manifest).is_empty());
+
+        std::fs::write(root.join("lib/server/libjvm.so"), "corrupted").unwrap();
+        std::fs::write(root.join("legal/NOTICE"), "changed").unwrap();
+        assert_eq!(integrity_drift(&root, &manifest), ["lib/server/libjvm.so"]);
+
+        std::fs::remove_file(root.join("lib/modules")).unwrap();
+        assert_eq!(
+            integrity_drift(&root, &manifest),
+            ["lib/modules", "lib/server/libjvm.so"]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cache_status_reports_pending_backoff_and_cached_specs() {
+        let dir = std::env::temp_dir().join(format!("runtime-cache-status-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(
+            runtime_cache_status(&dir, 1_000),
+            RuntimeCacheStatus::default()
+        );
+
+        let spec = |stored_at| CachedRuntimeSpec {
+            stored_at,
+            spec: DownloadRuntimeSpec {
+                major: 21,
+                vendor: "Temurin".into(),
+                version: "21.0.4".into(),
+                arch: "x64".into(),
+                url: String::new(),
+                sha256: String::new(),
+            },
+        };
+        let cache = AdoptiumCache {
+            entries: HashMap::from([
+                ("21-x64".to_string(), spec(400)),
+                ("17-x64".to_string(), spec(900)),
+            ]),
+        };
+        std::fs::write(
+            dir.join(ADOPTIUM_CACHE_FILE),
+            serde_json::to_vec(&cache).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(GLOBAL_BACKOFF_429_FILE),
+            serde_json::to_vec(&Backoff429State { until_ts: 1_030 }).unwrap(),
+        )
+        .unwrap();
+
+        let status = runtime_cache_status(&dir, 1_000);
+        assert_eq!(status.backoff_until_ts, Some(1_030));
+        assert_eq!(status.cached_specs, 2);
+        assert_eq!(status.oldest_cached_spec_secs, Some(600));
+        // An expired backoff is not reported.
+        assert_eq!(runtime_cache_status(&dir, 2_000).backoff_until_ts, None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn clearing_caches_keeps_installed_runtimes() {
+        let dir = std::env::temp_dir().join(format!("runtime-cache-clear-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let runtimes_root = runtimes_root_for_role(&dir, RuntimeRole::Gamma);
+        std::fs::create_dir_all(runtimes_root.join("java21-temurin")).unwrap();
+        for path in [
+            dir.join(ADOPTIUM_CACHE_FILE),
+            dir.join(GLOBAL_BACKOFF_429_FILE),
+            resolved_cache_path(&dir, RuntimeRole::Gamma),
+        ] {
+            std::fs::write(path, "{}").unwrap();
+        }
+
+        let removed = clear_runtime_caches_in_dir(&dir).await.unwrap();
+        assert_eq!(removed.len(), 3);
+        assert!(!dir.join(ADOPTIUM_CACHE_FILE).exists());
+        assert!(!dir.join(GLOBAL_BACKOFF_429_FILE).exists());
+        assert!(!resolved_cache_path(&dir, RuntimeRole::Gamma).exists());
+        assert!(runtimes_root.join("java21-temurin").exists());
+
+        assert!(clear_runtime_caches_in_dir(&dir).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn vendor_release_urls_follow_each_vendor_naming() {
+        assert_eq!(
+            vendor_release_url(RuntimeVendor::Temurin, 21, "arm64", "mac", "jre").unwrap(),
+            format!("{ADOPTIUM_API_BASE}/21/hotspot?architecture=arm64&image_type=jre&os=mac")
+        );
+        let zulu = vendor_release_url(RuntimeVendor::Zulu, 17, "arm64", "mac", "jre").unwrap();
+        assert!(zulu.starts_with(ZULU_API_BASE));
+        assert!(zulu.contains("java_version=17&os=macos&arch=aarch64&archive_type=tar.gz"));
+        let zulu = vendor_release_url(RuntimeVendor::Zulu, 17, "x64", "windows", "jre").unwrap();
+        assert!(zulu.contains("os=windows&arch=x64&archive_type=zip"));
+        assert_eq!(
+            vendor_release_url(RuntimeVendor::Corretto, 21, "x64", "windows", "jdk").unwrap(),
+            "https://corretto.aws/downloads/latest/amazon-corretto-21-x64-windows-jdk.zip"
+        );
+        assert_eq!(
+            vendor_release_url(RuntimeVendor::GraalVm, 21, "x64", "linux", "jdk").unwrap(),
+            "https://download.oracle.com/graalvm/21/latest/graalvm-jdk-21_linux-x64_bin.tar.gz"
+        );
+        assert_eq!(
+            vendor_release_url(RuntimeVendor::Corretto, 17, "arm64", "mac", "jdk").unwrap(),
+            "https://corretto.aws/downloads/latest/amazon-corretto-17-aarch64-macos-jdk.tar.gz"
+        );
+        // GraalVM has neither JREs nor builds before Java 17.
+        assert!(vendor_release_url(RuntimeVendor::GraalVm, 21, "x64", "linux", "jre").is_none());
+        assert!(vendor_release_url(RuntimeVendor::GraalVm, 8, "x64", "linux", "jdk").is_none());
+    }
+
+    #[test]
+    fn other_vendors_fall_back_to_temurin() {
+        assert_eq!(
+            vendor_fallback_order(RuntimeVendor::GraalVm),
+            [RuntimeVendor::GraalVm, RuntimeVendor::Temurin]
+        );
+        assert_eq!(
+            vendor_fallback_order(RuntimeVendor::Temurin),
+            [RuntimeVendor::Temurin]
+        );
+        assert_eq!(
+            serde_json::to_string(&RuntimeVendor::GraalVm).unwrap(),
+            "\"graalvm\""
+        );
+    }
+
+    #[tokio::test]
+    async fn imported_runtimes_are_scanned_but_never_cleaned_up() {
+        let root = std::env::temp_dir().join(format!("runtime-import-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let data_dir = root.join("data");
+        let jdk = root.join("corp-jdk-17");
+        let java_bin = jdk.join("bin").join(java_exe());
+        std::fs::create_dir_all(java_bin.parent().unwrap()).unwrap();
+        std::fs::write(&java_bin, "java").unwrap();
+        let info = JavaInstallation {
+            path: java_bin.clone(),
+            version: "17.0.2".into(),
+            major: 17,
+            is_64bit: true,
+            vendor: "Corp".into(),
+            runtime_name: None,
+            java_vm_name: None,
+            has_shenandoah: false,
+            has_zgc: false,
+        };
+
+        let imported = register_external_runtime(&data_dir, &jdk, &java_bin, &info)
+            .await
+            .unwrap();
+        assert!(imported.external);
+        let runtimes_root = runtimes_root_for_role(&data_dir, RuntimeRole::Gamma);
+        let arch = platform::platform_arch();
+        let scanned = select::scan_runtime_candidates(&runtimes_root, &arch)
+            .await
+            .unwrap();
+        assert_eq!(scanned.len(), 1);
+        assert_eq!(scanned[0].root, jdk);
+        assert_eq!(scanned[0].java_bin, java_bin);
+
+        // Two newer managed runtimes of the same major would push it out.
+        for (identifier, installed_at) in [
+            ("java17-temurin-a", "2099-01-01T00:00:00Z"),
+            ("java17-temurin-b", "2099-02-01T00:00:00Z"),
+        ] {
+            let mut metadata = candidate(identifier, 17, "17.0.12").metadata;
+            metadata.arch = arch.clone();
+            metadata.installed_at = installed_at.into();
+            std::fs::create_dir_all(runtimes_root.join(identifier)).unwrap();
+            update_runtime_index(&runtimes_root, &metadata)
+                .await
+                .unwrap();
+        }
+        cleanup_old_runtimes(
+            &runtimes_root,
+            17,
+            &arch,
+            DEFAULT_RUNTIME_KEEP_PER_MAJOR,
+            &[],
+        )
+        .await
+        .unwrap();
+        let pruned = prune_runtimes_in_dir(&data_dir, 1, &[], &[]).await.unwrap();
+        assert_eq!(pruned, ["java17-temurin-a"]);
+
+        let index = read_runtime_index(&runtimes_root).await.unwrap();
+        assert!(index
+            .runtimes
+            .iter()
+            .any(|rt| rt.identifier == imported.identifier));
+        assert!(java_bin.exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn missing_pinned_runtime_resolves_to_none() {
+        let data_dir = std::env::temp_dir().join(format!("runtime-pin-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+
+        let pinned = pinned_runtime_in_dir(
+            &data_dir,
+            "java17-temurin-17.0.8-x64",
+            17,
+            RuntimeOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert!(pinned.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pinned_runtime_is_found_under_any_role() {
+        use crate::core::test_support::fake_java;
+
+        let data_dir =
+            std::env::temp_dir().join(format!("runtime-pin-role-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let identifier = "java17-temurin-17.0.8";
+        let runtime_root = runtimes_root_for_role(&data_dir, RuntimeRole::Delta).join(identifier);
+        let java_bin = runtime_root.join("bin").join("java");
+        fake_java(&java_bin, "17.0.8");
+        let mut metadata = candidate(identifier, 17, "17.0.8").metadata;
+        metadata.arch = platform::platform_arch();
+        metadata.role = RuntimeRole::Delta;
+        write_runtime_metadata(&runtime_root, &metadata)
+            .await
+            .unwrap();
+
+        let pinned = pinned_runtime_in_dir(&data_dir, identifier, 17, RuntimeOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(pinned, Some((RuntimeRole::Delta, java_bin)));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
 }
@@ -9,32 +9,10 @@ use tracing::{debug, warn};
 
 use crate::core::error::{LauncherError, LauncherResult};
 use crate::core::instance::{Instance, LoaderType};
+use crate::core::long_path::long_path;
 use crate::core::maven::MavenArtifact;
-
-fn parse_numeric_version_parts(raw: &str) -> Vec<u32> {
-    raw.split(|c: char| !c.is_ascii_digit())
-        .filter(|segment| !segment.is_empty())
-        .filter_map(|segment| segment.parse::<u32>().ok())
-        .collect()
-}
-
-fn compare_versions(a: &str, b: &str) -> Ordering {
-    let a_parts = parse_numeric_version_parts(a);
-    let b_parts = parse_numeric_version_parts(b);
-
-    let max_len = a_parts.len().max(b_parts.len());
-    for idx in 0..max_len {
-        let a_val = a_parts.get(idx).copied().unwrap_or(0);
-        let b_val = b_parts.get(idx).copied().unwrap_or(0);
-        match a_val.cmp(&b_val) {
-            Ordering::Equal => continue,
-            non_eq => return non_eq,
-        }
-    }
-
-    // Deterministic tiebreaker for versions with identical numeric parts.
-    a.cmp(b)
-}
+use crate::core::version::compare::compare_versions;
+use crate::core::version::{is_native_for_current_platform, NativeJar, VersionJson};
 
 /// Builds the full classpath string.
 pub fn build_classpath(
@@ -103,7 +81,7 @@ pub fn build_classpath(
 
     // Newest ASM first
     let mut asm: Vec<_> = best_asm_by_key.into_values().collect();
-    asm.sort_by(|(_, a), (_, b)| compare_versions(b, a));
+    asm.sort_by(|(_, a), (_, b)| compare_versions(b, a).then_with(|| b.cmp(a)));
 
     for (coord, _) in asm {
         if let Some(p) = resolve_library_entry(instance, libs_dir, &coord) {
@@ -142,7 +120,7 @@ pub fn build_classpath(
     }
 
     if entries.is_empty() {
-        return Err(LauncherError::Other("Classpath is empty".into()));
+        return Err(LauncherError::ClasspathEmpty);
     }
 
     dedup_preserving_order(&mut entries);
@@ -340,20 +318,126 @@ mod classpath_ordering_tests {
     }
 }
 
-/// Extract native libraries from JARs that contain `.dll`, `.so`, or `.dylib`.
+/// Natives jars for this launch: those declared by the version JSON (with
+/// their `extract.exclude` rules) plus any other `natives-*` library on the
+/// instance built for the current OS/arch.
+pub fn native_jars(version_json: Option<&VersionJson>, libraries: &[String]) -> Vec<NativeJar> {
+    let mut jars = version_json
+        .map(VersionJson::native_jars)
+        .unwrap_or_default();
+    for entry in libraries {
+        if !is_native_for_current_platform(entry) {
+            continue;
+        }
+        let path = match MavenArtifact::parse(entry) {
+            Ok(artifact) => artifact.local_path().to_string_lossy().replace('\\', "/"),
+            Err(_) => entry.replace('\\', "/"),
+        };
+        if jars.iter().all(|jar| jar.path != path) {
+            jars.push(NativeJar {
+                path,
+                exclude: vec!["META-INF/".to_string()],
+            });
+        }
+    }
+    jars
+}
+
+/// Whether an archive entry matches an `extract.exclude` pattern. Plain
+/// patterns are path prefixes (`META-INF/`); `*` matches any run of characters.
+fn is_excluded(name: &str, exclude: &[String]) -> bool {
+    fn wildcard(pattern: &str, text: &str) -> bool {
+        match pattern.split_once('*') {
+            None => pattern == text,
+            Some((head, tail)) => {
+                let Some(rest) = text.strip_prefix(head) else {
+                    return false;
+                };
+                (0..=rest.len())
+                    .filter(|&i| rest.is_char_boundary(i))
+                    .any(|i| wildcard(tail, &rest[i..]))
+            }
+        }
+    }
+
+    exclude.iter().any(|pattern| {
+        if pattern.contains('*') {
+            wildcard(pattern, name)
+        } else {
+            name.starts_with(pattern.as_str())
+        }
+    })
+}
+
+/// Unpack the `.dll`/`.so`/`.dylib`/`.jnilib` files of one natives jar into
+/// `dest_dir`, skipping excluded paths. Returns how many files were written.
+fn extract_native_jar(jar: &Path, exclude: &[String], dest_dir: &Path) -> LauncherResult<usize> {
+    let file = std::fs::File::open(long_path(jar)).map_err(|e| LauncherError::Io {
+        path: jar.to_path_buf(),
+        source: e,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut extracted = 0;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        if is_excluded(&name, exclude) {
+            continue;
+        }
+
+        let is_native = name.ends_with(".dll")
+            || name.ends_with(".so")
+            || name.ends_with(".dylib")
+            || name.ends_with(".jnilib");
+        if !is_native {
+            continue;
+        }
+        let Some(relative) = file.enclosed_name() else {
+            continue;
+        };
+
+        // Applied per file: entry names may use `/`, which extended-length
+        // paths do not normalize.
+        let dest = long_path(&dest_dir.join(relative));
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+        let mut out = std::fs::File::create(&dest).map_err(|e| LauncherError::Io {
+            path: dest.clone(),
+            source: e,
+        })?;
+        std::io::copy(&mut file, &mut out).map_err(|e| LauncherError::Io {
+            path: dest.clone(),
+            source: e,
+        })?;
+        debug!("Extracted native: {}", name);
+        extracted += 1;
+    }
+
+    Ok(extracted)
+}
+
+/// Extract native libraries from the given natives jars.
 ///
-/// Creates a temporary `natives/` directory inside the instance.
+/// Every launch gets its own `natives/<session>/` directory inside the
+/// instance, so launching again while a game is still running (or exiting)
+/// never rewrites or deletes libraries that process has loaded. Returns the
+/// session directory; pass it to [`cleanup_natives`] once that game exits.
 pub async fn extract_natives(
     instance: &Instance,
     libs_dir: &Path,
-    native_coords: &[String],
+    native_jars: &[NativeJar],
 ) -> LauncherResult<PathBuf> {
-    let natives_dir = instance.natives_dir();
-
-    // Clean previous session
-    if natives_dir.exists() {
-        let _ = tokio::fs::remove_dir_all(&natives_dir).await;
-    }
+    let natives_dir = instance
+        .natives_dir()
+        .join(uuid::Uuid::new_v4().simple().to_string());
     tokio::fs::create_dir_all(&natives_dir)
         .await
         .map_err(|e| LauncherError::Io {
@@ -361,85 +445,52 @@ pub async fn extract_natives(
             source: e,
         })?;
 
-    for coord in native_coords {
-        let artifact = match MavenArtifact::parse(coord) {
-            Ok(a) => a,
-            Err(_) => continue,
-        };
-        let jar_path = libs_dir.join(artifact.local_path());
+    for native in native_jars {
+        let jar_path = libs_dir.join(&native.path);
         let effective_path = if jar_path.exists() {
             jar_path
         } else {
-            let local_path = instance
-                .game_dir()
-                .join("libraries")
-                .join(artifact.local_path());
+            let local_path = instance.game_dir().join("libraries").join(&native.path);
             if !local_path.exists() {
                 continue;
             }
             local_path
         };
 
-        // Extract .dll/.so/.dylib from the JAR
-        let jar_bytes = tokio::fs::read(&effective_path)
-            .await
-            .map_err(|e| LauncherError::Io {
-                path: effective_path.clone(),
-                source: e,
-            })?;
-
         let dest_dir = natives_dir.clone();
-        let path_debug = effective_path.clone();
-        tokio::task::spawn_blocking(move || {
-            let cursor = std::io::Cursor::new(jar_bytes);
-            let mut archive = match zip::ZipArchive::new(cursor) {
-                Ok(a) => a,
-                Err(e) => {
-                    warn!("Cannot open native JAR {:?}: {}", path_debug, e);
-                    return;
-                }
-            };
-
-            for i in 0..archive.len() {
-                let file = archive.by_index(i);
-                if file.is_err() {
-                    continue;
-                }
-                let mut file = file.unwrap();
-                let name = file.name().to_string();
-
-                if name.contains("META-INF") || name.contains('/') || name.contains('\\') {
-                    continue;
-                }
-
-                let is_native = name.ends_with(".dll")
-                    || name.ends_with(".so")
-                    || name.ends_with(".dylib")
-                    || name.ends_with(".jnilib");
-
-                if is_native {
-                    let dest = dest_dir.join(&name);
-                    let mut out = match std::fs::File::create(&dest) {
-                        Ok(file) => file,
-                        Err(_) => continue,
-                    };
-                    let _ = std::io::copy(&mut file, &mut out);
-                    debug!("Extracted native: {}", name);
-                }
-            }
+        let exclude = native.exclude.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            extract_native_jar(&effective_path, &exclude, &dest_dir)
+                .map_err(|e| (effective_path, e))
         })
         .await
         .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))?;
+        if let Err((path, e)) = result {
+            warn!("Cannot extract natives from {:?}: {}", path, e);
+        }
     }
 
     Ok(natives_dir)
 }
 
-/// Clean up the temporary natives directory after the game exits.
-pub async fn cleanup_natives(instance: &Instance) {
+/// Remove the natives session directory of a game that exited, and the
+/// `natives/` folder itself once no other session uses it.
+pub async fn cleanup_natives(natives_dir: &Path) {
+    if natives_dir.exists() {
+        let _ = tokio::fs::remove_dir_all(long_path(natives_dir)).await;
+    }
+    if let Some(parent) = natives_dir.parent() {
+        // Fails while other sessions are still there, which is what we want.
+        let _ = tokio::fs::remove_dir(parent).await;
+    }
+}
+
+/// Remove every natives session of an instance that is not running, e.g.
+/// leftovers of a launcher that crashed while the game was open.
+pub async fn clear_stale_natives(instance: &Instance) {
     let natives_dir = instance.natives_dir();
     if natives_dir.exists() {
-        let _ = tokio::fs::remove_dir_all(&natives_dir).await;
+        let _ = tokio::fs::remove_dir_all(long_path(&natives_dir)).await;
     }
 }
 
@@ -868,11 +919,13 @@ mod tests {
         std::fs::create_dir_all(&mods_dir).unwrap();
         std::fs::write(mods_dir.join("example-mod.jar"), b"mod").unwrap();
         std::fs::write(mods_dir.join("README.txt"), b"not-a-mod").unwrap();
+        std::fs::write(mods_dir.join("old-mod.jar.disabled"), b"mod").unwrap();
 
         let classpath = build_classpath(&instance, &temp.join("libraries"), &[]).unwrap();
 
         assert!(classpath.contains("example-mod.jar"));
         assert!(!classpath.contains("README.txt"));
+        assert!(!classpath.contains("old-mod.jar"));
 
         let _ = std::fs::remove_dir_all(&temp);
     }
@@ -1013,4 +1066,148 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&temp);
     }
+
+    const NATIVES_JAR: &str = "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar";
+
+    fn write_natives_jar(libs_dir: &Path) -> [NativeJar; 1] {
+        use std::io::Write;
+
+        std::fs::create_dir_all(libs_dir.join(NATIVES_JAR).parent().unwrap()).unwrap();
+        let file = std::fs::File::create(libs_dir.join(NATIVES_JAR)).unwrap();
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("META-INF/liblwjgl.so", options).unwrap();
+        writer.write_all(b"signed").unwrap();
+        writer.start_file("META-INF/MANIFEST.MF", options).unwrap();
+        writer.write_all(b"Manifest-Version: 1.0").unwrap();
+        writer.start_file("liblwjgl.so", options).unwrap();
+        writer.write_all(b"native").unwrap();
+        writer.finish().unwrap();
+
+        [NativeJar {
+            path: NATIVES_JAR.into(),
+            exclude: vec!["META-INF/".into()],
+        }]
+    }
+
+    #[tokio::test]
+    async fn extract_natives_skips_excluded_paths() {
+        let temp =
+            std::env::temp_dir().join(format!("classpath-test-natives-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let instance = test_instance(&temp);
+        let libs_dir = temp.join("libraries");
+        let natives = write_natives_jar(&libs_dir);
+        let natives_dir = extract_natives(&instance, &libs_dir, &natives)
+            .await
+            .unwrap();
+
+        let mut extracted: Vec<PathBuf> = list_files(&natives_dir);
+        extracted.sort();
+        assert_eq!(extracted, [natives_dir.join("liblwjgl.so")]);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn concurrent_launches_get_their_own_natives_dir() {
+        let temp = std::env::temp_dir().join(format!(
+            "classpath-test-natives-sessions-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&temp);
+        let instance = test_instance(&temp);
+        let libs_dir = temp.join("libraries");
+        let natives = write_natives_jar(&libs_dir);
+
+        let (first, second) = tokio::join!(
+            extract_natives(&instance, &libs_dir, &natives),
+            extract_natives(&instance, &libs_dir, &natives),
+        );
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(instance.natives_dir().as_path()));
+        assert_eq!(second.parent(), Some(instance.natives_dir().as_path()));
+
+        // The first game exiting leaves the second one's natives alone.
+        cleanup_natives(&first).await;
+        assert!(!first.exists());
+        assert_eq!(
+            std::fs::read(second.join("liblwjgl.so")).unwrap(),
+            b"native"
+        );
+
+        cleanup_natives(&second).await;
+        assert!(!instance.natives_dir().exists());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    fn list_files(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .flat_map(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    list_files(&path)
+                } else {
+                    vec![path]
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn native_jars_use_current_platform_classifier() {
+        let version: VersionJson = serde_json::from_value(serde_json::json!({
+            "id": "1.12.2",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [{
+                "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+                "natives": { "linux": "natives-linux", "osx": "natives-osx", "windows": "natives-windows" },
+                "extract": { "exclude": ["META-INF/"] },
+                "downloads": { "classifiers": {
+                    "natives-linux": { "path": "lwjgl-platform-natives-linux.jar", "sha1": "0", "size": 0, "url": "" },
+                    "natives-osx": { "path": "lwjgl-platform-natives-osx.jar", "sha1": "0", "size": 0, "url": "" },
+                    "natives-windows": { "path": "lwjgl-platform-natives-windows.jar", "sha1": "0", "size": 0, "url": "" }
+                } }
+            }]
+        }))
+        .unwrap();
+
+        let jars = native_jars(
+            Some(&version),
+            &["org.lwjgl:lwjgl:3.3.3:natives-windows-arm64".to_string()],
+        );
+
+        let os = if cfg!(target_os = "windows") {
+            "windows"
+        } else if cfg!(target_os = "macos") {
+            "osx"
+        } else {
+            "linux"
+        };
+        let expected = NativeJar {
+            path: format!("lwjgl-platform-natives-{os}.jar"),
+            exclude: vec!["META-INF/".into()],
+        };
+        assert_eq!(jars[0], expected);
+        assert_eq!(
+            jars.len(),
+            if cfg!(all(target_os = "windows", target_arch = "aarch64")) {
+                2
+            } else {
+                1
+            }
+        );
+    }
+
+    #[test]
+    fn exclude_patterns_match_prefixes_and_wildcards() {
+        let exclude = ["META-INF/".to_string(), "*.sha1".to_string()];
+        assert!(is_excluded("META-INF/MANIFEST.MF", &exclude));
+        assert!(is_excluded("linux/x64/liblwjgl.so.sha1", &exclude));
+        assert!(!is_excluded("liblwjgl.so", &exclude));
+    }
 }
@@ -0,0 +1,174 @@
+// ─── Crash Reports ───
+// Locates and summarizes `crash-reports/crash-*.txt` after an abnormal exit,
+// falling back to the tail of the game log when Minecraft wrote none
+// (e.g. the JVM was killed by OutOfMemory).
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+/// Lines of log returned when no crash report exists.
+pub const LOG_TAIL_LINES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashSource {
+    CrashReport,
+    LogTail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportSummary {
+    pub source: CrashSource,
+    pub path: String,
+    pub description: Option<String>,
+    pub exception: Option<String>,
+    pub raw: String,
+}
+
+/// Extract the `Description:` line and the exception class that follows it.
+pub fn parse_crash_report(text: &str) -> (Option<String>, Option<String>) {
+    let mut lines = text.lines().map(str::trim);
+    let description = lines
+        .by_ref()
+        .find_map(|line| line.strip_prefix("Description:"))
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    // The stack trace starts with the first non-empty line after the description.
+    let exception = description
+        .as_ref()
+        .and_then(|_| lines.find(|line| !line.is_empty()))
+        .map(|line| {
+            line.split_once(':')
+                .map_or(line, |(class, _)| class)
+                .trim()
+                .to_string()
+        })
+        .filter(|class| !class.is_empty() && !class.contains(' '));
+
+    (description, exception)
+}
+
+/// Newest `crash-*.txt` in `game_dir/crash-reports`, optionally only those
+/// written at or after `since`.
+pub fn find_latest_crash_report(game_dir: &Path, since: Option<SystemTime>) -> Option<PathBuf> {
+    std::fs::read_dir(game_dir.join("crash-reports"))
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("crash-") && name.ends_with(".txt")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .filter(|(modified, _)| since.is_none_or(|since| *modified >= since))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn tail_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// Summarize the crash of an instance: the newest crash report if there is
+/// one, otherwise the tail of the first existing log in `fallback_logs`.
+pub fn summarize_crash(
+    game_dir: &Path,
+    fallback_logs: &[PathBuf],
+    since: Option<SystemTime>,
+) -> Option<CrashReportSummary> {
+    if let Some(path) = find_latest_crash_report(game_dir, since) {
+        if let Ok(raw) = std::fs::read_to_string(&path) {
+            let (description, exception) = parse_crash_report(&raw);
+            return Some(CrashReportSummary {
+                source: CrashSource::CrashReport,
+                path: path.to_string_lossy().to_string(),
+                description,
+                exception,
+                raw,
+            });
+        }
+    }
+
+    fallback_logs.iter().find_map(|log| {
+        let text = std::fs::read_to_string(log).ok()?;
+        Some(CrashReportSummary {
+            source: CrashSource::LogTail,
+            path: log.to_string_lossy().to_string(),
+            description: None,
+            exception: None,
+            raw: tail_lines(&text, LOG_TAIL_LINES),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "---- Minecraft Crash Report ----
+// Who set us up the TNT?
+
+Time: 2024-03-02 18:41:07
+Description: Rendering overlay
+
+java.lang.NullPointerException: Cannot invoke \"net.minecraft.class_1.method_2()\" because \"this.field\" is null
+\tat net.minecraft.class_3.render(class_3.java:42)
+\tat net.minecraft.client.MinecraftClient.render(MinecraftClient.java:1200)
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+";
+
+    #[test]
+    fn parses_description_and_exception_class() {
+        let (description, exception) = parse_crash_report(SAMPLE);
+        assert_eq!(description.as_deref(), Some("Rendering overlay"));
+        assert_eq!(exception.as_deref(), Some("java.lang.NullPointerException"));
+    }
+
+    #[test]
+    fn parse_handles_reports_without_description() {
+        let (description, exception) = parse_crash_report("garbage\nmore garbage");
+        assert!(description.is_none());
+        assert!(exception.is_none());
+    }
+
+    #[test]
+    fn summarize_prefers_crash_report_then_log_tail() {
+        let temp = std::env::temp_dir().join(format!("crash-report-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(temp.join("logs")).unwrap();
+
+        let log = temp.join("logs").join("latest.log");
+        let log_text: String = (0..80).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(&log, log_text).unwrap();
+
+        let fallback = summarize_crash(&temp, std::slice::from_ref(&log), None).unwrap();
+        assert_eq!(fallback.source, CrashSource::LogTail);
+        assert_eq!(fallback.raw.lines().count(), LOG_TAIL_LINES);
+        assert!(fallback.raw.ends_with("line 79"));
+
+        std::fs::create_dir_all(temp.join("crash-reports")).unwrap();
+        std::fs::write(
+            temp.join("crash-reports")
+                .join("crash-2024-03-02_18.41.07-client.txt"),
+            SAMPLE,
+        )
+        .unwrap();
+
+        let report = summarize_crash(&temp, &[log], None).unwrap();
+        assert_eq!(report.source, CrashSource::CrashReport);
+        assert_eq!(report.description.as_deref(), Some("Rendering overlay"));
+
+        let future = SystemTime::now() + std::time::Duration::from_secs(3600);
+        assert!(find_latest_crash_report(&temp, Some(future)).is_none());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
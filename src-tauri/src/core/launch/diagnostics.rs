@@ -0,0 +1,284 @@
+// ─── Launch Diagnostics ───
+// Recognizes known failure signatures in the game output. Each match is
+// reported to the frontend as an `instance-diagnostic` event carrying the
+// variant, a severity and a stable i18n key so the UI can localize it and
+// link to docs; the hint is still written to the launch log.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::core::i18n::Locale;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum LaunchDiagnostic {
+    NeoForgeEarlyDisplayRendererFuture,
+    NeoForgeEarlyDisplayStillEnabled,
+    CorruptedLibraryArchive,
+    LoaderAsmTooOldForJava21,
+    UrlFactoryAlreadyDefined,
+    /// `libGL` or GLFW could not be loaded (Linux without GL drivers).
+    MissingOpenGlLibraries,
+    /// Legacy LWJGL found no hardware-accelerated pixel format.
+    PixelFormatNotAccelerated,
+    /// The JVM could not reserve the requested `-Xmx`.
+    HeapReservationFailed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// Payload of the `instance-diagnostic` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceDiagnosticEvent {
+    pub id: String,
+    pub diagnostic: LaunchDiagnostic,
+    pub severity: DiagnosticSeverity,
+    pub i18n_key: &'static str,
+}
+
+pub fn detect_launch_diagnostic(line: &str) -> Option<LaunchDiagnostic> {
+    if line.contains("rendererFuture") || line.contains("DisplayWindow.takeOverGlfwWindow") {
+        return Some(LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture);
+    }
+
+    if line.contains("Loading ImmediateWindowProvider fmlearlywindow") {
+        return Some(LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled);
+    }
+
+    if line.contains("ZipException: zip END header not found") {
+        return Some(LaunchDiagnostic::CorruptedLibraryArchive);
+    }
+
+    if line.contains("Unsupported class file major version 65")
+        || line.contains("org.objectweb.asm.ClassReader")
+    {
+        return Some(LaunchDiagnostic::LoaderAsmTooOldForJava21);
+    }
+
+    if line.contains("factory already defined")
+        || line.contains("URL.setURLStreamHandlerFactory")
+        || line.contains("cpw.mods.cl.ModuleClassLoader")
+    {
+        return Some(LaunchDiagnostic::UrlFactoryAlreadyDefined);
+    }
+
+    if (line.contains("libGL") && line.contains("cannot open shared object file"))
+        || line.contains("Failed to locate library: libglfw")
+        || line.contains("GLFW error 65542")
+    {
+        return Some(LaunchDiagnostic::MissingOpenGlLibraries);
+    }
+
+    if line.contains("Pixel format not accelerated") {
+        return Some(LaunchDiagnostic::PixelFormatNotAccelerated);
+    }
+
+    if line.contains("Could not reserve enough space for object heap") {
+        return Some(LaunchDiagnostic::HeapReservationFailed);
+    }
+
+    None
+}
+
+impl LaunchDiagnostic {
+    pub fn i18n_key(self) -> &'static str {
+        match self {
+            LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture => {
+                "diagnostic.neoforge_early_display_renderer_future"
+            }
+            LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled => {
+                "diagnostic.neoforge_early_display_still_enabled"
+            }
+            LaunchDiagnostic::CorruptedLibraryArchive => "diagnostic.corrupted_library_archive",
+            LaunchDiagnostic::LoaderAsmTooOldForJava21 => {
+                "diagnostic.loader_asm_too_old_for_java21"
+            }
+            LaunchDiagnostic::UrlFactoryAlreadyDefined => "diagnostic.url_factory_already_defined",
+            LaunchDiagnostic::MissingOpenGlLibraries => "diagnostic.missing_opengl_libraries",
+            LaunchDiagnostic::PixelFormatNotAccelerated => {
+                "diagnostic.pixel_format_not_accelerated"
+            }
+            LaunchDiagnostic::HeapReservationFailed => "diagnostic.heap_reservation_failed",
+        }
+    }
+
+    pub fn severity(self) -> DiagnosticSeverity {
+        match self {
+            LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled => DiagnosticSeverity::Warning,
+            _ => DiagnosticSeverity::Error,
+        }
+    }
+
+    /// Diagnostics sharing a kind are reported once per launch.
+    fn kind(self) -> LaunchDiagnostic {
+        match self {
+            LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled => {
+                LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture
+            }
+            other => other,
+        }
+    }
+
+    /// Hint written to the launch log.
+    pub fn message(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Es => self.message_es(),
+            Locale::En => self.message_en(),
+        }
+    }
+
+    fn message_es(self) -> &'static str {
+        match self {
+            LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture => {
+                "[DIAGNÓSTICO] NeoForge falló en early display (rendererFuture nulo). Usa JVM args (antes de -cp): -Dfml.earlyprogresswindow=false. Si el log muestra 'Loading ImmediateWindowProvider fmlearlywindow', el flag no está entrando."
+            }
+            LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled => {
+                "[DIAGNÓSTICO] El early window sigue activo ('Loading ImmediateWindowProvider fmlearlywindow'). Revisa que el JVM arg sea exactamente -Dfml.earlyprogresswindow=false y que se inyecte antes de -cp."
+            }
+            LaunchDiagnostic::CorruptedLibraryArchive => {
+                "[DIAGNÓSTICO] Se detectó una librería dañada (zip END header not found). Cierra la instancia, borra la ruta `libraries/net/neoforged/neoform/...` indicada en el log y reinicia para forzar una descarga limpia."
+            }
+            LaunchDiagnostic::LoaderAsmTooOldForJava21 => {
+                "[DIAGNÓSTICO] El loader usa ASM antiguo y no soporta bytecode Java 21 (major 65). Actualiza Forge/NeoForge de esta línea de Minecraft a una build más reciente (ASM 9.7+)."
+            }
+            LaunchDiagnostic::UrlFactoryAlreadyDefined => {
+                "[DIAGNÓSTICO] Bootstrap abortó con 'factory already defined'. Normalmente indica classpath contaminado con jars de installer tooling (binarypatcher/jarsplitter/AutoRenamingTool). Se filtraron automáticamente para NeoForge/Forge; reinicia la instancia para reconstruir launch args limpios."
+            }
+            LaunchDiagnostic::MissingOpenGlLibraries => {
+                "[DIAGNÓSTICO] No se pudo cargar libGL/GLFW. Instala los drivers de video y los paquetes de OpenGL de tu distribución (por ejemplo libgl1 y libglfw3) y vuelve a iniciar."
+            }
+            LaunchDiagnostic::PixelFormatNotAccelerated => {
+                "[DIAGNÓSTICO] 'Pixel format not accelerated': la tarjeta gráfica no ofrece aceleración OpenGL. Actualiza los drivers de video o fuerza el uso de la GPU dedicada."
+            }
+            LaunchDiagnostic::HeapReservationFailed => {
+                "[DIAGNÓSTICO] Java no pudo reservar la memoria pedida. Baja la memoria máxima de la instancia o usa una Java de 64 bits."
+            }
+        }
+    }
+
+    fn message_en(self) -> &'static str {
+        match self {
+            LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture => {
+                "[DIAGNOSTIC] NeoForge failed in the early display (null rendererFuture). Add the JVM arg -Dfml.earlyprogresswindow=false (before -cp). If the log shows 'Loading ImmediateWindowProvider fmlearlywindow', the flag is not being applied."
+            }
+            LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled => {
+                "[DIAGNOSTIC] The early window is still enabled ('Loading ImmediateWindowProvider fmlearlywindow'). Check that the JVM arg is exactly -Dfml.earlyprogresswindow=false and that it is injected before -cp."
+            }
+            LaunchDiagnostic::CorruptedLibraryArchive => {
+                "[DIAGNOSTIC] A damaged library was found (zip END header not found). Close the instance, delete the `libraries/net/neoforged/neoform/...` path shown in the log and restart to force a clean download."
+            }
+            LaunchDiagnostic::LoaderAsmTooOldForJava21 => {
+                "[DIAGNOSTIC] The loader ships an old ASM that cannot read Java 21 bytecode (major 65). Update Forge/NeoForge for this Minecraft version to a newer build (ASM 9.7+)."
+            }
+            LaunchDiagnostic::UrlFactoryAlreadyDefined => {
+                "[DIAGNOSTIC] Bootstrap aborted with 'factory already defined'. This usually means installer tooling jars (binarypatcher/jarsplitter/AutoRenamingTool) leaked into the classpath. They are filtered automatically for NeoForge/Forge; restart the instance to rebuild clean launch args."
+            }
+            LaunchDiagnostic::MissingOpenGlLibraries => {
+                "[DIAGNOSTIC] libGL/GLFW could not be loaded. Install the video drivers and your distribution's OpenGL packages (for example libgl1 and libglfw3) and launch again."
+            }
+            LaunchDiagnostic::PixelFormatNotAccelerated => {
+                "[DIAGNOSTIC] 'Pixel format not accelerated': the graphics card offers no OpenGL acceleration. Update the video drivers or force the dedicated GPU."
+            }
+            LaunchDiagnostic::HeapReservationFailed => {
+                "[DIAGNOSTIC] Java could not reserve the requested memory. Lower the instance's maximum memory or use a 64-bit Java."
+            }
+        }
+    }
+}
+
+/// Remembers which diagnostic kinds were already reported for a launch.
+#[derive(Debug, Default)]
+pub struct DiagnosticTracker {
+    reported: HashSet<LaunchDiagnostic>,
+}
+
+impl DiagnosticTracker {
+    /// Returns the diagnostic in `line` unless its kind was already reported.
+    pub fn observe(&mut self, line: &str) -> Option<LaunchDiagnostic> {
+        let diagnostic = detect_launch_diagnostic(line)?;
+        self.reported
+            .insert(diagnostic.kind())
+            .then_some(diagnostic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_lines_map_to_variant_and_key() {
+        let cases = [
+            (
+                "java.lang.NullPointerException: Cannot invoke \"rendererFuture.get()\"",
+                LaunchDiagnostic::NeoForgeEarlyDisplayRendererFuture,
+                "diagnostic.neoforge_early_display_renderer_future",
+            ),
+            (
+                "java.util.zip.ZipException: zip END header not found",
+                LaunchDiagnostic::CorruptedLibraryArchive,
+                "diagnostic.corrupted_library_archive",
+            ),
+            (
+                "Exception in thread \"main\" java.lang.UnsatisfiedLinkError: /tmp/lwjgl/liblwjgl_opengl.so: libGL.so.1: cannot open shared object file: No such file or directory",
+                LaunchDiagnostic::MissingOpenGlLibraries,
+                "diagnostic.missing_opengl_libraries",
+            ),
+            (
+                "[LWJGL] Failed to locate library: libglfw.so",
+                LaunchDiagnostic::MissingOpenGlLibraries,
+                "diagnostic.missing_opengl_libraries",
+            ),
+            (
+                "org.lwjgl.LWJGLException: Pixel format not accelerated",
+                LaunchDiagnostic::PixelFormatNotAccelerated,
+                "diagnostic.pixel_format_not_accelerated",
+            ),
+            (
+                "Error occurred during initialization of VM\nCould not reserve enough space for object heap",
+                LaunchDiagnostic::HeapReservationFailed,
+                "diagnostic.heap_reservation_failed",
+            ),
+        ];
+
+        for (line, variant, key) in cases {
+            let detected = detect_launch_diagnostic(line);
+            assert_eq!(detected, Some(variant), "{line}");
+            assert_eq!(variant.i18n_key(), key);
+        }
+        assert_eq!(
+            detect_launch_diagnostic("[Render thread/INFO]: Setting user"),
+            None
+        );
+
+        let event = serde_json::to_value(InstanceDiagnosticEvent {
+            id: "abc".into(),
+            diagnostic: LaunchDiagnostic::HeapReservationFailed,
+            severity: LaunchDiagnostic::HeapReservationFailed.severity(),
+            i18n_key: LaunchDiagnostic::HeapReservationFailed.i18n_key(),
+        })
+        .unwrap();
+        assert_eq!(event["diagnostic"], "HeapReservationFailed");
+        assert_eq!(event["severity"], "error");
+    }
+
+    #[test]
+    fn each_kind_is_reported_once() {
+        let mut tracker = DiagnosticTracker::default();
+        assert_eq!(
+            tracker.observe("Loading ImmediateWindowProvider fmlearlywindow"),
+            Some(LaunchDiagnostic::NeoForgeEarlyDisplayStillEnabled)
+        );
+        assert_eq!(tracker.observe("at DisplayWindow.takeOverGlfwWindow"), None);
+        assert_eq!(
+            tracker.observe("Pixel format not accelerated"),
+            Some(LaunchDiagnostic::PixelFormatNotAccelerated)
+        );
+        assert_eq!(tracker.observe("Pixel format not accelerated"), None);
+    }
+}
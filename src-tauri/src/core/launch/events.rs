@@ -0,0 +1,286 @@
+// ─── Launch / Create Events ───
+// Progress and log events of the instance creation and launch pipelines,
+// with an i18n key the frontend can translate.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::{Locale, LogKey, MessageKey};
+use crate::core::launch;
+
+/// `stage` is the localized text; `stage_key` lets the frontend translate it
+/// and is `None` for dynamic labels such as loader processor names.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InstanceLaunchProgressEvent {
+    id: String,
+    value: u8,
+    stage: String,
+    stage_key: Option<MessageKey>,
+    state: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InstanceCrashEvent {
+    pub(crate) id: String,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) summary: launch::CrashReportSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InstanceLaunchLogEvent {
+    id: String,
+    level: String,
+    message: String,
+    /// Catalog key of `message` and its arguments; `None` for raw output
+    /// such as game and hook lines.
+    message_key: Option<LogKey>,
+    args: Vec<String>,
+    /// i18n key of the error or diagnostic the line reports.
+    key: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InstanceCreationProgressEvent {
+    id: String,
+    value: u8,
+    stage: String,
+    stage_key: Option<MessageKey>,
+    state: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InstanceCreationLogEvent {
+    id: String,
+    level: String,
+    message: String,
+    /// Catalog key of `message` and its arguments; `None` for raw
+    /// installer output.
+    message_key: Option<LogKey>,
+    args: Vec<String>,
+    /// i18n key of the error or diagnostic the line reports.
+    key: Option<&'static str>,
+}
+
+pub(crate) fn emit_launch_progress(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    value: u8,
+    stage: MessageKey,
+    state: &str,
+) {
+    events.emit(
+        "instance-launch-progress",
+        InstanceLaunchProgressEvent {
+            id: id.to_string(),
+            value,
+            stage: stage.text(locale).to_string(),
+            stage_key: Some(stage),
+            state: state.to_string(),
+        },
+    );
+}
+
+/// Raw output, such as game and hook lines, written as it is.
+pub(crate) fn emit_launch_log(events: &dyn EventEmitter, id: &str, level: &str, message: String) {
+    emit_launch_log_keyed(events, id, level, None, message);
+}
+
+/// Like `emit_launch_log`, tagging the line with the i18n key of the error or
+/// diagnostic it reports.
+pub(crate) fn emit_launch_log_keyed(
+    events: &dyn EventEmitter,
+    id: &str,
+    level: &str,
+    key: Option<&'static str>,
+    message: String,
+) {
+    events.emit(
+        "instance-launch-log",
+        InstanceLaunchLogEvent {
+            id: id.to_string(),
+            level: level.to_string(),
+            message,
+            message_key: None,
+            args: Vec::new(),
+            key,
+        },
+    );
+}
+
+/// A catalog line, written in `locale` with `args` in its placeholders.
+pub(crate) fn emit_launch_message(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    level: &str,
+    message: LogKey,
+    args: &[&dyn Display],
+) {
+    emit_launch_line(events, locale, id, level, message, args, None);
+}
+
+/// A catalog line whose only argument is `err`, tagged with its i18n key.
+pub(crate) fn emit_launch_error(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    message: LogKey,
+    err: &LauncherError,
+) {
+    emit_launch_line(
+        events,
+        locale,
+        id,
+        "error",
+        message,
+        &[err],
+        Some(err.i18n_key()),
+    );
+}
+
+fn emit_launch_line(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    level: &str,
+    message: LogKey,
+    args: &[&dyn Display],
+    key: Option<&'static str>,
+) {
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    events.emit(
+        "instance-launch-log",
+        InstanceLaunchLogEvent {
+            id: id.to_string(),
+            level: level.to_string(),
+            message: message.format(locale, &args),
+            message_key: Some(message),
+            args,
+            key,
+        },
+    );
+}
+
+pub(crate) fn emit_create_progress(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    value: u8,
+    stage: MessageKey,
+    state: &str,
+) {
+    events.emit(
+        "instance-create-progress",
+        InstanceCreationProgressEvent {
+            id: id.to_string(),
+            value,
+            stage: stage.text(locale).to_string(),
+            stage_key: Some(stage),
+            state: state.to_string(),
+        },
+    );
+}
+
+pub(crate) fn emit_create_progress_label(
+    events: &dyn EventEmitter,
+    id: &str,
+    value: u8,
+    label: &str,
+    state: &str,
+) {
+    events.emit(
+        "instance-create-progress",
+        InstanceCreationProgressEvent {
+            id: id.to_string(),
+            value,
+            stage: label.to_string(),
+            stage_key: None,
+            state: state.to_string(),
+        },
+    );
+}
+
+/// Raw output, such as installer processor lines, written as it is.
+pub(crate) fn emit_create_log(events: &dyn EventEmitter, id: &str, level: &str, message: String) {
+    emit_create_log_keyed(events, id, level, None, message);
+}
+
+/// Like `emit_create_log`, tagging the line with the i18n key of the error or
+/// diagnostic it reports.
+pub(crate) fn emit_create_log_keyed(
+    events: &dyn EventEmitter,
+    id: &str,
+    level: &str,
+    key: Option<&'static str>,
+    message: String,
+) {
+    events.emit(
+        "instance-create-log",
+        InstanceCreationLogEvent {
+            id: id.to_string(),
+            level: level.to_string(),
+            message,
+            message_key: None,
+            args: Vec::new(),
+            key,
+        },
+    );
+}
+
+/// A catalog line, written in `locale` with `args` in its placeholders.
+pub(crate) fn emit_create_message(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    level: &str,
+    message: LogKey,
+    args: &[&dyn Display],
+) {
+    emit_create_line(events, locale, id, level, message, args, None);
+}
+
+/// A catalog line whose only argument is `err`, tagged with its i18n key.
+pub(crate) fn emit_create_error(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    message: LogKey,
+    err: &LauncherError,
+) {
+    emit_create_line(
+        events,
+        locale,
+        id,
+        "error",
+        message,
+        &[err],
+        Some(err.i18n_key()),
+    );
+}
+
+fn emit_create_line(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    id: &str,
+    level: &str,
+    message: LogKey,
+    args: &[&dyn Display],
+    key: Option<&'static str>,
+) {
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    events.emit(
+        "instance-create-log",
+        InstanceCreationLogEvent {
+            id: id.to_string(),
+            level: level.to_string(),
+            message: message.format(locale, &args),
+            message_key: Some(message),
+            args,
+            key,
+        },
+    );
+}
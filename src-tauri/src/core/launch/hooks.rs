@@ -0,0 +1,318 @@
+// ─── Instance Hooks ───
+// Runs the user's pre-launch / post-exit shell commands with the game
+// directory as working directory, streaming their output line by line.
+// A hook that outlives its timeout or whose launch is cancelled is killed.
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::Instance;
+
+/// Default limit for a single hook run.
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 300;
+/// Upper bound accepted from settings.
+pub const MAX_HOOK_TIMEOUT_SECS: u64 = 3600;
+
+/// How often the wait loop checks the child, the deadline and cancellation.
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    PreLaunch,
+    PostExit,
+}
+
+impl HookKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            HookKind::PreLaunch => "pre-launch",
+            HookKind::PostExit => "post-exit",
+        }
+    }
+
+    fn script(self, instance: &Instance) -> Option<&str> {
+        match self {
+            HookKind::PreLaunch => instance.pre_launch_hook.as_deref(),
+            HookKind::PostExit => instance.post_exit_hook.as_deref(),
+        }
+        .map(str::trim)
+        .filter(|script| !script.is_empty())
+    }
+}
+
+fn shell_command(script: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(script);
+        cmd
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+}
+
+/// Run `script` through the platform shell in `cwd`, passing every stdout and
+/// stderr line to `on_line`. A non-zero exit is a `LauncherError::HookFailed`;
+/// the child is killed when `timeout` elapses or `cancel` fires.
+pub fn run_hook(
+    kind: HookKind,
+    script: &str,
+    cwd: &Path,
+    timeout: Duration,
+    cancel: &CancellationToken,
+    mut on_line: impl FnMut(&str) + Send,
+) -> LauncherResult<()> {
+    let io_error = |source| LauncherError::Io {
+        path: cwd.to_path_buf(),
+        source,
+    };
+    let mut child = shell_command(script)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(io_error)?;
+
+    // Readers are detached: a grandchild that inherited the pipes may keep
+    // them open after the hook itself was killed.
+    let (tx, rx) = std::sync::mpsc::channel::<String>();
+    if let Some(stderr) = child.stderr.take() {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let _ = tx.send(line);
+            }
+        });
+    }
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let _ = tx.send(line);
+            }
+        });
+    } else {
+        drop(tx);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match rx.recv_timeout(HOOK_POLL_INTERVAL) {
+            Ok(line) => on_line(&line),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => std::thread::sleep(HOOK_POLL_INTERVAL),
+        }
+        if let Some(status) = child.try_wait().map_err(io_error)? {
+            break status;
+        }
+        let timed_out = Instant::now() >= deadline;
+        if timed_out || cancel.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            tracing::warn!(
+                "{} hook killed ({})",
+                kind.label(),
+                if timed_out { "timeout" } else { "cancelled" }
+            );
+            return Err(if timed_out {
+                LauncherError::message(
+                    ErrorText::HookTimedOut,
+                    &[&kind.label(), &timeout.as_secs()],
+                )
+            } else {
+                LauncherError::Cancelled
+            });
+        }
+    };
+
+    // Flush what the hook printed right before exiting, without waiting on
+    // pipes a leftover grandchild may still hold.
+    while let Ok(line) = rx.recv_timeout(HOOK_POLL_INTERVAL) {
+        on_line(&line);
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LauncherError::HookFailed {
+            hook: kind.label().to_string(),
+            status: status
+                .code()
+                .map_or_else(|| "signal".to_string(), |code| code.to_string()),
+        })
+    }
+}
+
+/// Run the configured hook of `instance`, if any, unless hooks are disabled
+/// globally. Returns whether a hook actually ran.
+pub fn run_instance_hook(
+    kind: HookKind,
+    instance: &Instance,
+    hooks_enabled: bool,
+    timeout: Duration,
+    cancel: &CancellationToken,
+    on_line: impl FnMut(&str) + Send,
+) -> LauncherResult<bool> {
+    let Some(script) = kind.script(instance) else {
+        return Ok(false);
+    };
+    if !hooks_enabled {
+        return Ok(false);
+    }
+
+    let game_dir = instance.game_dir();
+    std::fs::create_dir_all(&game_dir).map_err(|source| LauncherError::Io {
+        path: game_dir.clone(),
+        source,
+    })?;
+    run_hook(kind, script, &game_dir, timeout, cancel, on_line)?;
+    Ok(true)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+
+    fn instance_with_hook(base: &Path, script: &str) -> Instance {
+        let mut instance = Instance::new(
+            "hooks".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            base,
+        );
+        instance.pre_launch_hook = Some(script.into());
+        instance
+    }
+
+    fn run_hook_with_defaults(
+        kind: HookKind,
+        instance: &Instance,
+        hooks_enabled: bool,
+        on_line: impl FnMut(&str) + Send,
+    ) -> LauncherResult<bool> {
+        let timeout = Duration::from_secs(DEFAULT_HOOK_TIMEOUT_SECS);
+        run_instance_hook(
+            kind,
+            instance,
+            hooks_enabled,
+            timeout,
+            &CancellationToken::new(),
+            on_line,
+        )
+    }
+
+    #[test]
+    fn hook_output_is_streamed_from_game_dir() {
+        let temp = std::env::temp_dir().join(format!("hooks-ok-{}", std::process::id()));
+        let instance = instance_with_hook(&temp, "echo ready; pwd; echo oops >&2");
+
+        let mut lines = Vec::new();
+        let ran = run_hook_with_defaults(HookKind::PreLaunch, &instance, true, |l| {
+            lines.push(l.to_string())
+        })
+        .unwrap();
+
+        assert!(ran);
+        assert!(lines.contains(&"ready".to_string()));
+        assert!(lines.contains(&"oops".to_string()));
+        assert!(lines.iter().any(|l| l.ends_with("minecraft")));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn failing_hook_aborts_with_hook_error() {
+        let temp = std::env::temp_dir().join(format!("hooks-fail-{}", std::process::id()));
+        let instance = instance_with_hook(&temp, "echo syncing; exit 3");
+
+        let result = run_hook_with_defaults(HookKind::PreLaunch, &instance, true, |_| {});
+        match result {
+            Err(LauncherError::HookFailed { hook, status }) => {
+                assert_eq!(hook, "pre-launch");
+                assert_eq!(status, "3");
+            }
+            other => panic!("expected hook failure, got {other:?}"),
+        }
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn disabled_hooks_do_not_run() {
+        let temp = std::env::temp_dir().join(format!("hooks-off-{}", std::process::id()));
+        let instance = instance_with_hook(&temp, "exit 1");
+
+        let ran = run_hook_with_defaults(HookKind::PreLaunch, &instance, false, |_| {}).unwrap();
+        assert!(!ran);
+        assert!(!run_hook_with_defaults(HookKind::PostExit, &instance, true, |_| {}).unwrap());
+    }
+
+    #[test]
+    fn hung_hook_is_killed_at_the_timeout() {
+        let temp = std::env::temp_dir().join(format!("hooks-slow-{}", std::process::id()));
+        let instance = instance_with_hook(&temp, "echo waiting; sleep 30");
+
+        let started = Instant::now();
+        let mut lines = Vec::new();
+        let result = run_instance_hook(
+            HookKind::PreLaunch,
+            &instance,
+            true,
+            Duration::from_millis(300),
+            &CancellationToken::new(),
+            |l| lines.push(l.to_string()),
+        );
+
+        let err = result.unwrap_err();
+        assert_eq!(err.i18n_key(), "error.text.hook_timed_out");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(lines, vec!["waiting".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn cancelled_launch_kills_the_hook() {
+        let temp = std::env::temp_dir().join(format!("hooks-cancel-{}", std::process::id()));
+        let instance = instance_with_hook(&temp, "sleep 30");
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            trigger.cancel();
+        });
+
+        let started = Instant::now();
+        let result = run_instance_hook(
+            HookKind::PreLaunch,
+            &instance,
+            true,
+            Duration::from_secs(DEFAULT_HOOK_TIMEOUT_SECS),
+            &cancel,
+            |_| {},
+        );
+
+        assert!(
+            matches!(result, Err(LauncherError::Cancelled)),
+            "{result:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
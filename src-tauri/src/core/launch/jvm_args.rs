@@ -0,0 +1,256 @@
+// ─── JVM Arguments ───
+// Garbage collector selection, GC flag helpers and validation of user JVM args.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+/// Garbage collector applied by the optimizer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GarbageCollector {
+    #[default]
+    G1,
+    Z,
+    Shenandoah,
+    Parallel,
+    Serial,
+}
+
+impl GarbageCollector {
+    /// The `-XX:+Use…GC` selector for this collector.
+    pub fn selector(self) -> &'static str {
+        match self {
+            GarbageCollector::G1 => "-XX:+UseG1GC",
+            GarbageCollector::Z => "-XX:+UseZGC",
+            GarbageCollector::Shenandoah => "-XX:+UseShenandoahGC",
+            GarbageCollector::Parallel => "-XX:+UseParallelGC",
+            GarbageCollector::Serial => "-XX:+UseSerialGC",
+        }
+    }
+
+    /// Check the collector is usable with the instance runtime.
+    ///
+    /// ZGC is production-ready from Java 15 (generational from 21), and
+    /// Shenandoah is absent from Oracle builds.
+    pub fn ensure_supported(
+        self,
+        java_major: u32,
+        java_vendor: Option<&str>,
+    ) -> LauncherResult<()> {
+        match self {
+            GarbageCollector::Z if java_major < 15 => Err(LauncherError::message(
+                ErrorText::ZgcNeedsJava15,
+                &[&java_major],
+            )),
+            GarbageCollector::Shenandoah if java_major < 12 => Err(LauncherError::message(
+                ErrorText::ShenandoahNeedsJava12,
+                &[&java_major],
+            )),
+            GarbageCollector::Shenandoah
+                if java_vendor
+                    .is_some_and(|vendor| vendor.to_ascii_lowercase().contains("oracle")) =>
+            {
+                Err(LauncherError::message(
+                    ErrorText::ShenandoahUnavailableOnOracle,
+                    &[],
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whether `arg` selects or tunes a specific collector, so it must be dropped
+/// before another collector's flags are merged in.
+pub fn is_gc_specific_flag(arg: &str) -> bool {
+    gc_selector(arg).is_some()
+        || [
+            "-XX:G1",
+            "-XX:+ZGenerational",
+            "-XX:-ZGenerational",
+            "-XX:ZCollectionInterval",
+            "-XX:Shenandoah",
+            "-XX:MaxGCPauseMillis",
+            "-XX:InitiatingHeapOccupancyPercent",
+            "-XX:ParallelGCThreads",
+        ]
+        .iter()
+        .any(|prefix| arg.starts_with(prefix))
+}
+
+/// The collector enabled by `arg`, if it is a `-XX:+Use…GC` selector.
+pub fn gc_selector(arg: &str) -> Option<&str> {
+    let name = arg.strip_prefix("-XX:+Use")?.strip_suffix("GC")?;
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())).then_some(name)
+}
+
+/// Heap sizing flags that must appear at most once.
+const HEAP_FLAGS: [&str; 4] = ["-Xmx", "-Xms", "-Xss", "-Xmn"];
+
+/// A problem in user-supplied JVM arguments, tagged by `kind` for the UI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JvmArgConflict {
+    DuplicateHeapFlag { flag: String, args: Vec<String> },
+    ConflictingGarbageCollectors { args: Vec<String> },
+    MalformedProperty { arg: String },
+}
+
+impl fmt::Display for JvmArgConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JvmArgConflict::DuplicateHeapFlag { flag, args } => {
+                write!(f, "{flag} set more than once ({})", args.join(", "))
+            }
+            JvmArgConflict::ConflictingGarbageCollectors { args } => {
+                write!(
+                    f,
+                    "multiple garbage collectors selected ({})",
+                    args.join(", ")
+                )
+            }
+            JvmArgConflict::MalformedProperty { arg } => {
+                write!(f, "malformed system property {arg:?}")
+            }
+        }
+    }
+}
+
+/// Module and bootstrap flags injected for Forge/NeoForge. They are
+/// deduplicated when the launch command is built, so they are not validated.
+fn is_loader_workaround(arg: &str) -> bool {
+    arg.starts_with("--add-") || arg == "-p" || arg.starts_with("--module-path")
+}
+
+/// Detect JVM argument combinations that make the JVM refuse to start:
+/// repeated heap flags, several GC selectors and malformed `-D` properties.
+pub fn validate_jvm_args(args: &[String]) -> Result<(), Vec<JvmArgConflict>> {
+    let mut conflicts = Vec::new();
+    let mut heap_flags: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    let mut collectors: Vec<String> = Vec::new();
+
+    for arg in args.iter().map(|arg| arg.trim()) {
+        if is_loader_workaround(arg) {
+            continue;
+        }
+
+        if let Some(flag) = HEAP_FLAGS.iter().find(|flag| arg.starts_with(**flag)) {
+            heap_flags.entry(flag).or_default().push(arg.to_string());
+        } else if gc_selector(arg).is_some() {
+            if !collectors.iter().any(|existing| existing == arg) {
+                collectors.push(arg.to_string());
+            }
+        } else if let Some(property) = arg.strip_prefix("-D") {
+            let key = property.split('=').next().unwrap_or_default();
+            if key.is_empty() || key.chars().any(char::is_whitespace) {
+                conflicts.push(JvmArgConflict::MalformedProperty {
+                    arg: arg.to_string(),
+                });
+            }
+        }
+    }
+
+    for (flag, args) in heap_flags {
+        if args.len() > 1 {
+            conflicts.push(JvmArgConflict::DuplicateHeapFlag {
+                flag: flag.to_string(),
+                args,
+            });
+        }
+    }
+    if collectors.len() > 1 {
+        conflicts.push(JvmArgConflict::ConflictingGarbageCollectors { args: collectors });
+    }
+
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_gc_selectors_and_tuning_flags() {
+        assert_eq!(gc_selector("-XX:+UseZGC"), Some("Z"));
+        assert_eq!(gc_selector("-XX:+UseShenandoahGC"), Some("Shenandoah"));
+        assert_eq!(gc_selector("-XX:+UseStringDeduplication"), None);
+        assert!(is_gc_specific_flag("-XX:G1HeapRegionSize=16M"));
+        assert!(is_gc_specific_flag("-XX:MaxGCPauseMillis=50"));
+        assert!(!is_gc_specific_flag("-XX:+AlwaysPreTouch"));
+        assert!(!is_gc_specific_flag("-Dfile.encoding=UTF-8"));
+    }
+
+    #[test]
+    fn collector_support_depends_on_runtime() {
+        assert!(GarbageCollector::Z.ensure_supported(8, None).is_err());
+        assert!(GarbageCollector::Z.ensure_supported(21, None).is_ok());
+        assert!(GarbageCollector::Shenandoah
+            .ensure_supported(17, Some("Oracle Corporation"))
+            .is_err());
+        assert!(GarbageCollector::Shenandoah
+            .ensure_supported(17, Some("Temurin"))
+            .is_ok());
+        assert!(GarbageCollector::Serial.ensure_supported(8, None).is_ok());
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn valid_args_pass() {
+        assert!(validate_jvm_args(&args(&[
+            "-Xmx4G",
+            "-Xms2G",
+            "-XX:+UseG1GC",
+            "-XX:+UseG1GC",
+            "-Dfml.earlyprogresswindow=false",
+            "-Dflag",
+            "--add-opens",
+            "java.base/java.lang=ALL-UNNAMED",
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn duplicate_heap_flags_are_reported() {
+        let conflicts = validate_jvm_args(&args(&["-Xmx2G", "-Xms1G", "-Xmx4G"])).unwrap_err();
+        assert_eq!(
+            conflicts,
+            vec![JvmArgConflict::DuplicateHeapFlag {
+                flag: "-Xmx".into(),
+                args: args(&["-Xmx2G", "-Xmx4G"]),
+            }]
+        );
+    }
+
+    #[test]
+    fn mixed_collectors_are_reported() {
+        let conflicts = validate_jvm_args(&args(&["-XX:+UseG1GC", "-XX:+UseZGC"])).unwrap_err();
+        assert_eq!(
+            conflicts,
+            vec![JvmArgConflict::ConflictingGarbageCollectors {
+                args: args(&["-XX:+UseG1GC", "-XX:+UseZGC"]),
+            }]
+        );
+    }
+
+    #[test]
+    fn malformed_properties_are_reported() {
+        let conflicts =
+            validate_jvm_args(&args(&["-D", "-D=value", "-Dmy key=1", "-Dok=1"])).unwrap_err();
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts
+            .iter()
+            .all(|c| matches!(c, JvmArgConflict::MalformedProperty { .. })));
+    }
+}
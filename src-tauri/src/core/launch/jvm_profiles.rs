@@ -0,0 +1,168 @@
+// ─── JVM Profiles ───
+// Named, reusable JVM argument sets persisted in `<data_dir>/jvm_profiles.json`.
+// Only user profiles are stored; the built-in ones are generated by the
+// optimizer on demand and cannot be replaced.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+/// File name of the profile book inside the data dir.
+pub const JVM_PROFILES_FILE: &str = "jvm_profiles.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JvmProfile {
+    pub name: String,
+    pub args: Vec<String>,
+    /// Shipped with the launcher and read-only.
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JvmProfileBook {
+    #[serde(default)]
+    pub profiles: Vec<JvmProfile>,
+}
+
+impl JvmProfileBook {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(JVM_PROFILES_FILE)
+    }
+
+    /// Read the book from `data_dir`. A missing file is an empty book; an
+    /// unreadable one is moved aside to a `.bak` file first so saving user
+    /// profiles again cannot overwrite it.
+    pub fn load(data_dir: &Path) -> Self {
+        let path = Self::path(data_dir);
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&raw).unwrap_or_else(|err| {
+            match atomic_file::set_aside(&path) {
+                Ok(backup) => warn!(
+                    "Unreadable {} ({}); moved it to {}",
+                    path.display(),
+                    err,
+                    backup.display()
+                ),
+                Err(rename_err) => warn!(
+                    "Unreadable {} ({}) and cannot move it aside: {}",
+                    path.display(),
+                    err,
+                    rename_err
+                ),
+            }
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, data_dir: &Path) -> LauncherResult<()> {
+        let path = Self::path(data_dir);
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(&path, json).map_err(|source| LauncherError::Io { path, source })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&JvmProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Store `args` under `name`, replacing a user profile of the same name.
+    /// Names taken by a built-in profile are refused.
+    pub fn upsert(
+        &mut self,
+        builtins: &[JvmProfile],
+        name: &str,
+        args: Vec<String>,
+    ) -> LauncherResult<&JvmProfile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(LauncherError::message(
+                ErrorText::JvmProfileNameRequired,
+                &[],
+            ));
+        }
+        if builtins.iter().any(|profile| profile.name == name) {
+            return Err(LauncherError::message(
+                ErrorText::JvmProfileBuiltIn,
+                &[&name],
+            ));
+        }
+
+        let profile = JvmProfile {
+            name: name.to_string(),
+            args,
+            builtin: false,
+        };
+        let index = match self.profiles.iter().position(|p| p.name == name) {
+            Some(index) => {
+                self.profiles[index] = profile;
+                index
+            }
+            None => {
+                self.profiles.push(profile);
+                self.profiles.len() - 1
+            }
+        };
+        Ok(&self.profiles[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(name: &str) -> JvmProfile {
+        JvmProfile {
+            name: name.into(),
+            args: vec!["-XX:+UseG1GC".into()],
+            builtin: true,
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_user_profiles_but_not_builtins() {
+        let builtins = [builtin("G1 de Aikar")];
+        let mut book = JvmProfileBook::default();
+
+        book.upsert(&builtins, "Mine", vec!["-XX:+UseZGC".into()])
+            .unwrap();
+        book.upsert(&builtins, " Mine ", vec!["-XX:+UseSerialGC".into()])
+            .unwrap();
+        assert_eq!(book.profiles.len(), 1);
+        assert_eq!(book.get("Mine").unwrap().args, ["-XX:+UseSerialGC"]);
+
+        assert!(book.upsert(&builtins, "G1 de Aikar", Vec::new()).is_err());
+        assert!(book.upsert(&builtins, "  ", Vec::new()).is_err());
+        assert!(book.get("G1 de Aikar").is_none());
+    }
+
+    #[test]
+    fn book_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!("jvm-profiles-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert!(JvmProfileBook::load(&dir).profiles.is_empty());
+        let mut book = JvmProfileBook::default();
+        book.upsert(&[], "Mine", vec!["-XX:+UseZGC".into()])
+            .unwrap();
+        book.save(&dir).unwrap();
+        assert_eq!(JvmProfileBook::load(&dir).get("Mine"), book.get("Mine"));
+
+        std::fs::write(JvmProfileBook::path(&dir), "[oops").unwrap();
+        assert!(JvmProfileBook::load(&dir).profiles.is_empty());
+        assert!(!JvmProfileBook::path(&dir).exists());
+        assert!(std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().ends_with(".bak")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
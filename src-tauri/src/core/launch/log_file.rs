@@ -0,0 +1,173 @@
+// ─── Instance Log File ───
+// Persists game stdout/stderr to `logs/launcher/<instance-id>.log` with
+// size-based rotation, so users have a file to attach to bug reports.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use crate::core::error::{LauncherError, LauncherResult};
+
+/// Rotation used when the launcher settings don't override it.
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_LOG_ROTATIONS: usize = 3;
+/// Bounds accepted for the configurable rotation.
+pub const MIN_MAX_LOG_BYTES: u64 = 1024 * 1024;
+pub const MAX_LOG_ROTATIONS: usize = 20;
+
+/// Path of the current log file for an instance.
+pub fn instance_log_path(logs_dir: &Path, instance_id: &str) -> PathBuf {
+    logs_dir.join(format!("{instance_id}.log"))
+}
+
+/// Whether appending `incoming` bytes would push the file past `max_bytes`.
+/// An empty file always accepts the line so oversized lines still get written.
+fn should_rotate(current: u64, incoming: u64, max_bytes: u64) -> bool {
+    current > 0 && current.saturating_add(incoming) > max_bytes
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// Appends timestamped game output and rotates `<id>.log` → `<id>.log.1` → …
+/// keeping at most `rotations` old files.
+pub struct InstanceLogWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+    written: u64,
+    max_bytes: u64,
+    rotations: usize,
+}
+
+impl InstanceLogWriter {
+    pub fn open(path: PathBuf, max_bytes: u64, rotations: usize) -> LauncherResult<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+
+        let file = open_append(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            file: BufWriter::new(file),
+            written,
+            max_bytes,
+            rotations,
+        })
+    }
+
+    pub fn write_line(&mut self, stream: &str, line: &str) -> LauncherResult<()> {
+        let entry = format!(
+            "[{}] [{}] {}\n",
+            Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            stream,
+            line
+        );
+
+        if should_rotate(self.written, entry.len() as u64, self.max_bytes) {
+            self.rotate()?;
+        }
+
+        self.file
+            .write_all(entry.as_bytes())
+            .map_err(|source| LauncherError::Io {
+                path: self.path.clone(),
+                source,
+            })?;
+        self.written += entry.len() as u64;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> LauncherResult<()> {
+        self.file.flush().map_err(|source| LauncherError::Io {
+            path: self.path.clone(),
+            source,
+        })
+    }
+
+    fn rotate(&mut self) -> LauncherResult<()> {
+        self.flush()?;
+
+        if self.rotations == 0 {
+            let file = File::create(&self.path).map_err(|source| LauncherError::Io {
+                path: self.path.clone(),
+                source,
+            })?;
+            self.file = BufWriter::new(file);
+            self.written = 0;
+            return Ok(());
+        }
+
+        let _ = fs::remove_file(rotated_path(&self.path, self.rotations));
+        for index in (1..self.rotations).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                let _ = fs::rename(&from, rotated_path(&self.path, index + 1));
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1)).map_err(|source| {
+            LauncherError::Io {
+                path: self.path.clone(),
+                source,
+            }
+        })?;
+
+        self.file = BufWriter::new(open_append(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> LauncherResult<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|source| LauncherError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_threshold() {
+        assert!(!should_rotate(0, 500, 100));
+        assert!(!should_rotate(60, 40, 100));
+        assert!(should_rotate(61, 40, 100));
+    }
+
+    #[test]
+    fn writer_rotates_and_keeps_limited_history() {
+        let temp = std::env::temp_dir().join(format!("instance-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+
+        let path = instance_log_path(&temp, "abc");
+        let mut writer = InstanceLogWriter::open(path.clone(), 128, 2).unwrap();
+        for i in 0..20 {
+            writer.write_line("stdout", &format!("line {i}")).unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert!(path.exists());
+        assert!(rotated_path(&path, 1).exists());
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+        assert!(fs::metadata(&path).unwrap().len() <= 128);
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.contains("[stdout] line 19"));
+
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
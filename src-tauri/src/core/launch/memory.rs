@@ -0,0 +1,110 @@
+// ─── Memory Suggestion ───
+// Default heap size for new instances from the machine's physical memory,
+// clamped to bounds the game runs well with.
+
+use serde::Serialize;
+use sysinfo::System;
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MemorySuggestion {
+    pub recommended_xmx_mb: u32,
+    pub recommended_xms_mb: u32,
+    pub total_mb: u64,
+    pub available_mb: u64,
+}
+
+/// Heap the game is given even when little memory is free.
+const MIN_HEAP_MB: u32 = 2048;
+
+/// `MIN_HEAP_MB`, lowered on small machines so the game never takes more
+/// than half of the physical memory from the OS.
+fn heap_floor_mb(total_mb: u64) -> u32 {
+    MIN_HEAP_MB.min((total_mb / 2) as u32)
+}
+
+pub(crate) fn clamp_memory_to_safe_bounds(
+    total_mb: u64,
+    available_mb: u64,
+    suggested_mb: u32,
+) -> (u32, Vec<String>) {
+    let mut notes = Vec::new();
+    let hard_cap_by_total = ((total_mb as f64) * 0.60).floor() as u32;
+    let available_cap = available_mb.saturating_sub(if total_mb >= 32 * 1024 {
+        6144
+    } else if total_mb >= 16 * 1024 {
+        4096
+    } else {
+        3072
+    }) as u32;
+    let floor = heap_floor_mb(total_mb);
+    let cap = hard_cap_by_total.min(available_cap.max(floor)).max(floor);
+
+    let mut final_mb = suggested_mb.max(floor);
+    if final_mb > cap {
+        final_mb = cap;
+        notes.push(
+            "Ajustamos la RAM para evitar inestabilidad del sistema (límite dinámico aplicado)."
+                .into(),
+        );
+    }
+
+    (final_mb, notes)
+}
+
+/// Default heap for a new instance based on the machine, before any mods are known.
+pub(crate) fn suggest_memory_for_system(total_mb: u64, available_mb: u64) -> MemorySuggestion {
+    let suggested_mb = if total_mb < 6 * 1024 {
+        MIN_HEAP_MB
+    } else if total_mb < 12 * 1024 {
+        3072
+    } else if total_mb < 24 * 1024 {
+        4096
+    } else {
+        8192
+    };
+    let (recommended_xmx_mb, _) = clamp_memory_to_safe_bounds(total_mb, available_mb, suggested_mb);
+
+    MemorySuggestion {
+        recommended_xmx_mb,
+        recommended_xms_mb: (recommended_xmx_mb / 2).max(1024).min(recommended_xmx_mb),
+        total_mb,
+        available_mb,
+    }
+}
+
+pub(crate) fn detect_memory_suggestion() -> MemorySuggestion {
+    let mut system = System::new();
+    system.refresh_memory();
+    suggest_memory_for_system(
+        system.total_memory() / (1024 * 1024),
+        system.available_memory() / (1024 * 1024),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_suggestion_scales_with_system_ram() {
+        let cases = [
+            // (total, available, expected xmx)
+            (2 * 1024, 1024, 1024),
+            (3 * 1024, 1024, 1536),
+            (4 * 1024, 2 * 1024, 2048),
+            (8 * 1024, 6 * 1024, 3072),
+            (8 * 1024, 4 * 1024, 2048),
+            (16 * 1024, 10 * 1024, 4096),
+            (32 * 1024, 24 * 1024, 8192),
+        ];
+
+        for (total, available, expected) in cases {
+            let suggestion = suggest_memory_for_system(total, available);
+            assert_eq!(
+                suggestion.recommended_xmx_mb, expected,
+                "total={total} available={available}"
+            );
+            assert!(suggestion.recommended_xms_mb <= suggestion.recommended_xmx_mb);
+        }
+    }
+}
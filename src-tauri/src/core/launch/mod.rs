@@ -1,7 +1,33 @@
 pub mod classpath;
+pub mod crash_report;
+pub mod diagnostics;
+pub mod events;
+pub mod hooks;
+pub mod jvm_args;
+pub mod jvm_profiles;
+pub mod log_file;
+pub mod memory;
+pub mod optimizer;
+pub mod phases;
+pub mod preflight;
+pub mod session;
+pub mod shutdown;
 pub mod task;
 
 #[allow(unused_imports)]
-pub use classpath::{build_classpath, cleanup_natives, extract_natives};
+pub use classpath::{
+    build_classpath, cleanup_natives, clear_stale_natives, extract_natives, native_jars,
+};
+pub use crash_report::{summarize_crash, CrashReportSummary};
+pub use diagnostics::{DiagnosticTracker, InstanceDiagnosticEvent};
+pub use hooks::{run_instance_hook, HookKind, DEFAULT_HOOK_TIMEOUT_SECS, MAX_HOOK_TIMEOUT_SECS};
+pub use jvm_args::{validate_jvm_args, GarbageCollector, JvmArgConflict};
+pub use jvm_profiles::{JvmProfile, JvmProfileBook};
+pub use log_file::{
+    instance_log_path, InstanceLogWriter, DEFAULT_LOG_ROTATIONS, DEFAULT_MAX_LOG_BYTES,
+    MAX_LOG_ROTATIONS, MIN_MAX_LOG_BYTES,
+};
+pub use phases::{LaunchPhase, PhaseTimeline};
+pub use shutdown::{stop_process, DEFAULT_STOP_GRACE_SECS, FORCE_STOP_GRACE, MAX_STOP_GRACE_SECS};
 #[allow(unused_imports)]
-pub use task::launch;
+pub use task::{find_program_on_path, launch, launch_config, preview_command, LaunchPreview};
@@ -0,0 +1,656 @@
+// ─── Instance Optimizer ───
+// Mod analysis, memory recommendation and JVM arguments for an instance,
+// plus the cleanup of old game logs. The built-in JVM profiles are
+// optimizer presets generated here for the Java major they run on.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use super::jvm_args;
+use super::jvm_profiles::{JvmProfile, JvmProfileBook};
+use super::memory::clamp_memory_to_safe_bounds;
+use super::GarbageCollector;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{Instance, LoaderType};
+use crate::core::java;
+use crate::core::mods::metadata as mod_metadata;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationMode {
+    Balanced,
+    MaxPerformance,
+    LowPower,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OptimizeOptions {
+    #[serde(default)]
+    pub mode: Option<OptimizationMode>,
+    #[serde(default)]
+    pub garbage_collector: Option<GarbageCollector>,
+    /// Logs modified within this many days survive the cleanup; `latest.log`
+    /// is always kept.
+    #[serde(default)]
+    pub keep_log_days: Option<u32>,
+    /// Report the logs that would be removed without deleting them.
+    #[serde(default)]
+    pub dry_run_logs: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptimizationReport {
+    pub recommended_xmx_mb: u32,
+    pub recommended_xms_mb: u32,
+    pub detected_mods: usize,
+    pub duplicate_mods: Vec<String>,
+    pub potentially_conflicting_mods: Vec<String>,
+    pub missing_recommended_mods: Vec<String>,
+    pub removed_logs: usize,
+    pub freed_log_bytes: u64,
+    /// `removed_logs`/`freed_log_bytes` describe a dry run; nothing was deleted.
+    pub logs_dry_run: bool,
+    pub mode: String,
+    pub garbage_collector: GarbageCollector,
+    pub notes: Vec<String>,
+}
+
+/// Read-only profiles shipped with the launcher: optimizer presets whose
+/// arguments depend on the Java major they run on.
+const BUILTIN_JVM_PROFILES: [(&str, OptimizationMode, GarbageCollector); 2] = [
+    (
+        "G1 de Aikar",
+        OptimizationMode::MaxPerformance,
+        GarbageCollector::G1,
+    ),
+    (
+        "ZGC de baja latencia",
+        OptimizationMode::Balanced,
+        GarbageCollector::Z,
+    ),
+];
+
+/// Java major the built-in profiles are listed for when no instance is given.
+pub const LISTED_PROFILES_JAVA_MAJOR: u32 = 21;
+
+fn recommended_memory_for_mod_count(mod_count: usize, mode: &OptimizationMode) -> u32 {
+    let base = if mod_count <= 50 {
+        5120
+    } else if mod_count <= 150 {
+        7168
+    } else {
+        10240
+    };
+
+    match mode {
+        OptimizationMode::Balanced => base,
+        OptimizationMode::MaxPerformance => base.saturating_add(1024),
+        OptimizationMode::LowPower => base.saturating_sub(1024).max(4096),
+    }
+}
+
+fn normalize_mod_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn collect_mod_analysis(
+    instance: &Instance,
+) -> (usize, Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
+    let mut installed_mods = Vec::new();
+    let mut conflict_hits = Vec::new();
+    let mut notes = Vec::new();
+
+    let mods_dir = instance.mods_dir();
+    if let Ok(entries) = fs::read_dir(&mods_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_jar = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case("jar"))
+                .unwrap_or(false);
+            if !is_jar {
+                continue;
+            }
+
+            let installed = mod_metadata::InstalledMod::read(&path);
+            let has_metadata = installed.metadata.is_some();
+            installed_mods.push(installed);
+            let normalized = normalize_mod_name(&path);
+            if normalized.is_empty() {
+                continue;
+            }
+
+            if normalized.contains("optifine") {
+                conflict_hits.push("OptiFine puede generar conflictos en packs modernos (usa Sodium/Embeddium según loader).".into());
+            }
+            // Jars with readable metadata are checked by `compatibility_issues`.
+            if has_metadata {
+                continue;
+            }
+            if normalized.contains("rubidium") && instance.loader == LoaderType::Fabric {
+                conflict_hits
+                    .push("Rubidium no es para Fabric; revisa compatibilidad del loader.".into());
+            }
+            if normalized.contains("sodium") && instance.loader == LoaderType::Forge {
+                conflict_hits.push(
+                    "Sodium en Forge suele indicar mod incorrecto; usa Embeddium/Rubidium.".into(),
+                );
+            }
+        }
+    } else {
+        notes.push("No se pudo leer la carpeta de mods para análisis automático.".into());
+    }
+
+    let mod_count = installed_mods.len();
+    let duplicates = mod_metadata::duplicate_mods(&installed_mods);
+    conflict_hits.extend(mod_metadata::compatibility_issues(
+        &installed_mods,
+        &instance.loader,
+        &instance.minecraft_version,
+    ));
+    let mod_names: HashSet<String> = installed_mods.iter().map(|m| m.key()).collect();
+    let mut missing = Vec::new();
+    let recommendations = ["sodium", "lithium", "ferritecore"];
+    for item in recommendations {
+        if !mod_names.contains(item) {
+            missing.push(item.to_string());
+        }
+    }
+
+    (mod_count, duplicates, conflict_hits, missing, notes)
+}
+
+/// Remove `.log`/`.gz` files from the instance's `logs/` folder. The current
+/// `latest.log` is always kept, as is anything modified within `keep_days`.
+/// With `dry_run` nothing is deleted; the counts are what would be freed.
+pub fn clean_old_logs(
+    instance: &Instance,
+    keep_days: Option<u32>,
+    dry_run: bool,
+    now: SystemTime,
+) -> (usize, u64) {
+    let mut removed = 0usize;
+    let mut freed = 0u64;
+    let logs_dir = instance.game_dir().join("logs");
+    let keep_for = keep_days.map(|days| Duration::from_secs(u64::from(days) * 86_400));
+
+    if let Ok(entries) = fs::read_dir(logs_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_log = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case("log") || e.eq_ignore_ascii_case("gz"))
+                .unwrap_or(false);
+            if !is_log || path.file_name() == Some(OsStr::new("latest.log")) {
+                continue;
+            }
+
+            let Ok(meta) = fs::metadata(&path) else {
+                continue;
+            };
+            let recent = keep_for.is_some_and(|keep_for| {
+                meta.modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_none_or(|age| age < keep_for)
+            });
+            if recent {
+                continue;
+            }
+
+            if dry_run || fs::remove_file(&path).is_ok() {
+                removed += 1;
+                freed = freed.saturating_add(meta.len());
+            }
+        }
+    }
+
+    (removed, freed)
+}
+
+fn optimized_jvm_args(
+    java_major: u32,
+    mode: &OptimizationMode,
+    collector: GarbageCollector,
+) -> Vec<String> {
+    if collector != GarbageCollector::G1 {
+        let mut args = vec![collector.selector().to_string()];
+        if collector == GarbageCollector::Z && java_major >= 21 {
+            args.push("-XX:+ZGenerational".into());
+        }
+        if !matches!(mode, OptimizationMode::LowPower) {
+            args.push("-XX:+AlwaysPreTouch".into());
+        }
+        return args;
+    }
+
+    let mut args = vec![
+        "-XX:+UseG1GC".to_string(),
+        "-XX:+UnlockExperimentalVMOptions".to_string(),
+        "-XX:G1NewSizePercent=20".to_string(),
+        "-XX:G1MaxNewSizePercent=60".to_string(),
+        "-XX:MaxGCPauseMillis=50".to_string(),
+        "-XX:G1HeapRegionSize=16M".to_string(),
+        "-XX:+AlwaysPreTouch".to_string(),
+    ];
+
+    if java_major < 17 {
+        args.retain(|item| item != "-XX:+UnlockExperimentalVMOptions");
+    }
+
+    match mode {
+        OptimizationMode::Balanced => {}
+        OptimizationMode::MaxPerformance => {
+            args.push("-XX:InitiatingHeapOccupancyPercent=15".into())
+        }
+        OptimizationMode::LowPower => args.push("-XX:MaxGCPauseMillis=80".into()),
+    }
+
+    args
+}
+
+/// Add `extra` to the instance's JVM arguments. When `extra` brings its own
+/// collector flags the previous collector's are dropped, since they would
+/// conflict; heap sizes are left to `max_memory_mb`.
+fn merge_jvm_args(existing: &[String], extra: Vec<String>) -> Vec<String> {
+    let replaces_collector = extra.iter().any(|arg| jvm_args::is_gc_specific_flag(arg));
+    let mut merged: Vec<String> = existing
+        .iter()
+        .filter(|arg| !replaces_collector || !jvm_args::is_gc_specific_flag(arg))
+        .cloned()
+        .chain(extra)
+        .filter(|arg| {
+            !arg.trim().is_empty() && !arg.starts_with("-Xmx") && !arg.starts_with("-Xms")
+        })
+        .collect();
+    merged.sort();
+    merged.dedup();
+    merged
+}
+
+/// Run the optimizer on an in-memory copy of `instance`: mod analysis, memory
+/// recommendation and JVM arguments. Nothing is written to disk; the returned
+/// instance carries the proposed settings.
+pub fn plan_optimization(
+    instance: &Instance,
+    options: &OptimizeOptions,
+    total_mb: u64,
+    available_mb: u64,
+) -> LauncherResult<(Instance, OptimizationReport)> {
+    let mut instance = instance.clone();
+    let mode = options.mode.clone().unwrap_or(OptimizationMode::Balanced);
+
+    let (
+        detected_mods,
+        duplicate_mods,
+        potentially_conflicting_mods,
+        missing_recommended_mods,
+        mut notes,
+    ) = collect_mod_analysis(&instance);
+
+    let raw_suggested_mb = recommended_memory_for_mod_count(detected_mods, &mode);
+    let (recommended_xmx_mb, mut clamp_notes) =
+        clamp_memory_to_safe_bounds(total_mb, available_mb, raw_suggested_mb);
+    notes.append(&mut clamp_notes);
+
+    let recommended_xms_mb = (recommended_xmx_mb / 2).max(1024).min(recommended_xmx_mb);
+
+    let java_major = instance_java_major(&instance);
+    let collector = options.garbage_collector.unwrap_or_default();
+    let java_vendor = match collector {
+        GarbageCollector::Shenandoah => instance
+            .java_path
+            .as_deref()
+            .and_then(java::inspect_java_binary)
+            .map(|installation| installation.vendor),
+        _ => None,
+    };
+    collector.ensure_supported(java_major, java_vendor.as_deref())?;
+
+    instance.max_memory_mb = recommended_xmx_mb;
+    instance.jvm_args = merge_jvm_args(
+        &instance.jvm_args,
+        optimized_jvm_args(java_major, &mode, collector),
+    );
+
+    let report = OptimizationReport {
+        recommended_xmx_mb,
+        recommended_xms_mb,
+        detected_mods,
+        duplicate_mods,
+        potentially_conflicting_mods,
+        missing_recommended_mods,
+        removed_logs: 0,
+        freed_log_bytes: 0,
+        logs_dry_run: false,
+        mode: match mode {
+            OptimizationMode::Balanced => "balanced".into(),
+            OptimizationMode::MaxPerformance => "max_performance".into(),
+            OptimizationMode::LowPower => "low_power".into(),
+        },
+        garbage_collector: collector,
+        notes,
+    };
+    Ok((instance, report))
+}
+
+/// [`plan_optimization`] with the log cleanup counted as a dry run, for
+/// showing what the optimizer would change.
+pub fn preview_optimization(
+    instance: &Instance,
+    options: &OptimizeOptions,
+    total_mb: u64,
+    available_mb: u64,
+) -> LauncherResult<(Instance, OptimizationReport)> {
+    let (planned, mut report) = plan_optimization(instance, options, total_mb, available_mb)?;
+    let (removed_logs, freed_log_bytes) =
+        clean_old_logs(instance, options.keep_log_days, true, SystemTime::now());
+    report.removed_logs = removed_logs;
+    report.freed_log_bytes = freed_log_bytes;
+    report.logs_dry_run = true;
+    Ok((planned, report))
+}
+
+/// The built-in profiles usable on `java_major`, with their arguments
+/// generated for it.
+pub fn builtin_jvm_profiles(java_major: u32) -> Vec<JvmProfile> {
+    BUILTIN_JVM_PROFILES
+        .iter()
+        .filter(|(_, _, collector)| collector.ensure_supported(java_major, None).is_ok())
+        .map(|(name, mode, collector)| JvmProfile {
+            name: (*name).into(),
+            args: optimized_jvm_args(java_major, mode, *collector),
+            builtin: true,
+        })
+        .collect()
+}
+
+/// Java major `instance` runs on, as the optimizer assumes it.
+pub fn instance_java_major(instance: &Instance) -> u32 {
+    instance
+        .required_java_major
+        .unwrap_or_else(|| java::required_java_for_minecraft_version(&instance.minecraft_version))
+}
+
+/// Merge the profile `name` into the instance's JVM arguments, resolving
+/// collector conflicts the way the optimizer does. Built-in profiles are
+/// generated for the instance's Java and refused when its collector is not
+/// available there.
+pub fn apply_jvm_profile(
+    instance: &mut Instance,
+    book: &JvmProfileBook,
+    name: &str,
+) -> LauncherResult<()> {
+    let java_major = instance_java_major(instance);
+    let profile = match BUILTIN_JVM_PROFILES.iter().find(|(n, _, _)| *n == name) {
+        Some((_, mode, collector)) => {
+            collector.ensure_supported(java_major, None)?;
+            optimized_jvm_args(java_major, mode, *collector)
+        }
+        None => book
+            .get(name)
+            .map(|profile| profile.args.clone())
+            .ok_or_else(|| LauncherError::message(ErrorText::JvmProfileNotFound, &[&name]))?,
+    };
+
+    let merged = merge_jvm_args(&instance.jvm_args, profile);
+    super::validate_jvm_args(&merged).map_err(LauncherError::InvalidJvmArgs)?;
+    instance.jvm_args = merged;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimized_jvm_args_never_mix_collectors() {
+        use crate::core::launch::jvm_args::gc_selector;
+
+        let collectors = [
+            GarbageCollector::G1,
+            GarbageCollector::Z,
+            GarbageCollector::Shenandoah,
+            GarbageCollector::Parallel,
+            GarbageCollector::Serial,
+        ];
+        let modes = [
+            OptimizationMode::Balanced,
+            OptimizationMode::MaxPerformance,
+            OptimizationMode::LowPower,
+        ];
+
+        for collector in collectors {
+            for mode in &modes {
+                let args = optimized_jvm_args(21, mode, collector);
+                let selectors: Vec<_> = args.iter().filter_map(|a| gc_selector(a)).collect();
+                assert_eq!(selectors.len(), 1, "{collector:?}: {args:?}");
+                assert!(args.iter().any(|a| a == collector.selector()));
+                if collector != GarbageCollector::G1 {
+                    assert!(!args.iter().any(|a| a.starts_with("-XX:G1")));
+                }
+            }
+        }
+
+        assert!(optimized_jvm_args(21, &modes[0], GarbageCollector::Z)
+            .contains(&"-XX:+ZGenerational".to_string()));
+        assert!(!optimized_jvm_args(17, &modes[0], GarbageCollector::Z)
+            .contains(&"-XX:+ZGenerational".to_string()));
+    }
+
+    #[test]
+    fn applying_a_jvm_profile_replaces_only_the_collector_flags() {
+        let mut instance = Instance::new(
+            "Profiles".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            Path::new("/tmp"),
+        );
+        instance.jvm_args = vec![
+            "-XX:+UseZGC".into(),
+            "-XX:+ZGenerational".into(),
+            "-Dfile.encoding=UTF-8".into(),
+            "-Xmx4G".into(),
+        ];
+
+        let mut book = JvmProfileBook::default();
+        book.upsert(
+            &builtin_jvm_profiles(21),
+            "Encoding",
+            vec![
+                "-Dfile.encoding=UTF-8".into(),
+                "-Dsun.stdout.encoding=UTF-8".into(),
+            ],
+        )
+        .unwrap();
+
+        apply_jvm_profile(&mut instance, &book, "G1 de Aikar").unwrap();
+        assert!(instance.jvm_args.contains(&"-XX:+UseG1GC".to_string()));
+        assert!(!instance.jvm_args.iter().any(|arg| arg.contains("ZGC")
+            || arg.contains("ZGenerational")
+            || arg.starts_with("-Xmx")));
+        assert!(instance
+            .jvm_args
+            .contains(&"-Dfile.encoding=UTF-8".to_string()));
+
+        // A profile without collector flags keeps the current collector.
+        apply_jvm_profile(&mut instance, &book, "Encoding").unwrap();
+        assert!(instance.jvm_args.contains(&"-XX:+UseG1GC".to_string()));
+        assert_eq!(
+            instance
+                .jvm_args
+                .iter()
+                .filter(|arg| *arg == "-Dfile.encoding=UTF-8")
+                .count(),
+            1
+        );
+        assert!(instance
+            .jvm_args
+            .contains(&"-Dsun.stdout.encoding=UTF-8".to_string()));
+
+        assert!(apply_jvm_profile(&mut instance, &book, "Missing").is_err());
+
+        // Built-in profiles follow the instance's Java instead of assuming 21.
+        let java_8 = builtin_jvm_profiles(8);
+        assert_eq!(java_8.len(), 1);
+        assert!(!java_8[0]
+            .args
+            .contains(&"-XX:+UnlockExperimentalVMOptions".to_string()));
+        instance.required_java_major = Some(8);
+        assert!(apply_jvm_profile(&mut instance, &book, "ZGC de baja latencia").is_err());
+        instance.required_java_major = Some(17);
+        apply_jvm_profile(&mut instance, &book, "ZGC de baja latencia").unwrap();
+        assert!(!instance
+            .jvm_args
+            .contains(&"-XX:+ZGenerational".to_string()));
+    }
+
+    #[test]
+    fn builtin_jvm_profiles_cannot_be_overwritten() {
+        let builtins = builtin_jvm_profiles(21);
+        assert!(builtins.iter().all(|profile| profile.builtin));
+        let mut book = JvmProfileBook::default();
+        for profile in &builtins {
+            assert!(book
+                .upsert(&builtins, &profile.name, vec!["-XX:+UseSerialGC".into()])
+                .is_err());
+        }
+        assert!(book.profiles.is_empty());
+    }
+
+    #[test]
+    fn log_cleanup_keeps_recent_logs_and_latest_log() {
+        let root = std::env::temp_dir().join(format!("log-cleanup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let instance = Instance::new(
+            "Logs".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &root,
+        );
+        let logs_dir = instance.game_dir().join("logs");
+        fs::create_dir_all(&logs_dir).unwrap();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(86_400);
+        for (name, age_days) in [
+            ("latest.log", 30),
+            ("2024-01-01-1.log.gz", 30),
+            ("2024-01-02-1.log.gz", 10),
+            ("2024-01-03-1.log.gz", 1),
+            ("notes.txt", 30),
+        ] {
+            let path = logs_dir.join(name);
+            fs::write(&path, "0123456789").unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - day * age_days)
+                .unwrap();
+        }
+
+        // A dry run counts what would go but deletes nothing.
+        assert_eq!(clean_old_logs(&instance, None, true, now), (3, 30));
+        assert_eq!(clean_old_logs(&instance, Some(7), true, now), (2, 20));
+        assert_eq!(fs::read_dir(&logs_dir).unwrap().count(), 5);
+
+        assert_eq!(clean_old_logs(&instance, Some(7), false, now), (2, 20));
+        assert!(logs_dir.join("latest.log").exists());
+        assert!(logs_dir.join("2024-01-03-1.log.gz").exists());
+        assert!(!logs_dir.join("2024-01-01-1.log.gz").exists());
+        assert!(!logs_dir.join("2024-01-02-1.log.gz").exists());
+        assert!(logs_dir.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn optimization_preview_leaves_the_instance_untouched() {
+        use crate::core::instance::InstanceManager;
+
+        let root = std::env::temp_dir().join(format!("optimize-preview-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let manager = InstanceManager::new(root.clone());
+        let mut instance = Instance::new(
+            "Preview".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            None,
+            2048,
+            &root,
+        );
+        instance.jvm_args = vec!["-XX:+UseZGC".into()];
+        let created = manager.create(instance).await.unwrap();
+        let logs_dir = created.game_dir().join("logs");
+        fs::create_dir_all(&logs_dir).unwrap();
+        fs::write(logs_dir.join("latest.log"), "log").unwrap();
+        let config_path = root.join(&created.id).join("instance.json");
+        let before = fs::read(&config_path).unwrap();
+
+        let options = OptimizeOptions {
+            mode: Some(OptimizationMode::MaxPerformance),
+            ..OptimizeOptions::default()
+        };
+        let stored = manager.load(&created.id).await.unwrap();
+        let (planned, report) =
+            preview_optimization(&stored, &options, 32 * 1024, 24 * 1024).unwrap();
+
+        assert_ne!(report.recommended_xmx_mb, 2048);
+        assert_eq!(planned.max_memory_mb, report.recommended_xmx_mb);
+        assert_eq!(report.removed_logs, 0);
+        assert_eq!(fs::read(&config_path).unwrap(), before);
+        assert!(logs_dir.join("latest.log").exists());
+        let stored = manager.load(&created.id).await.unwrap();
+        assert_eq!(stored.max_memory_mb, 2048);
+        assert_eq!(stored.jvm_args, vec!["-XX:+UseZGC".to_string()]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn mod_analysis_skips_disabled_jars() {
+        let temp =
+            std::env::temp_dir().join(format!("mod-analysis-disabled-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+
+        let instance = Instance::new(
+            "Modded".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            Some("0.15.7".into()),
+            4096,
+            &temp,
+        );
+        let mods_dir = instance.mods_dir();
+        fs::create_dir_all(&mods_dir).unwrap();
+        fs::write(mods_dir.join("sodium-fabric-0.5.3.jar"), b"mod").unwrap();
+        fs::write(mods_dir.join("lithium-fabric-0.11.2.jar"), b"mod").unwrap();
+        fs::write(mods_dir.join("sodium-fabric-0.5.8.jar"), b"mod").unwrap();
+
+        let (count, duplicates, ..) = collect_mod_analysis(&instance);
+        assert_eq!(count, 3);
+        assert_eq!(duplicates.len(), 1);
+
+        crate::core::mods::toggle::disable_mod(&mods_dir, "sodium-fabric-0.5.3.jar").unwrap();
+        let (count, duplicates, ..) = collect_mod_analysis(&instance);
+        assert_eq!(count, 2);
+        assert!(duplicates.is_empty());
+
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,182 @@
+// ─── Launch Phases ───
+// Typed timeline of a launch, reported as `instance-phase` events so the UI
+// can draw the phases and their timings without parsing `[FASE]` log lines.
+// Each phase emits `started` and then `finished` or `failed`; a phase still
+// open when the timeline is dropped (an early return) is reported as failed.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::core::events::EventEmitter;
+
+/// Event name of [`InstancePhaseEvent`].
+pub const INSTANCE_PHASE_EVENT: &str = "instance-phase";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchPhase {
+    Validation,
+    Preparation,
+    Bootstrap,
+    JarAnalysis,
+    Launch,
+    /// From the process spawn until it exits.
+    Running,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseStatus {
+    Started,
+    Finished,
+    Failed,
+}
+
+/// Payload of the `instance-phase` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstancePhaseEvent {
+    pub id: String,
+    pub phase: LaunchPhase,
+    pub status: PhaseStatus,
+    /// Time spent in the phase; 0 when it starts.
+    pub elapsed_ms: u64,
+    /// Time since the launch was requested.
+    pub since_start_ms: u64,
+}
+
+pub struct PhaseTimeline {
+    events: Arc<dyn EventEmitter>,
+    id: String,
+    started_at: Instant,
+    current: Option<(LaunchPhase, Instant)>,
+}
+
+impl PhaseTimeline {
+    pub fn new(events: Arc<dyn EventEmitter>, id: &str) -> Self {
+        Self {
+            events,
+            id: id.to_string(),
+            started_at: Instant::now(),
+            current: None,
+        }
+    }
+
+    /// Close the current phase as finished and start `phase`.
+    pub fn enter(&mut self, phase: LaunchPhase) {
+        self.close(PhaseStatus::Finished);
+        self.emit(phase, PhaseStatus::Started, 0);
+        self.current = Some((phase, Instant::now()));
+    }
+
+    /// Close the current phase as finished (`success`) or failed.
+    pub fn finish(&mut self, success: bool) {
+        self.close(if success {
+            PhaseStatus::Finished
+        } else {
+            PhaseStatus::Failed
+        });
+    }
+
+    fn close(&mut self, status: PhaseStatus) {
+        if let Some((phase, entered)) = self.current.take() {
+            self.emit(phase, status, entered.elapsed().as_millis() as u64);
+        }
+    }
+
+    fn emit(&self, phase: LaunchPhase, status: PhaseStatus, elapsed_ms: u64) {
+        self.events.emit(
+            INSTANCE_PHASE_EVENT,
+            InstancePhaseEvent {
+                id: self.id.clone(),
+                phase,
+                status,
+                elapsed_ms,
+                since_start_ms: self.started_at.elapsed().as_millis() as u64,
+            },
+        );
+    }
+}
+
+impl Drop for PhaseTimeline {
+    fn drop(&mut self) {
+        self.close(PhaseStatus::Failed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(std::sync::Mutex<Vec<(String, String)>>);
+
+    impl EventEmitter for Recorder {
+        fn emit_json(&self, event: &str, payload: serde_json::Value) {
+            assert_eq!(event, INSTANCE_PHASE_EVENT);
+            self.0.lock().unwrap().push((
+                payload["phase"].as_str().unwrap().to_string(),
+                payload["status"].as_str().unwrap().to_string(),
+            ));
+        }
+    }
+
+    fn recorded(recorder: &Recorder) -> Vec<(String, String)> {
+        recorder.0.lock().unwrap().clone()
+    }
+
+    fn pair(phase: &str, status: &str) -> (String, String) {
+        (phase.to_string(), status.to_string())
+    }
+
+    #[test]
+    fn successful_launch_starts_and_finishes_each_phase_in_order() {
+        let recorder = Arc::new(Recorder::default());
+        let mut timeline = PhaseTimeline::new(recorder.clone(), "abc");
+        for phase in [
+            LaunchPhase::Validation,
+            LaunchPhase::Preparation,
+            LaunchPhase::Bootstrap,
+            LaunchPhase::JarAnalysis,
+            LaunchPhase::Launch,
+            LaunchPhase::Running,
+        ] {
+            timeline.enter(phase);
+        }
+        timeline.finish(true);
+        drop(timeline);
+
+        let expected: Vec<_> = [
+            "validation",
+            "preparation",
+            "bootstrap",
+            "jar_analysis",
+            "launch",
+            "running",
+        ]
+        .iter()
+        .flat_map(|phase| [pair(phase, "started"), pair(phase, "finished")])
+        .collect();
+        assert_eq!(recorded(&recorder), expected);
+    }
+
+    #[test]
+    fn dropping_an_open_phase_reports_it_as_failed() {
+        let recorder = Arc::new(Recorder::default());
+        let mut timeline = PhaseTimeline::new(recorder.clone(), "abc");
+        timeline.enter(LaunchPhase::Validation);
+        timeline.enter(LaunchPhase::Preparation);
+        drop(timeline);
+
+        assert_eq!(
+            recorded(&recorder),
+            [
+                pair("validation", "started"),
+                pair("validation", "finished"),
+                pair("preparation", "started"),
+                pair("preparation", "failed"),
+            ]
+        );
+    }
+}
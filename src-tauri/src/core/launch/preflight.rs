@@ -0,0 +1,1310 @@
+// ─── Launch Preflight ───
+// Checks an instance before launch: loader / Java compatibility, the files
+// and placeholders the command line needs, and the bootstrap runtime. Failed
+// checks go through bounded repair passes before the launch gives up.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::process::Command;
+use std::{fs, path::Path};
+
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::{ErrorText, Locale, LogKey};
+use crate::core::install::prepare_instance_for_launch;
+use crate::core::instance::{Instance, InstanceState, LoaderType};
+use crate::core::java::{self, RuntimeRole};
+use crate::core::launch::events::emit_launch_message;
+use crate::core::loaders::versions::{
+    fetch_metadata, is_neoforge_compatible, parse_maven_metadata, sort_versions_desc,
+    FORGE_METADATA_KEY, FORGE_METADATA_URL, NEOFORGE_METADATA_KEY, NEOFORGE_METADATA_URL,
+};
+use crate::core::state::{AppState, JavaRuntimePreference, LauncherSettings};
+use crate::core::version::VersionJson;
+
+pub(crate) fn parse_numeric_version_parts(raw: &str) -> Vec<u32> {
+    raw.split(|c: char| !c.is_ascii_digit())
+        .filter(|segment| !segment.is_empty())
+        .filter_map(|segment| segment.parse::<u32>().ok())
+        .collect()
+}
+
+pub(crate) fn asm_version_supports_java_21(version: &str) -> bool {
+    let parts = parse_numeric_version_parts(version);
+    let major = parts.first().copied().unwrap_or(0);
+    let minor = parts.get(1).copied().unwrap_or(0);
+    major > 9 || (major == 9 && minor >= 7)
+}
+
+pub(crate) fn detect_loader_asm_incompatibility(
+    instance: &Instance,
+    target_java_major: u32,
+) -> Option<String> {
+    if target_java_major < 21 {
+        return None;
+    }
+
+    if !matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge) {
+        return None;
+    }
+
+    let asm_versions: Vec<String> = instance
+        .libraries
+        .iter()
+        .filter_map(|coord| crate::core::maven::MavenArtifact::parse(coord).ok())
+        .filter(|artifact| artifact.group_id == "org.ow2.asm")
+        .map(|artifact| artifact.version)
+        .collect();
+
+    if asm_versions.is_empty() {
+        return None;
+    }
+
+    let has_valid_asm = asm_versions
+        .iter()
+        .any(|version| asm_version_supports_java_21(version));
+
+    if has_valid_asm {
+        return None;
+    }
+
+    Some(asm_versions.join(", "))
+}
+
+pub(crate) fn should_force_loader_upgrade_for_java21(
+    instance: &Instance,
+    target_java_major: u32,
+) -> bool {
+    detect_loader_asm_incompatibility(instance, target_java_major).is_some()
+        && matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge)
+}
+
+pub(crate) async fn resolve_bootstrap_java_major(
+    state: &crate::core::state::AppState,
+    instance: &Instance,
+    fallback_major: u32,
+) -> Result<u32, LauncherError> {
+    if instance.bootstrap_runtime == RuntimeRole::Gamma {
+        return Ok(fallback_major);
+    }
+
+    let runtime_path = java::resolve_runtime_in_dir(
+        &state.data_dir,
+        RuntimeRole::Delta,
+        RuntimeRole::Delta.expected_major(Some(&instance.minecraft_version)),
+        Some(&instance.minecraft_version),
+        state.runtime_options(),
+    )
+    .await?;
+
+    let detected_major = java::runtime::inspect_java_binary(&runtime_path)
+        .map(|runtime| runtime.major)
+        .unwrap_or_else(|| RuntimeRole::Delta.expected_major(Some(&instance.minecraft_version)));
+
+    Ok(detected_major)
+}
+
+pub(crate) async fn validate_instance_state_before_launch(
+    _state: &crate::core::state::AppState,
+    instance: &Instance,
+) -> Result<(), LauncherError> {
+    if instance.state != InstanceState::Ready && instance.state != InstanceState::Error {
+        return Err(LauncherError::InstanceNotReady {
+            id: instance.id.clone(),
+            state: format!("{:?}", instance.state),
+        });
+    }
+
+    if instance.needs_recovery {
+        return Err(LauncherError::message(
+            ErrorText::CorruptInstanceNeedsRecovery,
+            &[],
+        ));
+    }
+
+    if instance.main_class.is_none() {
+        return Err(LauncherError::message(ErrorText::MainClassMissing, &[]));
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn validate_or_resolve_java(
+    state: &crate::core::state::AppState,
+    instance: &mut Instance,
+) -> Result<(), LauncherError> {
+    let required_major = instance
+        .required_java_major
+        .unwrap_or_else(|| java::required_java_for_minecraft_version(&instance.minecraft_version));
+
+    let allow_newer_major = state.launcher_settings.allow_newer_java_major;
+    let loader = instance.loader.clone();
+    let is_valid = |candidate: &std::path::PathBuf| {
+        java::runtime::inspect_java_binary(candidate).is_some_and(|info| {
+            java::is_java_compatible_for_loader(
+                info.major,
+                required_major,
+                &loader,
+                allow_newer_major,
+            ) && info.is_64bit
+        })
+    };
+
+    if let Some(identifier) = instance.pinned_runtime_identifier.clone() {
+        match java::pinned_runtime_in_dir(
+            &state.data_dir,
+            &identifier,
+            required_major,
+            state.runtime_options(),
+        )
+        .await?
+        {
+            Some((role, pinned)) => {
+                instance.java_path = Some(pinned);
+                if !instance.loader_requires_delta {
+                    instance.bootstrap_runtime = role;
+                }
+                instance.game_runtime = role;
+                return Ok(());
+            }
+            None => warn!(
+                "Pinned runtime {} for instance {} is missing or incompatible with Java {}; resolving automatically",
+                identifier, instance.id, required_major
+            ),
+        }
+    }
+
+    if let Some(custom_path) = state.launcher_settings.selected_java_path.as_ref() {
+        if is_valid(custom_path) {
+            instance.java_path = Some(custom_path.clone());
+            if !instance.loader_requires_delta {
+                instance.bootstrap_runtime = RuntimeRole::Gamma;
+            }
+            instance.game_runtime = RuntimeRole::Gamma;
+            return Ok(());
+        }
+    }
+
+    match state.launcher_settings.java_runtime {
+        JavaRuntimePreference::System => {
+            let system_java = std::path::PathBuf::from("java");
+            if is_valid(&system_java) {
+                instance.java_path = Some(system_java);
+                if !instance.loader_requires_delta {
+                    instance.bootstrap_runtime = RuntimeRole::Gamma;
+                }
+                instance.game_runtime = RuntimeRole::Gamma;
+                return Ok(());
+            }
+            return Err(LauncherError::message(ErrorText::SystemJavaNotFound, &[]));
+        }
+        JavaRuntimePreference::Embedded => {
+            let embedded_java = state.embedded_java_path();
+            if is_valid(&embedded_java) {
+                instance.java_path = Some(embedded_java);
+                if !instance.loader_requires_delta {
+                    instance.bootstrap_runtime = RuntimeRole::Gamma;
+                }
+                instance.game_runtime = RuntimeRole::Gamma;
+                return Ok(());
+            }
+        }
+        JavaRuntimePreference::Auto => {}
+    }
+
+    let resolved = java::resolve_runtime_in_dir(
+        &state.data_dir,
+        java::RuntimeRole::Gamma,
+        required_major,
+        Some(&instance.minecraft_version),
+        state.runtime_options(),
+    )
+    .await?;
+    instance.java_path = Some(resolved);
+    if !instance.loader_requires_delta {
+        instance.bootstrap_runtime = RuntimeRole::Gamma;
+    }
+    instance.game_runtime = RuntimeRole::Gamma;
+    Ok(())
+}
+
+/// One line of the preflight checklist; `args` fill the placeholders of
+/// `check` after its ✅ / ❌ mark.
+pub(crate) fn log_preflight_check(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    instance_id: &str,
+    ok: bool,
+    check: LogKey,
+    args: &[&dyn std::fmt::Display],
+) {
+    let (mark, level) = if ok {
+        ("✅", "info")
+    } else {
+        ("❌", "error")
+    };
+    let args: Vec<&dyn std::fmt::Display> = std::iter::once(&mark as &dyn std::fmt::Display)
+        .chain(args.iter().copied())
+        .collect();
+    emit_launch_message(events, locale, instance_id, level, check, &args);
+}
+
+pub(crate) fn collect_placeholders(arg: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut rest = arg;
+
+    while let Some(start) = rest.find("${") {
+        let after_start = &rest[start + 2..];
+        if let Some(end) = after_start.find('}') {
+            placeholders.push(format!("${{{}}}", &after_start[..end]));
+            rest = &after_start[end + 1..];
+        } else {
+            break;
+        }
+    }
+
+    placeholders
+}
+
+pub(crate) fn unresolved_placeholders(
+    args: &[String],
+    known: &HashSet<&'static str>,
+) -> Vec<String> {
+    let mut unresolved = HashSet::new();
+
+    for arg in args {
+        for token in collect_placeholders(arg) {
+            if !known.contains(token.as_str()) {
+                unresolved.insert(token);
+            }
+        }
+    }
+
+    let mut unresolved: Vec<_> = unresolved.into_iter().collect();
+    unresolved.sort();
+    unresolved
+}
+
+/// Libraries checked per blocking task in `missing_library_coords`.
+pub(crate) const LIBRARY_CHECK_CHUNK: usize = 256;
+
+/// Coordinates in `coords` whose Maven artifact is not present under
+/// `libs_dir`, in input order. The filesystem checks run on the blocking
+/// pool in parallel chunks so slow (e.g. network-mounted) data dirs do not
+/// stall the async runtime. Unparseable coordinates are ignored.
+pub(crate) async fn missing_library_coords(
+    libs_dir: &Path,
+    coords: Vec<String>,
+) -> Result<Vec<String>, LauncherError> {
+    let tasks: Vec<_> = coords
+        .chunks(LIBRARY_CHECK_CHUNK)
+        .map(|chunk| {
+            let libs_dir = libs_dir.to_path_buf();
+            let chunk = chunk.to_vec();
+            tokio::task::spawn_blocking(move || {
+                chunk
+                    .into_iter()
+                    .filter(|coord| {
+                        crate::core::maven::MavenArtifact::parse(coord)
+                            .is_ok_and(|artifact| !libs_dir.join(artifact.local_path()).exists())
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut missing = Vec::new();
+    for task in tasks {
+        missing.extend(
+            task.await
+                .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))?,
+        );
+    }
+    Ok(missing)
+}
+
+/// State of `client.jar` against the SHA-1 in the saved version JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClientJarCheck {
+    Missing,
+    /// Matches the expected SHA-1; carries the size in bytes.
+    Verified(u64),
+    /// No version JSON or client hash to compare with, and not empty.
+    Unverified(u64),
+    /// Wrong SHA-1, or empty when there is nothing to compare with.
+    Corrupt(u64),
+}
+
+pub(crate) async fn check_client_jar(instance: &Instance) -> ClientJarCheck {
+    let client_jar = instance.client_jar_path();
+    let Ok(metadata) = tokio::fs::metadata(&client_jar).await else {
+        return ClientJarCheck::Missing;
+    };
+    if !metadata.is_file() {
+        return ClientJarCheck::Missing;
+    }
+    let size = metadata.len();
+
+    let version_json_path = instance
+        .runtime_root_dir()
+        .join(format!("{}.json", instance.minecraft_version));
+    let expected_sha1 = tokio::fs::read_to_string(&version_json_path)
+        .await
+        .ok()
+        .and_then(|raw| serde_json::from_str::<VersionJson>(&raw).ok())
+        .and_then(|version_json| version_json.downloads?.client.map(|client| client.sha1));
+
+    match expected_sha1 {
+        Some(sha1) => {
+            match crate::core::downloader::Downloader::validate_sha1(&client_jar, &sha1).await {
+                Ok(true) => ClientJarCheck::Verified(size),
+                _ => ClientJarCheck::Corrupt(size),
+            }
+        }
+        None if size == 0 => ClientJarCheck::Corrupt(size),
+        None => ClientJarCheck::Unverified(size),
+    }
+}
+
+pub(crate) async fn verify_instance_runtime_readiness(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &Instance,
+    libs_dir: &Path,
+) -> Result<Vec<PreflightFailure>, LauncherError> {
+    let instance_id = instance.id.as_str();
+    let locale = state.launcher_settings.language;
+
+    let instance_dir_ok = instance.path.is_dir();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        instance_dir_ok,
+        LogKey::CheckInstanceDir,
+        &[&instance.path.display()],
+    );
+
+    let game_dir = instance.game_dir();
+    let game_dir_ok = game_dir.is_dir();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        game_dir_ok,
+        LogKey::CheckGameDir,
+        &[&game_dir.display()],
+    );
+
+    let assets_dir = game_dir.join("assets");
+    let assets_ok = assets_dir.is_dir();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        assets_ok,
+        LogKey::CheckAssetsDir,
+        &[&assets_dir.display()],
+    );
+
+    let client_jar = instance.client_jar_path();
+    let client_jar_check = check_client_jar(instance).await;
+    let client_jar_ok = client_jar_check != ClientJarCheck::Missing;
+    let client_jar_corrupted = matches!(client_jar_check, ClientJarCheck::Corrupt(_));
+    let client_jar_ok_and_intact = client_jar_ok && !client_jar_corrupted;
+    match client_jar_check {
+        ClientJarCheck::Verified(size) => log_preflight_check(
+            events,
+            locale,
+            instance_id,
+            client_jar_ok_and_intact,
+            LogKey::CheckClientJarVerified,
+            &[&size, &client_jar.display()],
+        ),
+        ClientJarCheck::Unverified(size) => log_preflight_check(
+            events,
+            locale,
+            instance_id,
+            client_jar_ok_and_intact,
+            LogKey::CheckClientJarUnverified,
+            &[&size, &client_jar.display()],
+        ),
+        ClientJarCheck::Corrupt(size) => log_preflight_check(
+            events,
+            locale,
+            instance_id,
+            client_jar_ok_and_intact,
+            LogKey::CheckClientJarCorrupt,
+            &[&size, &client_jar.display()],
+        ),
+        ClientJarCheck::Missing => log_preflight_check(
+            events,
+            locale,
+            instance_id,
+            client_jar_ok_and_intact,
+            LogKey::CheckClientJarMissing,
+            &[&client_jar.display()],
+        ),
+    }
+
+    let loader_ok = matches!(instance.loader, LoaderType::Vanilla)
+        || instance
+            .loader_version
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty());
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        loader_ok,
+        LogKey::CheckLoader,
+        &[
+            &format!("{:?}", instance.loader),
+            &format!("{:?}", instance.loader_version),
+        ],
+    );
+
+    let main_class_ok = instance.main_class.is_some();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        main_class_ok,
+        LogKey::CheckMainClass,
+        &[&format!("{:?}", instance.main_class)],
+    );
+
+    let asset_index_ok = asset_index_ready(instance);
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        asset_index_ok,
+        LogKey::CheckAssetIndex,
+        &[&format!("{:?}", instance.asset_index)],
+    );
+
+    let java_path = instance
+        .java_path
+        .as_ref()
+        .ok_or_else(|| LauncherError::message(ErrorText::NoJavaAssigned, &[]))?;
+    let java_exists = java_path.is_file();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        java_exists,
+        LogKey::CheckJavaBinary,
+        &[&java_path.display()],
+    );
+
+    let required_major = instance
+        .required_java_major
+        .unwrap_or_else(|| java::required_java_for_minecraft_version(&instance.minecraft_version));
+
+    let bootstrap_java_major =
+        resolve_bootstrap_java_major(state, instance, required_major).await?;
+    let target_java_major = required_major.max(bootstrap_java_major);
+    let loader_java_compat_issue = detect_loader_asm_incompatibility(instance, target_java_major);
+    let loader_java_ok = loader_java_compat_issue.is_none();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        loader_java_ok,
+        LogKey::CheckLoaderJava,
+        &[
+            &format!("{:?}", instance.bootstrap_runtime),
+            &target_java_major,
+            &bootstrap_java_major,
+        ],
+    );
+    if let Some(asm_versions) = loader_java_compat_issue {
+        emit_launch_message(
+            events,
+            locale,
+            instance_id,
+            "error",
+            LogKey::CheckLoaderAsmTooOld,
+            &[
+                &target_java_major,
+                &asm_versions,
+                &format!("{:?}", instance.loader),
+                &instance.minecraft_version,
+            ],
+        );
+        emit_launch_message(
+            events,
+            locale,
+            instance_id,
+            "warn",
+            LogKey::CheckLoaderNeedsDelta,
+            &[],
+        );
+    }
+
+    // Verificar el binario asignado directamente evita falsos negativos cuando
+    // la ruta no coincide exactamente con entradas indexadas/canonizadas.
+    let java_info = java::runtime::inspect_java_binary(java_path);
+    let detected_java_major = java_info.as_ref().map(|candidate| candidate.major);
+    let java_major_ok = detected_java_major.is_some_and(|major| {
+        java::is_java_compatible_for_loader(
+            major,
+            required_major,
+            &instance.loader,
+            state.launcher_settings.allow_newer_java_major,
+        )
+    });
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        java_major_ok,
+        LogKey::CheckJavaVersion,
+        &[
+            &instance.minecraft_version,
+            &required_major,
+            &format!("{detected_java_major:?}"),
+        ],
+    );
+
+    let java_64_ok = java_info
+        .as_ref()
+        .is_some_and(|candidate| candidate.is_64bit);
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        java_64_ok,
+        LogKey::CheckJava64,
+        &[],
+    );
+
+    let known_jvm_placeholders = HashSet::from([
+        "${natives_directory}",
+        "${library_directory}",
+        "${classpath}",
+        "${classpath_separator}",
+        "${game_directory}",
+        "${version_name}",
+        "${version}",
+        "${mc_version}",
+        "${launcher_name}",
+        "${launcher_version}",
+    ]);
+    let known_game_placeholders = HashSet::from([
+        "${auth_player_name}",
+        "${version_name}",
+        "${version}",
+        "${mc_version}",
+        "${game_directory}",
+        "${assets_root}",
+        "${assets_index_name}",
+        "${auth_uuid}",
+        "${auth_access_token}",
+        "${auth_xuid}",
+        "${clientid}",
+        "${user_properties}",
+        "${user_type}",
+        "${version_type}",
+        "${quickPlayMultiplayer}",
+        "${quickPlaySingleplayer}",
+        "${quickPlayRealms}",
+        "${quickPlayPath}",
+        "${resolution_width}",
+        "${resolution_height}",
+    ]);
+
+    let unresolved_jvm = unresolved_placeholders(&instance.jvm_args, &known_jvm_placeholders);
+    let unresolved_game = unresolved_placeholders(&instance.game_args, &known_game_placeholders);
+    let args_ok = unresolved_jvm.is_empty() && unresolved_game.is_empty();
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        args_ok,
+        LogKey::CheckArgs,
+        &[&unresolved_jvm.is_empty(), &unresolved_game.is_empty()],
+    );
+    if !unresolved_jvm.is_empty() || !unresolved_game.is_empty() {
+        emit_launch_message(
+            events,
+            locale,
+            instance_id,
+            "error",
+            LogKey::CheckUnresolvedPlaceholders,
+            &[
+                &format!("{unresolved_jvm:?}"),
+                &format!("{unresolved_game:?}"),
+            ],
+        );
+    }
+
+    let missing_maven_artifacts = missing_library_coords(libs_dir, instance.libraries.clone())
+        .await?
+        .len();
+    let maven_ok = missing_maven_artifacts == 0;
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        maven_ok,
+        LogKey::CheckMaven,
+        &[&missing_maven_artifacts],
+    );
+
+    let external_mod_jars = fs::read_dir(instance.mods_dir())
+        .ok()
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().extension() == Some(OsStr::new("jar")))
+                .count()
+        })
+        .unwrap_or(0);
+    log_preflight_check(
+        events,
+        locale,
+        instance_id,
+        true,
+        LogKey::CheckExtraMods,
+        &[&external_mod_jars],
+    );
+
+    let mut failures = Vec::new();
+
+    if !java_exists {
+        failures.push(PreflightFailure::MissingJava);
+    }
+    if java_exists && (!java_major_ok || !java_64_ok) {
+        failures.push(PreflightFailure::WrongJavaVersion);
+    }
+    if !instance_dir_ok || !game_dir_ok || !assets_ok || !client_jar_ok {
+        failures.push(PreflightFailure::MissingStructure);
+    }
+    if !maven_ok {
+        failures.push(PreflightFailure::MissingLibraries);
+    }
+    if client_jar_corrupted {
+        failures.push(PreflightFailure::CorruptedFiles);
+    }
+    if !loader_ok || !main_class_ok {
+        failures.push(PreflightFailure::InvalidLoader);
+    }
+    if !loader_java_ok {
+        failures.push(PreflightFailure::IncompatibleLoaderJava);
+    }
+    if !asset_index_ok {
+        failures.push(PreflightFailure::MissingAssetIndex);
+    }
+    if !args_ok {
+        failures.push(PreflightFailure::Unknown);
+    }
+
+    Ok(failures)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PreflightFailure {
+    MissingJava,
+    WrongJavaVersion,
+    MissingStructure,
+    MissingLibraries,
+    CorruptedFiles,
+    InvalidLoader,
+    IncompatibleLoaderJava,
+    MissingAssetIndex,
+    Unknown,
+}
+
+impl PreflightFailure {
+    fn label(self) -> &'static str {
+        match self {
+            PreflightFailure::MissingJava => "MissingJava",
+            PreflightFailure::WrongJavaVersion => "WrongJavaVersion",
+            PreflightFailure::MissingStructure => "MissingStructure",
+            PreflightFailure::MissingLibraries => "MissingLibraries",
+            PreflightFailure::CorruptedFiles => "CorruptedFiles",
+            PreflightFailure::InvalidLoader => "InvalidLoader",
+            PreflightFailure::IncompatibleLoaderJava => "IncompatibleLoaderJava",
+            PreflightFailure::MissingAssetIndex => "MissingAssetIndex",
+            PreflightFailure::Unknown => "Unknown",
+        }
+    }
+}
+
+/// One automatic repair pass over the preflight failures.
+#[async_trait::async_trait]
+pub(crate) trait RepairPass: Send {
+    /// Returns `true` when the instance passes preflight afterwards.
+    async fn run(&mut self, attempt: u32) -> Result<bool, LauncherError>;
+}
+
+/// Repairs the failures, then re-runs the preflight checklist.
+pub(crate) struct LaunchRepairPass<'a> {
+    pub(crate) events: &'a dyn EventEmitter,
+    pub(crate) state: &'a AppState,
+    pub(crate) instance: &'a mut Instance,
+    pub(crate) failures: &'a mut Vec<PreflightFailure>,
+    pub(crate) libs_dir: &'a Path,
+    pub(crate) cancel: &'a CancellationToken,
+    pub(crate) max_attempts: u32,
+}
+
+#[async_trait::async_trait]
+impl RepairPass for LaunchRepairPass<'_> {
+    async fn run(&mut self, attempt: u32) -> Result<bool, LauncherError> {
+        emit_launch_message(
+            self.events,
+            self.state.launcher_settings.language,
+            &self.instance.id,
+            "info",
+            LogKey::RepairAttempt,
+            &[&attempt, &self.max_attempts],
+        );
+        attempt_preflight_repair(
+            self.events,
+            self.state,
+            self.instance,
+            self.failures,
+            self.cancel,
+        )
+        .await?;
+        *self.failures = verify_instance_runtime_readiness(
+            self.events,
+            self.state,
+            self.instance,
+            self.libs_dir,
+        )
+        .await?;
+        Ok(self.failures.is_empty())
+    }
+}
+
+/// Run up to `max_attempts` repair passes, stopping at the first one that
+/// leaves the instance healthy. Returns whether any pass succeeded.
+pub(crate) async fn repair_with_retries(
+    max_attempts: u32,
+    pass: &mut dyn RepairPass,
+) -> Result<bool, LauncherError> {
+    for attempt in 1..=max_attempts {
+        if pass.run(attempt).await? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Versions with their own asset index must launch with it; only versions
+/// up to 1.7.2 may fall back to the shared `legacy` index.
+pub(crate) fn asset_index_ready(instance: &Instance) -> bool {
+    instance.asset_index.is_some()
+        || crate::core::assets::uses_legacy_asset_index(&instance.minecraft_version)
+}
+
+pub(crate) fn preflight_error(failures: &[PreflightFailure]) -> LauncherError {
+    LauncherError::PreflightFailed {
+        failures: failures
+            .iter()
+            .map(|failure| failure.label().to_string())
+            .collect(),
+    }
+}
+
+pub(crate) fn has_loader_java_incompatibility(failures: &[PreflightFailure]) -> bool {
+    failures
+        .iter()
+        .any(|failure| matches!(failure, PreflightFailure::IncompatibleLoaderJava))
+}
+
+pub(crate) fn user_forced_gamma_only(settings: &LauncherSettings, _instance: &Instance) -> bool {
+    settings
+        .selected_java_path
+        .as_ref()
+        .and_then(|path| path.to_str())
+        .is_some_and(|path| path.to_ascii_lowercase().contains("java-gamma"))
+}
+
+pub(crate) async fn cleanup_loader_and_runtime_artifacts(
+    state: &crate::core::state::AppState,
+    instance: &mut Instance,
+) -> Result<(), LauncherError> {
+    let libs_dir = state.libraries_dir();
+    for coord in &instance.libraries {
+        let Ok(artifact) = crate::core::maven::MavenArtifact::parse(coord) else {
+            continue;
+        };
+
+        let remove_artifact = artifact.group_id == "org.ow2.asm"
+            || (matches!(instance.loader, LoaderType::NeoForge)
+                && artifact.group_id.starts_with("net.neoforged"))
+            || (matches!(instance.loader, LoaderType::Forge)
+                && artifact.group_id.starts_with("net.minecraftforge"));
+        if !remove_artifact {
+            continue;
+        }
+
+        let artifact_path = libs_dir.join(artifact.local_path());
+        if artifact_path.exists() {
+            let _ = tokio::fs::remove_file(&artifact_path).await;
+        }
+    }
+
+    let loader_cache_root = match instance.loader {
+        LoaderType::NeoForge => libs_dir.join("net").join("neoforged"),
+        LoaderType::Forge => libs_dir.join("net").join("minecraftforge"),
+        _ => libs_dir.join("__no_loader_cache__"),
+    };
+    if loader_cache_root.exists() {
+        let _ = tokio::fs::remove_dir_all(&loader_cache_root).await;
+    }
+
+    let client_jar = instance.client_jar_path();
+    if client_jar.exists() {
+        let _ = tokio::fs::remove_file(&client_jar).await;
+    }
+
+    instance.main_class = None;
+    instance.libraries.clear();
+    instance.resolved_libraries.clear();
+    instance.jvm_args.clear();
+    instance.game_args.clear();
+
+    Ok(())
+}
+
+pub(crate) async fn recommend_latest_loader_version(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &Instance,
+) -> Result<Option<String>, LauncherError> {
+    let Some(current_version) = instance.loader_version.as_ref() else {
+        return Ok(None);
+    };
+
+    let (key, url) = match instance.loader {
+        LoaderType::NeoForge => (NEOFORGE_METADATA_KEY, NEOFORGE_METADATA_URL),
+        LoaderType::Forge => (FORGE_METADATA_KEY, FORGE_METADATA_URL),
+        _ => return Ok(None),
+    };
+
+    let metadata = fetch_metadata(
+        events,
+        &state.metadata_cache(),
+        &state.http_client,
+        key,
+        url,
+        parse_maven_metadata,
+    )
+    .await?;
+
+    let mut versions: Vec<String> = match instance.loader {
+        LoaderType::NeoForge => metadata
+            .versioning
+            .versions
+            .version
+            .into_iter()
+            .filter(|v| is_neoforge_compatible(v, &instance.minecraft_version))
+            .collect(),
+        LoaderType::Forge => metadata
+            .versioning
+            .versions
+            .version
+            .into_iter()
+            .filter_map(|v| {
+                v.strip_prefix(&format!("{}-", instance.minecraft_version))
+                    .map(str::to_owned)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    sort_versions_desc(&mut versions);
+
+    let Some(recommended) = versions.into_iter().next() else {
+        return Ok(None);
+    };
+
+    if &recommended == current_version {
+        return Ok(None);
+    }
+
+    Ok(Some(recommended))
+}
+
+pub(crate) async fn attempt_preflight_repair(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &mut Instance,
+    failures: &[PreflightFailure],
+    cancel: &CancellationToken,
+) -> Result<(), LauncherError> {
+    let labels = failures
+        .iter()
+        .map(|failure| failure.label())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let locale = state.launcher_settings.language;
+    emit_launch_message(
+        events,
+        locale,
+        &instance.id,
+        "warn",
+        LogKey::RepairClassified,
+        &[&labels],
+    );
+
+    let mut needs_prepare = false;
+    let mut force_full_prepare = false;
+
+    for failure in failures {
+        match failure {
+            PreflightFailure::MissingJava | PreflightFailure::WrongJavaVersion => {
+                emit_launch_message(
+                    events,
+                    locale,
+                    &instance.id,
+                    "info",
+                    LogKey::RepairResolvingJava,
+                    &[],
+                );
+                validate_or_resolve_java(state, instance).await?;
+            }
+            PreflightFailure::MissingStructure | PreflightFailure::MissingLibraries => {
+                needs_prepare = true;
+            }
+            PreflightFailure::CorruptedFiles => {
+                let client_jar = instance.client_jar_path();
+                if client_jar.exists() {
+                    let _ = tokio::fs::remove_file(&client_jar).await;
+                }
+                force_full_prepare = true;
+            }
+            PreflightFailure::InvalidLoader | PreflightFailure::MissingAssetIndex => {
+                force_full_prepare = true;
+            }
+            PreflightFailure::IncompatibleLoaderJava => {
+                emit_launch_message(
+                    events,
+                    locale,
+                    &instance.id,
+                    "info",
+                    LogKey::RepairLoaderToolingJava,
+                    &[],
+                );
+                instance.loader_requires_delta = true;
+                instance.bootstrap_runtime = RuntimeRole::Delta;
+                instance.game_runtime = RuntimeRole::Gamma;
+                let delta_runtime = java::resolve_runtime_in_dir(
+                    &state.data_dir,
+                    RuntimeRole::Delta,
+                    RuntimeRole::Delta.expected_major(Some(&instance.minecraft_version)),
+                    Some(&instance.minecraft_version),
+                    state.runtime_options(),
+                )
+                .await?;
+                emit_launch_message(
+                    events,
+                    locale,
+                    &instance.id,
+                    "info",
+                    LogKey::RepairPhaseRuntimes,
+                    &[&delta_runtime.display()],
+                );
+
+                if should_force_loader_upgrade_for_java21(
+                    instance,
+                    instance.required_java_major.unwrap_or_else(|| {
+                        java::required_java_for_minecraft_version(&instance.minecraft_version)
+                    }),
+                ) {
+                    emit_launch_message(
+                        events,
+                        locale,
+                        &instance.id,
+                        "warn",
+                        LogKey::RepairPurgingOldAsm,
+                        &[],
+                    );
+
+                    if let Some(recommended_version) =
+                        recommend_latest_loader_version(events, state, instance).await?
+                    {
+                        emit_launch_message(
+                            events,
+                            locale,
+                            &instance.id,
+                            "info",
+                            LogKey::RepairUpdatingLoader,
+                            &[
+                                &instance.loader,
+                                &format!("{:?}", instance.loader_version),
+                                &recommended_version,
+                            ],
+                        );
+                        instance.loader_version = Some(recommended_version);
+                    }
+
+                    cleanup_loader_and_runtime_artifacts(state, instance).await?;
+                    force_full_prepare = true;
+                }
+            }
+            PreflightFailure::Unknown => {
+                needs_prepare = true;
+            }
+        }
+    }
+
+    if force_full_prepare {
+        instance.main_class = None;
+        needs_prepare = true;
+    }
+
+    if needs_prepare {
+        emit_launch_message(
+            events,
+            locale,
+            &instance.id,
+            "info",
+            LogKey::RepairRetryingPhase,
+            &[],
+        );
+        prepare_instance_for_launch(events, state, instance, cancel, None).await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn run_bootstrap_runtime_probe(
+    events: &dyn EventEmitter,
+    state: &crate::core::state::AppState,
+    instance: &Instance,
+) -> Result<(), LauncherError> {
+    let runtime_role = instance.bootstrap_runtime;
+    let runtime_path = match runtime_role {
+        RuntimeRole::Gamma => instance
+            .java_path
+            .clone()
+            .ok_or_else(|| LauncherError::message(ErrorText::NoGammaJavaAssigned, &[]))?,
+        RuntimeRole::Delta => {
+            java::resolve_runtime_in_dir(
+                &state.data_dir,
+                RuntimeRole::Delta,
+                RuntimeRole::Delta.expected_major(Some(&instance.minecraft_version)),
+                Some(&instance.minecraft_version),
+                state.runtime_options(),
+            )
+            .await?
+        }
+    };
+
+    let java_home = runtime_path
+        .parent()
+        .and_then(|bin| bin.parent())
+        .ok_or_else(|| LauncherError::message(ErrorText::JavaHomeUnresolved, &[]))?;
+
+    emit_launch_message(
+        events,
+        state.launcher_settings.language,
+        &instance.id,
+        "info",
+        LogKey::BootstrapRuntime,
+        &[
+            &format!("{runtime_role:?}"),
+            &runtime_path.display(),
+            &java_home.display(),
+        ],
+    );
+
+    let output = Command::new(&runtime_path)
+        .arg("-version")
+        .env("JAVA_HOME", java_home)
+        .output()
+        .map_err(|source| LauncherError::Io {
+            path: runtime_path.clone(),
+            source,
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LauncherError::message(
+            ErrorText::InvalidBootstrapRuntime,
+            &[&format!("{:?}", runtime_role), &stderr],
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asm_java21_threshold_requires_9_7_or_newer() {
+        assert!(asm_version_supports_java_21("9.7"));
+        assert!(asm_version_supports_java_21("9.7.1"));
+        assert!(!asm_version_supports_java_21("9.6"));
+        assert!(!asm_version_supports_java_21("9.6.1"));
+    }
+
+    #[test]
+    fn preflight_requires_an_asset_index_for_modern_versions() {
+        let mut instance = Instance::new(
+            "Assets".into(),
+            "1.20".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        assert!(!asset_index_ready(&instance));
+        instance.asset_index = Some("5".into());
+        assert!(asset_index_ready(&instance));
+
+        instance.minecraft_version = "1.7.2".into();
+        instance.asset_index = None;
+        assert!(asset_index_ready(&instance));
+    }
+
+    #[test]
+    fn parse_numeric_version_parts_ignores_suffixes() {
+        assert_eq!(parse_numeric_version_parts("9.7"), vec![9, 7]);
+        assert_eq!(parse_numeric_version_parts("9.6.1-neoforge"), vec![9, 6, 1]);
+    }
+
+    #[test]
+    fn detects_loader_asm_issue_for_java21_modded_instance() {
+        let mut instance = Instance::new(
+            "Test".into(),
+            "1.20.6".into(),
+            LoaderType::NeoForge,
+            Some("20.6.139".into()),
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        instance.libraries = vec![
+            "org.ow2.asm:asm:9.6".into(),
+            "org.ow2.asm:asm-tree:9.6".into(),
+        ];
+
+        let issue = detect_loader_asm_incompatibility(&instance, 21);
+        assert!(issue.is_some());
+    }
+
+    #[tokio::test]
+    async fn client_jar_check_compares_against_version_json_sha1() {
+        use sha1::{Digest, Sha1};
+
+        let temp = std::env::temp_dir().join(format!("client-jar-check-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let instance = Instance::new(
+            "Jar".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &temp,
+        );
+        std::fs::create_dir_all(instance.runtime_root_dir()).unwrap();
+        assert_eq!(check_client_jar(&instance).await, ClientJarCheck::Missing);
+
+        std::fs::write(instance.client_jar_path(), b"PK client").unwrap();
+        assert_eq!(
+            check_client_jar(&instance).await,
+            ClientJarCheck::Unverified(9)
+        );
+
+        let version_json = serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "downloads": { "client": {
+                "sha1": hex::encode(Sha1::digest(b"PK client")),
+                "size": 9,
+                "url": "https://example.com/client.jar"
+            }}
+        });
+        std::fs::write(
+            instance.runtime_root_dir().join("1.20.1.json"),
+            version_json.to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            check_client_jar(&instance).await,
+            ClientJarCheck::Verified(9)
+        );
+
+        // Same length, one byte flipped.
+        std::fs::write(instance.client_jar_path(), b"PK clienT").unwrap();
+        assert_eq!(
+            check_client_jar(&instance).await,
+            ClientJarCheck::Corrupt(9)
+        );
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    /// Fails until `healthy_on`, recording every attempt.
+    struct ScriptedRepair {
+        calls: Vec<u32>,
+        healthy_on: Option<u32>,
+    }
+
+    #[async_trait::async_trait]
+    impl RepairPass for ScriptedRepair {
+        async fn run(&mut self, attempt: u32) -> Result<bool, LauncherError> {
+            self.calls.push(attempt);
+            Ok(self.healthy_on == Some(attempt))
+        }
+    }
+
+    #[tokio::test]
+    async fn repair_loop_honours_the_attempt_limit() {
+        for max_attempts in [0, 1, 3] {
+            let mut pass = ScriptedRepair {
+                calls: Vec::new(),
+                healthy_on: None,
+            };
+            let repaired = repair_with_retries(max_attempts, &mut pass).await.unwrap();
+            assert!(!repaired);
+            assert_eq!(pass.calls, (1..=max_attempts).collect::<Vec<_>>());
+        }
+
+        let mut pass = ScriptedRepair {
+            calls: Vec::new(),
+            healthy_on: Some(2),
+        };
+        assert!(repair_with_retries(3, &mut pass).await.unwrap());
+        assert_eq!(pass.calls, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn missing_library_check_handles_thousands_of_coords() {
+        let libs_dir = std::env::temp_dir().join(format!("library-check-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&libs_dir);
+
+        let coords: Vec<String> = (0..4000)
+            .map(|i| format!("com.example:lib{i}:1.0"))
+            .collect();
+        let mut expected_missing = Vec::new();
+        for (i, coord) in coords.iter().enumerate() {
+            if i % 7 == 0 {
+                expected_missing.push(coord.clone());
+                continue;
+            }
+            let jar = libs_dir.join(format!("com/example/lib{i}/1.0/lib{i}-1.0.jar"));
+            std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+            std::fs::write(jar, b"").unwrap();
+        }
+
+        let started = std::time::Instant::now();
+        let missing = missing_library_coords(&libs_dir, coords).await.unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(missing, expected_missing);
+
+        let _ = std::fs::remove_dir_all(&libs_dir);
+    }
+}
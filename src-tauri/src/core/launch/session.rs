@@ -0,0 +1,1107 @@
+// ─── Launch Session ───
+// Starts a prepared instance and tracks the game process until it exits:
+// account ownership, hooks, the persisted log, crash reports and the
+// instance state once the session ends.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{io::BufRead, io::BufReader as StdBufReader};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::core::auth::entitlements::{self, EntitlementCache};
+use crate::core::auth::profile as account_profile;
+use crate::core::auth::{AccountMode, LaunchAccountProfile};
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::{ErrorText, Locale, LogKey, MessageKey};
+use crate::core::install::prepare_instance_for_launch;
+use crate::core::install_progress::InstallProgress;
+use crate::core::instance::{play_stats, Instance, InstanceManager, InstanceState};
+use crate::core::launch;
+use crate::core::launch::events::{
+    emit_launch_error, emit_launch_log, emit_launch_log_keyed, emit_launch_message,
+    emit_launch_progress, InstanceCrashEvent,
+};
+use crate::core::launch::preflight::{
+    attempt_preflight_repair, detect_loader_asm_incompatibility, has_loader_java_incompatibility,
+    preflight_error, repair_with_retries, run_bootstrap_runtime_probe, user_forced_gamma_only,
+    validate_instance_state_before_launch, verify_instance_runtime_readiness, LaunchRepairPass,
+};
+use crate::core::state::{AppState, LaunchCancellationGuard, RunningInstance};
+use crate::core::version::VersionJson;
+
+pub(crate) fn append_instance_log(
+    writer: Option<&std::sync::Mutex<launch::InstanceLogWriter>>,
+    stream: &str,
+    line: &str,
+) {
+    if let Some(writer) = writer {
+        let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(err) = writer.write_line(stream, line) {
+            warn!("Cannot write instance log: {}", err);
+        }
+    }
+}
+
+/// Report a known failure signature in `line`, once per diagnostic kind.
+pub(crate) fn report_launch_diagnostic(
+    events: &dyn EventEmitter,
+    locale: Locale,
+    tracker: &std::sync::Mutex<launch::DiagnosticTracker>,
+    id: &str,
+    line: &str,
+) {
+    let Some(diagnostic) = tracker
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .observe(line)
+    else {
+        return;
+    };
+    emit_launch_log_keyed(
+        events,
+        id,
+        "error",
+        Some(diagnostic.i18n_key()),
+        diagnostic.message(locale).into(),
+    );
+    events.emit(
+        "instance-diagnostic",
+        launch::InstanceDiagnosticEvent {
+            id: id.to_string(),
+            diagnostic,
+            severity: diagnostic.severity(),
+            i18n_key: diagnostic.i18n_key(),
+        },
+    );
+}
+
+/// Run an instance hook off the async runtime, forwarding its output as launch logs.
+pub(crate) async fn run_instance_hook_with_logs(
+    events: &Arc<dyn EventEmitter>,
+    instance: &Instance,
+    kind: launch::HookKind,
+    hooks_enabled: bool,
+    timeout: Duration,
+    cancel: CancellationToken,
+) -> Result<bool, LauncherError> {
+    let events = events.clone();
+    let instance = instance.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let label = kind.label();
+        launch::run_instance_hook(kind, &instance, hooks_enabled, timeout, &cancel, |line| {
+            emit_launch_log(
+                &events,
+                &instance.id,
+                "info",
+                format!("[HOOK {label}] {line}"),
+            );
+        })
+    })
+    .await
+    .map_err(|e| LauncherError::message(ErrorText::HookCrashed, &[&e]))?
+}
+
+/// Logs tailed when a crash left no report: the game's own log first, then ours.
+pub(crate) fn crash_fallback_logs(
+    instance: &Instance,
+    launcher_logs_dir: &Path,
+) -> Vec<std::path::PathBuf> {
+    vec![
+        instance.game_dir().join("logs").join("latest.log"),
+        launch::instance_log_path(launcher_logs_dir, &instance.id),
+    ]
+}
+
+pub(crate) async fn start_instance(
+    events: Arc<dyn EventEmitter>,
+    state_arc: Arc<Mutex<AppState>>,
+    id: String,
+    cancel_guard: LaunchCancellationGuard,
+) -> Result<(), LauncherError> {
+    // The ownership check may hit the network, so it runs without the lock.
+    let (locale, data_dir, client, mut account) = {
+        let state_guard = state_arc.lock().await;
+        let locale = state_guard.launcher_settings.language;
+        emit_launch_progress(
+            &events,
+            locale,
+            &id,
+            5,
+            MessageKey::LaunchValidating,
+            "running",
+        );
+        emit_launch_message(&events, locale, &id, "info", LogKey::LaunchRequested, &[]);
+        let instance = state_guard.instance_manager.load(&id).await?;
+        let account = state_guard
+            .accounts
+            .resolve(instance.account_id.as_deref(), &instance.account)
+            .clone();
+        (
+            locale,
+            state_guard.data_dir.clone(),
+            state_guard.http_client.clone(),
+            account,
+        )
+    };
+    let mut timeline = launch::PhaseTimeline::new(events.clone(), &id);
+    timeline.enter(launch::LaunchPhase::Validation);
+    check_account_ownership(&data_dir, &client, &mut account).await;
+
+    // Crash reports older than this belong to a previous session.
+    let launched_at = std::time::SystemTime::now();
+    let (instance, libs_dir, hooks_enabled, hook_timeout) = {
+        let state_guard = state_arc.lock().await;
+        // Early exit only; the check that counts is repeated under the lock
+        // that registers the pid.
+        if state_guard.running_instances.contains_key(&id) {
+            warn!("Refusing to launch {}: it is already running", id);
+            return Err(LauncherError::InstanceAlreadyRunning(id));
+        }
+        let mut instance = state_guard.instance_manager.load(&id).await?;
+        // Refresh the inlined copy so it stays usable if the account is removed.
+        instance.account = account;
+        if matches!(instance.account.mode, AccountMode::Microsoft)
+            && instance.account.is_unlicensed()
+        {
+            warn!(
+                "Account {} does not own Minecraft; launching {} in demo mode",
+                instance.account.username, id
+            );
+            emit_launch_message(
+                &events,
+                locale,
+                &id,
+                "warn",
+                LogKey::LaunchDemoMode,
+                &[&instance.account.username],
+            );
+        }
+
+        if let Err(err) = validate_instance_state_before_launch(&state_guard, &instance).await {
+            emit_launch_progress(
+                &events,
+                locale,
+                &id,
+                100,
+                MessageKey::LaunchValidationFailed,
+                "error",
+            );
+            emit_launch_error(&events, locale, &id, LogKey::LaunchValidationFailed, &err);
+            instance.state = InstanceState::Error;
+            state_guard.instance_manager.save(&instance).await?;
+            return Err(err);
+        }
+
+        emit_launch_progress(
+            &events,
+            locale,
+            &id,
+            15,
+            MessageKey::LaunchValidated,
+            "running",
+        );
+        emit_launch_message(&events, locale, &id, "info", LogKey::LaunchValidated, &[]);
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "info",
+            LogKey::LaunchPhasePreparation,
+            &[],
+        );
+        timeline.enter(launch::LaunchPhase::Preparation);
+
+        instance.state = InstanceState::Installing;
+        state_guard.instance_manager.save(&instance).await?;
+
+        let progress = {
+            let events = events.clone();
+            let id = id.clone();
+            InstallProgress::new(15, 72, MessageKey::LaunchValidated, move |value, stage| {
+                emit_launch_progress(events.as_ref(), locale, &id, value, stage, "running")
+            })
+        };
+        if let Err(err) = prepare_instance_for_launch(
+            events.as_ref(),
+            &state_guard,
+            &mut instance,
+            cancel_guard.token(),
+            Some(&progress),
+        )
+        .await
+        {
+            // Not a failure: `Launcher::launch_instance` restores the previous
+            // state and reports the launch as cancelled.
+            if matches!(err, LauncherError::Cancelled) {
+                return Err(err);
+            }
+            emit_launch_progress(
+                &events,
+                locale,
+                &id,
+                100,
+                MessageKey::LaunchPreparationFailed,
+                "error",
+            );
+            emit_launch_error(&events, locale, &id, LogKey::LaunchPreparationFailed, &err);
+            instance.state = InstanceState::Error;
+            state_guard.instance_manager.save(&instance).await?;
+            return Err(err);
+        }
+
+        emit_launch_progress(
+            &events,
+            locale,
+            &id,
+            72,
+            MessageKey::LaunchPrepared,
+            "running",
+        );
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "info",
+            LogKey::LaunchResourcesReady,
+            &[],
+        );
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "info",
+            LogKey::LaunchPhaseBootstrap,
+            &[],
+        );
+        timeline.enter(launch::LaunchPhase::Bootstrap);
+
+        let libs_dir = state_guard.libraries_dir();
+
+        // [SELF-HEALING] Revertir estado "requires_delta" si los checks de ASM ahora pasan (debido a actualizaciones o correcciones de lógica).
+        if instance.loader_requires_delta {
+            let required_major = instance.required_java_major.unwrap_or_else(|| {
+                crate::core::java::required_java_for_minecraft_version(&instance.minecraft_version)
+            });
+            if detect_loader_asm_incompatibility(&instance, required_major).is_none() {
+                emit_launch_message(
+                    &events,
+                    locale,
+                    &id,
+                    "info",
+                    LogKey::RepairRevertedToGamma,
+                    &[],
+                );
+                instance.loader_requires_delta = false;
+                instance.bootstrap_runtime = crate::core::java::RuntimeRole::Gamma;
+                state_guard.instance_manager.save(&instance).await?;
+            }
+        }
+
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "info",
+            LogKey::LaunchPreflightRunning,
+            &[],
+        );
+        let mut preflight_failures =
+            verify_instance_runtime_readiness(&events, &state_guard, &instance, &libs_dir).await?;
+        if !preflight_failures.is_empty() {
+            if has_loader_java_incompatibility(&preflight_failures)
+                && user_forced_gamma_only(&state_guard.launcher_settings, &instance)
+            {
+                let err = LauncherError::message(ErrorText::LoaderNeedsDeltaButGammaForced, &[]);
+                emit_launch_progress(
+                    &events,
+                    locale,
+                    &id,
+                    100,
+                    MessageKey::LaunchPreflightFailed,
+                    "error",
+                );
+                emit_launch_error(&events, locale, &id, LogKey::LaunchError, &err);
+                instance.state = InstanceState::Error;
+                state_guard.instance_manager.save(&instance).await?;
+                return Err(err);
+            }
+
+            if has_loader_java_incompatibility(&preflight_failures) {
+                if state_guard.launcher_settings.auto_fix_loader_java {
+                    emit_launch_message(
+                        &events,
+                        locale,
+                        &id,
+                        "warn",
+                        LogKey::LaunchPreflightLoaderJavaFix,
+                        &[],
+                    );
+                    attempt_preflight_repair(
+                        &events,
+                        &state_guard,
+                        &mut instance,
+                        &preflight_failures,
+                        cancel_guard.token(),
+                    )
+                    .await?;
+                    preflight_failures = verify_instance_runtime_readiness(
+                        &events,
+                        &state_guard,
+                        &instance,
+                        &libs_dir,
+                    )
+                    .await?;
+                } else {
+                    emit_launch_message(
+                        &events,
+                        locale,
+                        &id,
+                        "warn",
+                        LogKey::LaunchPreflightLoaderJavaFixDisabled,
+                        &[],
+                    );
+                }
+            } else {
+                let max_attempts = state_guard.launcher_settings.preflight_repair_attempts;
+                if max_attempts == 0 {
+                    emit_launch_message(
+                        &events,
+                        locale,
+                        &id,
+                        "warn",
+                        LogKey::LaunchPreflightRepairDisabled,
+                        &[],
+                    );
+                } else {
+                    emit_launch_message(
+                        &events,
+                        locale,
+                        &id,
+                        "warn",
+                        LogKey::LaunchPreflightRepairStarting,
+                        &[&max_attempts],
+                    );
+                }
+
+                let repaired = repair_with_retries(
+                    max_attempts,
+                    &mut LaunchRepairPass {
+                        events: events.as_ref(),
+                        state: &state_guard,
+                        instance: &mut instance,
+                        failures: &mut preflight_failures,
+                        libs_dir: &libs_dir,
+                        cancel: cancel_guard.token(),
+                        max_attempts,
+                    },
+                )
+                .await?;
+                if repaired {
+                    emit_launch_message(&events, locale, &id, "info", LogKey::RepairSucceeded, &[]);
+                }
+
+                if !repaired && !preflight_failures.is_empty() {
+                    let err = preflight_error(&preflight_failures);
+                    emit_launch_progress(
+                        &events,
+                        locale,
+                        &id,
+                        100,
+                        MessageKey::LaunchPreflightFailed,
+                        "error",
+                    );
+                    emit_launch_error(&events, locale, &id, LogKey::LaunchError, &err);
+                    instance.state = InstanceState::Error;
+                    state_guard.instance_manager.save(&instance).await?;
+                    return Err(err);
+                }
+            }
+
+            if !preflight_failures.is_empty() {
+                let err = preflight_error(&preflight_failures);
+                emit_launch_progress(
+                    &events,
+                    locale,
+                    &id,
+                    100,
+                    MessageKey::LaunchPreflightFailed,
+                    "error",
+                );
+                emit_launch_error(&events, locale, &id, LogKey::LaunchError, &err);
+                instance.state = InstanceState::Error;
+                state_guard.instance_manager.save(&instance).await?;
+                return Err(err);
+            }
+
+            state_guard.instance_manager.save(&instance).await?;
+        }
+
+        run_bootstrap_runtime_probe(&events, &state_guard, &instance).await?;
+
+        let settings = &state_guard.launcher_settings;
+        (
+            instance,
+            libs_dir,
+            settings.hooks_enabled,
+            Duration::from_secs(settings.hook_timeout_seconds),
+        )
+    };
+
+    // The hook is user code that may hang, so it runs without the state lock;
+    // the timeout or `cancel_launch` kills it.
+    if instance.pre_launch_hook.is_some() && !hooks_enabled {
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "warn",
+            LogKey::LaunchHooksDisabled,
+            &[],
+        );
+    }
+    if let Err(err) = run_instance_hook_with_logs(
+        &events,
+        &instance,
+        launch::HookKind::PreLaunch,
+        hooks_enabled,
+        hook_timeout,
+        cancel_guard.token().clone(),
+    )
+    .await
+    {
+        // `Launcher::launch_instance` restores the state of cancelled launches.
+        if matches!(err, LauncherError::Cancelled) {
+            return Err(err);
+        }
+        emit_launch_progress(
+            &events,
+            locale,
+            &id,
+            100,
+            MessageKey::LaunchPreLaunchHookFailed,
+            "error",
+        );
+        emit_launch_error(&events, locale, &id, LogKey::LaunchAborted, &err);
+        let state_guard = state_arc.lock().await;
+        save_launch_state(
+            &state_guard.instance_manager,
+            &id,
+            InstanceState::Ready,
+            None,
+        )
+        .await?;
+        return Err(err);
+    }
+
+    let (mut child, (log_path, max_log_bytes, log_rotations), natives_dir, hook_instance) = {
+        let mut state_guard = state_arc.lock().await;
+        // Checked under the same lock that registers the pid below, so two
+        // launches racing for one instance cannot both get past this point.
+        if state_guard.running_instances.contains_key(&id) {
+            warn!("Refusing to launch {}: it is already running", id);
+            return Err(LauncherError::InstanceAlreadyRunning(id));
+        }
+
+        let classpath = launch::build_classpath(&instance, &libs_dir, &instance.libraries)?;
+        emit_launch_message(
+            &events,
+            locale,
+            &id,
+            "info",
+            LogKey::LaunchPhaseJarAnalysis,
+            &[],
+        );
+        timeline.enter(launch::LaunchPhase::JarAnalysis);
+        let version_json_path = instance
+            .runtime_root_dir()
+            .join(format!("{}.json", instance.minecraft_version));
+        let version_json = tokio::fs::read_to_string(&version_json_path)
+            .await
+            .ok()
+            .and_then(|raw| serde_json::from_str::<VersionJson>(&raw).ok());
+        let native_jars = launch::native_jars(version_json.as_ref(), &instance.libraries);
+        // Leftover sessions are only safe to remove while nothing runs,
+        // which the check above guarantees.
+        launch::clear_stale_natives(&instance).await;
+        let natives_dir = launch::extract_natives(&instance, &libs_dir, &native_jars).await?;
+
+        emit_launch_progress(
+            &events,
+            locale,
+            &id,
+            90,
+            MessageKey::LaunchStartingProcess,
+            "running",
+        );
+        emit_launch_message(&events, locale, &id, "info", LogKey::LaunchPhaseGame, &[]);
+        timeline.enter(launch::LaunchPhase::Launch);
+
+        // Diagnóstico: imprimir el comando final (classpath + jvm args) para detectar
+        // duplicados de bootstrap (securejarhandler/modlauncher) y mezcla module-path/-cp.
+        // Esto va a la UI (no solo a tracing) para facilitar soporte.
+        emit_launch_log(
+            &events,
+            &id,
+            "info",
+            format!("[DIAG] JVM args (raw): {}", instance.jvm_args.join(" ")),
+        );
+        emit_launch_log(
+            &events,
+            &id,
+            "info",
+            format!(
+                "[DIAG] Main class: {}",
+                instance.main_class.as_deref().unwrap_or("<none>")
+            ),
+        );
+        emit_launch_log(
+            &events,
+            &id,
+            "info",
+            format!("[DIAG] Classpath len={}", classpath.len()),
+        );
+        // Evitar un spam gigante en UI; aún así, el contenido completo es útil para depurar.
+        // Se imprime completo, pero si esto resulta demasiado verboso podemos recortarlo luego.
+        emit_launch_log(
+            &events,
+            &id,
+            "info",
+            format!("[DIAG] Classpath: {}", classpath),
+        );
+
+        let (child, java_bin) = match launch::launch(
+            &instance,
+            &classpath,
+            &libs_dir,
+            &natives_dir,
+            state_guard.launcher_settings.allow_newer_java_major,
+            &state_guard.data_dir,
+            state_guard.runtime_options(),
+        )
+        .await
+        {
+            Ok(launched) => launched,
+            Err(err) => {
+                emit_launch_progress(
+                    &events,
+                    locale,
+                    &id,
+                    100,
+                    MessageKey::LaunchProcessFailed,
+                    "error",
+                );
+                emit_launch_error(&events, locale, &id, LogKey::LaunchSpawnFailed, &err);
+                save_launch_state(
+                    &state_guard.instance_manager,
+                    &id,
+                    InstanceState::Error,
+                    None,
+                )
+                .await?;
+                return Err(err);
+            }
+        };
+        save_launch_state(
+            &state_guard.instance_manager,
+            &id,
+            InstanceState::Running,
+            Some(Utc::now()),
+        )
+        .await?;
+        let pid = child.id();
+        state_guard.running_instances.insert(
+            id.clone(),
+            RunningInstance {
+                pid,
+                natives_dir: natives_dir.clone(),
+                java_bin,
+            },
+        );
+        info!("Launched instance {}", instance.name);
+        timeline.enter(launch::LaunchPhase::Running);
+        emit_launch_progress(&events, locale, &id, 100, MessageKey::LaunchRunning, "done");
+        emit_launch_message(&events, locale, &id, "info", LogKey::LaunchRunning, &[&pid]);
+
+        (
+            child,
+            (
+                launch::instance_log_path(&state_guard.launcher_logs_dir(), &id),
+                state_guard.launcher_settings.max_log_bytes,
+                state_guard.launcher_settings.log_rotations,
+            ),
+            natives_dir,
+            instance,
+        )
+    };
+    // The process is running; from here on `force_close_instance` applies.
+    drop(cancel_guard);
+    let pid = child.id();
+    let session_started = std::time::Instant::now();
+
+    let log_writer = match launch::InstanceLogWriter::open(log_path, max_log_bytes, log_rotations) {
+        Ok(writer) => Some(Arc::new(std::sync::Mutex::new(writer))),
+        Err(err) => {
+            warn!("Cannot open log file for {}: {}", id, err);
+            None
+        }
+    };
+    let mut reader_tasks = Vec::new();
+    let diagnostics = Arc::new(std::sync::Mutex::new(launch::DiagnosticTracker::default()));
+
+    if let Some(stdout) = child.stdout.take() {
+        let instance_id = id.clone();
+        let events = events.clone();
+        let log_writer = log_writer.clone();
+        let diagnostics = diagnostics.clone();
+        reader_tasks.push(tauri::async_runtime::spawn(async move {
+            let _ = tauri::async_runtime::spawn_blocking(move || {
+                for line in StdBufReader::new(stdout).lines().map_while(Result::ok) {
+                    append_instance_log(log_writer.as_deref(), "stdout", &line);
+                    emit_launch_log(&events, &instance_id, "info", line.clone());
+                    report_launch_diagnostic(&events, locale, &diagnostics, &instance_id, &line);
+                    info!("[mc:{}][stdout] {}", instance_id, line);
+                }
+            })
+            .await;
+        }));
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        let instance_id = id.clone();
+        let events = events.clone();
+        let log_writer = log_writer.clone();
+        let diagnostics = diagnostics.clone();
+        reader_tasks.push(tauri::async_runtime::spawn(async move {
+            let _ = tauri::async_runtime::spawn_blocking(move || {
+                for line in StdBufReader::new(stderr).lines().map_while(Result::ok) {
+                    append_instance_log(log_writer.as_deref(), "stderr", &line);
+                    emit_launch_log(&events, &instance_id, "warn", line.clone());
+                    report_launch_diagnostic(&events, locale, &diagnostics, &instance_id, &line);
+                    warn!("[mc:{}][stderr] {}", instance_id, line);
+                }
+            })
+            .await;
+        }));
+    }
+
+    let events_for_wait = events.clone();
+    tauri::async_runtime::spawn(async move {
+        let wait_result = tauri::async_runtime::spawn_blocking(move || child.wait())
+            .await
+            .map_err(|e| std::io::Error::other(e.to_string()))
+            .and_then(|result| result);
+        timeline.finish(matches!(&wait_result, Ok(status) if status.success()));
+
+        // Readers hit EOF once the process is gone; drain them before closing the file.
+        for task in reader_tasks {
+            let _ = task.await;
+        }
+        if let Some(writer) = log_writer {
+            let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(err) = writer.flush() {
+                warn!("Cannot flush log file for {}: {}", id, err);
+            }
+        }
+
+        if let Err(err) = run_instance_hook_with_logs(
+            &events_for_wait,
+            &hook_instance,
+            launch::HookKind::PostExit,
+            hooks_enabled,
+            hook_timeout,
+            CancellationToken::new(),
+        )
+        .await
+        {
+            emit_launch_log_keyed(
+                &events_for_wait,
+                &id,
+                "warn",
+                Some(err.i18n_key()),
+                format!("[HOOK] {err}"),
+            );
+            warn!("Post-exit hook failed for {}: {}", id, err);
+        }
+        let mut state = state_arc.lock().await;
+
+        // A newer launch of the same instance may have replaced the entry.
+        if state
+            .running_instances
+            .get(&id)
+            .is_some_and(|running| running.pid == pid)
+        {
+            state.running_instances.remove(&id);
+        }
+        launch::cleanup_natives(&natives_dir).await;
+        let exit_state = match &wait_result {
+            Ok(status) if !status.success() => InstanceState::Error,
+            _ => InstanceState::Ready,
+        };
+        let persisted = match finish_session(
+            &state.instance_manager,
+            &id,
+            exit_state,
+            session_started.elapsed(),
+        )
+        .await
+        {
+            Ok(persisted) => Some(persisted),
+            Err(err) => {
+                error!("Cannot persist session end for {}: {}", id, err);
+                None
+            }
+        };
+
+        match wait_result {
+            Ok(status) => {
+                if status.success() {
+                    emit_launch_progress(
+                        &events_for_wait,
+                        locale,
+                        &id,
+                        0,
+                        MessageKey::LaunchIdle,
+                        "idle",
+                    );
+                    emit_launch_message(
+                        &events_for_wait,
+                        locale,
+                        &id,
+                        "info",
+                        LogKey::LaunchExited,
+                        &[],
+                    );
+                    info!(
+                        "Minecraft process for {} exited with status: {:?}",
+                        id, status
+                    );
+                } else {
+                    emit_launch_progress(
+                        &events_for_wait,
+                        locale,
+                        &id,
+                        100,
+                        MessageKey::LaunchExitedWithError,
+                        "error",
+                    );
+                    let exit_code = status.code();
+                    match exit_code {
+                        Some(code) => emit_launch_message(
+                            &events_for_wait,
+                            locale,
+                            &id,
+                            "error",
+                            LogKey::LaunchExitedWithCode,
+                            &[&code],
+                        ),
+                        None => emit_launch_message(
+                            &events_for_wait,
+                            locale,
+                            &id,
+                            "error",
+                            LogKey::LaunchExitedWithoutCode,
+                            &[],
+                        ),
+                    }
+                    if exit_code == Some(1) {
+                        emit_launch_message(
+                            &events_for_wait,
+                            locale,
+                            &id,
+                            "error",
+                            LogKey::LaunchExitCodeOneHint,
+                            &[],
+                        );
+                    }
+                    error!(
+                        "Minecraft process for {} exited abnormally with status: {:?}",
+                        id, status
+                    );
+                    if let Some(persisted) = &persisted {
+                        let fallback_logs =
+                            crash_fallback_logs(persisted, &state.launcher_logs_dir());
+                        if let Some(summary) = launch::summarize_crash(
+                            &persisted.game_dir(),
+                            &fallback_logs,
+                            Some(launched_at),
+                        ) {
+                            match &summary.description {
+                                Some(description) => emit_launch_message(
+                                    &events_for_wait,
+                                    locale,
+                                    &id,
+                                    "error",
+                                    LogKey::LaunchCrash,
+                                    &[description, &summary.path],
+                                ),
+                                None => emit_launch_message(
+                                    &events_for_wait,
+                                    locale,
+                                    &id,
+                                    "error",
+                                    LogKey::LaunchCrashWithoutReport,
+                                    &[&summary.path],
+                                ),
+                            }
+                            events_for_wait.emit(
+                                "instance-crash",
+                                InstanceCrashEvent {
+                                    id: id.clone(),
+                                    exit_code,
+                                    summary,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                emit_launch_progress(
+                    &events_for_wait,
+                    locale,
+                    &id,
+                    100,
+                    MessageKey::LaunchWaitFailed,
+                    "error",
+                );
+                emit_launch_message(
+                    &events_for_wait,
+                    locale,
+                    &id,
+                    "error",
+                    LogKey::LaunchWaitFailed,
+                    &[&err],
+                );
+                error!("Minecraft process for {} failed while waiting: {}", id, err)
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Record the outcome of a launch once the pre-launch hook has run. The
+/// hook runs without the state lock, so the instance may have been edited
+/// meanwhile; it is reloaded and only the state and `last_played` are set.
+async fn save_launch_state(
+    manager: &InstanceManager,
+    id: &str,
+    state: InstanceState,
+    last_played: Option<DateTime<Utc>>,
+) -> Result<(), LauncherError> {
+    let mut instance = manager.load(id).await?;
+    instance.state = state;
+    if last_played.is_some() {
+        instance.last_played = last_played;
+    }
+    manager.save(&instance).await
+}
+
+/// Record the end of a game session. The instance is reloaded rather than
+/// taken from the launch, so settings edited while the game ran (memory,
+/// arguments, account) survive; only the state and the play statistics
+/// belong to the session.
+pub(crate) async fn finish_session(
+    manager: &InstanceManager,
+    id: &str,
+    exit_state: InstanceState,
+    played: std::time::Duration,
+) -> Result<Instance, LauncherError> {
+    let mut instance = manager.load(id).await?;
+    instance.state = exit_state;
+    play_stats::record_session(&mut instance, played);
+    manager.save(&instance).await?;
+    Ok(instance)
+}
+
+/// Record on a Microsoft `account` whether it owns the game, from the
+/// entitlements cache or a fresh check. Offline accounts, and accounts whose
+/// ownership cannot be checked nor recalled, are left untouched.
+pub(crate) async fn check_account_ownership(
+    data_dir: &Path,
+    client: &reqwest::Client,
+    account: &mut LaunchAccountProfile,
+) {
+    if !matches!(account.mode, AccountMode::Microsoft) {
+        return;
+    }
+    let Ok(uuid) = account_profile::normalize_uuid(&account.uuid) else {
+        return;
+    };
+    let mut cache = EntitlementCache::load(data_dir);
+    let before = cache.get(&uuid);
+    let access_token = account.access_token.clone();
+    let owns = cache
+        .resolve(&uuid, Utc::now(), || {
+            entitlements::fetch_ownership(client, &access_token)
+        })
+        .await;
+    if cache.get(&uuid) != before {
+        if let Err(err) = cache.save(data_dir) {
+            warn!("Could not save the entitlements cache: {}", err);
+        }
+    }
+    if owns.is_some() {
+        account.owns_minecraft = owns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::LoaderType;
+
+    #[tokio::test]
+    async fn session_end_keeps_config_changed_while_running() {
+        use crate::core::instance::InstanceManager;
+
+        let root = std::env::temp_dir().join(format!("session-end-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let manager = InstanceManager::new(root.clone());
+        let mut instance = Instance::new(
+            "Session".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &root,
+        );
+        instance.state = InstanceState::Running;
+        let launched = manager.create(instance).await.unwrap();
+
+        // The user edits the launch config mid-session.
+        let mut edited = manager.load(&launched.id).await.unwrap();
+        edited.max_memory_mb = 6144;
+        edited.jvm_args = vec!["-XX:+UseZGC".into()];
+        manager.save(&edited).await.unwrap();
+
+        let finished = finish_session(
+            &manager,
+            &launched.id,
+            InstanceState::Ready,
+            std::time::Duration::from_secs(90),
+        )
+        .await
+        .unwrap();
+        assert_eq!(finished.state, InstanceState::Ready);
+
+        let stored = manager.load(&launched.id).await.unwrap();
+        assert_eq!(stored.state, InstanceState::Ready);
+        assert_eq!(stored.max_memory_mb, 6144);
+        assert_eq!(stored.jvm_args, vec!["-XX:+UseZGC".to_string()]);
+        assert_eq!(stored.total_play_seconds, 90);
+        assert_eq!(stored.launch_count, 1);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn launch_state_keeps_config_edited_during_the_pre_launch_hook() {
+        let root = std::env::temp_dir().join(format!("launch-state-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let manager = InstanceManager::new(root.clone());
+        let mut instance = Instance::new(
+            "Hooked".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &root,
+        );
+        instance.state = InstanceState::Installing;
+        let prepared = manager.create(instance).await.unwrap();
+
+        // The user edits the instance while the hook runs.
+        let mut edited = manager.load(&prepared.id).await.unwrap();
+        edited.max_memory_mb = 6144;
+        manager.save(&edited).await.unwrap();
+
+        let started = Utc::now();
+        save_launch_state(
+            &manager,
+            &prepared.id,
+            InstanceState::Running,
+            Some(started),
+        )
+        .await
+        .unwrap();
+        let stored = manager.load(&prepared.id).await.unwrap();
+        assert_eq!(stored.state, InstanceState::Running);
+        assert_eq!(stored.last_played, Some(started));
+        assert_eq!(stored.max_memory_mb, 6144);
+
+        save_launch_state(&manager, &prepared.id, InstanceState::Ready, None)
+            .await
+            .unwrap();
+        let stored = manager.load(&prepared.id).await.unwrap();
+        assert_eq!(stored.state, InstanceState::Ready);
+        assert_eq!(stored.last_played, Some(started));
+        assert_eq!(stored.max_memory_mb, 6144);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn second_launch_is_rejected_while_the_first_is_running() {
+        use crate::core::events::NoopEmitter;
+        use crate::core::state::{AppState, LaunchCancellations, RunningInstance};
+        use std::sync::Arc;
+        use tokio::sync::Mutex;
+
+        let data_dir = std::env::temp_dir().join(format!("second-launch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let state = AppState::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+        let mut instance = Instance::new(
+            "Running".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &data_dir.join("instances"),
+        );
+        instance.state = InstanceState::Running;
+        let instance = state.instance_manager.create(instance).await.unwrap();
+        let id = instance.id.clone();
+
+        // The first launch registered its pid.
+        let state = Arc::new(Mutex::new(state));
+        let natives_dir = instance.path.join("natives").join("first");
+        state.lock().await.running_instances.insert(
+            id.clone(),
+            RunningInstance {
+                pid: 4242,
+                natives_dir: natives_dir.clone(),
+                java_bin: std::path::PathBuf::from("java"),
+            },
+        );
+
+        let cancellations = Arc::new(LaunchCancellations::default());
+        let result = start_instance(
+            Arc::new(NoopEmitter),
+            state.clone(),
+            id.clone(),
+            cancellations.try_register(&id).unwrap(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(LauncherError::InstanceAlreadyRunning(ref running)) if running == &id
+        ));
+
+        // The first launch is left untouched.
+        let state = state.lock().await;
+        let running = &state.running_instances[&id];
+        assert_eq!(running.pid, 4242);
+        assert_eq!(running.natives_dir, natives_dir);
+        let stored = state.instance_manager.load(&id).await.unwrap();
+        assert_eq!(stored.state, InstanceState::Running);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,151 @@
+// ─── Process Shutdown ───
+// Stops a running game: first a polite request (SIGTERM, or WM_CLOSE through
+// `taskkill` without `/F` on Windows) so modded clients get time to save the
+// world, then a forced kill once the grace period runs out.
+
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+/// Grace period used when the launcher settings don't override it.
+pub const DEFAULT_STOP_GRACE_SECS: u64 = 10;
+/// Upper bound accepted for the configurable grace period.
+pub const MAX_STOP_GRACE_SECS: u64 = 120;
+
+/// Short window used by force-close and delete before killing outright.
+pub const FORCE_STOP_GRACE: Duration = Duration::from_millis(300);
+
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Poll `is_running` until it reports `false` or `timeout` elapses.
+/// Returns whether the process exited in time.
+pub async fn wait_for_exit<F>(mut is_running: F, timeout: Duration, poll: Duration) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        if !is_running() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        tokio::time::sleep(poll.min(deadline - now)).await;
+    }
+}
+
+/// Ask the process to close, wait up to `grace` for it to exit and kill it
+/// if it is still alive afterwards.
+pub async fn stop_process(pid: u32, grace: Duration) -> LauncherResult<()> {
+    if request_stop(pid) {
+        if wait_for_exit(|| is_process_running(pid), grace, EXIT_POLL_INTERVAL).await {
+            info!("Process {} exited after stop request", pid);
+            return Ok(());
+        }
+        warn!(
+            "Process {} still running after {:?}; forcing termination",
+            pid, grace
+        );
+    }
+    force_kill(pid)
+}
+
+#[cfg(target_os = "windows")]
+fn request_stop(pid: u32) -> bool {
+    // Without `/F`, taskkill posts WM_CLOSE to the process windows.
+    Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T"])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn request_stop(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-15", &pid.to_string()])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(target_os = "windows")]
+fn is_process_running(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .any(|token| token == pid.to_string())
+        })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_process_running(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn force_kill(pid: u32) -> LauncherResult<()> {
+    let status = if cfg!(target_os = "windows") {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status()
+    } else {
+        Command::new("kill").args(["-9", &pid.to_string()]).status()
+    }
+    .map_err(|e| LauncherError::message(ErrorText::KillFailed, &[&pid, &e]))?;
+
+    if !status.success() && is_process_running(pid) {
+        return Err(LauncherError::message(
+            ErrorText::KillCommandFailed,
+            &[&pid, &format!("{:?}", status.code())],
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[tokio::test]
+    async fn returns_as_soon_as_the_process_exits() {
+        let polls = Cell::new(0);
+        let exited = wait_for_exit(
+            || {
+                polls.set(polls.get() + 1);
+                polls.get() < 3
+            },
+            Duration::from_secs(5),
+            Duration::from_millis(5),
+        )
+        .await;
+
+        assert!(exited);
+        assert_eq!(polls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_grace_period() {
+        let started = Instant::now();
+        let exited = wait_for_exit(
+            || true,
+            Duration::from_millis(60),
+            Duration::from_millis(10),
+        )
+        .await;
+
+        assert!(!exited);
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+}
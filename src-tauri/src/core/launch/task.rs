@@ -8,10 +8,12 @@ use std::process::Stdio;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
+use serde::Serialize;
 use tracing::{debug, info};
 
-use crate::core::auth::LaunchAccountProfile;
+use crate::core::auth::{skin, AccountMode, LaunchAccountProfile};
 use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
 use crate::core::instance::Instance;
 use crate::core::java;
 
@@ -29,30 +31,35 @@ pub struct LaunchConfig {
     pub libraries_dir: PathBuf,
     pub launcher_name: String,
     pub launcher_version: String,
+    /// Wrapper program and its arguments; java becomes its first trailing arg.
+    pub wrapper_command: Vec<String>,
 }
 
 pub fn build_minecraft_command(config: &LaunchConfig) -> LauncherResult<std::process::Command> {
     if !config.java_bin.exists() {
-        return Err(LauncherError::Other(format!(
-            "Java embebido no encontrado en {:?}",
-            config.java_bin
-        )));
+        return Err(LauncherError::message(
+            ErrorText::EmbeddedJavaMissing,
+            &[&config.java_bin.display()],
+        ));
     }
 
     if config.main_class.trim().is_empty() {
-        return Err(LauncherError::Other(
-            "Main class vacía: no se puede construir el comando".into(),
-        ));
+        return Err(LauncherError::message(ErrorText::MainClassMissing, &[]));
     }
 
     if config.classpath.trim().is_empty() {
-        return Err(LauncherError::Other(
-            "Classpath vacío: se cancela el arranque para evitar 'java -cp' inválido".into(),
-        ));
+        return Err(LauncherError::ClasspathEmpty);
     }
 
     let java_program = safe_command_path(&config.java_bin);
-    let mut cmd = std::process::Command::new(java_program);
+    let mut cmd = match config.wrapper_command.split_first() {
+        Some((wrapper, wrapper_args)) => {
+            let mut cmd = std::process::Command::new(wrapper);
+            cmd.args(wrapper_args).arg(java_program);
+            cmd
+        }
+        None => std::process::Command::new(java_program),
+    };
 
     let java_home = config
         .java_bin
@@ -81,19 +88,24 @@ pub fn build_minecraft_command(config: &LaunchConfig) -> LauncherResult<std::pro
     Ok(cmd)
 }
 
-/// Launch the game as a child process.
+/// Launch the game as a child process, loading natives from the session
+/// directory returned by `extract_natives`.
 ///
-/// Returns immediately after spawning. The caller is responsible for monitoring
-/// the child process and setting state back to `Ready` when it exits.
+/// Returns immediately after spawning, with the Java binary the game runs on.
+/// The caller is responsible for monitoring the child process and setting
+/// state back to `Ready` when it exits.
 pub async fn launch(
     instance: &Instance,
     classpath: &str,
     libraries_dir: &std::path::Path,
-) -> LauncherResult<std::process::Child> {
-    let main_class = instance
-        .main_class
-        .as_deref()
-        .ok_or_else(|| LauncherError::Other("Main class not set on instance".into()))?;
+    natives_dir: &std::path::Path,
+    allow_newer_java_major: bool,
+    data_dir: &std::path::Path,
+    runtime: java::RuntimeOptions,
+) -> LauncherResult<(std::process::Child, PathBuf)> {
+    if instance.main_class.is_none() {
+        return Err(LauncherError::message(ErrorText::MainClassMissing, &[]));
+    }
 
     let required_java_major = instance
         .required_java_major
@@ -103,7 +115,13 @@ pub async fn launch(
         info!("Using Java override from instance config: {:?}", path);
         path.clone()
     } else {
-        java::resolve_runtime(instance.game_runtime, Some(&instance.minecraft_version)).await?
+        java::resolve_runtime(
+            data_dir,
+            instance.game_runtime,
+            Some(&instance.minecraft_version),
+            runtime,
+        )
+        .await?
     };
 
     let resolved_java_major = java::runtime::inspect_java_binary(&java_bin).map(|info| info.major);
@@ -113,18 +131,26 @@ pub async fn launch(
         required_java_major, instance.minecraft_version
     );
 
-    let java_compatible = resolved_java_major
-        .is_some_and(|major| java::is_java_compatible_major(major, required_java_major));
+    let java_compatible = resolved_java_major.is_some_and(|major| {
+        java::is_java_compatible_for_loader(
+            major,
+            required_java_major,
+            &instance.loader,
+            allow_newer_java_major,
+        )
+    });
     if !java_compatible {
-        return Err(LauncherError::Other(format!(
-            "Java incompatible para Minecraft {}: requerida {}, detectada {:?}",
-            instance.minecraft_version, required_java_major, resolved_java_major
-        )));
+        return Err(LauncherError::message(
+            ErrorText::IncompatibleJava,
+            &[
+                &instance.minecraft_version,
+                &required_java_major,
+                &format!("{resolved_java_major:?}"),
+            ],
+        ));
     }
 
-    let natives_dir = instance.natives_dir();
     let game_dir = instance.game_dir();
-    let assets_dir = game_dir.join("assets");
 
     assert!(
         java_bin.exists(),
@@ -146,13 +172,61 @@ pub async fn launch(
         info!("JAVA EXECUTABLE: {}", executable);
     }
 
+    // ── Offline Skin ──
+    // Applied through a generated resource pack, so no game argument or
+    // client patch is needed; an unusable skin is logged and skipped.
+    if let Some(pack_dir) =
+        skin::apply_offline_skin(&game_dir, &instance.minecraft_version, &instance.account)
+    {
+        info!("Offline skin pack ready at {:?}", pack_dir);
+    }
+
+    let launch_config = launch_config(
+        instance,
+        java_bin.clone(),
+        classpath,
+        libraries_dir,
+        natives_dir,
+    )?;
+
+    let mut cmd = build_minecraft_command(&launch_config)?;
+
+    log_runtime_java_version(&java_bin, &game_dir);
+    info!("Launching Minecraft with Java: {:?}", java_bin);
+    debug!("Command: {:?}", cmd);
+    debug!("Command (copy/paste): {}", format_command_for_logs(&cmd));
+
+    let child = cmd
+        .spawn()
+        .map_err(|e| LauncherError::JavaExecution(e.to_string()))?;
+
+    Ok((child, java_bin))
+}
+
+/// Everything `launch` passes to the game process for `instance` running on
+/// `java_bin`. Pure argument construction: nothing is resolved, written or
+/// spawned.
+pub fn launch_config(
+    instance: &Instance,
+    java_bin: PathBuf,
+    classpath: &str,
+    libraries_dir: &Path,
+    natives_dir: &Path,
+) -> LauncherResult<LaunchConfig> {
+    let main_class = instance
+        .main_class
+        .as_deref()
+        .ok_or_else(|| LauncherError::message(ErrorText::MainClassMissing, &[]))?;
+    let game_dir = instance.game_dir();
+    let assets_dir = game_dir.join("assets");
+
     // ── JVM Arguments ──
     let xmx_mb = instance.max_memory_mb.max(1024);
     let xms_mb = (xmx_mb / 2).max(512);
     let mut jvm_args = vec![
         format!("-Xmx{}M", xmx_mb),
         format!("-Xms{}M", xms_mb),
-        format!("-Djava.library.path={}", safe_path_str(&natives_dir)),
+        format!("-Djava.library.path={}", safe_path_str(natives_dir)),
         format!("-DlibraryDirectory={}", safe_path_str(libraries_dir)),
         "-Dminecraft.launcher.brand=InterfaceOficial".into(),
         "-Dminecraft.launcher.version=0.1.0".into(),
@@ -163,7 +237,7 @@ pub async fn launch(
     let mut effective_jvm_args = sanitize_jvm_args(
         instance,
         &instance.jvm_args,
-        &natives_dir,
+        natives_dir,
         libraries_dir,
         classpath,
     );
@@ -185,34 +259,120 @@ pub async fn launch(
         &instance.game_args,
         &game_dir,
         &assets_dir,
+        natives_dir,
         &instance.account,
     );
 
-    let launch_config = LaunchConfig {
-        java_bin: java_bin.clone(),
+    Ok(LaunchConfig {
+        java_bin,
         main_class: main_class.to_string(),
         classpath: classpath.to_string(),
         jvm_args,
         game_args: final_game_args,
         game_dir: game_dir.clone(),
-        natives_dir: natives_dir.clone(),
+        natives_dir: natives_dir.to_path_buf(),
         libraries_dir: libraries_dir.to_path_buf(),
         launcher_name: "InterfaceOficial".into(),
         launcher_version: "0.1.0".into(),
-    };
+        wrapper_command: instance.wrapper_command.clone().unwrap_or_default(),
+    })
+}
 
-    let mut cmd = build_minecraft_command(&launch_config)?;
+/// Stand-in for secrets in a [`LaunchPreview`].
+pub const REDACTED: &str = "<redacted>";
 
-    log_runtime_java_version(&java_bin, &game_dir);
-    info!("Launching Minecraft with Java: {:?}", java_bin);
-    debug!("Command: {:?}", cmd);
-    debug!("Command (copy/paste): {}", format_command_for_logs(&cmd));
+/// The process `launch` would spawn, split into its parts so it can be
+/// copied or diffed against another launcher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaunchPreview {
+    /// Wrapper program and its arguments, run in front of java.
+    pub wrapper_command: Vec<String>,
+    pub java_bin: String,
+    pub jvm_args: Vec<String>,
+    /// Classpath entries, in order.
+    pub classpath: Vec<String>,
+    pub main_class: String,
+    pub game_args: Vec<String>,
+    pub working_dir: String,
+    /// The whole command on one line, quoted for a shell.
+    pub command_line: String,
+}
 
-    let child = cmd
-        .spawn()
-        .map_err(|e| LauncherError::JavaExecution(e.to_string()))?;
+impl LaunchPreview {
+    /// Program and arguments in the order `build_minecraft_command` passes
+    /// them.
+    pub fn argv(&self) -> Vec<String> {
+        let separator = super::classpath::get_classpath_separator().to_string();
+        let mut argv = self.wrapper_command.clone();
+        argv.push(self.java_bin.clone());
+        argv.extend(self.jvm_args.iter().cloned());
+        argv.push("-cp".into());
+        argv.push(self.classpath.join(&separator));
+        argv.push(self.main_class.clone());
+        argv.extend(self.game_args.iter().cloned());
+        argv
+    }
+}
 
-    Ok(child)
+/// Describe the command for `config` with every occurrence of `secrets`
+/// (the account access token) replaced by [`REDACTED`].
+pub fn preview_command(config: &LaunchConfig, secrets: &[&str]) -> LaunchPreview {
+    let redact = |value: &str| {
+        secrets
+            .iter()
+            .filter(|secret| !secret.is_empty())
+            .fold(value.to_string(), |acc, secret| {
+                acc.replace(secret, REDACTED)
+            })
+    };
+    let redact_all = |values: &[String]| values.iter().map(|value| redact(value)).collect();
+
+    let separator = super::classpath::get_classpath_separator();
+    let mut preview = LaunchPreview {
+        wrapper_command: redact_all(&config.wrapper_command),
+        java_bin: safe_command_path(&config.java_bin)
+            .to_string_lossy()
+            .to_string(),
+        jvm_args: redact_all(&config.jvm_args),
+        classpath: config
+            .classpath
+            .split(separator)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect(),
+        main_class: config.main_class.clone(),
+        game_args: redact_all(&config.game_args),
+        working_dir: safe_path_str(&config.game_dir),
+        command_line: String::new(),
+    };
+    preview.command_line = preview
+        .argv()
+        .iter()
+        .map(|arg| shell_escape(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    preview
+}
+
+/// Resolve a wrapper program the way the OS would: as a path when it contains
+/// a separator, otherwise by searching `PATH`.
+pub fn find_program_on_path(program: &str) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+
+    let extensions: &[&str] = if cfg!(target_os = "windows") {
+        &["", ".exe", ".cmd", ".bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{program}{ext}")))
+            .find(|path| path.is_file())
+    })
 }
 
 fn safe_command_path(path: &Path) -> PathBuf {
@@ -260,14 +420,24 @@ fn build_placeholder_map(
     map.insert("${launcher_name}", "InterfaceOficial".to_string());
     map.insert("${launcher_version}", "0.1.0".to_string());
     map.insert("${auth_player_name}", account.username.clone());
+    // Only versions without their own index may fall back to `legacy`; for
+    // the rest the placeholder stays unresolved and its option is dropped.
+    let assets_index_name = instance.asset_index.clone().or_else(|| {
+        crate::core::assets::uses_legacy_asset_index(&instance.minecraft_version)
+            .then(|| "legacy".to_string())
+    });
     map.insert("${assets_root}", safe_path_str(assets_dir));
-    map.insert(
-        "${assets_index_name}",
-        instance
-            .asset_index
-            .clone()
-            .unwrap_or_else(|| "legacy".to_string()),
-    );
+    // Pre-1.7 `minecraftArguments` pass `--assetsDir ${game_assets}`.
+    if let Some(assets_index_name) = assets_index_name {
+        map.insert(
+            "${game_assets}",
+            safe_path_str(&crate::core::assets::game_assets_dir(
+                assets_dir,
+                &assets_index_name,
+            )),
+        );
+        map.insert("${assets_index_name}", assets_index_name);
+    }
     map.insert("${auth_uuid}", account.uuid.clone());
     map.insert("${auth_access_token}", account.access_token.clone());
     map.insert("${auth_xuid}", account.xuid.clone());
@@ -279,8 +449,9 @@ fn build_placeholder_map(
     map.insert("${quickPlaySingleplayer}", "".to_string());
     map.insert("${quickPlayRealms}", "".to_string());
     map.insert("${quickPlayPath}", "".to_string());
-    map.insert("${resolution_width}", "1280".to_string());
-    map.insert("${resolution_height}", "720".to_string());
+    let (width, height) = instance.window_size();
+    map.insert("${resolution_width}", width.to_string());
+    map.insert("${resolution_height}", height.to_string());
 
     map
 }
@@ -371,12 +542,13 @@ fn sanitize_game_args(
     raw_args: &[String],
     game_dir: &std::path::Path,
     assets_dir: &std::path::Path,
+    natives_dir: &std::path::Path,
     account: &LaunchAccountProfile,
 ) -> Vec<String> {
     let mut sanitized = Vec::new();
     let placeholders = build_placeholder_map(
         instance,
-        &instance.natives_dir(),
+        natives_dir,
         &instance.game_dir().join("libraries"),
         "",
         game_dir,
@@ -402,7 +574,16 @@ fn sanitize_game_args(
     }
 
     let sanitized = sanitize_numeric_window_args(sanitized);
-    let sanitized = strip_demo_mode_args(sanitized);
+    let sanitized = ensure_window_args(instance, sanitized);
+    // A Microsoft account without the game starts the demo either way; say
+    // so on the command line instead of letting the game decide silently.
+    let sanitized = if instance.allow_demo_mode
+        || (matches!(account.mode, AccountMode::Microsoft) && account.is_unlicensed())
+    {
+        ensure_demo_mode_for_unlicensed(account, sanitized)
+    } else {
+        strip_demo_mode_args(sanitized)
+    };
     ensure_required_fml_game_args(instance, sanitized)
 }
 
@@ -431,6 +612,22 @@ fn strip_demo_mode_args(args: Vec<String>) -> Vec<String> {
     cleaned
 }
 
+/// Offline accounts own no license, so with demo mode allowed they start the
+/// demo rather than the full game. So do Microsoft accounts without the game.
+fn ensure_demo_mode_for_unlicensed(
+    account: &LaunchAccountProfile,
+    mut args: Vec<String>,
+) -> Vec<String> {
+    let has_demo_flag = args.iter().any(|arg| {
+        is_demo_mode_flag(arg)
+            || split_flag_value(arg).is_some_and(|(flag, _)| is_demo_mode_flag(flag))
+    });
+    if account.is_unlicensed() && !has_demo_flag {
+        args.push("--demo".into());
+    }
+    args
+}
+
 fn is_demo_mode_flag(flag: &str) -> bool {
     matches!(flag, "--demo" | "--demoMode" | "--demo-mode")
 }
@@ -531,6 +728,25 @@ fn sanitize_numeric_window_args(args: Vec<String>) -> Vec<String> {
     sanitized
 }
 
+/// Pass the configured window options even when the version JSON only adds
+/// `--width`/`--height` behind its `has_custom_resolution` feature.
+fn ensure_window_args(instance: &Instance, mut args: Vec<String>) -> Vec<String> {
+    if let Some((width, height)) = instance.resolution {
+        if !contains_flag(&args, "--width") {
+            args.push("--width".into());
+            args.push(width.to_string());
+        }
+        if !contains_flag(&args, "--height") {
+            args.push("--height".into());
+            args.push(height.to_string());
+        }
+    }
+    if instance.start_fullscreen && !contains_flag(&args, "--fullscreen") {
+        args.push("--fullscreen".into());
+    }
+    args
+}
+
 fn launch_version_name(instance: &Instance) -> String {
     match instance.loader_version.as_deref() {
         Some(loader_version) if !loader_version.trim().is_empty() => {
@@ -734,6 +950,116 @@ fn shell_escape(raw: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn wrapper_command_prefixes_java_invocation() {
+        let temp = std::env::temp_dir().join(format!("wrapper-argv-{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let java_bin = temp.join("java");
+        std::fs::write(&java_bin, b"").unwrap();
+
+        let config = LaunchConfig {
+            java_bin: java_bin.clone(),
+            main_class: "net.minecraft.client.main.Main".into(),
+            classpath: "client.jar".into(),
+            jvm_args: vec!["-Xmx2048M".into()],
+            game_args: vec!["--demo".into()],
+            game_dir: temp.clone(),
+            natives_dir: temp.join("natives"),
+            libraries_dir: temp.join("libraries"),
+            launcher_name: "InterfaceOficial".into(),
+            launcher_version: "0.1.0".into(),
+            wrapper_command: vec!["prime-run".into(), "--flag".into()],
+        };
+
+        let cmd = build_minecraft_command(&config).unwrap();
+        let argv: Vec<String> = cmd
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(cmd.get_program(), "prime-run");
+        assert_eq!(
+            argv,
+            vec![
+                "--flag".to_string(),
+                safe_command_path(&java_bin).to_string_lossy().to_string(),
+                "-Xmx2048M".into(),
+                "-cp".into(),
+                "client.jar".into(),
+                "net.minecraft.client.main.Main".into(),
+                "--demo".into(),
+            ]
+        );
+        assert_eq!(cmd.get_current_dir(), Some(temp.as_path()));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn launch_preview_redacts_the_access_token_and_matches_the_spawned_command() {
+        let temp = std::env::temp_dir().join(format!("launch-preview-{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let java_bin = temp.join("java");
+        std::fs::write(&java_bin, b"").unwrap();
+
+        let mut instance = Instance::new(
+            "preview".into(),
+            "1.20.1".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            &temp,
+        );
+        instance.path = temp.join("instance");
+        instance.main_class = Some("net.minecraft.client.main.Main".into());
+        instance.asset_index = Some("5".into());
+        instance.jvm_args = vec!["-Dlauncher.token=${auth_access_token}".into()];
+        instance.game_args = vec![
+            "--username".into(),
+            "${auth_player_name}".into(),
+            "--accessToken".into(),
+            "${auth_access_token}".into(),
+        ];
+        instance.wrapper_command = Some(vec!["gamemoderun".into()]);
+        instance.account = LaunchAccountProfile::offline("Alex").sanitized();
+        instance.account.access_token = "secret-token-123".into();
+
+        let separator = super::super::classpath::get_classpath_separator();
+        let config = launch_config(
+            &instance,
+            java_bin,
+            &format!("client.jar{separator}lwjgl.jar"),
+            &temp.join("libraries"),
+            &temp.join("natives"),
+        )
+        .unwrap();
+        let preview = preview_command(&config, &[&instance.account.access_token]);
+
+        assert!(!serde_json::to_string(&preview)
+            .unwrap()
+            .contains("secret-token-123"));
+        assert!(preview
+            .jvm_args
+            .contains(&format!("-Dlauncher.token={REDACTED}")));
+        assert_eq!(
+            preview.game_args[..4],
+            ["--username", "Alex", "--accessToken", REDACTED]
+        );
+        assert_eq!(preview.classpath, ["client.jar", "lwjgl.jar"]);
+        assert_eq!(preview.main_class, "net.minecraft.client.main.Main");
+
+        // Apart from the token, the argv is exactly what `launch` spawns.
+        let cmd = build_minecraft_command(&config).unwrap();
+        let spawned: Vec<String> = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| arg.to_string_lossy().replace("secret-token-123", REDACTED))
+            .collect();
+        assert_eq!(preview.argv(), spawned);
+        assert!(preview.command_line.starts_with("gamemoderun "));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
     #[test]
     fn java_major_detection() {
         assert_eq!(java::required_java_for_minecraft_version("1.21.4"), 21);
@@ -854,6 +1180,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -882,6 +1209,127 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitize_game_args_uses_the_configured_window_options() {
+        let mut instance = Instance::new(
+            "window".into(),
+            "1.20.1".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        instance.resolution = Some((1920, 1080));
+        let args = vec![
+            "--width".into(),
+            "${resolution_width}".into(),
+            "--height".into(),
+            "${resolution_height}".into(),
+        ];
+
+        let sanitized = sanitize_game_args(
+            &instance,
+            &args,
+            std::path::Path::new("/tmp/game"),
+            std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
+            &instance.account,
+        );
+        assert_eq!(sanitized, vec!["--width", "1920", "--height", "1080"]);
+
+        // Versions that only pass the size behind a feature rule still get it.
+        instance.start_fullscreen = true;
+        let sanitized = sanitize_game_args(
+            &instance,
+            &["--demo".into()],
+            std::path::Path::new("/tmp/game"),
+            std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
+            &instance.account,
+        );
+        assert_eq!(
+            sanitized,
+            vec!["--width", "1920", "--height", "1080", "--fullscreen"]
+        );
+
+        // Without a configured size only the flag is added.
+        instance.resolution = None;
+        let sanitized = sanitize_game_args(
+            &instance,
+            &["--fullscreen".into()],
+            std::path::Path::new("/tmp/game"),
+            std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
+            &instance.account,
+        );
+        assert_eq!(sanitized, vec!["--fullscreen"]);
+    }
+
+    #[test]
+    fn modern_instance_never_launches_with_the_legacy_asset_index() {
+        let mut instance = Instance::new(
+            "modern".into(),
+            "1.20".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        let args = vec![
+            "--assetIndex".into(),
+            "${assets_index_name}".into(),
+            "--username".into(),
+            "${auth_player_name}".into(),
+        ];
+        let sanitize = |instance: &Instance| {
+            sanitize_game_args(
+                instance,
+                &args,
+                std::path::Path::new("/tmp/game"),
+                std::path::Path::new("/tmp/assets"),
+                std::path::Path::new("/tmp/natives"),
+                &instance.account,
+            )
+        };
+
+        let sanitized = sanitize(&instance);
+        assert!(!sanitized.iter().any(|arg| arg == "legacy"));
+        assert!(!sanitized.iter().any(|arg| arg == "--assetIndex"));
+
+        instance.asset_index = Some("5".into());
+        assert_eq!(sanitize(&instance)[..2], ["--assetIndex", "5"]);
+
+        instance.minecraft_version = "1.6.4".into();
+        instance.asset_index = None;
+        assert_eq!(sanitize(&instance)[..2], ["--assetIndex", "legacy"]);
+    }
+
+    #[test]
+    fn sanitize_game_args_resolves_legacy_game_assets() {
+        let mut instance = Instance::new(
+            "legacy".into(),
+            "1.5.2".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        instance.asset_index = Some("pre-1.6".into());
+        instance.account = LaunchAccountProfile::offline("Alex").sanitized();
+
+        let sanitized = sanitize_game_args(
+            &instance,
+            &["--assetsDir".into(), "${game_assets}".into()],
+            std::path::Path::new("/tmp/game"),
+            std::path::Path::new("/tmp/missing-assets"),
+            std::path::Path::new("/tmp/natives"),
+            &instance.account,
+        );
+
+        // Without a saved index the hashed layout root is used.
+        assert_eq!(sanitized, vec!["--assetsDir", "/tmp/missing-assets"]);
+    }
+
     #[test]
     fn sanitize_game_args_drops_dangling_option_for_unresolved_placeholder() {
         let mut instance = Instance::new(
@@ -907,6 +1355,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -944,6 +1393,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -985,6 +1435,7 @@ mod tests {
             &Vec::new(),
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -1026,6 +1477,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -1067,6 +1519,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
@@ -1084,6 +1537,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitize_game_args_keeps_demo_flags_only_when_allowed() {
+        let mut instance = Instance::new(
+            "demo".into(),
+            "1.20.1".into(),
+            crate::core::instance::LoaderType::Vanilla,
+            None,
+            2048,
+            std::path::Path::new("/tmp"),
+        );
+        let mut premium = LaunchAccountProfile::offline("Alex");
+        premium.mode = AccountMode::Microsoft;
+        let offline = LaunchAccountProfile::offline("Alex");
+        let sanitize = |instance: &Instance, args: &[String], account: &LaunchAccountProfile| {
+            sanitize_game_args(
+                instance,
+                args,
+                std::path::Path::new("/tmp/game"),
+                std::path::Path::new("/tmp/assets"),
+                std::path::Path::new("/tmp/natives"),
+                account,
+            )
+        };
+        let demo_args = vec!["--demo".to_string(), "--demoMode=true".to_string()];
+
+        assert!(sanitize(&instance, &demo_args, &premium).is_empty());
+        assert!(sanitize(&instance, &[], &offline).is_empty());
+        // A Microsoft account known not to own the game gets the demo regardless.
+        let non_owner = LaunchAccountProfile {
+            owns_minecraft: Some(false),
+            ..premium.clone()
+        };
+        assert_eq!(sanitize(&instance, &[], &non_owner), vec!["--demo"]);
+
+        instance.allow_demo_mode = true;
+        assert_eq!(sanitize(&instance, &demo_args, &premium), demo_args);
+        assert_eq!(sanitize(&instance, &demo_args, &offline), demo_args);
+        // Unlicensed accounts are put into the demo; licensed ones are not.
+        assert_eq!(sanitize(&instance, &[], &offline), vec!["--demo"]);
+        assert!(sanitize(&instance, &[], &premium).is_empty());
+    }
+
     #[test]
     fn sanitize_game_args_removes_demo_flags_in_all_supported_forms() {
         let mut instance = Instance::new(
@@ -1113,6 +1608,7 @@ mod tests {
             &args,
             std::path::Path::new("/tmp/game"),
             std::path::Path::new("/tmp/assets"),
+            std::path::Path::new("/tmp/natives"),
             &instance.account,
         );
 
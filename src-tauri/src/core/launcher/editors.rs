@@ -0,0 +1,585 @@
+// ─── Instance Editors ───
+// Per-instance settings edited from the instance screen: group and order,
+// launch config, window, wrapper, hooks, icon, account, pinned runtime,
+// JVM profiles and the optimizer. Unless noted, edits are allowed while
+// the game runs and apply from the next launch.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tracing::warn;
+
+use super::Launcher;
+use crate::core::auth::accounts::AccountBook;
+use crate::core::auth::LaunchAccountProfile;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::install;
+use crate::core::instance::{icon, ordering, Instance, LoaderType, WINDOW_SIZE_RANGE};
+use crate::core::java::{self, JavaInstallation};
+use crate::core::launch::optimizer::{self, OptimizationReport, OptimizeOptions};
+use crate::core::launch::{self, memory, JvmProfile, JvmProfileBook};
+use crate::core::loaders;
+use crate::core::state::AppState;
+
+/// Parameters for [`Launcher::update_launch_config`].
+#[derive(Debug, Clone)]
+pub struct LaunchConfigUpdate {
+    pub java_path: Option<String>,
+    pub max_memory_mb: u32,
+    pub jvm_args: Vec<String>,
+    pub game_args: Vec<String>,
+    /// Save `java_path` even when it is not a usable Java for the instance.
+    pub force: bool,
+}
+
+impl Launcher {
+    /// Load `id`, let `edit` change it and save it, all under the state lock.
+    async fn edit_instance(
+        &self,
+        id: &str,
+        edit: impl FnOnce(&AppState, &mut Instance) -> LauncherResult<()>,
+    ) -> LauncherResult<Instance> {
+        let state = self.state.lock().await;
+        let mut instance = state.instance_manager.load(id).await?;
+        edit(&state, &mut instance)?;
+        state.instance_manager.save(&instance).await?;
+        Ok(instance)
+    }
+
+    pub async fn set_instance_group(
+        &self,
+        id: &str,
+        group: Option<&str>,
+    ) -> LauncherResult<Instance> {
+        self.edit_instance(id, |_, instance| {
+            instance.group = ordering::normalize_group(group);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Rename a group (or ungroup its members when `to` is empty). Returns
+    /// the number of instances moved.
+    pub async fn rename_instance_group(
+        &self,
+        from: &str,
+        to: Option<&str>,
+    ) -> LauncherResult<usize> {
+        let state = self.state.lock().await;
+        state.instance_manager.rename_group(from.trim(), to).await
+    }
+
+    /// Persist a manual order: `ids` first, in that order, then everything else.
+    pub async fn reorder_instances(&self, ids: &[String]) -> LauncherResult<Vec<Instance>> {
+        self.state.lock().await.instance_manager.reorder(ids).await
+    }
+
+    /// Memory, arguments and Java of an instance. The new values are kept
+    /// when a running session ends.
+    pub async fn update_launch_config(
+        &self,
+        id: &str,
+        update: LaunchConfigUpdate,
+    ) -> LauncherResult<Instance> {
+        if update.max_memory_mb < 512 {
+            return Err(LauncherError::message(ErrorText::MemoryTooLow, &[]));
+        }
+        let jvm_args: Vec<String> = update
+            .jvm_args
+            .into_iter()
+            .filter(|arg| !arg.trim().is_empty())
+            .collect();
+        launch::validate_jvm_args(&jvm_args).map_err(LauncherError::InvalidJvmArgs)?;
+
+        self.edit_instance(id, |state, instance| {
+            instance.max_memory_mb = update.max_memory_mb;
+            instance.jvm_args = jvm_args;
+            instance.game_args = update
+                .game_args
+                .into_iter()
+                .filter(|arg| !arg.trim().is_empty())
+                .collect();
+            instance.java_path = check_instance_java_path(
+                instance,
+                update.java_path,
+                update.force,
+                state.launcher_settings.allow_newer_java_major,
+                java::runtime::inspect_java_binary,
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Set the game window size (`None` restores 1280x720) and whether the
+    /// game starts in fullscreen.
+    pub async fn update_window(
+        &self,
+        id: &str,
+        resolution: Option<(u32, u32)>,
+        start_fullscreen: bool,
+    ) -> LauncherResult<Instance> {
+        if let Some((width, height)) = resolution {
+            if !WINDOW_SIZE_RANGE.contains(&width) || !WINDOW_SIZE_RANGE.contains(&height) {
+                return Err(LauncherError::message(
+                    ErrorText::InvalidWindowSize,
+                    &[
+                        &width,
+                        &height,
+                        WINDOW_SIZE_RANGE.start(),
+                        WINDOW_SIZE_RANGE.end(),
+                    ],
+                ));
+            }
+        }
+
+        self.edit_instance(id, |_, instance| {
+            instance.resolution = resolution;
+            instance.start_fullscreen = start_fullscreen;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Allow demo mode for the instance; by default `--demo` flags are stripped.
+    pub async fn set_demo_mode(&self, id: &str, allow_demo_mode: bool) -> LauncherResult<Instance> {
+        self.edit_instance(id, |_, instance| {
+            instance.allow_demo_mode = allow_demo_mode;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Set or clear the wrapper program (`gamemoderun`, `prime-run`, …) used
+    /// to start the game. The program must be reachable on `PATH`.
+    pub async fn set_wrapper(
+        &self,
+        id: &str,
+        wrapper_command: Option<Vec<String>>,
+    ) -> LauncherResult<Instance> {
+        let wrapper_command = wrapper_command
+            .map(|parts| {
+                parts
+                    .into_iter()
+                    .map(|part| part.trim().to_string())
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|parts| !parts.is_empty());
+
+        if let Some(program) = wrapper_command.as_ref().and_then(|parts| parts.first()) {
+            if launch::find_program_on_path(program).is_none() {
+                return Err(LauncherError::message(
+                    ErrorText::WrapperNotFound,
+                    &[&program],
+                ));
+            }
+        }
+
+        self.edit_instance(id, |_, instance| {
+            instance.wrapper_command = wrapper_command;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Set or clear the shell commands run before launch and after the game
+    /// exits. Blank scripts clear the hook.
+    pub async fn set_hooks(
+        &self,
+        id: &str,
+        pre_launch_hook: Option<String>,
+        post_exit_hook: Option<String>,
+    ) -> LauncherResult<Instance> {
+        let normalize = |hook: Option<String>| {
+            hook.map(|script| script.trim().to_string())
+                .filter(|script| !script.is_empty())
+        };
+        self.edit_instance(id, |_, instance| {
+            instance.pre_launch_hook = normalize(pre_launch_hook);
+            instance.post_exit_hook = normalize(post_exit_hook);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Set the instance icon: a built-in id (`builtin:<name>`), a PNG/JPEG
+    /// file to copy into the instance, or `None` to clear it.
+    pub async fn set_icon(&self, id: &str, source: Option<&str>) -> LauncherResult<Instance> {
+        self.edit_instance(id, |_, instance| {
+            let custom_icon = instance.path.join(icon::ICON_FILE);
+            instance.icon = match source.map(str::trim) {
+                None | Some("") => None,
+                Some(builtin) if icon::is_builtin_icon(builtin) => Some(builtin.to_string()),
+                Some(path) => Some(icon::install_icon(&instance.path, Path::new(path))?),
+            };
+            if instance.icon.as_deref() != Some(icon::ICON_FILE) {
+                let _ = fs::remove_file(&custom_icon);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Custom icon of the instance as a `data:` URL.
+    pub async fn icon_data_url(&self, id: &str) -> LauncherResult<Option<String>> {
+        let instance = self.get_instance(id).await?;
+        tokio::task::spawn_blocking(move || instance.icon_data_url())
+            .await
+            .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))
+    }
+
+    /// Point the instance at the saved account `account_id`, or at the
+    /// `inline` profile. The running session keeps the account it started with.
+    pub async fn set_instance_account(
+        &self,
+        id: &str,
+        account_id: Option<&str>,
+        inline: Option<LaunchAccountProfile>,
+    ) -> LauncherResult<Instance> {
+        let inline = inline.map(LaunchAccountProfile::validated).transpose()?;
+        self.edit_instance(id, |state, instance| {
+            assign_instance_account(instance, &state.accounts, account_id, inline)
+        })
+        .await
+    }
+
+    /// Pin the instance to an installed managed runtime, or unpin it with
+    /// `None` so the newest compatible runtime is used again.
+    pub async fn pin_runtime(
+        &self,
+        id: &str,
+        identifier: Option<String>,
+    ) -> LauncherResult<Instance> {
+        let state = self.state.lock().await;
+        let mut instance = state.instance_manager.load(id).await?;
+
+        if let Some(identifier) = &identifier {
+            let manager = state.runtime_manager()?;
+            let known = manager
+                .list_runtimes(state.runtime_options())
+                .await?
+                .into_iter()
+                .any(|runtime| runtime.identifier == *identifier);
+            if !known {
+                return Err(LauncherError::message(
+                    ErrorText::RuntimeNotInstalled,
+                    &[&identifier],
+                ));
+            }
+        }
+
+        instance.pinned_runtime_identifier = identifier;
+        state.instance_manager.save(&instance).await?;
+        Ok(instance)
+    }
+
+    /// Reinstall the instance's loader from an installer JAR the user
+    /// downloaded beforehand, for when the loader's Maven is unreachable.
+    /// Runs the regular install, reporting `instance-create-*` progress.
+    pub async fn install_loader_from_jar(
+        &self,
+        id: &str,
+        installer_path: &Path,
+    ) -> LauncherResult<Instance> {
+        if !installer_path.is_file() {
+            return Err(LauncherError::message(
+                ErrorText::InstallerNotFound,
+                &[&installer_path.display()],
+            ));
+        }
+
+        // The install downloads libraries; other commands keep the lock.
+        let (detached, mut instance) = {
+            let state = self.state.lock().await;
+            if state.running_instances.contains_key(id) {
+                return Err(LauncherError::InstanceAlreadyRunning(id.to_string()));
+            }
+            (state.detached(), state.instance_manager.load(id).await?)
+        };
+        if !matches!(instance.loader, LoaderType::Forge | LoaderType::NeoForge) {
+            return Err(LauncherError::message(
+                ErrorText::LocalInstallerUnsupported,
+                &[&instance.loader],
+            ));
+        }
+        let contents = loaders::forge::ForgeInstallerContents::read(installer_path).await?;
+        if let Some(loader_version) = contents.loader_version() {
+            instance.loader_version = Some(loader_version);
+        }
+
+        install::install_created_instance(&self.events, &detached, instance, Some(installer_path))
+            .await
+    }
+
+    // ── JVM profiles ────────────────────────────────────
+
+    /// Built-in profiles followed by the user's own. With `instance_id` the
+    /// built-in ones are generated for that instance's Java.
+    pub async fn jvm_profiles(&self, instance_id: Option<&str>) -> LauncherResult<Vec<JvmProfile>> {
+        let state = self.state.lock().await;
+        let java_major = match instance_id {
+            Some(id) => optimizer::instance_java_major(&state.instance_manager.load(id).await?),
+            None => optimizer::LISTED_PROFILES_JAVA_MAJOR,
+        };
+        let mut profiles = optimizer::builtin_jvm_profiles(java_major);
+        profiles.extend(JvmProfileBook::load(&state.data_dir).profiles);
+        Ok(profiles)
+    }
+
+    /// Create or replace the user profile `name`.
+    pub async fn save_jvm_profile(
+        &self,
+        name: &str,
+        args: Vec<String>,
+    ) -> LauncherResult<JvmProfile> {
+        let args: Vec<String> = args
+            .into_iter()
+            .map(|arg| arg.trim().to_string())
+            .filter(|arg| !arg.is_empty())
+            .collect();
+        launch::validate_jvm_args(&args).map_err(LauncherError::InvalidJvmArgs)?;
+
+        let state = self.state.lock().await;
+        let mut book = JvmProfileBook::load(&state.data_dir);
+        let profile = book
+            .upsert(
+                &optimizer::builtin_jvm_profiles(optimizer::LISTED_PROFILES_JAVA_MAJOR),
+                name,
+                args,
+            )?
+            .clone();
+        book.save(&state.data_dir)?;
+        Ok(profile)
+    }
+
+    /// Merge the profile `name` into the instance's JVM arguments; see
+    /// [`optimizer::apply_jvm_profile`].
+    pub async fn apply_jvm_profile(&self, id: &str, name: &str) -> LauncherResult<Instance> {
+        self.edit_instance(id, |state, instance| {
+            let book = JvmProfileBook::load(&state.data_dir);
+            optimizer::apply_jvm_profile(instance, &book, name)
+        })
+        .await
+    }
+
+    // ── Optimizer ───────────────────────────────────────
+
+    /// What [`Launcher::optimize_instance`] would change. The instance, its
+    /// logs and `instance.json` are left as they are.
+    pub async fn preview_optimization(
+        &self,
+        id: &str,
+        options: &OptimizeOptions,
+    ) -> LauncherResult<(Instance, OptimizationReport)> {
+        let instance = self.get_instance(id).await?;
+        let memory = memory::detect_memory_suggestion();
+        optimizer::preview_optimization(&instance, options, memory.total_mb, memory.available_mb)
+    }
+
+    /// Apply the optimizer's memory and JVM arguments and clean old logs.
+    /// Refused while the game runs, since it still writes to `logs/`.
+    pub async fn optimize_instance(
+        &self,
+        id: &str,
+        options: &OptimizeOptions,
+    ) -> LauncherResult<(Instance, OptimizationReport)> {
+        let state = self.state.lock().await;
+        if state.running_instances.contains_key(id) {
+            return Err(LauncherError::InstanceAlreadyRunning(id.to_string()));
+        }
+        let instance = state.instance_manager.load(id).await?;
+
+        let memory = memory::detect_memory_suggestion();
+        let (instance, mut report) =
+            optimizer::plan_optimization(&instance, options, memory.total_mb, memory.available_mb)?;
+
+        let (removed_logs, freed_log_bytes) = optimizer::clean_old_logs(
+            &instance,
+            options.keep_log_days,
+            options.dry_run_logs,
+            SystemTime::now(),
+        );
+        if removed_logs > 0 && !options.dry_run_logs {
+            report.notes.push(format!(
+                "Se limpiaron {removed_logs} logs antiguos para reducir carga de disco."
+            ));
+        }
+        report.removed_logs = removed_logs;
+        report.freed_log_bytes = freed_log_bytes;
+        report.logs_dry_run = options.dry_run_logs;
+
+        state.instance_manager.save(&instance).await?;
+        Ok((instance, report))
+    }
+}
+
+/// Make sure `java_path` runs and matches the Java major the instance needs,
+/// so a wrong path is reported when it is saved rather than at launch. A
+/// blank path clears the override; `force` skips the check.
+fn check_instance_java_path(
+    instance: &Instance,
+    java_path: Option<String>,
+    force: bool,
+    allow_newer_major: bool,
+    inspect: impl Fn(&Path) -> Option<JavaInstallation>,
+) -> LauncherResult<Option<PathBuf>> {
+    let Some(java_path) = java_path
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from)
+    else {
+        return Ok(None);
+    };
+    if force {
+        return Ok(Some(java_path));
+    }
+
+    let Some(info) = inspect(&java_path) else {
+        return Err(LauncherError::message(
+            ErrorText::InvalidJavaExecutable,
+            &[&java_path.display()],
+        ));
+    };
+
+    let required_major = instance
+        .required_java_major
+        .unwrap_or_else(|| java::required_java_for_minecraft_version(&instance.minecraft_version));
+    if !java::is_java_compatible_for_loader(
+        info.major,
+        required_major,
+        &instance.loader,
+        allow_newer_major,
+    ) {
+        return Err(LauncherError::message(
+            ErrorText::JavaTooOld,
+            &[
+                &java_path.display(),
+                &info.major,
+                &instance.name,
+                &required_major,
+            ],
+        ));
+    }
+    Ok(Some(java_path))
+}
+
+/// Point `instance` at the saved account `account_id`, or at the `inline`
+/// profile. An id missing from the book falls back to `inline`, or to the
+/// instance's current inlined account when none is given.
+fn assign_instance_account(
+    instance: &mut Instance,
+    book: &AccountBook,
+    account_id: Option<&str>,
+    inline: Option<LaunchAccountProfile>,
+) -> LauncherResult<()> {
+    if let Some(saved) = account_id.and_then(|id| book.get(id)) {
+        instance.account = saved.profile.clone();
+        instance.account_id = Some(saved.id.clone());
+        return Ok(());
+    }
+
+    match (account_id, inline) {
+        (_, Some(account)) => instance.account = account,
+        (Some(id), None) => warn!(
+            "Account {} not found for instance {}; keeping its inlined account",
+            id, instance.id
+        ),
+        (None, None) => return Err(LauncherError::message(ErrorText::AccountRequired, &[])),
+    }
+    instance.account_id = None;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_java(major: u32) -> impl Fn(&Path) -> Option<JavaInstallation> {
+        move |path| {
+            (path == Path::new("/opt/jdk/bin/java")).then(|| JavaInstallation {
+                path: path.to_path_buf(),
+                version: format!("{major}.0.1"),
+                major,
+                is_64bit: true,
+                vendor: "Eclipse Adoptium".into(),
+                runtime_name: None,
+                java_vm_name: None,
+                has_shenandoah: false,
+                has_zgc: false,
+            })
+        }
+    }
+
+    #[test]
+    fn instance_java_path_must_be_a_java_of_the_required_major() {
+        let temp = std::env::temp_dir().join(format!("java-path-check-{}", std::process::id()));
+        let mut instance = Instance::new(
+            "Vanilla".into(),
+            "1.20.4".into(),
+            LoaderType::Vanilla,
+            None,
+            4096,
+            &temp,
+        );
+        instance.required_java_major = Some(17);
+        let java = || Some("/opt/jdk/bin/java".to_string());
+
+        let saved = check_instance_java_path(&instance, java(), false, false, fake_java(17));
+        assert_eq!(
+            saved.unwrap().as_deref(),
+            Some(Path::new("/opt/jdk/bin/java"))
+        );
+        assert_eq!(
+            check_instance_java_path(&instance, Some(" ".into()), false, false, fake_java(17))
+                .unwrap(),
+            None
+        );
+
+        let not_java = Some("/bin/true".to_string());
+        let rejected =
+            check_instance_java_path(&instance, not_java.clone(), false, false, fake_java(17));
+        assert!(rejected
+            .unwrap_err()
+            .to_string()
+            .contains("no es un ejecutable de Java"));
+        let forced = check_instance_java_path(&instance, not_java, true, false, fake_java(17));
+        assert_eq!(forced.unwrap().as_deref(), Some(Path::new("/bin/true")));
+
+        let wrong_major = check_instance_java_path(&instance, java(), false, false, fake_java(8));
+        assert!(wrong_major
+            .unwrap_err()
+            .to_string()
+            .contains("necesita Java 17"));
+    }
+
+    #[test]
+    fn instance_account_prefers_saved_id_and_keeps_inlined_fallback() {
+        let mut book = AccountBook::default();
+        let saved_id = book.add(LaunchAccountProfile::offline("Saved")).id.clone();
+        let mut instance = Instance::new(
+            "Accounts".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &std::env::temp_dir(),
+        );
+
+        assign_instance_account(&mut instance, &book, Some(&saved_id), None).unwrap();
+        assert_eq!(instance.account.username, "Saved");
+        assert_eq!(instance.account_id.as_deref(), Some(saved_id.as_str()));
+
+        // An unknown id keeps the current inlined copy.
+        assign_instance_account(&mut instance, &book, Some("gone"), None).unwrap();
+        assert_eq!(instance.account.username, "Saved");
+        assert_eq!(instance.account_id, None);
+
+        let inline = LaunchAccountProfile::offline("Inline");
+        assign_instance_account(&mut instance, &book, Some("gone"), Some(inline)).unwrap();
+        assert_eq!(instance.account.username, "Inline");
+
+        assert!(assign_instance_account(&mut instance, &book, None, None).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+// ─── Instance Files ───
+// Files inside an instance the launcher reads and edits for the user:
+// `options.txt` and other text configs, screenshots, the server list,
+// world backups, shortcuts and the logs left by the last session.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use super::Launcher;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{config_files, screenshots};
+use crate::core::launch::{self, session};
+use crate::core::servers;
+use crate::core::shortcuts;
+use crate::core::worlds;
+
+impl Launcher {
+    pub async fn read_game_options(
+        &self,
+        id: &str,
+    ) -> LauncherResult<Vec<config_files::GameOption>> {
+        let instance = self.get_instance(id).await?;
+        config_files::read_game_options(&instance)
+    }
+
+    /// Set the given `options.txt` keys; other options are kept. Refused
+    /// while the game runs, since it rewrites the file when it closes.
+    pub async fn write_game_options(
+        &self,
+        id: &str,
+        options: &BTreeMap<String, String>,
+    ) -> LauncherResult<Vec<config_files::GameOption>> {
+        let state = self.state.lock().await;
+        if state.running_instances.contains_key(id) {
+            return Err(LauncherError::InstanceAlreadyRunning(id.to_string()));
+        }
+        let instance = state.instance_manager.load(id).await?;
+        config_files::write_game_options(&instance, options)
+    }
+
+    /// Text file of the instance, by path relative to the instance directory.
+    pub async fn read_text_config(&self, id: &str, path: &str) -> LauncherResult<String> {
+        let instance = self.get_instance(id).await?;
+        config_files::read_text_config(&instance, path)
+    }
+
+    pub async fn write_text_config(
+        &self,
+        id: &str,
+        path: &str,
+        contents: &str,
+    ) -> LauncherResult<()> {
+        let instance = self.get_instance(id).await?;
+        config_files::write_text_config(&instance, path, contents)?;
+        info!("Wrote {} in instance {}", path, id);
+        Ok(())
+    }
+
+    // ── Screenshots ─────────────────────────────────────
+
+    pub async fn list_screenshots(&self, id: &str) -> LauncherResult<Vec<screenshots::Screenshot>> {
+        let instance = self.get_instance(id).await?;
+        screenshots::list_screenshots(&instance.screenshots_dir())
+    }
+
+    pub async fn delete_screenshot(&self, id: &str, file: &str) -> LauncherResult<()> {
+        let instance = self.get_instance(id).await?;
+        screenshots::delete_screenshot(&instance.screenshots_dir(), file)?;
+        info!("Deleted screenshot {} of instance {}", file, id);
+        Ok(())
+    }
+
+    /// Thumbnail as a `data:image/png` URL; `max_edge` is capped at
+    /// [`screenshots::MAX_THUMBNAIL_EDGE`].
+    pub async fn screenshot_thumbnail(
+        &self,
+        id: &str,
+        file: &str,
+        max_edge: u32,
+    ) -> LauncherResult<String> {
+        let screenshots_dir = self.get_instance(id).await?.screenshots_dir();
+        let file = file.to_string();
+        // Decoding a full-size screenshot takes a while; keep it off the async workers.
+        tokio::task::spawn_blocking(move || {
+            screenshots::screenshot_thumbnail(&screenshots_dir, &file, max_edge)
+        })
+        .await
+        .map_err(|e| LauncherError::message(ErrorText::ThumbnailFailed, &[&e]))?
+    }
+
+    // ── Servers ─────────────────────────────────────────
+
+    /// Multiplayer servers saved in the instance's `servers.dat`.
+    pub async fn list_servers(&self, id: &str) -> LauncherResult<Vec<servers::ServerEntry>> {
+        let instance = self.get_instance(id).await?;
+        servers::list_servers(&instance.game_dir())
+    }
+
+    pub async fn add_server(
+        &self,
+        id: &str,
+        name: &str,
+        address: &str,
+    ) -> LauncherResult<Vec<servers::ServerEntry>> {
+        let instance = self.get_instance(id).await?;
+        servers::add_server(&instance.game_dir(), name, address)
+    }
+
+    pub async fn remove_server(
+        &self,
+        id: &str,
+        index: usize,
+    ) -> LauncherResult<Vec<servers::ServerEntry>> {
+        let instance = self.get_instance(id).await?;
+        servers::remove_server(&instance.game_dir(), index)
+    }
+
+    // ── Worlds ──────────────────────────────────────────
+
+    /// Zip `saves/<world_folder>` into the instance's `backups/` folder.
+    /// Progress is reported as `world-backup-progress`.
+    pub async fn backup_world(&self, id: &str, world_folder: &str) -> LauncherResult<PathBuf> {
+        let instance = self.get_instance(id).await?;
+        let saves_dir = instance.saves_dir();
+        let backups_dir = instance.backups_dir();
+        let world_folder = world_folder.to_string();
+
+        let progress =
+            worlds::progress_emitter(self.events.clone(), id.to_string(), world_folder.clone());
+        let archive = tokio::task::spawn_blocking(move || {
+            worlds::backup_world(&saves_dir, &backups_dir, &world_folder, progress)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+        info!("Backed up world of {} to {:?}", id, archive);
+        Ok(archive)
+    }
+
+    /// Extract a world backup into `saves/`. An existing world is only
+    /// replaced when `overwrite` is set, and never while the instance runs.
+    /// Returns the restored world folder.
+    pub async fn restore_world(
+        &self,
+        id: &str,
+        backup: &Path,
+        overwrite: bool,
+    ) -> LauncherResult<String> {
+        let instance = {
+            let state = self.state.lock().await;
+            if state.running_instances.contains_key(id) {
+                return Err(LauncherError::message(ErrorText::CloseBeforeRestore, &[]));
+            }
+            state.instance_manager.load(id).await?
+        };
+        let saves_dir = instance.saves_dir();
+        let backups_dir = instance.backups_dir();
+        let backup = backup.to_path_buf();
+        let label = backup
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let progress = worlds::progress_emitter(self.events.clone(), id.to_string(), label);
+        let world = tokio::task::spawn_blocking(move || {
+            worlds::restore_world(&saves_dir, &backups_dir, &backup, overwrite, progress)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+        info!("Restored world {} of {}", world, id);
+        Ok(world)
+    }
+
+    pub async fn list_world_backups(&self, id: &str) -> LauncherResult<Vec<worlds::WorldBackup>> {
+        let instance = self.get_instance(id).await?;
+        worlds::list_world_backups(&instance.backups_dir())
+    }
+
+    // ── Shortcuts and logs ──────────────────────────────
+
+    /// Create a desktop/menu shortcut that starts the instance directly.
+    /// Returns the files written.
+    pub async fn create_instance_shortcut(&self, id: &str) -> LauncherResult<Vec<PathBuf>> {
+        let instance = self.get_instance(id).await?;
+        let shortcut = shortcuts::Shortcut::instance(&instance)?;
+        let written = shortcuts::create_shortcut(&shortcut)?;
+        info!("Created shortcuts for instance {}: {:?}", id, written);
+        Ok(written)
+    }
+
+    /// Newest crash report of an instance (or the log tail when there is none).
+    pub async fn latest_crash_report(
+        &self,
+        id: &str,
+    ) -> LauncherResult<Option<launch::CrashReportSummary>> {
+        let state = self.state.lock().await;
+        let instance = state.instance_manager.load(id).await?;
+        let fallback_logs = session::crash_fallback_logs(&instance, &state.launcher_logs_dir());
+        Ok(launch::summarize_crash(
+            &instance.game_dir(),
+            &fallback_logs,
+            None,
+        ))
+    }
+
+    /// The persisted game log of an instance. Fails when no session of it
+    /// has been logged yet.
+    pub async fn instance_log_path(&self, id: &str) -> LauncherResult<PathBuf> {
+        let logs_dir = self.state.lock().await.launcher_logs_dir();
+        let log_path = launch::instance_log_path(&logs_dir, id);
+        if !log_path.exists() {
+            return Err(LauncherError::message(ErrorText::NoSavedLog, &[&id]));
+        }
+        Ok(log_path)
+    }
+}
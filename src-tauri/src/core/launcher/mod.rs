@@ -0,0 +1,1040 @@
+// ─── Launcher ───
+// Headless entry point to the backend. Owns the shared `AppState` and an
+// `EventEmitter`, so the Tauri commands, integration tests and the CLI
+// drive the same code. Instance lifecycle, launching, mods, accounts and
+// settings are here; the per-instance editors, instance files, running
+// instances, Java runtimes, version lists and setup are in the child
+// modules. The install and launch pipelines live in `core::install` and
+// `core::launch`.
+//
+// One `Launcher` per data dir. Running instances are tracked in memory and
+// settings / instance.json are rewritten without cross-process locking, so
+// two launchers on the same dir diverge and race. The desktop app enforces
+// this with the single-instance plugin; the CLI and other library callers
+// must serialize access themselves (one process at a time, sharing clones
+// of a single `Launcher` within it).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::core::auth::accounts::AccountBook;
+use crate::core::auth::profile::{self, AccountProfile};
+use crate::core::auth::LaunchAccountProfile;
+use crate::core::deep_link::{self, DeepLink, LaunchRequest};
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::events::EventEmitter;
+use crate::core::i18n::{LogKey, MessageKey};
+use crate::core::install;
+use crate::core::install_progress;
+use crate::core::instance::transfer::{self, CloneOptions};
+use crate::core::instance::{ordering, play_stats, verify, Instance, InstanceState, LoaderType};
+use crate::core::java;
+use crate::core::launch::{self, events, memory, session};
+use crate::core::loaders::versions;
+use crate::core::long_path::long_path;
+use crate::core::modpack::{self, ModpackFormat};
+use crate::core::mods::{companion, toggle, updates};
+use crate::core::state::{AppState, LaunchCancellations, SettingsSnapshot, SettingsUpdate};
+
+mod editors;
+mod files;
+mod processes;
+mod runtimes;
+mod setup;
+mod version_lists;
+
+pub use editors::LaunchConfigUpdate;
+pub use processes::{BatchOutcome, DeleteInstanceResponse, LAUNCH_GROUP_DELAY_SECS};
+
+/// Parameters for [`Launcher::create_instance`].
+#[derive(Debug, Clone)]
+pub struct NewInstance {
+    pub name: String,
+    pub minecraft_version: String,
+    pub loader: LoaderType,
+    pub loader_version: Option<String>,
+    /// Defaults to the suggestion for this machine's memory.
+    pub memory_max_mb: Option<u32>,
+}
+
+/// Cheap to clone; clones share the same state. See the module notes on
+/// running one launcher per data dir.
+#[derive(Clone)]
+pub struct Launcher {
+    state: Arc<Mutex<AppState>>,
+    cancellations: Arc<LaunchCancellations>,
+    events: Arc<dyn EventEmitter>,
+}
+
+impl Launcher {
+    pub fn new(state: AppState, events: Arc<dyn EventEmitter>) -> Self {
+        Self {
+            cancellations: state.launch_cancellations.clone(),
+            state: Arc::new(Mutex::new(state)),
+            events,
+        }
+    }
+
+    /// Open the launcher data in `data_dir` without a Tauri app.
+    pub async fn open(data_dir: PathBuf, events: Arc<dyn EventEmitter>) -> Self {
+        let state = AppState::open(data_dir, events.clone()).await;
+        Self::new(state, events)
+    }
+
+    pub fn state(&self) -> Arc<Mutex<AppState>> {
+        self.state.clone()
+    }
+
+    /// Instances in display order.
+    pub async fn list_instances(&self) -> LauncherResult<Vec<Instance>> {
+        self.state.lock().await.instance_manager.list_sorted().await
+    }
+
+    pub async fn get_instance(&self, id: &str) -> LauncherResult<Instance> {
+        self.state.lock().await.instance_manager.load(id).await
+    }
+
+    /// Validate the loader version and create the instance folders on disk.
+    /// Nothing is downloaded until [`Launcher::install_instance`]. The
+    /// instance starts with the default saved account, if any.
+    pub async fn create_instance(&self, request: NewInstance) -> LauncherResult<Instance> {
+        // The loader metadata may be fetched; other commands keep the lock.
+        let detached = self.state.lock().await.detached();
+        versions::validate_loader_version(
+            self.events.as_ref(),
+            &detached,
+            &request.loader,
+            &request.minecraft_version,
+            request.loader_version.as_deref(),
+        )
+        .await?;
+
+        let (instance, locale) = {
+            let state = self.state.lock().await;
+            let instance = state
+                .instance_manager
+                .create(new_instance(&state, request))
+                .await?;
+            (instance, state.launcher_settings.language)
+        };
+        events::emit_create_progress(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            8,
+            MessageKey::CreateStructureCreated,
+            "running",
+        );
+        events::emit_create_message(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            "info",
+            LogKey::CreateStarted,
+            &[],
+        );
+        Ok(instance)
+    }
+
+    /// Create an instance from a CurseForge or Modrinth pack archive, fetch
+    /// its files and install it like [`Launcher::create_instance`] followed
+    /// by [`Launcher::install_instance`]. A pack that fails to download is
+    /// removed again.
+    pub async fn import_modpack(
+        &self,
+        format: ModpackFormat,
+        archive: &Path,
+    ) -> LauncherResult<Instance> {
+        let plan = modpack::read_modpack(format, archive).await?;
+
+        let (instance, locale, client, downloader) = {
+            let state = self.state.lock().await;
+            let request = NewInstance {
+                name: plan.name.clone(),
+                minecraft_version: plan.minecraft_version.clone(),
+                loader: plan.loader.clone(),
+                loader_version: plan.loader_version.clone(),
+                memory_max_mb: plan.memory_max_mb,
+            };
+            let instance = state
+                .instance_manager
+                .create(new_instance(&state, request))
+                .await?;
+            (
+                instance,
+                state.launcher_settings.language,
+                state.http_client.clone(),
+                state.downloader.clone(),
+            )
+        };
+        events::emit_create_progress(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            8,
+            MessageKey::CreateStructureCreated,
+            "running",
+        );
+        events::emit_create_message(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            "info",
+            LogKey::CreateStarted,
+            &[],
+        );
+
+        // Packs can hold hundreds of files; other commands keep the lock.
+        let on_file = |done: usize, total: usize| {
+            events::emit_create_progress(
+                self.events.as_ref(),
+                locale,
+                &instance.id,
+                install_progress::weighted_percent(8, 15, &[(1, done as f64 / total as f64)]),
+                MessageKey::CreateDownloadingModpack,
+                "running",
+            );
+        };
+        let populated =
+            modpack::populate_instance(&plan, archive, &instance, &client, &downloader, &on_file)
+                .await;
+        let skipped = match populated {
+            Ok(skipped) => skipped,
+            Err(err) => {
+                events::emit_create_progress(
+                    self.events.as_ref(),
+                    locale,
+                    &instance.id,
+                    100,
+                    MessageKey::CreateFailed,
+                    "error",
+                );
+                events::emit_create_error(
+                    self.events.as_ref(),
+                    locale,
+                    &instance.id,
+                    LogKey::CreateFailed,
+                    &err,
+                );
+                let state = self.state.lock().await;
+                if let Err(cleanup_err) = state.instance_manager.delete(&instance.id).await {
+                    warn!(
+                        "Cannot remove partially imported instance {}: {}",
+                        instance.id, cleanup_err
+                    );
+                }
+                return Err(err);
+            }
+        };
+        let mods_dir = instance.mods_dir();
+        for file in &skipped {
+            events::emit_create_message(
+                self.events.as_ref(),
+                locale,
+                &instance.id,
+                "warn",
+                LogKey::CreateModpackFileSkipped,
+                &[&file.project_id, &file.file_id, &mods_dir.display()],
+            );
+        }
+
+        events::emit_create_progress(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            15,
+            MessageKey::CreateModpackImported,
+            "running",
+        );
+        events::emit_create_message(
+            self.events.as_ref(),
+            locale,
+            &instance.id,
+            "info",
+            LogKey::CreateModpackImported,
+            &[
+                &format.label(),
+                &instance.name,
+                &instance.loader,
+                &instance.loader_version.as_deref().unwrap_or("-"),
+            ],
+        );
+
+        self.install_instance(&instance.id).await
+    }
+
+    /// Install Vanilla, the loader and the assets for an instance.
+    pub async fn install_instance(&self, id: &str) -> LauncherResult<Instance> {
+        // Downloads run on a detached copy; other commands keep the lock.
+        let (detached, instance) = {
+            let state = self.state.lock().await;
+            (state.detached(), state.instance_manager.load(id).await?)
+        };
+        install::install_created_instance(&self.events, &detached, instance, None).await
+    }
+
+    /// Rebuild the metadata of a salvaged instance from its folder and
+    /// reinstall it. Instances with a readable `instance.json` are returned
+    /// as they are.
+    pub async fn recover_instance(&self, id: &str) -> LauncherResult<Instance> {
+        let (detached, needs_recovery, instance) = {
+            let state = self.state.lock().await;
+            let needs_recovery = state.instance_manager.load(id).await?.needs_recovery;
+            let instance = state.instance_manager.recover(id).await?;
+            (state.detached(), needs_recovery, instance)
+        };
+        if !needs_recovery {
+            return Ok(instance);
+        }
+        install::install_created_instance(&self.events, &detached, instance, None).await
+    }
+
+    /// Stop the instance if it is running and remove it from disk.
+    pub async fn delete_instance(&self, id: &str) -> LauncherResult<()> {
+        let mut state = self.state.lock().await;
+        if let Some(running) = state.running_instances.remove(id) {
+            launch::stop_process(running.pid, launch::FORCE_STOP_GRACE).await?;
+        }
+        state.instance_manager.delete(id).await?;
+        info!("Deleted instance {}", id);
+        Ok(())
+    }
+
+    /// Check the files and launch metadata of an installed instance without
+    /// starting it. With `repair`, broken files that have a known download
+    /// URL are fetched again and dropped from the report.
+    pub async fn verify_instance(
+        &self,
+        id: &str,
+        repair: bool,
+    ) -> LauncherResult<verify::VerifyReport> {
+        // Hashing every file takes a while; other commands keep the lock.
+        let (instance, libs_dir, downloader) = {
+            let state = self.state.lock().await;
+            let instance = state.instance_manager.load(id).await?;
+            (instance, state.libraries_dir(), state.downloader.clone())
+        };
+        let mut report =
+            verify::verify_instance(&instance, &libs_dir, downloader.max_concurrency()).await?;
+        if repair && !report.is_clean() {
+            let repaired = verify::repair(&mut report, downloader.as_ref()).await;
+            info!("Repaired {} files of instance {}", repaired, id);
+        }
+        info!(
+            "Verified instance {}: {} files checked, {} issues",
+            id,
+            report.checked,
+            report.issues.len()
+        );
+        Ok(report)
+    }
+
+    /// Copy an instance under a new id, leaving out the folders `options`
+    /// excludes. Progress is reported as `instance-clone-progress`.
+    pub async fn clone_instance(
+        &self,
+        id: &str,
+        options: CloneOptions,
+    ) -> LauncherResult<Instance> {
+        // Copying can take minutes; other commands keep the lock meanwhile.
+        let (source, cloned) = {
+            let state = self.state.lock().await;
+            let source = state.instance_manager.load(id).await?;
+
+            let mut cloned = source.clone();
+            cloned.id = Uuid::new_v4().to_string();
+            cloned.name = format!("{} (Copia)", source.name);
+            cloned.path = state.instances_dir().join(&cloned.id);
+            cloned.state = InstanceState::Ready;
+            cloned.last_played = None;
+            cloned.total_play_seconds = 0;
+            cloned.launch_count = 0;
+            cloned.created_at = Utc::now();
+            cloned.sort_index = ordering::next_sort_index(&state.instance_manager.list().await?);
+            (source, cloned)
+        };
+
+        let source_dir = long_path(&source.path);
+        let clone_dir = long_path(&cloned.path);
+        let excluded = options.excluded_dirs(&source_dir);
+        let progress = transfer::clone_progress_emitter(
+            self.events.clone(),
+            source.id.clone(),
+            cloned.id.clone(),
+        );
+        tokio::task::spawn_blocking(move || {
+            transfer::copy_instance_dir(&source_dir, &clone_dir, &excluded, progress)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+        self.state
+            .lock()
+            .await
+            .instance_manager
+            .save(&cloned)
+            .await?;
+        info!("Cloned instance {} into {}", source.id, cloned.id);
+        Ok(cloned)
+    }
+
+    /// Zip an instance into `target` without credentials, launch commands or
+    /// files the installers download again. Returns the number of files.
+    pub async fn export_instance(&self, id: &str, target: &Path) -> LauncherResult<usize> {
+        let instance = self.get_instance(id).await?;
+        let source_dir = instance.path.clone();
+        let archive = target.to_path_buf();
+        let written = tokio::task::spawn_blocking(move || {
+            transfer::write_instance_archive(&source_dir, &archive)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+        info!(
+            "Exported instance {} ({} files) to {:?}",
+            instance.id, written, target
+        );
+        Ok(written)
+    }
+
+    /// Unpack an archive written by [`Launcher::export_instance`] as a new
+    /// instance.
+    pub async fn import_instance(&self, archive: &Path) -> LauncherResult<Instance> {
+        // Unpacking can take a while; other commands keep the lock.
+        let (instance_manager, instances_dir, sort_index) = {
+            let state = self.state.lock().await;
+            let sort_index = ordering::next_sort_index(&state.instance_manager.list().await?);
+            (
+                state.instance_manager.clone(),
+                state.instances_dir(),
+                sort_index,
+            )
+        };
+        let archive = archive.to_path_buf();
+
+        let instance = tokio::task::spawn_blocking(move || {
+            transfer::read_instance_archive(&archive, &instances_dir, sort_index)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+        if let Err(err) = instance_manager.verify_structure(&instance).await {
+            let _ = tokio::fs::remove_dir_all(&instance.path).await;
+            return Err(err);
+        }
+        info!("Imported instance '{}' as {}", instance.name, instance.id);
+        Ok(instance)
+    }
+
+    /// Prepare and start the game. Returns once the process is spawned; a
+    /// background task tracks it until it exits.
+    pub async fn launch_instance(&self, id: &str) -> LauncherResult<()> {
+        // Registering is atomic and needs no state lock, which a preparing
+        // launch holds, so of two racing launches only one gets past here.
+        let Some(cancel_guard) = self.cancellations.try_register(id) else {
+            warn!("Refusing to launch {}: a launch is already preparing", id);
+            return Err(LauncherError::InstanceAlreadyRunning(id.to_string()));
+        };
+        if self.is_running(id).await {
+            return Err(LauncherError::InstanceAlreadyRunning(id.to_string()));
+        }
+        let previous_state = self
+            .get_instance(id)
+            .await
+            .map(|instance| instance.state)
+            .ok();
+
+        let result = session::start_instance(
+            self.events.clone(),
+            self.state.clone(),
+            id.to_string(),
+            cancel_guard,
+        )
+        .await;
+        if matches!(result, Err(LauncherError::Cancelled)) {
+            let state = self.state.lock().await;
+            if let Ok(mut instance) = state.instance_manager.load(id).await {
+                instance.state = match previous_state {
+                    Some(InstanceState::Error) => InstanceState::Error,
+                    _ => InstanceState::Ready,
+                };
+                if let Err(err) = state.instance_manager.save(&instance).await {
+                    error!("Cannot restore state for cancelled launch {}: {}", id, err);
+                }
+            }
+            let locale = state.launcher_settings.language;
+            events::emit_launch_progress(
+                self.events.as_ref(),
+                locale,
+                id,
+                0,
+                MessageKey::LaunchCancelled,
+                "cancelled",
+            );
+            events::emit_launch_message(
+                self.events.as_ref(),
+                locale,
+                id,
+                "warn",
+                LogKey::LaunchCancelled,
+                &[],
+            );
+            self.events.emit("launch-cancelled", id);
+        }
+
+        result
+    }
+
+    /// Act on an `interface://` URI. Nothing starts from the link itself: a
+    /// launch link of a known instance is handed to the frontend as
+    /// [`deep_link::LAUNCH_REQUEST_EVENT`], which asks the user and then
+    /// launches it like any other instance. Unknown ids are rejected.
+    ///
+    /// The request is also kept as pending until [`Self::take_pending_deep_link`],
+    /// so a link that arrives before the window listens is not lost.
+    pub async fn open_deep_link(&self, uri: &str) -> LauncherResult<DeepLink> {
+        let link = deep_link::parse_deep_link(uri)?;
+        match &link {
+            DeepLink::Launch { id } => {
+                let request = {
+                    let mut state = self.state.lock().await;
+                    let instance = state.instance_manager.load(id).await?;
+                    let request = LaunchRequest {
+                        uri: uri.to_string(),
+                        id: id.clone(),
+                        name: instance.name,
+                    };
+                    state.pending_deep_link = Some(request.clone());
+                    request
+                };
+                info!("Deep link asks to launch {}; waiting for confirmation", id);
+                self.events.emit(deep_link::LAUNCH_REQUEST_EVENT, &request);
+            }
+        }
+        Ok(link)
+    }
+
+    /// Launch request from a deep link the frontend has not picked up yet.
+    /// Taking it clears it, so each link is confirmed at most once.
+    pub async fn take_pending_deep_link(&self) -> Option<LaunchRequest> {
+        self.state.lock().await.pending_deep_link.take()
+    }
+
+    /// Signal a launch that is still preparing (downloads, Java resolution).
+    /// Returns `false` when the instance has no launch in preparation.
+    pub fn cancel_launch(&self, id: &str) -> bool {
+        let cancelled = self.cancellations.cancel(id);
+        if cancelled {
+            info!("Cancellation requested for launch of {}", id);
+        }
+        cancelled
+    }
+
+    /// Whether the game of `id` is still running.
+    pub async fn is_running(&self, id: &str) -> bool {
+        self.state.lock().await.running_instances.contains_key(id)
+    }
+
+    /// Make sure a managed Java suitable for `minecraft_version` is
+    /// installed, downloading it when needed. Returns the Java binary.
+    pub async fn install_java(&self, minecraft_version: &str) -> LauncherResult<PathBuf> {
+        let state = self.state.lock().await;
+        let required_java_major = java::required_java_for_minecraft_version(minecraft_version);
+
+        // Forward progress only when the phase or whole percentage changes so a
+        // 200 MB runtime does not flood the listeners with per-chunk events.
+        let events = self.events.clone();
+        let last_emitted = std::sync::Mutex::new(None);
+        let on_progress = move |progress: java::runtime::JavaInstallProgress| {
+            let percent = progress
+                .total_bytes
+                .filter(|total| *total > 0)
+                .map(|total| (progress.downloaded_bytes.saturating_mul(100) / total).min(100));
+            let key = (progress.phase, percent);
+            let Ok(mut last) = last_emitted.lock() else {
+                return;
+            };
+            if *last == Some(key) {
+                return;
+            }
+            *last = Some(key);
+            events.emit("java-install-progress", progress);
+        };
+        java::resolve_java_binary_in_dir(
+            &state.data_dir,
+            required_java_major,
+            state.runtime_options(),
+            Some(&on_progress),
+        )
+        .await
+    }
+
+    pub async fn play_stats(&self) -> LauncherResult<play_stats::PlayStats> {
+        let instances = self.state.lock().await.instance_manager.list().await?;
+        Ok(play_stats::aggregate(&instances))
+    }
+
+    // ── Mods ────────────────────────────────────────────
+
+    /// Jars in the instance `mods/` folder, including disabled ones.
+    pub async fn list_mods(&self, id: &str) -> LauncherResult<Vec<toggle::ModEntry>> {
+        let instance = self.get_instance(id).await?;
+        toggle::list_mods(&instance.mods_dir())
+    }
+
+    pub async fn disable_mod(
+        &self,
+        id: &str,
+        file_name: &str,
+    ) -> LauncherResult<Vec<toggle::ModEntry>> {
+        let instance = self.get_instance(id).await?;
+        toggle::disable_mod(&instance.mods_dir(), file_name)?;
+        toggle::list_mods(&instance.mods_dir())
+    }
+
+    pub async fn enable_mod(
+        &self,
+        id: &str,
+        file_name: &str,
+    ) -> LauncherResult<Vec<toggle::ModEntry>> {
+        let instance = self.get_instance(id).await?;
+        toggle::enable_mod(&instance.mods_dir(), file_name)?;
+        toggle::list_mods(&instance.mods_dir())
+    }
+
+    /// Whether the instance's mods need the loader API mod (Fabric API,
+    /// QFAPI/QSL) and it is installed.
+    pub async fn loader_companion_status(
+        &self,
+        id: &str,
+    ) -> LauncherResult<companion::CompanionStatus> {
+        let state = self.state.lock().await;
+        let instance = state.instance_manager.load(id).await?;
+        let mods = companion::installed_mods(&instance.mods_dir());
+        Ok(companion::companion_status(
+            &instance.loader,
+            &mods,
+            state.launcher_settings.language,
+        ))
+    }
+
+    /// Install the loader API mod build matching the instance; see
+    /// [`companion::ensure_companion`].
+    pub async fn ensure_loader_companion(&self, id: &str) -> LauncherResult<Option<String>> {
+        // Modrinth lookups and the download run without the lock.
+        let (instance, client, downloader, locale) = {
+            let state = self.state.lock().await;
+            let instance = state.instance_manager.load(id).await?;
+            (
+                instance,
+                state.http_client.clone(),
+                state.downloader.clone(),
+                state.launcher_settings.language,
+            )
+        };
+        companion::ensure_companion(&client, &downloader, &instance, locale).await
+    }
+
+    /// Newer Modrinth versions of the mods of an instance.
+    pub async fn check_mod_updates(&self, id: &str) -> LauncherResult<Vec<updates::ModUpdate>> {
+        let (instance, client) = {
+            let state = self.state.lock().await;
+            let instance = state.instance_manager.load(id).await?;
+            (instance, state.http_client.clone())
+        };
+        updates::check_instance_updates(&client, &instance).await
+    }
+
+    /// Replace a mod with its newest compatible version; see
+    /// [`updates::update_instance_mod`].
+    pub async fn update_mod(
+        &self,
+        id: &str,
+        project_id: &str,
+    ) -> LauncherResult<updates::ModUpdate> {
+        let (instance, client, downloader) = {
+            let state = self.state.lock().await;
+            let instance = state.instance_manager.load(id).await?;
+            (
+                instance,
+                state.http_client.clone(),
+                state.downloader.clone(),
+            )
+        };
+        updates::update_instance_mod(&client, &downloader, &instance, project_id).await
+    }
+
+    // ── Accounts ────────────────────────────────────────
+
+    pub async fn accounts(&self) -> AccountBook {
+        self.state.lock().await.accounts.clone()
+    }
+
+    /// Save an account. The ownership of a Microsoft account is checked
+    /// first so the UI can tell a demo-only account apart.
+    pub async fn add_account(&self, profile: LaunchAccountProfile) -> LauncherResult<AccountBook> {
+        let mut profile = profile.validated()?;
+        let (data_dir, client) = {
+            let state = self.state.lock().await;
+            (state.data_dir.clone(), state.http_client.clone())
+        };
+        session::check_account_ownership(&data_dir, &client, &mut profile).await;
+        let mut state = self.state.lock().await;
+        state.accounts.add(profile);
+        state.accounts.save(&state.data_dir)?;
+        Ok(state.accounts.clone())
+    }
+
+    /// Instances that used the account keep launching with their inlined copy.
+    pub async fn remove_account(&self, id: &str) -> LauncherResult<AccountBook> {
+        let mut state = self.state.lock().await;
+        state.accounts.remove(id)?;
+        state.accounts.save(&state.data_dir)?;
+        Ok(state.accounts.clone())
+    }
+
+    pub async fn set_default_account(&self, id: &str) -> LauncherResult<AccountBook> {
+        let mut state = self.state.lock().await;
+        state.accounts.set_default(id)?;
+        state.accounts.save(&state.data_dir)?;
+        Ok(state.accounts.clone())
+    }
+
+    /// Skin and cape of a Microsoft account, cached per uuid. Offline, the
+    /// last known profile is returned with `stale` set.
+    pub async fn account_profile(&self, uuid: &str) -> LauncherResult<AccountProfile> {
+        let uuid = profile::normalize_uuid(uuid)?;
+        // The session server may be slow or rate-limited; don't hold the state lock meanwhile.
+        let (data_dir, client, cache) = {
+            let state = self.state.lock().await;
+            (
+                state.data_dir.clone(),
+                state.http_client.clone(),
+                state.profile_cache.clone(),
+            )
+        };
+
+        profile::resolve_cached(&cache, &data_dir, &uuid, Utc::now(), || {
+            profile::fetch_session_profile(&client, &uuid)
+        })
+        .await
+    }
+
+    // ── Settings ────────────────────────────────────────
+
+    pub async fn settings(&self) -> SettingsSnapshot {
+        self.state.lock().await.settings_snapshot()
+    }
+
+    pub async fn update_settings(
+        &self,
+        update: SettingsUpdate,
+    ) -> LauncherResult<SettingsSnapshot> {
+        let mut state = self.state.lock().await;
+        state.update_settings(update)?;
+        Ok(state.settings_snapshot())
+    }
+
+    /// Move the launcher data to `target` and point the saved paths of
+    /// instances and runtimes at it.
+    pub async fn migrate_data_dir(&self, target: PathBuf) -> LauncherResult<SettingsSnapshot> {
+        let mut state = self.state.lock().await;
+        let old_dir = state.data_dir.clone();
+        let migrated_to = state.migrate_data_dir(target)?;
+        if migrated_to != old_dir {
+            state.relocate_data_dir_references(&old_dir).await?;
+        }
+        Ok(state.settings_snapshot())
+    }
+}
+
+/// The instance for `request` in the instances folder, with the memory
+/// suggestion for this machine unless the request sets one and the default
+/// saved account, if any.
+fn new_instance(state: &AppState, request: NewInstance) -> Instance {
+    let mut instance = Instance::new(
+        request.name,
+        request.minecraft_version,
+        request.loader,
+        request.loader_version,
+        request
+            .memory_max_mb
+            .unwrap_or_else(|| memory::detect_memory_suggestion().recommended_xmx_mb),
+        &state.instances_dir(),
+    );
+    if let Some(default_account) = state.accounts.default_account() {
+        instance.account = default_account.profile.clone();
+        instance.account_id = Some(default_account.id.clone());
+    }
+    instance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::NoopEmitter;
+    use crate::core::test_support::stalled_server;
+
+    #[derive(Default)]
+    struct Recorder(std::sync::Mutex<Vec<(String, serde_json::Value)>>);
+
+    impl EventEmitter for Recorder {
+        fn emit_json(&self, event: &str, payload: serde_json::Value) {
+            self.0.lock().unwrap().push((event.to_string(), payload));
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_mid_download_restores_the_instance() {
+        let data_dir = std::env::temp_dir().join(format!("launch-cancel-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let recorder = Arc::new(Recorder::default());
+        let mut state = AppState::open(data_dir.clone(), recorder.clone()).await;
+        // Every request of the install hangs, as on a stalled connection.
+        state.http_client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(stalled_server()).unwrap())
+            .build()
+            .unwrap();
+        let mut instance = Instance::new(
+            "Cancel".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &state.instances_dir(),
+        );
+        instance.state = InstanceState::Ready;
+        instance.main_class = Some("net.minecraft.client.main.Main".into());
+        let id = state.instance_manager.create(instance).await.unwrap().id;
+        let launcher = Launcher::new(state, recorder.clone());
+
+        let launch = tokio::spawn({
+            let launcher = launcher.clone();
+            let id = id.clone();
+            async move { launcher.launch_instance(&id).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        assert!(launcher.cancel_launch(&id));
+        let result = tokio::time::timeout(std::time::Duration::from_secs(10), launch)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(result, Err(LauncherError::Cancelled)));
+        assert_eq!(
+            launcher.get_instance(&id).await.unwrap().state,
+            InstanceState::Ready
+        );
+        let events = recorder.0.lock().unwrap().clone();
+        assert!(events.iter().any(|(event, _)| event == "launch-cancelled"));
+        assert!(!events.iter().any(|(event, payload)| {
+            event == "instance-launch-progress" && payload["state"] == "error"
+        }));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn a_second_launch_is_refused_while_the_first_prepares() {
+        let data_dir = std::env::temp_dir().join(format!("launch-twice-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let mut state = AppState::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+        // The first launch hangs in its install downloads.
+        state.http_client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(stalled_server()).unwrap())
+            .build()
+            .unwrap();
+        let mut instance = Instance::new(
+            "Twice".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &state.instances_dir(),
+        );
+        instance.state = InstanceState::Ready;
+        instance.main_class = Some("net.minecraft.client.main.Main".into());
+        let id = state.instance_manager.create(instance).await.unwrap().id;
+        let launcher = Launcher::new(state, Arc::new(NoopEmitter));
+
+        let launch = |launcher: &Launcher| {
+            let launcher = launcher.clone();
+            let id = id.clone();
+            tokio::spawn(async move { launcher.launch_instance(&id).await })
+        };
+        let first = launch(&launcher);
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        let second = launch(&launcher);
+        let second = tokio::time::timeout(std::time::Duration::from_secs(10), second)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            second,
+            Err(LauncherError::InstanceAlreadyRunning(ref running)) if running == &id
+        ));
+
+        // The refused launch left the first one cancellable.
+        assert!(launcher.cancel_launch(&id));
+        let first = tokio::time::timeout(std::time::Duration::from_secs(10), first)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(first, Err(LauncherError::Cancelled)));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn state_stays_available_while_an_instance_installs() {
+        let data_dir =
+            std::env::temp_dir().join(format!("install-unlocked-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let mut state = AppState::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+        // Every request of the install hangs, as on a stalled connection.
+        state.http_client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all(stalled_server()).unwrap())
+            .build()
+            .unwrap();
+        let launcher = Launcher::new(state, Arc::new(NoopEmitter));
+        let created = launcher
+            .create_instance(NewInstance {
+                name: "Slow".into(),
+                minecraft_version: "1.20.1".into(),
+                loader: LoaderType::Vanilla,
+                loader_version: None,
+                memory_max_mb: Some(2048),
+            })
+            .await
+            .unwrap();
+
+        let install = tokio::spawn({
+            let launcher = launcher.clone();
+            let id = created.id.clone();
+            async move { launcher.install_instance(&id).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        assert!(!install.is_finished());
+
+        let listed =
+            tokio::time::timeout(std::time::Duration::from_secs(5), launcher.list_instances())
+                .await
+                .expect("the state lock is held during the install")
+                .unwrap();
+        assert_eq!(listed[0].state, InstanceState::Installing);
+
+        install.abort();
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    /// Holds the first `instance-clone-progress` event until released.
+    struct CloneGate {
+        entered: std::sync::Mutex<Option<std::sync::mpsc::Sender<()>>>,
+        release: std::sync::Mutex<std::sync::mpsc::Receiver<()>>,
+    }
+
+    impl EventEmitter for CloneGate {
+        fn emit_json(&self, event: &str, _payload: serde_json::Value) {
+            if event != "instance-clone-progress" {
+                return;
+            }
+            if let Some(entered) = self.entered.lock().unwrap().take() {
+                entered.send(()).unwrap();
+                self.release.lock().unwrap().recv().unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn state_stays_available_while_an_instance_is_cloned() {
+        let data_dir = std::env::temp_dir().join(format!("clone-unlocked-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let (entered_tx, entered_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel();
+        let gate = Arc::new(CloneGate {
+            entered: std::sync::Mutex::new(Some(entered_tx)),
+            release: std::sync::Mutex::new(release_rx),
+        });
+        let state = AppState::open(data_dir.clone(), gate.clone()).await;
+        let instance = Instance::new(
+            "Source".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &state.instances_dir(),
+        );
+        let source = state.instance_manager.create(instance).await.unwrap();
+        std::fs::write(source.game_dir().join("options.txt"), "fov:0.5\n").unwrap();
+        let launcher = Launcher::new(state, gate);
+
+        let clone = tokio::spawn({
+            let launcher = launcher.clone();
+            let id = source.id.clone();
+            async move { launcher.clone_instance(&id, CloneOptions::default()).await }
+        });
+        tokio::task::spawn_blocking(move || entered_rx.recv().unwrap())
+            .await
+            .unwrap();
+
+        // Mid-copy, the instance list is still reachable.
+        let listed =
+            tokio::time::timeout(std::time::Duration::from_secs(5), launcher.list_instances())
+                .await
+                .expect("the state lock is held during the copy")
+                .unwrap();
+        assert_eq!(listed.len(), 1);
+
+        release_tx.send(()).unwrap();
+        let cloned = tokio::time::timeout(std::time::Duration::from_secs(10), clone)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(cloned.game_dir().join("options.txt").is_file());
+        assert_eq!(launcher.list_instances().await.unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn deep_link_before_the_window_listens_stays_pending() {
+        let data_dir =
+            std::env::temp_dir().join(format!("deep-link-pending-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let recorder = Arc::new(Recorder::default());
+        let state = AppState::open(data_dir.clone(), recorder.clone()).await;
+        let instance = Instance::new(
+            "Linked".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &state.instances_dir(),
+        );
+        let id = state.instance_manager.create(instance).await.unwrap().id;
+        let launcher = Launcher::new(state, recorder.clone());
+
+        // At cold start nobody hears the event; the request must survive it.
+        let uri = deep_link::launch_uri(&id);
+        launcher.open_deep_link(&uri).await.unwrap();
+        let pending = launcher.take_pending_deep_link().await.unwrap();
+        assert_eq!(pending.id, id);
+        assert_eq!(pending.name, "Linked");
+        assert_eq!(pending.uri, uri);
+        // Taken once, so a reload doesn't ask again.
+        assert_eq!(launcher.take_pending_deep_link().await, None);
+
+        assert!(launcher
+            .open_deep_link(&deep_link::launch_uri("missing"))
+            .await
+            .is_err());
+        assert_eq!(launcher.take_pending_deep_link().await, None);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,379 @@
+// ─── Running Instances ───
+// Stopping games, previewing the launch command and the batch commands
+// that act on several instances at once. A batch reports one outcome per
+// instance, so a failure never hides the instances that worked.
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::Launcher;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::{ErrorText, LogKey, MessageKey};
+use crate::core::instance::{ordering, InstanceState};
+use crate::core::java;
+use crate::core::launch::{self, events};
+use crate::core::state::AppState;
+
+/// Default pause between the launches of [`Launcher::launch_group`].
+pub const LAUNCH_GROUP_DELAY_SECS: u64 = 5;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeleteInstanceResponse {
+    Deleted,
+    NeedsElevation,
+    ElevationRequested,
+}
+
+/// Outcome of one instance in a batch command.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchOutcome {
+    Done,
+    NeedsElevation,
+    ElevationRequested,
+    Failed { error: String },
+}
+
+impl From<DeleteInstanceResponse> for BatchOutcome {
+    fn from(response: DeleteInstanceResponse) -> Self {
+        match response {
+            DeleteInstanceResponse::Deleted => BatchOutcome::Done,
+            DeleteInstanceResponse::NeedsElevation => BatchOutcome::NeedsElevation,
+            DeleteInstanceResponse::ElevationRequested => BatchOutcome::ElevationRequested,
+        }
+    }
+}
+
+impl Launcher {
+    /// The java command [`Launcher::launch_instance`] would run, without
+    /// preparing or starting anything. The access token is redacted.
+    pub async fn preview_launch_command(&self, id: &str) -> LauncherResult<launch::LaunchPreview> {
+        let state = self.state.lock().await;
+        let mut instance = state.instance_manager.load(id).await?;
+        // The launch runs with the saved account, not the inlined copy.
+        instance.account = state
+            .accounts
+            .resolve(instance.account_id.as_deref(), &instance.account)
+            .clone();
+        let libs_dir = state.libraries_dir();
+
+        let java_bin = match instance.java_path.clone() {
+            Some(path) => path,
+            None => {
+                let required_major = instance.required_java_major.unwrap_or_else(|| {
+                    java::required_java_for_minecraft_version(&instance.minecraft_version)
+                });
+                java::managed_runtime_info_in_dir(
+                    &state.data_dir,
+                    required_major,
+                    state.runtime_options(),
+                )
+                .await?
+                .map(|runtime| runtime.java_bin)
+                .ok_or_else(|| {
+                    LauncherError::message(ErrorText::JavaRuntimeNotInstalled, &[&required_major])
+                })?
+            }
+        };
+
+        let classpath = launch::build_classpath(&instance, &libs_dir, &instance.libraries)?;
+        // Natives are extracted into a fresh session folder at launch time.
+        let natives_dir = instance.natives_dir().join("<session>");
+        let config =
+            launch::launch_config(&instance, java_bin, &classpath, &libs_dir, &natives_dir)?;
+        Ok(launch::preview_command(
+            &config,
+            &[instance.account.access_token.as_str()],
+        ))
+    }
+
+    /// Kill the game right away. An instance left as running without a
+    /// process is reset to ready and reported as not running.
+    pub async fn force_close_instance(&self, id: &str) -> LauncherResult<()> {
+        let mut state = self.state.lock().await;
+        let mut instance = state.instance_manager.load(id).await?;
+
+        let Some(pid) = state
+            .running_instances
+            .remove(id)
+            .map(|running| running.pid)
+        else {
+            if instance.state == InstanceState::Running {
+                instance.state = InstanceState::Ready;
+                state.instance_manager.save(&instance).await?;
+                let locale = state.launcher_settings.language;
+                events::emit_launch_progress(
+                    self.events.as_ref(),
+                    locale,
+                    id,
+                    0,
+                    MessageKey::LaunchIdle,
+                    "idle",
+                );
+                events::emit_launch_message(
+                    self.events.as_ref(),
+                    locale,
+                    id,
+                    "warn",
+                    LogKey::LaunchNoPid,
+                    &[],
+                );
+            }
+            return Err(LauncherError::message(ErrorText::NoRunningProcess, &[&id]));
+        };
+
+        launch::stop_process(pid, launch::FORCE_STOP_GRACE).await?;
+        instance.state = InstanceState::Ready;
+        state.instance_manager.save(&instance).await?;
+        let locale = state.launcher_settings.language;
+        events::emit_launch_progress(
+            self.events.as_ref(),
+            locale,
+            id,
+            0,
+            MessageKey::LaunchStopped,
+            "idle",
+        );
+        events::emit_launch_message(
+            self.events.as_ref(),
+            locale,
+            id,
+            "warn",
+            LogKey::LaunchStoppedByUser,
+            &[&pid],
+        );
+
+        info!("Force closed instance {} (pid {})", id, pid);
+        Ok(())
+    }
+
+    /// Ask the game to close and give it the configured grace period to
+    /// save before killing it. The process-wait task resets the instance
+    /// state once the process is gone.
+    pub async fn stop_instance(&self, id: &str) -> LauncherResult<()> {
+        let (pid, grace, locale) = {
+            let state = self.state.lock().await;
+            let Some(pid) = state.running_instances.get(id).map(|running| running.pid) else {
+                return Err(LauncherError::message(ErrorText::NoRunningProcess, &[&id]));
+            };
+            (
+                pid,
+                Duration::from_secs(state.launcher_settings.stop_grace_seconds),
+                state.launcher_settings.language,
+            )
+        };
+
+        events::emit_launch_message(
+            self.events.as_ref(),
+            locale,
+            id,
+            "info",
+            LogKey::LaunchStopRequested,
+            &[&pid, &grace.as_secs()],
+        );
+        launch::stop_process(pid, grace).await?;
+
+        info!("Stopped instance {} (pid {})", id, pid);
+        Ok(())
+    }
+
+    /// Ask several running instances to close, all at once.
+    pub async fn stop_instances(&self, ids: Vec<String>) -> BTreeMap<String, BatchOutcome> {
+        let stops = dedup_ids(ids).into_iter().map(|id| async move {
+            let result = self.stop_instance(&id).await.map(|()| BatchOutcome::Done);
+            (id, result)
+        });
+        collect_batch(futures_util::future::join_all(stops).await)
+    }
+
+    /// Launch every instance of `group` in display order, waiting `delay`
+    /// between launches. Instances already running are reported as failed
+    /// and skipped.
+    pub async fn launch_group(
+        &self,
+        group: &str,
+        delay: Duration,
+    ) -> LauncherResult<BTreeMap<String, BatchOutcome>> {
+        let Some(group) = ordering::normalize_group(Some(group)) else {
+            return Err(LauncherError::message(ErrorText::EmptyGroup, &[]));
+        };
+        let ids: Vec<String> = self
+            .list_instances()
+            .await?
+            .into_iter()
+            .filter(|instance| instance.group.as_deref() == Some(group.as_str()))
+            .map(|instance| instance.id)
+            .collect();
+        if ids.is_empty() {
+            return Err(LauncherError::message(
+                ErrorText::EmptyGroupInstances,
+                &[&group],
+            ));
+        }
+
+        let mut results = Vec::new();
+        for (index, id) in ids.into_iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(delay).await;
+            }
+            let result = self.launch_instance(&id).await.map(|()| BatchOutcome::Done);
+            results.push((id, result));
+        }
+        Ok(collect_batch(results))
+    }
+
+    /// Like [`Launcher::delete_instance`], but a folder the user may not
+    /// remove is reported instead of failing, and on Windows removed with
+    /// an elevated prompt when `request_elevation` is set.
+    pub async fn delete_instance_with_elevation(
+        &self,
+        id: &str,
+        request_elevation: bool,
+    ) -> LauncherResult<DeleteInstanceResponse> {
+        let mut state = self.state.lock().await;
+        delete_instance_in(&mut state, id, request_elevation).await
+    }
+
+    /// Delete several instances, stopping the running ones first.
+    pub async fn delete_instances(
+        &self,
+        ids: Vec<String>,
+        request_elevation: bool,
+    ) -> BTreeMap<String, BatchOutcome> {
+        let mut state = self.state.lock().await;
+        let mut results = Vec::new();
+        for id in dedup_ids(ids) {
+            let result = delete_instance_in(&mut state, &id, request_elevation)
+                .await
+                .map(BatchOutcome::from);
+            results.push((id, result));
+        }
+        collect_batch(results)
+    }
+}
+
+async fn delete_instance_in(
+    state: &mut AppState,
+    id: &str,
+    request_elevation: bool,
+) -> LauncherResult<DeleteInstanceResponse> {
+    if let Some(running) = state.running_instances.remove(id) {
+        launch::stop_process(running.pid, launch::FORCE_STOP_GRACE).await?;
+    }
+
+    match state.instance_manager.delete(id).await {
+        Ok(_) => {
+            info!("Deleted instance {}", id);
+            Ok(DeleteInstanceResponse::Deleted)
+        }
+        Err(error) if is_permission_error(&error) => {
+            if !request_elevation {
+                return Ok(DeleteInstanceResponse::NeedsElevation);
+            }
+
+            #[cfg(target_os = "windows")]
+            {
+                let target = state.instances_dir().join(id);
+                request_windows_elevated_delete(&target)?;
+                return Ok(DeleteInstanceResponse::ElevationRequested);
+            }
+
+            #[cfg(not(target_os = "windows"))]
+            {
+                Err(LauncherError::message(ErrorText::ElevationWindowsOnly, &[]))
+            }
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn is_permission_error(error: &LauncherError) -> bool {
+    match error {
+        LauncherError::Io { source, .. } => {
+            source.kind() == std::io::ErrorKind::PermissionDenied
+                || matches!(source.raw_os_error(), Some(5 | 32))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn request_windows_elevated_delete(target: &std::path::Path) -> LauncherResult<()> {
+    let escaped_target = target.display().to_string().replace('"', "`\"");
+    let script = format!(
+        "Start-Process -FilePath powershell -Verb RunAs -WindowStyle Hidden -ArgumentList @('-NoProfile','-Command','Remove-Item -LiteralPath \"{}\" -Recurse -Force')",
+        escaped_target
+    );
+
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .status()
+        .map_err(|source| LauncherError::Io {
+            path: target.to_path_buf(),
+            source,
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LauncherError::message(ErrorText::ElevationFailed, &[]))
+    }
+}
+
+/// Drop repeated ids, keeping the first occurrence.
+fn dedup_ids(ids: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ids.into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .collect()
+}
+
+/// One entry per id, so a failure never hides the instances that worked.
+fn collect_batch(
+    results: Vec<(String, LauncherResult<BatchOutcome>)>,
+) -> BTreeMap<String, BatchOutcome> {
+    results
+        .into_iter()
+        .map(|(id, result)| {
+            let outcome = result.unwrap_or_else(|error| {
+                warn!("Batch operation failed for {}: {}", id, error);
+                BatchOutcome::Failed {
+                    error: error.to_string(),
+                }
+            });
+            (id, outcome)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_results_keep_every_id_with_its_own_outcome() {
+        let ids = dedup_ids(vec!["a".into(), "b".into(), "a".into(), "c".into()]);
+        assert_eq!(ids, ["a", "b", "c"]);
+
+        let results = collect_batch(vec![
+            ("a".into(), Ok(BatchOutcome::Done)),
+            (
+                "b".into(),
+                Err(LauncherError::InstanceAlreadyRunning("b".into())),
+            ),
+            ("c".into(), Ok(BatchOutcome::NeedsElevation)),
+        ]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results["a"], BatchOutcome::Done);
+        assert!(matches!(&results["b"], BatchOutcome::Failed { error } if !error.is_empty()));
+
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["a"]["status"], "done");
+        assert_eq!(json["b"]["status"], "failed");
+        assert_eq!(json["c"]["status"], "needs_elevation");
+    }
+}
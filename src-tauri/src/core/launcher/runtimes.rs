@@ -0,0 +1,136 @@
+// ─── Java Runtimes ───
+// Java installations found on the machine and the managed runtimes under
+// the data dir. Runtimes a running game was started with, or an instance
+// is pinned to, survive the cleanups.
+
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use super::Launcher;
+use crate::core::error::LauncherResult;
+use crate::core::java::{self, JavaInstallation, ManagedRuntimeInfo, RuntimeDiagnostic};
+use crate::core::state::AppState;
+
+impl Launcher {
+    pub async fn java_installations(&self) -> Vec<JavaInstallation> {
+        let data_dir = self.state.lock().await.data_dir.clone();
+        java::detect_java_installations(&data_dir).await
+    }
+
+    /// Folder the managed runtime for `required_java_major` is installed
+    /// in, and that runtime if it is there.
+    pub async fn managed_runtime(
+        &self,
+        required_java_major: u32,
+    ) -> LauncherResult<(PathBuf, Option<ManagedRuntimeInfo>)> {
+        let state = self.state.lock().await;
+        let runtime_dir = java::managed_runtime_dir(&state.data_dir, required_java_major);
+        let runtime = java::managed_runtime_info_in_dir(
+            &state.data_dir,
+            required_java_major,
+            state.runtime_options(),
+        )
+        .await?;
+        Ok((runtime_dir, runtime))
+    }
+
+    pub async fn list_runtimes(&self) -> LauncherResult<Vec<ManagedRuntimeInfo>> {
+        let state = self.state.lock().await;
+        let manager = state.runtime_manager()?;
+        manager.list_runtimes(state.runtime_options()).await
+    }
+
+    /// Java binary of a managed runtime for `required_java_major`,
+    /// installing one when needed.
+    pub async fn resolve_java(&self, required_java_major: u32) -> LauncherResult<PathBuf> {
+        // Installing a runtime downloads ~200 MB; other commands keep the lock.
+        let (options, manager) = {
+            let state = self.state.lock().await;
+            (state.runtime_options(), state.runtime_manager()?)
+        };
+        manager.resolve_java(required_java_major, options).await
+    }
+
+    /// Canonical path of `path` and whether it is a usable Java for
+    /// `required_java_major`.
+    pub async fn validate_java(
+        &self,
+        path: &Path,
+        required_java_major: u32,
+    ) -> LauncherResult<(PathBuf, bool)> {
+        let manager = self.state.lock().await.runtime_manager()?;
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let valid = manager.validate_java(&canonical, required_java_major);
+        Ok((canonical, valid))
+    }
+
+    pub async fn clear_runtimes(&self) -> LauncherResult<()> {
+        let manager = self.state.lock().await.runtime_manager()?;
+        manager.clear_runtimes().await
+    }
+
+    /// Delete a single managed runtime. Runtimes a running game was started
+    /// with are kept.
+    pub async fn remove_runtime(&self, identifier: &str) -> LauncherResult<bool> {
+        let state = self.state.lock().await;
+        let manager = state.runtime_manager()?;
+        manager
+            .remove_runtime(identifier, &runtimes_in_use(&state))
+            .await
+    }
+
+    /// Apply `runtime_keep_per_major` to every Java major now instead of at
+    /// the next install. Runtimes pinned by an instance are kept. Returns
+    /// the removed runtime identifiers.
+    pub async fn prune_runtimes(&self) -> LauncherResult<Vec<String>> {
+        let state = self.state.lock().await;
+        let pinned: Vec<String> = state
+            .instance_manager
+            .list()
+            .await?
+            .into_iter()
+            .filter_map(|instance| instance.pinned_runtime_identifier)
+            .collect();
+        java::runtime::prune_runtimes_in_dir(
+            &state.data_dir,
+            state.launcher_settings.runtime_keep_per_major,
+            &runtimes_in_use(&state),
+            &pinned,
+        )
+        .await
+    }
+
+    /// Track an existing JDK/JRE folder as a managed runtime without copying
+    /// it. The folder is never deleted by runtime cleanups.
+    pub async fn import_runtime(&self, path: &Path) -> LauncherResult<ManagedRuntimeInfo> {
+        let data_dir = self.state.lock().await.data_dir.clone();
+        java::runtime::import_runtime_in_dir(&data_dir, path).await
+    }
+
+    /// Remove the Adoptium spec cache, the 429 backoff marker and the
+    /// resolved Java caches so the next install queries Adoptium again.
+    /// Installed runtimes are kept. Returns the number of files removed.
+    pub async fn clear_runtime_caches(&self) -> LauncherResult<usize> {
+        let data_dir = self.state.lock().await.data_dir.clone();
+        let manager = java::runtime::RuntimeManager::for_data_dir(&data_dir)?;
+        let mut removed = manager.clear_caches().await?;
+        removed.extend(java::runtime::clear_runtime_caches_in_dir(&data_dir).await?);
+        info!("Cleared {} runtime cache files", removed.len());
+        Ok(removed.len())
+    }
+
+    pub async fn runtime_diagnostic(&self) -> LauncherResult<RuntimeDiagnostic> {
+        let manager = self.state.lock().await.runtime_manager()?;
+        manager.diagnostics().await
+    }
+}
+
+/// Java binaries the running games were started with.
+fn runtimes_in_use(state: &AppState) -> Vec<PathBuf> {
+    state
+        .running_instances
+        .values()
+        .map(|running| running.java_bin.clone())
+        .collect()
+}
@@ -0,0 +1,97 @@
+// ─── Setup ───
+// First-launch wizard, full reinstall and launcher-wide maintenance of the
+// data dir. `resource_dir` is where the app bundle keeps its resources
+// (the bundled runtime); headless callers pass `None`.
+
+use std::path::{Path, PathBuf};
+
+use super::Launcher;
+use crate::core::assets::{shared_store, ShareReport};
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::state::{self, SettingsSnapshot, SetupProgress, SetupRequest, SetupStep};
+
+impl Launcher {
+    /// Whether the launcher was never installed into its data dir.
+    pub async fn is_first_launch(&self) -> bool {
+        self.state.lock().await.is_first_launch()
+    }
+
+    pub async fn setup_progress(&self) -> SetupProgress {
+        self.state.lock().await.setup_progress()
+    }
+
+    /// Run one wizard step; a failed step can be run again on its own.
+    pub async fn run_setup_step(
+        &self,
+        resource_dir: Option<&Path>,
+        step: SetupStep,
+        request: &SetupRequest,
+    ) -> LauncherResult<SetupProgress> {
+        state::run_setup_step(
+            &self.state,
+            self.events.as_ref(),
+            resource_dir,
+            step,
+            request,
+        )
+        .await
+    }
+
+    /// Run the whole wizard into `target_dir` in one go.
+    pub async fn install_launcher(
+        &self,
+        resource_dir: Option<&Path>,
+        target_dir: PathBuf,
+        create_desktop_shortcut: bool,
+    ) -> LauncherResult<SettingsSnapshot> {
+        state::install_launcher(
+            &self.state,
+            self.events.as_ref(),
+            resource_dir,
+            target_dir,
+            create_desktop_shortcut,
+        )
+        .await
+        .map_err(|e| LauncherError::message(ErrorText::SetupFailed, &[&e]))?;
+        Ok(self.settings().await)
+    }
+
+    /// Wipe the data dir and set it up again.
+    pub async fn reinstall_launcher(
+        &self,
+        resource_dir: Option<&Path>,
+    ) -> LauncherResult<SettingsSnapshot> {
+        let mut state = self.state.lock().await;
+        state
+            .reinstall_launcher(resource_dir)
+            .map_err(|e| LauncherError::message(ErrorText::ReinstallFailed, &[&e]))?;
+        Ok(state.settings_snapshot())
+    }
+
+    /// Replace asset objects duplicated across instances by links into the
+    /// shared store under the data dir. Works regardless of `shared_assets`.
+    pub async fn dedupe_assets(&self) -> LauncherResult<ShareReport> {
+        let (store, assets_dirs) = {
+            let state = self.state.lock().await;
+            let instances = state.instance_manager.list().await?;
+            (
+                state.assets_dir(),
+                instances
+                    .iter()
+                    .map(|instance| instance.game_dir().join("assets"))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut total = ShareReport::default();
+            for assets_dir in assets_dirs {
+                total.merge(&shared_store::dedupe_into_store(&store, &assets_dir)?);
+            }
+            Ok(total)
+        })
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))?
+    }
+}
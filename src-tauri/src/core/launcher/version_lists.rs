@@ -0,0 +1,68 @@
+// ─── Version Lists ───
+// Minecraft and loader versions offered when creating an instance. The
+// lists come from the metadata cache when the APIs are unreachable, which
+// is reported as `using-cached-metadata`.
+
+use super::Launcher;
+use crate::core::error::LauncherResult;
+use crate::core::instance::LoaderType;
+use crate::core::loaders::{self, versions};
+use crate::core::version::manifest::VERSION_MANIFEST_CACHE_KEY;
+use crate::core::version::{VersionChannel, VersionEntry, VersionManifest};
+
+impl Launcher {
+    /// Manifest entries of `channel`, in manifest order.
+    pub async fn minecraft_versions(
+        &self,
+        channel: VersionChannel,
+    ) -> LauncherResult<Vec<VersionEntry>> {
+        let (client, cache, mirrors) = {
+            let state = self.state.lock().await;
+            (
+                state.http_client.clone(),
+                state.metadata_cache(),
+                state.downloader.mirrors(),
+            )
+        };
+        let (manifest, cached_at) =
+            VersionManifest::fetch_cached(&client, &cache, &mirrors).await?;
+        versions::emit_cached_metadata_warning(
+            self.events.as_ref(),
+            VERSION_MANIFEST_CACHE_KEY,
+            cached_at,
+        );
+
+        Ok(manifest
+            .versions
+            .into_iter()
+            .filter(|entry| channel.matches(&entry.version_type))
+            .collect())
+    }
+
+    pub async fn loader_versions(
+        &self,
+        loader_type: &LoaderType,
+        minecraft_version: &str,
+        include_unstable: bool,
+    ) -> LauncherResult<Vec<versions::LoaderVersionInfo>> {
+        // The loader APIs may be slow; other commands keep the lock.
+        let detached = self.state.lock().await.detached();
+        versions::fetch_loader_versions(
+            self.events.as_ref(),
+            &detached,
+            loader_type,
+            minecraft_version,
+            include_unstable,
+        )
+        .await
+    }
+
+    /// API mod hint for `loader_type` (QFAPI/QSL for Quilt), if any.
+    pub async fn loader_companion(
+        &self,
+        loader_type: &LoaderType,
+    ) -> Option<loaders::LoaderCompanion> {
+        let locale = self.state.lock().await.launcher_settings.language;
+        loaders::loader_companion(loader_type, locale)
+    }
+}
@@ -1,6 +1,12 @@
 use std::path::Path;
 
 use crate::core::downloader::Downloader;
+use crate::core::java::RuntimeOptions;
+use crate::core::metadata_cache::MetadataCache;
+
+/// Progress callback for multi-step installs: `(step, total, label)`, with
+/// `step` starting at 1.
+pub type LoaderStepFn<'a> = dyn Fn(usize, usize, &str) + Send + Sync + 'a;
 
 /// Contexto completo de instalación.
 /// Permite escalar sin romper la API.
@@ -11,4 +17,18 @@ pub struct InstallContext<'a> {
     pub libs_dir: &'a Path,
     pub downloader: &'a Downloader,
     pub http_client: &'a reqwest::Client,
+    /// Resolve the POMs of the declared libraries and download transitive
+    /// dependencies the installer profile does not list (Forge/NeoForge).
+    pub resolve_transitive: bool,
+    /// Offline fallback for the version manifest.
+    pub metadata_cache: &'a MetadataCache,
+    /// Notified before each installer processor runs (NeoForge).
+    pub on_step: Option<&'a LoaderStepFn<'a>>,
+    /// Settings for the Java runtime installer processors run on.
+    pub runtime: RuntimeOptions,
+    /// Data dir holding that runtime.
+    pub data_dir: &'a Path,
+    /// Installer JAR the user downloaded beforehand (Forge/NeoForge), used
+    /// instead of fetching one from the loader's Maven.
+    pub local_installer: Option<&'a Path>,
 }
@@ -11,6 +11,8 @@ use super::installer::{LoaderInstallResult, LoaderInstaller};
 use crate::core::downloader::Downloader;
 use crate::core::error::{LauncherError, LauncherResult};
 use crate::core::maven::{MavenArtifact, FABRIC_MAVEN};
+use crate::core::metadata_cache::{self, MetadataCache};
+use crate::core::version::ResolvedLibrary;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +28,10 @@ pub struct FabricProfile {
 pub struct FabricLibrary {
     pub name: String,
     pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -45,8 +51,11 @@ impl FabricInstaller {
         Self { client }
     }
 
+    /// Fetch the launch profile, revalidating the cached copy keyed by
+    /// Minecraft and loader version.
     async fn fetch_profile(
         &self,
+        cache: &MetadataCache,
         minecraft_version: &str,
         loader_version: &str,
     ) -> LauncherResult<FabricProfile> {
@@ -55,25 +64,18 @@ impl FabricInstaller {
             FABRIC_META_BASE, minecraft_version, loader_version
         );
 
-        let resp = self.client.get(&url).send().await?;
-
-        if !resp.status().is_success() {
-            return Err(LauncherError::LoaderApi(format!(
-                "Fabric Meta returned {} for {}",
-                resp.status(),
-                url
-            )));
-        }
-
-        let profile = resp.json::<FabricProfile>().await?;
-
-        if profile.main_class.is_empty() {
-            return Err(LauncherError::LoaderApi(
-                "Fabric profile missing main_class".into(),
-            ));
-        }
-
-        Ok(profile)
+        let response = cache
+            .fetch_revalidated(
+                &format!("fabric-profile/{minecraft_version}/{loader_version}"),
+                &url,
+                false,
+                |validators| metadata_cache::fetch_conditional(&self.client, &url, validators),
+                parse_profile,
+            )
+            .await
+            .map_err(|err| LauncherError::LoaderApi(format!("Fabric Meta: {err}")))?;
+
+        Ok(response.value)
     }
 
     fn ensure_loader_artifact(libraries: &mut Vec<String>, loader_version: &str) {
@@ -89,7 +91,7 @@ impl FabricInstaller {
         profile: &FabricProfile,
         libs_dir: &Path,
         downloader: &Downloader,
-    ) -> LauncherResult<Vec<String>> {
+    ) -> LauncherResult<Vec<ResolvedLibrary>> {
         fs::create_dir_all(libs_dir).await?;
 
         let tasks = stream::iter(profile.libraries.iter().cloned())
@@ -102,13 +104,19 @@ impl FabricInstaller {
 
                     let artifact = MavenArtifact::parse(&lib.name)?;
                     let dest = libs_dir.join(artifact.local_path());
+                    let url = artifact.url(repo);
 
                     if !dest.try_exists().unwrap_or(false) {
-                        let url = artifact.url(repo);
                         downloader.download_file(&url, &dest, None).await?;
                     }
 
-                    Ok::<_, LauncherError>(lib.name)
+                    Ok::<_, LauncherError>(ResolvedLibrary {
+                        coord: lib.name,
+                        path: artifact.local_path(),
+                        url,
+                        sha1: lib.sha1,
+                        size: lib.size,
+                    })
                 }
             })
             .buffer_unordered(8) // Descarga 8 en paralelo
@@ -126,6 +134,17 @@ impl FabricInstaller {
 
 const FABRIC_META_BASE: &str = "https://meta.fabricmc.net/v2";
 
+/// A Fabric profile, rejected without a main class so it is never cached.
+fn parse_profile(body: &str) -> LauncherResult<FabricProfile> {
+    let profile: FabricProfile = serde_json::from_str(body)?;
+    if profile.main_class.is_empty() {
+        return Err(LauncherError::LoaderApi(
+            "Fabric profile missing main_class".into(),
+        ));
+    }
+    Ok(profile)
+}
+
 #[async_trait]
 impl LoaderInstaller for FabricInstaller {
     async fn install(&self, ctx: InstallContext<'_>) -> LauncherResult<LoaderInstallResult> {
@@ -136,7 +155,11 @@ impl LoaderInstaller for FabricInstaller {
 
         // 1️⃣ Fetch profile
         let profile = self
-            .fetch_profile(ctx.minecraft_version, ctx.loader_version)
+            .fetch_profile(
+                ctx.metadata_cache,
+                ctx.minecraft_version,
+                ctx.loader_version,
+            )
             .await?;
 
         // 2️⃣ Guardar profile local
@@ -151,9 +174,13 @@ impl LoaderInstaller for FabricInstaller {
         fs::write(&profile_path, profile_json).await?;
 
         // 3️⃣ Instalar librerías en paralelo
-        let mut libraries = self
+        let resolved_libraries = self
             .install_libraries(&profile, ctx.libs_dir, ctx.downloader)
             .await?;
+        let mut libraries: Vec<String> = resolved_libraries
+            .iter()
+            .map(|lib| lib.coord.clone())
+            .collect();
         Self::ensure_loader_artifact(&mut libraries, ctx.loader_version);
 
         // 4️⃣ Argumentos
@@ -169,6 +196,7 @@ impl LoaderInstaller for FabricInstaller {
             extra_jvm_args: jvm_args,
             extra_game_args: game_args,
             libraries,
+            resolved_libraries,
             asset_index_id: None,
             asset_index_url: None,
             java_major: None,
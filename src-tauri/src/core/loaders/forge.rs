@@ -8,7 +8,9 @@ use tracing::info;
 use super::context::InstallContext;
 use super::installer::{LoaderInstallResult, LoaderInstaller};
 use crate::core::error::{LauncherError, LauncherResult};
-use crate::core::maven::MavenArtifact;
+use crate::core::maven::{
+    MavenArtifact, MavenResolver, TransitivePolicy, MAVEN_CENTRAL, MOJANG_LIBRARIES,
+};
 use crate::core::version::VersionJson;
 
 /// Installs Forge by downloading and executing the official installer JAR.
@@ -26,6 +28,10 @@ const FORGE_MAVEN: &str = "https://maven.minecraftforge.net";
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ForgeInstallProfile {
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub minecraft: Option<String>,
     #[serde(default)]
     pub libraries: Vec<ForgeLibrary>,
     #[serde(default)]
@@ -54,6 +60,8 @@ pub struct ForgeProcessor {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ForgeVersionJson {
+    #[serde(default)]
+    pub id: Option<String>,
     pub main_class: String,
     #[serde(default)]
     pub libraries: Vec<ForgeLibrary>,
@@ -66,6 +74,9 @@ impl LoaderInstaller for ForgeInstaller {
             "Installing Forge {} for MC {}",
             ctx.loader_version, ctx.minecraft_version
         );
+        if let Some(installer_path) = ctx.local_installer {
+            return install_forge_from_jar(installer_path, ctx).await;
+        }
 
         let forge_id = format!("{}-{}", ctx.minecraft_version, ctx.loader_version);
         let installer_name = format!("forge-{}-installer.jar", forge_id);
@@ -87,7 +98,28 @@ impl LoaderInstaller for ForgeInstaller {
                     source: e,
                 })?;
 
-        let cursor = std::io::Cursor::new(&installer_bytes);
+        let contents = ForgeInstallerContents::parse(&installer_bytes)?;
+        let result =
+            install_with_installer(ctx, &installer_path, &installer_bytes, contents, &forge_id)
+                .await?;
+
+        let _ = tokio::fs::remove_file(&installer_path).await;
+
+        info!("Forge {} installed successfully", forge_id);
+        Ok(result)
+    }
+}
+
+/// `install_profile.json` and `version.json` read from a Forge installer JAR.
+#[derive(Debug)]
+pub struct ForgeInstallerContents {
+    pub install_profile: ForgeInstallProfile,
+    pub version_json: ForgeVersionJson,
+}
+
+impl ForgeInstallerContents {
+    pub fn parse(installer_bytes: &[u8]) -> LauncherResult<Self> {
+        let cursor = std::io::Cursor::new(installer_bytes);
         let mut archive = zip::ZipArchive::new(cursor)?;
 
         let install_profile: ForgeInstallProfile = {
@@ -104,146 +136,315 @@ impl LoaderInstaller for ForgeInstaller {
             serde_json::from_reader(file)?
         };
 
-        let java_bin = crate::core::java::resolve_runtime(
-            crate::core::java::RuntimeRole::Delta,
-            Some(ctx.minecraft_version),
-        )
-        .await?;
-        log_runtime_role("Delta", &java_bin, ctx.instance_dir);
+        Ok(Self {
+            install_profile,
+            version_json,
+        })
+    }
 
-        let minecraft_dir = ctx.instance_dir.join("minecraft");
-        tokio::fs::create_dir_all(&minecraft_dir)
+    pub async fn read(installer_path: &Path) -> LauncherResult<Self> {
+        let bytes = tokio::fs::read(installer_path)
             .await
             .map_err(|e| LauncherError::Io {
-                path: minecraft_dir.clone(),
+                path: installer_path.to_path_buf(),
                 source: e,
             })?;
+        Self::parse(&bytes)
+    }
 
-        let launcher_profiles_path = minecraft_dir.join("launcher_profiles.json");
-        if !launcher_profiles_path.exists() {
-            tokio::fs::write(
-                &launcher_profiles_path,
-                br#"{"profiles":{},"selectedProfile":null}"#,
-            )
-            .await
-            .map_err(|e| LauncherError::Io {
-                path: launcher_profiles_path.clone(),
-                source: e,
-            })?;
-        }
+    /// Version id the installer writes under `versions/`, e.g. `1.20.1-forge-47.2.0`.
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_json
+            .id
+            .as_deref()
+            .or(self.install_profile.version.as_deref())
+    }
 
-        let java_home = java_bin
-            .parent()
-            .and_then(|bin| bin.parent())
-            .unwrap_or(ctx.instance_dir);
-        let output = std::process::Command::new(&java_bin)
-            .env("JAVA_HOME", java_home)
-            .arg("-jar")
-            .arg(&installer_path)
-            .arg("--installClient")
-            .arg(&minecraft_dir)
-            .current_dir(&minecraft_dir)
-            .output()
-            .map_err(|e| LauncherError::JavaExecution(e.to_string()))?;
+    /// Loader version taken from the version id: `47.2.0` for Forge (and
+    /// the 1.20.1 NeoForge builds), `21.1.77` for `neoforge-21.1.77`.
+    pub fn loader_version(&self) -> Option<String> {
+        let id = self.version_id()?;
+        id.split_once("-forge-")
+            .map(|(_, version)| version)
+            .or_else(|| id.strip_prefix("neoforge-"))
+            .map(str::to_string)
+    }
+}
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
+/// Install Forge from an installer JAR the user downloaded beforehand, for
+/// when `maven.minecraftforge.net` is unreachable. The JAR is left in place.
+pub async fn install_forge_from_jar(
+    installer_path: &Path,
+    ctx: InstallContext<'_>,
+) -> LauncherResult<LoaderInstallResult> {
+    let installer_bytes = tokio::fs::read(installer_path)
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: installer_path.to_path_buf(),
+            source: e,
+        })?;
+    let contents = ForgeInstallerContents::parse(&installer_bytes)?;
+
+    if let Some(minecraft) = contents.install_profile.minecraft.as_deref() {
+        if minecraft != ctx.minecraft_version {
             return Err(LauncherError::Loader(format!(
-                "Forge installer failed (code {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
-                output.status.code(),
-                stdout,
-                stderr
+                "Forge installer {} targets Minecraft {}, instance uses {}",
+                installer_path.display(),
+                minecraft,
+                ctx.minecraft_version
             )));
         }
+    }
 
-        let mut libraries = BTreeSet::new();
-        for lib in &install_profile.libraries {
-            libraries.insert(lib.name.clone());
-        }
-        for lib in &version_json.libraries {
-            libraries.insert(lib.name.clone());
+    let version_id = contents
+        .version_id()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}-{}", ctx.minecraft_version, ctx.loader_version));
+    info!(
+        "Installing Forge {} from local installer {}",
+        version_id,
+        installer_path.display()
+    );
+
+    let result =
+        install_with_installer(ctx, installer_path, &installer_bytes, contents, &version_id)
+            .await?;
+
+    info!("Forge {} installed successfully", version_id);
+    Ok(result)
+}
+
+/// Run the installer, download the declared libraries and run the client
+/// processors. Shared by the online and local-JAR install paths.
+async fn install_with_installer(
+    ctx: InstallContext<'_>,
+    installer_path: &Path,
+    installer_bytes: &[u8],
+    contents: ForgeInstallerContents,
+    forge_id: &str,
+) -> LauncherResult<LoaderInstallResult> {
+    let ForgeInstallerContents {
+        install_profile,
+        version_json,
+    } = contents;
+
+    let java_bin = crate::core::java::resolve_runtime(
+        ctx.data_dir,
+        crate::core::java::RuntimeRole::Delta,
+        Some(ctx.minecraft_version),
+        ctx.runtime.clone(),
+    )
+    .await?;
+    log_runtime_role("Delta", &java_bin, ctx.instance_dir);
+
+    let minecraft_dir = ctx.instance_dir.join("minecraft");
+    tokio::fs::create_dir_all(&minecraft_dir)
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: minecraft_dir.clone(),
+            source: e,
+        })?;
+
+    let launcher_profiles_path = minecraft_dir.join("launcher_profiles.json");
+    if !launcher_profiles_path.exists() {
+        tokio::fs::write(
+            &launcher_profiles_path,
+            br#"{"profiles":{},"selectedProfile":null}"#,
+        )
+        .await
+        .map_err(|e| LauncherError::Io {
+            path: launcher_profiles_path.clone(),
+            source: e,
+        })?;
+    }
+
+    let java_home = java_bin
+        .parent()
+        .and_then(|bin| bin.parent())
+        .unwrap_or(ctx.instance_dir);
+    let output = std::process::Command::new(&java_bin)
+        .env("JAVA_HOME", java_home)
+        .arg("-jar")
+        .arg(installer_path)
+        .arg("--installClient")
+        .arg(&minecraft_dir)
+        .current_dir(&minecraft_dir)
+        .output()
+        .map_err(|e| LauncherError::JavaExecution(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(LauncherError::Loader(format!(
+            "Forge installer failed (code {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
+            output.status.code(),
+            stdout,
+            stderr
+        )));
+    }
+
+    let bundled = unpack_bundled_libraries(installer_bytes, ctx.libs_dir)?;
+    if bundled > 0 {
+        info!(
+            "Unpacked {} libraries bundled in the Forge installer",
+            bundled
+        );
+    }
+
+    let mut libraries = BTreeSet::new();
+    for lib in &install_profile.libraries {
+        libraries.insert(lib.name.clone());
+    }
+    for lib in &version_json.libraries {
+        libraries.insert(lib.name.clone());
+    }
+
+    let installed_version_path = minecraft_dir
+        .join("versions")
+        .join(forge_id)
+        .join(format!("{}.json", forge_id));
+    let mut resolved_main_class = version_json.main_class.clone();
+    let mut extra_jvm_args = Vec::new();
+    let mut extra_game_args = Vec::new();
+    let mut java_major = None;
+    let mut resolved_libraries = Vec::new();
+
+    if installed_version_path.exists() {
+        let raw_version = tokio::fs::read_to_string(&installed_version_path)
+            .await
+            .map_err(|e| LauncherError::Io {
+                path: installed_version_path.clone(),
+                source: e,
+            })?;
+        let installed_version = resolve_version_with_inheritance(
+            &raw_version,
+            installed_version_path.parent().unwrap_or(ctx.instance_dir),
+        )?;
+
+        resolved_main_class = installed_version.main_class.clone();
+        extra_jvm_args = installed_version.simple_jvm_args();
+        extra_game_args = installed_version.simple_game_args();
+        java_major = Some(installed_version.required_java_major());
+        resolved_libraries = installed_version.resolved_libraries();
+
+        for lib in installed_version
+            .download_libraries(ctx.libs_dir, ctx.downloader)
+            .await?
+        {
+            libraries.insert(lib);
         }
+    }
+
+    for lib_name in &libraries {
+        let Ok(artifact) = MavenArtifact::parse(lib_name) else {
+            // Some metadata entries are direct artifact paths already resolved
+            // from `downloads.artifact.path`; those are handled by classpath
+            // resolution and do not need Maven coordinate downloads.
+            continue;
+        };
 
-        let installed_version_path = minecraft_dir
-            .join("versions")
-            .join(&forge_id)
-            .join(format!("{}.json", forge_id));
-        let mut resolved_main_class = version_json.main_class.clone();
-        let mut extra_jvm_args = Vec::new();
-        let mut extra_game_args = Vec::new();
-        let mut java_major = None;
-
-        if installed_version_path.exists() {
-            let raw_version = tokio::fs::read_to_string(&installed_version_path)
+        let dest = ctx.libs_dir.join(artifact.local_path());
+        if !dest.exists() {
+            let primary = artifact.url(FORGE_MAVEN);
+            if ctx
+                .downloader
+                .download_file(&primary, &dest, None)
                 .await
-                .map_err(|e| LauncherError::Io {
-                    path: installed_version_path.clone(),
-                    source: e,
-                })?;
-            let installed_version = resolve_version_with_inheritance(
-                &raw_version,
-                installed_version_path.parent().unwrap_or(ctx.instance_dir),
-            )?;
-
-            resolved_main_class = installed_version.main_class.clone();
-            extra_jvm_args = installed_version.simple_jvm_args();
-            extra_game_args = installed_version.simple_game_args();
-            java_major = Some(installed_version.required_java_major());
-
-            for lib in installed_version
-                .download_libraries(ctx.libs_dir, ctx.downloader)
-                .await?
+                .is_err()
             {
-                libraries.insert(lib);
+                let fallback = artifact.url(crate::core::maven::MOJANG_LIBRARIES);
+                let _ = ctx.downloader.download_file(&fallback, &dest, None).await;
             }
         }
+    }
 
-        for lib_name in &libraries {
-            let Ok(artifact) = MavenArtifact::parse(lib_name) else {
-                // Some metadata entries are direct artifact paths already resolved
-                // from `downloads.artifact.path`; those are handled by classpath
-                // resolution and do not need Maven coordinate downloads.
-                continue;
-            };
-
-            let dest = ctx.libs_dir.join(artifact.local_path());
-            if !dest.exists() {
-                let primary = artifact.url(FORGE_MAVEN);
-                if ctx
-                    .downloader
-                    .download_file(&primary, &dest, None)
-                    .await
-                    .is_err()
-                {
-                    let fallback = artifact.url(crate::core::maven::MOJANG_LIBRARIES);
-                    let _ = ctx.downloader.download_file(&fallback, &dest, None).await;
-                }
-            }
+    if ctx.resolve_transitive {
+        let declared: Vec<String> = libraries.iter().cloned().collect();
+        let mut resolver = MavenResolver::new(vec![
+            FORGE_MAVEN.to_string(),
+            MAVEN_CENTRAL.to_string(),
+            MOJANG_LIBRARIES.to_string(),
+        ]);
+        let transitive = resolver
+            .resolve_missing_transitive(
+                &declared,
+                ctx.libs_dir,
+                ctx.downloader,
+                &TransitivePolicy::LOADER_LIBRARIES,
+            )
+            .await;
+        if !transitive.is_empty() {
+            info!(
+                "Added {} transitive Forge libraries: {}",
+                transitive.len(),
+                transitive.join(", ")
+            );
         }
+        libraries.extend(transitive);
+    }
 
-        run_processors(
-            ctx,
-            &java_bin,
-            &installer_bytes,
-            &installer_path,
-            &install_profile,
-        )?;
-
-        let _ = tokio::fs::remove_file(&installer_path).await;
-
-        info!("Forge {} installed successfully", forge_id);
+    run_processors(
+        ctx,
+        &java_bin,
+        installer_bytes,
+        installer_path,
+        &install_profile,
+    )?;
+
+    Ok(LoaderInstallResult {
+        main_class: resolved_main_class,
+        extra_jvm_args,
+        extra_game_args,
+        libraries: libraries.into_iter().collect(),
+        resolved_libraries,
+        asset_index_id: None,
+        asset_index_url: None,
+        java_major,
+    })
+}
 
-        Ok(LoaderInstallResult {
-            main_class: resolved_main_class,
-            extra_jvm_args,
-            extra_game_args,
-            libraries: libraries.into_iter().collect(),
-            asset_index_id: None,
-            asset_index_url: None,
-            java_major,
-        })
+/// Copy the libraries an installer bundles under `maven/` (the loader's own
+/// jars) into `libs_dir`, so they are not fetched from the loader's Maven.
+/// Files already present are kept. Returns how many were written.
+pub(super) fn unpack_bundled_libraries(
+    installer_bytes: &[u8],
+    libs_dir: &Path,
+) -> LauncherResult<usize> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(installer_bytes))?;
+    let mut unpacked = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(relative) = entry
+            .enclosed_name()
+            .and_then(|name| name.strip_prefix("maven").ok().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+        let dest = libs_dir.join(relative);
+        if dest.exists() {
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|source| LauncherError::Io {
+                path: dest.clone(),
+                source,
+            })?;
+        crate::core::atomic_file::write(&dest, bytes)
+            .map_err(|source| LauncherError::Io { path: dest, source })?;
+        unpacked += 1;
     }
+    Ok(unpacked)
 }
 
 fn run_processors(
@@ -551,3 +752,114 @@ fn resolve_version_with_inheritance(
 
     serde_json::from_value(current_json).map_err(LauncherError::from)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Minimal installer JAR with just the two JSON documents we parse.
+    fn fixture_installer() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("install_profile.json", options).unwrap();
+        writer
+            .write_all(
+                br#"{
+                    "version": "1.20.1-forge-47.2.0",
+                    "minecraft": "1.20.1",
+                    "libraries": [{"name": "net.minecraftforge:installertools:1.3.0"}],
+                    "processors": [{
+                        "sides": ["client"],
+                        "jar": "net.minecraftforge:installertools:1.3.0",
+                        "args": ["--task", "MCP_DATA"]
+                    }]
+                }"#,
+            )
+            .unwrap();
+        writer.start_file("version.json", options).unwrap();
+        writer
+            .write_all(
+                br#"{
+                    "id": "1.20.1-forge-47.2.0",
+                    "inheritsFrom": "1.20.1",
+                    "mainClass": "cpw.mods.bootstraplauncher.BootstrapLauncher",
+                    "libraries": [
+                        {"name": "cpw.mods:bootstraplauncher:1.1.2"},
+                        {"name": "net.minecraftforge:fmlloader:1.20.1-47.2.0"}
+                    ]
+                }"#,
+            )
+            .unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn parses_local_installer_jar() {
+        let contents = ForgeInstallerContents::parse(&fixture_installer()).unwrap();
+
+        assert_eq!(
+            contents.version_json.main_class,
+            "cpw.mods.bootstraplauncher.BootstrapLauncher"
+        );
+        let libraries: Vec<&str> = contents
+            .version_json
+            .libraries
+            .iter()
+            .map(|lib| lib.name.as_str())
+            .collect();
+        assert_eq!(
+            libraries,
+            [
+                "cpw.mods:bootstraplauncher:1.1.2",
+                "net.minecraftforge:fmlloader:1.20.1-47.2.0"
+            ]
+        );
+        assert_eq!(contents.install_profile.processors.len(), 1);
+        assert_eq!(
+            contents.install_profile.minecraft.as_deref(),
+            Some("1.20.1")
+        );
+        assert_eq!(contents.loader_version().as_deref(), Some("47.2.0"));
+    }
+
+    #[test]
+    fn bundled_libraries_are_unpacked_once() {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        let bundled =
+            "maven/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-universal.jar";
+        writer.start_file(bundled, options).unwrap();
+        writer.write_all(b"universal").unwrap();
+        writer.start_file("install_profile.json", options).unwrap();
+        writer.write_all(b"{}").unwrap();
+        let installer = writer.finish().unwrap().into_inner();
+        let libs = std::env::temp_dir().join(format!("forge-bundled-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&libs);
+
+        assert_eq!(unpack_bundled_libraries(&installer, &libs).unwrap(), 1);
+        let unpacked = libs.join(bundled.trim_start_matches("maven/"));
+        assert_eq!(std::fs::read(&unpacked).unwrap(), b"universal");
+        assert!(!libs.join("install_profile.json").exists());
+        assert_eq!(unpack_bundled_libraries(&installer, &libs).unwrap(), 0);
+        let _ = std::fs::remove_dir_all(&libs);
+    }
+
+    #[test]
+    fn installer_without_version_json_is_rejected() {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                "install_profile.json",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"{}").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        assert!(matches!(
+            ForgeInstallerContents::parse(&bytes),
+            Err(LauncherError::Loader(_))
+        ));
+    }
+}
@@ -2,7 +2,9 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::core::error::LauncherResult;
+use crate::core::i18n::Locale;
 use crate::core::instance::LoaderType;
+use crate::core::version::ResolvedLibrary;
 
 use super::{
     context::InstallContext, fabric::FabricInstaller, forge::ForgeInstaller,
@@ -16,11 +18,55 @@ pub struct LoaderInstallResult {
     pub extra_jvm_args: Vec<String>,
     pub extra_game_args: Vec<String>,
     pub libraries: Vec<String>,
+    /// Source URL and hash of the libraries above, when known.
+    #[serde(default)]
+    pub resolved_libraries: Vec<ResolvedLibrary>,
     pub asset_index_id: Option<String>,
     pub asset_index_url: Option<String>,
     pub java_major: Option<u32>,
 }
 
+/// API mod that most content for a loader depends on, shown as a hint
+/// next to the loader versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LoaderCompanion {
+    pub name: &'static str,
+    pub modrinth_slug: &'static str,
+    /// Stable key of the hint, for the frontend to translate.
+    pub i18n_key: &'static str,
+    /// Bundled text of `i18n_key` in the requested locale.
+    pub hint: &'static str,
+}
+
+/// Companion mod for `loader`, if it has one, with its hint in `locale`.
+pub fn loader_companion(loader: &LoaderType, locale: Locale) -> Option<LoaderCompanion> {
+    let hint = |es, en| match locale {
+        Locale::Es => es,
+        Locale::En => en,
+    };
+    match loader {
+        LoaderType::Fabric => Some(LoaderCompanion {
+            name: "Fabric API",
+            modrinth_slug: "fabric-api",
+            i18n_key: "companion.fabric_api",
+            hint: hint(
+                "La mayoría de los mods de Fabric necesitan Fabric API; instálalo como mod en la instancia.",
+                "Most Fabric mods need Fabric API; install it as a mod in the instance.",
+            ),
+        }),
+        LoaderType::Quilt => Some(LoaderCompanion {
+            name: "Quilted Fabric API (QFAPI) / Quilt Standard Libraries (QSL)",
+            modrinth_slug: "qsl",
+            i18n_key: "companion.quilt_api",
+            hint: hint(
+                "La mayoría de los mods de Quilt necesitan QFAPI/QSL; instálalo como mod en la instancia.",
+                "Most Quilt mods need QFAPI/QSL; install it as a mod in the instance.",
+            ),
+        }),
+        _ => None,
+    }
+}
+
 #[async_trait]
 pub trait LoaderInstaller: Send + Sync {
     async fn install(&self, ctx: InstallContext<'_>) -> LauncherResult<LoaderInstallResult>;
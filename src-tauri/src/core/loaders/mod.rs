@@ -5,7 +5,10 @@ pub mod installer;
 pub mod neoforge;
 pub mod quilt;
 pub mod vanilla;
+pub mod versions;
 
 pub use context::InstallContext;
 #[allow(unused_imports)]
-pub use installer::{Installer, LoaderInstallResult, LoaderInstaller};
+pub use installer::{
+    loader_companion, Installer, LoaderCompanion, LoaderInstallResult, LoaderInstaller,
+};
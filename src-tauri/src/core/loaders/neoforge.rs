@@ -9,7 +9,9 @@ use super::context::InstallContext;
 use super::installer::{LoaderInstallResult, LoaderInstaller};
 use crate::core::downloader::Downloader;
 use crate::core::error::{LauncherError, LauncherResult};
-use crate::core::maven::MavenArtifact;
+use crate::core::maven::{
+    MavenArtifact, MavenResolver, TransitivePolicy, MAVEN_CENTRAL, MOJANG_LIBRARIES,
+};
 use crate::core::version::VersionJson;
 
 /// NeoForge installer — similar to Forge but uses the NeoForge Maven and API.
@@ -129,20 +131,32 @@ impl LoaderInstaller for NeoForgeInstaller {
         let mut last_err: Option<LauncherError> = None;
         let mut downloaded = false;
 
-        for (name, url) in candidates {
-            info!("Trying NeoForge installer: {}", url);
-            let dest = ctx.instance_dir.join(&name);
-            match download_with_archive_validation(ctx.downloader, &url, &dest).await {
-                Ok(()) => {
-                    // Normalize to installer_path for the rest of the pipeline.
-                    if dest != installer_path {
-                        let _ = tokio::fs::remove_file(&installer_path).await;
-                        let _ = tokio::fs::rename(&dest, &installer_path).await;
+        // A local installer is copied: the copy is removed after the install.
+        if let Some(local) = ctx.local_installer {
+            info!("Using local NeoForge installer {}", local.display());
+            tokio::fs::copy(local, &installer_path)
+                .await
+                .map_err(|e| LauncherError::Io {
+                    path: local.to_path_buf(),
+                    source: e,
+                })?;
+            downloaded = true;
+        } else {
+            for (name, url) in candidates {
+                info!("Trying NeoForge installer: {}", url);
+                let dest = ctx.instance_dir.join(&name);
+                match download_with_archive_validation(ctx.downloader, &url, &dest).await {
+                    Ok(()) => {
+                        // Normalize to installer_path for the rest of the pipeline.
+                        if dest != installer_path {
+                            let _ = tokio::fs::remove_file(&installer_path).await;
+                            let _ = tokio::fs::rename(&dest, &installer_path).await;
+                        }
+                        downloaded = true;
+                        break;
                     }
-                    downloaded = true;
-                    break;
+                    Err(e) => last_err = Some(e),
                 }
-                Err(e) => last_err = Some(e),
             }
         }
 
@@ -179,12 +193,22 @@ impl LoaderInstaller for NeoForgeInstaller {
         };
 
         let java_bin = crate::core::java::resolve_runtime(
+            ctx.data_dir,
             crate::core::java::RuntimeRole::Delta,
             Some(ctx.minecraft_version),
+            ctx.runtime.clone(),
         )
         .await?;
         log_runtime_role("Delta", &java_bin, ctx.instance_dir);
 
+        let bundled = super::forge::unpack_bundled_libraries(&installer_bytes, ctx.libs_dir)?;
+        if bundled > 0 {
+            info!(
+                "Unpacked {} libraries bundled in the NeoForge installer",
+                bundled
+            );
+        }
+
         // Download libraries from install_profile
         let mut libraries = BTreeSet::new();
         for lib in &install_profile.libraries {
@@ -227,11 +251,12 @@ impl LoaderInstaller for NeoForgeInstaller {
         );
 
         // Run processors (client side)
-        for processor in &install_profile.processors {
-            if let Some(sides) = &processor.sides {
-                if !sides.iter().any(|s| s == "client") {
-                    continue;
-                }
+        let steps = client_processor_steps(&install_profile.processors);
+        let total_steps = steps.len();
+        for (index, (processor, label)) in steps.into_iter().enumerate() {
+            info!("{}", label);
+            if let Some(on_step) = ctx.on_step {
+                on_step(index + 1, total_steps, &label);
             }
 
             let jar_artifact = MavenArtifact::parse(&processor.jar)?;
@@ -290,13 +315,19 @@ impl LoaderInstaller for NeoForgeInstaller {
                 .map_err(|e| LauncherError::JavaExecution(e.to_string()))?;
 
             if !output.status.success() {
-                return Err(LauncherError::Loader(format!(
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                warn!(
                     "NeoForge processor {} failed (code {:?})\nSTDOUT:\n{}\nSTDERR:\n{}",
                     processor.jar,
                     output.status.code(),
-                    String::from_utf8_lossy(&output.stdout),
-                    String::from_utf8_lossy(&output.stderr)
-                )));
+                    stdout,
+                    stderr
+                );
+                return Err(LauncherError::LoaderProcessorFailed {
+                    name: processor_name(processor),
+                    output_tail: output_tail(&stdout, &stderr, PROCESSOR_OUTPUT_TAIL_LINES),
+                });
             }
 
             // `processor.classpath` y `processor.jar` se usan solo durante instalación.
@@ -307,6 +338,7 @@ impl LoaderInstaller for NeoForgeInstaller {
         let mut extra_jvm_args = Vec::new();
         let mut extra_game_args = Vec::new();
         let mut resolved_main_class = version_json.main_class.clone();
+        let mut resolved_libraries = Vec::new();
 
         let installed_version_path = resolve_installed_neoforge_version_path(&ctx);
         if installed_version_path.exists() {
@@ -324,6 +356,7 @@ impl LoaderInstaller for NeoForgeInstaller {
             resolved_main_class = installed_version.main_class.clone();
             extra_jvm_args = installed_version.simple_jvm_args();
             extra_game_args = installed_version.simple_game_args();
+            resolved_libraries = installed_version.resolved_libraries();
 
             for lib in installed_version
                 .download_libraries(ctx.libs_dir, ctx.downloader)
@@ -360,6 +393,31 @@ impl LoaderInstaller for NeoForgeInstaller {
             }
         }
 
+        if ctx.resolve_transitive {
+            let declared: Vec<String> = libraries.iter().cloned().collect();
+            let mut resolver = MavenResolver::new(vec![
+                NEOFORGE_MAVEN.to_string(),
+                MAVEN_CENTRAL.to_string(),
+                MOJANG_LIBRARIES.to_string(),
+            ]);
+            let transitive = resolver
+                .resolve_missing_transitive(
+                    &declared,
+                    ctx.libs_dir,
+                    ctx.downloader,
+                    &TransitivePolicy::LOADER_LIBRARIES,
+                )
+                .await;
+            if !transitive.is_empty() {
+                info!(
+                    "Added {} transitive NeoForge libraries: {}",
+                    transitive.len(),
+                    transitive.join(", ")
+                );
+            }
+            libraries.extend(transitive);
+        }
+
         let _ = tokio::fs::remove_file(&installer_path).await;
 
         info!("NeoForge {} installed successfully", ctx.loader_version);
@@ -369,6 +427,7 @@ impl LoaderInstaller for NeoForgeInstaller {
             extra_jvm_args,
             extra_game_args,
             libraries: libraries.into_iter().collect(),
+            resolved_libraries,
             asset_index_id: None,
             asset_index_url: None,
             java_major: Some(crate::core::java::required_java_for_minecraft_version(
@@ -378,6 +437,55 @@ impl LoaderInstaller for NeoForgeInstaller {
     }
 }
 
+/// Lines of processor output kept in `LoaderProcessorFailed`.
+const PROCESSOR_OUTPUT_TAIL_LINES: usize = 20;
+
+/// Short name for a processor: the installertools `--task` when present
+/// (`MERGE_MAPPINGS`), otherwise the artifact name (`binarypatcher`).
+fn processor_name(processor: &NeoForgeProcessor) -> String {
+    processor
+        .args
+        .iter()
+        .position(|arg| arg == "--task")
+        .and_then(|index| processor.args.get(index + 1))
+        .cloned()
+        .or_else(|| processor.jar.split(':').nth(1).map(str::to_string))
+        .unwrap_or_else(|| processor.jar.clone())
+}
+
+/// Client-side processors paired with their progress label.
+fn client_processor_steps(processors: &[NeoForgeProcessor]) -> Vec<(&NeoForgeProcessor, String)> {
+    let client: Vec<&NeoForgeProcessor> = processors
+        .iter()
+        .filter(|processor| {
+            processor
+                .sides
+                .as_ref()
+                .is_none_or(|sides| sides.iter().any(|s| s == "client"))
+        })
+        .collect();
+    let total = client.len();
+    client
+        .into_iter()
+        .enumerate()
+        .map(|(index, processor)| {
+            let label = format!(
+                "Ejecutando procesador {}/{}: {}",
+                index + 1,
+                total,
+                processor_name(processor)
+            );
+            (processor, label)
+        })
+        .collect()
+}
+
+/// Last `max_lines` lines of stdout followed by stderr.
+fn output_tail(stdout: &str, stderr: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = stdout.lines().chain(stderr.lines()).collect();
+    lines[lines.len().saturating_sub(max_lines)..].join("\n")
+}
+
 fn log_runtime_role(role: &str, java_bin: &Path, cwd: &Path) {
     let version = std::process::Command::new(java_bin)
         .arg("-version")
@@ -666,3 +774,64 @@ fn resolve_version_with_inheritance(
 
     serde_json::from_value(current_json).map_err(LauncherError::from)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(jar: &str, sides: Option<&[&str]>, args: &[&str]) -> NeoForgeProcessor {
+        NeoForgeProcessor {
+            sides: sides.map(|sides| sides.iter().map(|s| s.to_string()).collect()),
+            jar: jar.into(),
+            classpath: Vec::new(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn labels_client_processors_in_order() {
+        let processors = [
+            processor(
+                "net.neoforged.installertools:installertools:2.1.2",
+                None,
+                &["--task", "EXTRACT_FILES", "--archive", "{INSTALLER}"],
+            ),
+            processor(
+                "net.neoforged.installertools:installertools:2.1.2",
+                Some(&["server"]),
+                &["--task", "BUNDLER_EXTRACT"],
+            ),
+            processor(
+                "net.neoforged.installertools:installertools:2.1.2",
+                Some(&["client"]),
+                &["--task", "MERGE_MAPPINGS"],
+            ),
+            processor(
+                "net.neoforged.installertools:binarypatcher:2.1.2",
+                Some(&["client", "server"]),
+                &["--clean", "{MC_SRG}"],
+            ),
+        ];
+
+        let labels: Vec<String> = client_processor_steps(&processors)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Ejecutando procesador 1/3: EXTRACT_FILES",
+                "Ejecutando procesador 2/3: MERGE_MAPPINGS",
+                "Ejecutando procesador 3/3: binarypatcher",
+            ]
+        );
+    }
+
+    #[test]
+    fn output_tail_keeps_last_lines() {
+        let stdout: String = (1..=25).map(|i| format!("line {i}\n")).collect();
+        let tail = output_tail(&stdout, "boom", 3);
+        assert_eq!(tail, "line 24\nline 25\nboom");
+        assert_eq!(output_tail("", "only", 20), "only");
+    }
+}
@@ -1,10 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::context::InstallContext;
 use super::installer::{LoaderInstallResult, LoaderInstaller};
 use crate::core::error::{LauncherError, LauncherResult};
 use crate::core::http::build_http_client;
+use crate::core::version::ResolvedLibrary;
 
 /// Installs Quilt loader via the Quilt Meta API (nearly identical to Fabric's API).
 pub struct QuiltInstaller {
@@ -33,6 +34,10 @@ pub struct QuiltProfile {
 pub struct QuiltLibrary {
     pub name: String,
     pub url: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,6 +92,7 @@ impl LoaderInstaller for QuiltInstaller {
 
         // Download libraries
         let mut lib_names = Vec::new();
+        let mut resolved_libraries = Vec::new();
         for lib in &profile.libraries {
             let repo = lib
                 .url
@@ -94,11 +100,18 @@ impl LoaderInstaller for QuiltInstaller {
                 .unwrap_or(crate::core::maven::QUILT_MAVEN);
             let artifact = crate::core::maven::MavenArtifact::parse(&lib.name)?;
             let dest = ctx.libs_dir.join(artifact.local_path());
+            let url = artifact.url(repo);
             if !dest.exists() {
-                let url = artifact.url(repo);
                 ctx.downloader.download_file(&url, &dest, None).await?;
             }
             lib_names.push(lib.name.clone());
+            resolved_libraries.push(ResolvedLibrary {
+                coord: lib.name.clone(),
+                path: artifact.local_path(),
+                url,
+                sha1: lib.sha1.clone(),
+                size: lib.size,
+            });
         }
 
         let (jvm_args, game_args) = match &profile.arguments {
@@ -113,6 +126,7 @@ impl LoaderInstaller for QuiltInstaller {
             extra_jvm_args: jvm_args,
             extra_game_args: game_args,
             libraries: lib_names,
+            resolved_libraries,
             asset_index_id: None,
             asset_index_url: None,
             java_major: None,
@@ -120,11 +134,21 @@ impl LoaderInstaller for QuiltInstaller {
     }
 }
 
+/// A Quilt loader build. Quilt Meta has no stability flag, so builds with a
+/// semver pre-release suffix (`0.26.0-beta.1`) are the unstable ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuiltLoaderRelease {
+    pub version: String,
+    pub stable: bool,
+}
+
 /// Fetch available Quilt loader versions for a Minecraft version.
-pub async fn list_loader_versions(minecraft_version: &str) -> LauncherResult<Vec<String>> {
+pub async fn list_loader_versions(
+    minecraft_version: &str,
+) -> LauncherResult<Vec<QuiltLoaderRelease>> {
     let url = format!("{}/versions/loader/{}", QUILT_META_BASE, minecraft_version);
     let client = build_http_client()?;
-    let resp = client.get(&url).send().await?;
+    let resp = crate::core::net::get_with_retry(&client, &url).await?;
     if !resp.status().is_success() {
         return Err(LauncherError::LoaderApi(format!(
             "Quilt Meta returned {}",
@@ -132,9 +156,23 @@ pub async fn list_loader_versions(minecraft_version: &str) -> LauncherResult<Vec
         )));
     }
 
-    let versions: Vec<QuiltLoaderEntry> = resp.json().await?;
+    parse_loader_versions(&resp.text().await?)
+}
 
-    Ok(versions.into_iter().map(|v| v.loader.version).collect())
+/// Parse a Quilt Meta `versions/loader/<mc>` response.
+pub fn parse_loader_versions(body: &str) -> LauncherResult<Vec<QuiltLoaderRelease>> {
+    let entries: Vec<QuiltLoaderEntry> = serde_json::from_str(body)?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let version = entry.loader.version;
+            let core = version.split('+').next().unwrap_or(&version);
+            QuiltLoaderRelease {
+                stable: !core.contains('-'),
+                version,
+            }
+        })
+        .collect())
 }
 
 #[derive(Deserialize)]
@@ -146,3 +184,31 @@ struct QuiltLoaderEntry {
 struct QuiltLoaderVersion {
     version: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loader_versions_are_marked_by_their_prerelease_suffix() {
+        let body = r#"[
+            { "loader": { "separator": ".", "build": 0, "maven": "org.quiltmc:quilt-loader:0.26.0-beta.1", "version": "0.26.0-beta.1" } },
+            { "loader": { "separator": ".", "build": 0, "maven": "org.quiltmc:quilt-loader:0.25.0", "version": "0.25.0" } },
+            { "loader": { "separator": ".", "build": 0, "maven": "org.quiltmc:quilt-loader:0.25.0+local", "version": "0.25.0+local" } }
+        ]"#;
+
+        let releases = parse_loader_versions(body).unwrap();
+        let summary: Vec<(&str, bool)> = releases
+            .iter()
+            .map(|release| (release.version.as_str(), release.stable))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("0.26.0-beta.1", false),
+                ("0.25.0", true),
+                ("0.25.0+local", true)
+            ]
+        );
+    }
+}
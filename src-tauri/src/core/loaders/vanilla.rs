@@ -25,7 +25,12 @@ impl LoaderInstaller for VanillaInstaller {
         info!("Installing Vanilla {}", ctx.minecraft_version);
 
         // 1. Fetch version manifest
-        let manifest = VersionManifest::fetch(&self.client).await?;
+        let (manifest, _) = VersionManifest::fetch_cached(
+            &self.client,
+            ctx.metadata_cache,
+            &ctx.downloader.mirrors(),
+        )
+        .await?;
 
         // 2. Find matching version entry
         let entry = manifest
@@ -37,26 +42,33 @@ impl LoaderInstaller for VanillaInstaller {
                 ))
             })?;
 
-        // 3. Fetch and save version JSON
-        let (version_json, raw_json) = VersionJson::fetch(&self.client, &entry.url).await?;
+        // 3. Fetch (or reuse the cached) version JSON and save it
+        let (version_json, raw_json) = VersionJson::fetch_cached(
+            &self.client,
+            ctx.metadata_cache,
+            ctx.minecraft_version,
+            &entry.url,
+        )
+        .await?;
         VersionJson::save_to(&raw_json, ctx.instance_dir, ctx.minecraft_version).await?;
 
-        // 4. Download client.jar
-        version_json
-            .download_client(ctx.instance_dir, ctx.downloader)
-            .await?;
+        // 4. Download client.jar and the libraries (with OS rules evaluation)
+        //    side by side; both share the downloader's concurrency limit.
+        let (client_size, lib_coords) = tokio::try_join!(
+            version_json.download_client(ctx.instance_dir, ctx.downloader),
+            version_json.download_libraries(ctx.libs_dir, ctx.downloader),
+        )?;
+        if let Some(size) = client_size {
+            info!("client.jar verified ({} bytes)", size);
+        }
 
-        // 5. Download libraries (with OS rules evaluation)
-        let lib_coords = version_json
-            .download_libraries(ctx.libs_dir, ctx.downloader)
-            .await?;
-
-        // 6. Collect asset index info
+        // 5. Collect asset index info
         let asset_index_id = version_json.asset_index.as_ref().map(|ai| ai.id.clone());
         let asset_index_url = version_json.asset_index.as_ref().map(|ai| ai.url.clone());
         let extra_jvm_args = version_json.simple_jvm_args();
         let extra_game_args = version_json.simple_game_args();
         let java_major = Some(version_json.required_java_major());
+        let resolved_libraries = version_json.resolved_libraries();
 
         info!("Vanilla {} installed successfully", ctx.minecraft_version);
 
@@ -65,6 +77,7 @@ impl LoaderInstaller for VanillaInstaller {
             extra_jvm_args,
             extra_game_args,
             libraries: lib_coords,
+            resolved_libraries,
             asset_index_id,
             asset_index_url,
             java_major,
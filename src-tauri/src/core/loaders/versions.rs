@@ -0,0 +1,498 @@
+// ─── Loader Versions ───
+// Published loader versions for a Minecraft version, read from the Fabric /
+// Quilt meta APIs and the Forge / NeoForge Maven metadata (with the metadata
+// cache as offline fallback), and the checks that reject a version the
+// loader never published for that Minecraft line.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::core::error::LauncherError;
+use crate::core::events::EventEmitter;
+use crate::core::i18n::ErrorText;
+use crate::core::instance::LoaderType;
+use crate::core::loaders;
+use crate::core::metadata_cache::{parse_json, MetadataCache};
+use crate::core::state::AppState;
+use crate::core::version::compare::{compare_versions, version_tokens, VersionToken};
+
+/// A loader version as listed to the UI. `stable: false` marks beta builds
+/// so they can be rendered differently.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoaderVersionInfo {
+    pub version: String,
+    pub stable: bool,
+}
+
+impl AsRef<str> for LoaderVersionInfo {
+    fn as_ref(&self) -> &str {
+        &self.version
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FabricLoaderEntry {
+    loader: FabricLoaderVersion,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FabricLoaderVersion {
+    version: String,
+    #[serde(default)]
+    stable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MavenMetadata {
+    pub versioning: MavenVersioning,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MavenVersioning {
+    pub versions: MavenVersions,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MavenVersions {
+    #[serde(rename = "version", default)]
+    pub version: Vec<String>,
+}
+
+pub(crate) const FORGE_METADATA_KEY: &str = "forge-maven-metadata";
+
+pub(crate) const FORGE_METADATA_URL: &str =
+    "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
+
+pub(crate) const NEOFORGE_METADATA_KEY: &str = "neoforge-maven-metadata";
+
+pub(crate) const NEOFORGE_METADATA_URL: &str =
+    "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
+
+pub(crate) const NEOFORGE_LEGACY_METADATA_KEY: &str = "neoforge-legacy-maven-metadata";
+
+pub(crate) const NEOFORGE_LEGACY_METADATA_URL: &str =
+    "https://maven.neoforged.net/releases/net/neoforged/forge/maven-metadata.xml";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CachedMetadataEvent {
+    key: String,
+    cached_at: String,
+}
+
+/// Tell the frontend a listing is served from the offline cache.
+pub(crate) fn emit_cached_metadata_warning(
+    events: &dyn EventEmitter,
+    key: &str,
+    cached_at: Option<chrono::DateTime<Utc>>,
+) {
+    let Some(cached_at) = cached_at else {
+        return;
+    };
+    warn!("Using cached metadata '{}' from {}", key, cached_at);
+    events.emit(
+        "using-cached-metadata",
+        CachedMetadataEvent {
+            key: key.to_string(),
+            cached_at: cached_at.to_rfc3339(),
+        },
+    );
+}
+
+/// Fetch and `parse` a metadata document through the offline cache.
+pub(crate) async fn fetch_metadata<T>(
+    events: &dyn EventEmitter,
+    cache: &MetadataCache,
+    client: &reqwest::Client,
+    key: &str,
+    url: &str,
+    parse: impl Fn(&str) -> Result<T, LauncherError>,
+) -> Result<T, LauncherError> {
+    let response = cache
+        .fetch_with_fallback(
+            key,
+            || crate::core::metadata_cache::fetch_text(client, url),
+            parse,
+        )
+        .await?;
+    emit_cached_metadata_warning(events, key, response.cached_at);
+    Ok(response.value)
+}
+
+pub(crate) fn parse_maven_metadata(xml: &str) -> Result<MavenMetadata, LauncherError> {
+    quick_xml::de::from_str(xml)
+        .map_err(|e| LauncherError::LoaderApi(format!("Unable to parse loader metadata: {e}")))
+}
+
+/// Sort newest-first and drop duplicates. The string tiebreaker keeps equal
+/// entries adjacent so `dedup` sees them.
+pub(crate) fn sort_versions_desc<T: AsRef<str>>(versions: &mut Vec<T>) {
+    versions.sort_by(|a, b| {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        compare_versions(b, a).then_with(|| b.cmp(a))
+    });
+    versions.dedup_by(|a, b| a.as_ref() == b.as_ref());
+}
+
+/// Whether a version carries an alpha/beta/pre/rc qualifier.
+pub(crate) fn is_prerelease_version(version: &str) -> bool {
+    version_tokens(version)
+        .iter()
+        .any(|token| matches!(token, VersionToken::Qualifier(rank, _) if *rank < 4))
+}
+
+/// Loaders without a stability flag in their metadata are marked from the
+/// version string itself.
+pub(crate) fn loader_version_info(version: String) -> LoaderVersionInfo {
+    LoaderVersionInfo {
+        stable: !is_prerelease_version(&version),
+        version,
+    }
+}
+
+/// Fabric Meta publishes a `stable` flag; unstable builds are only listed on
+/// request, which is often the only option right after a Minecraft release.
+pub(crate) fn fabric_loader_versions(
+    entries: Vec<FabricLoaderEntry>,
+    include_unstable: bool,
+) -> Vec<LoaderVersionInfo> {
+    entries
+        .into_iter()
+        .filter(|entry| include_unstable || entry.loader.stable)
+        .map(|entry| LoaderVersionInfo {
+            version: entry.loader.version,
+            stable: entry.loader.stable,
+        })
+        .collect()
+}
+
+/// Quilt's beta channel is often the only one for a new Minecraft release,
+/// so it is listed on request just like Fabric's unstable builds.
+pub(crate) fn quilt_loader_versions(
+    releases: Vec<loaders::quilt::QuiltLoaderRelease>,
+    include_unstable: bool,
+) -> Vec<LoaderVersionInfo> {
+    releases
+        .into_iter()
+        .filter(|release| include_unstable || release.stable)
+        .map(|release| LoaderVersionInfo {
+            version: release.version,
+            stable: release.stable,
+        })
+        .collect()
+}
+
+pub(crate) fn is_neoforge_compatible(version: &str, minecraft_version: &str) -> bool {
+    let mut mc_parts = minecraft_version
+        .trim_start_matches("1.")
+        .split('.')
+        .filter_map(|part| part.parse::<u64>().ok());
+
+    let Some(mc_major) = mc_parts.next() else {
+        return false;
+    };
+    let Some(mc_minor) = mc_parts.next() else {
+        return false;
+    };
+
+    let mut loader_parts = version
+        .split('.')
+        .filter_map(|part| part.parse::<u64>().ok());
+    let Some(loader_major) = loader_parts.next() else {
+        return false;
+    };
+    let Some(loader_minor) = loader_parts.next() else {
+        return false;
+    };
+
+    loader_major == mc_major && loader_minor == mc_minor
+}
+
+pub(crate) async fn fetch_loader_versions(
+    events: &dyn EventEmitter,
+    state: &AppState,
+    loader_type: &LoaderType,
+    minecraft_version: &str,
+    include_unstable: bool,
+) -> Result<Vec<LoaderVersionInfo>, LauncherError> {
+    let client = state.http_client.clone();
+    let cache = state.metadata_cache();
+
+    let mut versions: Vec<LoaderVersionInfo> = match loader_type {
+        LoaderType::Vanilla => vec![],
+        LoaderType::Fabric => {
+            let url = format!(
+                "https://meta.fabricmc.net/v2/versions/loader/{}",
+                minecraft_version
+            );
+            let entries: Vec<FabricLoaderEntry> = fetch_metadata(
+                events,
+                &cache,
+                &client,
+                &format!("fabric-loader-{minecraft_version}"),
+                &url,
+                parse_json,
+            )
+            .await?;
+            fabric_loader_versions(entries, include_unstable)
+        }
+        LoaderType::Quilt => {
+            let key = format!("quilt-loader-releases-{minecraft_version}");
+            let response = cache
+                .fetch_with_fallback(
+                    &key,
+                    || async {
+                        let versions =
+                            loaders::quilt::list_loader_versions(minecraft_version).await?;
+                        Ok(serde_json::to_string(&versions)?)
+                    },
+                    parse_json,
+                )
+                .await?;
+            emit_cached_metadata_warning(events, &key, response.cached_at);
+
+            quilt_loader_versions(response.value, include_unstable)
+        }
+        LoaderType::Forge => {
+            let metadata = fetch_metadata(
+                events,
+                &cache,
+                &client,
+                FORGE_METADATA_KEY,
+                FORGE_METADATA_URL,
+                parse_maven_metadata,
+            )
+            .await?;
+
+            metadata
+                .versioning
+                .versions
+                .version
+                .into_iter()
+                .filter_map(|v| {
+                    v.strip_prefix(&format!("{}-", minecraft_version))
+                        .map(str::to_owned)
+                })
+                .map(loader_version_info)
+                .collect()
+        }
+        LoaderType::NeoForge => {
+            let metadata = fetch_metadata(
+                events,
+                &cache,
+                &client,
+                NEOFORGE_METADATA_KEY,
+                NEOFORGE_METADATA_URL,
+                parse_maven_metadata,
+            )
+            .await?;
+
+            let mut resolved: Vec<String> = metadata
+                .versioning
+                .versions
+                .version
+                .into_iter()
+                .filter(|v| is_neoforge_compatible(v, minecraft_version))
+                .collect();
+
+            if minecraft_version == "1.20.1" {
+                let legacy_metadata = fetch_metadata(
+                    events,
+                    &cache,
+                    &client,
+                    NEOFORGE_LEGACY_METADATA_KEY,
+                    NEOFORGE_LEGACY_METADATA_URL,
+                    parse_maven_metadata,
+                )
+                .await?;
+
+                resolved.extend(legacy_metadata.versioning.versions.version);
+            }
+
+            resolved.into_iter().map(loader_version_info).collect()
+        }
+    };
+
+    sort_versions_desc(&mut versions);
+
+    Ok(versions)
+}
+
+/// Rejects a `loader_version` that is not published for `minecraft_version`
+/// in the list `get_loader_versions` returns.
+pub(crate) fn check_loader_version(
+    loader_type: &LoaderType,
+    minecraft_version: &str,
+    loader_version: &str,
+    available: &[LoaderVersionInfo],
+) -> Result<(), LauncherError> {
+    // 1.20.1 NeoForge builds come from the legacy `forge` artifact and keep
+    // Forge-style numbering, so only the Maven list can vouch for them.
+    if *loader_type == LoaderType::NeoForge
+        && minecraft_version != "1.20.1"
+        && !is_neoforge_compatible(loader_version, minecraft_version)
+    {
+        return Err(LauncherError::message(
+            ErrorText::NeoForgeVersionMismatch,
+            &[&loader_version, &minecraft_version],
+        ));
+    }
+
+    if available.iter().any(|info| info.version == loader_version) {
+        Ok(())
+    } else {
+        Err(LauncherError::message(
+            ErrorText::LoaderVersionMissing,
+            &[&loader_version, loader_type, &minecraft_version],
+        ))
+    }
+}
+
+/// Checks the requested loader version before anything is downloaded. When
+/// the loader metadata cannot be fetched (and nothing is cached) creation goes
+/// ahead and the installer reports the problem instead.
+pub(crate) async fn validate_loader_version(
+    events: &dyn EventEmitter,
+    state: &AppState,
+    loader_type: &LoaderType,
+    minecraft_version: &str,
+    loader_version: Option<&str>,
+) -> Result<(), LauncherError> {
+    let Some(loader_version) = loader_version else {
+        return Ok(());
+    };
+    if *loader_type == LoaderType::Vanilla {
+        return Ok(());
+    }
+
+    match fetch_loader_versions(events, state, loader_type, minecraft_version, true).await {
+        Ok(available) => {
+            check_loader_version(loader_type, minecraft_version, loader_version, &available)
+        }
+        Err(err) if err.is_recoverable() => {
+            warn!(
+                "Cannot verify {} {} for MC {}: {}",
+                loader_type, loader_version, minecraft_version, err
+            );
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neoforge_compatibility_matches_same_minor_line() {
+        assert!(is_neoforge_compatible("21.1.127", "1.21.1"));
+        assert!(is_neoforge_compatible("20.6.95-beta", "1.20.6"));
+    }
+
+    #[test]
+    fn neoforge_compatibility_rejects_other_minor_lines() {
+        assert!(!is_neoforge_compatible("21.10.4", "1.21.1"));
+        assert!(!is_neoforge_compatible("20.5.12", "1.20.1"));
+        assert!(!is_neoforge_compatible("invalid", "1.20.1"));
+    }
+
+    #[test]
+    fn sort_versions_desc_orders_and_dedups() {
+        let mut versions = vec![
+            "1.20.9".to_string(),
+            "21.1.0-beta".to_string(),
+            "1.20.10".to_string(),
+            "21.1.0".to_string(),
+            "1.20.10".to_string(),
+        ];
+        sort_versions_desc(&mut versions);
+        assert_eq!(versions, vec!["21.1.0", "21.1.0-beta", "1.20.10", "1.20.9"]);
+    }
+
+    #[test]
+    fn fabric_loader_versions_hide_unstable_by_default() {
+        let entries = || -> Vec<FabricLoaderEntry> {
+            serde_json::from_str(
+                r#"[
+                    { "loader": { "version": "0.16.0-beta.1", "stable": false } },
+                    { "loader": { "version": "0.15.11", "stable": true } },
+                    { "loader": { "version": "0.15.10" } }
+                ]"#,
+            )
+            .unwrap()
+        };
+
+        let stable = fabric_loader_versions(entries(), false);
+        assert_eq!(stable.len(), 1);
+        assert_eq!(stable[0].version, "0.15.11");
+
+        let all = fabric_loader_versions(entries(), true);
+        assert_eq!(all.len(), 3);
+        assert!(!all[0].stable);
+        assert!(all[1].stable);
+    }
+
+    #[test]
+    fn quilt_loader_versions_filter_betas_and_sort_semantically() {
+        let fixture = r#"[
+            { "loader": { "version": "0.26.0-beta.2" } },
+            { "loader": { "version": "0.25.1" } },
+            { "loader": { "version": "0.26.0-beta.10" } },
+            { "loader": { "version": "0.25.10" } },
+            { "loader": { "version": "0.25.9" } }
+        ]"#;
+        let releases = || crate::core::loaders::quilt::parse_loader_versions(fixture).unwrap();
+        let versions = |infos: &[super::LoaderVersionInfo]| -> Vec<String> {
+            infos.iter().map(|info| info.version.clone()).collect()
+        };
+
+        let mut stable = quilt_loader_versions(releases(), false);
+        sort_versions_desc(&mut stable);
+        assert_eq!(versions(&stable), ["0.25.10", "0.25.9", "0.25.1"]);
+
+        let mut all = quilt_loader_versions(releases(), true);
+        sort_versions_desc(&mut all);
+        assert_eq!(
+            versions(&all),
+            [
+                "0.26.0-beta.10",
+                "0.26.0-beta.2",
+                "0.25.10",
+                "0.25.9",
+                "0.25.1"
+            ]
+        );
+        assert!(!all[0].stable);
+        assert!(all[2].stable);
+    }
+
+    #[test]
+    fn neoforge_version_must_match_minecraft_line() {
+        let available = [loader_version_info("21.1.77".into())];
+        assert!(
+            check_loader_version(&LoaderType::NeoForge, "1.21.1", "21.1.77", &available).is_ok()
+        );
+        assert!(
+            check_loader_version(&LoaderType::NeoForge, "1.21.1", "20.4.237", &available).is_err()
+        );
+    }
+
+    #[test]
+    fn fabric_version_outside_published_list_is_rejected() {
+        let stable = fabric_loader_versions(
+            serde_json::from_str(r#"[{ "loader": { "version": "0.15.11", "stable": true } }]"#)
+                .unwrap(),
+            false,
+        );
+        assert!(check_loader_version(&LoaderType::Fabric, "1.20.1", "0.15.11", &stable).is_ok());
+        assert!(check_loader_version(&LoaderType::Fabric, "1.20.1", "0.99.0", &stable).is_err());
+    }
+
+    #[test]
+    fn loader_version_info_marks_prereleases() {
+        assert!(!loader_version_info("0.26.0-beta.1".into()).stable);
+        assert!(!loader_version_info("21.0.10-beta".into()).stable);
+        assert!(loader_version_info("47.2.0".into()).stable);
+    }
+}
@@ -0,0 +1,70 @@
+// ─── Long Paths ───
+// Modpacks nest deep enough to exceed Windows' 260-character MAX_PATH. File
+// operations over whole instance trees take their root through `long_path`,
+// which makes it absolute and adds the `\\?\` extended-length prefix on
+// Windows. Java does not accept that prefix, so launch arguments keep going
+// through `classpath::safe_path_str`, which strips it again.
+
+use std::path::{Path, PathBuf};
+
+/// `path` in a form that file APIs accept beyond MAX_PATH: an absolute
+/// `\\?\C:\...` or `\\?\UNC\server\share\...` path on Windows, unchanged
+/// elsewhere. Separators and `..` are normalized first because Windows does
+/// not normalize extended-length paths.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        use std::path::{Component, Prefix};
+
+        let Ok(absolute) = std::path::absolute(path) else {
+            return path.to_path_buf();
+        };
+        let Some(Component::Prefix(prefix)) = absolute.components().next() else {
+            return absolute;
+        };
+        let Some(text) = absolute.to_str() else {
+            return absolute;
+        };
+        match prefix.kind() {
+            Prefix::Disk(_) => PathBuf::from(format!(r"\\?\{text}")),
+            Prefix::UNC(..) => match text.strip_prefix(r"\\") {
+                Some(rest) => PathBuf::from(format!(r"\\?\UNC\{rest}")),
+                None => absolute,
+            },
+            // Already verbatim, or a device path.
+            _ => absolute,
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn paths_are_unchanged_off_windows() {
+        let path = Path::new("/data/instances/abc/minecraft");
+        assert_eq!(long_path(path), path);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_paths_get_the_extended_length_prefix() {
+        assert_eq!(
+            long_path(Path::new(r"C:\Games/Interface\instances\..\runtime")),
+            PathBuf::from(r"\\?\C:\Games\Interface\runtime")
+        );
+        assert_eq!(
+            long_path(Path::new(r"\\nas\games\Interface")),
+            PathBuf::from(r"\\?\UNC\nas\games\Interface")
+        );
+        let verbatim = Path::new(r"\\?\C:\Games\Interface");
+        assert_eq!(long_path(verbatim), verbatim);
+    }
+}
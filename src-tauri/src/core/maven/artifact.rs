@@ -110,6 +110,13 @@ impl MavenArtifact {
         clone
     }
 
+    /// The artifact's POM, which never carries the JAR classifier.
+    pub fn pom(&self) -> Self {
+        let mut clone = self.with_packaging("pom");
+        clone.classifier = None;
+        clone
+    }
+
     /// Check if this artifact is a POM-only artifact.
     pub fn is_pom(&self) -> bool {
         self.packaging == "pom"
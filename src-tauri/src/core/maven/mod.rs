@@ -6,7 +6,7 @@ pub use artifact::MavenArtifact;
 #[allow(unused_imports)]
 pub use pom::{PomDependency, PomDocument};
 #[allow(unused_imports)]
-pub use resolver::MavenResolver;
+pub use resolver::{MavenResolver, TransitivePolicy};
 
 /// Well-known Maven repositories used by Minecraft ecosystem.
 pub const MOJANG_LIBRARIES: &str = "https://libraries.minecraft.net";
@@ -93,6 +93,27 @@ impl PomDocument {
         None
     }
 
+    /// Return dependencies in one of `scopes` (a missing scope is `compile`),
+    /// with optional ones only when `include_optional`.
+    pub fn dependencies_in_scopes(
+        &self,
+        scopes: &[&str],
+        include_optional: bool,
+    ) -> Vec<PomDependency> {
+        let deps = match &self.dependencies {
+            Some(d) => &d.items,
+            None => return vec![],
+        };
+
+        deps.iter()
+            .filter(|d| {
+                let scope = d.scope.as_deref().unwrap_or("compile");
+                scopes.contains(&scope) && (include_optional || !d.optional.unwrap_or(false))
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Return compile-scope dependencies, ignoring optional and test/provided scopes.
     pub fn compile_dependencies(&self) -> Vec<PomDependency> {
         let deps = match &self.dependencies {
@@ -1,13 +1,41 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 use tracing::{debug, warn};
 
 use super::artifact::MavenArtifact;
-use super::pom::PomDocument;
+use super::pom::{PomDependency, PomDocument};
 use crate::core::downloader::Downloader;
 use crate::core::error::{LauncherError, LauncherResult};
 
+/// What `MavenResolver::transitive_closure` follows below the declared
+/// libraries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitivePolicy {
+    /// Levels of dependencies added; 1 takes only the direct dependencies of
+    /// the declared libraries.
+    pub max_depth: usize,
+    /// POM scopes whose dependencies are followed.
+    pub scopes: &'static [&'static str],
+    /// Dependency types that are followed; others (`pom` BOMs, `test-jar`,
+    /// ...) are skipped.
+    pub packagings: &'static [&'static str],
+    /// Whether dependencies marked `<optional>` are followed.
+    pub include_optional: bool,
+}
+
+impl TransitivePolicy {
+    /// Policy of the Forge and NeoForge installers: the jars needed at
+    /// runtime, deep enough for loader library trees without walking whole
+    /// ecosystems.
+    pub const LOADER_LIBRARIES: TransitivePolicy = TransitivePolicy {
+        max_depth: 8,
+        scopes: &["compile", "runtime"],
+        packagings: &["jar"],
+        include_optional: false,
+    };
+}
+
 /// Resolves Maven artifacts transitively, downloading JARs and parsing POMs.
 pub struct MavenResolver {
     /// Ordered list of repository base URLs to search.
@@ -79,7 +107,7 @@ impl MavenResolver {
             }
 
             // 2. Download and parse POM for transitive dependencies
-            let pom_artifact = artifact.with_packaging("pom");
+            let pom_artifact = artifact.pom();
             let pom_dest = libs_dir.join(pom_artifact.local_path());
 
             if !pom_dest.exists() {
@@ -144,6 +172,149 @@ impl MavenResolver {
         }) // end Box::pin
     }
 
+    /// Transitive dependencies of `declared` that `policy` follows and that
+    /// are not declared themselves, as `group:artifact:version[:classifier]`
+    /// coordinates.
+    ///
+    /// Declared libraries win: a dependency whose `group:artifact[:classifier]`
+    /// is already declared is skipped whatever its version. POMs are read from
+    /// `libs_dir` and downloaded from the repositories when missing; an
+    /// artifact without a usable POM simply contributes nothing.
+    pub async fn transitive_closure(
+        &mut self,
+        declared: &[String],
+        libs_dir: &Path,
+        downloader: &Downloader,
+        policy: &TransitivePolicy,
+    ) -> Vec<String> {
+        let declared: Vec<MavenArtifact> = declared
+            .iter()
+            .filter_map(|coord| MavenArtifact::parse(coord).ok())
+            .collect();
+        let mut known: HashSet<String> = declared.iter().map(dedup_key).collect();
+        let mut queue: VecDeque<(MavenArtifact, Exclusions, usize)> = declared
+            .into_iter()
+            .map(|artifact| (artifact, Vec::new(), 0))
+            .collect();
+        let mut found = Vec::new();
+
+        while let Some((artifact, exclusions, depth)) = queue.pop_front() {
+            if depth >= policy.max_depth {
+                continue;
+            }
+            let Some(pom) = self.load_pom(&artifact, libs_dir, downloader).await else {
+                continue;
+            };
+
+            for dep in pom.dependencies_in_scopes(policy.scopes, policy.include_optional) {
+                if is_excluded(&exclusions, &dep) {
+                    continue;
+                }
+                let packaging = dep.dep_type.as_deref().unwrap_or("jar");
+                if !policy.packagings.contains(&packaging) {
+                    continue;
+                }
+                let Some(version) = pom
+                    .resolve_version(&dep)
+                    .map(|version| interpolate_project_version(&version, &pom, &artifact))
+                    .filter(|version| !version.contains("${"))
+                else {
+                    debug!(
+                        "Cannot resolve version for {}:{} (skipping)",
+                        dep.group_id, dep.artifact_id
+                    );
+                    continue;
+                };
+
+                let child = MavenArtifact {
+                    group_id: dep.group_id.clone(),
+                    artifact_id: dep.artifact_id.clone(),
+                    version,
+                    classifier: dep.classifier.clone(),
+                    packaging: packaging.to_string(),
+                };
+                if !known.insert(dedup_key(&child)) {
+                    continue;
+                }
+
+                found.push(plain_coordinate(&child));
+                let mut child_exclusions = exclusions.clone();
+                if let Some(excluded) = &dep.exclusions {
+                    child_exclusions.extend(
+                        excluded
+                            .items
+                            .iter()
+                            .map(|e| (e.group_id.clone(), e.artifact_id.clone())),
+                    );
+                }
+                queue.push_back((child, child_exclusions, depth + 1));
+            }
+        }
+
+        found
+    }
+
+    /// Download the JARs of every transitive dependency missing from
+    /// `declared` and return the coordinates that are now available locally.
+    pub async fn resolve_missing_transitive(
+        &mut self,
+        declared: &[String],
+        libs_dir: &Path,
+        downloader: &Downloader,
+        policy: &TransitivePolicy,
+    ) -> Vec<String> {
+        let mut available = Vec::new();
+        for coord in self
+            .transitive_closure(declared, libs_dir, downloader, policy)
+            .await
+        {
+            let Ok(artifact) = MavenArtifact::parse(&coord) else {
+                continue;
+            };
+            let dest = libs_dir.join(artifact.local_path());
+            if dest.exists() {
+                available.push(coord);
+                continue;
+            }
+            match self.try_download(&artifact, &dest, downloader).await {
+                Ok(()) => {
+                    debug!("Downloaded transitive dependency {}", artifact);
+                    available.push(coord);
+                }
+                Err(e) => warn!("Transitive dependency {} unavailable: {}", artifact, e),
+            }
+        }
+        available
+    }
+
+    async fn load_pom(
+        &self,
+        artifact: &MavenArtifact,
+        libs_dir: &Path,
+        downloader: &Downloader,
+    ) -> Option<PomDocument> {
+        let pom_artifact = artifact.pom();
+        let pom_dest: PathBuf = libs_dir.join(pom_artifact.local_path());
+        if !pom_dest.exists() {
+            if let Err(e) = self
+                .try_download(&pom_artifact, &pom_dest, downloader)
+                .await
+            {
+                debug!("POM not available for {}: {}", artifact, e);
+                return None;
+            }
+        }
+
+        let content = tokio::fs::read_to_string(&pom_dest).await.ok()?;
+        match PomDocument::parse(&content) {
+            Ok(pom) => Some(pom),
+            Err(e) => {
+                warn!("Failed to parse POM for {}: {}", artifact, e);
+                None
+            }
+        }
+    }
+
     /// Try each repository until a successful download occurs.
     async fn try_download(
         &self,
@@ -169,3 +340,161 @@ impl MavenResolver {
         }))
     }
 }
+
+/// Identity used to deduplicate artifacts regardless of version.
+fn dedup_key(artifact: &MavenArtifact) -> String {
+    match &artifact.classifier {
+        Some(classifier) => format!(
+            "{}:{}:{}",
+            artifact.group_id, artifact.artifact_id, classifier
+        ),
+        None => format!("{}:{}", artifact.group_id, artifact.artifact_id),
+    }
+}
+
+/// Coordinate in the form stored in `Instance::libraries`.
+fn plain_coordinate(artifact: &MavenArtifact) -> String {
+    match &artifact.classifier {
+        Some(classifier) => format!(
+            "{}:{}:{}:{}",
+            artifact.group_id, artifact.artifact_id, artifact.version, classifier
+        ),
+        None => format!(
+            "{}:{}:{}",
+            artifact.group_id, artifact.artifact_id, artifact.version
+        ),
+    }
+}
+
+/// `(groupId, artifactId)` pairs a dependency path excludes; `*` matches any.
+type Exclusions = Vec<(String, String)>;
+
+fn is_excluded(exclusions: &[(String, String)], dep: &PomDependency) -> bool {
+    exclusions.iter().any(|(group, artifact)| {
+        (group == "*" || *group == dep.group_id)
+            && (artifact == "*" || *artifact == dep.artifact_id)
+    })
+}
+
+fn interpolate_project_version(
+    version: &str,
+    pom: &PomDocument,
+    artifact: &MavenArtifact,
+) -> String {
+    let project_version = pom.version.as_deref().unwrap_or(&artifact.version);
+    version
+        .replace("${project.version}", project_version)
+        .replace("${version}", project_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pom(libs_dir: &Path, coord: &str, dependencies: &str) {
+        let artifact = MavenArtifact::parse(coord).unwrap().pom();
+        let path = libs_dir.join(artifact.local_path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            format!(
+                "<project><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version>{}</project>",
+                artifact.group_id, artifact.artifact_id, artifact.version, dependencies
+            ),
+        )
+        .unwrap();
+    }
+
+    fn dep(group: &str, artifact: &str, extra: &str) -> String {
+        format!("<dependency><groupId>{group}</groupId><artifactId>{artifact}</artifactId>{extra}</dependency>")
+    }
+
+    #[tokio::test]
+    async fn closure_follows_runtime_scopes_and_skips_declared() {
+        let libs = std::env::temp_dir().join(format!("maven-closure-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&libs);
+
+        write_pom(
+            &libs,
+            "net.minecraftforge:forge:1.20.1-47.2.0:universal",
+            &format!(
+                "<dependencyManagement><dependencies>{}</dependencies></dependencyManagement><dependencies>{}{}{}{}{}{}</dependencies>",
+                dep("org.example", "managed", "<version>2.1</version>"),
+                dep(
+                    "org.example",
+                    "core",
+                    "<version>1.0</version><exclusions><exclusion><groupId>org.example</groupId><artifactId>banned</artifactId></exclusion></exclusions>"
+                ),
+                dep("org.example", "managed", ""),
+                dep("junit", "junit", "<version>4.13</version><scope>test</scope>"),
+                dep("javax.servlet", "servlet-api", "<version>3.0</version><scope>provided</scope>"),
+                dep("org.example", "extra", "<version>1.0</version><optional>true</optional>"),
+                dep("org.ow2.asm", "asm", "<version>9.5</version>"),
+            ),
+        );
+        write_pom(
+            &libs,
+            "org.example:core:1.0",
+            &format!(
+                "<dependencies>{}{}</dependencies>",
+                dep(
+                    "org.example",
+                    "runtime-only",
+                    "<version>${project.version}</version><scope>runtime</scope>"
+                ),
+                dep("org.example", "banned", "<version>1.0</version>"),
+            ),
+        );
+
+        let downloader = Downloader::new(None);
+        let declared = [
+            "net.minecraftforge:forge:1.20.1-47.2.0:universal".to_string(),
+            "org.ow2.asm:asm:9.6".to_string(),
+        ];
+        let closure = |policy: TransitivePolicy| {
+            let (libs, downloader, declared) = (&libs, &downloader, &declared);
+            async move {
+                let mut resolver = MavenResolver::new(Vec::new());
+                let mut closure = resolver
+                    .transitive_closure(declared, libs, downloader, &policy)
+                    .await;
+                closure.sort();
+                closure
+            }
+        };
+
+        assert_eq!(
+            closure(TransitivePolicy::LOADER_LIBRARIES).await,
+            [
+                "org.example:core:1.0",
+                "org.example:managed:2.1",
+                "org.example:runtime-only:1.0",
+            ]
+        );
+        // One level stops before the dependencies of `core`.
+        assert_eq!(
+            closure(TransitivePolicy {
+                max_depth: 1,
+                ..TransitivePolicy::LOADER_LIBRARIES
+            })
+            .await,
+            ["org.example:core:1.0", "org.example:managed:2.1"]
+        );
+        // Optional dependencies are followed only when asked for.
+        assert_eq!(
+            closure(TransitivePolicy {
+                max_depth: 1,
+                include_optional: true,
+                ..TransitivePolicy::LOADER_LIBRARIES
+            })
+            .await,
+            [
+                "org.example:core:1.0",
+                "org.example:extra:1.0",
+                "org.example:managed:2.1",
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&libs);
+    }
+}
@@ -0,0 +1,564 @@
+// ─── Metadata Cache ───
+// Keeps the last successful response of every metadata endpoint (Mojang
+// manifest, loader version lists) on disk so the launcher keeps working
+// offline with slightly stale data instead of failing outright. Entries also
+// keep the source URL and HTTP validators so version JSONs and loader
+// profiles are revalidated with a conditional GET instead of refetched.
+// Every lookup takes the parser of the document: a body is only cached once
+// it parses, so an error page or a truncated response never replaces a good
+// copy, and a cached copy that no longer parses is never served.
+
+use std::future::Future;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::core::error::{LauncherError, LauncherResult};
+
+/// Bump when the layout of cached entries changes; revalidated lookups then
+/// ignore older entries and fetch again.
+const CACHE_SCHEMA: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedEntry {
+    fetched_at: DateTime<Utc>,
+    body: String,
+    #[serde(default)]
+    schema: u32,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    validators: Validators,
+}
+
+/// A parsed metadata document with its body and, when it is a stale copy
+/// used because the network failed, when it was originally fetched.
+#[derive(Debug, Clone)]
+pub struct MetadataResponse<T> {
+    pub value: T,
+    pub body: String,
+    pub cached_at: Option<DateTime<Utc>>,
+}
+
+/// Parser for JSON documents, to pass as the `parse` of a lookup.
+pub fn parse_json<T: serde::de::DeserializeOwned>(body: &str) -> LauncherResult<T> {
+    Ok(serde_json::from_str(body)?)
+}
+
+/// HTTP validators of a cached response, sent back as `If-None-Match` /
+/// `If-Modified-Since`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// Result of a conditional GET.
+#[derive(Debug, Clone)]
+pub enum Revalidated {
+    NotModified,
+    Modified {
+        body: String,
+        validators: Validators,
+    },
+}
+
+pub struct MetadataCache {
+    dir: PathBuf,
+}
+
+impl MetadataCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        let file_name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{file_name}.json"))
+    }
+
+    pub fn store(&self, key: &str, body: &str) -> LauncherResult<()> {
+        self.store_entry(
+            key,
+            &CachedEntry {
+                fetched_at: Utc::now(),
+                body: body.to_string(),
+                schema: CACHE_SCHEMA,
+                url: None,
+                validators: Validators::default(),
+            },
+        )
+    }
+
+    fn store_entry(&self, key: &str, entry: &CachedEntry) -> LauncherResult<()> {
+        std::fs::create_dir_all(&self.dir).map_err(|source| LauncherError::Io {
+            path: self.dir.clone(),
+            source,
+        })?;
+        let path = self.entry_path(key);
+        std::fs::write(&path, serde_json::to_vec(entry)?)
+            .map_err(|source| LauncherError::Io { path, source })
+    }
+
+    fn load(&self, key: &str) -> Option<CachedEntry> {
+        let raw = std::fs::read(self.entry_path(key)).ok()?;
+        serde_json::from_slice(&raw).ok()
+    }
+
+    /// Run `fetch` and `parse` its body; cache the body when both succeed,
+    /// or fall back to the cached copy when either fails. The error is
+    /// returned only when there is no usable cached copy.
+    pub async fn fetch_with_fallback<T, F, Fut, P>(
+        &self,
+        key: &str,
+        fetch: F,
+        parse: P,
+    ) -> LauncherResult<MetadataResponse<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = LauncherResult<String>>,
+        P: Fn(&str) -> LauncherResult<T>,
+    {
+        let fetched = match fetch().await {
+            Ok(body) => parse(&body).map(|value| (value, body)),
+            Err(err) => Err(err),
+        };
+        match fetched {
+            Ok((value, body)) => {
+                if let Err(err) = self.store(key, &body) {
+                    warn!("Cannot cache metadata '{}': {}", key, err);
+                }
+                Ok(MetadataResponse {
+                    value,
+                    body,
+                    cached_at: None,
+                })
+            }
+            Err(err) => {
+                let cached = self.load(key).and_then(|entry| parsed(key, entry, &parse));
+                fall_back(key, cached, err)
+            }
+        }
+    }
+
+    /// Resolve `key`, whose body comes from `url`, through the cache.
+    ///
+    /// A copy cached from the same `url` is returned without calling `fetch`
+    /// when `immutable` (content-addressed URLs such as piston-meta version
+    /// JSONs). Otherwise `fetch` gets the cached validators for a conditional
+    /// GET and `NotModified` reuses the cached body. A modified body is
+    /// cached only if it parses; when `fetch` or `parse` fails the cached
+    /// copy is used if there is one.
+    pub async fn fetch_revalidated<T, F, Fut, P>(
+        &self,
+        key: &str,
+        url: &str,
+        immutable: bool,
+        fetch: F,
+        parse: P,
+    ) -> LauncherResult<MetadataResponse<T>>
+    where
+        F: FnOnce(Option<Validators>) -> Fut,
+        Fut: Future<Output = LauncherResult<Revalidated>>,
+        P: Fn(&str) -> LauncherResult<T>,
+    {
+        let cached = self
+            .load(key)
+            .filter(|entry| entry.schema == CACHE_SCHEMA && entry.url.as_deref() == Some(url))
+            .and_then(|entry| parsed(key, entry, &parse));
+
+        if immutable {
+            if let Some((entry, value)) = cached {
+                debug!("Metadata '{}' served from cache", key);
+                return Ok(MetadataResponse {
+                    value,
+                    body: entry.body,
+                    cached_at: None,
+                });
+            }
+        }
+
+        let validators = cached.as_ref().map(|(entry, _)| entry.validators.clone());
+        match fetch(validators).await {
+            Ok(Revalidated::Modified { body, validators }) => match parse(&body) {
+                Ok(value) => {
+                    let entry = CachedEntry {
+                        fetched_at: Utc::now(),
+                        body,
+                        schema: CACHE_SCHEMA,
+                        url: Some(url.to_string()),
+                        validators,
+                    };
+                    if let Err(err) = self.store_entry(key, &entry) {
+                        warn!("Cannot cache metadata '{}': {}", key, err);
+                    }
+                    Ok(MetadataResponse {
+                        value,
+                        body: entry.body,
+                        cached_at: None,
+                    })
+                }
+                Err(err) => fall_back(key, cached, err),
+            },
+            Ok(Revalidated::NotModified) => match cached {
+                Some((entry, value)) => {
+                    debug!("Metadata '{}' not modified since {}", key, entry.fetched_at);
+                    Ok(MetadataResponse {
+                        value,
+                        body: entry.body,
+                        cached_at: None,
+                    })
+                }
+                None => Err(LauncherError::Other(format!(
+                    "{url} answered 304 without a cached copy"
+                ))),
+            },
+            Err(err) => fall_back(key, cached, err),
+        }
+    }
+}
+
+/// `entry` with its parsed body, or `None` (logged) when it no longer parses.
+fn parsed<T>(
+    key: &str,
+    entry: CachedEntry,
+    parse: impl Fn(&str) -> LauncherResult<T>,
+) -> Option<(CachedEntry, T)> {
+    match parse(&entry.body) {
+        Ok(value) => Some((entry, value)),
+        Err(err) => {
+            warn!("Ignoring unreadable cached metadata '{}': {}", key, err);
+            None
+        }
+    }
+}
+
+/// The cached copy after a failed fetch, or `err` without one.
+fn fall_back<T>(
+    key: &str,
+    cached: Option<(CachedEntry, T)>,
+    err: LauncherError,
+) -> LauncherResult<MetadataResponse<T>> {
+    match cached {
+        Some((entry, value)) => {
+            warn!(
+                "Fetching '{}' failed ({}); using cached copy from {}",
+                key, err, entry.fetched_at
+            );
+            Ok(MetadataResponse {
+                value,
+                body: entry.body,
+                cached_at: Some(entry.fetched_at),
+            })
+        }
+        None => Err(err),
+    }
+}
+
+/// Conditional GET of `url`: sends the validators of the cached copy and
+/// reports `NotModified` on a 304.
+pub async fn fetch_conditional(
+    client: &reqwest::Client,
+    url: &str,
+    validators: Option<Validators>,
+) -> LauncherResult<Revalidated> {
+    let validators = validators.unwrap_or_default();
+    let response = crate::core::net::send_with_retry(
+        || {
+            let mut request = client.get(url);
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+            request
+        },
+        crate::core::net::RetryPolicy::default(),
+    )
+    .await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(Revalidated::NotModified);
+    }
+    if !response.status().is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let validators = Validators {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    };
+    Ok(Revalidated::Modified {
+        body: response.text().await?,
+        validators,
+    })
+}
+
+/// GET `url` as text, treating non-2xx statuses as errors so they fall back too.
+pub async fn fetch_text(client: &reqwest::Client, url: &str) -> LauncherResult<String> {
+    let response = crate::core::net::get_with_retry(client, url).await?;
+    if !response.status().is_success() {
+        return Err(LauncherError::LoaderApi(format!(
+            "{url} returned {}",
+            response.status()
+        )));
+    }
+    Ok(response.text().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(body: &str) -> LauncherResult<String> {
+        Ok(body.to_string())
+    }
+
+    fn modified(body: &str) -> LauncherResult<Revalidated> {
+        Ok(Revalidated::Modified {
+            body: body.to_string(),
+            validators: Validators::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_falls_back_to_cached_copy() {
+        let temp = std::env::temp_dir().join(format!("metadata-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let cache = MetadataCache::new(temp.clone());
+
+        // Nothing cached yet: the network error is surfaced.
+        let offline = || async { Err(LauncherError::LoaderApi("offline".into())) };
+        assert!(cache
+            .fetch_with_fallback("version_manifest_v2", offline, text)
+            .await
+            .is_err());
+
+        let fresh = cache
+            .fetch_with_fallback(
+                "version_manifest_v2",
+                || async { Ok(r#"{"versions":[]}"#.to_string()) },
+                text,
+            )
+            .await
+            .unwrap();
+        assert!(fresh.cached_at.is_none());
+
+        let fallback = cache
+            .fetch_with_fallback("version_manifest_v2", offline, text)
+            .await
+            .unwrap();
+        assert_eq!(fallback.body, r#"{"versions":[]}"#);
+        assert!(fallback.cached_at.is_some());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn bodies_that_do_not_parse_are_not_cached() {
+        let temp = std::env::temp_dir().join(format!("metadata-parse-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let cache = MetadataCache::new(temp.clone());
+        let json = parse_json::<serde_json::Value>;
+        let portal = || async { Ok("<html>captive portal</html>".to_string()) };
+
+        // Nothing to fall back to: the parse error is surfaced.
+        assert!(cache
+            .fetch_with_fallback("manifest", portal, json)
+            .await
+            .is_err());
+        assert!(cache.load("manifest").is_none());
+
+        cache
+            .fetch_with_fallback("manifest", || async { Ok(r#"{"v":1}"#.into()) }, json)
+            .await
+            .unwrap();
+        let fallback = cache
+            .fetch_with_fallback("manifest", portal, json)
+            .await
+            .unwrap();
+        assert_eq!(fallback.value, serde_json::json!({ "v": 1 }));
+        assert!(fallback.cached_at.is_some());
+        assert_eq!(cache.load("manifest").unwrap().body, r#"{"v":1}"#);
+
+        let url = "https://meta.fabricmc.net/v2/profile/json";
+        cache
+            .fetch_revalidated("profile", url, false, |_| async { modified("{}") }, json)
+            .await
+            .unwrap();
+        let kept = cache
+            .fetch_revalidated(
+                "profile",
+                url,
+                false,
+                |_| async { modified("truncated {") },
+                json,
+            )
+            .await
+            .unwrap();
+        assert_eq!(kept.body, "{}");
+        assert!(kept.cached_at.is_some());
+        assert_eq!(cache.load("profile").unwrap().body, "{}");
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn second_resolution_is_served_from_cache() {
+        let temp = std::env::temp_dir().join(format!("metadata-revalidate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let cache = MetadataCache::new(temp.clone());
+        let url = "https://piston-meta.mojang.com/v1/packages/abc/1.20.1.json";
+
+        let first = cache
+            .fetch_revalidated(
+                "version-json/1.20.1",
+                url,
+                true,
+                |validators| async move {
+                    assert_eq!(validators, None);
+                    modified(r#"{"id":"1.20.1"}"#)
+                },
+                text,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.body, r#"{"id":"1.20.1"}"#);
+
+        let second = cache
+            .fetch_revalidated(
+                "version-json/1.20.1",
+                url,
+                true,
+                |_| async { panic!("immutable entry should not be fetched again") },
+                text,
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.body, first.body);
+        assert!(second.cached_at.is_none());
+
+        // A new URL for the same key (republished version) is fetched.
+        let moved = cache
+            .fetch_revalidated(
+                "version-json/1.20.1",
+                "https://piston-meta.mojang.com/v1/packages/def/1.20.1.json",
+                true,
+                |_| async { modified(r#"{"id":"1.20.1","v":2}"#) },
+                text,
+            )
+            .await
+            .unwrap();
+        assert_eq!(moved.body, r#"{"id":"1.20.1","v":2}"#);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn mutable_entries_are_revalidated_with_their_validators() {
+        let temp = std::env::temp_dir().join(format!("metadata-etag-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let cache = MetadataCache::new(temp.clone());
+        let url = "https://meta.fabricmc.net/v2/versions/loader/1.20.1/0.15.0/profile/json";
+        let etag = Validators {
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+        };
+
+        cache
+            .fetch_revalidated(
+                "fabric-profile/1.20.1/0.15.0",
+                url,
+                false,
+                |_| {
+                    let validators = etag.clone();
+                    async move {
+                        Ok(Revalidated::Modified {
+                            body: "profile".to_string(),
+                            validators,
+                        })
+                    }
+                },
+                text,
+            )
+            .await
+            .unwrap();
+
+        let revalidated = cache
+            .fetch_revalidated(
+                "fabric-profile/1.20.1/0.15.0",
+                url,
+                false,
+                |sent| {
+                    assert_eq!(sent.as_ref(), Some(&etag));
+                    async { Ok(Revalidated::NotModified) }
+                },
+                text,
+            )
+            .await
+            .unwrap();
+        assert_eq!(revalidated.body, "profile");
+        assert!(revalidated.cached_at.is_none());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[tokio::test]
+    async fn entries_from_another_schema_are_ignored() {
+        let temp = std::env::temp_dir().join(format!("metadata-schema-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        let cache = MetadataCache::new(temp.clone());
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(
+            cache.entry_path("version-json/1.8.9"),
+            r#"{"fetched_at":"2024-01-01T00:00:00Z","body":"old","url":"https://x/1.8.9.json"}"#,
+        )
+        .unwrap();
+
+        let response = cache
+            .fetch_revalidated(
+                "version-json/1.8.9",
+                "https://x/1.8.9.json",
+                true,
+                |_| async { modified("new") },
+                text,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.body, "new");
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn keys_map_to_safe_file_names() {
+        let cache = MetadataCache::new(PathBuf::from("/cache"));
+        assert_eq!(
+            cache.entry_path("fabric-loader/1.20.1"),
+            PathBuf::from("/cache/fabric-loader_1.20.1.json")
+        );
+    }
+}
@@ -0,0 +1,177 @@
+// ─── Download Mirrors ───
+// Optional base-URL overrides (e.g. BMCLAPI) for the Mojang and Adoptium
+// hosts. Only the scheme/host prefix is swapped and paths stay intact; the
+// mirrored URL is tried first and the official one is used if it fails.
+// The active mirrors travel with the `Downloader` and `RuntimeOptions`.
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::core::error::LauncherResult;
+
+/// Base URLs replacing the official download hosts. `None` keeps the
+/// official host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadMirrors {
+    /// Version manifest, version JSONs and client jars (`piston-meta`,
+    /// `launchermeta`, `piston-data`).
+    #[serde(default)]
+    pub version_manifest: Option<String>,
+    #[serde(default)]
+    pub libraries: Option<String>,
+    #[serde(default)]
+    pub assets: Option<String>,
+    #[serde(default)]
+    pub adoptium: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum MirrorTarget {
+    VersionManifest,
+    Libraries,
+    Assets,
+    Adoptium,
+}
+
+const OFFICIAL_BASES: [(MirrorTarget, &str); 6] = [
+    (
+        MirrorTarget::VersionManifest,
+        "https://piston-meta.mojang.com",
+    ),
+    (
+        MirrorTarget::VersionManifest,
+        "https://launchermeta.mojang.com",
+    ),
+    (
+        MirrorTarget::VersionManifest,
+        "https://piston-data.mojang.com",
+    ),
+    (MirrorTarget::Libraries, "https://libraries.minecraft.net"),
+    (
+        MirrorTarget::Assets,
+        "https://resources.download.minecraft.net",
+    ),
+    (MirrorTarget::Adoptium, "https://api.adoptium.net"),
+];
+
+impl DownloadMirrors {
+    fn base_for(&self, target: MirrorTarget) -> Option<&str> {
+        let base = match target {
+            MirrorTarget::VersionManifest => &self.version_manifest,
+            MirrorTarget::Libraries => &self.libraries,
+            MirrorTarget::Assets => &self.assets,
+            MirrorTarget::Adoptium => &self.adoptium,
+        };
+        base.as_deref()
+            .map(|base| base.trim().trim_end_matches('/'))
+            .filter(|base| !base.is_empty())
+    }
+
+    /// `url` with its official host replaced by the configured mirror, or
+    /// `None` when the host is unknown or not mirrored.
+    pub fn rewrite(&self, url: &str) -> Option<String> {
+        OFFICIAL_BASES.iter().find_map(|(target, official)| {
+            let rest = url.strip_prefix(official)?;
+            if !(rest.is_empty() || rest.starts_with('/') || rest.starts_with('?')) {
+                return None;
+            }
+            Some(format!("{}{rest}", self.base_for(*target)?))
+        })
+    }
+}
+
+/// Run `fetch` against the URL mirrored by `mirrors`, retrying with the
+/// official `url` when there is no mirror or the mirror fails.
+pub async fn with_mirror_fallback<T, F, Fut>(
+    mirrors: &DownloadMirrors,
+    url: &str,
+    fetch: F,
+) -> LauncherResult<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = LauncherResult<T>>,
+{
+    if let Some(mirrored) = mirrors.rewrite(url) {
+        match fetch(mirrored.clone()).await {
+            Ok(value) => return Ok(value),
+            Err(err) => warn!("Mirror {} failed ({}); using {}", mirrored, err, url),
+        }
+    }
+    fetch(url.to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bmclapi() -> DownloadMirrors {
+        DownloadMirrors {
+            version_manifest: Some("https://bmclapi2.bangbang93.com".into()),
+            libraries: Some("https://bmclapi2.bangbang93.com/maven/".into()),
+            assets: Some("https://bmclapi2.bangbang93.com/assets".into()),
+            adoptium: Some("https://mirror.example.com/adoptium".into()),
+        }
+    }
+
+    #[test]
+    fn rewrites_each_official_base() {
+        let mirrors = bmclapi();
+        let cases = [
+            (
+                "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
+                "https://bmclapi2.bangbang93.com/mc/game/version_manifest_v2.json",
+            ),
+            (
+                "https://launchermeta.mojang.com/v1/packages/abc/1.20.1.json",
+                "https://bmclapi2.bangbang93.com/v1/packages/abc/1.20.1.json",
+            ),
+            (
+                "https://piston-data.mojang.com/v1/objects/abc/client.jar",
+                "https://bmclapi2.bangbang93.com/v1/objects/abc/client.jar",
+            ),
+            (
+                "https://libraries.minecraft.net/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+                "https://bmclapi2.bangbang93.com/maven/com/mojang/brigadier/1.0.18/brigadier-1.0.18.jar",
+            ),
+            (
+                "https://resources.download.minecraft.net/ab/abcdef",
+                "https://bmclapi2.bangbang93.com/assets/ab/abcdef",
+            ),
+            (
+                "https://api.adoptium.net/v3/assets/latest/17/hotspot?architecture=x64",
+                "https://mirror.example.com/adoptium/v3/assets/latest/17/hotspot?architecture=x64",
+            ),
+        ];
+        for (official, mirrored) in cases {
+            assert_eq!(mirrors.rewrite(official).as_deref(), Some(mirrored));
+        }
+    }
+
+    #[test]
+    fn leaves_unknown_or_unmirrored_urls_alone() {
+        let mirrors = bmclapi();
+        assert_eq!(
+            mirrors.rewrite("https://maven.fabricmc.net/net/fabricmc/x.jar"),
+            None
+        );
+        assert_eq!(
+            mirrors.rewrite("https://libraries.minecraft.net.evil.com/x.jar"),
+            None
+        );
+        assert_eq!(
+            DownloadMirrors::default().rewrite("https://libraries.minecraft.net/x.jar"),
+            None
+        );
+
+        let blank = DownloadMirrors {
+            assets: Some("  ".into()),
+            ..DownloadMirrors::default()
+        };
+        assert_eq!(
+            blank.rewrite("https://resources.download.minecraft.net/ab/abcdef"),
+            None
+        );
+    }
+}
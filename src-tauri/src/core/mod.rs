@@ -6,22 +6,59 @@
 //     instance/   — Instance model + CRUD manager
 //     version/    — Mojang manifest + version JSON + OS rules
 //     maven/      — Artifact parser, POM resolver, transitive deps
+//     metadata_cache — Last-known-good manifest / loader metadata
+//     mirrors     — Optional mirror hosts for Mojang / Adoptium downloads
+//     mods/       — Jar metadata + enable / disable toggling
+//     net         — Shared retry / backoff for API requests
 //     downloader/ — Concurrent downloads with SHA-1 validation
+//     install     — Vanilla + loader + assets install pipeline
+//     install_progress — Weighted create / launch progress from real download counts
+//     deep_link   — `interface://` URIs that launch an instance
+//     disk_space  — Free-space checks before large downloads
+//     atomic_file — Temp-then-rename writes for persisted JSON
+//     long_path   — Extended-length (`\\?\`) paths for deep Windows trees
 //     assets/     — Asset index + object downloads
 //     loaders/    — Vanilla, Fabric, Quilt, Forge, NeoForge
+//     modpack/    — CurseForge / Modrinth pack import
 //     launch/     — Classpath builder + process spawner
 //     java/       — Multi-platform Java detection
+//     servers     — Multiplayer server list (servers.dat)
+//     shortcuts   — Desktop / menu shortcuts for the launcher and instances
 //     state/      — Global application state
+//     events      — Event emitter trait (Tauri or headless)
+//     i18n/       — Message keys + Spanish / English fallback texts
+//     launcher/   — Headless facade over the state and pipelines
+//     worlds      — World backup / restore archives
+//     test_support — Fixtures shared by unit tests
 
 pub mod assets;
+pub mod atomic_file;
 pub mod auth;
+pub mod deep_link;
+pub mod disk_space;
 pub mod downloader;
 pub mod error;
+pub mod events;
 pub mod http;
+pub mod i18n;
+pub mod install;
+pub mod install_progress;
 pub mod instance;
 pub mod java;
 pub mod launch;
+pub mod launcher;
 pub mod loaders;
+pub mod long_path;
 pub mod maven;
+pub mod metadata_cache;
+pub mod mirrors;
+pub mod modpack;
+pub mod mods;
+pub mod net;
+pub mod servers;
+pub mod shortcuts;
 pub mod state;
+#[cfg(test)]
+pub mod test_support;
 pub mod version;
+pub mod worlds;
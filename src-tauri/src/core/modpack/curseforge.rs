@@ -0,0 +1,393 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures_util::stream::{self, StreamExt};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::{
+    extract_overrides, read_archive_json, FileProgress, ModpackPlan, PackContents, SkippedFile,
+};
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{Instance, LoaderType};
+
+/// Public download endpoint; redirects to the CDN file when the author allows
+/// third-party distribution.
+const CURSEFORGE_DOWNLOAD_BASE: &str = "https://www.curseforge.com/api/v1/mods";
+const MANIFEST_FILE: &str = "manifest.json";
+const DEFAULT_OVERRIDES_DIR: &str = "overrides";
+const DOWNLOAD_CONCURRENCY: usize = 6;
+
+// ── manifest.json ───────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    #[serde(default)]
+    pub manifest_type: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub files: Vec<CurseForgeFile>,
+    #[serde(default)]
+    pub overrides: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+    #[serde(default)]
+    pub recommended_ram: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// Read `manifest.json` and resolve the instance the pack asks for.
+pub(super) async fn read_pack(path: &Path) -> LauncherResult<ModpackPlan> {
+    let archive_path = path.to_path_buf();
+    let manifest: CurseForgeManifest =
+        tokio::task::spawn_blocking(move || read_archive_json(&archive_path, MANIFEST_FILE))
+            .await
+            .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+    if manifest
+        .manifest_type
+        .as_deref()
+        .is_some_and(|kind| kind != "minecraftModpack")
+    {
+        return Err(LauncherError::message(
+            ErrorText::UnsupportedCurseForgeManifest,
+            &[&manifest.manifest_type.as_deref().unwrap_or_default()],
+        ));
+    }
+
+    let (loader, loader_version) = resolve_loader(&manifest.minecraft.mod_loaders)?;
+    info!(
+        "Importing CurseForge pack '{}' {:?} (Minecraft {}, {} {:?})",
+        manifest.name, manifest.version, manifest.minecraft.version, loader, loader_version
+    );
+
+    Ok(ModpackPlan {
+        name: manifest.name.clone(),
+        minecraft_version: manifest.minecraft.version.clone(),
+        loader,
+        loader_version,
+        memory_max_mb: manifest.minecraft.recommended_ram,
+        contents: PackContents::CurseForge(manifest),
+    })
+}
+
+/// Download every required `files[]` entry into `mods/` and unpack
+/// `overrides/` into the game directory. Returns the files whose authors
+/// disabled third-party downloads.
+pub(super) async fn populate_instance(
+    archive_path: &Path,
+    manifest: &CurseForgeManifest,
+    instance: &Instance,
+    client: &Client,
+    downloader: &Downloader,
+    on_file: FileProgress<'_>,
+) -> LauncherResult<Vec<SkippedFile>> {
+    let mods_dir = instance.mods_dir();
+
+    let mut targets = Vec::new();
+    for file in &manifest.files {
+        if file.required {
+            targets.push((file.project_id, file.file_id));
+        } else {
+            info!(
+                "Skipping optional CurseForge file {}/{}",
+                file.project_id, file.file_id
+            );
+        }
+    }
+
+    let total = targets.len();
+    let done = AtomicUsize::new(0);
+    let results: Vec<LauncherResult<Option<SkippedFile>>> = stream::iter(targets)
+        .map(|(project_id, file_id)| {
+            let mods_dir = &mods_dir;
+            let done = &done;
+            async move {
+                let downloaded =
+                    download_project_file(client, downloader, project_id, file_id, mods_dir)
+                        .await?;
+                on_file(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+                match downloaded {
+                    Some(dest) => {
+                        info!("Downloaded {:?}", dest);
+                        Ok(None)
+                    }
+                    None => {
+                        warn!(
+                            "Project {} file {} does not allow third-party downloads; skipping",
+                            project_id, file_id
+                        );
+                        Ok(Some(SkippedFile {
+                            project_id,
+                            file_id,
+                        }))
+                    }
+                }
+            }
+        })
+        .buffer_unordered(DOWNLOAD_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut skipped = Vec::new();
+    for result in results {
+        skipped.extend(result?);
+    }
+
+    let overrides = manifest
+        .overrides
+        .clone()
+        .unwrap_or_else(|| DEFAULT_OVERRIDES_DIR.to_string());
+    let archive_path = archive_path.to_path_buf();
+    let instance = instance.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        extract_overrides(&archive_path, &overrides, &instance)
+    })
+    .await
+    .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+    info!("Unpacked {} override files", written);
+
+    Ok(skipped)
+}
+
+/// Download a single project file into `mods_dir`.
+///
+/// The public endpoint only redirects to the CDN, whose URL carries the file
+/// name, so it is resolved first and the file itself goes through the shared
+/// downloader. Returns `Ok(None)` when the project has disabled third-party
+/// distribution, which CurseForge reports as 403/404.
+async fn download_project_file(
+    client: &Client,
+    downloader: &Downloader,
+    project_id: u64,
+    file_id: u64,
+    mods_dir: &Path,
+) -> LauncherResult<Option<PathBuf>> {
+    let url = format!(
+        "{}/{}/files/{}/download",
+        CURSEFORGE_DOWNLOAD_BASE, project_id, file_id
+    );
+    // Only the headers are read; dropping the response leaves the body alone.
+    let response = client.get(&url).send().await?;
+
+    let status = response.status();
+    if matches!(status, StatusCode::FORBIDDEN | StatusCode::NOT_FOUND) {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url,
+            status: status.as_u16(),
+        });
+    }
+
+    let cdn_url = response.url().clone();
+    drop(response);
+    let file_name = cdn_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(decode_file_name)
+        .filter(|name| !name.is_empty() && !name.contains(['/', '\\']))
+        .unwrap_or_else(|| format!("{}-{}.jar", project_id, file_id));
+
+    let dest = mods_dir.join(file_name);
+    downloader
+        .download_file(cdn_url.as_str(), &dest, None)
+        .await?;
+
+    Ok(Some(dest))
+}
+
+/// Map `minecraft.modLoaders` (e.g. `forge-47.2.0`) to our loader fields.
+///
+/// The `primary` entry wins; packs without loaders are plain vanilla.
+fn resolve_loader(
+    mod_loaders: &[CurseForgeModLoader],
+) -> LauncherResult<(LoaderType, Option<String>)> {
+    let Some(entry) = mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| mod_loaders.first())
+    else {
+        return Ok((LoaderType::Vanilla, None));
+    };
+
+    let (kind, version) = entry
+        .id
+        .split_once('-')
+        .ok_or_else(|| LauncherError::message(ErrorText::InvalidCurseForgeLoader, &[&entry.id]))?;
+
+    let loader = match kind.to_ascii_lowercase().as_str() {
+        "forge" => LoaderType::Forge,
+        "neoforge" => LoaderType::NeoForge,
+        "fabric" => LoaderType::Fabric,
+        "quilt" => LoaderType::Quilt,
+        other => {
+            return Err(LauncherError::message(
+                ErrorText::UnsupportedCurseForgeLoader,
+                &[&other],
+            ))
+        }
+    };
+
+    Ok((loader, Some(version.to_string())))
+}
+
+/// Minimal percent-decoding for CDN file names (`Some%20Mod.jar`).
+fn decode_file_name(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                out.push(value);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn loader(id: &str, primary: bool) -> CurseForgeModLoader {
+        CurseForgeModLoader {
+            id: id.into(),
+            primary,
+        }
+    }
+
+    #[test]
+    fn resolve_loader_prefers_primary_entry() {
+        let (kind, version) =
+            resolve_loader(&[loader("fabric-0.15.7", false), loader("forge-47.2.0", true)])
+                .unwrap();
+        assert_eq!(kind, LoaderType::Forge);
+        assert_eq!(version.as_deref(), Some("47.2.0"));
+
+        let (kind, version) = resolve_loader(&[loader("neoforge-20.4.80", false)]).unwrap();
+        assert_eq!(kind, LoaderType::NeoForge);
+        assert_eq!(version.as_deref(), Some("20.4.80"));
+
+        let (kind, version) = resolve_loader(&[]).unwrap();
+        assert_eq!(kind, LoaderType::Vanilla);
+        assert!(version.is_none());
+
+        assert!(resolve_loader(&[loader("liteloader-1.12", true)]).is_err());
+    }
+
+    #[test]
+    fn manifest_defaults_files_to_required() {
+        let manifest: CurseForgeManifest = serde_json::from_str(
+            r#"{
+                "minecraft": { "version": "1.20.1", "modLoaders": [{ "id": "forge-47.2.0", "primary": true }] },
+                "manifestType": "minecraftModpack",
+                "name": "Pack",
+                "files": [
+                    { "projectID": 1, "fileID": 2 },
+                    { "projectID": 3, "fileID": 4, "required": false }
+                ],
+                "overrides": "overrides"
+            }"#,
+        )
+        .unwrap();
+
+        assert!(manifest.files[0].required);
+        assert!(!manifest.files[1].required);
+    }
+
+    #[test]
+    fn decode_file_name_handles_percent_escapes() {
+        assert_eq!(decode_file_name("Some%20Mod-1.0.jar"), "Some Mod-1.0.jar");
+        assert_eq!(decode_file_name("plain.jar"), "plain.jar");
+        assert_eq!(decode_file_name("broken%2"), "broken%2");
+    }
+
+    #[test]
+    fn extract_overrides_only_unpacks_prefixed_entries() {
+        let temp =
+            std::env::temp_dir().join(format!("modpack-test-overrides-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(&temp).unwrap();
+
+        let archive_path = temp.join("pack.zip");
+        {
+            let file = std::fs::File::create(&archive_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default();
+            writer.start_file("manifest.json", options).unwrap();
+            writer.write_all(b"{}").unwrap();
+            writer
+                .start_file("overrides/config/example.toml", options)
+                .unwrap();
+            writer.write_all(b"key = true").unwrap();
+            writer
+                .start_file("overrides/mods/extra.jar", options)
+                .unwrap();
+            writer.write_all(b"jar").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut instance = Instance::new(
+            "Pack".into(),
+            "1.20.1".into(),
+            LoaderType::Forge,
+            None,
+            2048,
+            &temp,
+        );
+        instance.path = temp.join("instance");
+        let written = extract_overrides(&archive_path, "overrides", &instance).unwrap();
+
+        assert_eq!(written, 2);
+        let game_dir = instance.game_dir();
+        assert!(game_dir.join("config").join("example.toml").exists());
+        assert!(!game_dir.join("manifest.json").exists());
+        // Override mods land where the launcher lists and toggles mods.
+        assert!(instance.mods_dir().join("extra.jar").exists());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
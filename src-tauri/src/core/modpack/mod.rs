@@ -0,0 +1,202 @@
+pub mod curseforge;
+pub mod modrinth;
+
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{Instance, LoaderType};
+
+use curseforge::CurseForgeManifest;
+use modrinth::ModrinthIndex;
+
+/// Archive formats accepted by [`read_modpack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModpackFormat {
+    /// A CurseForge `.zip` with a `manifest.json`.
+    CurseForge,
+    /// A Modrinth `.mrpack` with a `modrinth.index.json`.
+    Modrinth,
+}
+
+impl ModpackFormat {
+    /// Platform name shown in the creation log.
+    pub fn label(self) -> &'static str {
+        match self {
+            ModpackFormat::CurseForge => "CurseForge",
+            ModpackFormat::Modrinth => "Modrinth",
+        }
+    }
+}
+
+/// The instance a pack asks for, read from its manifest before anything is
+/// written to disk.
+#[derive(Debug)]
+pub struct ModpackPlan {
+    pub name: String,
+    pub minecraft_version: String,
+    pub loader: LoaderType,
+    pub loader_version: Option<String>,
+    /// Only CurseForge manifests recommend an amount of memory.
+    pub memory_max_mb: Option<u32>,
+    contents: PackContents,
+}
+
+#[derive(Debug)]
+enum PackContents {
+    CurseForge(CurseForgeManifest),
+    Modrinth(ModrinthIndex),
+}
+
+/// A CurseForge file whose author disabled third-party downloads; the user
+/// has to fetch it from the website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub project_id: u64,
+    pub file_id: u64,
+}
+
+/// Called with the number of finished and total pack files after each
+/// download.
+pub type FileProgress<'a> = &'a (dyn Fn(usize, usize) + Send + Sync);
+
+/// Read and validate the manifest of a pack archive.
+pub async fn read_modpack(format: ModpackFormat, archive: &Path) -> LauncherResult<ModpackPlan> {
+    match format {
+        ModpackFormat::CurseForge => curseforge::read_pack(archive).await,
+        ModpackFormat::Modrinth => modrinth::read_pack(archive).await,
+    }
+}
+
+/// Download the files of `plan` and unpack its overrides into an instance
+/// created from it. Vanilla and loader installation is left to the caller.
+/// Returns the files that could not be downloaded and have to be added by
+/// hand.
+pub async fn populate_instance(
+    plan: &ModpackPlan,
+    archive: &Path,
+    instance: &Instance,
+    client: &reqwest::Client,
+    downloader: &Downloader,
+    on_file: FileProgress<'_>,
+) -> LauncherResult<Vec<SkippedFile>> {
+    match &plan.contents {
+        PackContents::CurseForge(manifest) => {
+            curseforge::populate_instance(archive, manifest, instance, client, downloader, on_file)
+                .await
+        }
+        PackContents::Modrinth(index) => {
+            modrinth::install_pack_contents(archive, index, instance, downloader, on_file).await?;
+            modrinth::record_pack_mods(index, instance, client).await;
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Read and deserialize a JSON manifest stored at the root of a modpack archive.
+pub(crate) fn read_archive_json<T: DeserializeOwned>(
+    archive_path: &Path,
+    entry_name: &str,
+) -> LauncherResult<T> {
+    let file = std::fs::File::open(archive_path).map_err(|source| LauncherError::Io {
+        path: archive_path.to_path_buf(),
+        source,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let entry = archive
+        .by_name(entry_name)
+        .map_err(|e| LauncherError::message(ErrorText::ModpackEntryMissing, &[&entry_name, &e]))?;
+    Ok(serde_json::from_reader(entry)?)
+}
+
+/// Resolve a pack-relative path (`mods/foo.jar`, `config/bar.toml`) inside the
+/// instance. Mods go to the instance `mods/` folder, everything else is
+/// relative to the game directory. Returns `None` for paths that would escape it.
+pub(crate) fn pack_destination(instance: &Instance, relative: impl AsRef<Path>) -> Option<PathBuf> {
+    let relative = relative.as_ref();
+    if relative.as_os_str().is_empty()
+        || relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    match relative.strip_prefix("mods") {
+        Ok(rest) if rest.as_os_str().is_empty() => Some(instance.mods_dir()),
+        Ok(rest) => Some(instance.mods_dir().join(rest)),
+        Err(_) => Some(instance.game_dir().join(relative)),
+    }
+}
+
+/// Unpack every entry under `prefix/` (e.g. `overrides/`) into the instance,
+/// placing each one like [`pack_destination`] does (`overrides/mods/` into the
+/// instance `mods/` folder).
+///
+/// Entries are resolved through `enclosed_name` so a malicious archive cannot
+/// escape the instance directory. Returns the number of files written.
+pub(crate) fn extract_overrides(
+    archive_path: &Path,
+    prefix: &str,
+    instance: &Instance,
+) -> LauncherResult<usize> {
+    let file = std::fs::File::open(archive_path).map_err(|source| LauncherError::Io {
+        path: archive_path.to_path_buf(),
+        source,
+    })?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut written = 0;
+
+    for index in 0..archive.len() {
+        let mut zipped = archive.by_index(index)?;
+        let Some(enclosed_name) = zipped.enclosed_name() else {
+            continue;
+        };
+
+        let mut components = enclosed_name.components();
+        if components.next() != Some(Component::Normal(OsStr::new(prefix))) {
+            continue;
+        }
+
+        let mut rel_path = PathBuf::new();
+        for component in components {
+            if let Component::Normal(part) = component {
+                rel_path.push(part);
+            }
+        }
+
+        let Some(out_path) = pack_destination(instance, &rel_path) else {
+            continue;
+        };
+        if zipped.is_dir() {
+            std::fs::create_dir_all(&out_path).map_err(|source| LauncherError::Io {
+                path: out_path,
+                source,
+            })?;
+            continue;
+        }
+
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|source| LauncherError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+
+        let mut out = std::fs::File::create(&out_path).map_err(|source| LauncherError::Io {
+            path: out_path.clone(),
+            source,
+        })?;
+        std::io::copy(&mut zipped, &mut out).map_err(|source| LauncherError::Io {
+            path: out_path,
+            source,
+        })?;
+        written += 1;
+    }
+
+    Ok(written)
+}
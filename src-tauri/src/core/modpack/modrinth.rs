@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures_util::stream::{self, StreamExt};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::{
+    extract_overrides, pack_destination, read_archive_json, FileProgress, ModpackPlan, PackContents,
+};
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::{Instance, LoaderType};
+use crate::core::mods::manifest::{InstalledJar, ModsManifest};
+use crate::core::mods::updates;
+
+const INDEX_FILE: &str = "modrinth.index.json";
+const OVERRIDE_DIRS: [&str; 2] = ["overrides", "client-overrides"];
+const DOWNLOAD_CONCURRENCY: usize = 6;
+
+// ── modrinth.index.json ─────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModrinthIndex {
+    pub format_version: u32,
+    pub game: String,
+    #[serde(default)]
+    pub version_id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub files: Vec<ModrinthFile>,
+    #[serde(default)]
+    pub dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModrinthFile {
+    pub path: String,
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
+    #[serde(default)]
+    pub env: Option<ModrinthEnv>,
+    #[serde(default)]
+    pub downloads: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthEnv {
+    #[serde(default)]
+    pub client: Option<String>,
+}
+
+impl ModrinthFile {
+    /// Server-only files are marked `env.client = "unsupported"`.
+    fn is_client_file(&self) -> bool {
+        self.env
+            .as_ref()
+            .and_then(|env| env.client.as_deref())
+            .is_none_or(|client| client != "unsupported")
+    }
+}
+
+/// Read `modrinth.index.json` and resolve the instance the pack asks for.
+pub(super) async fn read_pack(path: &Path) -> LauncherResult<ModpackPlan> {
+    let index = read_index(path).await?;
+    let (minecraft_version, loader, loader_version) = resolve_dependencies(&index.dependencies)?;
+    info!(
+        "Importing Modrinth pack '{}' {:?} (Minecraft {}, {} {:?})",
+        index.name, index.version_id, minecraft_version, loader, loader_version
+    );
+
+    Ok(ModpackPlan {
+        name: index.name.clone(),
+        minecraft_version,
+        loader,
+        loader_version,
+        // mrpack indexes declare no memory.
+        memory_max_mb: None,
+        contents: PackContents::Modrinth(index),
+    })
+}
+
+/// Jars the pack put in `mods/`, with the SHA-1 its index declares.
+fn pack_mod_jars(index: &ModrinthIndex) -> Vec<InstalledJar> {
+    index
+        .files
+        .iter()
+        .filter(|file| file.is_client_file())
+        .filter_map(|file| {
+            let file_name = file.path.strip_prefix("mods/")?;
+            if file_name.contains(['/', '\\']) || !file_name.ends_with(".jar") {
+                return None;
+            }
+            Some(InstalledJar {
+                file_name: file_name.to_string(),
+                sha1: file.hashes.get("sha1")?.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Record the pack's mods in `mods.json` so they get update checks. The
+/// index only has paths and hashes, so they are looked up on Modrinth;
+/// failing that the pack is still usable and the next update check retries.
+pub(super) async fn record_pack_mods(
+    index: &ModrinthIndex,
+    instance: &Instance,
+    client: &reqwest::Client,
+) {
+    let jars = pack_mod_jars(index);
+    if jars.is_empty() {
+        return;
+    }
+    let mut manifest = ModsManifest::load(&instance.path);
+    let recorded = match updates::identify_jars(client, &mut manifest, &jars).await {
+        Ok(recorded) => recorded,
+        Err(err) => {
+            warn!("Cannot identify the pack mods on Modrinth: {}", err);
+            return;
+        }
+    };
+    if recorded > 0 {
+        if let Err(err) = manifest.save(&instance.path) {
+            warn!("Cannot record the pack mods of {}: {}", instance.id, err);
+        }
+    }
+}
+
+async fn read_index(path: &Path) -> LauncherResult<ModrinthIndex> {
+    let archive_path = path.to_path_buf();
+    let index: ModrinthIndex =
+        tokio::task::spawn_blocking(move || read_archive_json(&archive_path, INDEX_FILE))
+            .await
+            .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+
+    if index.game != "minecraft" {
+        return Err(LauncherError::message(
+            ErrorText::UnsupportedMrpackGame,
+            &[&index.game],
+        ));
+    }
+    if index.format_version != 1 {
+        return Err(LauncherError::message(
+            ErrorText::UnsupportedMrpackFormat,
+            &[&index.format_version],
+        ));
+    }
+
+    Ok(index)
+}
+
+/// Map the `dependencies` map to Minecraft version + loader fields.
+fn resolve_dependencies(
+    dependencies: &HashMap<String, String>,
+) -> LauncherResult<(String, LoaderType, Option<String>)> {
+    let minecraft_version = dependencies
+        .get("minecraft")
+        .cloned()
+        .ok_or_else(|| LauncherError::message(ErrorText::MrpackWithoutMinecraft, &[]))?;
+
+    let loaders = [
+        ("fabric-loader", LoaderType::Fabric),
+        ("quilt-loader", LoaderType::Quilt),
+        ("forge", LoaderType::Forge),
+        ("neoforge", LoaderType::NeoForge),
+    ];
+
+    for (key, loader) in loaders {
+        if let Some(version) = dependencies.get(key) {
+            return Ok((minecraft_version, loader, Some(version.clone())));
+        }
+    }
+
+    Ok((minecraft_version, LoaderType::Vanilla, None))
+}
+
+/// Download every client-side `files[]` entry (validated against
+/// `hashes.sha512`) and unpack `overrides/` and `client-overrides/`.
+pub(super) async fn install_pack_contents(
+    archive_path: &Path,
+    index: &ModrinthIndex,
+    instance: &Instance,
+    downloader: &Downloader,
+    on_file: FileProgress<'_>,
+) -> LauncherResult<()> {
+    let mut targets = Vec::new();
+    for file in &index.files {
+        if !file.is_client_file() {
+            info!("Skipping server-only file {}", file.path);
+            continue;
+        }
+
+        let dest = pack_destination(instance, &file.path)
+            .ok_or_else(|| LauncherError::message(ErrorText::InvalidMrpackPath, &[&file.path]))?;
+        let sha512 = file.hashes.get("sha512").cloned().ok_or_else(|| {
+            LauncherError::message(ErrorText::MrpackFileWithoutSha512, &[&file.path])
+        })?;
+        if file.downloads.is_empty() {
+            return Err(LauncherError::message(
+                ErrorText::MrpackFileWithoutDownloads,
+                &[&file.path],
+            ));
+        }
+
+        targets.push((file.downloads.clone(), dest, sha512));
+    }
+
+    let total = targets.len();
+    let done = AtomicUsize::new(0);
+    let results: Vec<LauncherResult<()>> = stream::iter(targets)
+        .map(|(urls, dest, sha512)| {
+            let done = &done;
+            async move {
+                let mut last_err = None;
+                for url in &urls {
+                    match downloader.download_file_sha512(url, &dest, &sha512).await {
+                        Ok(()) => {
+                            on_file(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+                            return Ok(());
+                        }
+                        Err(err) => {
+                            warn!("Download from {} failed: {}", url, err);
+                            last_err = Some(err);
+                        }
+                    }
+                }
+                Err(last_err.unwrap_or_else(|| {
+                    LauncherError::message(
+                        ErrorText::MrpackFileWithoutDownloads,
+                        &[&dest.display()],
+                    )
+                }))
+            }
+        })
+        .buffer_unordered(DOWNLOAD_CONCURRENCY)
+        .collect()
+        .await;
+
+    for result in results {
+        result?;
+    }
+
+    let archive_path = archive_path.to_path_buf();
+    let instance = instance.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        let mut written = 0;
+        for prefix in OVERRIDE_DIRS {
+            written += extract_overrides(&archive_path, prefix, &instance)?;
+        }
+        LauncherResult::Ok(written)
+    })
+    .await
+    .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))??;
+    info!("Unpacked {} override files", written);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_fixture(path: &Path) {
+        let index = serde_json::json!({
+            "formatVersion": 1,
+            "game": "minecraft",
+            "versionId": "1.0.0",
+            "name": "Fixture Pack",
+            "files": [{
+                "path": "mods/server-only.jar",
+                "hashes": { "sha1": "00", "sha512": "00" },
+                "env": { "client": "unsupported", "server": "required" },
+                "downloads": ["https://cdn.modrinth.com/data/x/server-only.jar"],
+                "fileSize": 1
+            }],
+            "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.7" }
+        });
+
+        let file = std::fs::File::create(path).unwrap();
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file(INDEX_FILE, options).unwrap();
+        writer.write_all(index.to_string().as_bytes()).unwrap();
+        writer
+            .start_file("overrides/config/shared.toml", options)
+            .unwrap();
+        writer.write_all(b"shared = true").unwrap();
+        writer
+            .start_file("client-overrides/options.txt", options)
+            .unwrap();
+        writer.write_all(b"lang:es_es").unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn resolve_dependencies_maps_loader_keys() {
+        let deps = HashMap::from([
+            ("minecraft".to_string(), "1.21.1".to_string()),
+            ("neoforge".to_string(), "21.1.77".to_string()),
+        ]);
+        let (mc, loader, version) = resolve_dependencies(&deps).unwrap();
+        assert_eq!(mc, "1.21.1");
+        assert_eq!(loader, LoaderType::NeoForge);
+        assert_eq!(version.as_deref(), Some("21.1.77"));
+
+        let vanilla = HashMap::from([("minecraft".to_string(), "1.20.4".to_string())]);
+        let (_, loader, version) = resolve_dependencies(&vanilla).unwrap();
+        assert_eq!(loader, LoaderType::Vanilla);
+        assert!(version.is_none());
+
+        assert!(resolve_dependencies(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn pack_destination_rejects_escaping_paths() {
+        let instance = Instance::new(
+            "test".into(),
+            "1.20.1".into(),
+            LoaderType::Fabric,
+            None,
+            2048,
+            Path::new("/tmp"),
+        );
+
+        assert_eq!(
+            pack_destination(&instance, "mods/a.jar"),
+            Some(instance.mods_dir().join("a.jar"))
+        );
+        assert_eq!(
+            pack_destination(&instance, "resourcepacks/b.zip"),
+            Some(instance.game_dir().join("resourcepacks/b.zip"))
+        );
+        assert!(pack_destination(&instance, "../outside.jar").is_none());
+        assert!(pack_destination(&instance, "/etc/passwd").is_none());
+    }
+
+    #[tokio::test]
+    async fn mrpack_fixture_skips_server_files_and_unpacks_overrides() {
+        let temp = std::env::temp_dir().join(format!("mrpack-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(&temp).unwrap();
+
+        let archive = temp.join("fixture.mrpack");
+        write_fixture(&archive);
+
+        let index = read_index(&archive).await.unwrap();
+        assert_eq!(index.name, "Fixture Pack");
+        let (mc, loader, version) = resolve_dependencies(&index.dependencies).unwrap();
+        assert_eq!(mc, "1.20.1");
+        assert_eq!(loader, LoaderType::Fabric);
+        assert_eq!(version.as_deref(), Some("0.15.7"));
+
+        let mut instance = Instance::new(index.name.clone(), mc, loader, version, 2048, &temp);
+        instance.path = temp.join("instance");
+        let downloader = Downloader::new(None);
+
+        install_pack_contents(&archive, &index, &instance, &downloader, &|_, _| {})
+            .await
+            .unwrap();
+
+        assert!(!instance.mods_dir().join("server-only.jar").exists());
+        assert!(pack_mod_jars(&index).is_empty());
+        assert!(instance.game_dir().join("config/shared.toml").exists());
+        assert!(instance.game_dir().join("options.txt").exists());
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
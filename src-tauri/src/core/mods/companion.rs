@@ -0,0 +1,261 @@
+// ─── Loader Companion ───
+// Most Fabric mods need Fabric API (and Quilt mods QFAPI/QSL), and a missing
+// one only shows up as a crash at launch. The mods of an instance are checked
+// for a dependency on it so the UI can offer to install the build matching
+// the instance's Minecraft version from Modrinth.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tracing::info;
+
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::{ErrorText, Locale};
+use crate::core::instance::{Instance, LoaderType};
+use crate::core::loaders::{loader_companion, LoaderCompanion};
+
+use super::manifest::{ModRecord, ModsManifest};
+use super::metadata::InstalledMod;
+use super::modrinth;
+
+/// Mod ids of Fabric API itself (`fabric` before 0.59).
+const FABRIC_API_IDS: [&str; 2] = ["fabric-api", "fabric"];
+/// Quilt's QFAPI/QSL, which stand in for Fabric API on Quilt only.
+const QUILT_API_IDS: [&str; 2] = ["quilted_fabric_api", "qsl"];
+
+/// Whether the mod `id` is the companion of `loader`.
+fn provides_companion(loader: &LoaderType, id: &str) -> bool {
+    match loader {
+        LoaderType::Fabric => FABRIC_API_IDS.contains(&id),
+        LoaderType::Quilt => FABRIC_API_IDS.contains(&id) || QUILT_API_IDS.contains(&id),
+        _ => false,
+    }
+}
+
+/// Whether a dependency on `id` is met by the companion of `loader`. Mods
+/// usually depend on single Fabric API modules (`fabric-networking-api-v1`),
+/// which ship inside it; `fabric-language-*` are separate mods.
+fn satisfied_by_companion(loader: &LoaderType, id: &str) -> bool {
+    let fabric_module = matches!(loader, LoaderType::Fabric | LoaderType::Quilt)
+        && id.starts_with("fabric-")
+        && !id.starts_with("fabric-language-");
+    fabric_module || provides_companion(loader, id)
+}
+
+/// Whether an instance needs its loader companion and has it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompanionStatus {
+    pub companion: Option<LoaderCompanion>,
+    pub installed: bool,
+    /// Ids of the mods that depend on the companion.
+    pub required_by: Vec<String>,
+}
+
+impl CompanionStatus {
+    /// A companion is missing that some mod needs.
+    pub fn is_missing(&self) -> bool {
+        self.companion.is_some() && !self.installed && !self.required_by.is_empty()
+    }
+}
+
+/// Check `mods` against the companion of `loader`; its hint is in `locale`.
+pub fn companion_status(
+    loader: &LoaderType,
+    mods: &[InstalledMod],
+    locale: Locale,
+) -> CompanionStatus {
+    let companion = loader_companion(loader, locale);
+    if companion.is_none() {
+        return CompanionStatus {
+            companion,
+            installed: false,
+            required_by: Vec::new(),
+        };
+    }
+
+    let installed = mods
+        .iter()
+        .any(|installed| provides_companion(loader, &installed.key()));
+    let mut required_by: Vec<String> = mods
+        .iter()
+        .filter_map(|installed| installed.metadata.as_ref())
+        .filter(|metadata| !satisfied_by_companion(loader, &metadata.id))
+        .filter(|metadata| {
+            metadata
+                .depends
+                .iter()
+                .any(|id| satisfied_by_companion(loader, id))
+        })
+        .map(|metadata| metadata.id.clone())
+        .collect();
+    required_by.sort();
+    required_by.dedup();
+
+    CompanionStatus {
+        companion,
+        installed,
+        required_by,
+    }
+}
+
+/// Enabled mod jars in `mods_dir`.
+pub fn installed_mods(mods_dir: &Path) -> Vec<InstalledMod> {
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+        })
+        .map(|path| InstalledMod::read(&path))
+        .collect()
+}
+
+/// Download the companion build matching `instance` from Modrinth into its
+/// `mods/` folder and record it in `mods.json`. Returns the installed file
+/// name, or `None` if it was already there.
+pub async fn ensure_companion(
+    client: &reqwest::Client,
+    downloader: &Downloader,
+    instance: &Instance,
+    locale: Locale,
+) -> LauncherResult<Option<String>> {
+    let mods_dir = instance.mods_dir();
+    let mods = installed_mods(&mods_dir);
+    let status = companion_status(&instance.loader, &mods, locale);
+    let Some(companion) = status.companion else {
+        return Err(LauncherError::message(
+            ErrorText::NoCompanionApi,
+            &[&instance.loader],
+        ));
+    };
+    if status.installed {
+        return Ok(None);
+    }
+
+    let loader = instance.loader.to_string();
+    let versions = modrinth::project_versions(
+        client,
+        companion.modrinth_slug,
+        &loader,
+        &instance.minecraft_version,
+    )
+    .await?;
+    let (version, file) = modrinth::pick_version(&versions, &loader, &instance.minecraft_version)
+        .and_then(|version| Some((version, version.primary_file()?)))
+        .ok_or_else(|| {
+            LauncherError::message(
+                ErrorText::NoCompanionVersion,
+                &[&companion.name, &instance.minecraft_version],
+            )
+        })?;
+
+    modrinth::download_version_file(downloader, file, &mods_dir).await?;
+    let mut manifest = ModsManifest::load(&instance.path);
+    manifest.record(ModRecord::new(version, file));
+    manifest.save(&instance.path)?;
+    info!(
+        "Installed {} into instance {}: {}",
+        companion.name, instance.id, file.filename
+    );
+    Ok(Some(file.filename.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::metadata::{ModLoaderKind, ModMetadata};
+    use crate::core::mods::modrinth::{pick_version, ModrinthVersion};
+
+    fn fabric_mod(id: &str, depends: &[&str]) -> InstalledMod {
+        InstalledMod {
+            file_name: format!("{id}.jar"),
+            metadata: Some(ModMetadata {
+                id: id.into(),
+                version: Some("1.0.0".into()),
+                loaders: vec![ModLoaderKind::Fabric],
+                minecraft: None,
+                depends: depends.iter().map(|id| id.to_string()).collect(),
+            }),
+        }
+    }
+
+    #[test]
+    fn mods_depending_on_fabric_api_are_detected() {
+        let mods = [
+            fabric_mod("sodium", &["fabricloader", "minecraft"]),
+            fabric_mod("modmenu", &["fabric-api", "minecraft"]),
+            fabric_mod("appleskin", &["fabric"]),
+            fabric_mod("lithium", &["fabric-networking-api-v1"]),
+            fabric_mod("kotlin-mod", &["fabric-language-kotlin"]),
+        ];
+
+        let status = companion_status(&LoaderType::Fabric, &mods, Locale::Es);
+        assert_eq!(status.companion.unwrap().modrinth_slug, "fabric-api");
+        assert!(!status.installed);
+        assert_eq!(status.required_by, ["appleskin", "lithium", "modmenu"]);
+        assert!(status.is_missing());
+
+        // QSL does not provide Fabric API on Fabric.
+        let mut with_qsl = mods.to_vec();
+        with_qsl.push(fabric_mod("qsl", &[]));
+        assert!(!companion_status(&LoaderType::Fabric, &with_qsl, Locale::Es).installed);
+        assert!(companion_status(&LoaderType::Quilt, &with_qsl, Locale::Es).installed);
+
+        let mut with_api = mods.to_vec();
+        with_api.push(fabric_mod("fabric-api", &["fabricloader"]));
+        let status = companion_status(&LoaderType::Fabric, &with_api, Locale::Es);
+        assert!(status.installed);
+        assert!(!status.is_missing());
+
+        // Quilt mods may ask for QSL directly.
+        let quilt_mods = [fabric_mod("create", &["qsl"])];
+        let status = companion_status(&LoaderType::Quilt, &quilt_mods, Locale::En);
+        let companion = status.companion.unwrap();
+        assert_eq!(companion.modrinth_slug, "qsl");
+        assert_eq!(companion.i18n_key, "companion.quilt_api");
+        assert!(companion.hint.starts_with("Most Quilt mods"));
+        assert_eq!(status.required_by, ["create"]);
+
+        // Forge has no companion to offer.
+        let status = companion_status(&LoaderType::Forge, &mods, Locale::Es);
+        assert!(status.companion.is_none());
+        assert!(!status.is_missing());
+    }
+
+    #[test]
+    fn picks_the_newest_release_for_the_instance_version() {
+        let versions: Vec<ModrinthVersion> = serde_json::from_str(
+            r#"[
+                { "id": "beta", "project_id": "P7dR8mSH", "version_number": "0.93.0+1.20.1",
+                  "version_type": "beta", "game_versions": ["1.20.1"], "loaders": ["fabric"],
+                  "date_published": "2024-03-01T00:00:00Z",
+                  "files": [{ "url": "https://cdn.modrinth.com/b.jar", "filename": "b.jar", "primary": true }] },
+                { "id": "new", "project_id": "P7dR8mSH", "version_number": "0.92.2+1.20.1",
+                  "version_type": "release", "game_versions": ["1.20.1"], "loaders": ["fabric"],
+                  "date_published": "2024-02-01T00:00:00Z",
+                  "files": [{ "url": "https://cdn.modrinth.com/n.jar", "filename": "n.jar", "primary": true }] },
+                { "id": "old", "project_id": "P7dR8mSH", "version_number": "0.92.0+1.20.1",
+                  "version_type": "release", "game_versions": ["1.20.1"], "loaders": ["fabric"],
+                  "date_published": "2024-01-01T00:00:00Z",
+                  "files": [{ "url": "https://cdn.modrinth.com/o.jar", "filename": "o.jar", "primary": true }] },
+                { "id": "other-mc", "project_id": "P7dR8mSH", "version_number": "0.97.0+1.20.4",
+                  "version_type": "release", "game_versions": ["1.20.4"], "loaders": ["fabric"],
+                  "date_published": "2024-05-01T00:00:00Z",
+                  "files": [{ "url": "https://cdn.modrinth.com/x.jar", "filename": "x.jar", "primary": true }] }
+            ]"#,
+        )
+        .unwrap();
+
+        let picked = pick_version(&versions, "fabric", "1.20.1").unwrap();
+        assert_eq!(picked.id, "new");
+        assert_eq!(picked.primary_file().unwrap().filename, "n.jar");
+        assert!(pick_version(&versions, "quilt", "1.20.1").is_none());
+        assert!(pick_version(&versions, "fabric", "1.21").is_none());
+    }
+}
@@ -0,0 +1,215 @@
+// ─── Mods Manifest ───
+// `<instance>/mods.json` remembers where mods installed by the launcher came
+// from (Modrinth project and version), which a jar alone does not say. It is
+// what update checks work from. Jars that got into `mods/` some other way
+// (modpack imports, copied by hand) are identified by their SHA-1 and
+// recorded when Modrinth knows them; the rest are left alone.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use sha1::Sha1;
+
+use crate::core::atomic_file;
+use crate::core::downloader::resume::hash_file;
+use crate::core::error::{LauncherError, LauncherResult};
+
+use super::modrinth::{ModrinthVersion, ModrinthVersionFile};
+use super::toggle::{list_mods, DISABLED_SUFFIX};
+
+pub const MODS_MANIFEST_FILE: &str = "mods.json";
+
+/// A mod installed from Modrinth.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModRecord {
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    /// Publication date of the installed version (RFC 3339), used to tell
+    /// newer versions from older ones.
+    #[serde(default)]
+    pub date_published: String,
+    /// Jar name in `mods/`, without the `.disabled` suffix.
+    pub file_name: String,
+    /// SHA-1 of the installed jar, the key of Modrinth's update lookup.
+    /// Empty in manifests written before it was recorded.
+    #[serde(default)]
+    pub sha1: String,
+}
+
+impl ModRecord {
+    pub fn new(version: &ModrinthVersion, file: &ModrinthVersionFile) -> Self {
+        Self {
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            version_number: version.version_number.clone(),
+            date_published: version.date_published.clone(),
+            file_name: file.filename.clone(),
+            sha1: file.hashes.get("sha1").cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Whether the jar is still in `mods_dir`, enabled or disabled.
+    pub fn is_present(&self, mods_dir: &Path) -> bool {
+        mods_dir.join(&self.file_name).is_file()
+            || mods_dir
+                .join(format!("{}{DISABLED_SUFFIX}", self.file_name))
+                .is_file()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModsManifest {
+    #[serde(default)]
+    pub mods: Vec<ModRecord>,
+}
+
+impl ModsManifest {
+    /// Manifest of `instance_dir`; empty when missing or unreadable.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(MODS_MANIFEST_FILE);
+        let Ok(bytes) = std::fs::read(&path) else {
+            return Self::default();
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+            tracing::warn!("Ignoring unreadable {:?}: {}", path, err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, instance_dir: &Path) -> LauncherResult<()> {
+        let path = instance_dir.join(MODS_MANIFEST_FILE);
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(&path, json).map_err(|source| LauncherError::Io { path, source })
+    }
+
+    pub fn get(&self, project_id: &str) -> Option<&ModRecord> {
+        self.mods
+            .iter()
+            .find(|record| record.project_id == project_id)
+    }
+
+    /// Add `record`, replacing the one of the same project.
+    pub fn record(&mut self, record: ModRecord) {
+        self.mods
+            .retain(|existing| existing.project_id != record.project_id);
+        self.mods.push(record);
+    }
+
+    /// Record `jar` as the file of `version` it has the hash of, under its
+    /// name in `mods/`. Returns whether the version ships that file.
+    pub fn record_identified(&mut self, jar: &InstalledJar, version: &ModrinthVersion) -> bool {
+        let Some(file) = version
+            .files
+            .iter()
+            .find(|file| file.hashes.get("sha1") == Some(&jar.sha1))
+        else {
+            return false;
+        };
+        self.record(ModRecord {
+            file_name: jar.file_name.clone(),
+            sha1: jar.sha1.clone(),
+            ..ModRecord::new(version, file)
+        });
+        true
+    }
+
+    /// Match `jars` against the records: a record whose jar has no hash yet
+    /// takes the one found on disk. Returns the jars no record covers, e.g.
+    /// ones replaced by hand or never recorded.
+    pub fn reconcile(&mut self, jars: &[InstalledJar]) -> Vec<InstalledJar> {
+        let mut unrecorded = Vec::new();
+        for jar in jars {
+            match self
+                .mods
+                .iter_mut()
+                .find(|record| record.file_name == jar.file_name)
+            {
+                Some(record) if record.sha1.is_empty() => record.sha1 = jar.sha1.clone(),
+                Some(record) if record.sha1 == jar.sha1 => {}
+                _ => unrecorded.push(jar.clone()),
+            }
+        }
+        unrecorded
+    }
+
+    /// Records whose jar is still installed; deleted mods are not checked.
+    pub fn installed(&self, mods_dir: &Path) -> Vec<ModRecord> {
+        self.mods
+            .iter()
+            .filter(|record| record.is_present(mods_dir))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A jar in `mods/`, enabled or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledJar {
+    /// Name without the `.disabled` suffix.
+    pub file_name: String,
+    pub sha1: String,
+}
+
+/// Every jar in `mods_dir` with its SHA-1. Hashes whole files, so call it
+/// off the async runtime.
+pub fn installed_jars(mods_dir: &Path) -> LauncherResult<Vec<InstalledJar>> {
+    list_mods(mods_dir)?
+        .into_iter()
+        .map(|entry| {
+            let sha1 = hash_file::<Sha1>(&mods_dir.join(&entry.file_name))?;
+            let file_name = match entry.file_name.strip_suffix(DISABLED_SUFFIX) {
+                Some(original) => original.to_string(),
+                None => entry.file_name,
+            };
+            Ok(InstalledJar { file_name, sha1 })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(project_id: &str, file_name: &str) -> ModRecord {
+        ModRecord {
+            project_id: project_id.into(),
+            version_id: format!("{project_id}-v1"),
+            version_number: "1.0.0".into(),
+            date_published: "2024-01-01T00:00:00Z".into(),
+            file_name: file_name.into(),
+            sha1: String::new(),
+        }
+    }
+
+    #[test]
+    fn records_round_trip_and_follow_the_jar() {
+        let dir = std::env::temp_dir().join(format!("mods-manifest-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mods_dir = dir.join("mods");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        std::fs::write(mods_dir.join("sodium.jar"), b"jar").unwrap();
+        std::fs::write(mods_dir.join("lithium.jar.disabled"), b"jar").unwrap();
+
+        let mut manifest = ModsManifest::load(&dir);
+        assert!(manifest.mods.is_empty());
+        manifest.record(record("sodium", "old-sodium.jar"));
+        manifest.record(record("sodium", "sodium.jar"));
+        manifest.record(record("lithium", "lithium.jar"));
+        manifest.record(record("deleted", "deleted.jar"));
+        manifest.save(&dir).unwrap();
+
+        let manifest = ModsManifest::load(&dir);
+        assert_eq!(manifest.mods.len(), 3);
+        assert_eq!(manifest.get("sodium").unwrap().file_name, "sodium.jar");
+        let installed: Vec<_> = manifest
+            .installed(&mods_dir)
+            .into_iter()
+            .map(|record| record.project_id)
+            .collect();
+        assert_eq!(installed, ["sodium", "lithium"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,731 @@
+// ─── Mod Metadata ───
+// Reads the metadata mod jars ship for their loader (`fabric.mod.json`,
+// `quilt.mod.json`, `META-INF/mods.toml`, `META-INF/neoforge.mods.toml`) so
+// the optimizer can reason about real mod ids, the loaders a jar targets and
+// the Minecraft versions it declares support for, instead of file names.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::core::instance::LoaderType;
+use crate::core::version::compare::{compare_parts, release_before, release_parts};
+
+const FABRIC_METADATA: &str = "fabric.mod.json";
+const QUILT_METADATA: &str = "quilt.mod.json";
+const FORGE_METADATA: &str = "META-INF/mods.toml";
+const NEOFORGE_METADATA: &str = "META-INF/neoforge.mods.toml";
+const MANIFEST: &str = "META-INF/MANIFEST.MF";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModLoaderKind {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+impl std::fmt::Display for ModLoaderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModLoaderKind::Fabric => write!(f, "fabric"),
+            ModLoaderKind::Quilt => write!(f, "quilt"),
+            ModLoaderKind::Forge => write!(f, "forge"),
+            ModLoaderKind::NeoForge => write!(f, "neoforge"),
+        }
+    }
+}
+
+/// Minecraft versions a mod declares support for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRequirement {
+    /// Fabric/Quilt predicates (`>=1.20 <1.21`, `~1.20.1`, `1.20.x`); any
+    /// of them may match.
+    Predicates(Vec<String>),
+    /// Forge/NeoForge Maven range (`[1.20.1,1.21)`).
+    MavenRange(String),
+}
+
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionRequirement::Predicates(predicates) => write!(f, "{}", predicates.join(" || ")),
+            VersionRequirement::MavenRange(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+impl Serialize for VersionRequirement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Identity of a mod as declared by its own metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModMetadata {
+    pub id: String,
+    pub version: Option<String>,
+    /// Every loader the jar ships metadata for, in lookup order.
+    pub loaders: Vec<ModLoaderKind>,
+    pub minecraft: Option<VersionRequirement>,
+    /// Lowercase ids of the mods it depends on (`fabric-api`, `minecraft`, ...).
+    pub depends: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct FabricModJson {
+    id: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    depends: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoader,
+}
+
+#[derive(Deserialize)]
+struct QuiltLoader {
+    id: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    depends: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ModsToml {
+    #[serde(default)]
+    mods: Vec<ModsTomlEntry>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, Vec<ModsTomlDependency>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlEntry {
+    mod_id: String,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlDependency {
+    mod_id: String,
+    #[serde(default)]
+    version_range: Option<String>,
+}
+
+/// Identity, Minecraft requirement and dependency ids read from one
+/// metadata file.
+type Declared = (
+    String,
+    Option<String>,
+    Option<VersionRequirement>,
+    Vec<String>,
+);
+type MetadataParser = fn(&str) -> Option<Declared>;
+
+/// `"1.20.1"` or `["1.20", "1.20.1"]` as a list of predicates.
+fn predicates(value: &serde_json::Value) -> Option<VersionRequirement> {
+    let list = match value {
+        serde_json::Value::String(predicate) => vec![predicate.clone()],
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => return None,
+    };
+    (!list.is_empty()).then_some(VersionRequirement::Predicates(list))
+}
+
+fn parse_fabric(text: &str) -> Option<Declared> {
+    let parsed: FabricModJson = serde_json::from_str(text).ok()?;
+    let minecraft = parsed.depends.get("minecraft").and_then(predicates);
+    let depends = parsed.depends.keys().map(|id| id.to_lowercase()).collect();
+    Some((parsed.id, parsed.version, minecraft, depends))
+}
+
+fn parse_quilt(text: &str) -> Option<Declared> {
+    let parsed: QuiltModJson = serde_json::from_str(text).ok()?;
+    let minecraft = parsed.quilt_loader.depends.iter().find_map(|dependency| {
+        (dependency.get("id")?.as_str()? == "minecraft")
+            .then(|| dependency.get("versions").and_then(predicates))
+            .flatten()
+    });
+    // Entries are either a bare id or an object with an `id`.
+    let depends = parsed
+        .quilt_loader
+        .depends
+        .iter()
+        .filter_map(|dependency| {
+            dependency
+                .as_str()
+                .or_else(|| dependency.get("id")?.as_str())
+        })
+        .map(str::to_lowercase)
+        .collect();
+    Some((
+        parsed.quilt_loader.id,
+        parsed.quilt_loader.version,
+        minecraft,
+        depends,
+    ))
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut text = String::new();
+    file.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+/// `Implementation-Version` from the jar manifest, which Forge substitutes
+/// for `${file.jarVersion}`.
+fn manifest_version(archive: &mut zip::ZipArchive<std::fs::File>) -> Option<String> {
+    read_entry(archive, MANIFEST)?.lines().find_map(|line| {
+        line.strip_prefix("Implementation-Version:")
+            .map(|version| version.trim().to_string())
+    })
+}
+
+fn parse_mods_toml(text: &str) -> Option<Declared> {
+    let parsed: ModsToml = toml::from_str(text).ok()?;
+    let entry = parsed.mods.into_iter().next()?;
+    let dependencies = parsed.dependencies.get(&entry.mod_id);
+    let minecraft = dependencies
+        .into_iter()
+        .flatten()
+        .find(|dependency| dependency.mod_id == "minecraft")
+        .and_then(|dependency| dependency.version_range.clone())
+        .map(VersionRequirement::MavenRange);
+    let depends = dependencies
+        .into_iter()
+        .flatten()
+        .map(|dependency| dependency.mod_id.to_lowercase())
+        .collect();
+    Some((entry.mod_id, entry.version, minecraft, depends))
+}
+
+/// Read the loader metadata of a mod jar. Returns `None` when the jar cannot
+/// be opened or carries no metadata the launcher understands.
+pub fn read_mod_metadata(jar: &Path) -> Option<ModMetadata> {
+    let file = std::fs::File::open(jar).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+
+    let mut identity: Option<Declared> = None;
+    let mut loaders = Vec::new();
+
+    let sources: [(&str, ModLoaderKind, MetadataParser); 4] = [
+        (FABRIC_METADATA, ModLoaderKind::Fabric, parse_fabric),
+        (QUILT_METADATA, ModLoaderKind::Quilt, parse_quilt),
+        (NEOFORGE_METADATA, ModLoaderKind::NeoForge, parse_mods_toml),
+        (FORGE_METADATA, ModLoaderKind::Forge, parse_mods_toml),
+    ];
+    for (entry, loader, parse) in sources {
+        if let Some(parsed) = read_entry(&mut archive, entry).and_then(|text| parse(&text)) {
+            identity.get_or_insert(parsed);
+            loaders.push(loader);
+        }
+    }
+
+    let (id, mut version, minecraft, depends) = identity?;
+    if version
+        .as_deref()
+        .is_some_and(|v| v.contains("${file.jarVersion}"))
+    {
+        version = manifest_version(&mut archive);
+    }
+    Some(ModMetadata {
+        id: id.to_lowercase(),
+        version,
+        loaders,
+        minecraft,
+        depends,
+    })
+}
+
+/// A jar in the instance `mods/` folder and its metadata, if readable.
+#[derive(Debug, Clone)]
+pub struct InstalledMod {
+    pub file_name: String,
+    pub metadata: Option<ModMetadata>,
+}
+
+impl InstalledMod {
+    pub fn read(path: &Path) -> Self {
+        Self {
+            file_name: path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string(),
+            metadata: read_mod_metadata(path),
+        }
+    }
+
+    /// Mod id from the metadata, or a guess from the file name
+    /// (`sodium-fabric-0.5.3.jar` → `sodium`) for unreadable jars.
+    pub fn key(&self) -> String {
+        if let Some(metadata) = &self.metadata {
+            return metadata.id.clone();
+        }
+        let normalized = self.file_name.to_lowercase();
+        normalized
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(&normalized)
+            .to_string()
+    }
+
+    fn label(&self) -> String {
+        match self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.version.as_deref())
+        {
+            Some(version) => format!("{} ({version})", self.file_name),
+            None => self.file_name.clone(),
+        }
+    }
+}
+
+/// Mods present more than once, as `id: file (version), file (version)`.
+pub fn duplicate_mods(mods: &[InstalledMod]) -> Vec<String> {
+    let mut by_key: BTreeMap<String, Vec<&InstalledMod>> = BTreeMap::new();
+    for installed in mods {
+        by_key.entry(installed.key()).or_default().push(installed);
+    }
+
+    by_key
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(key, mut copies)| {
+            copies.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+            let labels: Vec<String> = copies.iter().map(|copy| copy.label()).collect();
+            format!("{key}: {}", labels.join(", "))
+        })
+        .collect()
+}
+
+// ─── Minecraft version requirements ───
+
+/// One space-separated Fabric predicate list; every comparator must hold.
+fn matches_predicate(predicate: &str, version: &[u64]) -> Option<bool> {
+    for comparator in predicate.split_whitespace() {
+        if comparator == "*" {
+            continue;
+        }
+        let (op, raw) = [">=", "<=", ">", "<", "=", "^", "~"]
+            .iter()
+            .find_map(|op| comparator.strip_prefix(op).map(|rest| (*op, rest)))
+            .unwrap_or(("=", comparator));
+        let raw = raw.trim_end_matches('-');
+
+        let wildcard = raw
+            .split('.')
+            .position(|part| matches!(part, "x" | "X" | "*"));
+        let target: Vec<u64> = match wildcard {
+            Some(index) => raw
+                .split('.')
+                .take(index)
+                .map(|part| part.parse().ok())
+                .collect::<Option<_>>()?,
+            None => release_parts(raw)?,
+        };
+        if let Some(index) = wildcard {
+            if version.get(..index) != Some(&target[..]) {
+                return Some(false);
+            }
+            continue;
+        }
+
+        let ordering = compare_parts(version, &target);
+        let holds = match op {
+            ">=" => ordering != Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            "<" => ordering == Ordering::Less,
+            "^" => ordering != Ordering::Less && version.first() == target.first(),
+            "~" => ordering != Ordering::Less && version.get(..2) == target.get(..2),
+            _ => ordering == Ordering::Equal,
+        };
+        if !holds {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+/// Maven range list such as `[1.20,1.20.2)` or `[1.19,1.20),[1.20.1,)`. A
+/// bare version is only a recommendation and matches everything.
+fn matches_maven_range(range: &str, version: &[u64]) -> Option<bool> {
+    let range = range.trim();
+    if !range.starts_with(['[', '(']) {
+        return Some(true);
+    }
+
+    let mut rest = range;
+    while !rest.is_empty() {
+        let end = rest.find([']', ')'])?;
+        let (lower_inclusive, upper_inclusive) =
+            (rest.starts_with('['), rest.as_bytes()[end] == b']');
+        let body = &rest[1..end];
+        let holds = match body.split_once(',') {
+            None => compare_parts(version, &release_parts(body.trim())?) == Ordering::Equal,
+            Some((lower, upper)) => {
+                let lower_ok = match lower.trim() {
+                    "" => true,
+                    lower => match compare_parts(version, &release_parts(lower)?) {
+                        Ordering::Greater => true,
+                        Ordering::Equal => lower_inclusive,
+                        Ordering::Less => false,
+                    },
+                };
+                let upper_ok = match upper.trim() {
+                    "" => true,
+                    upper => match compare_parts(version, &release_parts(upper)?) {
+                        Ordering::Less => true,
+                        Ordering::Equal => upper_inclusive,
+                        Ordering::Greater => false,
+                    },
+                };
+                lower_ok && upper_ok
+            }
+        };
+        if holds {
+            return Some(true);
+        }
+        rest = rest[end + 1..].trim_start_matches([',', ' ']);
+    }
+    Some(false)
+}
+
+impl VersionRequirement {
+    /// Whether `minecraft_version` satisfies the requirement; `None` when
+    /// either side cannot be interpreted (snapshots, unusual syntax).
+    pub fn matches(&self, minecraft_version: &str) -> Option<bool> {
+        let version = release_parts(minecraft_version)?;
+        match self {
+            VersionRequirement::Predicates(predicates) => {
+                let mut any_unknown = false;
+                for predicate in predicates {
+                    match matches_predicate(predicate, &version) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => any_unknown = true,
+                    }
+                }
+                (!any_unknown).then_some(false)
+            }
+            VersionRequirement::MavenRange(range) => matches_maven_range(range, &version),
+        }
+    }
+}
+
+/// Whether a jar with metadata for `loaders` can run on `instance_loader`.
+/// Quilt loads Fabric mods, and NeoForge read Forge's `mods.toml` until
+/// 1.20.5 introduced `neoforge.mods.toml`. Vanilla loads no jars at all, so
+/// there is no loader for a mod to mismatch.
+fn loader_supported(loaders: &[ModLoaderKind], instance_loader: &LoaderType, mc: &str) -> bool {
+    loaders.iter().any(|loader| match instance_loader {
+        LoaderType::Vanilla => true,
+        LoaderType::Fabric => *loader == ModLoaderKind::Fabric,
+        LoaderType::Quilt => matches!(loader, ModLoaderKind::Quilt | ModLoaderKind::Fabric),
+        LoaderType::Forge => *loader == ModLoaderKind::Forge,
+        LoaderType::NeoForge => {
+            *loader == ModLoaderKind::NeoForge
+                || (*loader == ModLoaderKind::Forge
+                    && release_before(mc, &[1, 20, 5]) == Some(true))
+        }
+    })
+}
+
+/// Mods built for another loader or declaring a Minecraft range that
+/// excludes the instance version, with the reason for each.
+pub fn compatibility_issues(
+    mods: &[InstalledMod],
+    instance_loader: &LoaderType,
+    minecraft_version: &str,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    for installed in mods {
+        let Some(metadata) = &installed.metadata else {
+            continue;
+        };
+
+        if !loader_supported(&metadata.loaders, instance_loader, minecraft_version) {
+            let declared: Vec<String> = metadata.loaders.iter().map(|l| l.to_string()).collect();
+            issues.push(format!(
+                "{}: es un mod para {} y la instancia usa {}.",
+                installed.file_name,
+                declared.join("/"),
+                instance_loader
+            ));
+            continue;
+        }
+
+        if let Some(requirement) = &metadata.minecraft {
+            if requirement.matches(minecraft_version) == Some(false) {
+                issues.push(format!(
+                    "{}: requiere Minecraft {} y la instancia usa {}.",
+                    installed.file_name, requirement, minecraft_version
+                ));
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::temp_dir;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn write_jar(dir: &Path, name: &str, entries: &[(&str, &str)]) -> PathBuf {
+        let path = dir.join(name);
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        for (entry, content) in entries {
+            writer.start_file(*entry, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_fabric_and_forge_metadata() {
+        let dir = temp_dir("mod-metadata-read");
+        let fabric = write_jar(
+            &dir,
+            "sodium-fabric-0.5.3.jar",
+            &[(
+                FABRIC_METADATA,
+                r#"{"schemaVersion": 1, "id": "sodium", "version": "0.5.3+mc1.20.1"}"#,
+            )],
+        );
+        let forge = write_jar(
+            &dir,
+            "jei.jar",
+            &[
+                (
+                    FORGE_METADATA,
+                    "modLoader=\"javafml\"\n[[mods]]\nmodId=\"jei\"\nversion=\"${file.jarVersion}\"\n",
+                ),
+                (MANIFEST, "Manifest-Version: 1.0\r\nImplementation-Version: 15.2.0.27\r\n"),
+            ],
+        );
+
+        assert_eq!(
+            read_mod_metadata(&fabric),
+            Some(ModMetadata {
+                id: "sodium".into(),
+                version: Some("0.5.3+mc1.20.1".into()),
+                loaders: vec![ModLoaderKind::Fabric],
+                minecraft: None,
+                depends: vec![],
+            })
+        );
+        assert_eq!(
+            read_mod_metadata(&forge),
+            Some(ModMetadata {
+                id: "jei".into(),
+                version: Some("15.2.0.27".into()),
+                loaders: vec![ModLoaderKind::Forge],
+                minecraft: None,
+                depends: vec![],
+            })
+        );
+        assert_eq!(read_mod_metadata(&dir.join("missing.jar")), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn dependency_ids_are_read_from_every_format() {
+        let dir = temp_dir("mod-metadata-depends");
+        let fabric = write_jar(
+            &dir,
+            "modmenu.jar",
+            &[(
+                FABRIC_METADATA,
+                r#"{"id": "modmenu", "depends": {"fabric-api": "*", "minecraft": ">=1.20"}}"#,
+            )],
+        );
+        let quilt = write_jar(
+            &dir,
+            "create.jar",
+            &[(
+                QUILT_METADATA,
+                r#"{"quilt_loader": {"id": "create", "depends": ["qsl", {"id": "minecraft", "versions": "1.20.1"}]}}"#,
+            )],
+        );
+        let forge = write_jar(
+            &dir,
+            "jei.jar",
+            &[(
+                FORGE_METADATA,
+                "[[mods]]\nmodId=\"jei\"\n[[dependencies.jei]]\nmodId=\"forge\"\n",
+            )],
+        );
+
+        let depends = |jar: &Path| read_mod_metadata(jar).unwrap().depends;
+        assert_eq!(depends(&fabric), ["fabric-api", "minecraft"]);
+        assert_eq!(depends(&quilt), ["qsl", "minecraft"]);
+        assert_eq!(depends(&forge), ["forge"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn duplicates_are_detected_by_mod_id() {
+        let dir = temp_dir("mod-metadata-duplicates");
+        let jars = [
+            write_jar(
+                &dir,
+                "sodium-fabric-0.5.3.jar",
+                &[(FABRIC_METADATA, r#"{"id": "sodium", "version": "0.5.3"}"#)],
+            ),
+            // Renamed copy of the same mod.
+            write_jar(
+                &dir,
+                "renderer-optimizations.jar",
+                &[(FABRIC_METADATA, r#"{"id": "sodium", "version": "0.5.8"}"#)],
+            ),
+            // Same filename prefix, different mods.
+            write_jar(
+                &dir,
+                "create-0.5.1.jar",
+                &[(
+                    NEOFORGE_METADATA,
+                    "[[mods]]\nmodId=\"create\"\nversion=\"0.5.1\"\n",
+                )],
+            ),
+            write_jar(
+                &dir,
+                "create-addition-1.2.jar",
+                &[(
+                    NEOFORGE_METADATA,
+                    "[[mods]]\nmodId=\"createaddition\"\nversion=\"1.2\"\n",
+                )],
+            ),
+            // Unreadable jars fall back to the filename heuristic.
+            write_jar(&dir, "lithium-1.0.jar", &[]),
+            write_jar(&dir, "lithium-1.1.jar", &[]),
+        ];
+
+        let mods: Vec<InstalledMod> = jars.iter().map(|jar| InstalledMod::read(jar)).collect();
+        assert_eq!(
+            duplicate_mods(&mods),
+            [
+                "lithium: lithium-1.0.jar, lithium-1.1.jar",
+                "sodium: renderer-optimizations.jar (0.5.8), sodium-fabric-0.5.3.jar (0.5.3)",
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn version_requirements_follow_fabric_and_maven_syntax() {
+        let fabric = |list: &[&str]| {
+            VersionRequirement::Predicates(list.iter().map(|p| p.to_string()).collect())
+        };
+        assert_eq!(fabric(&[">=1.20 <1.21"]).matches("1.20.1"), Some(true));
+        assert_eq!(fabric(&["~1.20.1"]).matches("1.20.4"), Some(true));
+        assert_eq!(fabric(&["~1.20.1"]).matches("1.21"), Some(false));
+        assert_eq!(fabric(&["1.19.x", "1.20.x"]).matches("1.20.1"), Some(true));
+        assert_eq!(fabric(&["1.19.4"]).matches("1.20.1"), Some(false));
+        assert_eq!(fabric(&["*"]).matches("1.20.1"), Some(true));
+        assert_eq!(fabric(&[">=1.20"]).matches("23w51b"), None);
+
+        let maven = |range: &str| VersionRequirement::MavenRange(range.into());
+        assert_eq!(maven("[1.20.1,1.21)").matches("1.20.1"), Some(true));
+        assert_eq!(maven("[1.20.1,1.21)").matches("1.21"), Some(false));
+        assert_eq!(maven("(1.20.1,]").matches("1.20.1"), Some(false));
+        assert_eq!(maven("[1.19,1.20),[1.20.1,)").matches("1.20.4"), Some(true));
+        assert_eq!(maven("[1.20.1]").matches("1.20.2"), Some(false));
+        assert_eq!(maven("1.20.1").matches("1.19.2"), Some(true));
+    }
+
+    #[test]
+    fn reports_mods_for_other_loaders_and_minecraft_versions() {
+        let dir = temp_dir("mod-metadata-compatibility");
+        let jars = [
+            write_jar(
+                &dir,
+                "sodium.jar",
+                &[(
+                    FABRIC_METADATA,
+                    r#"{"id": "sodium", "depends": {"minecraft": "~1.20.1"}}"#,
+                )],
+            ),
+            write_jar(
+                &dir,
+                "jei.jar",
+                &[(
+                    FORGE_METADATA,
+                    "[[mods]]\nmodId=\"jei\"\n[[dependencies.jei]]\nmodId=\"minecraft\"\nversionRange=\"[1.20.1,1.21)\"\n",
+                )],
+            ),
+            write_jar(
+                &dir,
+                "create.jar",
+                &[(
+                    FORGE_METADATA,
+                    "[[mods]]\nmodId=\"create\"\n[[dependencies.create]]\nmodId=\"minecraft\"\nversionRange=\"[1.19.2,1.19.3)\"\n",
+                )],
+            ),
+            write_jar(
+                &dir,
+                "qsl.jar",
+                &[(
+                    QUILT_METADATA,
+                    r#"{"quilt_loader": {"id": "qsl", "depends": [{"id": "minecraft", "versions": ">=1.20"}]}}"#,
+                )],
+            ),
+            write_jar(&dir, "unknown.jar", &[]),
+        ];
+        let mods: Vec<InstalledMod> = jars.iter().map(|jar| InstalledMod::read(jar)).collect();
+
+        assert_eq!(
+            compatibility_issues(&mods, &LoaderType::Forge, "1.20.1"),
+            [
+                "sodium.jar: es un mod para fabric y la instancia usa forge.",
+                "create.jar: requiere Minecraft [1.19.2,1.19.3) y la instancia usa 1.20.1.",
+                "qsl.jar: es un mod para quilt y la instancia usa forge.",
+            ]
+        );
+        assert_eq!(
+            compatibility_issues(&mods, &LoaderType::Quilt, "1.20.1"),
+            [
+                "jei.jar: es un mod para forge y la instancia usa quilt.",
+                "create.jar: es un mod para forge y la instancia usa quilt.",
+            ]
+        );
+        // NeoForge 1.20.1 still loads Forge's mods.toml.
+        assert_eq!(
+            compatibility_issues(&mods[1..2], &LoaderType::NeoForge, "1.20.1"),
+            Vec::<String>::new()
+        );
+        // Vanilla has no loader to mismatch; only version ranges are reported.
+        assert_eq!(
+            compatibility_issues(&mods, &LoaderType::Vanilla, "1.20.1"),
+            ["create.jar: requiere Minecraft [1.19.2,1.19.3) y la instancia usa 1.20.1."]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
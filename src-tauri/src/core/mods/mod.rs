@@ -0,0 +1,12 @@
+// ─── Mods ───
+// Jars in an instance `mods/` folder: the metadata they declare,
+// enabling/disabling them without deleting anything, fetching the loader's
+// API mod from Modrinth when other mods need it, and updating the mods the
+// launcher installed from there.
+
+pub mod companion;
+pub mod manifest;
+pub mod metadata;
+pub mod modrinth;
+pub mod toggle;
+pub mod updates;
@@ -0,0 +1,202 @@
+// ─── Modrinth API ───
+// The slice of the Modrinth v2 API the launcher needs to fetch single mods:
+// the versions of a project filtered by loader and Minecraft version, the
+// file each version ships, and batched lookups of installed jars by hash.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::net::{self, RetryPolicy};
+
+pub const MODRINTH_API_BASE: &str = "https://api.modrinth.com/v2";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub version_number: String,
+    /// `release`, `beta` or `alpha`.
+    pub version_type: String,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    #[serde(default)]
+    pub date_published: String,
+    #[serde(default)]
+    pub files: Vec<ModrinthVersionFile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthVersionFile {
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+impl ModrinthVersion {
+    /// The file marked primary, or the first one.
+    pub fn primary_file(&self) -> Option<&ModrinthVersionFile> {
+        self.files
+            .iter()
+            .find(|file| file.primary)
+            .or_else(|| self.files.first())
+    }
+}
+
+/// Versions of `project` (slug or id) published for `loader` and
+/// `minecraft_version`.
+pub async fn project_versions(
+    client: &reqwest::Client,
+    project: &str,
+    loader: &str,
+    minecraft_version: &str,
+) -> LauncherResult<Vec<ModrinthVersion>> {
+    let url = format!("{MODRINTH_API_BASE}/project/{project}/version");
+    let loaders = serde_json::to_string(&[loader])?;
+    let game_versions = serde_json::to_string(&[minecraft_version])?;
+    let response = net::send_with_retry(
+        || {
+            client
+                .get(&url)
+                .query(&[("loaders", &loaders), ("game_versions", &game_versions)])
+        },
+        RetryPolicy::default(),
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url,
+            status: response.status().as_u16(),
+        });
+    }
+    Ok(response.json().await?)
+}
+
+/// Hash algorithm the launcher identifies installed jars by.
+pub const FILE_HASH_ALGORITHM: &str = "sha1";
+
+/// Versions whose files have the given SHA-1 `hashes`, keyed by hash.
+/// Hashes Modrinth does not know are missing from the map.
+pub async fn versions_from_hashes(
+    client: &reqwest::Client,
+    hashes: &[String],
+) -> LauncherResult<HashMap<String, ModrinthVersion>> {
+    post_hashes(
+        client,
+        format!("{MODRINTH_API_BASE}/version_files"),
+        serde_json::json!({ "hashes": hashes, "algorithm": FILE_HASH_ALGORITHM }),
+    )
+    .await
+}
+
+/// Newest version for `loader` and `minecraft_version` of the project behind
+/// each of the SHA-1 `hashes`, keyed by hash, in a single request.
+pub async fn latest_versions_from_hashes(
+    client: &reqwest::Client,
+    hashes: &[String],
+    loader: &str,
+    minecraft_version: &str,
+) -> LauncherResult<HashMap<String, ModrinthVersion>> {
+    post_hashes(
+        client,
+        format!("{MODRINTH_API_BASE}/version_files/update"),
+        serde_json::json!({
+            "hashes": hashes,
+            "algorithm": FILE_HASH_ALGORITHM,
+            "loaders": [loader],
+            "game_versions": [minecraft_version],
+        }),
+    )
+    .await
+}
+
+async fn post_hashes(
+    client: &reqwest::Client,
+    url: String,
+    body: serde_json::Value,
+) -> LauncherResult<HashMap<String, ModrinthVersion>> {
+    if body["hashes"].as_array().is_some_and(Vec::is_empty) {
+        return Ok(HashMap::new());
+    }
+    let response =
+        net::send_with_retry(|| client.post(&url).json(&body), RetryPolicy::default()).await?;
+    if !response.status().is_success() {
+        return Err(LauncherError::DownloadFailed {
+            url,
+            status: response.status().as_u16(),
+        });
+    }
+    Ok(response.json().await?)
+}
+
+/// Newest version of a project listing that supports `loader` and
+/// `minecraft_version`, preferring releases over betas and alphas.
+pub fn pick_version<'a>(
+    versions: &'a [ModrinthVersion],
+    loader: &str,
+    minecraft_version: &str,
+) -> Option<&'a ModrinthVersion> {
+    let rank = |version: &ModrinthVersion| match version.version_type.as_str() {
+        "release" => 2,
+        "beta" => 1,
+        _ => 0,
+    };
+    versions
+        .iter()
+        .filter(|version| {
+            version.game_versions.iter().any(|v| v == minecraft_version)
+                && version.loaders.iter().any(|l| l == loader)
+                && version.primary_file().is_some()
+        })
+        .max_by(|a, b| {
+            rank(a)
+                .cmp(&rank(b))
+                .then_with(|| a.date_published.cmp(&b.date_published))
+        })
+}
+
+/// Download `file` into `mods_dir`, checking the SHA-512 Modrinth publishes
+/// (SHA-1 for old uploads). Returns the path written.
+pub async fn download_version_file(
+    downloader: &Downloader,
+    file: &ModrinthVersionFile,
+    mods_dir: &Path,
+) -> LauncherResult<PathBuf> {
+    if file.filename.contains(['/', '\\']) || file.filename.starts_with('.') {
+        return Err(LauncherError::message(
+            ErrorText::InvalidModrinthFileName,
+            &[&file.filename],
+        ));
+    }
+
+    std::fs::create_dir_all(mods_dir)?;
+    let dest = mods_dir.join(&file.filename);
+    match file.hashes.get("sha512") {
+        Some(sha512) => {
+            downloader
+                .download_file_sha512(&file.url, &dest, sha512)
+                .await?
+        }
+        None => {
+            downloader
+                .download_file(
+                    &file.url,
+                    &dest,
+                    file.hashes.get("sha1").map(String::as_str),
+                )
+                .await?
+        }
+    }
+    Ok(dest)
+}
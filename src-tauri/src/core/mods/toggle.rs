@@ -0,0 +1,170 @@
+// ─── Mod Toggle ───
+// Disables a mod by renaming `foo.jar` to `foo.jar.disabled` so it can be
+// brought back later. Everything that scans `mods/` only picks up files with
+// a `.jar` extension, so disabled jars never reach the classpath or analysis.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::metadata::{read_mod_metadata, ModMetadata};
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+/// A mod jar in the `mods/` folder, enabled or not.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModEntry {
+    pub file_name: String,
+    pub enabled: bool,
+    pub metadata: Option<ModMetadata>,
+}
+
+fn is_jar_name(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+}
+
+/// Resolve `file_name` inside `mods_dir`, rejecting anything that is not a
+/// plain file name.
+fn mod_path(mods_dir: &Path, file_name: &str) -> LauncherResult<PathBuf> {
+    if file_name.is_empty() || Path::new(file_name).file_name() != Some(file_name.as_ref()) {
+        return Err(LauncherError::message(
+            ErrorText::InvalidModName,
+            &[&file_name],
+        ));
+    }
+    Ok(mods_dir.join(file_name))
+}
+
+fn rename_mod(from: &Path, to: &Path) -> LauncherResult<()> {
+    if !from.is_file() {
+        return Err(LauncherError::message(
+            ErrorText::ModFileNotFound,
+            &[&from.display()],
+        ));
+    }
+    if to.exists() {
+        return Err(LauncherError::message(
+            ErrorText::FileAlreadyExists,
+            &[&to.display()],
+        ));
+    }
+    std::fs::rename(from, to).map_err(|source| LauncherError::Io {
+        path: from.to_path_buf(),
+        source,
+    })
+}
+
+/// Rename `<file_name>` to `<file_name>.disabled`. Returns the new file name.
+pub fn disable_mod(mods_dir: &Path, file_name: &str) -> LauncherResult<String> {
+    if !is_jar_name(file_name) {
+        return Err(LauncherError::message(
+            ErrorText::OnlyJarsCanBeDisabled,
+            &[&file_name],
+        ));
+    }
+    let disabled = format!("{file_name}{DISABLED_SUFFIX}");
+    rename_mod(
+        &mod_path(mods_dir, file_name)?,
+        &mod_path(mods_dir, &disabled)?,
+    )?;
+    Ok(disabled)
+}
+
+/// Reverse [`disable_mod`]. Accepts either the disabled file name or the
+/// original jar name. Returns the restored file name.
+pub fn enable_mod(mods_dir: &Path, file_name: &str) -> LauncherResult<String> {
+    let enabled = file_name
+        .strip_suffix(DISABLED_SUFFIX)
+        .unwrap_or(file_name)
+        .to_string();
+    if !is_jar_name(&enabled) {
+        return Err(LauncherError::message(
+            ErrorText::NotADisabledMod,
+            &[&file_name],
+        ));
+    }
+    let disabled = format!("{enabled}{DISABLED_SUFFIX}");
+    rename_mod(
+        &mod_path(mods_dir, &disabled)?,
+        &mod_path(mods_dir, &enabled)?,
+    )?;
+    Ok(enabled)
+}
+
+/// Every enabled and disabled jar in `mods_dir`, sorted by file name.
+/// A missing folder yields an empty list.
+pub fn list_mods(mods_dir: &Path) -> LauncherResult<Vec<ModEntry>> {
+    let entries = match std::fs::read_dir(mods_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(LauncherError::Io {
+                path: mods_dir.to_path_buf(),
+                source,
+            })
+        }
+    };
+
+    let mut mods: Vec<ModEntry> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?.to_string();
+            let enabled = match file_name.strip_suffix(DISABLED_SUFFIX) {
+                Some(original) if is_jar_name(original) => false,
+                Some(_) => return None,
+                None if is_jar_name(&file_name) => true,
+                None => return None,
+            };
+            Some(ModEntry {
+                metadata: read_mod_metadata(&path),
+                file_name,
+                enabled,
+            })
+        })
+        .collect();
+    mods.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(mods)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disable_and_enable_round_trip() {
+        let temp = std::env::temp_dir().join(format!("mod-toggle-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&temp);
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("sodium-fabric-0.5.3.jar"), b"mod").unwrap();
+        std::fs::write(temp.join("notes.txt"), b"not-a-mod").unwrap();
+
+        let disabled = disable_mod(&temp, "sodium-fabric-0.5.3.jar").unwrap();
+        assert_eq!(disabled, "sodium-fabric-0.5.3.jar.disabled");
+        assert!(!temp.join("sodium-fabric-0.5.3.jar").exists());
+        assert!(temp.join(&disabled).exists());
+
+        let listed = list_mods(&temp).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].file_name, disabled);
+        assert!(!listed[0].enabled);
+
+        assert!(disable_mod(&temp, "sodium-fabric-0.5.3.jar").is_err());
+        assert!(disable_mod(&temp, "../sodium-fabric-0.5.3.jar").is_err());
+        assert!(enable_mod(&temp, "notes.txt").is_err());
+
+        let enabled = enable_mod(&temp, &disabled).unwrap();
+        assert_eq!(enabled, "sodium-fabric-0.5.3.jar");
+        assert!(temp.join(&enabled).exists());
+        assert!(!temp.join(&disabled).exists());
+        assert!(list_mods(&temp).unwrap()[0].enabled);
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
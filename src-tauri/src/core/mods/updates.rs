@@ -0,0 +1,358 @@
+// ─── Mod Updates ───
+// Compares the mods recorded in `mods.json` with what Modrinth lists for the
+// instance loader and Minecraft version. Every installed jar is looked up by
+// its SHA-1 in one batched request instead of one request per project; jars
+// missing from the manifest are identified by hash first and recorded.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::manifest::{installed_jars, InstalledJar, ModRecord, ModsManifest};
+use super::modrinth::{self, ModrinthVersion};
+use super::toggle;
+use crate::core::downloader::Downloader;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::Instance;
+
+/// A newer version of an installed mod.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModUpdate {
+    /// Jar currently installed.
+    #[serde(rename = "mod")]
+    pub file_name: String,
+    pub project_id: String,
+    /// Installed version number.
+    pub current: String,
+    /// Version number of the update.
+    pub latest: String,
+    pub latest_version_id: String,
+}
+
+/// Update for `record` when `latest`, the newest version Modrinth lists for
+/// its project, supports `loader` and `minecraft_version` and is published
+/// after the installed one. Never offers a downgrade, e.g. from a beta to an
+/// older release.
+pub fn find_update(
+    record: &ModRecord,
+    latest: &ModrinthVersion,
+    loader: &str,
+    minecraft_version: &str,
+) -> Option<ModUpdate> {
+    if latest.id == record.version_id
+        || latest.project_id != record.project_id
+        || latest.primary_file().is_none()
+        || !latest.loaders.iter().any(|l| l == loader)
+        || !latest.game_versions.iter().any(|v| v == minecraft_version)
+    {
+        return None;
+    }
+    // Unknown install date (hand-edited manifest): any other version is an update.
+    if !record.date_published.is_empty()
+        && !published_after(&latest.date_published, &record.date_published)
+    {
+        return None;
+    }
+    Some(ModUpdate {
+        file_name: record.file_name.clone(),
+        project_id: record.project_id.clone(),
+        current: record.version_number.clone(),
+        latest: latest.version_number.clone(),
+        latest_version_id: latest.id.clone(),
+    })
+}
+
+fn published_after(candidate: &str, installed: &str) -> bool {
+    match (
+        chrono::DateTime::parse_from_rfc3339(candidate),
+        chrono::DateTime::parse_from_rfc3339(installed),
+    ) {
+        (Ok(candidate), Ok(installed)) => candidate > installed,
+        _ => candidate > installed,
+    }
+}
+
+/// Record the `jars` Modrinth knows by hash in `manifest`. Returns how many
+/// were recorded.
+pub async fn identify_jars(
+    client: &reqwest::Client,
+    manifest: &mut ModsManifest,
+    jars: &[InstalledJar],
+) -> LauncherResult<usize> {
+    let hashes: Vec<String> = jars.iter().map(|jar| jar.sha1.clone()).collect();
+    let versions = modrinth::versions_from_hashes(client, &hashes).await?;
+    let recorded = jars
+        .iter()
+        .filter(|jar| {
+            versions
+                .get(&jar.sha1)
+                .is_some_and(|version| manifest.record_identified(jar, version))
+        })
+        .count();
+    if recorded > 0 {
+        info!("Identified {} of {} unrecorded mods", recorded, jars.len());
+    }
+    Ok(recorded)
+}
+
+/// Updates available for the `jars` installed in an instance. Brings
+/// `manifest` up to date with them first, so the caller should save it.
+pub async fn check_updates(
+    client: &reqwest::Client,
+    manifest: &mut ModsManifest,
+    jars: &[InstalledJar],
+    loader: &str,
+    minecraft_version: &str,
+) -> LauncherResult<Vec<ModUpdate>> {
+    let unrecorded = manifest.reconcile(jars);
+    if !unrecorded.is_empty() {
+        // Identification is a nicety; the recorded mods can still be checked.
+        if let Err(err) = identify_jars(client, manifest, &unrecorded).await {
+            warn!("Cannot identify {} mods by hash: {}", unrecorded.len(), err);
+        }
+    }
+
+    let records: HashMap<&str, &ModRecord> = manifest
+        .mods
+        .iter()
+        .filter(|record| {
+            !record.sha1.is_empty()
+                && jars
+                    .iter()
+                    .any(|jar| jar.file_name == record.file_name && jar.sha1 == record.sha1)
+        })
+        .map(|record| (record.sha1.as_str(), record))
+        .collect();
+    let hashes: Vec<String> = records.keys().map(|hash| hash.to_string()).collect();
+    let latest =
+        modrinth::latest_versions_from_hashes(client, &hashes, loader, minecraft_version).await?;
+
+    let mut updates: Vec<ModUpdate> = latest
+        .iter()
+        .filter_map(|(hash, version)| {
+            find_update(
+                records.get(hash.as_str())?,
+                version,
+                loader,
+                minecraft_version,
+            )
+        })
+        .collect();
+    updates.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(updates)
+}
+
+/// [`check_updates`] for the jars in the `mods/` folder of `instance`,
+/// saving what it learned about them in `mods.json`.
+pub async fn check_instance_updates(
+    client: &reqwest::Client,
+    instance: &Instance,
+) -> LauncherResult<Vec<ModUpdate>> {
+    let jars = installed_jars_of(instance.mods_dir()).await?;
+    if jars.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut manifest = ModsManifest::load(&instance.path);
+    let before = manifest.clone();
+    let updates = check_updates(
+        client,
+        &mut manifest,
+        &jars,
+        &instance.loader.to_string(),
+        &instance.minecraft_version,
+    )
+    .await?;
+    if manifest != before {
+        manifest.save(&instance.path)?;
+    }
+    Ok(updates)
+}
+
+/// Replace a mod of `instance` with its newest compatible version. The old
+/// jar is removed only after the new one is downloaded, and a disabled mod
+/// stays disabled.
+pub async fn update_instance_mod(
+    client: &reqwest::Client,
+    downloader: &Downloader,
+    instance: &Instance,
+    project_id: &str,
+) -> LauncherResult<ModUpdate> {
+    let mods_dir = instance.mods_dir();
+    let jars = installed_jars_of(mods_dir.clone()).await?;
+    let mut manifest = ModsManifest::load(&instance.path);
+    let unrecorded = manifest.reconcile(&jars);
+    if !unrecorded.is_empty() {
+        identify_jars(client, &mut manifest, &unrecorded).await?;
+    }
+    let record = manifest
+        .get(project_id)
+        .filter(|record| {
+            jars.iter()
+                .any(|jar| jar.file_name == record.file_name && jar.sha1 == record.sha1)
+        })
+        .cloned()
+        .ok_or_else(|| LauncherError::message(ErrorText::ModNotFromModrinth, &[&project_id]))?;
+
+    let loader = instance.loader.to_string();
+    let latest = modrinth::latest_versions_from_hashes(
+        client,
+        std::slice::from_ref(&record.sha1),
+        &loader,
+        &instance.minecraft_version,
+    )
+    .await?;
+    let version = latest
+        .get(&record.sha1)
+        .ok_or_else(|| LauncherError::message(ErrorText::ModUpToDate, &[&record.file_name]))?;
+    let update = find_update(&record, version, &loader, &instance.minecraft_version)
+        .ok_or_else(|| LauncherError::message(ErrorText::ModUpToDate, &[&record.file_name]))?;
+    let file = version.primary_file().ok_or_else(|| {
+        LauncherError::message(
+            ErrorText::VersionWithoutFiles,
+            &[&update.latest, &record.file_name],
+        )
+    })?;
+
+    let disabled_name = format!("{}{}", record.file_name, toggle::DISABLED_SUFFIX);
+    let was_disabled =
+        !mods_dir.join(&record.file_name).is_file() && mods_dir.join(&disabled_name).is_file();
+    let dest = modrinth::download_version_file(downloader, file, &mods_dir).await?;
+    for old in [
+        mods_dir.join(&record.file_name),
+        mods_dir.join(&disabled_name),
+    ] {
+        if old != dest && old.is_file() {
+            std::fs::remove_file(&old).map_err(|source| LauncherError::Io {
+                path: old.clone(),
+                source,
+            })?;
+        }
+    }
+    if was_disabled {
+        toggle::disable_mod(&mods_dir, &file.filename)?;
+    }
+
+    manifest.record(ModRecord::new(version, file));
+    manifest.save(&instance.path)?;
+    info!(
+        "Updated {} in instance {}: {} -> {}",
+        project_id, instance.id, update.current, update.latest
+    );
+    Ok(update)
+}
+
+async fn installed_jars_of(mods_dir: PathBuf) -> LauncherResult<Vec<InstalledJar>> {
+    tokio::task::spawn_blocking(move || installed_jars(&mods_dir))
+        .await
+        .map_err(|e| LauncherError::Other(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: &str, number: &str, kind: &str, mc: &str, date: &str) -> ModrinthVersion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "project_id": "AANobbMI",
+            "version_number": number,
+            "version_type": kind,
+            "game_versions": [mc],
+            "loaders": ["fabric"],
+            "date_published": date,
+            "files": [{ "url": format!("https://cdn.modrinth.com/{id}.jar"),
+                        "filename": format!("sodium-{number}.jar"), "primary": true,
+                        "hashes": { "sha1": format!("sha1-{id}") } }]
+        }))
+        .unwrap()
+    }
+
+    fn installed(id: &str, number: &str, date: &str) -> ModRecord {
+        ModRecord {
+            project_id: "AANobbMI".into(),
+            version_id: id.into(),
+            version_number: number.into(),
+            date_published: date.into(),
+            file_name: format!("sodium-{number}.jar"),
+            sha1: format!("sha1-{id}"),
+        }
+    }
+
+    #[test]
+    fn offers_the_newest_compatible_version_only() {
+        let new = version("new", "0.5.8", "release", "1.20.1", "2024-03-01T00:00:00Z");
+        let old = installed("cur", "0.5.3", "2023-09-01T00:00:00Z");
+
+        let update = find_update(&old, &new, "fabric", "1.20.1").unwrap();
+        assert_eq!(update.file_name, "sodium-0.5.3.jar");
+        assert_eq!(update.current, "0.5.3");
+        assert_eq!(update.latest, "0.5.8");
+        assert_eq!(update.latest_version_id, "new");
+
+        // Already on the newest compatible version.
+        let current = installed("new", "0.5.8", "2024-03-01T00:00:00Z");
+        assert_eq!(find_update(&current, &new, "fabric", "1.20.1"), None);
+        // Nothing for another loader or Minecraft version.
+        assert_eq!(find_update(&old, &new, "quilt", "1.20.1"), None);
+        let other = version("other", "0.6.0", "release", "1.21", "2024-07-01T00:00:00Z");
+        assert_eq!(find_update(&old, &other, "fabric", "1.20.1"), None);
+    }
+
+    #[test]
+    fn never_downgrades_from_a_newer_beta() {
+        let release = version("rel", "0.5.8", "release", "1.20.1", "2024-03-01T00:00:00Z");
+        let on_beta = installed("beta", "0.6.0-beta.1", "2024-05-01T00:00:00Z");
+        assert_eq!(find_update(&on_beta, &release, "fabric", "1.20.1"), None);
+
+        let on_old_release = installed("old", "0.5.3", "2023-09-01T00:00:00Z");
+        assert_eq!(
+            find_update(&on_old_release, &release, "fabric", "1.20.1").map(|update| update.latest),
+            Some("0.5.8".to_string())
+        );
+    }
+
+    #[test]
+    fn unrecorded_and_replaced_jars_are_identified_by_hash() {
+        let jar = |name: &str, sha1: &str| InstalledJar {
+            file_name: name.into(),
+            sha1: sha1.into(),
+        };
+        let mut manifest = ModsManifest::default();
+        manifest.record(installed("cur", "0.5.3", "2023-09-01T00:00:00Z"));
+        manifest.record(ModRecord {
+            project_id: "gvQqBUqZ".into(),
+            file_name: "lithium.jar".into(),
+            sha1: String::new(),
+            ..installed("lith", "0.11.2", "")
+        });
+
+        let jars = [
+            jar("sodium-0.5.3.jar", "sha1-cur"),
+            // Recorded before hashes were kept: takes the one on disk.
+            jar("lithium.jar", "sha1-lith"),
+            // Never recorded, e.g. installed by a modpack.
+            jar("from-pack.jar", "sha1-pack"),
+        ];
+        // Same name as a record, other contents: replaced by hand.
+        let mut replaced = jars.to_vec();
+        replaced[0].sha1 = "sha1-edited".into();
+
+        assert_eq!(manifest.clone().reconcile(&jars), [jars[2].clone()]);
+        assert_eq!(
+            manifest.reconcile(&replaced),
+            [replaced[0].clone(), replaced[2].clone()]
+        );
+        assert_eq!(manifest.get("gvQqBUqZ").unwrap().sha1, "sha1-lith");
+
+        // The pack jar turns out to be a known Sodium build.
+        let pack = version("pack", "0.5.0", "release", "1.20.1", "2023-06-01T00:00:00Z");
+        assert!(!manifest.record_identified(&replaced[0], &pack));
+        assert!(manifest.record_identified(&jar("from-pack.jar", "sha1-pack"), &pack));
+        let sodium = manifest.get("AANobbMI").unwrap();
+        assert_eq!(sodium.file_name, "from-pack.jar");
+        assert_eq!(sodium.version_id, "pack");
+    }
+}
@@ -0,0 +1,184 @@
+// ─── Network Retry ───
+// Shared retry/backoff for metadata and API requests. Connection errors and
+// transient statuses (429, 5xx) are retried with exponential backoff, and a
+// `Retry-After` header on 429/503 overrides the computed delay.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use tracing::warn;
+
+use crate::core::error::{LauncherError, LauncherResult};
+
+/// Longest wait honoured from a `Retry-After` header.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Attempts after the first one.
+    pub retries: u32,
+    /// Delay before the first retry; doubled for every further attempt.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt + 1`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt))
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Delay requested by a 429/503 `Retry-After` header (seconds or HTTP date).
+fn retry_after(response: &Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => (DateTime::parse_from_rfc2822(value)
+            .ok()?
+            .with_timezone(&Utc)
+            - Utc::now())
+        .to_std()
+        .unwrap_or_default(),
+    };
+    Some(delay.min(MAX_RETRY_AFTER))
+}
+
+/// Send the request built by `build`, retrying connection errors and
+/// transient statuses. After the last attempt the final response is returned
+/// as-is (even if unsuccessful) so callers keep their own status handling.
+pub async fn send_with_retry<F>(build: F, policy: RetryPolicy) -> LauncherResult<Response>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        let (delay, reason) = match build().send().await {
+            Ok(response) if attempt < policy.retries && is_transient(response.status()) => {
+                let delay = retry_after(&response).unwrap_or_else(|| policy.backoff(attempt));
+                (delay, response.status().to_string())
+            }
+            Ok(response) => return Ok(response),
+            Err(err) if attempt < policy.retries => (policy.backoff(attempt), err.to_string()),
+            Err(err) => return Err(LauncherError::Http(err)),
+        };
+
+        attempt += 1;
+        warn!(
+            "Request failed ({}); retrying in {:?} (attempt {}/{})",
+            reason, delay, attempt, policy.retries
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// GET `url` with the default retry policy.
+pub async fn get_with_retry(client: &reqwest::Client, url: &str) -> LauncherResult<Response> {
+    send_with_retry(|| client.get(url), RetryPolicy::default()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{response, serve};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serves the given responses in order (repeating the last one) and
+    /// counts requests.
+    async fn serve_in_order(responses: Vec<Vec<u8>>) -> (String, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let served = count.clone();
+        let base = serve(move |_| {
+            let index = served.fetch_add(1, Ordering::SeqCst);
+            let reply = responses[index.min(responses.len() - 1)].clone();
+            async move { reply }
+        })
+        .await;
+        (format!("{base}/meta.json"), count)
+    }
+
+    fn ok() -> Vec<u8> {
+        response("200 OK", &[], b"ok")
+    }
+
+    fn fast() -> RetryPolicy {
+        RetryPolicy {
+            retries: 3,
+            base_delay: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_after_success() {
+        let (url, count) = serve_in_order(vec![response("502 Bad Gateway", &[], b""), ok()]).await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(|| client.get(&url), fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn honours_retry_after_header() {
+        let (url, count) = serve_in_order(vec![
+            response(
+                "503 Service Unavailable",
+                &[("Retry-After", "1".into())],
+                b"",
+            ),
+            ok(),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let started = std::time::Instant::now();
+        let response = send_with_retry(|| client.get(&url), fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn returns_last_response_when_retries_run_out() {
+        let (url, count) =
+            serve_in_order(vec![response("503 Service Unavailable", &[], b"")]).await;
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(|| client.get(&url), fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+}
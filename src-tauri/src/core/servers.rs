@@ -0,0 +1,200 @@
+// ─── Server List ───
+// Reads and writes the multiplayer list (`servers.dat`, uncompressed NBT) in
+// an instance's game directory. Entries are edited in place so tags the
+// launcher does not know about (`icon`, `acceptTextures`, …) survive.
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use quartz_nbt::io::{self as nbt_io, Flavor};
+use quartz_nbt::{NbtCompound, NbtList, NbtTag};
+use serde::Serialize;
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+const SERVERS_FILE: &str = "servers.dat";
+const SERVERS_KEY: &str = "servers";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServerEntry {
+    pub name: String,
+    pub address: String,
+    pub has_icon: bool,
+    pub accept_textures: Option<bool>,
+}
+
+impl ServerEntry {
+    fn from_compound(compound: &NbtCompound) -> Self {
+        Self {
+            name: compound
+                .get::<_, &str>("name")
+                .unwrap_or_default()
+                .to_string(),
+            address: compound
+                .get::<_, &str>("ip")
+                .unwrap_or_default()
+                .to_string(),
+            has_icon: compound.contains_key("icon"),
+            accept_textures: compound
+                .get::<_, i8>("acceptTextures")
+                .ok()
+                .map(|value| value != 0),
+        }
+    }
+}
+
+pub fn servers_dat_path(game_dir: &Path) -> PathBuf {
+    game_dir.join(SERVERS_FILE)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> LauncherError {
+    LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn write_servers_file(path: &Path, root: &NbtCompound) -> LauncherResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| io_error(parent, source))?;
+    }
+
+    let mut bytes = Vec::new();
+    nbt_io::write_nbt(&mut bytes, None, root, Flavor::Uncompressed)?;
+    // The game may be reading the list, so it never sees a truncated file.
+    atomic_file::write(path, bytes).map_err(|source| io_error(path, source))
+}
+
+/// Load `servers.dat`; a missing file reads as an empty list and is only
+/// written once a server is added.
+fn load_servers_file(game_dir: &Path) -> LauncherResult<NbtCompound> {
+    let path = servers_dat_path(game_dir);
+    if !path.exists() {
+        let mut root = NbtCompound::new();
+        root.insert(SERVERS_KEY, NbtList::new());
+        return Ok(root);
+    }
+
+    let file = File::open(&path).map_err(|source| io_error(&path, source))?;
+    let (mut root, _) = nbt_io::read_nbt(&mut BufReader::new(file), Flavor::Uncompressed)?;
+    if !root.contains_key(SERVERS_KEY) {
+        root.insert(SERVERS_KEY, NbtList::new());
+    }
+    Ok(root)
+}
+
+fn servers_list(root: &NbtCompound) -> LauncherResult<&NbtList> {
+    root.get::<_, &NbtList>(SERVERS_KEY)
+        .map_err(|e| LauncherError::message(ErrorText::InvalidServersDat, &[&e]))
+}
+
+fn servers_list_mut(root: &mut NbtCompound) -> LauncherResult<&mut NbtList> {
+    root.get_mut::<_, &mut NbtList>(SERVERS_KEY)
+        .map_err(|e| LauncherError::message(ErrorText::InvalidServersDat, &[&e]))
+}
+
+fn entries(root: &NbtCompound) -> LauncherResult<Vec<ServerEntry>> {
+    Ok(servers_list(root)?
+        .iter()
+        .filter_map(|tag| match tag {
+            NbtTag::Compound(compound) => Some(ServerEntry::from_compound(compound)),
+            _ => None,
+        })
+        .collect())
+}
+
+pub fn list_servers(game_dir: &Path) -> LauncherResult<Vec<ServerEntry>> {
+    entries(&load_servers_file(game_dir)?)
+}
+
+/// Append a server to the list and return the updated entries.
+pub fn add_server(game_dir: &Path, name: &str, address: &str) -> LauncherResult<Vec<ServerEntry>> {
+    let name = name.trim();
+    let address = address.trim();
+    if name.is_empty() || address.is_empty() {
+        return Err(LauncherError::message(ErrorText::ServerNameRequired, &[]));
+    }
+
+    let mut root = load_servers_file(game_dir)?;
+    let mut server = NbtCompound::new();
+    server.insert("name", name);
+    server.insert("ip", address);
+    servers_list_mut(&mut root)?.push(server);
+
+    write_servers_file(&servers_dat_path(game_dir), &root)?;
+    entries(&root)
+}
+
+/// Remove the server at `index` and return the updated entries.
+pub fn remove_server(game_dir: &Path, index: usize) -> LauncherResult<Vec<ServerEntry>> {
+    let mut root = load_servers_file(game_dir)?;
+    let servers = servers_list_mut(&mut root)?;
+    if index >= servers.len() {
+        return Err(LauncherError::message(ErrorText::ServerNotFound, &[&index]));
+    }
+    servers.inner_mut().remove(index);
+
+    write_servers_file(&servers_dat_path(game_dir), &root)?;
+    entries(&root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_file_lists_empty_without_creating_it() {
+        let temp = std::env::temp_dir().join(format!("servers-empty-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+
+        assert!(list_servers(&temp).unwrap().is_empty());
+        assert!(!servers_dat_path(&temp).exists());
+        assert!(!temp.exists());
+
+        add_server(&temp, "Local", "localhost").unwrap();
+        assert!(servers_dat_path(&temp).exists());
+        assert_eq!(list_servers(&temp).unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn add_and_remove_preserve_unknown_tags() {
+        let temp = std::env::temp_dir().join(format!("servers-roundtrip-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+
+        let mut existing = NbtCompound::new();
+        existing.insert("name", "Hypixel");
+        existing.insert("ip", "mc.hypixel.net");
+        existing.insert("icon", "iVBORw0KGgo=");
+        existing.insert("acceptTextures", 1i8);
+        let mut list = NbtList::new();
+        list.push(existing);
+        let mut root = NbtCompound::new();
+        root.insert(SERVERS_KEY, list);
+        write_servers_file(&servers_dat_path(&temp), &root).unwrap();
+
+        add_server(&temp, " Local ", "localhost:25565").unwrap();
+
+        let servers = list_servers(&temp).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[1].name, "Local");
+        assert_eq!(servers[1].address, "localhost:25565");
+        assert!(servers[0].has_icon);
+        assert_eq!(servers[0].accept_textures, Some(true));
+
+        let root = load_servers_file(&temp).unwrap();
+        let first = servers_list(&root).unwrap().get::<&NbtCompound>(0).unwrap();
+        assert_eq!(first.get::<_, &str>("icon").unwrap(), "iVBORw0KGgo=");
+
+        let remaining = remove_server(&temp, 0).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "Local");
+        assert!(remove_server(&temp, 5).is_err());
+
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,458 @@
+// ─── Desktop Shortcuts ───
+// Launcher and per-instance shortcuts. Linux gets a `.desktop` entry in
+// `~/.local/share/applications` (plus a copy on the desktop when there is
+// one), macOS a small `.app` bundle in `~/Applications` with an alias on the
+// desktop, Windows a `.bat` on the desktop. Instance shortcuts pass the
+// launch URI (`interface://launch/<id>`) so the launcher starts that
+// instance directly.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::deep_link::launch_uri;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+use crate::core::instance::Instance;
+
+/// Icon formats desktop environments can show.
+const ICON_EXTENSIONS: [&str; 4] = ["png", "svg", "xpm", "icns"];
+
+/// Arguments a desktop entry must quote (Desktop Entry spec, "Exec key").
+const EXEC_RESERVED: [char; 19] = [
+    ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')',
+    '`',
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Name shown on the desktop and in menus.
+    pub name: String,
+    /// File name without extension, e.g. `interface-launcher`.
+    pub file_stem: String,
+    pub exec: PathBuf,
+    pub args: Vec<String>,
+    pub working_dir: Option<PathBuf>,
+    pub icon: Option<PathBuf>,
+}
+
+impl Shortcut {
+    /// Shortcut that opens the launcher.
+    pub fn launcher(data_dir: &Path) -> LauncherResult<Self> {
+        let exec = launcher_executable()?;
+        Ok(Self {
+            name: "Interface Launcher".into(),
+            file_stem: "interface-launcher".into(),
+            icon: launcher_icon(&exec),
+            exec,
+            args: Vec::new(),
+            working_dir: Some(data_dir.to_path_buf()),
+        })
+    }
+
+    /// Shortcut that starts `instance` through the launch URI.
+    pub fn instance(instance: &Instance) -> LauncherResult<Self> {
+        let exec = launcher_executable()?;
+        let icon = instance
+            .icon_path()
+            .and_then(|path| validate_icon(&path))
+            .or_else(|| launcher_icon(&exec));
+        Ok(Self {
+            name: format!("{} (Interface)", instance.name.trim()),
+            file_stem: format!("interface-instance-{}", file_safe(&instance.id)),
+            exec,
+            args: vec![launch_uri(&instance.id)],
+            working_dir: Some(instance.path.clone()),
+            icon,
+        })
+    }
+}
+
+/// `path` if it is an absolute path to an existing image a desktop can use.
+pub fn validate_icon(path: &Path) -> Option<PathBuf> {
+    let usable = path.is_absolute()
+        && path.is_file()
+        && !path.to_string_lossy().chars().any(char::is_control)
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                ICON_EXTENSIONS
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            });
+    usable.then(|| path.to_path_buf())
+}
+
+/// Write the shortcut where the platform expects it; returns the files created.
+pub fn create_shortcut(shortcut: &Shortcut) -> LauncherResult<Vec<PathBuf>> {
+    let fields = [
+        shortcut.name.clone(),
+        shortcut.exec.to_string_lossy().into_owned(),
+    ];
+    if fields
+        .iter()
+        .chain(&shortcut.args)
+        .any(|field| field.trim().is_empty() || field.chars().any(char::is_control))
+    {
+        return Err(LauncherError::message(
+            ErrorText::InvalidShortcut,
+            &[&shortcut.name],
+        ));
+    }
+    install(shortcut)
+}
+
+/// Contents of a freedesktop `.desktop` entry for `shortcut`.
+pub fn desktop_entry(shortcut: &Shortcut) -> String {
+    let exec = std::iter::once(shortcut.exec.to_string_lossy().into_owned())
+        .chain(shortcut.args.iter().cloned())
+        .map(|arg| desktop_exec_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut entry = String::from("[Desktop Entry]\nType=Application\nVersion=1.0\n");
+    entry.push_str(&format!("Name={}\n", desktop_string(&shortcut.name)));
+    entry.push_str(&format!("Exec={exec}\n"));
+    if let Some(dir) = &shortcut.working_dir {
+        entry.push_str(&format!(
+            "Path={}\n",
+            desktop_string(&dir.to_string_lossy())
+        ));
+    }
+    if let Some(icon) = &shortcut.icon {
+        entry.push_str(&format!(
+            "Icon={}\n",
+            desktop_string(&icon.to_string_lossy())
+        ));
+    }
+    entry.push_str("Terminal=false\nCategories=Game;\n");
+    entry
+}
+
+/// Escape a value of type string (`\`, newlines and tabs).
+fn desktop_string(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+/// One `Exec` argument: `%` doubled, quoted when it holds a reserved
+/// character, then escaped as a string value.
+fn desktop_exec_arg(arg: &str) -> String {
+    let arg = arg.replace('%', "%%");
+    if !arg.is_empty() && !arg.contains(EXEC_RESERVED) {
+        return arg;
+    }
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    desktop_string(&quoted)
+}
+
+/// POSIX shell single-quoting.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Keep ids to characters every filesystem accepts in a file name.
+fn file_safe(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Executable to put in shortcuts. An AppImage runs from a temporary mount,
+/// so the image itself is used instead.
+fn launcher_executable() -> LauncherResult<PathBuf> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE").map(PathBuf::from) {
+        if appimage.is_file() {
+            return Ok(appimage);
+        }
+    }
+    Ok(std::env::current_exe()?)
+}
+
+/// Icon installed with the launcher: the hicolor icon of the Linux packages
+/// or the bundle icon on macOS.
+fn launcher_icon(exec: &Path) -> Option<PathBuf> {
+    let stem = exec.file_stem()?.to_string_lossy().into_owned();
+    let prefix = exec.parent()?.parent()?;
+    [
+        prefix
+            .join("share/icons/hicolor/128x128/apps")
+            .join(format!("{stem}.png")),
+        prefix.join("share/pixmaps").join(format!("{stem}.png")),
+        prefix.join("Resources/icon.icns"),
+    ]
+    .iter()
+    .find_map(|candidate| validate_icon(candidate))
+}
+
+#[cfg(unix)]
+fn write_executable(path: &Path, contents: &str) -> LauncherResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let io_error = |source| LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    std::fs::write(path, contents).map_err(io_error)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).map_err(io_error)
+}
+
+#[cfg(target_os = "linux")]
+fn install(shortcut: &Shortcut) -> LauncherResult<Vec<PathBuf>> {
+    let applications = dirs::data_dir()
+        .ok_or_else(|| LauncherError::message(ErrorText::ApplicationsDirNotFound, &[]))?
+        .join("applications");
+    let mut targets = vec![applications];
+    targets.extend(dirs::desktop_dir().filter(|dir| dir.is_dir()));
+
+    let entry = desktop_entry(shortcut);
+    let mut written = Vec::new();
+    for dir in targets {
+        std::fs::create_dir_all(&dir).map_err(|source| LauncherError::Io {
+            path: dir.clone(),
+            source,
+        })?;
+        let path = dir.join(format!("{}.desktop", shortcut.file_stem));
+        write_executable(&path, &entry)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(target_os = "macos")]
+fn install(shortcut: &Shortcut) -> LauncherResult<Vec<PathBuf>> {
+    let home =
+        dirs::home_dir().ok_or_else(|| LauncherError::message(ErrorText::HomeDirNotFound, &[]))?;
+    let bundle_name = format!("{}.app", shortcut.name.replace(['/', ':'], "-"));
+    let bundle = home.join("Applications").join(&bundle_name);
+    let contents = bundle.join("Contents");
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| LauncherError::Io { path, source }
+    };
+    for dir in [contents.join("MacOS"), contents.join("Resources")] {
+        std::fs::create_dir_all(&dir).map_err(io_error(&dir))?;
+    }
+
+    let mut script = String::from("#!/bin/sh\n");
+    if let Some(dir) = &shortcut.working_dir {
+        script.push_str(&format!(
+            "cd {} || exit 1\n",
+            shell_quote(&dir.to_string_lossy())
+        ));
+    }
+    script.push_str("exec");
+    for arg in std::iter::once(shortcut.exec.to_string_lossy().into_owned())
+        .chain(shortcut.args.iter().cloned())
+    {
+        script.push(' ');
+        script.push_str(&shell_quote(&arg));
+    }
+    script.push('\n');
+    write_executable(&contents.join("MacOS").join(&shortcut.file_stem), &script)?;
+
+    let mut icon_entry = String::new();
+    if let Some(icon) = &shortcut.icon {
+        let ext = icon
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+        let target = contents.join("Resources").join(format!("icon.{ext}"));
+        std::fs::copy(icon, &target).map_err(io_error(&target))?;
+        icon_entry = format!("  <key>CFBundleIconFile</key><string>icon.{ext}</string>\n");
+    }
+    let plist = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n\
+         \x20 <key>CFBundleName</key><string>{name}</string>\n\
+         \x20 <key>CFBundleExecutable</key><string>{stem}</string>\n\
+         \x20 <key>CFBundleIdentifier</key><string>com.arthur.interface.{stem}</string>\n\
+         \x20 <key>CFBundlePackageType</key><string>APPL</string>\n\
+         {icon_entry}</dict>\n</plist>\n",
+        name = xml_escape(&shortcut.name),
+        stem = xml_escape(&shortcut.file_stem),
+    );
+    let plist_path = contents.join("Info.plist");
+    std::fs::write(&plist_path, plist).map_err(io_error(&plist_path))?;
+
+    let mut written = vec![bundle.clone()];
+    if let Some(desktop) = dirs::desktop_dir().filter(|dir| dir.is_dir()) {
+        let alias = desktop.join(&bundle_name);
+        if alias.is_symlink() {
+            let _ = std::fs::remove_file(&alias);
+        }
+        if !alias.exists() {
+            std::os::unix::fs::symlink(&bundle, &alias).map_err(io_error(&alias))?;
+            written.push(alias);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(target_os = "macos")]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(target_os = "windows")]
+fn install(shortcut: &Shortcut) -> LauncherResult<Vec<PathBuf>> {
+    let desktop = dirs::desktop_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let path = desktop.join(format!(
+        "{}.bat",
+        shortcut.name.replace(['\\', '/', ':'], "-")
+    ));
+    let mut command = format!("start \"\" \"{}\"", shortcut.exec.display());
+    for arg in &shortcut.args {
+        command.push_str(&format!(" \"{}\"", arg.replace('"', "")));
+    }
+    let mut content = String::from("@echo off\r\n");
+    if let Some(dir) = &shortcut.working_dir {
+        content.push_str(&format!("cd /d \"{}\"\r\n", dir.display()));
+    }
+    content.push_str(&format!("{command}\r\n"));
+    std::fs::write(&path, content).map_err(|source| LauncherError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(vec![path])
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn install(_shortcut: &Shortcut) -> LauncherResult<Vec<PathBuf>> {
+    Err(LauncherError::message(ErrorText::ShortcutsUnsupported, &[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut() -> Shortcut {
+        Shortcut {
+            name: "Survival 1.20 (Interface)".into(),
+            file_stem: "interface-instance-survival".into(),
+            exec: PathBuf::from("/opt/Interface Launcher/interface"),
+            args: vec![launch_uri("survival")],
+            working_dir: Some(PathBuf::from(
+                "/home/ana/InterfaceOficial/instances/survival",
+            )),
+            icon: Some(PathBuf::from(
+                "/home/ana/InterfaceOficial/instances/survival/icon.png",
+            )),
+        }
+    }
+
+    #[test]
+    fn desktop_entry_launches_the_instance_uri() {
+        assert_eq!(
+            desktop_entry(&shortcut()),
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Version=1.0\n\
+             Name=Survival 1.20 (Interface)\n\
+             Exec=\"/opt/Interface Launcher/interface\" interface://launch/survival\n\
+             Path=/home/ana/InterfaceOficial/instances/survival\n\
+             Icon=/home/ana/InterfaceOficial/instances/survival/icon.png\n\
+             Terminal=false\n\
+             Categories=Game;\n"
+        );
+
+        let mut bare = shortcut();
+        bare.working_dir = None;
+        bare.icon = None;
+        let entry = desktop_entry(&bare);
+        assert!(!entry.contains("Path="));
+        assert!(!entry.contains("Icon="));
+    }
+
+    #[test]
+    fn exec_arguments_are_escaped() {
+        assert_eq!(desktop_exec_arg("/usr/bin/interface"), "/usr/bin/interface");
+        assert_eq!(desktop_exec_arg("100%"), "100%%");
+        assert_eq!(desktop_exec_arg("$HOME/a b"), "\"\\\\$HOME/a b\"");
+        assert_eq!(desktop_exec_arg(r"C:\x"), "\"C:\\\\\\\\x\"");
+        assert_eq!(desktop_exec_arg(""), "\"\"");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn only_existing_absolute_images_are_used_as_icons() {
+        let dir = std::env::temp_dir().join(format!("shortcut-icons-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let png = dir.join("icon.png");
+        let text = dir.join("icon.txt");
+        std::fs::write(&png, b"png").unwrap();
+        std::fs::write(&text, b"txt").unwrap();
+
+        assert_eq!(validate_icon(&png), Some(png.clone()));
+        assert_eq!(validate_icon(&text), None);
+        assert_eq!(validate_icon(&dir.join("missing.png")), None);
+        assert_eq!(validate_icon(Path::new("icon.png")), None);
+        assert_eq!(file_safe("mi instancia/1"), "mi_instancia_1");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn instance_shortcut_launches_from_a_closed_launcher() {
+        use crate::core::deep_link::forwarded_deep_link;
+        use crate::core::events::NoopEmitter;
+        use crate::core::instance::LoaderType;
+        use crate::core::launcher::Launcher;
+        use std::sync::Arc;
+
+        let data_dir = std::env::temp_dir().join(format!("shortcut-cold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let launcher = Launcher::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+        let instance = {
+            let state = launcher.state();
+            let state = state.lock().await;
+            let instance = Instance::new(
+                "Survival".into(),
+                "1.20.1".into(),
+                LoaderType::Vanilla,
+                None,
+                2048,
+                &state.instances_dir(),
+            );
+            state.instance_manager.create(instance).await.unwrap()
+        };
+
+        // The OS starts a new launcher process with the shortcut's command
+        // line; `setup` handles its URI before the window listens.
+        let shortcut = Shortcut::instance(&instance).unwrap();
+        let argv: Vec<String> = std::iter::once(shortcut.exec.to_string_lossy().into_owned())
+            .chain(shortcut.args.iter().cloned())
+            .collect();
+        let uri = forwarded_deep_link(&argv).unwrap();
+        launcher.open_deep_link(&uri).await.unwrap();
+
+        // The window picks the request up once it mounts.
+        let pending = launcher.take_pending_deep_link().await.unwrap();
+        assert_eq!(pending.id, instance.id);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
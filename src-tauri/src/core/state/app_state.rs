@@ -1,19 +1,42 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tauri::Manager;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
 
-use crate::core::downloader::Downloader;
+use crate::core::atomic_file;
+use crate::core::auth::accounts::AccountBook;
+use crate::core::auth::profile::ProfileCache;
+use crate::core::deep_link::LaunchRequest;
+use crate::core::downloader::{default_max_concurrent_downloads, Downloader};
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::events::EventEmitter;
 use crate::core::http::build_http_client;
+use crate::core::i18n::{ErrorText, Locale};
+use crate::core::instance::transfer::copy_symlink;
 use crate::core::instance::InstanceManager;
 use crate::core::java;
+use crate::core::long_path::long_path;
+use crate::core::metadata_cache::MetadataCache;
+use crate::core::mirrors::DownloadMirrors;
+use crate::core::shortcuts::{create_shortcut, Shortcut};
+
+use super::setup::{SetupProgress, SetupRequest, SetupStep, SETUP_PROGRESS_EVENT};
+use super::LaunchCancellations;
 
 const APP_DIR_NAME: &str = "InterfaceOficial";
 const BOOTSTRAP_FILE: &str = "launcher_bootstrap.json";
 const INSTALL_MARKER_FILE: &str = "launcher_installation.json";
+const SETUP_FILE: &str = "launcher_setup.json";
+/// Java major fetched by the optional setup step; current releases need it.
+const SETUP_JAVA_MAJOR: u32 = 21;
+const SETTINGS_FILE: &str = "launcher_settings.json";
+/// Shape of `launcher_settings.json`. Files without the field are version 0.
+const SETTINGS_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -25,8 +48,107 @@ pub enum JavaRuntimePreference {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherSettings {
+    /// Version of the file this was loaded from; see `migrate_settings`.
+    #[serde(default)]
+    pub schema_version: u32,
     pub java_runtime: JavaRuntimePreference,
     pub selected_java_path: Option<PathBuf>,
+    /// Parallel downloads allowed across libraries, assets and loaders.
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// Global switch for per-instance pre-launch / post-exit hooks.
+    #[serde(default = "default_hooks_enabled")]
+    pub hooks_enabled: bool,
+    /// On arm64 hosts, allow x64 Java runtimes under Rosetta 2 / box64 when
+    /// no native runtime exists. Off by default because emulation is slow.
+    #[serde(default)]
+    pub allow_x64_java_emulation: bool,
+    /// Accept any Java newer than required (e.g. 17 for 1.16.5) for Vanilla,
+    /// Fabric and Quilt instead of the exact 8/17/21 track.
+    #[serde(default)]
+    pub allow_newer_java_major: bool,
+    /// Seconds a game gets to exit after a stop request before it is killed.
+    #[serde(default = "default_stop_grace_seconds")]
+    pub stop_grace_seconds: u64,
+    /// Seconds a pre-launch / post-exit hook may run before it is killed.
+    #[serde(default = "default_hook_timeout_seconds")]
+    pub hook_timeout_seconds: u64,
+    /// Mirror hosts for the manifest, libraries, assets and Adoptium.
+    #[serde(default)]
+    pub download_mirrors: DownloadMirrors,
+    /// Repair passes before a launch with transient preflight failures
+    /// (missing libraries, corrupted files, ...) gives up. 0 disables them.
+    #[serde(default = "default_preflight_repair_attempts")]
+    pub preflight_repair_attempts: u32,
+    /// Try one automatic runtime fix when preflight finds the loader and the
+    /// assigned Java incompatible.
+    #[serde(default = "default_auto_fix_loader_java")]
+    pub auto_fix_loader_java: bool,
+    /// Language of backend logs and fallback texts in events.
+    #[serde(default)]
+    pub language: Locale,
+    /// Download asset objects once into `<data_dir>/assets` and hardlink them
+    /// into each instance instead of keeping a full copy per instance.
+    #[serde(default)]
+    pub shared_assets: bool,
+    /// Managed runtimes kept per Java major after an install; at least 1.
+    #[serde(default = "default_runtime_keep_per_major")]
+    pub runtime_keep_per_major: usize,
+    /// Re-hash the key files of managed runtimes before using them and
+    /// reinstall the ones that changed.
+    #[serde(default)]
+    pub verify_runtime_integrity: bool,
+    /// Vendor of newly downloaded managed runtimes; Temurin is the fallback.
+    #[serde(default)]
+    pub runtime_vendor: java::runtime::RuntimeVendor,
+    /// Cap on the combined download rate of the launcher; `None` is unlimited.
+    #[serde(default)]
+    pub max_download_bytes_per_sec: Option<u64>,
+    /// Size at which a game log under `logs/launcher` is rotated.
+    #[serde(default = "default_max_log_bytes")]
+    pub max_log_bytes: u64,
+    /// Rotated game logs kept per instance.
+    #[serde(default = "default_log_rotations")]
+    pub log_rotations: usize,
+    /// Keys this build does not know, e.g. written by a newer launcher.
+    /// Kept so saving the settings does not drop them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_hooks_enabled() -> bool {
+    true
+}
+
+fn default_auto_fix_loader_java() -> bool {
+    true
+}
+
+pub const DEFAULT_PREFLIGHT_REPAIR_ATTEMPTS: u32 = 2;
+pub const MAX_PREFLIGHT_REPAIR_ATTEMPTS: u32 = 10;
+
+fn default_preflight_repair_attempts() -> u32 {
+    DEFAULT_PREFLIGHT_REPAIR_ATTEMPTS
+}
+
+fn default_stop_grace_seconds() -> u64 {
+    crate::core::launch::DEFAULT_STOP_GRACE_SECS
+}
+
+fn default_hook_timeout_seconds() -> u64 {
+    crate::core::launch::DEFAULT_HOOK_TIMEOUT_SECS
+}
+
+fn default_max_log_bytes() -> u64 {
+    crate::core::launch::DEFAULT_MAX_LOG_BYTES
+}
+
+fn default_log_rotations() -> usize {
+    crate::core::launch::DEFAULT_LOG_ROTATIONS
+}
+
+fn default_runtime_keep_per_major() -> usize {
+    java::runtime::DEFAULT_RUNTIME_KEEP_PER_MAJOR
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,22 +161,76 @@ struct InstallationMarker {
     initialized: bool,
 }
 
+impl LauncherSettings {
+    /// The runtime settings, for Java resolution and downloads.
+    pub fn runtime_options(&self) -> java::RuntimeOptions {
+        java::RuntimeOptions {
+            vendor: self.runtime_vendor,
+            keep_per_major: self.runtime_keep_per_major.max(1),
+            allow_x64_emulation: self.allow_x64_java_emulation,
+            verify_integrity: self.verify_runtime_integrity,
+            mirrors: self.download_mirrors.clone(),
+            throttle: None,
+        }
+    }
+}
+
 impl Default for LauncherSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             java_runtime: JavaRuntimePreference::Auto,
             selected_java_path: None,
+            max_concurrent_downloads: default_max_concurrent_downloads(),
+            hooks_enabled: default_hooks_enabled(),
+            allow_x64_java_emulation: false,
+            allow_newer_java_major: false,
+            stop_grace_seconds: default_stop_grace_seconds(),
+            hook_timeout_seconds: default_hook_timeout_seconds(),
+            download_mirrors: DownloadMirrors::default(),
+            preflight_repair_attempts: default_preflight_repair_attempts(),
+            auto_fix_loader_java: default_auto_fix_loader_java(),
+            language: Locale::default(),
+            shared_assets: false,
+            runtime_keep_per_major: default_runtime_keep_per_major(),
+            verify_runtime_integrity: false,
+            runtime_vendor: java::runtime::RuntimeVendor::default(),
+            max_download_bytes_per_sec: None,
+            max_log_bytes: default_max_log_bytes(),
+            log_rotations: default_log_rotations(),
+            extra: serde_json::Map::new(),
         }
     }
 }
 
+/// A game process started by the launcher.
+#[derive(Debug, Clone)]
+pub struct RunningInstance {
+    pub pid: u32,
+    /// Natives session directory of this launch, removed when it exits.
+    pub natives_dir: PathBuf,
+    /// Java binary the game runs on.
+    pub java_bin: PathBuf,
+}
+
 pub struct AppState {
     pub data_dir: PathBuf,
     pub instance_manager: InstanceManager,
     pub downloader: Arc<Downloader>,
     pub http_client: Client,
-    pub running_instances: HashMap<String, u32>,
+    pub running_instances: HashMap<String, RunningInstance>,
     pub launcher_settings: LauncherSettings,
+    /// Saved accounts, persisted in `accounts.json`.
+    pub accounts: AccountBook,
+    /// Skin and cape lookups, with their own lock so a slow session server
+    /// doesn't hold the state one.
+    pub profile_cache: Arc<Mutex<ProfileCache>>,
+    /// Deep link launch waiting for the frontend, see `take_pending_deep_link`.
+    pub pending_deep_link: Option<LaunchRequest>,
+    /// Shared with Tauri's managed state so `cancel_launch` bypasses the state lock.
+    pub launch_cancellations: Arc<LaunchCancellations>,
+    /// Holds the bootstrap file and the setup progress, outside any data dir.
+    pub bootstrap_dir: PathBuf,
 }
 
 impl AppState {
@@ -70,14 +246,27 @@ impl AppState {
                 }
             }
         }
-        let _ = tauri::async_runtime::block_on(java::ensure_embedded_runtime_registered(&data_dir));
+        tauri::async_runtime::block_on(Self::open(data_dir, Arc::new(app_handle)))
+    }
+
+    /// Build the state for `data_dir` without a Tauri app; progress events
+    /// go to `events`.
+    pub async fn open(data_dir: PathBuf, events: Arc<dyn EventEmitter>) -> Self {
+        let _ = java::ensure_embedded_runtime_registered(&data_dir).await;
         let instances_dir = data_dir.join("instances");
         let instance_manager = InstanceManager::new(instances_dir);
 
         let http_client = build_http_client().expect("Failed to build HTTP client");
 
-        let downloader = Arc::new(Downloader::new(Some(app_handle)));
-        let launcher_settings = load_settings_from_disk(&data_dir).unwrap_or_default();
+        let launcher_settings = load_settings_from_disk(&data_dir);
+        let accounts = AccountBook::load(&data_dir);
+        let profile_cache = Arc::new(Mutex::new(ProfileCache::load(&data_dir)));
+        let downloader = Arc::new(
+            Downloader::new(Some(events))
+                .with_concurrency(launcher_settings.max_concurrent_downloads)
+                .with_mirrors(launcher_settings.download_mirrors.clone())
+                .with_max_bytes_per_sec(launcher_settings.max_download_bytes_per_sec),
+        );
 
         Self {
             data_dir,
@@ -86,6 +275,30 @@ impl AppState {
             http_client,
             running_instances: HashMap::new(),
             launcher_settings,
+            accounts,
+            profile_cache,
+            pending_deep_link: None,
+            launch_cancellations: Arc::new(LaunchCancellations::default()),
+            bootstrap_dir: default_base_dir(),
+        }
+    }
+
+    /// Copy for a long install that runs without the state lock. It shares
+    /// the downloader and cancellations; running instances and accounts are
+    /// left out.
+    pub fn detached(&self) -> Self {
+        Self {
+            data_dir: self.data_dir.clone(),
+            instance_manager: self.instance_manager.clone(),
+            downloader: self.downloader.clone(),
+            http_client: self.http_client.clone(),
+            running_instances: HashMap::new(),
+            launcher_settings: self.launcher_settings.clone(),
+            accounts: AccountBook::default(),
+            profile_cache: self.profile_cache.clone(),
+            pending_deep_link: None,
+            launch_cancellations: self.launch_cancellations.clone(),
+            bootstrap_dir: self.bootstrap_dir.clone(),
         }
     }
 
@@ -93,10 +306,34 @@ impl AppState {
         self.data_dir.join("libraries")
     }
 
+    /// Shared asset store used when `shared_assets` is on.
     pub fn assets_dir(&self) -> PathBuf {
         self.data_dir.join("assets")
     }
 
+    /// Persisted game output, one rotating file per instance.
+    pub fn launcher_logs_dir(&self) -> PathBuf {
+        self.data_dir.join("logs").join("launcher")
+    }
+
+    /// Last successful metadata responses, used as an offline fallback.
+    /// `LauncherSettings::runtime_options` plus the downloader's bandwidth cap.
+    pub fn runtime_options(&self) -> java::RuntimeOptions {
+        java::RuntimeOptions {
+            throttle: Some(self.downloader.throttle()),
+            ..self.launcher_settings.runtime_options()
+        }
+    }
+
+    /// Manager for the Java runtimes of the current data dir.
+    pub fn runtime_manager(&self) -> LauncherResult<java::runtime::RuntimeManager> {
+        java::runtime::RuntimeManager::for_data_dir(&self.data_dir)
+    }
+
+    pub fn metadata_cache(&self) -> MetadataCache {
+        MetadataCache::new(self.data_dir.join("cache").join("metadata"))
+    }
+
     pub fn instances_dir(&self) -> PathBuf {
         self.data_dir.join("instances")
     }
@@ -110,48 +347,26 @@ impl AppState {
     }
 
     pub fn save_settings(&self) -> std::io::Result<()> {
-        let settings_path = self.data_dir.join("launcher_settings.json");
-        let json = serde_json::to_string_pretty(&self.launcher_settings)?;
-        std::fs::write(settings_path, json)
+        write_settings(&self.data_dir, &self.launcher_settings)
     }
 
     pub fn is_first_launch(&self) -> bool {
         !self.data_dir.join(INSTALL_MARKER_FILE).exists()
     }
 
-    pub fn initialize_launcher_installation(
-        &mut self,
-        app_handle: &tauri::AppHandle,
-        target_dir: PathBuf,
-        create_shortcut: bool,
-    ) -> std::io::Result<PathBuf> {
-        let destination = normalize_install_dir(target_dir);
-
-        std::fs::create_dir_all(&destination)?;
-        std::fs::create_dir_all(destination.join("instances"))?;
-        std::fs::create_dir_all(destination.join("libraries"))?;
-        std::fs::create_dir_all(destination.join("assets"))?;
-
-        self.data_dir = destination.clone();
-        self.instance_manager = InstanceManager::new(self.instances_dir());
-        self.launcher_settings = load_settings_from_disk(&self.data_dir).unwrap_or_default();
-
-        self.install_embedded_runtime(app_handle)?;
-        let _ = tauri::async_runtime::block_on(java::ensure_embedded_runtime_registered(
-            &self.data_dir,
-        ));
-        self.save_settings()?;
-        self.save_install_marker()?;
-        save_bootstrap_config(&self.data_dir)?;
-
-        if create_shortcut {
-            let _ = create_desktop_shortcut(app_handle, &self.data_dir);
-        }
+    /// Where the first-launch wizard keeps its progress.
+    fn setup_progress_path(&self) -> PathBuf {
+        self.bootstrap_dir.join(SETUP_FILE)
+    }
 
-        Ok(destination)
+    /// Saved progress of the first-launch wizard.
+    pub fn setup_progress(&self) -> SetupProgress {
+        SetupProgress::load(&self.setup_progress_path())
     }
 
-    pub fn reinstall_launcher(&mut self, app_handle: &tauri::AppHandle) -> std::io::Result<()> {
+    /// Wipe the data dir and set it up again. `resource_dir` holds the
+    /// bundled runtime.
+    pub fn reinstall_launcher(&mut self, resource_dir: Option<&Path>) -> std::io::Result<()> {
         if self.data_dir.exists() {
             std::fs::remove_dir_all(&self.data_dir)?;
         }
@@ -163,19 +378,36 @@ impl AppState {
 
         self.running_instances.clear();
         self.launcher_settings = LauncherSettings::default();
+        self.accounts = AccountBook::default();
+        self.apply_launcher_settings();
         self.instance_manager = InstanceManager::new(self.instances_dir());
 
-        self.install_embedded_runtime(app_handle)?;
+        self.install_embedded_runtime(resource_dir)?;
         let _ = tauri::async_runtime::block_on(java::ensure_embedded_runtime_registered(
             &self.data_dir,
         ));
         self.save_settings()?;
         self.save_install_marker()?;
-        save_bootstrap_config(&self.data_dir)?;
+        save_bootstrap_config(&self.bootstrap_dir, &self.data_dir)?;
+        // Progress of an earlier wizard run belongs to the wiped install.
+        match std::fs::remove_file(self.setup_progress_path()) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
         Ok(())
     }
 
-    pub fn migrate_data_dir(&mut self, target_dir: PathBuf) -> std::io::Result<PathBuf> {
+    /// Copy the data dir under `target_dir` and make it the one used from
+    /// now on. Refused while `INTERFACE_DATA_DIR` is set: it would keep
+    /// pointing the next start at the old dir.
+    pub fn migrate_data_dir(&mut self, target_dir: PathBuf) -> LauncherResult<PathBuf> {
+        if let Some(dir) = java::paths::data_dir_override() {
+            return Err(LauncherError::message(
+                ErrorText::DataDirOverridden,
+                &[&dir.display()],
+            ));
+        }
+
         let destination = if target_dir
             .file_name()
             .map(|n| n.to_string_lossy() == APP_DIR_NAME)
@@ -190,32 +422,109 @@ impl AppState {
             return Ok(destination);
         }
 
-        std::fs::create_dir_all(&destination)?;
-        copy_dir_recursive(&self.data_dir, &destination)?;
+        self.copy_data_dir_to(&destination)
+            .and_then(|()| save_bootstrap_config(&self.bootstrap_dir, &destination))
+            .map_err(|e| LauncherError::message(ErrorText::MigrationFailed, &[&e]))?;
 
-        let bootstrap = BootstrapConfig {
-            data_dir: destination.clone(),
-        };
-        let bootstrap_json = serde_json::to_string_pretty(&bootstrap)?;
-        std::fs::write(default_base_dir().join(BOOTSTRAP_FILE), bootstrap_json)?;
+        Ok(destination)
+    }
+
+    /// Copy everything under the data dir to `destination` and switch to it.
+    fn copy_data_dir_to(&mut self, destination: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(destination)?;
+        copy_dir_recursive(&long_path(&self.data_dir), &long_path(destination))?;
+        #[cfg(not(unix))]
+        if self.launcher_settings.shared_assets {
+            warn!(
+                "Shared assets were copied per instance into {:?}; deduplicate assets to link them again",
+                destination
+            );
+        }
 
-        self.data_dir = destination.clone();
+        self.data_dir = destination.to_path_buf();
         self.instance_manager = InstanceManager::new(self.instances_dir());
-        self.launcher_settings = load_settings_from_disk(&self.data_dir).unwrap_or_default();
-        self.save_settings()?;
+        self.launcher_settings = load_settings_from_disk(&self.data_dir);
+        self.accounts = AccountBook::load(&self.data_dir);
+        self.apply_launcher_settings();
+        self.save_settings()
+    }
 
-        Ok(destination)
+    /// Repoint what still refers to `old_dir` after `migrate_data_dir`.
+    ///
+    /// The resolved Java caches hold canonical paths into the old dir, so
+    /// they are cleared and rebuilt on the next resolution. Instance paths
+    /// and Java overrides inside the old dir move with it, or are cleared
+    /// when their target did not come along.
+    pub async fn relocate_data_dir_references(&mut self, old_dir: &Path) -> LauncherResult<()> {
+        java::runtime::clear_runtime_caches_in_dir(&self.data_dir).await?;
+        java::ensure_embedded_runtime_registered(&self.data_dir).await?;
+
+        let data_dir = self.data_dir.clone();
+        let old_dirs = [
+            old_dir.to_path_buf(),
+            std::fs::canonicalize(old_dir).unwrap_or_else(|_| old_dir.to_path_buf()),
+        ];
+        let relocate = |path: &Path| -> Option<Option<PathBuf>> {
+            let relative = old_dirs
+                .iter()
+                .find_map(|old| path.strip_prefix(old).ok())?;
+            let moved = data_dir.join(relative);
+            Some(moved.exists().then_some(moved))
+        };
+
+        if let Some(moved) = self
+            .launcher_settings
+            .selected_java_path
+            .as_deref()
+            .and_then(&relocate)
+        {
+            self.launcher_settings.selected_java_path = moved;
+            self.save_settings()?;
+        }
+
+        let instances_dir = self.instances_dir();
+        for mut instance in self.instance_manager.list().await? {
+            let mut changed = false;
+            if relocate(&instance.path).is_some() {
+                instance.path = instances_dir.join(&instance.id);
+                changed = true;
+            }
+            if let Some(moved) = instance.java_path.as_deref().and_then(&relocate) {
+                info!(
+                    "Java override of instance {} moved to {:?}",
+                    instance.id, moved
+                );
+                instance.java_path = moved;
+                changed = true;
+            }
+            if changed {
+                self.instance_manager.save(&instance).await?;
+            }
+        }
+        Ok(())
     }
 }
 
 impl AppState {
-    fn install_embedded_runtime(&self, app_handle: &tauri::AppHandle) -> std::io::Result<()> {
+    /// Push settings that live outside `AppState` into their subsystems.
+    fn apply_launcher_settings(&self) {
+        self.downloader
+            .set_max_concurrency(self.launcher_settings.max_concurrent_downloads);
+        self.downloader
+            .throttle()
+            .set_max_bytes_per_sec(self.launcher_settings.max_download_bytes_per_sec);
+        self.downloader
+            .set_mirrors(self.launcher_settings.download_mirrors.clone());
+    }
+
+    /// Copy the runtime bundled in `resource_dir` into the data dir.
+    fn install_embedded_runtime(&self, resource_dir: Option<&Path>) -> std::io::Result<()> {
         let embedded_runtime = self.data_dir.join("runtime");
         if embedded_runtime.exists() {
             std::fs::remove_dir_all(&embedded_runtime)?;
         }
 
-        if let Some(resource_dir) = app_handle.path().resource_dir().ok() {
+        if let Some(resource_dir) = resource_dir {
             let bundled_runtime = resource_dir.join("runtime");
             if bundled_runtime.exists() {
                 std::fs::create_dir_all(&embedded_runtime)?;
@@ -234,10 +543,108 @@ impl AppState {
     }
 }
 
-fn load_settings_from_disk(data_dir: &PathBuf) -> Option<LauncherSettings> {
-    let path = data_dir.join("launcher_settings.json");
-    let raw = std::fs::read_to_string(path).ok()?;
-    serde_json::from_str(&raw).ok()
+/// Language of the settings in `data_dir`, read without opening the state,
+/// for text printed before the launcher is up.
+pub fn configured_locale(data_dir: &Path) -> Locale {
+    std::fs::read_to_string(data_dir.join(SETTINGS_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|settings| serde_json::from_value(settings.get("language")?.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Read `settings.json` from `data_dir`. A missing file gives the defaults;
+/// an unreadable one is moved aside to a `.bak` file first, so the next save
+/// does not overwrite what the user had.
+fn load_settings_from_disk(data_dir: &Path) -> LauncherSettings {
+    let path = data_dir.join(SETTINGS_FILE);
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return LauncherSettings::default();
+    };
+    let parsed = serde_json::from_str(&raw).and_then(migrate_settings);
+    let (settings, migrated) = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            match atomic_file::set_aside(&path) {
+                Ok(backup) => warn!(
+                    "Unreadable {} ({}); moved it to {}",
+                    path.display(),
+                    err,
+                    backup.display()
+                ),
+                Err(rename_err) => warn!(
+                    "Unreadable {} ({}) and cannot move it aside: {}",
+                    path.display(),
+                    err,
+                    rename_err
+                ),
+            }
+            return LauncherSettings::default();
+        }
+    };
+
+    if migrated {
+        match write_settings(data_dir, &settings) {
+            Ok(()) => info!(
+                "Migrated {} to schema v{}",
+                path.display(),
+                SETTINGS_SCHEMA_VERSION
+            ),
+            Err(err) => warn!("Could not rewrite migrated {}: {}", path.display(), err),
+        }
+    }
+    settings
+}
+
+/// Writes at least the current schema version; a file loaded from a newer
+/// launcher keeps its version, and its unknown keys ride along in `extra`.
+fn write_settings(data_dir: &std::path::Path, settings: &LauncherSettings) -> std::io::Result<()> {
+    let current = LauncherSettings {
+        schema_version: settings.schema_version.max(SETTINGS_SCHEMA_VERSION),
+        ..settings.clone()
+    };
+    let json = serde_json::to_string_pretty(&current)?;
+    atomic_file::write(&data_dir.join(SETTINGS_FILE), json)
+}
+
+/// Upgrade a raw settings file to `SETTINGS_SCHEMA_VERSION`, one step per
+/// version. Returns the settings and whether anything had to be upgraded.
+/// Files from a newer launcher are read as they are and never rewritten.
+fn migrate_settings(mut value: serde_json::Value) -> serde_json::Result<(LauncherSettings, bool)> {
+    let Some(fields) = value.as_object_mut() else {
+        return Err(serde::de::Error::custom("expected a JSON object"));
+    };
+    let version = fields
+        .get("schema_version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0);
+
+    if version < 1 {
+        migrate_settings_v0(fields);
+    }
+
+    let migrated = version < u64::from(SETTINGS_SCHEMA_VERSION);
+    if migrated {
+        fields.insert("schema_version".into(), SETTINGS_SCHEMA_VERSION.into());
+    }
+    let settings = serde_json::from_value(value)?;
+    Ok((settings, migrated))
+}
+
+/// v0 files were written by whatever launcher build created them, so keys
+/// added since may be missing and early builds wrote `null` for unset
+/// values. Fill both from the defaults so the rewritten file is complete.
+fn migrate_settings_v0(fields: &mut serde_json::Map<String, serde_json::Value>) {
+    let Ok(serde_json::Value::Object(defaults)) = serde_json::to_value(LauncherSettings::default())
+    else {
+        return;
+    };
+    for (key, default) in defaults {
+        let missing = fields.get(&key).is_none_or(serde_json::Value::is_null);
+        if missing {
+            fields.insert(key, default);
+        }
+    }
 }
 
 fn default_base_dir() -> PathBuf {
@@ -256,55 +663,163 @@ fn normalize_install_dir(target_dir: PathBuf) -> PathBuf {
     }
 }
 
-fn save_bootstrap_config(data_dir: &PathBuf) -> std::io::Result<()> {
+fn save_bootstrap_config(bootstrap_dir: &Path, data_dir: &Path) -> std::io::Result<()> {
     let bootstrap = BootstrapConfig {
-        data_dir: data_dir.clone(),
+        data_dir: data_dir.to_path_buf(),
     };
     let bootstrap_json = serde_json::to_string_pretty(&bootstrap)?;
-    std::fs::write(default_base_dir().join(BOOTSTRAP_FILE), bootstrap_json)
+    atomic_file::write(&bootstrap_dir.join(BOOTSTRAP_FILE), bootstrap_json)
 }
 
-fn create_desktop_shortcut(
-    _app_handle: &tauri::AppHandle,
-    _data_dir: &PathBuf,
-) -> std::io::Result<()> {
-    let desktop_dir = dirs::desktop_dir()
-        .or_else(dirs::home_dir)
-        .unwrap_or_else(|| PathBuf::from("."));
+/// Run one step of the first-launch wizard and persist the outcome, so a
+/// failed step is retried on its own, even after a restart. `resource_dir`
+/// holds the bundled runtime. The Java download of `PredownloadJava` runs
+/// without holding the state lock.
+pub async fn run_setup_step(
+    state: &Mutex<AppState>,
+    events: &dyn EventEmitter,
+    resource_dir: Option<&Path>,
+    step: SetupStep,
+    request: &SetupRequest,
+) -> LauncherResult<SetupProgress> {
+    let path = state.lock().await.setup_progress_path();
+    let mut progress = SetupProgress::load(&path);
+    progress.begin(step)?;
 
-    #[cfg(target_os = "windows")]
-    {
-        let launcher_exe = std::env::current_exe()?;
-        let shortcut_path = desktop_dir.join("Interface Launcher.bat");
-        let content = format!("@echo off\r\nstart \"\" \"{}\"\r\n", launcher_exe.display());
-        std::fs::write(shortcut_path, content)?;
-    }
+    events.emit(SETUP_PROGRESS_EVENT, progress.event(step, "running"));
+    let result = perform_setup_step(state, resource_dir, step, request, &mut progress).await;
+    progress.finish(step, result.as_ref().err());
+    progress.save(&path)?;
 
-    #[cfg(target_os = "linux")]
-    {
-        let launcher_exe = std::env::current_exe()?;
-        let shortcut_path = desktop_dir.join("interface-launcher.desktop");
-        let content = format!(
-            "[Desktop Entry]\nType=Application\nName=Interface Launcher\nExec={}\nPath={}\nTerminal=false\n",
-            launcher_exe.display(),
-            _data_dir.display()
-        );
-        std::fs::write(&shortcut_path, content)?;
+    match result {
+        Ok(()) => {
+            info!("Setup step {:?} completed", step);
+            events.emit(SETUP_PROGRESS_EVENT, progress.event(step, "done"));
+            Ok(progress)
+        }
+        Err(err) => {
+            warn!("Setup step {:?} failed: {}", step, err);
+            events.emit(SETUP_PROGRESS_EVENT, progress.event(step, "error"));
+            Err(err)
+        }
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        let launcher_exe = std::env::current_exe()?;
-        let shortcut_path = desktop_dir.join("Interface Launcher.command");
-        let content = format!("#!/bin/bash\n\"{}\"\n", launcher_exe.display());
-        std::fs::write(&shortcut_path, content)?;
+/// Run the whole wizard from the start in one go, without the Java
+/// download: the one-shot installation of the first-launch screen.
+pub async fn install_launcher(
+    state: &Mutex<AppState>,
+    events: &dyn EventEmitter,
+    resource_dir: Option<&Path>,
+    target_dir: PathBuf,
+    create_desktop_shortcut: bool,
+) -> LauncherResult<SetupProgress> {
+    let path = state.lock().await.setup_progress_path();
+    SetupProgress::default().save(&path)?;
+    let request = SetupRequest {
+        target_dir: Some(target_dir.to_string_lossy().to_string()),
+        create_desktop_shortcut,
+        skip_java: true,
+    };
+    let mut progress = SetupProgress::default();
+    for step in SetupStep::ALL {
+        progress = run_setup_step(state, events, resource_dir, step, &request).await?;
     }
+    Ok(progress)
+}
 
+async fn perform_setup_step(
+    state: &Mutex<AppState>,
+    resource_dir: Option<&Path>,
+    step: SetupStep,
+    request: &SetupRequest,
+    progress: &mut SetupProgress,
+) -> LauncherResult<()> {
+    match step {
+        SetupStep::ChooseDir => {
+            let mut state = state.lock().await;
+            let target = match request.target_dir.as_deref().map(str::trim) {
+                Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+                _ => state.data_dir.clone(),
+            };
+            let destination = normalize_install_dir(target);
+            java::paths::ensure_writable_dir(&destination)?;
+
+            state.data_dir = destination.clone();
+            state.instance_manager = InstanceManager::new(state.instances_dir());
+            state.launcher_settings = load_settings_from_disk(&state.data_dir);
+            state.accounts = AccountBook::load(&state.data_dir);
+            state.apply_launcher_settings();
+            save_bootstrap_config(&state.bootstrap_dir, &state.data_dir)?;
+
+            progress.data_dir = Some(destination);
+            progress.create_desktop_shortcut = request.create_desktop_shortcut;
+        }
+        SetupStep::CreateLayout => {
+            let state = state.lock().await;
+            if let Some(chosen) = progress
+                .data_dir
+                .as_ref()
+                .filter(|dir| **dir != state.data_dir)
+            {
+                return Err(LauncherError::Other(format!(
+                    "La carpeta activa no coincide con la elegida ({}); vuelve a iniciar el launcher",
+                    chosen.display()
+                )));
+            }
+            std::fs::create_dir_all(state.instances_dir())?;
+            std::fs::create_dir_all(state.libraries_dir())?;
+            std::fs::create_dir_all(state.assets_dir())?;
+            state.install_embedded_runtime(resource_dir)?;
+            state.save_settings()?;
+            if progress.create_desktop_shortcut {
+                if let Err(err) = Shortcut::launcher(&state.data_dir)
+                    .and_then(|shortcut| create_shortcut(&shortcut))
+                {
+                    warn!("Cannot create the desktop shortcut: {}", err);
+                }
+            }
+        }
+        SetupStep::RegisterRuntime => {
+            let state = state.lock().await;
+            java::ensure_embedded_runtime_registered(&state.data_dir).await?;
+        }
+        SetupStep::PredownloadJava => {
+            if request.skip_java {
+                info!("Java predownload skipped during setup");
+            } else {
+                let (data_dir, options) = {
+                    let state = state.lock().await;
+                    (state.data_dir.clone(), state.runtime_options())
+                };
+                java::resolve_runtime_in_dir(
+                    &data_dir,
+                    java::RuntimeRole::Gamma,
+                    SETUP_JAVA_MAJOR,
+                    None,
+                    options,
+                )
+                .await?;
+            }
+        }
+        SetupStep::Done => state.lock().await.save_install_marker()?,
+    }
     Ok(())
 }
 
-fn default_data_dir() -> PathBuf {
-    let base = default_base_dir();
+/// Data dir from `INTERFACE_DATA_DIR`, else the one chosen by the user
+/// (bootstrap file), else the per-OS default.
+pub fn default_data_dir() -> PathBuf {
+    data_dir_from(java::paths::data_dir_override(), &default_base_dir())
+}
+
+/// [`default_data_dir`] for an explicit `override_dir` and bootstrap `base`.
+fn data_dir_from(override_dir: Option<PathBuf>, base: &Path) -> PathBuf {
+    if let Some(dir) = override_dir {
+        let _ = std::fs::create_dir_all(&dir);
+        return dir;
+    }
+
     let bootstrap_path = base.join(BOOTSTRAP_FILE);
 
     if let Ok(raw) = std::fs::read_to_string(&bootstrap_path) {
@@ -325,10 +840,45 @@ fn default_data_dir() -> PathBuf {
     dir
 }
 
-fn copy_dir_recursive(
-    source: &std::path::Path,
-    destination: &std::path::Path,
-) -> std::io::Result<()> {
+/// Copies of the files under the data dir that have more than one link,
+/// so a file hardlinked twice (the shared asset store and an instance) is
+/// copied once and linked again in the new dir. Link counts are only
+/// readable on Unix; elsewhere every link becomes its own copy.
+#[derive(Default)]
+struct HardLinks {
+    #[cfg(unix)]
+    copied: HashMap<(u64, u64), PathBuf>,
+}
+
+impl HardLinks {
+    fn copy(&mut self, source: &Path, destination: &Path) -> std::io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            let metadata = std::fs::metadata(source)?;
+            if metadata.nlink() > 1 {
+                let key = (metadata.dev(), metadata.ino());
+                if let Some(first) = self.copied.get(&key) {
+                    return std::fs::hard_link(first, destination);
+                }
+                std::fs::copy(source, destination)?;
+                self.copied.insert(key, destination.to_path_buf());
+                return Ok(());
+            }
+        }
+
+        std::fs::copy(source, destination).map(|_| ())
+    }
+}
+
+/// Copy `source` into `destination`, recreating symlinks as links and
+/// keeping hardlinked files linked (see [`HardLinks`]).
+fn copy_dir_recursive(source: &Path, destination: &Path) -> std::io::Result<()> {
+    copy_tree(source, destination, &mut HardLinks::default())
+}
+
+fn copy_tree(source: &Path, destination: &Path, hard_links: &mut HardLinks) -> std::io::Result<()> {
     for entry in std::fs::read_dir(source)? {
         let entry = entry?;
         let src_path = entry.path();
@@ -337,14 +887,380 @@ fn copy_dir_recursive(
 
         if file_type.is_dir() {
             std::fs::create_dir_all(&dst_path)?;
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_tree(&src_path, &dst_path, hard_links)?;
+        } else if file_type.is_symlink() {
+            if dst_path.symlink_metadata().is_ok() {
+                std::fs::remove_file(&dst_path)?;
+            }
+            copy_symlink(&src_path, &dst_path)?;
         } else if file_type.is_file() {
-            if dst_path.exists() {
+            if dst_path.symlink_metadata().is_ok() {
                 std::fs::remove_file(&dst_path)?;
             }
-            std::fs::copy(&src_path, &dst_path)?;
+            hard_links.copy(&src_path, &dst_path)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::NoopEmitter;
+
+    #[test]
+    fn data_dir_override_roots_every_derived_path() {
+        let root = std::env::temp_dir().join(format!("data-dir-override-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let base = root.join("base");
+        let chosen = root.join("chosen");
+        let dir = root.join("override");
+
+        // Without an override or a bootstrap file, the per-OS default.
+        assert_eq!(data_dir_from(None, &base), base.join(APP_DIR_NAME));
+        save_bootstrap_config(&base, &chosen).unwrap();
+        assert_eq!(data_dir_from(None, &base), chosen);
+        // The override wins over the directory chosen in the wizard.
+        let data_dir = data_dir_from(Some(dir.clone()), &base);
+        assert_eq!(data_dir, dir);
+        java::paths::ensure_writable_dir(&data_dir).unwrap();
+
+        for derived in [
+            java::managed_runtime_dir(&data_dir, 17),
+            java::paths::RuntimePaths::under(&data_dir)
+                .unwrap()
+                .app_data_dir()
+                .to_path_buf(),
+        ] {
+            assert!(
+                derived.starts_with(&dir) || derived.starts_with(dir.canonicalize().unwrap()),
+                "{derived:?}"
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn each_state_resolves_runtimes_in_its_own_data_dir() {
+        let root = std::env::temp_dir().join(format!("runtime-data-dirs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let first = AppState::open(root.join("first"), Arc::new(NoopEmitter)).await;
+        // Opening a second state no longer moves the first one's runtimes.
+        let _second = AppState::open(root.join("second"), Arc::new(NoopEmitter)).await;
+
+        let diagnostic = first
+            .runtime_manager()
+            .unwrap()
+            .diagnostics()
+            .await
+            .unwrap();
+        let expected = root.join("first").canonicalize().unwrap();
+        assert_eq!(Path::new(&diagnostic.app_data_dir), expected);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn setup_steps_run_in_order_and_reinstalling_resets_them() {
+        let root = std::env::temp_dir().join(format!("setup-steps-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let bootstrap_dir = root.join("bootstrap");
+        std::fs::create_dir_all(&bootstrap_dir).unwrap();
+        let mut state = AppState::open(root.join("default"), Arc::new(NoopEmitter)).await;
+        state.bootstrap_dir = bootstrap_dir.clone();
+        let state = Mutex::new(state);
+        let request = SetupRequest {
+            target_dir: Some(root.join("chosen").to_string_lossy().to_string()),
+            create_desktop_shortcut: false,
+            skip_java: true,
+        };
+
+        // Steps cannot be skipped.
+        let early = run_setup_step(
+            &state,
+            &NoopEmitter,
+            None,
+            SetupStep::CreateLayout,
+            &request,
+        )
+        .await;
+        assert!(early.is_err());
+
+        for step in SetupStep::ALL {
+            run_setup_step(&state, &NoopEmitter, None, step, &request)
+                .await
+                .unwrap();
+        }
+        let chosen = root.join("chosen").join(APP_DIR_NAME);
+        let mut state = state.into_inner();
+        assert_eq!(state.data_dir, chosen);
+        assert!(state.setup_progress().is_complete());
+        assert!(!state.is_first_launch());
+        assert!(chosen.join("instances").is_dir());
+        let bootstrap: BootstrapConfig =
+            serde_json::from_slice(&std::fs::read(bootstrap_dir.join(BOOTSTRAP_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(bootstrap.data_dir, chosen);
+
+        // Reinstalling blocks on the runtime registration, so it runs off
+        // the test's runtime.
+        let state = tokio::task::spawn_blocking(move || {
+            state.reinstall_launcher(None).unwrap();
+            state
+        })
+        .await
+        .unwrap();
+        assert_eq!(state.setup_progress(), SetupProgress::default());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn migrated_data_dir_keeps_managed_runtimes_resolvable() {
+        use crate::core::instance::{Instance, LoaderType};
+        use crate::core::test_support::fake_java;
+
+        let root = std::env::temp_dir().join(format!("data-dir-migration-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let old_dir = root.join("old").join(APP_DIR_NAME);
+        let new_dir = root.join("new").join(APP_DIR_NAME);
+
+        // A bundled Java 17, registered as a managed runtime on open.
+        fake_java(&old_dir.join("runtime").join("bin").join("java"), "17.0.8");
+
+        let mut state = AppState::open(old_dir.clone(), Arc::new(NoopEmitter)).await;
+        let resolved = java::resolve_runtime_in_dir(
+            &old_dir,
+            java::RuntimeRole::Gamma,
+            17,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        assert!(resolved.starts_with(old_dir.canonicalize().unwrap()));
+
+        let mut instance = Instance::new(
+            "Pinned".into(),
+            "1.20.1".into(),
+            LoaderType::Vanilla,
+            None,
+            2048,
+            &state.instances_dir(),
+        );
+        instance.java_path = Some(resolved);
+        let instance = state.instance_manager.create(instance).await.unwrap();
+
+        state.copy_data_dir_to(&new_dir).unwrap();
+        state.relocate_data_dir_references(&old_dir).await.unwrap();
+
+        // The old dir is still there, so a stale cache would resolve into it.
+        let new_root = new_dir.canonicalize().unwrap();
+        let resolved = java::resolve_runtime_in_dir(
+            &new_dir,
+            java::RuntimeRole::Gamma,
+            17,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        assert!(resolved.starts_with(&new_root), "{resolved:?}");
+
+        let moved = state.instance_manager.load(&instance.id).await.unwrap();
+        assert_eq!(moved.path, new_dir.join("instances").join(&instance.id));
+        let java_path = moved.java_path.unwrap().canonicalize().unwrap();
+        assert!(java_path.starts_with(&new_root), "{java_path:?}");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn data_dir_copy_keeps_symlinks_and_hardlinks() {
+        use std::os::unix::fs::MetadataExt;
+
+        let root = std::env::temp_dir().join(format!("data-dir-links-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let old_dir = root.join("old");
+        let new_dir = root.join("new");
+        let store = old_dir.join("assets").join("objects").join("ab");
+        let instance_assets = old_dir.join("instances").join("a").join("assets");
+        std::fs::create_dir_all(&store).unwrap();
+        std::fs::create_dir_all(&instance_assets).unwrap();
+        std::fs::write(store.join("abcd"), b"object").unwrap();
+        std::fs::hard_link(store.join("abcd"), instance_assets.join("abcd")).unwrap();
+        std::os::unix::fs::symlink("../assets", old_dir.join("instances").join("shared")).unwrap();
+        std::fs::create_dir_all(&new_dir).unwrap();
+
+        copy_dir_recursive(&old_dir, &new_dir).unwrap();
+
+        let stored = std::fs::metadata(new_dir.join("assets/objects/ab/abcd")).unwrap();
+        let linked = std::fs::metadata(new_dir.join("instances/a/assets/abcd")).unwrap();
+        assert_eq!(stored.ino(), linked.ino());
+        assert_eq!(stored.nlink(), 2);
+        let shared = new_dir.join("instances").join("shared");
+        assert!(shared.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_link(&shared).unwrap(), Path::new("../assets"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn v0_settings_migrate_to_the_current_shape() {
+        let v0 = serde_json::json!({
+            "java_runtime": "system",
+            "selected_java_path": "/opt/java/bin/java",
+            "max_concurrent_downloads": null,
+        });
+
+        let (settings, migrated) = migrate_settings(v0).unwrap();
+        assert!(migrated);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        // Values present in the old file survive the migration.
+        assert!(matches!(
+            settings.java_runtime,
+            JavaRuntimePreference::System
+        ));
+        assert_eq!(
+            settings.selected_java_path,
+            Some(PathBuf::from("/opt/java/bin/java"))
+        );
+        // Missing and null fields take their defaults.
+        let defaults = LauncherSettings::default();
+        assert_eq!(
+            settings.max_concurrent_downloads,
+            defaults.max_concurrent_downloads
+        );
+        assert!(settings.hooks_enabled);
+        assert!(settings.auto_fix_loader_java);
+        assert_eq!(settings.stop_grace_seconds, defaults.stop_grace_seconds);
+        assert_eq!(settings.hook_timeout_seconds, defaults.hook_timeout_seconds);
+        assert_eq!(
+            settings.preflight_repair_attempts,
+            DEFAULT_PREFLIGHT_REPAIR_ATTEMPTS
+        );
+        assert_eq!(
+            settings.max_log_bytes,
+            crate::core::launch::DEFAULT_MAX_LOG_BYTES
+        );
+        assert_eq!(
+            settings.log_rotations,
+            crate::core::launch::DEFAULT_LOG_ROTATIONS
+        );
+
+        // An empty v0 file is just the defaults.
+        let (empty, migrated) = migrate_settings(serde_json::json!({})).unwrap();
+        assert!(migrated);
+        assert!(matches!(empty.java_runtime, JavaRuntimePreference::Auto));
+        assert_eq!(empty.selected_java_path, None);
+    }
+
+    #[test]
+    fn current_and_newer_files_are_not_migrated() {
+        let current = serde_json::to_value(LauncherSettings::default()).unwrap();
+        let (_, migrated) = migrate_settings(current).unwrap();
+        assert!(!migrated);
+
+        let mut newer = serde_json::to_value(LauncherSettings::default()).unwrap();
+        newer["schema_version"] = (SETTINGS_SCHEMA_VERSION + 1).into();
+        let (settings, migrated) = migrate_settings(newer).unwrap();
+        assert!(!migrated);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION + 1);
+
+        assert!(migrate_settings(serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn saving_keeps_unknown_keys_and_newer_versions() {
+        let dir = std::env::temp_dir().join(format!("settings-newer-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+
+        let mut newer = serde_json::to_value(LauncherSettings::default()).unwrap();
+        newer["schema_version"] = (SETTINGS_SCHEMA_VERSION + 1).into();
+        newer["future_option"] = serde_json::json!({ "enabled": true });
+        std::fs::write(&path, newer.to_string()).unwrap();
+
+        let mut settings = load_settings_from_disk(&dir);
+        settings.hooks_enabled = false;
+        write_settings(&dir, &settings).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["schema_version"], SETTINGS_SCHEMA_VERSION + 1);
+        assert_eq!(saved["future_option"]["enabled"], true);
+        assert_eq!(saved["hooks_enabled"], false);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn configured_locale_reads_the_language_without_opening_the_state() {
+        let dir = std::env::temp_dir().join(format!("settings-locale-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(configured_locale(&dir), Locale::Es);
+
+        std::fs::write(dir.join(SETTINGS_FILE), r#"{"language":"en"}"#).unwrap();
+        assert_eq!(configured_locale(&dir), Locale::En);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn loading_a_v0_file_rewrites_it_at_the_current_version() {
+        let dir = std::env::temp_dir().join(format!("settings-migration-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+        std::fs::write(
+            &path,
+            r#"{"java_runtime":"embedded","selected_java_path":null}"#,
+        )
+        .unwrap();
+
+        let settings = load_settings_from_disk(&dir);
+        assert!(matches!(
+            settings.java_runtime,
+            JavaRuntimePreference::Embedded
+        ));
+
+        let rewritten: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(rewritten["schema_version"], SETTINGS_SCHEMA_VERSION);
+        assert_eq!(rewritten["java_runtime"], "embedded");
+        assert_eq!(rewritten["hooks_enabled"], true);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unreadable_settings_are_set_aside_and_defaulted() {
+        let dir = std::env::temp_dir().join(format!("settings-corrupt-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+        std::fs::write(&path, r#"{"language":"#).unwrap();
+
+        let settings = load_settings_from_disk(&dir);
+        assert_eq!(settings.language, Locale::default());
+        assert!(!path.exists());
+        let backups: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".bak"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(backups[0].path()).unwrap(),
+            r#"{"language":"#
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio_util::sync::CancellationToken;
+
+/// Cancellation tokens for launches that are still preparing, keyed by instance id.
+///
+/// Lives outside the `AppState` mutex (which `launch_instance` holds during
+/// preparation) so `cancel_launch` can reach it while a download is running.
+#[derive(Debug, Default)]
+pub struct LaunchCancellations {
+    tokens: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl LaunchCancellations {
+    /// Register a fresh token for `id`, or `None` if a launch of `id` is
+    /// already preparing. The entry is removed when the guard drops.
+    pub fn try_register(self: &Arc<Self>, id: &str) -> Option<LaunchCancellationGuard> {
+        let token = CancellationToken::new();
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if tokens.contains_key(id) {
+            return None;
+        }
+        tokens.insert(id.to_string(), token.clone());
+
+        Some(LaunchCancellationGuard {
+            registry: Arc::clone(self),
+            id: id.to_string(),
+            token,
+        })
+    }
+
+    /// Signal the pending launch of `id`. Returns `false` if nothing was preparing.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self
+            .tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+        {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[cfg(test)]
+    fn is_pending(&self, id: &str) -> bool {
+        self.tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(id)
+    }
+}
+
+/// Keeps a launch cancellable for as long as it is alive.
+pub struct LaunchCancellationGuard {
+    registry: Arc<LaunchCancellations>,
+    id: String,
+    token: CancellationToken,
+}
+
+impl LaunchCancellationGuard {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for LaunchCancellationGuard {
+    fn drop(&mut self) {
+        self.registry
+            .tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_pending_launch_blocks_a_second_registration() {
+        let registry = Arc::new(LaunchCancellations::default());
+        let first = registry.try_register("a").unwrap();
+        assert!(registry.try_register("a").is_none());
+        assert!(registry.is_pending("a"));
+
+        assert!(registry.cancel("a"));
+        assert!(first.token().is_cancelled());
+
+        drop(first);
+        assert!(!registry.is_pending("a"));
+        assert!(!registry.cancel("a"));
+        assert!(registry.try_register("a").is_some());
+    }
+}
@@ -1,3 +1,12 @@
 mod app_state;
+mod launch_cancellation;
+mod settings;
+mod setup;
 
-pub use app_state::{AppState, JavaRuntimePreference, LauncherSettings};
+pub use app_state::{
+    configured_locale, default_data_dir, install_launcher, run_setup_step, AppState,
+    JavaRuntimePreference, LauncherSettings, RunningInstance, MAX_PREFLIGHT_REPAIR_ATTEMPTS,
+};
+pub use launch_cancellation::{LaunchCancellationGuard, LaunchCancellations};
+pub use settings::{SettingsSnapshot, SettingsUpdate};
+pub use setup::{SetupProgress, SetupRequest, SetupStep};
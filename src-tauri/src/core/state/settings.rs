@@ -0,0 +1,163 @@
+// ─── Settings Updates ───
+// Edits from the settings screen. Every value is clamped to what the
+// launcher supports and the ones the downloader already holds (parallel
+// downloads, bandwidth cap, mirrors) are applied to it right away, before
+// the file is saved.
+
+use std::path::PathBuf;
+
+use super::app_state::{AppState, JavaRuntimePreference, LauncherSettings};
+use super::MAX_PREFLIGHT_REPAIR_ATTEMPTS;
+use crate::core::downloader::MAX_CONCURRENT_DOWNLOADS_CAP;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::{ErrorText, Locale};
+use crate::core::java;
+use crate::core::launch;
+use crate::core::mirrors::DownloadMirrors;
+
+/// The settings with the data dir they are stored in.
+#[derive(Debug, Clone)]
+pub struct SettingsSnapshot {
+    pub settings: LauncherSettings,
+    pub data_dir: PathBuf,
+    /// Whether the Java bundled with the launcher is usable.
+    pub embedded_java_available: bool,
+}
+
+/// Changes to [`LauncherSettings`]. `None` leaves a setting as it is, except
+/// for `selected_java_path`, which is always replaced.
+#[derive(Debug, Clone)]
+pub struct SettingsUpdate {
+    pub java_runtime: JavaRuntimePreference,
+    pub selected_java_path: Option<PathBuf>,
+    pub max_concurrent_downloads: Option<usize>,
+    pub hooks_enabled: Option<bool>,
+    pub allow_x64_java_emulation: Option<bool>,
+    pub allow_newer_java_major: Option<bool>,
+    pub stop_grace_seconds: Option<u64>,
+    pub hook_timeout_seconds: Option<u64>,
+    pub download_mirrors: Option<DownloadMirrors>,
+    pub preflight_repair_attempts: Option<u32>,
+    pub auto_fix_loader_java: Option<bool>,
+    pub language: Option<Locale>,
+    pub shared_assets: Option<bool>,
+    pub runtime_keep_per_major: Option<usize>,
+    pub verify_runtime_integrity: Option<bool>,
+    pub runtime_vendor: Option<java::runtime::RuntimeVendor>,
+    /// `Some(0)` removes the cap.
+    pub max_download_bytes_per_sec: Option<u64>,
+    pub max_log_bytes: Option<u64>,
+    pub log_rotations: Option<usize>,
+}
+
+impl AppState {
+    pub fn settings_snapshot(&self) -> SettingsSnapshot {
+        SettingsSnapshot {
+            settings: self.launcher_settings.clone(),
+            data_dir: self.data_dir.clone(),
+            embedded_java_available: java::runtime::is_usable_java_binary(
+                &self.embedded_java_path(),
+            ),
+        }
+    }
+
+    /// Apply `update` and save the settings. A custom Java path must point
+    /// to a working Java binary.
+    pub fn update_settings(&mut self, update: SettingsUpdate) -> LauncherResult<()> {
+        let settings = &mut self.launcher_settings;
+
+        settings.java_runtime = update.java_runtime;
+        settings.selected_java_path = if let Some(candidate) = update.selected_java_path {
+            let canonical =
+                std::fs::canonicalize(&candidate).map_err(|source| LauncherError::Io {
+                    path: candidate.clone(),
+                    source,
+                })?;
+            if java::runtime::inspect_java_binary(&canonical).is_none() {
+                return Err(LauncherError::message(
+                    ErrorText::InvalidJavaOverride,
+                    &[&canonical.display()],
+                ));
+            }
+            Some(canonical)
+        } else {
+            None
+        };
+
+        if let Some(max_downloads) = update.max_concurrent_downloads {
+            let max_downloads = max_downloads.clamp(1, MAX_CONCURRENT_DOWNLOADS_CAP);
+            settings.max_concurrent_downloads = max_downloads;
+            self.downloader.set_max_concurrency(max_downloads);
+        }
+
+        if let Some(hooks_enabled) = update.hooks_enabled {
+            settings.hooks_enabled = hooks_enabled;
+        }
+
+        if let Some(allowed) = update.allow_x64_java_emulation {
+            settings.allow_x64_java_emulation = allowed;
+        }
+
+        if let Some(attempts) = update.preflight_repair_attempts {
+            settings.preflight_repair_attempts = attempts.min(MAX_PREFLIGHT_REPAIR_ATTEMPTS);
+        }
+
+        if let Some(enabled) = update.auto_fix_loader_java {
+            settings.auto_fix_loader_java = enabled;
+        }
+
+        if let Some(language) = update.language {
+            settings.language = language;
+        }
+
+        if let Some(allowed) = update.allow_newer_java_major {
+            settings.allow_newer_java_major = allowed;
+        }
+
+        if let Some(shared) = update.shared_assets {
+            settings.shared_assets = shared;
+        }
+
+        if let Some(keep) = update.runtime_keep_per_major {
+            settings.runtime_keep_per_major = keep.max(1);
+        }
+
+        if let Some(enabled) = update.verify_runtime_integrity {
+            settings.verify_runtime_integrity = enabled;
+        }
+
+        if let Some(vendor) = update.runtime_vendor {
+            settings.runtime_vendor = vendor;
+        }
+
+        if let Some(limit) = update.max_download_bytes_per_sec {
+            let limit = (limit > 0).then_some(limit);
+            settings.max_download_bytes_per_sec = limit;
+            self.downloader.throttle().set_max_bytes_per_sec(limit);
+        }
+
+        if let Some(max_bytes) = update.max_log_bytes {
+            settings.max_log_bytes = max_bytes.max(launch::MIN_MAX_LOG_BYTES);
+        }
+
+        if let Some(rotations) = update.log_rotations {
+            settings.log_rotations = rotations.min(launch::MAX_LOG_ROTATIONS);
+        }
+
+        if let Some(grace) = update.stop_grace_seconds {
+            settings.stop_grace_seconds = grace.clamp(1, launch::MAX_STOP_GRACE_SECS);
+        }
+
+        if let Some(timeout) = update.hook_timeout_seconds {
+            settings.hook_timeout_seconds = timeout.clamp(1, launch::MAX_HOOK_TIMEOUT_SECS);
+        }
+
+        if let Some(download_mirrors) = update.download_mirrors {
+            self.downloader.set_mirrors(download_mirrors.clone());
+            settings.download_mirrors = download_mirrors;
+        }
+
+        self.save_settings()
+            .map_err(|e| LauncherError::message(ErrorText::SettingsSaveFailed, &[&e]))
+    }
+}
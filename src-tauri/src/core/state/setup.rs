@@ -0,0 +1,210 @@
+// ─── First-Launch Setup ───
+// First run as a sequence of persisted steps, so a step that fails (usually
+// the Java download) can be retried without redoing the ones before it.
+// Progress is kept next to the bootstrap file because the data dir is only
+// known once the first step has run.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::atomic_file;
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::i18n::ErrorText;
+
+/// Event emitted when a step starts, finishes or fails.
+pub const SETUP_PROGRESS_EVENT: &str = "setup-progress";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    ChooseDir,
+    CreateLayout,
+    RegisterRuntime,
+    PredownloadJava,
+    Done,
+}
+
+impl SetupStep {
+    /// Every step, in the order they run.
+    pub const ALL: [SetupStep; 5] = [
+        SetupStep::ChooseDir,
+        SetupStep::CreateLayout,
+        SetupStep::RegisterRuntime,
+        SetupStep::PredownloadJava,
+        SetupStep::Done,
+    ];
+}
+
+/// Options for `run_setup_step`; each step reads the ones it needs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SetupRequest {
+    /// Folder picked in `ChooseDir`; the current data dir when empty.
+    pub target_dir: Option<String>,
+    pub create_desktop_shortcut: bool,
+    /// Complete `PredownloadJava` without downloading anything.
+    pub skip_java: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupFailure {
+    pub step: SetupStep,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupProgress {
+    /// Steps finished so far, in order.
+    #[serde(default)]
+    pub completed: Vec<SetupStep>,
+    /// Data dir chosen in `ChooseDir`.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub create_desktop_shortcut: bool,
+    /// Why the last attempt failed; cleared once a step succeeds.
+    #[serde(default)]
+    pub last_error: Option<SetupFailure>,
+}
+
+/// Payload of [`SETUP_PROGRESS_EVENT`].
+#[derive(Debug, Serialize)]
+pub struct SetupEvent<'a> {
+    pub step: SetupStep,
+    /// `running`, `done` or `error`.
+    pub status: &'static str,
+    pub percent: u8,
+    pub progress: &'a SetupProgress,
+}
+
+impl SetupProgress {
+    /// Saved progress, or a fresh one when there is none or it is unreadable.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> LauncherResult<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        atomic_file::write(path, json).map_err(|source| LauncherError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// First step that has not completed yet.
+    pub fn next_step(&self) -> Option<SetupStep> {
+        SetupStep::ALL
+            .into_iter()
+            .find(|step| !self.completed.contains(step))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_step().is_none()
+    }
+
+    pub fn percent(&self) -> u8 {
+        (self.completed.len() * 100 / SetupStep::ALL.len()) as u8
+    }
+
+    /// Check that `step` may run now: it is the next pending one. A failed
+    /// step stays pending, so retrying it is always allowed.
+    pub fn begin(&self, step: SetupStep) -> LauncherResult<()> {
+        match self.next_step() {
+            Some(next) if next == step => Ok(()),
+            Some(next) if self.completed.contains(&step) => Err(LauncherError::message(
+                ErrorText::SetupStepDone,
+                &[&format!("{step:?}"), &format!("{next:?}")],
+            )),
+            Some(next) => Err(LauncherError::message(
+                ErrorText::SetupStepOutOfOrder,
+                &[&format!("{step:?}"), &format!("{next:?}")],
+            )),
+            None => Err(LauncherError::message(ErrorText::SetupComplete, &[])),
+        }
+    }
+
+    /// Record how `step` went.
+    pub fn finish(&mut self, step: SetupStep, error: Option<&LauncherError>) {
+        match error {
+            None => {
+                self.completed.push(step);
+                self.last_error = None;
+            }
+            Some(err) => {
+                self.last_error = Some(SetupFailure {
+                    step,
+                    error: err.to_string(),
+                });
+            }
+        }
+    }
+
+    pub fn event(&self, step: SetupStep, status: &'static str) -> SetupEvent<'_> {
+        SetupEvent {
+            step,
+            status,
+            percent: self.percent(),
+            progress: self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_failed_step_is_retried_without_redoing_earlier_ones() {
+        let dir = std::env::temp_dir().join(format!("setup-progress-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("launcher_setup.json");
+
+        let mut progress = SetupProgress::load(&path);
+        assert_eq!(progress.next_step(), Some(SetupStep::ChooseDir));
+        // Steps cannot be skipped.
+        assert!(progress.begin(SetupStep::RegisterRuntime).is_err());
+
+        for step in [
+            SetupStep::ChooseDir,
+            SetupStep::CreateLayout,
+            SetupStep::RegisterRuntime,
+        ] {
+            progress.begin(step).unwrap();
+            progress.finish(step, None);
+        }
+        progress.save(&path).unwrap();
+
+        // The Java download fails; the failure survives a restart.
+        progress.begin(SetupStep::PredownloadJava).unwrap();
+        let err = LauncherError::Other("sin conexión".into());
+        progress.finish(SetupStep::PredownloadJava, Some(&err));
+        progress.save(&path).unwrap();
+
+        let mut progress = SetupProgress::load(&path);
+        assert_eq!(progress.next_step(), Some(SetupStep::PredownloadJava));
+        assert_eq!(
+            progress.last_error.as_ref().map(|failure| failure.step),
+            Some(SetupStep::PredownloadJava)
+        );
+        assert_eq!(progress.percent(), 60);
+        // Earlier steps are not run again.
+        assert!(progress.begin(SetupStep::CreateLayout).is_err());
+
+        progress.begin(SetupStep::PredownloadJava).unwrap();
+        progress.finish(SetupStep::PredownloadJava, None);
+        assert_eq!(progress.last_error, None);
+        progress.begin(SetupStep::Done).unwrap();
+        progress.finish(SetupStep::Done, None);
+        assert!(progress.is_complete());
+        assert_eq!(progress.percent(), 100);
+        assert_eq!(progress.completed, SetupStep::ALL);
+        assert!(progress.begin(SetupStep::Done).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,138 @@
+// ─── Version Comparison ───
+// The one place that parses and orders version ids: loader and library
+// versions compare token by token, Minecraft release ids reduce to their
+// numeric parts so callers can gate features on a release.
+
+use std::cmp::Ordering;
+
+/// One segment of a version: `21.1.0-beta` is `[21, 1, 0, beta]`.
+///
+/// Qualifiers sort below numbers so `1.0-rc1` < `1.0.0`, and the variant
+/// order makes the derived `Ord` do exactly that.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum VersionToken {
+    Qualifier(u8, String),
+    Number(u64),
+}
+
+pub(crate) fn version_tokens(version: &str) -> Vec<VersionToken> {
+    let mut tokens = Vec::new();
+    for part in version
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+    {
+        // Split `pre1` / `rc2` into qualifier + number.
+        let mut rest = part;
+        while !rest.is_empty() {
+            let is_digit = rest.starts_with(|c: char| c.is_ascii_digit());
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            let (chunk, tail) = rest.split_at(end);
+            tokens.push(if is_digit {
+                VersionToken::Number(chunk.parse().unwrap_or(u64::MAX))
+            } else {
+                let qualifier = chunk.to_ascii_lowercase();
+                let rank = match qualifier.as_str() {
+                    "snapshot" | "alpha" | "a" => 0,
+                    "beta" | "b" => 1,
+                    "pre" => 2,
+                    "rc" => 3,
+                    _ => 4,
+                };
+                VersionToken::Qualifier(rank, qualifier)
+            });
+            rest = tail;
+        }
+    }
+    tokens
+}
+
+/// Semantic version comparison for loader and library versions.
+///
+/// Numeric segments compare as numbers (`1.20.10` > `1.20.9`) and a trailing
+/// pre-release qualifier ranks below the bare release (`21.1.0-beta` < `21.1.0`).
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_tokens(a), version_tokens(b));
+    for index in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(index), b.get(index)) {
+            (Some(left), Some(right)) => left.cmp(right),
+            (Some(VersionToken::Number(_)), None) => Ordering::Greater,
+            (Some(VersionToken::Qualifier(..)), None) => Ordering::Less,
+            (None, Some(VersionToken::Number(_))) => Ordering::Less,
+            (None, Some(VersionToken::Qualifier(..))) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Numeric components of a release id, ignoring pre-release/build suffixes
+/// (`1.20.1-pre2` → `[1, 20, 1]`). `None` for snapshots and other ids.
+pub fn release_parts(version: &str) -> Option<Vec<u64>> {
+    let core = version.trim().split(['-', '+', ' ', '_']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Order of two [`release_parts`], missing components counting as zero.
+pub fn compare_parts(a: &[u64], b: &[u64]) -> Ordering {
+    for index in 0..a.len().max(b.len()) {
+        let ordering = a.get(index).unwrap_or(&0).cmp(b.get(index).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// `(minor, patch)` of a `1.x[.y]` Minecraft release, ignoring `-pre`/`-rc`
+/// suffixes. `None` for snapshots, alphas and betas.
+pub fn minecraft_release(minecraft_version: &str) -> Option<(u64, u64)> {
+    match release_parts(minecraft_version)?.as_slice() {
+        [1, minor, rest @ ..] => Some((*minor, rest.first().copied().unwrap_or(0))),
+        _ => None,
+    }
+}
+
+/// Whether the release `minecraft_version` predates `release` (e.g.
+/// `[1, 20, 5]`). `None` when the id is not a release.
+pub fn release_before(minecraft_version: &str, release: &[u64]) -> Option<bool> {
+    release_parts(minecraft_version).map(|parts| compare_parts(&parts, release) == Ordering::Less)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_versions_is_semantic() {
+        assert_eq!(compare_versions("1.20.10", "1.20.9"), Ordering::Greater);
+        assert_eq!(compare_versions("21.1.0", "21.1.0-beta"), Ordering::Greater);
+        assert_eq!(
+            compare_versions("21.1.0-beta", "21.1.0-rc1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions("0.26.0-beta.2", "0.26.0-beta.10"),
+            Ordering::Less
+        );
+        assert_eq!(compare_versions("1.20.1", "1.20"), Ordering::Greater);
+        assert_eq!(compare_versions("47.2.0", "47.2.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn release_ids_reduce_to_their_numeric_parts() {
+        assert_eq!(release_parts("1.20.1-pre2"), Some(vec![1, 20, 1]));
+        assert_eq!(release_parts(" 1.8.9 "), Some(vec![1, 8, 9]));
+        assert_eq!(release_parts("23w13a"), None);
+        assert_eq!(minecraft_release("1.19"), Some((19, 0)));
+        assert_eq!(minecraft_release("1.21.4-rc1"), Some((21, 4)));
+        assert_eq!(minecraft_release("b1.7.3"), None);
+        assert_eq!(release_before("1.20.4", &[1, 20, 5]), Some(true));
+        assert_eq!(release_before("1.20.5", &[1, 20, 5]), Some(false));
+        assert_eq!(release_before("24w14a", &[1, 20, 5]), None);
+    }
+}
@@ -0,0 +1,359 @@
+// ─── Library Set ───
+// Canonicalizes an instance's declared libraries before launch: entries the
+// version JSON rules exclude on this OS/arch and natives built for another
+// platform are dropped, and duplicate group:artifact(:classifier) entries
+// collapse to the highest version. Installers also report where each
+// library came from (`ResolvedLibrary`) so it can be verified and fetched
+// again without re-resolving metadata.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::core::downloader::DownloadEntry;
+
+use super::compare::compare_versions;
+use super::version_file::{current_arch_matches, current_os_name, VersionJson};
+
+/// A library as the installer downloaded it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedLibrary {
+    /// Maven coordinate (`group:artifact:version[:classifier]`).
+    pub coord: String,
+    /// Location under the shared `libraries/` directory.
+    pub path: PathBuf,
+    pub url: String,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+impl ResolvedLibrary {
+    /// Where to download the library, or `None` when its path could leave
+    /// `libs_dir` (absolute, `..`, drive prefixes). Paths come from version
+    /// JSONs, loader profiles and `instance.json`, none of which is trusted.
+    pub fn download_entry(&self, libs_dir: &Path) -> Option<DownloadEntry> {
+        let contained = !self.path.as_os_str().is_empty()
+            && self
+                .path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !contained {
+            warn!(
+                "Ignoring library {} with unsafe path {}",
+                self.coord,
+                self.path.display()
+            );
+            return None;
+        }
+        Some(DownloadEntry {
+            url: self.url.clone(),
+            dest: libs_dir.join(&self.path),
+            sha1: self.sha1.clone(),
+            size: self.size,
+        })
+    }
+}
+
+/// Append the entries of `extra` whose path is not in `target` yet.
+pub fn merge_resolved_libraries(target: &mut Vec<ResolvedLibrary>, extra: Vec<ResolvedLibrary>) {
+    let mut seen: HashSet<PathBuf> = target.iter().map(|lib| lib.path.clone()).collect();
+    target.extend(
+        extra
+            .into_iter()
+            .filter(|lib| seen.insert(lib.path.clone())),
+    );
+}
+
+/// Identity of a library entry, parsed from either a Maven coordinate
+/// (`group:artifact:version[:classifier]`) or a repository-relative path
+/// (`group/path/artifact/version/artifact-version[-classifier].jar`).
+#[derive(Debug, PartialEq, Eq)]
+struct LibraryId {
+    group: String,
+    artifact: String,
+    version: String,
+    classifier: Option<String>,
+}
+
+impl LibraryId {
+    fn parse(entry: &str) -> Option<Self> {
+        if entry.contains(':') {
+            let mut parts = entry.split(':');
+            let group = parts.next()?.to_string();
+            let artifact = parts.next()?.to_string();
+            let version = parts.next()?.to_string();
+            let classifier = parts
+                .next()
+                .map(|c| c.split('@').next().unwrap_or(c).to_string());
+            return Some(Self {
+                group,
+                artifact,
+                version: version.split('@').next().unwrap_or(&version).to_string(),
+                classifier,
+            });
+        }
+
+        let parts: Vec<&str> = entry.split(['/', '\\']).collect();
+        if parts.len() < 4 {
+            return None;
+        }
+        let file = parts[parts.len() - 1];
+        let version = parts[parts.len() - 2];
+        let artifact = parts[parts.len() - 3];
+        let stem = file.strip_suffix(".jar")?;
+        let rest = stem.strip_prefix(&format!("{artifact}-{version}"))?;
+        let classifier = match rest.strip_prefix('-') {
+            Some(classifier) => Some(classifier.to_string()),
+            None if rest.is_empty() => None,
+            None => return None,
+        };
+        Some(Self {
+            group: parts[..parts.len() - 3].join("."),
+            artifact: artifact.to_string(),
+            version: version.to_string(),
+            classifier,
+        })
+    }
+
+    fn key(&self) -> (String, String, Option<String>) {
+        (
+            self.group.clone(),
+            self.artifact.clone(),
+            self.classifier.clone(),
+        )
+    }
+}
+
+/// Whether a `natives-<os>[-<arch>]` classifier targets this platform.
+/// Non-native classifiers always match.
+fn native_classifier_matches(classifier: &str) -> bool {
+    let Some(platform) = classifier.strip_prefix("natives-") else {
+        return true;
+    };
+    let (os, arch) = match platform.split_once('-') {
+        Some((os, arch)) => (os, Some(arch)),
+        None => (platform, None),
+    };
+    let os = if os == "macos" { "osx" } else { os };
+    if !matches!(os, "windows" | "linux" | "osx") {
+        return true;
+    }
+    os == current_os_name() && arch.is_none_or(current_arch_matches)
+}
+
+/// Whether `entry` (coordinate or Maven path) is a `natives-*` classifier
+/// built for the current OS/arch.
+pub fn is_native_for_current_platform(entry: &str) -> bool {
+    LibraryId::parse(entry)
+        .and_then(|id| id.classifier)
+        .is_some_and(|classifier| {
+            classifier.starts_with("natives-") && native_classifier_matches(&classifier)
+        })
+}
+
+/// Drop libraries not meant for the current OS/arch and keep only the
+/// highest version of each group:artifact(:classifier). Entries that are
+/// neither coordinates nor Maven paths are kept untouched. The result is
+/// sorted and deduplicated.
+pub fn canonicalize_libraries(
+    libraries: Vec<String>,
+    version_json: Option<&VersionJson>,
+) -> Vec<String> {
+    let excluded: HashSet<&str> = version_json
+        .map(|version| {
+            version
+                .libraries
+                .iter()
+                .filter(|lib| !lib.is_allowed_for_current_os())
+                .flat_map(|lib| {
+                    let path = lib
+                        .downloads
+                        .as_ref()
+                        .and_then(|d| d.artifact.as_ref())
+                        .map(|artifact| artifact.path.as_str());
+                    std::iter::once(lib.name.as_str()).chain(path)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut best: HashMap<(String, String, Option<String>), (String, String)> = HashMap::new();
+    let mut untyped = Vec::new();
+    for entry in libraries {
+        if excluded.contains(entry.as_str()) {
+            debug!("Dropping library excluded by OS rules: {}", entry);
+            continue;
+        }
+        let Some(id) = LibraryId::parse(&entry) else {
+            untyped.push(entry);
+            continue;
+        };
+        if id
+            .classifier
+            .as_deref()
+            .is_some_and(|classifier| !native_classifier_matches(classifier))
+        {
+            debug!("Dropping natives for another platform: {}", entry);
+            continue;
+        }
+
+        match best.get(&id.key()) {
+            // The string tiebreaker keeps the winner independent of input order.
+            Some((_, version))
+                if compare_versions(&id.version, version).then_with(|| id.version.cmp(version))
+                    != Ordering::Greater =>
+            {
+                if id.version != *version {
+                    debug!("Dropping older duplicate library: {}", entry);
+                }
+            }
+            _ => {
+                best.insert(id.key(), (entry, id.version));
+            }
+        }
+    }
+
+    let mut result: Vec<String> = best.into_values().map(|(entry, _)| entry).collect();
+    result.extend(untyped);
+    result.sort();
+    result.dedup();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version_json() -> VersionJson {
+        serde_json::from_value(serde_json::json!({
+            "id": "1.12.2",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [
+                {
+                    "name": "ca.weblite:java-objc-bridge:1.0.0",
+                    "rules": [{ "action": "allow", "os": { "name": "osx" } }]
+                },
+                {
+                    "name": "com.example:windows-only:1.0",
+                    "downloads": { "artifact": {
+                        "path": "com/example/windows-only/1.0/windows-only-1.0.jar",
+                        "sha1": "0", "size": 0, "url": "https://example.invalid"
+                    } },
+                    "rules": [{ "action": "allow", "os": { "name": "windows" } }]
+                },
+                {
+                    "name": "com.example:not-on-osx:1.0",
+                    "rules": [
+                        { "action": "allow" },
+                        { "action": "disallow", "os": { "name": "osx" } }
+                    ]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn keeps_only_libraries_for_current_os() {
+        let libraries = vec![
+            "ca.weblite:java-objc-bridge:1.0.0".to_string(),
+            "com/example/windows-only/1.0/windows-only-1.0.jar".to_string(),
+            "com.example:not-on-osx:1.0".to_string(),
+            "org.lwjgl:lwjgl:3.3.1:natives-windows".to_string(),
+            "org.lwjgl:lwjgl:3.3.1:natives-macos".to_string(),
+            "org.lwjgl:lwjgl:3.3.1:natives-linux".to_string(),
+            "com.google.guava:guava:21.0".to_string(),
+        ];
+
+        let kept = canonicalize_libraries(libraries, Some(&version_json()));
+        let has = |entry: &str| kept.iter().any(|k| k == entry);
+
+        assert!(has("com.google.guava:guava:21.0"));
+        assert_eq!(
+            has("ca.weblite:java-objc-bridge:1.0.0"),
+            cfg!(target_os = "macos")
+        );
+        assert_eq!(
+            has("org.lwjgl:lwjgl:3.3.1:natives-macos"),
+            cfg!(target_os = "macos")
+        );
+        assert_eq!(
+            has("com/example/windows-only/1.0/windows-only-1.0.jar"),
+            cfg!(target_os = "windows")
+        );
+        assert_eq!(
+            has("org.lwjgl:lwjgl:3.3.1:natives-windows"),
+            cfg!(target_os = "windows")
+        );
+        assert_eq!(
+            has("org.lwjgl:lwjgl:3.3.1:natives-linux"),
+            cfg!(target_os = "linux")
+        );
+        assert_eq!(
+            has("com.example:not-on-osx:1.0"),
+            !cfg!(target_os = "macos")
+        );
+    }
+
+    #[test]
+    fn library_paths_must_stay_inside_the_libraries_dir() {
+        let library = |path: &str| ResolvedLibrary {
+            coord: "com.example:lib:1.0".into(),
+            path: PathBuf::from(path),
+            url: "https://example.invalid/lib.jar".into(),
+            sha1: None,
+            size: None,
+        };
+        let libs_dir = Path::new("/data/libraries");
+
+        assert_eq!(
+            library("com/example/lib/1.0/lib-1.0.jar")
+                .download_entry(libs_dir)
+                .map(|entry| entry.dest),
+            Some(libs_dir.join("com/example/lib/1.0/lib-1.0.jar"))
+        );
+        for unsafe_path in [
+            "",
+            "../../.bashrc",
+            "com/../../x.jar",
+            "/etc/passwd",
+            "./x.jar",
+        ] {
+            assert!(
+                library(unsafe_path).download_entry(libs_dir).is_none(),
+                "{unsafe_path} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn conflicting_versions_collapse_to_highest() {
+        let kept = canonicalize_libraries(
+            vec![
+                "com.google.guava:guava:21.0".into(),
+                "com/google/guava/guava/31.1-jre/guava-31.1-jre.jar".into(),
+                "com.google.guava:guava:25.1-jre".into(),
+                "org.ow2.asm:asm:9.7".into(),
+                "org.ow2.asm:asm-tree:9.5".into(),
+                "org.ow2.asm:asm:9.7".into(),
+                "not-a-library.jar".into(),
+            ],
+            None,
+        );
+
+        assert_eq!(
+            kept,
+            [
+                "com/google/guava/guava/31.1-jre/guava-31.1-jre.jar",
+                "not-a-library.jar",
+                "org.ow2.asm:asm-tree:9.5",
+                "org.ow2.asm:asm:9.7",
+            ]
+        );
+    }
+}
@@ -1,13 +1,18 @@
 // ─── Version Manifest ───
 // Handles fetching and parsing the Mojang version manifest v2.
 
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tracing::info;
 
 use crate::core::error::LauncherResult;
+use crate::core::metadata_cache::{self, MetadataCache};
+use crate::core::mirrors::{self, DownloadMirrors};
 
 const VERSION_MANIFEST_URL: &str =
     "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+/// Metadata cache key of the manifest.
+pub const VERSION_MANIFEST_CACHE_KEY: &str = "version_manifest_v2";
 
 /// Top-level Mojang version manifest.
 #[derive(Debug, Deserialize)]
@@ -15,6 +20,31 @@ pub struct VersionManifest {
     pub versions: Vec<VersionEntry>,
 }
 
+/// Release channel of a manifest entry, used to filter version listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionChannel {
+    #[default]
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+    All,
+}
+
+impl VersionChannel {
+    /// Whether a manifest `type` value belongs to this channel.
+    pub fn matches(self, version_type: &str) -> bool {
+        match self {
+            VersionChannel::Release => version_type == "release",
+            VersionChannel::Snapshot => version_type == "snapshot",
+            VersionChannel::OldBeta => version_type == "old_beta",
+            VersionChannel::OldAlpha => version_type == "old_alpha",
+            VersionChannel::All => true,
+        }
+    }
+}
+
 /// A single entry in the manifest.
 #[derive(Debug, Clone, Deserialize)]
 pub struct VersionEntry {
@@ -29,19 +59,30 @@ pub struct VersionEntry {
 }
 
 impl VersionManifest {
-    /// Fetch the version manifest from Mojang using a shared HTTP client.
-    pub async fn fetch(client: &reqwest::Client) -> LauncherResult<Self> {
+    /// Fetch the manifest, falling back to the last cached copy when offline.
+    /// The timestamp is set when the cached copy was used.
+    pub async fn fetch_cached(
+        client: &reqwest::Client,
+        cache: &MetadataCache,
+        mirrors: &DownloadMirrors,
+    ) -> LauncherResult<(Self, Option<DateTime<Utc>>)> {
         info!("Fetching Minecraft version manifest...");
 
-        let manifest: VersionManifest = client
-            .get(VERSION_MANIFEST_URL)
-            .send()
-            .await?
-            .json()
+        let response = cache
+            .fetch_with_fallback(
+                VERSION_MANIFEST_CACHE_KEY,
+                || {
+                    mirrors::with_mirror_fallback(mirrors, VERSION_MANIFEST_URL, |url| async move {
+                        metadata_cache::fetch_text(client, &url).await
+                    })
+                },
+                metadata_cache::parse_json::<VersionManifest>,
+            )
             .await?;
+        let manifest = response.value;
 
         info!("Loaded {} versions from manifest", manifest.versions.len());
-        Ok(manifest)
+        Ok((manifest, response.cached_at))
     }
 
     /// Find a specific version entry by ID (e.g. "1.20.4").
@@ -51,9 +92,14 @@ impl VersionManifest {
 
     /// List all official stable versions (release only).
     pub fn releases(&self) -> Vec<&VersionEntry> {
+        self.by_channel(VersionChannel::Release)
+    }
+
+    /// List the versions published on the given channel.
+    pub fn by_channel(&self, channel: VersionChannel) -> Vec<&VersionEntry> {
         self.versions
             .iter()
-            .filter(|v| v.version_type == "release")
+            .filter(|v| channel.matches(&v.version_type))
             .collect()
     }
 }
@@ -76,4 +122,17 @@ mod tests {
         assert_eq!(entry.version_type, "release");
         assert_eq!(entry.release_time, "2023-12-07T08:00:00+00:00");
     }
+
+    #[test]
+    fn version_channel_filters_manifest_types() {
+        let channel: VersionChannel = serde_json::from_str("\"old_beta\"").unwrap();
+        assert_eq!(channel, VersionChannel::OldBeta);
+        assert!(channel.matches("old_beta"));
+        assert!(!channel.matches("release"));
+
+        assert!(VersionChannel::Snapshot.matches("snapshot"));
+        assert!(!VersionChannel::Snapshot.matches("release"));
+        assert!(VersionChannel::default().matches("release"));
+        assert!(VersionChannel::All.matches("old_alpha"));
+    }
 }
@@ -1,10 +1,16 @@
+pub mod compare;
+pub mod libraries;
 pub mod manifest;
 pub mod version_file;
 
+pub use libraries::{
+    canonicalize_libraries, is_native_for_current_platform, merge_resolved_libraries,
+    ResolvedLibrary,
+};
 #[allow(unused_imports)]
-pub use manifest::{VersionEntry, VersionManifest};
+pub use manifest::{VersionChannel, VersionEntry, VersionManifest};
 #[allow(unused_imports)]
 pub use version_file::{
     Arguments, AssetIndexInfo, DownloadArtifact, LibDownloadArtifact, LibraryDownloads,
-    LibraryEntry, LibraryRule, OsRule, RuleAction, VersionDownloads, VersionJson,
+    LibraryEntry, LibraryRule, NativeJar, OsRule, RuleAction, VersionDownloads, VersionJson,
 };
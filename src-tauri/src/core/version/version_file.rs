@@ -1,13 +1,16 @@
 // ─── Version File ───
 // Parses a Mojang version JSON and evaluates OS rules for libraries.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::core::downloader::Downloader;
+use crate::core::downloader::{DownloadEntry, Downloader, ExpectedHash};
 use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::metadata_cache::{self, MetadataCache};
+
+use super::libraries::{is_native_for_current_platform, ResolvedLibrary};
 
 /// A fully parsed Mojang version JSON.
 #[derive(Debug, Deserialize)]
@@ -54,11 +57,9 @@ pub struct DownloadArtifact {
 pub struct AssetIndexInfo {
     pub id: String,
     pub url: String,
-    #[allow(dead_code)]
     #[serde(default)]
     pub sha1: Option<String>,
-    #[allow(dead_code)]
-    #[serde(default)]
+    #[serde(default, rename = "totalSize")]
     pub total_size: Option<u64>,
 }
 
@@ -81,6 +82,24 @@ pub struct LibraryEntry {
     pub rules: Option<Vec<LibraryRule>>,
     #[serde(default)]
     pub natives: Option<serde_json::Value>,
+    #[serde(default)]
+    pub extract: Option<ExtractRules>,
+}
+
+/// `extract` block of a natives library: archive paths that must not be
+/// unpacked into the natives directory.
+#[derive(Debug, Default, Deserialize)]
+pub struct ExtractRules {
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// A natives jar (relative to the libraries dir) to unpack before launch,
+/// with the archive paths to skip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeJar {
+    pub path: String,
+    pub exclude: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,7 +113,6 @@ pub struct LibraryDownloads {
 pub struct LibDownloadArtifact {
     pub path: String,
     pub sha1: String,
-    #[allow(dead_code)]
     pub size: u64,
     pub url: String,
 }
@@ -119,7 +137,6 @@ pub enum RuleAction {
 pub struct OsRule {
     #[serde(default)]
     pub name: Option<String>,
-    #[allow(dead_code)]
     #[serde(default)]
     pub arch: Option<String>,
     #[allow(dead_code)]
@@ -147,10 +164,10 @@ impl LibraryEntry {
         for rule in rules {
             let os_matches = match &rule.os {
                 None => true, // No OS constraint → rule applies universally
-                Some(os) => match &os.name {
-                    None => true,
-                    Some(name) => name == current_os,
-                },
+                Some(os) => {
+                    os.name.as_deref().is_none_or(|name| name == current_os)
+                        && os.arch.as_deref().is_none_or(current_arch_matches)
+                }
             };
 
             if os_matches {
@@ -178,7 +195,7 @@ impl LibraryEntry {
 }
 
 /// Get the Mojang OS name for the current platform.
-fn current_os_name() -> &'static str {
+pub(crate) fn current_os_name() -> &'static str {
     if cfg!(target_os = "windows") {
         "windows"
     } else if cfg!(target_os = "macos") {
@@ -188,12 +205,41 @@ fn current_os_name() -> &'static str {
     }
 }
 
+/// Whether an architecture from a rule or natives classifier (`x86`,
+/// `x86_64`, `arm64`, `arm32`, or the legacy `64`/`32` word sizes) matches
+/// the current platform.
+pub(crate) fn current_arch_matches(arch: &str) -> bool {
+    match arch {
+        "64" => cfg!(target_pointer_width = "64"),
+        "32" => cfg!(target_pointer_width = "32"),
+        "x86" => cfg!(target_arch = "x86"),
+        "x86_64" | "x64" | "amd64" => cfg!(target_arch = "x86_64"),
+        "arm64" | "aarch64" => cfg!(target_arch = "aarch64"),
+        "arm32" | "arm" => cfg!(target_arch = "arm"),
+        _ => false,
+    }
+}
+
 impl VersionJson {
-    /// Fetch and parse a version JSON from the given URL using a shared client.
-    pub async fn fetch(client: &reqwest::Client, url: &str) -> LauncherResult<(Self, String)> {
-        let raw = client.get(url).send().await?.text().await?;
-        let version_json: VersionJson = serde_json::from_str(&raw)?;
-        Ok((version_json, raw))
+    /// Fetch the JSON of `version_id` through the metadata cache. Piston-meta
+    /// URLs embed the file's SHA-1, so a copy cached from the same URL is
+    /// reused without a request.
+    pub async fn fetch_cached(
+        client: &reqwest::Client,
+        cache: &MetadataCache,
+        version_id: &str,
+        url: &str,
+    ) -> LauncherResult<(Self, String)> {
+        let response = cache
+            .fetch_revalidated(
+                &format!("version-json/{version_id}"),
+                url,
+                true,
+                |validators| metadata_cache::fetch_conditional(client, url, validators),
+                metadata_cache::parse_json,
+            )
+            .await?;
+        Ok((response.value, response.body))
     }
 
     /// Save the raw version JSON to the instance directory.
@@ -209,31 +255,51 @@ impl VersionJson {
         Ok(())
     }
 
-    /// Download client.jar to the instance directory.
+    /// Make sure `<instance_dir>/client.jar` matches `downloads.client.sha1`.
+    /// A missing or corrupt jar is downloaded again (with mirror fallback)
+    /// and checked once more. Returns the verified size, or `None` when the
+    /// version lists no client download.
     pub async fn download_client(
         &self,
         instance_dir: &Path,
         downloader: &Downloader,
-    ) -> LauncherResult<()> {
-        if let Some(ref downloads) = self.downloads {
-            if let Some(ref client_dl) = downloads.client {
-                let client_jar_path = instance_dir.join("client.jar");
-                downloader
-                    .download_file(&client_dl.url, &client_jar_path, Some(&client_dl.sha1))
-                    .await?;
-                info!("Downloaded client.jar");
+    ) -> LauncherResult<Option<u64>> {
+        let Some(client_dl) = self.downloads.as_ref().and_then(|d| d.client.as_ref()) else {
+            return Ok(None);
+        };
+        let client_jar_path = instance_dir.join("client.jar");
+        if client_jar_path.is_file() {
+            if Downloader::validate_sha1(&client_jar_path, &client_dl.sha1).await? {
+                debug!("client.jar already matches its SHA-1");
+                return Ok(Some(client_dl.size));
             }
+            warn!("client.jar does not match its SHA-1; downloading it again");
         }
-        Ok(())
+
+        downloader
+            .download_file(&client_dl.url, &client_jar_path, Some(&client_dl.sha1))
+            .await?;
+        ExpectedHash::Sha1(client_dl.sha1.clone())
+            .verify(&client_jar_path)
+            .await?;
+        info!(
+            "Downloaded client.jar ({} bytes, SHA-1 verified)",
+            client_dl.size
+        );
+        Ok(Some(client_dl.size))
     }
 
     /// Download all allowed libraries (respecting OS rules).
+    ///
+    /// Missing artifacts are fetched as one concurrent batch so the frontend
+    /// receives aggregate `download-progress` events.
     pub async fn download_libraries(
         &self,
         libs_dir: &Path,
         downloader: &Downloader,
     ) -> LauncherResult<Vec<String>> {
         let mut lib_coords = Vec::new();
+        let mut pending = Vec::new();
 
         for lib in &self.libraries {
             // ── Evaluate OS rules ──
@@ -242,23 +308,26 @@ impl VersionJson {
                 continue;
             }
 
-            // ── Download main artifact ──
+            // ── Main artifact ──
             let mut classpath_entry = lib.name.clone();
 
             if let Some(ref downloads) = lib.downloads {
                 if let Some(ref artifact) = downloads.artifact {
                     let dest = libs_dir.join(&artifact.path);
                     if !dest.exists() {
-                        downloader
-                            .download_file(&artifact.url, &dest, Some(&artifact.sha1))
-                            .await?;
+                        pending.push(DownloadEntry {
+                            url: artifact.url.clone(),
+                            dest,
+                            sha1: Some(artifact.sha1.clone()),
+                            size: Some(artifact.size),
+                        });
                     }
 
                     // Prefer concrete artifact path for classpath resolution.
                     classpath_entry = artifact.path.clone();
                 }
 
-                // ── Download native classifiers ──
+                // ── Native classifiers ──
                 if let Some(classifier) = lib.native_classifier_for_current_os() {
                     if let Some(ref classifiers) = downloads.classifiers {
                         if let Some(native_info) = classifiers.get(&classifier) {
@@ -269,7 +338,12 @@ impl VersionJson {
                             ) {
                                 let dest = libs_dir.join(path);
                                 if !dest.exists() {
-                                    downloader.download_file(url, &dest, Some(sha1)).await?;
+                                    pending.push(DownloadEntry {
+                                        url: url.to_string(),
+                                        dest,
+                                        sha1: Some(sha1.to_string()),
+                                        size: native_info.get("size").and_then(|v| v.as_u64()),
+                                    });
                                 }
                             }
                         }
@@ -280,6 +354,12 @@ impl VersionJson {
             lib_coords.push(classpath_entry);
         }
 
+        let failures = downloader.download_batch(pending).await;
+        if let Some((entry, err)) = failures.into_iter().next() {
+            warn!("Library download failed: {}", entry.url);
+            return Err(err);
+        }
+
         info!(
             "Processed {} libraries ({} allowed)",
             self.libraries.len(),
@@ -288,6 +368,166 @@ impl VersionJson {
         Ok(lib_coords)
     }
 
+    /// Re-download allowed library artifacts that are missing or whose SHA-1
+    /// no longer matches the version JSON (e.g. truncated by an interrupted
+    /// copy). Returns how many artifacts were repaired.
+    pub async fn repair_libraries(
+        &self,
+        libs_dir: &Path,
+        downloader: &Downloader,
+    ) -> LauncherResult<usize> {
+        let mut pending = Vec::new();
+
+        for lib in &self.libraries {
+            if !lib.is_allowed_for_current_os() {
+                continue;
+            }
+            let Some(artifact) = lib.downloads.as_ref().and_then(|d| d.artifact.as_ref()) else {
+                continue;
+            };
+
+            let dest = libs_dir.join(&artifact.path);
+            if dest.is_file() {
+                if Downloader::validate_sha1(&dest, &artifact.sha1).await? {
+                    continue;
+                }
+                warn!("SHA-1 mismatch for library {}; re-downloading", lib.name);
+                tokio::fs::remove_file(&dest)
+                    .await
+                    .map_err(|e| LauncherError::Io {
+                        path: dest.clone(),
+                        source: e,
+                    })?;
+            }
+
+            pending.push(DownloadEntry {
+                url: artifact.url.clone(),
+                dest,
+                sha1: Some(artifact.sha1.clone()),
+                size: Some(artifact.size),
+            });
+        }
+
+        let repaired = pending.len();
+        let failures = downloader.download_batch(pending).await;
+        if let Some((entry, err)) = failures.into_iter().next() {
+            warn!("Library repair failed: {}", entry.url);
+            return Err(err);
+        }
+        Ok(repaired)
+    }
+
+    /// Every allowed library artifact and current-OS natives jar, whether or
+    /// not it is already under `libs_dir`.
+    pub fn library_downloads(&self, libs_dir: &Path) -> Vec<DownloadEntry> {
+        self.resolved_libraries()
+            .iter()
+            .filter_map(|lib| lib.download_entry(libs_dir))
+            .collect()
+    }
+
+    /// Source URL, path and hash of every allowed library artifact and
+    /// current-OS natives jar.
+    pub fn resolved_libraries(&self) -> Vec<ResolvedLibrary> {
+        let mut resolved = Vec::new();
+        for lib in &self.libraries {
+            if !lib.is_allowed_for_current_os() {
+                continue;
+            }
+            let Some(downloads) = lib.downloads.as_ref() else {
+                continue;
+            };
+            if let Some(artifact) = &downloads.artifact {
+                resolved.push(ResolvedLibrary {
+                    coord: lib.name.clone(),
+                    path: PathBuf::from(&artifact.path),
+                    url: artifact.url.clone(),
+                    sha1: Some(artifact.sha1.clone()),
+                    size: Some(artifact.size),
+                });
+            }
+            let classifier = lib.native_classifier_for_current_os();
+            let native = classifier
+                .as_ref()
+                .and_then(|classifier| downloads.classifiers.as_ref()?.get(classifier));
+            if let (Some(classifier), Some(native)) = (&classifier, native) {
+                let field = |key: &str| native.get(key).and_then(|v| v.as_str());
+                if let (Some(url), Some(path)) = (field("url"), field("path")) {
+                    resolved.push(ResolvedLibrary {
+                        coord: format!("{}:{}", lib.name, classifier),
+                        path: PathBuf::from(path),
+                        url: url.to_string(),
+                        sha1: field("sha1").map(str::to_string),
+                        size: native.get("size").and_then(|v| v.as_u64()),
+                    });
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Rough number of bytes an install of this version still has to
+    /// download: allowed libraries and natives missing from `libs_dir`, the
+    /// client jar, and the asset total unless its index is already in
+    /// `assets_dir`.
+    pub fn estimated_download_bytes(&self, libs_dir: &Path, assets_dir: &Path) -> u64 {
+        let mut total = self
+            .downloads
+            .as_ref()
+            .and_then(|downloads| downloads.client.as_ref())
+            .map_or(0, |client| client.size);
+        total += self
+            .library_downloads(libs_dir)
+            .iter()
+            .filter(|entry| !entry.dest.exists())
+            .filter_map(|entry| entry.size)
+            .sum::<u64>();
+
+        if let Some(index) = &self.asset_index {
+            let index_path = assets_dir
+                .join("indexes")
+                .join(format!("{}.json", index.id));
+            if !index_path.exists() {
+                total += index.total_size.unwrap_or(0);
+            }
+        }
+        total
+    }
+
+    /// Natives jars to unpack on this OS/arch: the legacy `natives` classifier
+    /// of each allowed library, or the artifact itself for libraries that are
+    /// published as `natives-<os>[-<arch>]` classifiers.
+    pub fn native_jars(&self) -> Vec<NativeJar> {
+        self.libraries
+            .iter()
+            .filter(|lib| lib.is_allowed_for_current_os())
+            .filter_map(|lib| {
+                let downloads = lib.downloads.as_ref()?;
+                let path = match lib.native_classifier_for_current_os() {
+                    Some(classifier) => downloads
+                        .classifiers
+                        .as_ref()?
+                        .get(&classifier)?
+                        .get("path")?
+                        .as_str()?
+                        .to_string(),
+                    None if is_native_for_current_platform(&lib.name) => {
+                        downloads.artifact.as_ref()?.path.clone()
+                    }
+                    None => return None,
+                };
+                Some(NativeJar {
+                    path,
+                    exclude: lib
+                        .extract
+                        .as_ref()
+                        .map(|extract| extract.exclude.clone())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
     /// Get the required Java major version from the version JSON.
     pub fn required_java_major(&self) -> u32 {
         self.java_version
@@ -458,6 +698,7 @@ mod tests {
             downloads: None,
             rules: None,
             natives: None,
+            extract: None,
         };
         assert!(lib.is_allowed_for_current_os());
     }
@@ -476,6 +717,7 @@ mod tests {
                 }),
             }]),
             natives: None,
+            extract: None,
         };
         assert!(lib.is_allowed_for_current_os());
     }
@@ -500,6 +742,7 @@ mod tests {
                 },
             ]),
             natives: None,
+            extract: None,
         };
         assert!(!lib.is_allowed_for_current_os());
     }
@@ -574,4 +817,165 @@ mod tests {
 
         assert!(parsed.is_err());
     }
+
+    /// Serve `body` to every request on a local port; returns the base URL
+    /// and a counter of requests.
+    async fn serve_body(
+        body: &'static [u8],
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use crate::core::test_support::{response, serve};
+
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let served = requests.clone();
+        let base = serve(move |_| {
+            served.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move { response("200 OK", &[], body) }
+        })
+        .await;
+        (base, requests)
+    }
+
+    #[tokio::test]
+    async fn corrupted_library_is_downloaded_again() {
+        use sha1::{Digest, Sha1};
+
+        const BODY: &[u8] = b"PK intact library bytes";
+        let (base, requests) = serve_body(BODY).await;
+
+        let version_json: VersionJson = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [{
+                "name": "com.example:lib:1.0",
+                "downloads": { "artifact": {
+                    "path": "com/example/lib/1.0/lib-1.0.jar",
+                    "sha1": hex::encode(Sha1::digest(BODY)),
+                    "size": BODY.len(),
+                    "url": format!("{base}/lib-1.0.jar")
+                }}
+            }]
+        }))
+        .unwrap();
+
+        let libs_dir = std::env::temp_dir().join(format!("library-repair-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&libs_dir);
+        let jar = libs_dir.join("com/example/lib/1.0/lib-1.0.jar");
+        std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+        std::fs::write(&jar, b"PK trunc").unwrap();
+
+        let downloader = Downloader::new(None);
+        let repaired = version_json
+            .repair_libraries(&libs_dir, &downloader)
+            .await
+            .unwrap();
+        assert_eq!(repaired, 1);
+        assert_eq!(std::fs::read(&jar).unwrap(), BODY);
+
+        // An intact library is left alone.
+        let repaired = version_json
+            .repair_libraries(&libs_dir, &downloader)
+            .await
+            .unwrap();
+        assert_eq!(repaired, 0);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let _ = std::fs::remove_dir_all(&libs_dir);
+    }
+
+    #[tokio::test]
+    async fn corrupted_client_jar_is_detected_and_fetched_again() {
+        use sha1::{Digest, Sha1};
+
+        const BODY: &[u8] = b"PK intact client bytes";
+        let (base, requests) = serve_body(BODY).await;
+        let version_json: VersionJson = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "downloads": { "client": {
+                "sha1": hex::encode(Sha1::digest(BODY)),
+                "size": BODY.len(),
+                "url": format!("{base}/client.jar")
+            }}
+        }))
+        .unwrap();
+
+        let instance_dir =
+            std::env::temp_dir().join(format!("client-jar-verify-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&instance_dir);
+        std::fs::create_dir_all(&instance_dir).unwrap();
+        let jar = instance_dir.join("client.jar");
+        let mut corrupted = BODY.to_vec();
+        corrupted[3] ^= 0xff;
+        std::fs::write(&jar, &corrupted).unwrap();
+
+        let downloader = Downloader::new(None);
+        let size = version_json
+            .download_client(&instance_dir, &downloader)
+            .await
+            .unwrap();
+        assert_eq!(size, Some(BODY.len() as u64));
+        assert_eq!(std::fs::read(&jar).unwrap(), BODY);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A jar that already matches is not downloaded again.
+        version_json
+            .download_client(&instance_dir, &downloader)
+            .await
+            .unwrap();
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let _ = std::fs::remove_dir_all(&instance_dir);
+    }
+
+    #[test]
+    fn estimated_download_bytes_counts_only_missing_files() {
+        let version_json: VersionJson = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "downloads": {
+                "client": { "sha1": "c", "size": 1000, "url": "https://example.com/client.jar" }
+            },
+            "assetIndex": {
+                "id": "5",
+                "url": "https://example.com/5.json",
+                "totalSize": 50000
+            },
+            "libraries": [
+                { "name": "com.example:present:1.0", "downloads": { "artifact": {
+                    "path": "com/example/present-1.0.jar", "sha1": "a", "size": 300,
+                    "url": "https://example.com/present.jar" } } },
+                { "name": "com.example:missing:1.0", "downloads": { "artifact": {
+                    "path": "com/example/missing-1.0.jar", "sha1": "b", "size": 200,
+                    "url": "https://example.com/missing.jar" } } },
+                { "name": "com.example:elsewhere:1.0", "rules": [
+                    { "action": "disallow", "os": { "name": current_os_name() } }
+                  ], "downloads": { "artifact": {
+                    "path": "com/example/elsewhere-1.0.jar", "sha1": "d", "size": 7000,
+                    "url": "https://example.com/elsewhere.jar" } } }
+            ]
+        }))
+        .unwrap();
+
+        let root = std::env::temp_dir().join(format!("download-estimate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (libs_dir, assets_dir) = (root.join("libraries"), root.join("assets"));
+        let present = libs_dir.join("com/example/present-1.0.jar");
+        std::fs::create_dir_all(present.parent().unwrap()).unwrap();
+        std::fs::write(&present, b"jar").unwrap();
+
+        assert_eq!(
+            version_json.estimated_download_bytes(&libs_dir, &assets_dir),
+            1000 + 200 + 50000
+        );
+
+        // A cached asset index means the objects are already there.
+        std::fs::create_dir_all(assets_dir.join("indexes")).unwrap();
+        std::fs::write(assets_dir.join("indexes/5.json"), b"{}").unwrap();
+        assert_eq!(
+            version_json.estimated_download_bytes(&libs_dir, &assets_dir),
+            1000 + 200
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,447 @@
+// ─── World Backups ───
+// Zips `saves/<world>` into `backups/<world>-<timestamp>.zip` and restores
+// those archives. Entries are stored under `<world>/…` so a backup extracts
+// straight back into `saves/`.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::Serialize;
+
+use crate::core::error::{LauncherError, LauncherResult};
+use crate::core::events::EventEmitter;
+use crate::core::i18n::ErrorText;
+
+/// Lock file Minecraft holds while a world is open; never archived.
+const SESSION_LOCK: &str = "session.lock";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldBackup {
+    pub file_name: String,
+    pub path: String,
+    pub world: String,
+    pub size_bytes: u64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WorldBackupProgressEvent {
+    id: String,
+    world: String,
+    value: u8,
+    state: String,
+}
+
+/// Progress callback for world backup/restore, reported as
+/// `world-backup-progress`. It only emits when the percentage changes, so
+/// multi-GB worlds do not flood the listeners.
+pub fn progress_emitter(
+    events: Arc<dyn EventEmitter>,
+    id: String,
+    world: String,
+) -> impl FnMut(u64, u64) + Send + 'static {
+    let mut last_value = None;
+    move |done, total| {
+        let value = done
+            .saturating_mul(100)
+            .checked_div(total)
+            .map_or(100, |percent| percent.min(100) as u8);
+        if last_value == Some(value) {
+            return;
+        }
+        last_value = Some(value);
+        events.emit(
+            "world-backup-progress",
+            WorldBackupProgressEvent {
+                id: id.clone(),
+                world: world.clone(),
+                value,
+                state: if value == 100 { "done" } else { "running" }.to_string(),
+            },
+        );
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> LauncherError {
+    LauncherError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Reject world names that would escape `saves/` (separators, `..`).
+fn validate_world_name(world: &str) -> LauncherResult<()> {
+    let mut components = Path::new(world).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(()),
+        _ => Err(LauncherError::message(
+            ErrorText::InvalidWorldName,
+            &[&world],
+        )),
+    }
+}
+
+/// Files of `dir` (recursively) with their sizes, skipping `session.lock`.
+fn collect_world_files(dir: &Path) -> LauncherResult<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let entries = fs::read_dir(&current).map_err(|source| io_error(&current, source))?;
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file() && entry.file_name() != SESSION_LOCK {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                files.push((path, size));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn copy_with_progress(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    path: &Path,
+    done: &mut u64,
+    total: u64,
+    on_progress: &mut impl FnMut(u64, u64),
+) -> LauncherResult<()> {
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|source| io_error(path, source))?;
+        if read == 0 {
+            return Ok(());
+        }
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|source| io_error(path, source))?;
+        *done += read as u64;
+        on_progress(*done, total);
+    }
+}
+
+/// Archive `saves_dir/<world>` into `backups_dir`, reporting `(bytes_done, bytes_total)`.
+pub fn backup_world(
+    saves_dir: &Path,
+    backups_dir: &Path,
+    world: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> LauncherResult<PathBuf> {
+    validate_world_name(world)?;
+    let world_dir = saves_dir.join(world);
+    if !world_dir.is_dir() {
+        return Err(LauncherError::message(ErrorText::WorldNotFound, &[&world]));
+    }
+
+    fs::create_dir_all(backups_dir).map_err(|source| io_error(backups_dir, source))?;
+    let target = backups_dir.join(format!(
+        "{world}-{}.zip",
+        Local::now().format(TIMESTAMP_FORMAT)
+    ));
+    if target.exists() {
+        return Err(LauncherError::message(
+            ErrorText::BackupAlreadyExists,
+            &[&world],
+        ));
+    }
+
+    let files = collect_world_files(&world_dir)?;
+    let total: u64 = files.iter().map(|(_, size)| size).sum();
+    let mut done = 0;
+
+    // Build under a temporary name so a failed backup never looks complete.
+    let partial = target.with_extension("zip.part");
+    let result = (|| {
+        let file = File::create(&partial).map_err(|source| io_error(&partial, source))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+
+        writer.add_directory(format!("{world}/"), options)?;
+        for (path, _) in &files {
+            let Ok(relative) = path.strip_prefix(saves_dir) else {
+                continue;
+            };
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            writer.start_file(name, options)?;
+            let mut source = File::open(path).map_err(|source| io_error(path, source))?;
+            copy_with_progress(
+                &mut source,
+                &mut writer,
+                path,
+                &mut done,
+                total,
+                &mut on_progress,
+            )?;
+        }
+        writer.finish()?;
+        fs::rename(&partial, &target).map_err(|source| io_error(&target, source))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result?;
+    on_progress(total, total);
+    Ok(target)
+}
+
+/// World folder stored in a backup archive (the first path component, which
+/// every entry must share).
+fn archive_world_name<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> LauncherResult<String> {
+    let mut world: Option<String> = None;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        let Some(first) = entry
+            .enclosed_name()
+            .and_then(|name| name.components().next().map(|c| c.as_os_str().to_owned()))
+        else {
+            continue;
+        };
+        let first = first.to_string_lossy().to_string();
+        match &world {
+            None => world = Some(first),
+            Some(existing) if *existing == first => {}
+            Some(_) => {
+                return Err(LauncherError::message(
+                    ErrorText::BackupHasSeveralWorlds,
+                    &[],
+                ))
+            }
+        }
+    }
+    world.ok_or_else(|| LauncherError::message(ErrorText::BackupEmpty, &[]))
+}
+
+/// `backup_path` resolved through symlinks and `..`, if it is a file inside
+/// `backups_dir`. Restores only read archives the launcher made.
+fn resolve_backup_path(backups_dir: &Path, backup_path: &Path) -> LauncherResult<PathBuf> {
+    let outside =
+        || LauncherError::message(ErrorText::BackupOutsideFolder, &[&backup_path.display()]);
+    let backups_dir = fs::canonicalize(backups_dir).map_err(|_| outside())?;
+    let resolved = fs::canonicalize(backup_path).map_err(|source| io_error(backup_path, source))?;
+    if resolved.parent() != Some(backups_dir.as_path()) || !resolved.is_file() {
+        return Err(outside());
+    }
+    Ok(resolved)
+}
+
+/// Extract a backup from `backups_dir` into `saves_dir`, reporting
+/// `(bytes_done, bytes_total)`.
+///
+/// Refuses to replace an existing world unless `overwrite` is set; when it
+/// is, the archive is extracted aside first and only then swapped in.
+pub fn restore_world(
+    saves_dir: &Path,
+    backups_dir: &Path,
+    backup_path: &Path,
+    overwrite: bool,
+    mut on_progress: impl FnMut(u64, u64),
+) -> LauncherResult<String> {
+    let backup_path = resolve_backup_path(backups_dir, backup_path)?;
+    let file = File::open(&backup_path).map_err(|source| io_error(&backup_path, source))?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let world = archive_world_name(&mut archive)?;
+    validate_world_name(&world)?;
+
+    let world_dir = saves_dir.join(&world);
+    if world_dir.exists() && !overwrite {
+        return Err(LauncherError::message(
+            ErrorText::WorldAlreadyExists,
+            &[&world],
+        ));
+    }
+
+    let staging = saves_dir.join(format!(".{world}.restoring"));
+    let _ = fs::remove_dir_all(&staging);
+    let total: u64 = (0..archive.len())
+        .filter_map(|index| archive.by_index(index).ok().map(|entry| entry.size()))
+        .sum();
+    let mut done = 0;
+
+    let result = (|| {
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            let Some(relative) = entry.enclosed_name() else {
+                continue;
+            };
+            let Ok(inner) = relative.strip_prefix(&world) else {
+                continue;
+            };
+            let out_path = staging.join(inner);
+
+            if entry.is_dir() {
+                fs::create_dir_all(&out_path).map_err(|source| io_error(&out_path, source))?;
+                continue;
+            }
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent).map_err(|source| io_error(parent, source))?;
+            }
+            let mut out = File::create(&out_path).map_err(|source| io_error(&out_path, source))?;
+            copy_with_progress(
+                &mut entry,
+                &mut out,
+                &out_path,
+                &mut done,
+                total,
+                &mut on_progress,
+            )?;
+        }
+
+        if world_dir.exists() {
+            fs::remove_dir_all(&world_dir).map_err(|source| io_error(&world_dir, source))?;
+        }
+        fs::rename(&staging, &world_dir).map_err(|source| io_error(&world_dir, source))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result?;
+    on_progress(total, total);
+    Ok(world)
+}
+
+/// Split `<world>-<timestamp>` into the world name and its creation date.
+fn parse_backup_stem(stem: &str) -> Option<(&str, DateTime<Local>)> {
+    let (world, timestamp) = stem.rsplit_once('-')?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    let created_at = Local.from_local_datetime(&naive).earliest()?;
+    (!world.is_empty()).then_some((world, created_at))
+}
+
+/// Backups in `backups_dir`, newest first.
+pub fn list_world_backups(backups_dir: &Path) -> LauncherResult<Vec<WorldBackup>> {
+    let entries = match fs::read_dir(backups_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(io_error(backups_dir, source)),
+    };
+
+    let mut backups: Vec<(DateTime<Local>, WorldBackup)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("zip") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            let (world, created_at) = match parse_backup_stem(&stem) {
+                Some((world, created_at)) => (world.to_string(), created_at),
+                None => (
+                    stem.clone(),
+                    DateTime::<Local>::from(metadata.modified().ok()?),
+                ),
+            };
+            Some((
+                created_at,
+                WorldBackup {
+                    file_name: entry.file_name().to_string_lossy().to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    world,
+                    size_bytes: metadata.len(),
+                    created_at: created_at.to_rfc3339(),
+                },
+            ))
+        })
+        .collect();
+
+    backups.sort_by_key(|(created_at, _)| std::cmp::Reverse(*created_at));
+    Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn parses_backup_file_names() {
+        let (world, created_at) = parse_backup_stem("My-World-20240302_184107").unwrap();
+        assert_eq!(world, "My-World");
+        assert_eq!(
+            created_at.format(TIMESTAMP_FORMAT).to_string(),
+            "20240302_184107"
+        );
+        assert!(parse_backup_stem("world").is_none());
+        assert!(validate_world_name("../escape").is_err());
+        assert!(validate_world_name("a/b").is_err());
+        assert!(validate_world_name("New World").is_ok());
+    }
+
+    #[test]
+    fn backup_delete_restore_round_trip() {
+        let temp = std::env::temp_dir().join(format!("world-backup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&temp);
+        let saves = temp.join("minecraft").join("saves");
+        let backups = temp.join("backups");
+        let world = saves.join("New World");
+
+        write(&world.join("level.dat"), b"level data");
+        write(&world.join("region").join("r.0.0.mca"), &[7u8; 200_000]);
+        write(&world.join(SESSION_LOCK), b"lock");
+
+        let mut last = (0, 0);
+        let archive = backup_world(&saves, &backups, "New World", |done, total| {
+            last = (done, total)
+        })
+        .unwrap();
+        assert!(archive.exists());
+        assert_eq!(last.0, last.1);
+        assert_eq!(last.1, 200_000 + 10);
+
+        let listed = list_world_backups(&backups).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].world, "New World");
+        assert!(listed[0].size_bytes > 0);
+
+        fs::remove_dir_all(&world).unwrap();
+        let restored = restore_world(&saves, &backups, &archive, false, |_, _| {}).unwrap();
+        assert_eq!(restored, "New World");
+        assert_eq!(fs::read(world.join("level.dat")).unwrap(), b"level data");
+        assert_eq!(
+            fs::read(world.join("region").join("r.0.0.mca")).unwrap(),
+            vec![7u8; 200_000]
+        );
+        assert!(!world.join(SESSION_LOCK).exists());
+
+        assert!(restore_world(&saves, &backups, &archive, false, |_, _| {}).is_err());
+        fs::write(world.join("level.dat"), b"changed").unwrap();
+        restore_world(&saves, &backups, &archive, true, |_, _| {}).unwrap();
+        assert_eq!(fs::read(world.join("level.dat")).unwrap(), b"level data");
+
+        // Archives outside the backups folder are refused, however they are named.
+        let elsewhere = temp.join("elsewhere.zip");
+        fs::copy(&archive, &elsewhere).unwrap();
+        let sneaky = backups.join("..").join("elsewhere.zip");
+        for path in [&elsewhere, &sneaky] {
+            assert!(restore_world(&saves, &backups, path, true, |_, _| {}).is_err());
+        }
+
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
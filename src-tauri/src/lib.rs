@@ -3,11 +3,20 @@ mod core;
 
 use std::sync::Arc;
 use tauri::Manager;
-use tokio::sync::Mutex;
+use tauri_plugin_deep_link::DeepLinkExt;
 use tracing_subscriber::EnvFilter;
 
+use crate::core::deep_link::forwarded_deep_link;
 use crate::core::state::AppState;
 
+pub use crate::core::error::LauncherError;
+pub use crate::core::events::{EventEmitter, NoopEmitter};
+pub use crate::core::i18n::{CliText, Locale};
+pub use crate::core::instance::{Instance, LoaderType};
+pub use crate::core::java::paths::ensure_writable_dir;
+pub use crate::core::launcher::{Launcher, NewInstance};
+pub use crate::core::state::{configured_locale, default_data_dir};
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize structured logging
@@ -20,25 +29,70 @@ pub fn run() {
     tracing::info!("InterfaceOficial launcher starting...");
 
     tauri::Builder::default()
+        // Must come first: a second copy exits here, before touching the data dir.
+        .plugin(tauri_plugin_single_instance::init(
+            |app, argv, _cwd| match forwarded_deep_link(&argv) {
+                Some(uri) => handle_deep_link(app, uri),
+                None => focus_main_window(app),
+            },
+        ))
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_deep_link::init())
         .setup(|app| {
             let handle = app.handle().clone();
-            let state = AppState::new(handle);
-            app.manage(Arc::new(Mutex::new(state)));
+            crate::core::java::paths::ensure_writable_dir(&default_data_dir())?;
+            let launcher = Launcher::new(AppState::new(handle.clone()), Arc::new(handle.clone()));
+            app.manage(launcher);
+
+            // Linux has no installer-time registration for AppImages and dev builds.
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            if let Err(err) = app.deep_link().register_all() {
+                tracing::warn!("Cannot register the interface:// scheme: {}", err);
+            }
+            let link_handle = handle.clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&link_handle, url.to_string());
+                }
+            });
+            let argv: Vec<String> = std::env::args().collect();
+            if let Some(uri) = forwarded_deep_link(&argv) {
+                handle_deep_link(&handle, uri);
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_minecraft_versions,
             commands::get_minecraft_versions_detailed,
             commands::get_loader_versions,
+            commands::get_loader_companion,
             commands::create_instance,
+            commands::import_curseforge_modpack,
+            commands::import_modrinth_modpack,
             commands::list_instances,
+            commands::set_instance_group,
+            commands::rename_instance_group,
+            commands::reorder_instances,
+            commands::verify_instance,
+            commands::recover_instance,
             commands::delete_instance,
             commands::delete_instance_with_elevation,
+            commands::delete_instances,
             commands::clone_instance,
+            commands::export_instance,
+            commands::import_instance,
             commands::launch_instance,
+            commands::preview_launch_command,
+            commands::launch_group,
+            commands::cancel_launch,
             commands::force_close_instance,
+            commands::stop_instance_graceful,
+            commands::stop_instances,
+            commands::get_play_stats,
+            commands::take_pending_deep_link,
             commands::open_instance_folder,
+            commands::open_instance_log,
+            commands::get_latest_crash_report,
             commands::get_java_installations,
             commands::get_java_metadata,
             commands::get_required_java_version,
@@ -49,18 +103,91 @@ pub fn run() {
             commands::resolve_java,
             commands::validate_java,
             commands::clear_runtimes,
+            commands::remove_runtime,
+            commands::prune_runtimes,
+            commands::import_runtime,
+            commands::clear_runtime_caches,
             commands::runtime_diagnostic,
             commands::get_first_launch_status,
             commands::initialize_launcher_installation,
+            commands::get_setup_progress,
+            commands::run_setup_step,
             commands::reinstall_launcher_completely,
             commands::get_launcher_settings,
             commands::update_launcher_settings,
             commands::migrate_launcher_data_dir,
+            commands::dedupe_assets,
             commands::update_instance_launch_config,
+            commands::list_jvm_profiles,
+            commands::save_jvm_profile,
+            commands::apply_jvm_profile,
+            commands::update_instance_window,
+            commands::update_instance_demo_mode,
+            commands::update_instance_wrapper,
+            commands::update_instance_hooks,
+            commands::pin_instance_runtime,
+            commands::install_loader_from_local_jar,
+            commands::set_instance_icon,
+            commands::get_instance_icon,
+            commands::list_builtin_icons,
+            commands::create_instance_shortcut,
+            commands::read_game_options,
+            commands::write_game_options,
+            commands::read_text_config,
+            commands::write_text_config,
+            commands::list_screenshots,
+            commands::delete_screenshot,
+            commands::get_screenshot_thumbnail,
+            commands::list_servers,
+            commands::add_server,
+            commands::remove_server,
+            commands::list_mods,
+            commands::disable_mod,
+            commands::enable_mod,
+            commands::check_loader_companion,
+            commands::ensure_loader_companion,
+            commands::check_mod_updates,
+            commands::update_mod,
+            commands::backup_world,
+            commands::restore_world,
+            commands::list_world_backups,
+            commands::preview_instance_optimization,
             commands::optimize_instance_with_real_process,
+            commands::suggest_instance_memory,
             commands::update_instance_account,
+            commands::list_accounts,
+            commands::add_account,
+            commands::remove_account,
+            commands::get_account_profile,
+            commands::set_default_account,
             commands::get_auth_research_info,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// Bring the window forward and ask to start the instance named by `uri`. Failures
+/// (unknown id, bad URI) are reported to the frontend as `deep-link-error`.
+fn handle_deep_link(app: &tauri::AppHandle, uri: String) {
+    focus_main_window(app);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let launcher = app.state::<Launcher>();
+        if let Err(err) = launcher.open_deep_link(&uri).await {
+            tracing::warn!("Deep link {} failed: {}", uri, err);
+            let events: &dyn EventEmitter = &app;
+            events.emit(
+                "deep-link-error",
+                serde_json::json!({ "uri": uri, "error": err }),
+            );
+        }
+    });
+}
+
+fn focus_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
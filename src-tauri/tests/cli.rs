@@ -0,0 +1,64 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+use std::sync::Arc;
+
+use interface_lib::{Launcher, LoaderType, NewInstance, NoopEmitter};
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_interface-cli"))
+}
+
+#[tokio::test]
+async fn list_prints_one_tab_separated_line_per_instance() {
+    let data_dir = std::env::temp_dir().join(format!("interface-cli-list-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let launcher = Launcher::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+    let instance = launcher
+        .create_instance(NewInstance {
+            name: "Servidor".into(),
+            minecraft_version: "1.20.1".into(),
+            loader: LoaderType::Vanilla,
+            loader_version: None,
+            memory_max_mb: Some(2048),
+        })
+        .await
+        .unwrap();
+
+    let output = cli()
+        .arg("--data-dir")
+        .arg(&data_dir)
+        .arg("list")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        [format!(
+            "{}\tServidor\t1.20.1\tvanilla\tcreated",
+            instance.id
+        )]
+    );
+
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[test]
+fn unknown_commands_exit_with_usage_error() {
+    let data_dir = std::env::temp_dir().join(format!("interface-cli-usage-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let output = cli()
+        .arg("--data-dir")
+        .arg(&data_dir)
+        .arg("frobnicate")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Uso: interface-cli"));
+
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use interface_lib::{Launcher, LoaderType, NewInstance, NoopEmitter};
+
+#[tokio::test]
+async fn create_list_and_delete_without_tauri() {
+    let data_dir = std::env::temp_dir().join(format!("launcher-facade-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let launcher = Launcher::open(data_dir.clone(), Arc::new(NoopEmitter)).await;
+    assert!(launcher.list_instances().await.unwrap().is_empty());
+
+    let created = launcher
+        .create_instance(NewInstance {
+            name: "Headless".into(),
+            minecraft_version: "1.20.1".into(),
+            loader: LoaderType::Vanilla,
+            loader_version: None,
+            memory_max_mb: Some(2048),
+        })
+        .await
+        .unwrap();
+    assert!(created.path.starts_with(data_dir.join("instances")));
+    assert!(created.mods_dir().exists());
+
+    let listed = launcher.list_instances().await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, created.id);
+    assert_eq!(listed[0].name, "Headless");
+    assert_eq!(listed[0].max_memory_mb, 2048);
+
+    launcher.delete_instance(&created.id).await.unwrap();
+    assert!(launcher.list_instances().await.unwrap().is_empty());
+    assert!(!created.path.exists());
+
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
@@ -27,12 +27,14 @@ interface InstanceInfo {
   account?: {
     username?: string;
   };
+  account_id?: string | null;
   max_memory_mb?: number;
   jvm_args?: string[];
   game_args?: string[];
   java_path?: string | null;
   icon_path?: string | null;
   total_size_bytes: number;
+  needs_recovery?: boolean;
 }
 
 interface DeleteInstanceResponse {
@@ -59,6 +61,7 @@ interface LaunchProgressEvent {
   id: string;
   value: number;
   stage: string;
+  stage_key: string | null;
   state: "idle" | "running" | "done" | "error";
 }
 
@@ -66,12 +69,14 @@ interface LaunchLogEvent {
   id: string;
   level: "info" | "warn" | "error";
   message: string;
+  key: string | null;
 }
 
 interface CreateProgressEvent {
   id: string;
   value: number;
   stage: string;
+  stage_key: string | null;
   state: "idle" | "running" | "done" | "error";
 }
 
@@ -79,6 +84,13 @@ interface CreateLogEvent {
   id: string;
   level: "info" | "warn" | "error";
   message: string;
+  key: string | null;
+}
+
+interface DeepLinkLaunchRequest {
+  uri: string;
+  id: string;
+  name: string;
 }
 
 interface MinecraftVersionEntry {
@@ -305,13 +317,13 @@ function App() {
       setLoaderVersionsLoading(true);
       setLoaderVersionsError(null);
       try {
-        const versions = await invoke<string[]>("get_loader_versions", {
+        const versions = await invoke<{ version: string; stable: boolean }[]>("get_loader_versions", {
           loaderType: selectedLoaderType,
           minecraftVersion: selectedMinecraftVersion,
         });
-        const normalized = versions.map((version, index) => ({
-          version,
-          stable: index === 0,
+        const normalized = versions.map((entry, index) => ({
+          version: entry.version,
+          stable: index === 0 && entry.stable,
           source: "official" as const,
         }));
         setLoaderVersions(normalized);
@@ -355,7 +367,25 @@ function App() {
         setCreateLogs((prev) => [...prev.slice(-100), event.payload]);
       });
 
-      listeners.push(unlistenProgress, unlistenLog, unlistenCreateProgress, unlistenCreateLog);
+      // The request is taken from the backend rather than the event so a link
+      // that arrived before this listener (e.g. at cold start) is not lost and
+      // is confirmed only once.
+      const confirmPendingDeepLink = async () => {
+        const request = await invoke<DeepLinkLaunchRequest | null>("take_pending_deep_link");
+        if (!mounted || !request) return;
+        if (!window.confirm(`Un enlace quiere iniciar la instancia "${request.name}". ¿Iniciarla?`)) return;
+        void invoke("launch_instance", { id: request.id }).catch((error) => {
+          setLaunchError(typeof error === "string" ? error : "No se pudo iniciar la instancia.");
+        });
+      };
+
+      const unlistenDeepLink = await listen<DeepLinkLaunchRequest>("deep-link-launch-request", () => {
+        if (!mounted) return;
+        void confirmPendingDeepLink();
+      });
+
+      listeners.push(unlistenProgress, unlistenLog, unlistenCreateProgress, unlistenCreateLog, unlistenDeepLink);
+      void confirmPendingDeepLink();
     };
 
     void setupListeners();
@@ -576,6 +606,7 @@ function App() {
       id: selectedInstance.id,
       value: 0,
       stage: "Solicitando lanzamiento",
+      stage_key: null,
       state: "running",
     });
 
@@ -588,6 +619,7 @@ function App() {
         id: selectedInstance.id,
         value: 100,
         stage: "Error al iniciar",
+        stage_key: null,
         state: "error",
       });
     }